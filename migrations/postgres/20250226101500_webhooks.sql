@@ -0,0 +1,39 @@
+/* ------------------------------ */
+
+CREATE TABLE webhook_subscriptions(
+    id UUID PRIMARY KEY,
+    owner_account_id VARCHAR(100) NOT NULL,
+    dataset_id VARCHAR(100),
+    target_url VARCHAR(2048) NOT NULL,
+    label VARCHAR(100) NOT NULL,
+    event_types JSONB NOT NULL,
+    secret VARCHAR(100) NOT NULL,
+    status VARCHAR(20) NOT NULL,
+    created_at timestamptz NOT NULL
+);
+
+CREATE INDEX idx_webhook_subscriptions_owner_account_id ON webhook_subscriptions(owner_account_id);
+
+CREATE INDEX idx_webhook_subscriptions_dataset_id ON webhook_subscriptions(dataset_id);
+
+/* ------------------------------ */
+
+CREATE TABLE webhook_deliveries(
+    id UUID PRIMARY KEY,
+    subscription_id UUID NOT NULL,
+    event_type VARCHAR(50) NOT NULL,
+    event JSONB NOT NULL,
+    status VARCHAR(20) NOT NULL,
+    attempts INTEGER NOT NULL,
+    next_attempt_at timestamptz,
+    response_status_code INTEGER,
+    last_error TEXT,
+    created_at timestamptz NOT NULL,
+    updated_at timestamptz NOT NULL
+);
+
+CREATE INDEX idx_webhook_deliveries_subscription_id ON webhook_deliveries(subscription_id);
+
+CREATE INDEX idx_webhook_deliveries_status_next_attempt_at ON webhook_deliveries(status, next_attempt_at);
+
+/* ------------------------------ */
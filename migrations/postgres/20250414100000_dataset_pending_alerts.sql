@@ -0,0 +1,14 @@
+/* ------------------------------ */
+
+CREATE TABLE dataset_pending_alerts(
+    alert_key VARCHAR(200) NOT NULL PRIMARY KEY,
+    dataset_id VARCHAR(100) NOT NULL,
+    recipients JSONB NOT NULL,
+    subject VARCHAR NOT NULL,
+    body VARCHAR NOT NULL,
+    created_at timestamptz NOT NULL
+);
+
+CREATE INDEX idx_dataset_pending_alerts_created_at ON dataset_pending_alerts(created_at);
+
+/* ------------------------------ */
@@ -0,0 +1,14 @@
+/* ------------------------------ */
+
+CREATE TABLE dataset_alert_settings(
+    dataset_id VARCHAR(100) NOT NULL PRIMARY KEY,
+    notify_owner BOOLEAN NOT NULL,
+    recipients JSONB NOT NULL,
+    notify_on_flow_failure BOOLEAN NOT NULL,
+    stale_after_secs BIGINT,
+    created_at timestamptz NOT NULL,
+    last_updated_at timestamptz NOT NULL,
+    stale_notified_at timestamptz
+);
+
+/* ------------------------------ */
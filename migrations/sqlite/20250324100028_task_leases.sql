@@ -0,0 +1,9 @@
+/* ------------------------------ */
+
+ALTER TABLE tasks ADD COLUMN logical_plan_kind VARCHAR(50);
+ALTER TABLE tasks ADD COLUMN lease_worker_id VARCHAR(100);
+ALTER TABLE tasks ADD COLUMN lease_expires_at TIMESTAMPTZ;
+
+CREATE INDEX idx_tasks_lease_expires_at ON tasks(lease_expires_at) WHERE task_status = 'running';
+
+/* ------------------------------ */
@@ -0,0 +1,9 @@
+/* ------------------------------ */
+
+ALTER TABLE tasks ADD COLUMN priority SMALLINT NOT NULL DEFAULT 1;
+ALTER TABLE tasks ADD COLUMN account_id VARCHAR(100);
+
+CREATE INDEX idx_tasks_account_id_status ON tasks(account_id, task_status);
+CREATE INDEX idx_tasks_queued_priority ON tasks(priority DESC, task_id) WHERE task_status = 'queued';
+
+/* ------------------------------ */
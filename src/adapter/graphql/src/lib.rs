@@ -17,6 +17,7 @@ pub(crate) mod prelude;
 pub(crate) mod queries;
 mod root;
 pub mod scalars;
+pub mod subscriptions;
 pub(crate) mod utils;
 
 pub use root::*;
@@ -8,9 +8,17 @@
 // by the Apache License, Version 2.0.
 
 use email_utils::Email;
-use kamu_accounts::{Account, AccountRepository, UpdateAccountError};
+use kamu_accounts::{
+    Account,
+    AccountDeletionBlockedReason,
+    AccountDeletionError,
+    AccountManagementService,
+    AuthenticationService,
+    ChangePasswordError,
+    UpdateAccountEmailError,
+};
 
-use super::AccountFlowsMut;
+use super::{AccountFlowsMut, AccountWebhooksMut, DeleteAccountSuccess};
 use crate::prelude::*;
 
 #[derive(Debug)]
@@ -26,7 +34,8 @@ impl AccountMut {
         Self { account }
     }
 
-    /// Update account email
+    /// Update account email. The new email has to be confirmed via the sent
+    /// verification link before the next login
     #[tracing::instrument(level = "info", name = AccountMut_update_email, skip_all)]
     pub async fn update_email(
         &self,
@@ -39,23 +48,80 @@ impl AccountMut {
             }));
         };
 
-        let account_repo = from_catalog_n!(ctx, dyn AccountRepository);
-        match account_repo
-            .update_account_email(&self.account.id, new_email.clone())
+        let account_management_service = from_catalog_n!(ctx, dyn AccountManagementService);
+        match account_management_service
+            .update_account_email(&self.account, new_email.clone())
             .await
         {
-            Ok(_) => Ok(UpdateEmailResult::Success(UpdateEmailSuccess {
+            Ok(()) => Ok(UpdateEmailResult::Success(UpdateEmailSuccess {
                 new_email: new_email.as_ref().to_string(),
             })),
 
-            Err(UpdateAccountError::Duplicate(_)) => {
+            Err(UpdateAccountEmailError::Duplicate(_)) => {
                 Ok(UpdateEmailResult::NonUniqueEmail(UpdateEmailNonUnique {
                     dummy: false,
                 }))
             }
 
-            Err(UpdateAccountError::NotFound(e)) => Err(e.int_err().into()),
-            Err(UpdateAccountError::Internal(e)) => Err(e.into()),
+            Err(UpdateAccountEmailError::Internal(e)) => Err(e.into()),
+        }
+    }
+
+    /// Change the password of an account authenticated by password
+    #[tracing::instrument(level = "info", name = AccountMut_change_password, skip_all)]
+    pub async fn change_password(
+        &self,
+        ctx: &Context<'_>,
+        old_password: String,
+        new_password: String,
+    ) -> Result<ChangePasswordResult> {
+        let authentication_service = from_catalog_n!(ctx, dyn AuthenticationService);
+
+        match authentication_service
+            .change_password(&self.account, old_password, new_password)
+            .await
+        {
+            Ok(()) => Ok(ChangePasswordResult::Success(ChangePasswordSuccess {
+                dummy: false,
+            })),
+            Err(ChangePasswordError::WrongPassword(_)) => Ok(ChangePasswordResult::WrongPassword(
+                ChangePasswordWrongPassword { dummy: false },
+            )),
+            Err(ChangePasswordError::NotPasswordAccount(_)) => {
+                Ok(ChangePasswordResult::NotPasswordAccount(
+                    ChangePasswordNotPasswordAccount { dummy: false },
+                ))
+            }
+            Err(ChangePasswordError::Internal(e)) => Err(e.into()),
+        }
+    }
+
+    /// Deletes the account along with all datasets it owns. Refused when
+    /// datasets of other accounts depend on the owned datasets
+    #[tracing::instrument(level = "info", name = AccountMut_delete, skip_all)]
+    pub async fn delete(&self, ctx: &Context<'_>) -> Result<DeleteOwnAccountResult> {
+        let account_management_service = from_catalog_n!(ctx, dyn AccountManagementService);
+
+        match account_management_service
+            .delete_account(&self.account)
+            .await
+        {
+            Ok(()) => Ok(DeleteOwnAccountResult::Success(DeleteAccountSuccess {
+                deleted_account: self.account.account_name.clone().into(),
+            })),
+            Err(AccountDeletionError::Blocked(e)) => match e.reason {
+                AccountDeletionBlockedReason::ForeignDownstreamDatasets {
+                    downstream_datasets_count,
+                } => Ok(DeleteOwnAccountResult::HasForeignDownstreamDatasets(
+                    DeleteAccountHasForeignDownstreamDatasets {
+                        downstream_datasets_count,
+                    },
+                )),
+                AccountDeletionBlockedReason::Other(_) => {
+                    Err(GqlError::Gql(async_graphql::Error::new(e.to_string())))
+                }
+            },
+            Err(AccountDeletionError::Internal(e)) => Err(e.into()),
         }
     }
 
@@ -63,6 +129,11 @@ impl AccountMut {
     async fn flows(&self) -> AccountFlowsMut {
         AccountFlowsMut::new(self.account.clone())
     }
+
+    /// Access to the mutable webhook subscriptions of this account
+    async fn webhooks(&self) -> AccountWebhooksMut {
+        AccountWebhooksMut::new(self.account.clone())
+    }
 }
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
@@ -121,3 +192,77 @@ impl UpdateEmailNonUnique {
 }
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[derive(Interface, Debug)]
+#[graphql(field(name = "message", ty = "String"))]
+pub enum ChangePasswordResult {
+    Success(ChangePasswordSuccess),
+    WrongPassword(ChangePasswordWrongPassword),
+    NotPasswordAccount(ChangePasswordNotPasswordAccount),
+}
+
+#[derive(SimpleObject, Debug)]
+#[graphql(complex)]
+pub struct ChangePasswordSuccess {
+    pub dummy: bool,
+}
+
+#[ComplexObject]
+impl ChangePasswordSuccess {
+    pub async fn message(&self) -> String {
+        "Success".to_string()
+    }
+}
+
+#[derive(SimpleObject, Debug)]
+#[graphql(complex)]
+pub struct ChangePasswordWrongPassword {
+    pub dummy: bool,
+}
+
+#[ComplexObject]
+impl ChangePasswordWrongPassword {
+    pub async fn message(&self) -> String {
+        "Wrong password".to_string()
+    }
+}
+
+#[derive(SimpleObject, Debug)]
+#[graphql(complex)]
+pub struct ChangePasswordNotPasswordAccount {
+    pub dummy: bool,
+}
+
+#[ComplexObject]
+impl ChangePasswordNotPasswordAccount {
+    pub async fn message(&self) -> String {
+        "Account is not authenticated by password".to_string()
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[derive(Interface, Debug)]
+#[graphql(field(name = "message", ty = "String"))]
+pub enum DeleteOwnAccountResult {
+    Success(DeleteAccountSuccess),
+    HasForeignDownstreamDatasets(DeleteAccountHasForeignDownstreamDatasets),
+}
+
+#[derive(SimpleObject, Debug)]
+#[graphql(complex)]
+pub struct DeleteAccountHasForeignDownstreamDatasets {
+    pub downstream_datasets_count: usize,
+}
+
+#[ComplexObject]
+impl DeleteAccountHasForeignDownstreamDatasets {
+    pub async fn message(&self) -> String {
+        format!(
+            "{} dataset(s) of other accounts depend on datasets of this account",
+            self.downstream_datasets_count
+        )
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
@@ -0,0 +1,266 @@
+// Copyright Kamu Data, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use kamu_accounts::{
+    Account,
+    AccountDeletionError,
+    AccountManagementService,
+    RenameAccountError,
+    ResetPasswordError,
+};
+use kamu_datasets::{DatasetEntryService, DatasetEntryServiceExt};
+
+use crate::prelude::*;
+use crate::utils::get_logged_account;
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[derive(Debug)]
+pub struct AdminAccountMut {
+    account: Account,
+}
+
+#[common_macros::method_names_consts(const_value_prefix = "GQL: ")]
+#[Object]
+impl AdminAccountMut {
+    #[graphql(skip)]
+    pub fn new(account: Account) -> Self {
+        Self { account }
+    }
+
+    #[graphql(skip)]
+    fn ensure_not_self(&self, ctx: &Context<'_>) -> Result<()> {
+        if get_logged_account(ctx).account_id == self.account.id {
+            return Err(GqlError::Gql(Error::new(
+                "Administrators cannot perform this operation on their own account",
+            )));
+        }
+        Ok(())
+    }
+
+    /// Changes the account name
+    #[tracing::instrument(level = "info", name = AdminAccountMut_rename, skip_all, fields(%new_name))]
+    async fn rename(
+        &self,
+        ctx: &Context<'_>,
+        new_name: AccountName<'_>,
+    ) -> Result<RenameAccountResult<'static>> {
+        let account_management_service = from_catalog_n!(ctx, dyn AccountManagementService);
+
+        match account_management_service
+            .rename_account(&self.account, &new_name)
+            .await
+        {
+            Ok(account) => Ok(RenameAccountResult::Success(RenameAccountSuccess {
+                new_name: account.account_name.into(),
+            })),
+            Err(RenameAccountError::Duplicate(_)) => Ok(RenameAccountResult::NameCollision(
+                RenameAccountNameCollision {
+                    colliding_name: new_name.as_ref().clone().into(),
+                },
+            )),
+            Err(RenameAccountError::Internal(e)) => Err(e.into()),
+        }
+    }
+
+    /// Disables or re-enables the account. Disabled accounts can neither log
+    /// in nor use previously issued access tokens
+    #[tracing::instrument(level = "info", name = AdminAccountMut_set_disabled, skip_all, fields(%disabled))]
+    async fn set_disabled(&self, ctx: &Context<'_>, disabled: bool) -> Result<bool> {
+        self.ensure_not_self(ctx)?;
+
+        let account_management_service = from_catalog_n!(ctx, dyn AccountManagementService);
+        account_management_service
+            .set_account_disabled(&self.account, disabled)
+            .await?;
+
+        Ok(disabled)
+    }
+
+    /// Sets a new password for an account that logs in with a password
+    #[tracing::instrument(level = "info", name = AdminAccountMut_reset_password, skip_all)]
+    async fn reset_password(
+        &self,
+        ctx: &Context<'_>,
+        new_password: String,
+    ) -> Result<ResetPasswordResult> {
+        let account_management_service = from_catalog_n!(ctx, dyn AccountManagementService);
+
+        match account_management_service
+            .reset_password(&self.account, new_password)
+            .await
+        {
+            Ok(()) => Ok(ResetPasswordResult::Success(ResetPasswordSuccess {
+                dummy: false,
+            })),
+            Err(ResetPasswordError::NotPasswordAccount(_)) => {
+                Ok(ResetPasswordResult::NotPasswordAccount(
+                    ResetPasswordNotPasswordAccount { dummy: false },
+                ))
+            }
+            Err(ResetPasswordError::Internal(e)) => Err(e.into()),
+        }
+    }
+
+    /// Revokes all active access tokens of the account, returns their number
+    #[tracing::instrument(level = "info", name = AdminAccountMut_revoke_access_tokens, skip_all)]
+    async fn revoke_access_tokens(&self, ctx: &Context<'_>) -> Result<usize> {
+        let account_management_service = from_catalog_n!(ctx, dyn AccountManagementService);
+
+        let revoked_count = account_management_service
+            .revoke_access_tokens(&self.account)
+            .await?;
+
+        Ok(revoked_count)
+    }
+
+    /// Deletes the account. Datasets owned by the account have to be deleted
+    /// or transferred to another account first. Permissions and access tokens
+    /// of the account are removed along with it
+    #[tracing::instrument(level = "info", name = AdminAccountMut_delete, skip_all)]
+    async fn delete(&self, ctx: &Context<'_>) -> Result<DeleteAccountResult> {
+        self.ensure_not_self(ctx)?;
+
+        let (account_management_service, dataset_entry_service) =
+            from_catalog_n!(ctx, dyn AccountManagementService, dyn DatasetEntryService);
+
+        let owned_dataset_ids = dataset_entry_service
+            .get_owned_dataset_ids(&self.account.id)
+            .await
+            .int_err()?;
+        if !owned_dataset_ids.is_empty() {
+            return Ok(DeleteAccountResult::HasDatasets(DeleteAccountHasDatasets {
+                owned_datasets_count: owned_dataset_ids.len(),
+            }));
+        }
+
+        match account_management_service
+            .delete_account(&self.account)
+            .await
+        {
+            Ok(()) => Ok(DeleteAccountResult::Success(DeleteAccountSuccess {
+                deleted_account: self.account.account_name.clone().into(),
+            })),
+            Err(AccountDeletionError::Blocked(e)) => {
+                Err(GqlError::Gql(async_graphql::Error::new(e.to_string())))
+            }
+            Err(AccountDeletionError::Internal(e)) => Err(e.into()),
+        }
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[derive(Interface, Debug)]
+#[graphql(field(name = "message", ty = "String"))]
+pub enum RenameAccountResult<'a> {
+    Success(RenameAccountSuccess<'a>),
+    NameCollision(RenameAccountNameCollision<'a>),
+}
+
+#[derive(SimpleObject, Debug)]
+#[graphql(complex)]
+pub struct RenameAccountSuccess<'a> {
+    pub new_name: AccountName<'a>,
+}
+
+#[ComplexObject]
+impl RenameAccountSuccess<'_> {
+    async fn message(&self) -> String {
+        "Success".to_string()
+    }
+}
+
+#[derive(SimpleObject, Debug)]
+#[graphql(complex)]
+pub struct RenameAccountNameCollision<'a> {
+    pub colliding_name: AccountName<'a>,
+}
+
+#[ComplexObject]
+impl RenameAccountNameCollision<'_> {
+    async fn message(&self) -> String {
+        format!("Account '{}' already exists", self.colliding_name)
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[derive(Interface, Debug)]
+#[graphql(field(name = "message", ty = "String"))]
+pub enum ResetPasswordResult {
+    Success(ResetPasswordSuccess),
+    NotPasswordAccount(ResetPasswordNotPasswordAccount),
+}
+
+#[derive(SimpleObject, Debug)]
+#[graphql(complex)]
+pub struct ResetPasswordSuccess {
+    pub dummy: bool,
+}
+
+#[ComplexObject]
+impl ResetPasswordSuccess {
+    async fn message(&self) -> String {
+        "Success".to_string()
+    }
+}
+
+#[derive(SimpleObject, Debug)]
+#[graphql(complex)]
+pub struct ResetPasswordNotPasswordAccount {
+    pub dummy: bool,
+}
+
+#[ComplexObject]
+impl ResetPasswordNotPasswordAccount {
+    async fn message(&self) -> String {
+        "Account does not use password authentication".to_string()
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[derive(Interface, Debug)]
+#[graphql(field(name = "message", ty = "String"))]
+pub enum DeleteAccountResult {
+    Success(DeleteAccountSuccess),
+    HasDatasets(DeleteAccountHasDatasets),
+}
+
+#[derive(SimpleObject, Debug)]
+#[graphql(complex)]
+pub struct DeleteAccountSuccess {
+    pub deleted_account: AccountName<'static>,
+}
+
+#[ComplexObject]
+impl DeleteAccountSuccess {
+    async fn message(&self) -> String {
+        "Success".to_string()
+    }
+}
+
+#[derive(SimpleObject, Debug)]
+#[graphql(complex)]
+pub struct DeleteAccountHasDatasets {
+    pub owned_datasets_count: usize,
+}
+
+#[ComplexObject]
+impl DeleteAccountHasDatasets {
+    async fn message(&self) -> String {
+        format!(
+            "Account still owns {} dataset(s), delete or transfer them first",
+            self.owned_datasets_count
+        )
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
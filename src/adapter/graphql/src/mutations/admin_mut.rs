@@ -0,0 +1,214 @@
+// Copyright Kamu Data, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use email_utils::Email;
+use kamu_accounts::{AccountManagementService, AccountService, CreatePasswordAccountError};
+use kamu_datasets::{TransferDatasetOwnershipError, TransferDatasetOwnershipUseCase};
+
+use super::AdminAccountMut;
+use crate::prelude::*;
+use crate::queries::Account;
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+/// Administrative operations. Every field is only available to node
+/// administrators.
+pub struct AdminMut;
+
+#[common_macros::method_names_consts(const_value_prefix = "GQL: ")]
+#[Object]
+impl AdminMut {
+    /// Returns a mutable account by its name
+    #[tracing::instrument(level = "info", name = AdminMut_account, skip_all, fields(%account_name))]
+    async fn account(
+        &self,
+        ctx: &Context<'_>,
+        account_name: AccountName<'_>,
+    ) -> Result<Option<AdminAccountMut>> {
+        let account_service = from_catalog_n!(ctx, dyn AccountService);
+
+        let account_maybe = account_service.account_by_name(&account_name).await?;
+        Ok(account_maybe.map(AdminAccountMut::new))
+    }
+
+    /// Creates a new account that logs in with a password
+    #[tracing::instrument(level = "info", name = AdminMut_create_account, skip_all, fields(%account_name))]
+    async fn create_account(
+        &self,
+        ctx: &Context<'_>,
+        account_name: AccountName<'_>,
+        email: String,
+        password: String,
+    ) -> Result<CreateAccountResult> {
+        let Ok(email) = Email::parse(&email) else {
+            return Ok(CreateAccountResult::InvalidEmail(
+                CreateAccountInvalidEmail { email },
+            ));
+        };
+
+        let account_management_service = from_catalog_n!(ctx, dyn AccountManagementService);
+
+        match account_management_service
+            .create_password_account(&account_name, email, password)
+            .await
+        {
+            Ok(account) => Ok(CreateAccountResult::Success(CreateAccountSuccess {
+                account: Account::from_account(account),
+            })),
+            Err(CreatePasswordAccountError::Duplicate(e)) => {
+                Ok(CreateAccountResult::Duplicate(CreateAccountDuplicate {
+                    field: e.account_field.to_string(),
+                }))
+            }
+            Err(CreatePasswordAccountError::Internal(e)) => Err(e.into()),
+        }
+    }
+
+    /// Moves a dataset under another account, keeping its identity and
+    /// history
+    #[tracing::instrument(level = "info", name = AdminMut_transfer_dataset_ownership, skip_all, fields(%dataset_id, %new_owner_name))]
+    async fn transfer_dataset_ownership(
+        &self,
+        ctx: &Context<'_>,
+        dataset_id: DatasetID<'_>,
+        new_owner_name: AccountName<'_>,
+    ) -> Result<TransferDatasetOwnershipResult<'static>> {
+        let transfer_use_case = from_catalog_n!(ctx, dyn TransferDatasetOwnershipUseCase);
+
+        match transfer_use_case
+            .execute(&dataset_id.as_local_ref(), &new_owner_name)
+            .await
+        {
+            Ok(hdl) => Ok(TransferDatasetOwnershipResult::Success(
+                TransferDatasetOwnershipSuccess {
+                    new_alias: hdl.alias.into(),
+                },
+            )),
+            Err(TransferDatasetOwnershipError::NewOwnerNotFound(e)) => Ok(
+                TransferDatasetOwnershipResult::AccountNotFound(TransferDatasetAccountNotFound {
+                    account_name: e.account_name.into(),
+                }),
+            ),
+            Err(TransferDatasetOwnershipError::NameCollision(e)) => Ok(
+                TransferDatasetOwnershipResult::NameCollision(TransferDatasetNameCollision {
+                    colliding_alias: e.alias.into(),
+                }),
+            ),
+            Err(TransferDatasetOwnershipError::NotFound(e)) => Err(GqlError::Gql(
+                Error::new(e.to_string())
+                    .extend_with(|_, eev| eev.set("datasetId", dataset_id.to_string())),
+            )),
+            Err(TransferDatasetOwnershipError::Access(_)) => Err(GqlError::Gql(
+                Error::new("Dataset access error")
+                    .extend_with(|_, eev| eev.set("datasetId", dataset_id.to_string())),
+            )),
+            Err(TransferDatasetOwnershipError::Internal(e)) => Err(e.into()),
+        }
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[derive(Interface, Debug)]
+#[graphql(field(name = "message", ty = "String"))]
+pub enum CreateAccountResult {
+    Success(CreateAccountSuccess),
+    Duplicate(CreateAccountDuplicate),
+    InvalidEmail(CreateAccountInvalidEmail),
+}
+
+#[derive(SimpleObject, Debug)]
+#[graphql(complex)]
+pub struct CreateAccountSuccess {
+    pub account: Account,
+}
+
+#[ComplexObject]
+impl CreateAccountSuccess {
+    async fn message(&self) -> String {
+        "Success".to_string()
+    }
+}
+
+#[derive(SimpleObject, Debug)]
+#[graphql(complex)]
+pub struct CreateAccountDuplicate {
+    pub field: String,
+}
+
+#[ComplexObject]
+impl CreateAccountDuplicate {
+    async fn message(&self) -> String {
+        format!("Account with duplicate {} already exists", self.field)
+    }
+}
+
+#[derive(SimpleObject, Debug)]
+#[graphql(complex)]
+pub struct CreateAccountInvalidEmail {
+    pub email: String,
+}
+
+#[ComplexObject]
+impl CreateAccountInvalidEmail {
+    async fn message(&self) -> String {
+        "Invalid email".to_string()
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[derive(Interface, Debug)]
+#[graphql(field(name = "message", ty = "String"))]
+pub enum TransferDatasetOwnershipResult<'a> {
+    Success(TransferDatasetOwnershipSuccess<'a>),
+    AccountNotFound(TransferDatasetAccountNotFound<'a>),
+    NameCollision(TransferDatasetNameCollision<'a>),
+}
+
+#[derive(SimpleObject, Debug)]
+#[graphql(complex)]
+pub struct TransferDatasetOwnershipSuccess<'a> {
+    pub new_alias: DatasetAlias<'a>,
+}
+
+#[ComplexObject]
+impl TransferDatasetOwnershipSuccess<'_> {
+    async fn message(&self) -> String {
+        "Success".to_string()
+    }
+}
+
+#[derive(SimpleObject, Debug)]
+#[graphql(complex)]
+pub struct TransferDatasetAccountNotFound<'a> {
+    pub account_name: AccountName<'a>,
+}
+
+#[ComplexObject]
+impl TransferDatasetAccountNotFound<'_> {
+    async fn message(&self) -> String {
+        format!("Account '{}' not found", self.account_name)
+    }
+}
+
+#[derive(SimpleObject, Debug)]
+#[graphql(complex)]
+pub struct TransferDatasetNameCollision<'a> {
+    pub colliding_alias: DatasetAlias<'a>,
+}
+
+#[ComplexObject]
+impl TransferDatasetNameCollision<'_> {
+    async fn message(&self) -> String {
+        format!("Dataset '{}' already exists", self.colliding_alias)
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
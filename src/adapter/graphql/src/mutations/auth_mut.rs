@@ -7,7 +7,13 @@
 // the Business Source License, use of this software will be governed
 // by the Apache License, Version 2.0.
 
-use kamu_accounts::{CreateAccessTokenError, RevokeTokenError};
+use email_utils::Email;
+use kamu_accounts::{
+    CreateAccessTokenError,
+    RegisterAccountError,
+    RevokeTokenError,
+    VerifyEmailError,
+};
 
 use crate::prelude::*;
 use crate::queries::{Account, CreateAccessTokenResultSuccess, CreatedAccessToken};
@@ -39,6 +45,64 @@ impl AuthMut {
         }
     }
 
+    /// Registers a new account authenticated by password. The account can be
+    /// used only after its email is confirmed with the token sent to it
+    #[tracing::instrument(level = "info", name = AuthMut_register, skip_all, fields(%account_name))]
+    async fn register(
+        &self,
+        ctx: &Context<'_>,
+        account_name: AccountName<'static>,
+        email: String,
+        password: String,
+    ) -> Result<RegisterAccountResult> {
+        let Ok(email) = Email::parse(&email) else {
+            return Ok(RegisterAccountResult::InvalidEmail(
+                RegisterAccountInvalidEmail { dummy: false },
+            ));
+        };
+
+        let authentication_service = from_catalog_n!(ctx, dyn kamu_accounts::AuthenticationService);
+
+        match authentication_service
+            .register_password_account(&account_name, email, password)
+            .await
+        {
+            Ok(account) => Ok(RegisterAccountResult::Success(RegisterAccountSuccess {
+                account: Account::from_account(account),
+            })),
+            Err(RegisterAccountError::RegistrationDisabled(_)) => {
+                Ok(RegisterAccountResult::RegistrationDisabled(
+                    RegisterAccountDisabled { dummy: false },
+                ))
+            }
+            Err(RegisterAccountError::Duplicate(e)) => {
+                Ok(RegisterAccountResult::Duplicate(RegisterAccountDuplicate {
+                    field: e.account_field.to_string(),
+                }))
+            }
+            Err(RegisterAccountError::Internal(e)) => Err(e.into()),
+        }
+    }
+
+    /// Confirms the email of a registered account and logs into it
+    #[tracing::instrument(level = "info", name = AuthMut_verify_email, skip_all)]
+    async fn verify_email(
+        &self,
+        ctx: &Context<'_>,
+        verification_token: String,
+    ) -> Result<LoginResponse> {
+        let authentication_service = from_catalog_n!(ctx, dyn kamu_accounts::AuthenticationService);
+
+        match authentication_service
+            .verify_email(&verification_token)
+            .await
+        {
+            Ok(login_response) => Ok(login_response.into()),
+            Err(VerifyEmailError::InvalidToken(e)) => Err(GqlError::Gql(Error::new(e.to_string()))),
+            Err(VerifyEmailError::Internal(e)) => Err(e.into()),
+        }
+    }
+
     #[tracing::instrument(level = "info", name = AuthMut_account_details, skip_all)]
     async fn account_details(&self, ctx: &Context<'_>, access_token: String) -> Result<Account> {
         let authentication_service = from_catalog_n!(ctx, dyn kamu_accounts::AuthenticationService);
@@ -120,6 +184,9 @@ impl From<kamu_accounts::LoginError> for GqlError {
             kamu_accounts::LoginError::NoPrimaryEmail(e) => GqlError::Gql(
                 Error::new(e.to_string()).extend_with(|_, eev| eev.set("reason", e.to_string())),
             ),
+            kamu_accounts::LoginError::EmailNotVerified(e) => GqlError::Gql(
+                Error::new(e.to_string()).extend_with(|_, eev| eev.set("reason", e.to_string())),
+            ),
             kamu_accounts::LoginError::DuplicateCredentials => {
                 GqlError::Gql(Error::new(value.to_string()))
             }
@@ -218,3 +285,66 @@ impl CreateAccessTokenResultDuplicate {
 }
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[derive(Interface, Debug)]
+#[graphql(field(name = "message", ty = "String"))]
+pub enum RegisterAccountResult {
+    Success(RegisterAccountSuccess),
+    RegistrationDisabled(RegisterAccountDisabled),
+    InvalidEmail(RegisterAccountInvalidEmail),
+    Duplicate(RegisterAccountDuplicate),
+}
+
+#[derive(SimpleObject, Debug)]
+#[graphql(complex)]
+pub struct RegisterAccountSuccess {
+    pub account: Account,
+}
+
+#[ComplexObject]
+impl RegisterAccountSuccess {
+    pub async fn message(&self) -> String {
+        "Account registered, check the email to confirm it".to_string()
+    }
+}
+
+#[derive(SimpleObject, Debug)]
+#[graphql(complex)]
+pub struct RegisterAccountDisabled {
+    pub dummy: bool,
+}
+
+#[ComplexObject]
+impl RegisterAccountDisabled {
+    pub async fn message(&self) -> String {
+        "Registration of new accounts is disabled".to_string()
+    }
+}
+
+#[derive(SimpleObject, Debug)]
+#[graphql(complex)]
+pub struct RegisterAccountInvalidEmail {
+    pub dummy: bool,
+}
+
+#[ComplexObject]
+impl RegisterAccountInvalidEmail {
+    pub async fn message(&self) -> String {
+        "Invalid email".to_string()
+    }
+}
+
+#[derive(SimpleObject, Debug)]
+#[graphql(complex)]
+pub struct RegisterAccountDuplicate {
+    pub field: String,
+}
+
+#[ComplexObject]
+impl RegisterAccountDuplicate {
+    pub async fn message(&self) -> String {
+        format!("Account with such {} already exists", self.field)
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
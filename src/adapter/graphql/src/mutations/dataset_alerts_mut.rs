@@ -0,0 +1,146 @@
+// Copyright Kamu Data, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use email_utils::Email;
+use kamu_alerts::{self as al, DatasetAlertsService, SetDatasetAlertSettingsError};
+
+use crate::prelude::*;
+use crate::queries::DatasetAlertSettings;
+use crate::utils;
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+pub struct DatasetAlertsMut {
+    dataset_handle: odf::DatasetHandle,
+}
+
+#[common_macros::method_names_consts(const_value_prefix = "GQL: ")]
+#[Object]
+impl DatasetAlertsMut {
+    #[graphql(skip)]
+    pub fn new(dataset_handle: odf::DatasetHandle) -> Self {
+        Self { dataset_handle }
+    }
+
+    /// Replaces alert settings of this dataset
+    #[tracing::instrument(level = "info", name = DatasetAlertsMut_set_settings, skip_all)]
+    async fn set_settings(
+        &self,
+        ctx: &Context<'_>,
+        settings: DatasetAlertSettingsInput,
+    ) -> Result<SetDatasetAlertSettingsResult> {
+        utils::check_dataset_write_access(ctx, &self.dataset_handle).await?;
+
+        let mut recipients = Vec::with_capacity(settings.recipients.len());
+        for recipient in &settings.recipients {
+            let Ok(email) = Email::parse(recipient) else {
+                return Ok(SetDatasetAlertSettingsResult::Invalid(
+                    SetDatasetAlertSettingsResultInvalid {
+                        reason: format!("Recipient '{recipient}' is not a valid email"),
+                    },
+                ));
+            };
+            recipients.push(email);
+        }
+
+        let alerts_service = from_catalog_n!(ctx, dyn DatasetAlertsService);
+
+        match alerts_service
+            .set_settings(
+                &self.dataset_handle.id,
+                al::DatasetAlertSettingsInput {
+                    notify_owner: settings.notify_owner,
+                    recipients,
+                    notify_on_flow_failure: settings.notify_on_flow_failure,
+                    stale_after: settings.stale_after.map(Into::into),
+                },
+            )
+            .await
+        {
+            Ok(settings) => Ok(SetDatasetAlertSettingsResult::Success(
+                SetDatasetAlertSettingsResultSuccess {
+                    settings: DatasetAlertSettings::new(settings),
+                },
+            )),
+            Err(SetDatasetAlertSettingsError::Invalid(e)) => {
+                Ok(SetDatasetAlertSettingsResult::Invalid(
+                    SetDatasetAlertSettingsResultInvalid { reason: e.reason },
+                ))
+            }
+            Err(SetDatasetAlertSettingsError::Internal(e)) => Err(e.into()),
+        }
+    }
+
+    /// Disables all alerts of this dataset
+    #[tracing::instrument(level = "info", name = DatasetAlertsMut_remove_settings, skip_all)]
+    async fn remove_settings(&self, ctx: &Context<'_>) -> Result<bool> {
+        utils::check_dataset_write_access(ctx, &self.dataset_handle).await?;
+
+        let alerts_service = from_catalog_n!(ctx, dyn DatasetAlertsService);
+
+        alerts_service
+            .remove_settings(&self.dataset_handle.id)
+            .await?;
+
+        Ok(true)
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[derive(InputObject)]
+pub struct DatasetAlertSettingsInput {
+    /// Send alerts to the dataset owner along with the recipients
+    pub notify_owner: bool,
+    /// Additional addresses to send alerts to
+    pub recipients: Vec<String>,
+    /// Send an alert when a flow of the dataset fails
+    pub notify_on_flow_failure: bool,
+    /// Send an alert when the dataset receives no new data for this long
+    pub stale_after: Option<TimeDeltaInput>,
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+// Results
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[derive(Interface, Debug)]
+#[graphql(field(name = "message", ty = "String"))]
+pub enum SetDatasetAlertSettingsResult {
+    Success(SetDatasetAlertSettingsResultSuccess),
+    Invalid(SetDatasetAlertSettingsResultInvalid),
+}
+
+#[derive(SimpleObject, Debug)]
+#[graphql(complex)]
+pub struct SetDatasetAlertSettingsResultSuccess {
+    pub settings: DatasetAlertSettings,
+}
+
+#[ComplexObject]
+impl SetDatasetAlertSettingsResultSuccess {
+    async fn message(&self) -> String {
+        "Success".to_string()
+    }
+}
+
+#[derive(SimpleObject, Debug)]
+#[graphql(complex)]
+pub struct SetDatasetAlertSettingsResultInvalid {
+    pub reason: String,
+}
+
+#[ComplexObject]
+impl SetDatasetAlertSettingsResultInvalid {
+    async fn message(&self) -> String {
+        format!("Invalid dataset alert settings: {}", self.reason)
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
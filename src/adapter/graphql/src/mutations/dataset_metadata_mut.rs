@@ -7,13 +7,19 @@
 // the Business Source License, use of this software will be governed
 // by the Apache License, Version 2.0.
 
+use kamu_core::{
+    DatasetExpectationsService,
+    DatasetFreshnessService,
+    SetDataExpectationsError,
+    SetFreshnessSlaError,
+};
 use kamu_datasets::CommitDatasetEventUseCase;
 use odf::dataset::MetadataChainExt as _;
 
 use super::{CommitResultAppendError, CommitResultSuccess, NoChanges};
 use crate::mutations::MetadataChainMut;
 use crate::prelude::*;
-use crate::utils::{get_dataset, make_dataset_access_error};
+use crate::utils::{self, get_dataset, make_dataset_access_error};
 use crate::LoggedInGuard;
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
@@ -132,6 +138,90 @@ impl DatasetMetadataMut {
 
         Ok(result)
     }
+
+    /// Replaces all data quality expectations of the dataset
+    #[tracing::instrument(level = "info", name = DatasetMetadataMut_set_expectations, skip_all)]
+    #[graphql(guard = "LoggedInGuard::new()")]
+    async fn set_expectations(
+        &self,
+        ctx: &Context<'_>,
+        expectations: Vec<DataExpectationInput>,
+    ) -> Result<SetExpectationsResult> {
+        utils::check_dataset_write_access(ctx, &self.dataset_handle).await?;
+
+        let dataset_expectations_svc = from_catalog_n!(ctx, dyn DatasetExpectationsService);
+        let resolved_dataset = get_dataset(ctx, &self.dataset_handle).await;
+
+        let event = match dataset_expectations_svc
+            .prepare_set_expectations(
+                &resolved_dataset,
+                expectations.into_iter().map(Into::into).collect(),
+            )
+            .await
+        {
+            Ok(Some(event)) => event,
+            Ok(None) => {
+                return Ok(SetExpectationsResult::Success(
+                    SetExpectationsResultSuccess { _dummy: None },
+                ))
+            }
+            Err(SetDataExpectationsError::Invalid(e)) => {
+                return Ok(SetExpectationsResult::Invalid(
+                    SetExpectationsResultInvalid {
+                        message: e.to_string(),
+                    },
+                ))
+            }
+            Err(SetDataExpectationsError::Internal(e)) => return Err(GqlError::Internal(e)),
+        };
+
+        let commit_event = from_catalog_n!(ctx, dyn CommitDatasetEventUseCase);
+
+        match commit_event.execute(&self.dataset_handle, event).await {
+            Ok(_) => Ok(SetExpectationsResult::Success(
+                SetExpectationsResultSuccess { _dummy: None },
+            )),
+            Err(odf::dataset::CommitError::MetadataAppendError(e)) => Ok(
+                SetExpectationsResult::Invalid(SetExpectationsResultInvalid {
+                    message: e.to_string(),
+                }),
+            ),
+            Err(odf::dataset::CommitError::Access(_)) => {
+                Err(make_dataset_access_error(&self.dataset_handle))
+            }
+            Err(e) => Err(e.int_err().into()),
+        }
+    }
+
+    /// Declares or removes (when `sla` is omitted) the freshness SLA of the
+    /// dataset
+    #[tracing::instrument(level = "info", name = DatasetMetadataMut_set_freshness_sla, skip_all)]
+    #[graphql(guard = "LoggedInGuard::new()")]
+    async fn set_freshness_sla(
+        &self,
+        ctx: &Context<'_>,
+        sla: Option<DatasetFreshnessSlaInput>,
+    ) -> Result<SetFreshnessSlaResult> {
+        utils::check_dataset_write_access(ctx, &self.dataset_handle).await?;
+
+        let dataset_freshness_svc = from_catalog_n!(ctx, dyn DatasetFreshnessService);
+        let resolved_dataset = get_dataset(ctx, &self.dataset_handle).await;
+
+        match dataset_freshness_svc
+            .set_sla(&resolved_dataset, sla.map(Into::into))
+            .await
+        {
+            Ok(()) => Ok(SetFreshnessSlaResult::Success(
+                SetFreshnessSlaResultSuccess { _dummy: None },
+            )),
+            Err(SetFreshnessSlaError::Invalid(e)) => Ok(SetFreshnessSlaResult::Invalid(
+                SetFreshnessSlaResultInvalid {
+                    message: e.to_string(),
+                },
+            )),
+            Err(SetFreshnessSlaError::Internal(e)) => Err(GqlError::Internal(e)),
+        }
+    }
 }
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
@@ -143,5 +233,58 @@ pub enum UpdateReadmeResult<'a> {
     NoChanges(NoChanges),
     AppendError(CommitResultAppendError),
 }
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[derive(Interface, Debug)]
+#[graphql(field(name = "message", ty = "String"))]
+pub enum SetExpectationsResult {
+    Success(SetExpectationsResultSuccess),
+    Invalid(SetExpectationsResultInvalid),
+}
+
+#[derive(SimpleObject, Debug)]
+#[graphql(complex)]
+pub struct SetExpectationsResultSuccess {
+    _dummy: Option<String>,
+}
+
+#[ComplexObject]
+impl SetExpectationsResultSuccess {
+    async fn message(&self) -> String {
+        "Success".to_string()
+    }
+}
+
+#[derive(SimpleObject, Debug)]
+pub struct SetExpectationsResultInvalid {
+    pub message: String,
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[derive(Interface, Debug)]
+#[graphql(field(name = "message", ty = "String"))]
+pub enum SetFreshnessSlaResult {
+    Success(SetFreshnessSlaResultSuccess),
+    Invalid(SetFreshnessSlaResultInvalid),
+}
+
+#[derive(SimpleObject, Debug)]
+#[graphql(complex)]
+pub struct SetFreshnessSlaResultSuccess {
+    _dummy: Option<String>,
+}
+
+#[ComplexObject]
+impl SetFreshnessSlaResultSuccess {
+    async fn message(&self) -> String {
+        "Success".to_string()
+    }
+}
+
+#[derive(SimpleObject, Debug)]
+pub struct SetFreshnessSlaResultInvalid {
+    pub message: String,
+}
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
@@ -13,9 +13,11 @@ use kamu_datasets::{DeleteDatasetError, RenameDatasetError};
 
 use crate::mutations::{
     ensure_account_is_owner_or_admin,
+    DatasetAlertsMut,
     DatasetEnvVarsMut,
     DatasetFlowsMut,
     DatasetMetadataMut,
+    DatasetWebhooksMut,
 };
 use crate::prelude::*;
 use crate::queries::*;
@@ -55,6 +57,16 @@ impl DatasetMut {
         Ok(DatasetEnvVarsMut::new(self.dataset_handle.clone()))
     }
 
+    /// Access to the mutable alert settings of this dataset
+    async fn alerts(&self) -> DatasetAlertsMut {
+        DatasetAlertsMut::new(self.dataset_handle.clone())
+    }
+
+    /// Access to the mutable webhook subscriptions of this dataset
+    async fn webhooks(&self) -> DatasetWebhooksMut {
+        DatasetWebhooksMut::new(self.dataset_handle.clone())
+    }
+
     /// Rename the dataset
     #[graphql(guard = "LoggedInGuard::new()")]
     #[tracing::instrument(level = "info", name = DatasetMut_rename, skip_all)]
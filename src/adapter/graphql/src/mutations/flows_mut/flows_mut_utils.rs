@@ -125,13 +125,15 @@ pub(crate) async fn ensure_flow_preconditions(
                 }));
             }
         }
-        DatasetFlowType::ExecuteTransform => {
+        DatasetFlowType::ExecuteTransform | DatasetFlowType::Reprocess => {
             let (metadata_query_service, view_dataset_use_case) = from_catalog_n!(
                 ctx,
                 dyn kamu_core::MetadataQueryService,
                 dyn ViewDatasetUseCase
             );
-            let source_res = metadata_query_service.get_active_transform(target).await?;
+            let source_res = metadata_query_service
+                .get_active_transform(target.clone())
+                .await?;
 
             match source_res {
                 Some((_, set_transform_block)) => {
@@ -161,6 +163,24 @@ pub(crate) async fn ensure_flow_preconditions(
                     }));
                 }
             }
+
+            if let Some(FlowRunConfiguration::Reprocess(reprocess_configuration)) =
+                flow_run_configuration
+                && let Some(ReprocessStartInput::Block(start_block_hash)) =
+                    &reprocess_configuration.start
+            {
+                use odf::dataset::MetadataChainExt as _;
+                if !target
+                    .as_metadata_chain()
+                    .contains_block(&start_block_hash.clone().into())
+                    .await
+                    .int_err()?
+                {
+                    return Ok(Some(FlowPreconditionsNotMet {
+                        preconditions: "Start block hash not found".to_string(),
+                    }));
+                }
+            }
         }
         DatasetFlowType::HardCompaction => (),
         DatasetFlowType::Reset => {
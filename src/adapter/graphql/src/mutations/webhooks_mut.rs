@@ -0,0 +1,384 @@
+// Copyright Kamu Data, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use kamu_accounts::Account;
+use kamu_webhooks::{
+    self as wh,
+    CreateWebhookSubscriptionError,
+    DeleteWebhookSubscriptionError,
+    GetWebhookSubscriptionError,
+    UpdateWebhookSubscriptionError,
+    WebhookSubscriptionService,
+};
+
+use crate::prelude::*;
+use crate::queries::WebhookSubscription;
+use crate::utils;
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+pub struct DatasetWebhooksMut {
+    dataset_handle: odf::DatasetHandle,
+}
+
+#[common_macros::method_names_consts(const_value_prefix = "GQL: ")]
+#[Object]
+impl DatasetWebhooksMut {
+    #[graphql(skip)]
+    pub fn new(dataset_handle: odf::DatasetHandle) -> Self {
+        Self { dataset_handle }
+    }
+
+    #[graphql(skip)]
+    fn scope(&self) -> WebhookScope {
+        WebhookScope::Dataset(&self.dataset_handle.id)
+    }
+
+    /// Registers an endpoint that will receive events of this dataset. The
+    /// secret used to sign payloads is returned only once.
+    #[tracing::instrument(level = "info", name = DatasetWebhooksMut_create_subscription, skip_all)]
+    async fn create_subscription(
+        &self,
+        ctx: &Context<'_>,
+        target_url: String,
+        label: String,
+        event_types: Vec<WebhookEventType>,
+    ) -> Result<CreateWebhookSubscriptionResult> {
+        utils::check_dataset_write_access(ctx, &self.dataset_handle).await?;
+
+        let logged_account = utils::get_logged_account(ctx);
+
+        create_subscription(
+            ctx,
+            &logged_account.account_id,
+            Some(&self.dataset_handle.id),
+            target_url,
+            label,
+            event_types,
+        )
+        .await
+    }
+
+    /// Stops delivering events to the endpoint
+    #[tracing::instrument(level = "info", name = DatasetWebhooksMut_pause_subscription, skip_all, fields(%id))]
+    async fn pause_subscription(
+        &self,
+        ctx: &Context<'_>,
+        id: WebhookSubscriptionID<'static>,
+    ) -> Result<ModifyWebhookSubscriptionResult> {
+        utils::check_dataset_write_access(ctx, &self.dataset_handle).await?;
+
+        set_subscription_status(ctx, self.scope(), id, wh::WebhookSubscriptionStatus::Paused).await
+    }
+
+    /// Resumes delivering events to the endpoint
+    #[tracing::instrument(level = "info", name = DatasetWebhooksMut_resume_subscription, skip_all, fields(%id))]
+    async fn resume_subscription(
+        &self,
+        ctx: &Context<'_>,
+        id: WebhookSubscriptionID<'static>,
+    ) -> Result<ModifyWebhookSubscriptionResult> {
+        utils::check_dataset_write_access(ctx, &self.dataset_handle).await?;
+
+        set_subscription_status(
+            ctx,
+            self.scope(),
+            id,
+            wh::WebhookSubscriptionStatus::Enabled,
+        )
+        .await
+    }
+
+    /// Removes the endpoint along with its delivery log
+    #[tracing::instrument(level = "info", name = DatasetWebhooksMut_remove_subscription, skip_all, fields(%id))]
+    async fn remove_subscription(
+        &self,
+        ctx: &Context<'_>,
+        id: WebhookSubscriptionID<'static>,
+    ) -> Result<ModifyWebhookSubscriptionResult> {
+        utils::check_dataset_write_access(ctx, &self.dataset_handle).await?;
+
+        remove_subscription(ctx, self.scope(), id).await
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+pub struct AccountWebhooksMut {
+    account: Account,
+}
+
+#[common_macros::method_names_consts(const_value_prefix = "GQL: ")]
+#[Object]
+impl AccountWebhooksMut {
+    #[graphql(skip)]
+    pub fn new(account: Account) -> Self {
+        Self { account }
+    }
+
+    #[graphql(skip)]
+    fn scope(&self) -> WebhookScope {
+        WebhookScope::Account(&self.account.id)
+    }
+
+    /// Registers an endpoint that will receive events of all datasets owned by
+    /// this account. The secret used to sign payloads is returned only once.
+    #[tracing::instrument(level = "info", name = AccountWebhooksMut_create_subscription, skip_all)]
+    async fn create_subscription(
+        &self,
+        ctx: &Context<'_>,
+        target_url: String,
+        label: String,
+        event_types: Vec<WebhookEventType>,
+    ) -> Result<CreateWebhookSubscriptionResult> {
+        create_subscription(ctx, &self.account.id, None, target_url, label, event_types).await
+    }
+
+    /// Stops delivering events to the endpoint
+    #[tracing::instrument(level = "info", name = AccountWebhooksMut_pause_subscription, skip_all, fields(%id))]
+    async fn pause_subscription(
+        &self,
+        ctx: &Context<'_>,
+        id: WebhookSubscriptionID<'static>,
+    ) -> Result<ModifyWebhookSubscriptionResult> {
+        set_subscription_status(ctx, self.scope(), id, wh::WebhookSubscriptionStatus::Paused).await
+    }
+
+    /// Resumes delivering events to the endpoint
+    #[tracing::instrument(level = "info", name = AccountWebhooksMut_resume_subscription, skip_all, fields(%id))]
+    async fn resume_subscription(
+        &self,
+        ctx: &Context<'_>,
+        id: WebhookSubscriptionID<'static>,
+    ) -> Result<ModifyWebhookSubscriptionResult> {
+        set_subscription_status(
+            ctx,
+            self.scope(),
+            id,
+            wh::WebhookSubscriptionStatus::Enabled,
+        )
+        .await
+    }
+
+    /// Removes the endpoint along with its delivery log
+    #[tracing::instrument(level = "info", name = AccountWebhooksMut_remove_subscription, skip_all, fields(%id))]
+    async fn remove_subscription(
+        &self,
+        ctx: &Context<'_>,
+        id: WebhookSubscriptionID<'static>,
+    ) -> Result<ModifyWebhookSubscriptionResult> {
+        remove_subscription(ctx, self.scope(), id).await
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+// Helpers
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+/// Subscriptions a mutation is allowed to touch: ones attached to the dataset,
+/// or all ones owned by the account
+enum WebhookScope<'a> {
+    Dataset(&'a odf::DatasetID),
+    Account(&'a odf::AccountID),
+}
+
+impl WebhookScope<'_> {
+    fn contains(&self, subscription: &wh::WebhookSubscription) -> bool {
+        match self {
+            Self::Dataset(dataset_id) => subscription.dataset_id.as_ref() == Some(*dataset_id),
+            Self::Account(account_id) => &subscription.owner_account_id == *account_id,
+        }
+    }
+}
+
+async fn create_subscription(
+    ctx: &Context<'_>,
+    owner_account_id: &odf::AccountID,
+    dataset_id: Option<&odf::DatasetID>,
+    target_url: String,
+    label: String,
+    event_types: Vec<WebhookEventType>,
+) -> Result<CreateWebhookSubscriptionResult> {
+    let Ok(target_url) = url::Url::parse(&target_url) else {
+        return Ok(CreateWebhookSubscriptionResult::Invalid(
+            CreateWebhookSubscriptionResultInvalid {
+                reason: "Target URL is malformed".to_string(),
+            },
+        ));
+    };
+
+    let subscription_service = from_catalog_n!(ctx, dyn WebhookSubscriptionService);
+
+    match subscription_service
+        .create_subscription(
+            owner_account_id,
+            dataset_id,
+            target_url,
+            label,
+            event_types.into_iter().map(Into::into).collect(),
+        )
+        .await
+    {
+        Ok(subscription) => Ok(CreateWebhookSubscriptionResult::Success(
+            CreateWebhookSubscriptionResultSuccess {
+                secret: subscription.secret.clone(),
+                subscription: WebhookSubscription::new(subscription),
+            },
+        )),
+        Err(CreateWebhookSubscriptionError::Invalid(e)) => {
+            Ok(CreateWebhookSubscriptionResult::Invalid(
+                CreateWebhookSubscriptionResultInvalid { reason: e.reason },
+            ))
+        }
+        Err(CreateWebhookSubscriptionError::Internal(e)) => Err(e.into()),
+    }
+}
+
+/// Returns `false` if the subscription does not exist or is outside the scope
+async fn is_subscription_in_scope(
+    subscription_service: &dyn WebhookSubscriptionService,
+    scope: &WebhookScope<'_>,
+    subscription_id: &uuid::Uuid,
+) -> Result<bool> {
+    match subscription_service.get_subscription(subscription_id).await {
+        Ok(subscription) => Ok(scope.contains(&subscription)),
+        Err(GetWebhookSubscriptionError::NotFound(_)) => Ok(false),
+        Err(GetWebhookSubscriptionError::Internal(e)) => Err(e.into()),
+    }
+}
+
+async fn set_subscription_status(
+    ctx: &Context<'_>,
+    scope: WebhookScope<'_>,
+    id: WebhookSubscriptionID<'static>,
+    status: wh::WebhookSubscriptionStatus,
+) -> Result<ModifyWebhookSubscriptionResult> {
+    let subscription_service = from_catalog_n!(ctx, dyn WebhookSubscriptionService);
+
+    if !is_subscription_in_scope(subscription_service.as_ref(), &scope, &id).await? {
+        return Ok(ModifyWebhookSubscriptionResult::not_found(id));
+    }
+
+    match subscription_service
+        .set_subscription_status(&id, status)
+        .await
+    {
+        Ok(()) => Ok(ModifyWebhookSubscriptionResult::success(id)),
+        Err(UpdateWebhookSubscriptionError::NotFound(_)) => {
+            Ok(ModifyWebhookSubscriptionResult::not_found(id))
+        }
+        Err(UpdateWebhookSubscriptionError::Internal(e)) => Err(e.into()),
+    }
+}
+
+async fn remove_subscription(
+    ctx: &Context<'_>,
+    scope: WebhookScope<'_>,
+    id: WebhookSubscriptionID<'static>,
+) -> Result<ModifyWebhookSubscriptionResult> {
+    let subscription_service = from_catalog_n!(ctx, dyn WebhookSubscriptionService);
+
+    if !is_subscription_in_scope(subscription_service.as_ref(), &scope, &id).await? {
+        return Ok(ModifyWebhookSubscriptionResult::not_found(id));
+    }
+
+    match subscription_service.remove_subscription(&id).await {
+        Ok(()) => Ok(ModifyWebhookSubscriptionResult::success(id)),
+        Err(DeleteWebhookSubscriptionError::NotFound(_)) => {
+            Ok(ModifyWebhookSubscriptionResult::not_found(id))
+        }
+        Err(DeleteWebhookSubscriptionError::Internal(e)) => Err(e.into()),
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+// Results
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[derive(Interface, Debug)]
+#[graphql(field(name = "message", ty = "String"))]
+pub enum CreateWebhookSubscriptionResult {
+    Success(CreateWebhookSubscriptionResultSuccess),
+    Invalid(CreateWebhookSubscriptionResultInvalid),
+}
+
+#[derive(SimpleObject, Debug)]
+#[graphql(complex)]
+pub struct CreateWebhookSubscriptionResultSuccess {
+    pub subscription: WebhookSubscription,
+    /// Secret used to sign payloads, it cannot be retrieved later
+    pub secret: String,
+}
+
+#[ComplexObject]
+impl CreateWebhookSubscriptionResultSuccess {
+    async fn message(&self) -> String {
+        "Success".to_string()
+    }
+}
+
+#[derive(SimpleObject, Debug)]
+#[graphql(complex)]
+pub struct CreateWebhookSubscriptionResultInvalid {
+    pub reason: String,
+}
+
+#[ComplexObject]
+impl CreateWebhookSubscriptionResultInvalid {
+    async fn message(&self) -> String {
+        format!("Invalid webhook subscription: {}", self.reason)
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[derive(Interface, Debug)]
+#[graphql(field(name = "message", ty = "String"))]
+pub enum ModifyWebhookSubscriptionResult {
+    Success(ModifyWebhookSubscriptionResultSuccess),
+    NotFound(ModifyWebhookSubscriptionResultNotFound),
+}
+
+impl ModifyWebhookSubscriptionResult {
+    fn success(subscription_id: WebhookSubscriptionID<'static>) -> Self {
+        Self::Success(ModifyWebhookSubscriptionResultSuccess { subscription_id })
+    }
+
+    fn not_found(subscription_id: WebhookSubscriptionID<'static>) -> Self {
+        Self::NotFound(ModifyWebhookSubscriptionResultNotFound { subscription_id })
+    }
+}
+
+#[derive(SimpleObject, Debug)]
+#[graphql(complex)]
+pub struct ModifyWebhookSubscriptionResultSuccess {
+    pub subscription_id: WebhookSubscriptionID<'static>,
+}
+
+#[ComplexObject]
+impl ModifyWebhookSubscriptionResultSuccess {
+    async fn message(&self) -> String {
+        "Success".to_string()
+    }
+}
+
+#[derive(SimpleObject, Debug)]
+#[graphql(complex)]
+pub struct ModifyWebhookSubscriptionResultNotFound {
+    pub subscription_id: WebhookSubscriptionID<'static>,
+}
+
+#[ComplexObject]
+impl ModifyWebhookSubscriptionResultNotFound {
+    async fn message(&self) -> String {
+        format!("Webhook subscription '{}' not found", self.subscription_id)
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
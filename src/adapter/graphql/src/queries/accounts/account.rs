@@ -8,6 +8,7 @@
 // by the Apache License, Version 2.0.
 
 use kamu_accounts::{
+    AccountManagementService,
     AccountService,
     CurrentAccountSubject,
     DEFAULT_ACCOUNT_ID,
@@ -15,9 +16,10 @@ use kamu_accounts::{
 };
 use tokio::sync::OnceCell;
 
-use super::AccountFlows;
+use super::{AccountFlows, AccountWebhooks};
 use crate::prelude::*;
 use crate::utils::check_logged_account_id_match;
+use crate::AdminGuard;
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
 
@@ -186,6 +188,18 @@ impl Account {
         Ok(full_account_info.is_admin)
     }
 
+    /// Indicates whether the account was disabled by an administrator
+    #[graphql(guard = "AdminGuard::new()")]
+    async fn is_disabled(&self, ctx: &Context<'_>) -> Result<bool> {
+        let account_management_service = from_catalog_n!(ctx, dyn AccountManagementService);
+
+        let full_account_info = self.get_full_account_info(ctx).await?;
+
+        Ok(account_management_service
+            .is_account_disabled(full_account_info)
+            .await?)
+    }
+
     /// Access to the flow configurations of this account
     async fn flows(&self, ctx: &Context<'_>) -> Result<Option<AccountFlows>> {
         check_logged_account_id_match(ctx, &self.account_id)?;
@@ -194,6 +208,15 @@ impl Account {
             self.get_full_account_info(ctx).await?.clone(),
         )))
     }
+
+    /// Access to the webhook subscriptions of this account
+    async fn webhooks(&self, ctx: &Context<'_>) -> Result<Option<AccountWebhooks>> {
+        check_logged_account_id_match(ctx, &self.account_id)?;
+
+        Ok(Some(AccountWebhooks::new(
+            self.get_full_account_info(ctx).await?.clone(),
+        )))
+    }
 }
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
@@ -0,0 +1,47 @@
+// Copyright Kamu Data, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use kamu_accounts::Account;
+use kamu_webhooks::WebhookSubscriptionService;
+
+use crate::prelude::*;
+use crate::queries::WebhookSubscription;
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+pub struct AccountWebhooks {
+    account: Account,
+}
+
+#[common_macros::method_names_consts(const_value_prefix = "GQL: ")]
+#[Object]
+impl AccountWebhooks {
+    #[graphql(skip)]
+    pub fn new(account: Account) -> Self {
+        Self { account }
+    }
+
+    /// Returns all endpoints owned by this account, both account-wide and
+    /// dataset-specific ones
+    #[tracing::instrument(level = "info", name = AccountWebhooks_subscriptions, skip_all)]
+    async fn subscriptions(&self, ctx: &Context<'_>) -> Result<Vec<WebhookSubscription>> {
+        let subscription_service = from_catalog_n!(ctx, dyn WebhookSubscriptionService);
+
+        let subscriptions = subscription_service
+            .list_account_subscriptions(&self.account.id)
+            .await?;
+
+        Ok(subscriptions
+            .into_iter()
+            .map(WebhookSubscription::new)
+            .collect())
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
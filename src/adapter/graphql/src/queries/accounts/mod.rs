@@ -11,10 +11,12 @@ mod account;
 mod account_flow_runs;
 mod account_flow_triggers;
 mod account_flows;
+mod account_webhooks;
 mod accounts;
 
 pub(crate) use account::*;
 pub(crate) use account_flow_runs::*;
 pub(crate) use account_flow_triggers::*;
 pub(crate) use account_flows::*;
+pub(crate) use account_webhooks::*;
 pub(crate) use accounts::*;
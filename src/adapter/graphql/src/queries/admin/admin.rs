@@ -7,20 +7,148 @@
 // the Business Source License, use of this software will be governed
 // by the Apache License, Version 2.0.
 
+use std::collections::HashMap;
+
+use database_common::PaginationOpts;
+use futures::TryStreamExt;
+use kamu_accounts::ExpensiveAccountRepository;
+use kamu_task_system as ts;
+use messaging_outbox::{OutboxMessageConsumptionRepository, OutboxMessageRepository};
+
 use crate::prelude::*;
+use crate::queries::{Account, AccountConnection};
 use crate::AdminGuard;
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
 
 pub struct Admin;
 
+#[common_macros::method_names_consts(const_value_prefix = "GQL: ")]
 #[Object]
 impl Admin {
+    const DEFAULT_ACCOUNTS_PER_PAGE: usize = 15;
+
     #[allow(clippy::unused_async)]
     #[graphql(guard = "AdminGuard::new()")]
     async fn self_test(&self) -> Result<String> {
         Ok("OK".to_string())
     }
+
+    /// Lists all accounts registered on the node
+    #[tracing::instrument(level = "info", name = Admin_accounts, skip_all, fields(?page, ?per_page))]
+    #[graphql(guard = "AdminGuard::new()")]
+    async fn accounts(
+        &self,
+        ctx: &Context<'_>,
+        page: Option<usize>,
+        per_page: Option<usize>,
+    ) -> Result<AccountConnection> {
+        let expensive_account_repo = from_catalog_n!(ctx, dyn ExpensiveAccountRepository);
+
+        let page = page.unwrap_or(0);
+        let per_page = per_page.unwrap_or(Self::DEFAULT_ACCOUNTS_PER_PAGE);
+
+        let total_count = expensive_account_repo.accounts_count().await.int_err()?;
+        let accounts: Vec<_> = expensive_account_repo
+            .get_accounts(PaginationOpts {
+                offset: page * per_page,
+                limit: per_page,
+            })
+            .await
+            .map_ok(Account::from_account)
+            .try_collect()
+            .await?;
+
+        Ok(AccountConnection::new(
+            accounts,
+            page,
+            per_page,
+            total_count,
+        ))
+    }
+
+    /// Shows how far each outbox consumer lags behind its producer
+    #[tracing::instrument(level = "info", name = Admin_outbox_consumers, skip_all)]
+    #[graphql(guard = "AdminGuard::new()")]
+    async fn outbox_consumers(&self, ctx: &Context<'_>) -> Result<Vec<OutboxConsumerLag>> {
+        let (outbox_message_repo, outbox_consumption_repo) = from_catalog_n!(
+            ctx,
+            dyn OutboxMessageRepository,
+            dyn OutboxMessageConsumptionRepository
+        );
+
+        let latest_message_ids_by_producer: HashMap<_, _> = outbox_message_repo
+            .get_latest_message_ids_by_producer()
+            .await?
+            .into_iter()
+            .map(|(producer_name, message_id)| (producer_name, message_id.into_inner()))
+            .collect();
+
+        let mut boundaries: Vec<_> = outbox_consumption_repo
+            .list_consumption_boundaries()
+            .try_collect()
+            .await?;
+        boundaries.sort();
+
+        Ok(boundaries
+            .into_iter()
+            .map(|boundary| {
+                let last_consumed_message_id = boundary.last_consumed_message_id.into_inner();
+                let latest_message_id = latest_message_ids_by_producer
+                    .get(&boundary.producer_name)
+                    .copied()
+                    .unwrap_or(last_consumed_message_id);
+
+                OutboxConsumerLag {
+                    producer_name: boundary.producer_name,
+                    consumer_name: boundary.consumer_name,
+                    last_consumed_message_id,
+                    latest_message_id,
+                    lag: (latest_message_id - last_consumed_message_id).max(0),
+                }
+            })
+            .collect())
+    }
+
+    /// Shows how many tasks of each account are queued and running
+    #[tracing::instrument(level = "info", name = Admin_task_queue, skip_all)]
+    #[graphql(guard = "AdminGuard::new()")]
+    async fn task_queue(&self, ctx: &Context<'_>) -> Result<Vec<TaskQueueDepth>> {
+        let task_scheduler = from_catalog_n!(ctx, dyn ts::TaskScheduler);
+
+        Ok(task_scheduler
+            .get_queue_depth()
+            .await?
+            .into_iter()
+            .map(|depth| TaskQueueDepth {
+                account_id: depth.account_id.map(Into::into),
+                queued_count: depth.queued_count,
+                running_count: depth.running_count,
+            })
+            .collect())
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[derive(SimpleObject, Debug)]
+pub struct OutboxConsumerLag {
+    pub producer_name: String,
+    pub consumer_name: String,
+    pub last_consumed_message_id: i64,
+    pub latest_message_id: i64,
+    /// Number of messages the consumer has not processed yet
+    pub lag: i64,
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[derive(SimpleObject, Debug)]
+pub struct TaskQueueDepth {
+    /// Account the tasks are attributed to, tasks of system flows have none
+    pub account_id: Option<AccountID<'static>>,
+    pub queued_count: usize,
+    pub running_count: usize,
 }
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
@@ -13,7 +13,7 @@ use kamu_datasets::{ViewDatasetUseCase, ViewDatasetUseCaseError};
 
 use crate::prelude::*;
 use crate::queries::*;
-use crate::utils::{ensure_dataset_env_vars_enabled, get_dataset};
+use crate::utils::{check_dataset_write_access, ensure_dataset_env_vars_enabled, get_dataset};
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
 
@@ -147,6 +147,20 @@ impl Dataset {
         DatasetFlows::new(self.dataset_handle.clone())
     }
 
+    /// Access to the alert settings of this dataset
+    async fn alerts(&self, ctx: &Context<'_>) -> Result<DatasetAlerts> {
+        check_dataset_write_access(ctx, &self.dataset_handle).await?;
+
+        Ok(DatasetAlerts::new(self.dataset_handle.clone()))
+    }
+
+    /// Access to the webhook subscriptions of this dataset
+    async fn webhooks(&self, ctx: &Context<'_>) -> Result<DatasetWebhooks> {
+        check_dataset_write_access(ctx, &self.dataset_handle).await?;
+
+        Ok(DatasetWebhooks::new(self.dataset_handle.clone()))
+    }
+
     // TODO: PERF: Avoid traversing the entire chain
     /// Creation time of the first metadata block in the chain
     #[tracing::instrument(level = "info", name = Dataset_created_at, skip_all)]
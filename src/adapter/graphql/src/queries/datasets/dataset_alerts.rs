@@ -0,0 +1,90 @@
+// Copyright Kamu Data, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use chrono::{DateTime, Utc};
+use kamu_alerts::{self as al, DatasetAlertsService};
+
+use crate::prelude::*;
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+pub struct DatasetAlerts {
+    dataset_handle: odf::DatasetHandle,
+}
+
+#[common_macros::method_names_consts(const_value_prefix = "GQL: ")]
+#[Object]
+impl DatasetAlerts {
+    #[graphql(skip)]
+    pub fn new(dataset_handle: odf::DatasetHandle) -> Self {
+        Self { dataset_handle }
+    }
+
+    /// Returns alert settings of this dataset, if alerts are configured
+    #[tracing::instrument(level = "info", name = DatasetAlerts_settings, skip_all)]
+    async fn settings(&self, ctx: &Context<'_>) -> Result<Option<DatasetAlertSettings>> {
+        let alerts_service = from_catalog_n!(ctx, dyn DatasetAlertsService);
+
+        let maybe_settings = alerts_service.get_settings(&self.dataset_handle.id).await?;
+
+        Ok(maybe_settings.map(DatasetAlertSettings::new))
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[derive(Debug)]
+pub struct DatasetAlertSettings {
+    settings: al::DatasetAlertSettings,
+}
+
+#[common_macros::method_names_consts(const_value_prefix = "GQL: ")]
+#[Object]
+impl DatasetAlertSettings {
+    #[graphql(skip)]
+    pub fn new(settings: al::DatasetAlertSettings) -> Self {
+        Self { settings }
+    }
+
+    /// Whether the dataset owner receives alerts along with the recipients
+    async fn notify_owner(&self) -> bool {
+        self.settings.notify_owner
+    }
+
+    /// Additional addresses alerts are sent to
+    async fn recipients(&self) -> Vec<String> {
+        self.settings
+            .recipients
+            .iter()
+            .map(ToString::to_string)
+            .collect()
+    }
+
+    /// Whether an alert is sent when a flow of the dataset fails
+    async fn notify_on_flow_failure(&self) -> bool {
+        self.settings.notify_on_flow_failure
+    }
+
+    /// An alert is sent when the dataset receives no new data for this long
+    async fn stale_after(&self) -> Option<TimeDelta> {
+        self.settings.stale_after.map(Into::into)
+    }
+
+    /// Last time an update flow brought new data into the dataset
+    async fn last_updated_at(&self) -> DateTime<Utc> {
+        self.settings.last_updated_at
+    }
+
+    /// Set when the staleness alert was sent and no new data arrived since
+    async fn stale_notified_at(&self) -> Option<DateTime<Utc>> {
+        self.settings.stale_notified_at
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
@@ -10,6 +10,7 @@
 use kamu_core::{self as domain, QueryError};
 
 use crate::prelude::*;
+use crate::queries::Account;
 use crate::utils::get_dataset;
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
@@ -22,6 +23,7 @@ pub struct DatasetData {
 #[Object]
 impl DatasetData {
     const DEFAULT_TAIL_LIMIT: u64 = 20;
+    const DEFAULT_DIFF_LIMIT: u64 = 100;
 
     #[graphql(skip)]
     pub fn new(dataset_handle: odf::DatasetHandle) -> Self {
@@ -111,6 +113,116 @@ impl DatasetData {
 
         Ok(DataQueryResult::success(Some(schema), data, None, limit))
     }
+
+    /// Returns metadata and data changes that happened between two blocks.
+    /// The `fromBlock` is exclusive and defaults to the beginning of the chain,
+    /// the `toBlock` is inclusive and defaults to the current head. The
+    /// `limit` applies to the number of changed records returned.
+    #[tracing::instrument(level = "info", name = DatasetData_diff, skip_all, fields(?from_block, ?to_block, ?limit))]
+    async fn diff(
+        &self,
+        ctx: &Context<'_>,
+        from_block: Option<Multihash<'static>>,
+        to_block: Option<Multihash<'static>>,
+        limit: Option<u64>,
+        data_format: Option<DataBatchFormat>,
+        schema_format: Option<DataSchemaFormat>,
+    ) -> Result<DatasetDiff> {
+        let dataset_diff_svc = from_catalog_n!(ctx, dyn domain::DatasetDiffService);
+
+        let data_format = data_format.unwrap_or(DataBatchFormat::Json);
+        let schema_format = schema_format.unwrap_or(DataSchemaFormat::Parquet);
+        let limit = limit.unwrap_or(Self::DEFAULT_DIFF_LIMIT);
+
+        let resolved_dataset = get_dataset(ctx, &self.dataset_handle).await;
+
+        let from_block: Option<odf::Multihash> = from_block.map(Into::into);
+        let to_block: Option<odf::Multihash> = to_block.map(Into::into);
+
+        let diff = dataset_diff_svc
+            .diff(resolved_dataset, from_block.as_ref(), to_block.as_ref())
+            .await
+            .map_err(|e| match e {
+                domain::DatasetDiffError::BlockNotFound(e) => {
+                    GqlError::Gql(async_graphql::Error::new(e.to_string()))
+                }
+                domain::DatasetDiffError::InvalidInterval(e) => {
+                    GqlError::Gql(async_graphql::Error::new(e.to_string()))
+                }
+                domain::DatasetDiffError::Access(e) => {
+                    GqlError::Gql(async_graphql::Error::new(e.to_string()))
+                }
+                domain::DatasetDiffError::Internal(e) => GqlError::Internal(e),
+            })?;
+
+        let account = Account::from_dataset_alias(ctx, &self.dataset_handle.alias)
+            .await?
+            .expect("Account must exist");
+
+        let mut metadata_blocks = Vec::with_capacity(diff.metadata_blocks.len());
+        for (hash, block) in diff.metadata_blocks {
+            metadata_blocks
+                .push(MetadataBlockExtended::new(ctx, hash, block, account.clone()).await?);
+        }
+
+        let records = match diff.data.records {
+            None => DataQueryResult::no_schema_yet(data_format, limit),
+            Some(df) => {
+                let schema = DataSchema::from_data_frame_schema(df.schema(), schema_format)?;
+                let record_batches = df
+                    .limit(0, Some(usize::try_from(limit).unwrap()))
+                    .int_err()?
+                    .collect()
+                    .await
+                    .int_err()?;
+                let data = DataBatch::from_records(&record_batches, data_format)?;
+                DataQueryResult::success(Some(schema), data, None, limit)
+            }
+        };
+
+        Ok(DatasetDiff {
+            from_block: diff.from_block.map(Into::into),
+            to_block: diff.to_block.into(),
+            metadata_blocks,
+            data: DatasetDataDiff {
+                offset_interval: diff.data.offset_interval.map(Into::into),
+                num_appended: diff.data.num_appended,
+                num_retracted: diff.data.num_retracted,
+                num_corrected: diff.data.num_corrected,
+                primary_key: diff.data.primary_key,
+                records,
+            },
+        })
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[derive(SimpleObject)]
+pub struct DatasetDiff {
+    /// Block the comparison started from (exclusive)
+    pub from_block: Option<Multihash<'static>>,
+    /// Block the comparison ended at (inclusive)
+    pub to_block: Multihash<'static>,
+    /// Metadata blocks added within the interval in the reverse chronological
+    /// order
+    pub metadata_blocks: Vec<MetadataBlockExtended>,
+    /// Summary of the data changes within the interval
+    pub data: DatasetDataDiff,
+}
+
+#[derive(SimpleObject)]
+pub struct DatasetDataDiff {
+    /// Offsets of the records written within the interval
+    pub offset_interval: Option<OffsetInterval>,
+    pub num_appended: u64,
+    pub num_retracted: u64,
+    /// Number of corrections, where every `-C` / `+C` pair counts once
+    pub num_corrected: u64,
+    /// Primary key of the snapshot merge strategy the records are grouped by
+    pub primary_key: Vec<String>,
+    /// Changed records ordered by the primary key (if any) and offset
+    pub records: DataQueryResult,
 }
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
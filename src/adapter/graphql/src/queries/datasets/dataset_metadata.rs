@@ -10,6 +10,8 @@
 use chrono::prelude::*;
 use kamu_core::{
     DatasetDependency,
+    DatasetExpectationsService,
+    DatasetFreshnessService,
     GetDatasetDownstreamDependenciesUseCase,
     GetDatasetUpstreamDependenciesUseCase,
 };
@@ -285,6 +287,49 @@ impl DatasetMetadata {
             .into_event()
             .map(Into::into))
     }
+
+    /// Data quality expectations the new data is checked against
+    #[tracing::instrument(level = "info", name = DatasetMetadata_expectations, skip_all)]
+    async fn expectations(&self, ctx: &Context<'_>) -> Result<Vec<DataExpectation>> {
+        let dataset_expectations_svc = from_catalog_n!(ctx, dyn DatasetExpectationsService);
+        let resolved_dataset = get_dataset(ctx, &self.dataset_handle).await;
+
+        Ok(dataset_expectations_svc
+            .get_expectations(&resolved_dataset)
+            .await?
+            .into_iter()
+            .map(Into::into)
+            .collect())
+    }
+
+    /// Results of the expectations evaluated when the specified block was
+    /// committed, if any
+    #[tracing::instrument(level = "info", name = DatasetMetadata_expectations_report, skip_all, fields(%block_hash))]
+    async fn expectations_report(
+        &self,
+        ctx: &Context<'_>,
+        block_hash: Multihash<'static>,
+    ) -> Result<Option<DataExpectationsReport>> {
+        let dataset_expectations_svc = from_catalog_n!(ctx, dyn DatasetExpectationsService);
+        let resolved_dataset = get_dataset(ctx, &self.dataset_handle).await;
+
+        Ok(dataset_expectations_svc
+            .get_report(&resolved_dataset, &block_hash)
+            .await?
+            .map(Into::into))
+    }
+
+    /// Freshness SLA of the dataset and its current freshness status
+    #[tracing::instrument(level = "info", name = DatasetMetadata_freshness, skip_all)]
+    async fn freshness(&self, ctx: &Context<'_>) -> Result<DatasetFreshness> {
+        let dataset_freshness_svc = from_catalog_n!(ctx, dyn DatasetFreshnessService);
+        let resolved_dataset = get_dataset(ctx, &self.dataset_handle).await;
+
+        Ok(dataset_freshness_svc
+            .get_freshness(&resolved_dataset)
+            .await?
+            .into())
+    }
 }
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
@@ -8,6 +8,7 @@
 // by the Apache License, Version 2.0.
 
 mod dataset;
+mod dataset_alerts;
 mod dataset_data;
 mod dataset_endpoints;
 mod dataset_env_var;
@@ -17,10 +18,12 @@ mod dataset_flow_runs;
 mod dataset_flow_triggers;
 mod dataset_flows;
 mod dataset_metadata;
+mod dataset_webhooks;
 mod datasets;
 mod metadata_chain;
 
 pub(crate) use dataset::*;
+pub(crate) use dataset_alerts::*;
 pub(crate) use dataset_data::*;
 pub(crate) use dataset_endpoints::*;
 pub(crate) use dataset_env_var::*;
@@ -30,5 +33,6 @@ pub(crate) use dataset_flow_runs::*;
 pub(crate) use dataset_flow_triggers::*;
 pub(crate) use dataset_flows::*;
 pub(crate) use dataset_metadata::*;
+pub(crate) use dataset_webhooks::*;
 pub(crate) use datasets::*;
 pub(crate) use metadata_chain::*;
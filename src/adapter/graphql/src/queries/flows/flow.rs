@@ -130,6 +130,7 @@ impl Flow {
             if let Some(start_condition) = self.flow_state.start_condition.as_ref() {
                 Some(
                     FlowStartCondition::create_from_raw_flow_data(
+                        &self.flow_state.flow_key,
                         start_condition,
                         &self.flow_state.triggers,
                         ctx,
@@ -18,6 +18,7 @@ pub enum FlowConfigurationSnapshot {
     Compaction(FlowConfigurationCompactionRule),
     Ingest(FlowConfigurationIngest),
     Reset(FlowConfigurationReset),
+    Reprocess(FlowConfigurationReprocess),
 }
 
 #[derive(SimpleObject)]
@@ -32,6 +33,9 @@ impl From<fs::FlowConfigurationRule> for FlowConfigurationSnapshot {
         match value {
             fs::FlowConfigurationRule::IngestRule(ingest_rule) => Self::Ingest(ingest_rule.into()),
             fs::FlowConfigurationRule::ResetRule(reset_rule) => Self::Reset(reset_rule.into()),
+            fs::FlowConfigurationRule::ReprocessRule(reprocess_rule) => {
+                Self::Reprocess(reprocess_rule.into())
+            }
             fs::FlowConfigurationRule::CompactionRule(compaction_rule) => {
                 Self::Compaction(FlowConfigurationCompactionRule {
                     compaction_rule: match compaction_rule {
@@ -49,6 +49,7 @@ pub(crate) enum FlowDescriptionDataset {
     ExecuteTransform(FlowDescriptionDatasetExecuteTransform),
     HardCompaction(FlowDescriptionDatasetHardCompaction),
     Reset(FlowDescriptionDatasetReset),
+    Reprocess(FlowDescriptionDatasetReprocess),
 }
 
 #[derive(SimpleObject)]
@@ -83,6 +84,12 @@ pub(crate) struct FlowDescriptionDatasetReset {
     reset_result: Option<FlowDescriptionResetResult>,
 }
 
+#[derive(SimpleObject)]
+pub(crate) struct FlowDescriptionDatasetReprocess {
+    dataset_id: DatasetID<'static>,
+    reprocess_result: Option<FlowDescriptionUpdateResult>,
+}
+
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
 
 #[derive(Union)]
@@ -358,6 +365,20 @@ impl FlowDescriptionBuilder {
                     ),
                 })
             }
+            fs::DatasetFlowType::Reprocess => {
+                let dataset_changes_svc = from_catalog_n!(ctx, dyn DatasetChangesService);
+
+                FlowDescriptionDataset::Reprocess(FlowDescriptionDatasetReprocess {
+                    dataset_id: dataset_key.dataset_id.clone().into(),
+                    reprocess_result: FlowDescriptionUpdateResult::from_maybe_flow_outcome(
+                        flow_state.outcome.as_ref(),
+                        &dataset_key.dataset_id,
+                        dataset_changes_svc.as_ref(),
+                    )
+                    .await
+                    .int_err()?,
+                })
+            }
         })
     }
 }
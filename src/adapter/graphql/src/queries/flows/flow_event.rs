@@ -52,6 +52,7 @@ impl FlowEvent {
             }
             fs::FlowEvent::StartConditionUpdated(e) => {
                 let start_condition = FlowStartCondition::create_from_raw_flow_data(
+                    &flow_state.flow_key,
                     &e.start_condition,
                     &flow_state.triggers[0..e.last_trigger_index],
                     ctx,
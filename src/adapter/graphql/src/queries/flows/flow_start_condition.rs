@@ -7,8 +7,10 @@
 // the Business Source License, use of this software will be governed
 // by the Apache License, Version 2.0.
 
+use std::collections::HashSet;
+
 use chrono::{DateTime, Utc};
-use kamu_core::{DatasetChangesService, DatasetIntervalIncrement};
+use kamu_core::{DatasetChangesService, DatasetIntervalIncrement, DependencyGraphService};
 use kamu_flow_system::{self as fs};
 
 use crate::prelude::*;
@@ -20,11 +22,13 @@ pub(crate) enum FlowStartCondition {
     Schedule(FlowStartConditionSchedule),
     Throttling(FlowStartConditionThrottling),
     Batching(FlowStartConditionBatching),
+    Window(FlowStartConditionWindow),
     Executor(FlowStartConditionExecutor),
 }
 
 impl FlowStartCondition {
     pub async fn create_from_raw_flow_data(
+        flow_key: &fs::FlowKey,
         start_condition: &fs::FlowStartCondition,
         matching_triggers: &[fs::FlowTriggerType],
         ctx: &Context<'_>,
@@ -39,6 +43,7 @@ impl FlowStartCondition {
 
                 // Start from zero increment
                 let mut total_increment = DatasetIntervalIncrement::default();
+                let mut updated_inputs = HashSet::new();
 
                 // TODO: somehow limit dataset traversal to blocks that existed at the time of
                 // flow latest event, as they might have evolved after this state was loaded
@@ -57,17 +62,45 @@ impl FlowStartCondition {
                             )
                             .await
                             .int_err()?;
+                        updated_inputs.insert(dataset_trigger.dataset_id.clone());
                     }
                 }
 
+                // List inputs that still have to be updated, if the rule awaits all of them
+                let mut awaiting_inputs = Vec::new();
+                if b.active_batching_rule.await_all_inputs()
+                    && let fs::FlowKey::Dataset(fk_dataset) = flow_key
+                {
+                    let dependency_graph_service = from_catalog_n!(ctx, dyn DependencyGraphService);
+
+                    use futures::StreamExt;
+                    let input_ids: Vec<_> = dependency_graph_service
+                        .get_upstream_dependencies(&fk_dataset.dataset_id)
+                        .await
+                        .int_err()?
+                        .collect()
+                        .await;
+
+                    awaiting_inputs = input_ids
+                        .into_iter()
+                        .filter(|id| !updated_inputs.contains(id))
+                        .map(Into::into)
+                        .collect();
+                }
+
                 // Finally, present the full picture from condition + computed view results
                 Self::Batching(FlowStartConditionBatching {
                     active_batching_rule: b.active_batching_rule.into(),
                     batching_deadline: b.batching_deadline,
                     accumulated_records_count: total_increment.num_records,
                     watermark_modified: total_increment.updated_watermark.is_some(),
+                    awaiting_inputs,
                 })
             }
+            fs::FlowStartCondition::Window(w) => Self::Window(FlowStartConditionWindow {
+                time_window: w.time_window.into(),
+                wake_up_at: w.wake_up_at,
+            }),
             fs::FlowStartCondition::Executor(e) => Self::Executor(FlowStartConditionExecutor {
                 task_id: e.task_id.into(),
             }),
@@ -103,9 +136,17 @@ pub(crate) struct FlowStartConditionBatching {
     pub batching_deadline: DateTime<Utc>,
     pub accumulated_records_count: u64,
     pub watermark_modified: bool,
+    /// Inputs that still have to be updated before the flow can start
+    pub awaiting_inputs: Vec<DatasetID<'static>>,
     // TODO: we can list all applied input flows, if that is interesting for debugging
 }
 
+#[derive(SimpleObject)]
+pub(crate) struct FlowStartConditionWindow {
+    pub time_window: FlowTriggerTimeWindow,
+    pub wake_up_at: DateTime<Utc>,
+}
+
 #[derive(SimpleObject)]
 pub(crate) struct FlowStartConditionExecutor {
     pub task_id: TaskID,
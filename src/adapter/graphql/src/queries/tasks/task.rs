@@ -8,8 +8,10 @@
 // by the Apache License, Version 2.0.
 
 use chrono::{DateTime, Utc};
+use database_common::PaginationOpts;
 use kamu_task_system as ts;
 
+use super::{TaskLogEntry, TaskLogEntryConnection};
 use crate::prelude::*;
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
@@ -19,8 +21,11 @@ pub struct Task {
     state: ts::TaskState,
 }
 
+#[common_macros::method_names_consts(const_value_prefix = "GQL: ")]
 #[Object]
 impl Task {
+    const DEFAULT_LOGS_PER_PAGE: usize = 100;
+
     #[graphql(skip)]
     pub fn new(state: ts::TaskState) -> Self {
         Self { state }
@@ -36,6 +41,16 @@ impl Task {
         (&self.state.status()).into()
     }
 
+    /// Priority the task was queued with
+    async fn priority(&self) -> TaskPriority {
+        self.state.priority.into()
+    }
+
+    /// Account whose share of workers the task consumes
+    async fn account_id(&self) -> Option<AccountID<'_>> {
+        self.state.account_id.as_ref().map(Into::into)
+    }
+
     /// Whether the task was ordered to be cancelled
     async fn cancellation_requested(&self) -> bool {
         self.state.cancellation_requested
@@ -66,4 +81,37 @@ impl Task {
     async fn finished_at(&self) -> Option<DateTime<Utc>> {
         self.state.finished_at
     }
+
+    /// Log captured while running the task, in the order it was recorded
+    #[tracing::instrument(level = "info", name = Task_logs, skip_all, fields(?page, ?per_page))]
+    async fn logs(
+        &self,
+        ctx: &Context<'_>,
+        page: Option<usize>,
+        per_page: Option<usize>,
+    ) -> Result<TaskLogEntryConnection> {
+        let task_log_store = from_catalog_n!(ctx, dyn ts::TaskLogStore);
+
+        let page = page.unwrap_or(0);
+        let per_page = per_page.unwrap_or(Self::DEFAULT_LOGS_PER_PAGE);
+
+        let listing = task_log_store
+            .get_task_log(
+                self.state.task_id,
+                PaginationOpts {
+                    offset: page * per_page,
+                    limit: per_page,
+                },
+            )
+            .await?;
+
+        let nodes = listing.list.into_iter().map(TaskLogEntry::new).collect();
+
+        Ok(TaskLogEntryConnection::new(
+            nodes,
+            page,
+            per_page,
+            listing.total_count,
+        ))
+    }
 }
@@ -0,0 +1,50 @@
+// Copyright Kamu Data, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use chrono::{DateTime, Utc};
+use kamu_task_system as ts;
+
+use crate::prelude::*;
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[derive(Debug)]
+pub struct TaskLogEntry {
+    entry: ts::TaskLogEntry,
+}
+
+#[Object]
+impl TaskLogEntry {
+    #[graphql(skip)]
+    pub fn new(entry: ts::TaskLogEntry) -> Self {
+        Self { entry }
+    }
+
+    /// Time when the entry was recorded
+    async fn event_time(&self) -> DateTime<Utc> {
+        self.entry.event_time
+    }
+
+    /// Either `task` for messages of the task system itself, or the name of
+    /// a captured log file (e.g. `engine.stderr.txt`)
+    async fn source(&self) -> &String {
+        &self.entry.source
+    }
+
+    /// Single line of the log
+    async fn message(&self) -> &String {
+        &self.entry.message
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+page_based_connection!(TaskLogEntry, TaskLogEntryConnection, TaskLogEntryEdge);
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
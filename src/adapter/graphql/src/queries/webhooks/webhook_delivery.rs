@@ -0,0 +1,90 @@
+// Copyright Kamu Data, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use chrono::{DateTime, Utc};
+use kamu_webhooks as wh;
+
+use crate::prelude::*;
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[derive(Debug)]
+pub struct WebhookDelivery {
+    delivery: wh::WebhookDelivery,
+}
+
+#[Object]
+impl WebhookDelivery {
+    #[graphql(skip)]
+    pub fn new(delivery: wh::WebhookDelivery) -> Self {
+        Self { delivery }
+    }
+
+    /// Unique identifier of the delivery, also sent in the
+    /// `X-Kamu-Webhook-Delivery` header
+    async fn id(&self) -> WebhookDeliveryID {
+        self.delivery.id.into()
+    }
+
+    async fn event_type(&self) -> WebhookEventType {
+        self.delivery.event.event_type.into()
+    }
+
+    /// Time when the event has occurred
+    async fn event_time(&self) -> DateTime<Utc> {
+        self.delivery.event.event_time
+    }
+
+    /// JSON payload sent to the endpoint
+    async fn payload(&self) -> Result<String> {
+        Ok(serde_json::to_string(&self.delivery.event).int_err()?)
+    }
+
+    async fn status(&self) -> WebhookDeliveryStatus {
+        self.delivery.status.into()
+    }
+
+    /// Number of attempts made so far
+    async fn attempts(&self) -> u32 {
+        self.delivery.attempts
+    }
+
+    /// When the next attempt is due, set only while the delivery is pending
+    async fn next_attempt_at(&self) -> Option<DateTime<Utc>> {
+        self.delivery.next_attempt_at
+    }
+
+    /// HTTP status code returned by the endpoint on the last attempt
+    async fn response_status_code(&self) -> Option<u16> {
+        self.delivery.response_status_code
+    }
+
+    /// Transport error or unexpected response of the last attempt
+    async fn last_error(&self) -> Option<&String> {
+        self.delivery.last_error.as_ref()
+    }
+
+    async fn created_at(&self) -> DateTime<Utc> {
+        self.delivery.created_at
+    }
+
+    async fn updated_at(&self) -> DateTime<Utc> {
+        self.delivery.updated_at
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+page_based_connection!(
+    WebhookDelivery,
+    WebhookDeliveryConnection,
+    WebhookDeliveryEdge
+);
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
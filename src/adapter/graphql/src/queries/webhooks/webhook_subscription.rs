@@ -0,0 +1,104 @@
+// Copyright Kamu Data, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use chrono::{DateTime, Utc};
+use database_common::PaginationOpts;
+use kamu_webhooks as wh;
+
+use super::{WebhookDelivery, WebhookDeliveryConnection};
+use crate::prelude::*;
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[derive(Debug)]
+pub struct WebhookSubscription {
+    subscription: wh::WebhookSubscription,
+}
+
+#[common_macros::method_names_consts(const_value_prefix = "GQL: ")]
+#[Object]
+impl WebhookSubscription {
+    const DEFAULT_PER_PAGE: usize = 15;
+
+    #[graphql(skip)]
+    pub fn new(subscription: wh::WebhookSubscription) -> Self {
+        Self { subscription }
+    }
+
+    async fn id(&self) -> WebhookSubscriptionID {
+        self.subscription.id.into()
+    }
+
+    /// Dataset the subscription is limited to, if not set events of all
+    /// datasets of the owner are delivered
+    async fn dataset_id(&self) -> Option<DatasetID> {
+        self.subscription.dataset_id.as_ref().map(Into::into)
+    }
+
+    /// Endpoint the events are posted to
+    async fn target_url(&self) -> String {
+        self.subscription.target_url.to_string()
+    }
+
+    async fn label(&self) -> &String {
+        &self.subscription.label
+    }
+
+    async fn event_types(&self) -> Vec<WebhookEventType> {
+        self.subscription
+            .event_types
+            .iter()
+            .copied()
+            .map(Into::into)
+            .collect()
+    }
+
+    async fn status(&self) -> WebhookSubscriptionStatus {
+        self.subscription.status.into()
+    }
+
+    async fn created_at(&self) -> DateTime<Utc> {
+        self.subscription.created_at
+    }
+
+    /// Log of event deliveries to this endpoint, newest first
+    #[tracing::instrument(level = "info", name = WebhookSubscription_deliveries, skip_all, fields(?page, ?per_page))]
+    async fn deliveries(
+        &self,
+        ctx: &Context<'_>,
+        page: Option<usize>,
+        per_page: Option<usize>,
+    ) -> Result<WebhookDeliveryConnection> {
+        let subscription_service = from_catalog_n!(ctx, dyn wh::WebhookSubscriptionService);
+
+        let page = page.unwrap_or(0);
+        let per_page = per_page.unwrap_or(Self::DEFAULT_PER_PAGE);
+
+        let listing = subscription_service
+            .list_deliveries(
+                &self.subscription.id,
+                PaginationOpts {
+                    offset: page * per_page,
+                    limit: per_page,
+                },
+            )
+            .await?;
+
+        let nodes = listing.list.into_iter().map(WebhookDelivery::new).collect();
+
+        Ok(WebhookDeliveryConnection::new(
+            nodes,
+            page,
+            per_page,
+            listing.total_count,
+        ))
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
@@ -11,6 +11,8 @@ use crate::extensions::*;
 use crate::mutations::*;
 use crate::prelude::*;
 use crate::queries::*;
+use crate::subscriptions::*;
+use crate::AdminGuard;
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
 // Query
@@ -90,14 +92,89 @@ impl Mutation {
     async fn accounts(&self) -> AccountsMut {
         AccountsMut
     }
+
+    /// Admin-related functionality group
+    #[graphql(guard = "AdminGuard::new()")]
+    async fn admin(&self) -> AdminMut {
+        AdminMut
+    }
 }
 
-pub type Schema = async_graphql::Schema<Query, Mutation, EmptySubscription>;
-pub type SchemaBuilder = async_graphql::SchemaBuilder<Query, Mutation, EmptySubscription>;
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+// Subscription
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+pub struct Subscription;
+
+#[async_graphql::Subscription]
+impl Subscription {
+    /// Status transitions of the flow runs of a dataset
+    async fn flow_run_updates(
+        &self,
+        ctx: &Context<'_>,
+        dataset_id: DatasetID<'_>,
+    ) -> async_graphql::Result<impl futures::Stream<Item = FlowRunUpdate>> {
+        let dataset_handle = resolve_subscribed_dataset(ctx, dataset_id).await?;
+        let hub = from_catalog_n!(ctx, GqlEventHub);
+
+        Ok(dataset_events(
+            &hub,
+            dataset_handle.id,
+            |event| match event {
+                GqlEvent::FlowRunUpdated(e) => Some(e.into()),
+                _ => None,
+            },
+        ))
+    }
+
+    /// Progress of the tasks executed on behalf of a dataset
+    async fn task_updates(
+        &self,
+        ctx: &Context<'_>,
+        dataset_id: DatasetID<'_>,
+    ) -> async_graphql::Result<impl futures::Stream<Item = TaskUpdate>> {
+        let dataset_handle = resolve_subscribed_dataset(ctx, dataset_id).await?;
+        let hub = from_catalog_n!(ctx, GqlEventHub);
+
+        Ok(dataset_events(
+            &hub,
+            dataset_handle.id,
+            |event| match event {
+                GqlEvent::TaskUpdated(e) => Some(e.into()),
+                _ => None,
+            },
+        ))
+    }
+
+    /// Moves of the head of a dataset, whether made by flows, push ingest,
+    /// committed events or syncs
+    async fn dataset_head_updates(
+        &self,
+        ctx: &Context<'_>,
+        dataset_id: DatasetID<'_>,
+    ) -> async_graphql::Result<impl futures::Stream<Item = DatasetHeadUpdate>> {
+        let dataset_handle = resolve_subscribed_dataset(ctx, dataset_id).await?;
+        let hub = from_catalog_n!(ctx, GqlEventHub);
+
+        Ok(dataset_events(
+            &hub,
+            dataset_handle.id,
+            |event| match event {
+                GqlEvent::DatasetHeadUpdated(e) => Some(e.into()),
+                _ => None,
+            },
+        ))
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+pub type Schema = async_graphql::Schema<Query, Mutation, Subscription>;
+pub type SchemaBuilder = async_graphql::SchemaBuilder<Query, Mutation, Subscription>;
 
 /// Returns schema builder without any extensions
 pub fn schema_builder() -> SchemaBuilder {
-    Schema::build(Query, Mutation, EmptySubscription)
+    Schema::build(Query, Mutation, Subscription)
 }
 
 /// Returns schema preconfigured with default extensions
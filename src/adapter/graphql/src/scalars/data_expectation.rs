@@ -0,0 +1,229 @@
+// Copyright Kamu Data, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use crate::prelude::*;
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[derive(SimpleObject, Debug, Clone, PartialEq)]
+pub struct DataExpectation {
+    /// Unique name of the expectation within a dataset
+    pub name: String,
+    pub rule: DataExpectationRule,
+    /// What to do with the new data when the expectation is not met
+    pub on_failure: DataExpectationAction,
+}
+
+impl From<kamu_core::DataExpectation> for DataExpectation {
+    fn from(value: kamu_core::DataExpectation) -> Self {
+        Self {
+            name: value.name,
+            rule: value.rule.into(),
+            on_failure: value.on_failure.into(),
+        }
+    }
+}
+
+#[derive(InputObject, Debug)]
+pub struct DataExpectationInput {
+    /// Unique name of the expectation within a dataset
+    pub name: String,
+    pub rule: DataExpectationRuleInput,
+    /// What to do with the new data when the expectation is not met, `FAIL`
+    /// by default
+    pub on_failure: Option<DataExpectationAction>,
+}
+
+impl From<DataExpectationInput> for kamu_core::DataExpectation {
+    fn from(value: DataExpectationInput) -> Self {
+        Self {
+            name: value.name,
+            rule: value.rule.into(),
+            on_failure: value.on_failure.map(Into::into).unwrap_or_default(),
+        }
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[derive(Union, Debug, Clone, PartialEq)]
+pub enum DataExpectationRule {
+    NotNull(DataExpectationRuleNotNull),
+    Unique(DataExpectationRuleUnique),
+    ValueRange(DataExpectationRuleValueRange),
+    RegexMatch(DataExpectationRuleRegexMatch),
+    RowCount(DataExpectationRuleRowCount),
+}
+
+#[derive(OneofObject, Debug)]
+pub enum DataExpectationRuleInput {
+    NotNull(DataExpectationRuleNotNull),
+    Unique(DataExpectationRuleUnique),
+    ValueRange(DataExpectationRuleValueRange),
+    RegexMatch(DataExpectationRuleRegexMatch),
+    RowCount(DataExpectationRuleRowCount),
+}
+
+/// Column must not contain null values
+#[derive(SimpleObject, InputObject, Debug, Clone, PartialEq, Eq)]
+#[graphql(input_name = "DataExpectationRuleNotNullInput")]
+pub struct DataExpectationRuleNotNull {
+    pub column: String,
+}
+
+/// Combination of columns must be unique among the new records
+#[derive(SimpleObject, InputObject, Debug, Clone, PartialEq, Eq)]
+#[graphql(input_name = "DataExpectationRuleUniqueInput")]
+pub struct DataExpectationRuleUnique {
+    pub columns: Vec<String>,
+}
+
+/// Numeric column values must fall within the inclusive range
+#[derive(SimpleObject, InputObject, Debug, Clone, PartialEq)]
+#[graphql(input_name = "DataExpectationRuleValueRangeInput")]
+pub struct DataExpectationRuleValueRange {
+    pub column: String,
+    pub min: Option<f64>,
+    pub max: Option<f64>,
+}
+
+/// String column values must match the regular expression
+#[derive(SimpleObject, InputObject, Debug, Clone, PartialEq, Eq)]
+#[graphql(input_name = "DataExpectationRuleRegexMatchInput")]
+pub struct DataExpectationRuleRegexMatch {
+    pub column: String,
+    pub pattern: String,
+}
+
+/// Number of new records must fall within the inclusive range
+#[derive(SimpleObject, InputObject, Debug, Clone, PartialEq, Eq)]
+#[graphql(input_name = "DataExpectationRuleRowCountInput")]
+pub struct DataExpectationRuleRowCount {
+    pub min: Option<u64>,
+    pub max: Option<u64>,
+}
+
+impl From<kamu_core::DataExpectationRule> for DataExpectationRule {
+    fn from(value: kamu_core::DataExpectationRule) -> Self {
+        use kamu_core::DataExpectationRule as R;
+        match value {
+            R::NotNull { column } => Self::NotNull(DataExpectationRuleNotNull { column }),
+            R::Unique { columns } => Self::Unique(DataExpectationRuleUnique { columns }),
+            R::ValueRange { column, min, max } => {
+                Self::ValueRange(DataExpectationRuleValueRange { column, min, max })
+            }
+            R::RegexMatch { column, pattern } => {
+                Self::RegexMatch(DataExpectationRuleRegexMatch { column, pattern })
+            }
+            R::RowCount { min, max } => Self::RowCount(DataExpectationRuleRowCount { min, max }),
+        }
+    }
+}
+
+impl From<DataExpectationRuleInput> for kamu_core::DataExpectationRule {
+    fn from(value: DataExpectationRuleInput) -> Self {
+        use DataExpectationRuleInput as R;
+        match value {
+            R::NotNull(r) => Self::NotNull { column: r.column },
+            R::Unique(r) => Self::Unique { columns: r.columns },
+            R::ValueRange(r) => Self::ValueRange {
+                column: r.column,
+                min: r.min,
+                max: r.max,
+            },
+            R::RegexMatch(r) => Self::RegexMatch {
+                column: r.column,
+                pattern: r.pattern,
+            },
+            R::RowCount(r) => Self::RowCount {
+                min: r.min,
+                max: r.max,
+            },
+        }
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[derive(Enum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DataExpectationAction {
+    /// Reject the whole commit
+    Fail,
+    /// Exclude violating records from the commit (row-level rules during
+    /// ingest only)
+    Quarantine,
+    /// Commit the data as is and record the violation in the report
+    Warn,
+}
+
+impl From<kamu_core::DataExpectationAction> for DataExpectationAction {
+    fn from(value: kamu_core::DataExpectationAction) -> Self {
+        match value {
+            kamu_core::DataExpectationAction::Fail => Self::Fail,
+            kamu_core::DataExpectationAction::Quarantine => Self::Quarantine,
+            kamu_core::DataExpectationAction::Warn => Self::Warn,
+        }
+    }
+}
+
+impl From<DataExpectationAction> for kamu_core::DataExpectationAction {
+    fn from(value: DataExpectationAction) -> Self {
+        match value {
+            DataExpectationAction::Fail => Self::Fail,
+            DataExpectationAction::Quarantine => Self::Quarantine,
+            DataExpectationAction::Warn => Self::Warn,
+        }
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[derive(SimpleObject, Debug, Clone, PartialEq, Eq)]
+pub struct DataExpectationsReport {
+    pub results: Vec<DataExpectationResult>,
+    /// Number of records that were excluded from the commit
+    pub num_quarantined: u64,
+    /// Physical hash of the Parquet file in the data repository holding the
+    /// excluded records
+    pub quarantined_data: Option<Multihash<'static>>,
+}
+
+impl From<kamu_core::DataExpectationsReport> for DataExpectationsReport {
+    fn from(value: kamu_core::DataExpectationsReport) -> Self {
+        Self {
+            results: value.results.into_iter().map(Into::into).collect(),
+            num_quarantined: value.num_quarantined,
+            quarantined_data: value.quarantined_data.map(Into::into),
+        }
+    }
+}
+
+#[derive(SimpleObject, Debug, Clone, PartialEq, Eq)]
+pub struct DataExpectationResult {
+    pub name: String,
+    /// Action that was applied, which may differ from the declared one when
+    /// quarantine was not applicable
+    pub action: DataExpectationAction,
+    pub passed: bool,
+    /// Number of records violating the expectation
+    pub num_violations: u64,
+}
+
+impl From<kamu_core::DataExpectationResult> for DataExpectationResult {
+    fn from(value: kamu_core::DataExpectationResult) -> Self {
+        Self {
+            name: value.name,
+            action: value.action.into(),
+            passed: value.passed,
+            num_violations: value.num_violations,
+        }
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
@@ -0,0 +1,94 @@
+// Copyright Kamu Data, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use chrono::{DateTime, Utc};
+
+use crate::prelude::*;
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[derive(SimpleObject)]
+pub struct DatasetFreshness {
+    /// Freshness SLA declared on the dataset, if any
+    pub sla: Option<DatasetFreshnessSla>,
+    pub status: DatasetFreshnessStatus,
+    /// System time of the last block that brought new data into the dataset
+    pub last_updated_at: Option<DateTime<Utc>>,
+    /// Last recorded watermark
+    pub watermark: Option<DateTime<Utc>>,
+}
+
+impl From<kamu_core::DatasetFreshness> for DatasetFreshness {
+    fn from(value: kamu_core::DatasetFreshness) -> Self {
+        Self {
+            sla: value.sla.map(Into::into),
+            status: value.status.into(),
+            last_updated_at: value.last_updated_at,
+            watermark: value.watermark,
+        }
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[derive(SimpleObject)]
+pub struct DatasetFreshnessSla {
+    /// Maximal time allowed to pass since new data was last added
+    pub max_update_interval: TimeDelta,
+    /// Maximal allowed lag of the watermark behind the current time
+    pub max_watermark_lag: Option<TimeDelta>,
+}
+
+impl From<kamu_core::DatasetFreshnessSla> for DatasetFreshnessSla {
+    fn from(value: kamu_core::DatasetFreshnessSla) -> Self {
+        Self {
+            max_update_interval: value.max_update_interval.into(),
+            max_watermark_lag: value.max_watermark_lag.map(Into::into),
+        }
+    }
+}
+
+#[derive(InputObject)]
+pub struct DatasetFreshnessSlaInput {
+    /// Maximal time allowed to pass since new data was last added
+    pub max_update_interval: TimeDeltaInput,
+    /// Maximal allowed lag of the watermark behind the current time
+    pub max_watermark_lag: Option<TimeDeltaInput>,
+}
+
+impl From<DatasetFreshnessSlaInput> for kamu_core::DatasetFreshnessSla {
+    fn from(value: DatasetFreshnessSlaInput) -> Self {
+        Self {
+            max_update_interval: value.max_update_interval.into(),
+            max_watermark_lag: value.max_watermark_lag.map(Into::into),
+        }
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[derive(Enum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DatasetFreshnessStatus {
+    /// Dataset does not declare a freshness SLA
+    Undefined,
+    Fresh,
+    Stale,
+}
+
+impl From<kamu_core::FreshnessStatus> for DatasetFreshnessStatus {
+    fn from(value: kamu_core::FreshnessStatus) -> Self {
+        match value {
+            kamu_core::FreshnessStatus::Undefined => Self::Undefined,
+            kamu_core::FreshnessStatus::Fresh => Self::Fresh,
+            kamu_core::FreshnessStatus::Stale => Self::Stale,
+        }
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
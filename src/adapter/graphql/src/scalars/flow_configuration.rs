@@ -7,12 +7,14 @@
 // the Business Source License, use of this software will be governed
 // by the Apache License, Version 2.0.
 
+use kamu_core::ReprocessStart;
 use kamu_flow_system::{
     CompactionRule,
     CompactionRuleFull,
     CompactionRuleMetadataOnly,
     FlowConfigurationRule,
     IngestRule,
+    ReprocessRule,
     ResetRule,
 };
 use odf::dataset::MetadataChainExt as _;
@@ -27,11 +29,12 @@ pub struct FlowConfiguration {
     pub compaction: Option<FlowConfigurationCompaction>,
     pub ingest: Option<FlowConfigurationIngest>,
     pub reset: Option<FlowConfigurationReset>,
+    pub reprocess: Option<FlowConfigurationReprocess>,
 }
 
 impl From<kamu_flow_system::FlowConfigurationState> for FlowConfiguration {
     fn from(value: kamu_flow_system::FlowConfigurationState) -> Self {
-        let (compaction, ingest, reset) = match value.rule {
+        let (compaction, ingest, reset, reprocess) = match value.rule {
             FlowConfigurationRule::CompactionRule(compaction_rule) => {
                 let compaction = match compaction_rule {
                     CompactionRule::Full(full_rule) => {
@@ -42,18 +45,24 @@ impl From<kamu_flow_system::FlowConfigurationState> for FlowConfiguration {
                     ),
                 };
 
-                (compaction, None, None)
+                (compaction, None, None, None)
             }
             FlowConfigurationRule::IngestRule(ingest_rule) => {
-                (None, Some(ingest_rule.into()), None)
+                (None, Some(ingest_rule.into()), None, None)
+            }
+            FlowConfigurationRule::ResetRule(reset_rule) => {
+                (None, None, Some(reset_rule.into()), None)
+            }
+            FlowConfigurationRule::ReprocessRule(reprocess_rule) => {
+                (None, None, None, Some(reprocess_rule.into()))
             }
-            FlowConfigurationRule::ResetRule(reset_rule) => (None, None, Some(reset_rule.into())),
         };
 
         Self {
             compaction,
             ingest,
             reset,
+            reprocess,
         }
     }
 }
@@ -117,6 +126,36 @@ impl From<ResetRule> for FlowConfigurationReset {
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
 
+#[derive(SimpleObject, PartialEq, Eq)]
+pub struct FlowConfigurationReprocess {
+    /// Block to recompute the output from
+    pub start_block_hash: Option<Multihash<'static>>,
+    /// Output record offset to recompute the output from
+    pub start_offset: Option<u64>,
+    /// Transform replacing the current one
+    pub new_transform: Option<Transform>,
+    /// Columns identifying a record when comparing recomputed output
+    pub primary_key: Vec<String>,
+}
+
+impl From<ReprocessRule> for FlowConfigurationReprocess {
+    fn from(value: ReprocessRule) -> Self {
+        let (start_block_hash, start_offset) = match value.start {
+            Some(ReprocessStart::Block(block_hash)) => (Some(block_hash.into()), None),
+            Some(ReprocessStart::Offset(offset)) => (None, Some(offset)),
+            None => (None, None),
+        };
+        Self {
+            start_block_hash,
+            start_offset,
+            new_transform: value.new_transform.map(Into::into),
+            primary_key: value.primary_key,
+        }
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
 #[derive(Union, PartialEq, Eq)]
 pub enum FlowConfigurationCompaction {
     Full(CompactionFull),
@@ -160,6 +199,7 @@ pub enum FlowRunConfiguration {
     Compaction(CompactionConditionInput),
     Ingest(IngestConditionInput),
     Reset(ResetConditionInput),
+    Reprocess(ReprocessConditionInput),
 }
 
 #[derive(OneofObject)]
@@ -200,6 +240,91 @@ impl From<ResetConditionInput> for FlowRunConfiguration {
     }
 }
 
+#[derive(InputObject)]
+pub struct ReprocessConditionInput {
+    /// Point of history to recompute the output from, the entire history is
+    /// recomputed if not specified
+    pub start: Option<ReprocessStartInput>,
+    /// Transform replacing the current one, if not specified the current
+    /// transform is executed again
+    pub new_transform: Option<ReprocessTransformInput>,
+    /// Columns identifying a record. When specified, changed records are
+    /// committed as corrections, otherwise as retractions followed by appends.
+    #[graphql(default)]
+    pub primary_key: Vec<String>,
+}
+
+#[derive(OneofObject)]
+pub enum ReprocessStartInput {
+    Block(Multihash<'static>),
+    Offset(u64),
+}
+
+#[derive(InputObject)]
+pub struct ReprocessTransformInput {
+    pub engine: String,
+    pub queries: Vec<ReprocessSqlQueryStepInput>,
+}
+
+#[derive(InputObject)]
+pub struct ReprocessSqlQueryStepInput {
+    pub alias: Option<String>,
+    pub query: String,
+}
+
+impl ReprocessConditionInput {
+    fn try_into_rule(&self) -> Result<ReprocessRule, FlowInvalidRunConfigurations> {
+        let new_transform = if let Some(transform_input) = &self.new_transform {
+            let num_output_queries = transform_input
+                .queries
+                .iter()
+                .filter(|q| q.alias.is_none())
+                .count();
+            if num_output_queries != 1 {
+                return Err(FlowInvalidRunConfigurations {
+                    error: "Transform must have exactly one query without an alias".to_string(),
+                });
+            }
+
+            Some(odf::metadata::Transform::Sql(odf::metadata::TransformSql {
+                engine: transform_input.engine.clone(),
+                version: None,
+                query: None,
+                queries: Some(
+                    transform_input
+                        .queries
+                        .iter()
+                        .map(|q| odf::metadata::SqlQueryStep {
+                            alias: q.alias.clone(),
+                            query: q.query.clone(),
+                        })
+                        .collect(),
+                ),
+                temporal_tables: None,
+            }))
+        } else {
+            None
+        };
+
+        Ok(ReprocessRule {
+            start: self.start.as_ref().map(|start| match start {
+                ReprocessStartInput::Block(block_hash) => {
+                    ReprocessStart::Block(block_hash.clone().into())
+                }
+                ReprocessStartInput::Offset(offset) => ReprocessStart::Offset(*offset),
+            }),
+            new_transform,
+            primary_key: self.primary_key.clone(),
+        })
+    }
+}
+
+impl From<ReprocessConditionInput> for FlowRunConfiguration {
+    fn from(value: ReprocessConditionInput) -> Self {
+        Self::Reprocess(value)
+    }
+}
+
 #[derive(OneofObject, Copy, Clone)]
 pub enum CompactionConditionInput {
     Full(CompactionConditionFull),
@@ -359,6 +484,25 @@ impl FlowRunConfiguration {
                     recursive: false,
                 })));
             }
+            DatasetFlowType::Reprocess => {
+                if let Some(flow_run_configuration) = flow_run_configuration_maybe {
+                    if let Self::Reprocess(reprocess_input) = flow_run_configuration {
+                        return Ok(Some(FlowConfigurationRule::ReprocessRule(
+                            reprocess_input.try_into_rule()?,
+                        )));
+                    }
+                    return Err(FlowInvalidRunConfigurations {
+                        error: "Incompatible flow run configuration and dataset flow type"
+                            .to_string(),
+                    });
+                }
+                // Recompute the entire history with the current transform by default
+                return Ok(Some(FlowConfigurationRule::ReprocessRule(ReprocessRule {
+                    start: None,
+                    new_transform: None,
+                    primary_key: Vec::new(),
+                })));
+            }
         }
         Ok(None)
     }
@@ -383,6 +527,11 @@ impl FlowRunConfiguration {
                     return Ok(());
                 }
             }
+            Self::Reprocess(_) => {
+                if flow_type == DatasetFlowType::Reprocess {
+                    return Ok(());
+                }
+            }
         }
         Err(FlowTypeIsNotSupported)
     }
@@ -105,6 +105,7 @@ pub enum DatasetFlowType {
     ExecuteTransform,
     HardCompaction,
     Reset,
+    Reprocess,
 }
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
@@ -7,7 +7,14 @@
 // the Business Source License, use of this software will be governed
 // by the Apache License, Version 2.0.
 
-use kamu_flow_system::{BatchingRule, FlowTriggerRule, Schedule, ScheduleCron, ScheduleTimeDelta};
+use kamu_flow_system::{
+    BatchingRule,
+    FlowTriggerRule,
+    Schedule,
+    ScheduleCron,
+    ScheduleTimeDelta,
+    TimeWindow,
+};
 
 use crate::mutations::{FlowInvalidTriggerInputError, FlowTypeIsNotSupported};
 use crate::prelude::*;
@@ -40,6 +47,10 @@ pub enum FlowTriggerScheduleRule {
 pub struct FlowTriggerBatchingRule {
     pub min_records_to_await: u64,
     pub max_batching_interval: TimeDelta,
+    /// Whether every input dataset has to be updated before the flow starts
+    pub await_all_inputs: bool,
+    /// Daily time window in which the flow is allowed to start
+    pub time_window: Option<FlowTriggerTimeWindow>,
 }
 
 impl From<BatchingRule> for FlowTriggerBatchingRule {
@@ -47,10 +58,36 @@ impl From<BatchingRule> for FlowTriggerBatchingRule {
         Self {
             min_records_to_await: value.min_records_to_await(),
             max_batching_interval: (*value.max_batching_interval()).into(),
+            await_all_inputs: value.await_all_inputs(),
+            time_window: value.time_window().map(|w| (*w).into()),
         }
     }
 }
 
+#[derive(SimpleObject, PartialEq, Eq)]
+pub struct FlowTriggerTimeWindow {
+    /// Opening time in `HH:MM` format
+    pub start: String,
+    /// Closing time in `HH:MM` format
+    pub end: String,
+    /// IANA time zone the window is evaluated in
+    pub time_zone: String,
+}
+
+impl From<TimeWindow> for FlowTriggerTimeWindow {
+    fn from(value: TimeWindow) -> Self {
+        Self {
+            start: value.start().format(TIME_WINDOW_FORMAT).to_string(),
+            end: value.end().format(TIME_WINDOW_FORMAT).to_string(),
+            time_zone: value
+                .time_zone()
+                .map_or_else(|| "UTC".to_string(), |tz| tz.to_string()),
+        }
+    }
+}
+
+const TIME_WINDOW_FORMAT: &str = "%H:%M";
+
 impl From<kamu_flow_system::FlowTriggerState> for FlowTrigger {
     fn from(value: kamu_flow_system::FlowTriggerState) -> Self {
         Self {
@@ -82,12 +119,61 @@ pub enum ScheduleInput {
     TimeDelta(TimeDeltaInput),
     /// Supported CRON syntax: min hour dayOfMonth month dayOfWeek
     Cron5ComponentExpression(String),
+    /// CRON expression evaluated in a specific time zone
+    Cron(CronInput),
+}
+
+#[derive(InputObject)]
+pub struct CronInput {
+    /// Supported CRON syntax: min hour dayOfMonth month dayOfWeek
+    pub cron_5component_expression: String,
+    /// IANA time zone name, e.g. `Europe/Berlin`
+    pub time_zone: String,
 }
 
 #[derive(InputObject)]
 pub struct BatchingInput {
     pub min_records_to_await: u64,
     pub max_batching_interval: TimeDeltaInput,
+    /// Wait until every input dataset is updated since the last run
+    #[graphql(default)]
+    pub await_all_inputs: bool,
+    /// Only start the flow within this daily time window
+    pub time_window: Option<TimeWindowInput>,
+}
+
+#[derive(InputObject)]
+pub struct TimeWindowInput {
+    /// Opening time in `HH:MM` format
+    pub start: String,
+    /// Closing time in `HH:MM` format, may be earlier than the opening time
+    /// for windows spanning over midnight
+    pub end: String,
+    /// IANA time zone name, e.g. `Europe/Berlin`, UTC if not specified
+    pub time_zone: Option<String>,
+}
+
+impl TryFrom<TimeWindowInput> for TimeWindow {
+    type Error = FlowInvalidTriggerInputError;
+
+    fn try_from(value: TimeWindowInput) -> std::result::Result<Self, Self::Error> {
+        let parse = |s: &str| {
+            chrono::NaiveTime::parse_from_str(s, TIME_WINDOW_FORMAT).map_err(|_| {
+                FlowInvalidTriggerInputError {
+                    reason: format!("Time {s} is invalid, expected HH:MM format"),
+                }
+            })
+        };
+
+        let time_window = TimeWindow::new_checked(parse(&value.start)?, parse(&value.end)?);
+        match value.time_zone {
+            None => time_window,
+            Some(time_zone) => time_window.and_then(|w| w.in_time_zone(&time_zone)),
+        }
+        .map_err(|e| FlowInvalidTriggerInputError {
+            reason: e.to_string(),
+        })
+    }
 }
 
 #[derive(InputObject)]
@@ -125,12 +211,17 @@ impl From<&TimeDeltaInput> for chrono::Duration {
 #[derive(SimpleObject, PartialEq, Eq)]
 pub struct Cron5ComponentExpression {
     pub cron_5component_expression: String,
+    /// IANA time zone the expression is evaluated in
+    pub time_zone: String,
 }
 
 impl From<ScheduleCron> for Cron5ComponentExpression {
     fn from(value: ScheduleCron) -> Self {
         Self {
             cron_5component_expression: value.source_5component_cron_expression,
+            time_zone: value
+                .time_zone
+                .map_or_else(|| "UTC".to_string(), |tz| tz.to_string()),
         }
     }
 }
@@ -236,6 +327,15 @@ impl TryFrom<FlowTriggerInput> for FlowTriggerRule {
                             reason: err.to_string(),
                         })?
                     }
+                    ScheduleInput::Cron(cron_input) => {
+                        Schedule::try_from_5component_cron_expression_in_time_zone(
+                            &cron_input.cron_5component_expression,
+                            &cron_input.time_zone,
+                        )
+                        .map_err(|err| Self::Error {
+                            reason: err.to_string(),
+                        })?
+                    }
                 };
                 Ok(FlowTriggerRule::Schedule(schedule_rule))
             }
@@ -251,7 +351,15 @@ impl TryFrom<FlowTriggerInput> for FlowTriggerRule {
                         })
                     }
                 };
-                Ok(FlowTriggerRule::Batching(batching_rule))
+                let time_window = batching_input
+                    .time_window
+                    .map(TimeWindow::try_from)
+                    .transpose()?;
+                Ok(FlowTriggerRule::Batching(
+                    batching_rule
+                        .with_await_all_inputs(batching_input.await_all_inputs)
+                        .with_time_window(time_window),
+                ))
             }
         }
     }
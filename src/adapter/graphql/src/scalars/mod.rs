@@ -10,10 +10,12 @@
 mod access_token;
 mod account;
 mod data_batch;
+mod data_expectation;
 mod data_query;
 mod data_schema;
 mod dataset_endpoints;
 mod dataset_env_var;
+mod dataset_freshness;
 mod dataset_id_name;
 mod dataset_metadata;
 mod dataset_visibility;
@@ -30,14 +32,17 @@ mod os_path;
 mod pagination;
 mod task_id;
 mod task_status_outcome;
+mod webhook;
 
 pub(crate) use access_token::*;
 pub(crate) use account::*;
 pub(crate) use data_batch::*;
+pub(crate) use data_expectation::*;
 pub(crate) use data_query::*;
 pub(crate) use data_schema::*;
 pub(crate) use dataset_endpoints::*;
 pub(crate) use dataset_env_var::*;
+pub(crate) use dataset_freshness::*;
 pub(crate) use dataset_id_name::*;
 pub(crate) use dataset_metadata::*;
 pub(crate) use dataset_visibility::*;
@@ -54,6 +59,7 @@ pub(crate) use os_path::*;
 pub(crate) use pagination::*;
 pub(crate) use task_id::*;
 pub(crate) use task_status_outcome::*;
+pub(crate) use webhook::*;
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
 
@@ -36,6 +36,29 @@ impl From<&ts::TaskStatus> for TaskStatus {
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
 
+/// Determines the order in which queued tasks of the same account are taken
+#[derive(Enum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TaskPriority {
+    /// Task was triggered by an update of an input dataset
+    Cascaded,
+    /// Task was triggered by a schedule or by a push of new data
+    Scheduled,
+    /// Task was requested explicitly by a user
+    Manual,
+}
+
+impl From<ts::TaskPriority> for TaskPriority {
+    fn from(v: ts::TaskPriority) -> Self {
+        match v {
+            ts::TaskPriority::Cascaded => Self::Cascaded,
+            ts::TaskPriority::Scheduled => Self::Scheduled,
+            ts::TaskPriority::Manual => Self::Manual,
+        }
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
 /// Describes a certain final outcome of the task
 #[derive(Enum, Debug, Clone, Copy, PartialEq, Eq)]
 pub enum TaskOutcome {
@@ -0,0 +1,59 @@
+// Copyright Kamu Data, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use crate::prelude::*;
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+simple_string_scalar!(WebhookSubscriptionID, uuid::Uuid, try_parse);
+simple_string_scalar!(WebhookDeliveryID, uuid::Uuid, try_parse);
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[derive(Enum, Debug, Clone, Copy, PartialEq, Eq)]
+#[graphql(remote = "kamu_webhooks::WebhookEventType")]
+pub enum WebhookEventType {
+    DatasetCreated,
+    DatasetDeleted,
+    /// New data was committed to the dataset by an update flow
+    DatasetUpdated,
+    FlowRunning,
+    FlowSucceeded,
+    FlowFailed,
+    /// Flow was aborted while running or cancelled before it started
+    FlowAborted,
+    TaskRunning,
+    TaskFinished,
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[derive(Enum, Debug, Clone, Copy, PartialEq, Eq)]
+#[graphql(remote = "kamu_webhooks::WebhookSubscriptionStatus")]
+pub enum WebhookSubscriptionStatus {
+    /// Events are being delivered to the endpoint
+    Enabled,
+    /// Events are not recorded and pending deliveries are dropped
+    Paused,
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[derive(Enum, Debug, Clone, Copy, PartialEq, Eq)]
+#[graphql(remote = "kamu_webhooks::WebhookDeliveryStatus")]
+pub enum WebhookDeliveryStatus {
+    /// Delivery is waiting for the first attempt or a retry
+    Pending,
+    /// Endpoint acknowledged the event
+    Succeeded,
+    /// All attempts to deliver the event were exhausted
+    Failed,
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
@@ -0,0 +1,317 @@
+// Copyright Kamu Data, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use chrono::{DateTime, Utc};
+use dill::*;
+use internal_error::InternalError;
+use kamu_datasets::{DatasetReferenceMessage, MESSAGE_PRODUCER_KAMU_DATASET_REFERENCE_SERVICE};
+use kamu_flow_system::{
+    self as fs,
+    FlowKey,
+    FlowOutcome,
+    FlowProgressMessage,
+    FlowQueryService,
+    GetFlowError,
+};
+use kamu_flow_system_services::MESSAGE_PRODUCER_KAMU_FLOW_PROGRESS_SERVICE;
+use kamu_task_system::{
+    self as ts,
+    GetTaskError,
+    TaskProgressMessage,
+    TaskScheduler,
+    MESSAGE_PRODUCER_KAMU_TASK_AGENT,
+};
+use messaging_outbox::{
+    MessageConsumer,
+    MessageConsumerMeta,
+    MessageConsumerT,
+    MessageDeliveryMechanism,
+    OutboxPostCommitActions,
+};
+use tokio::sync::broadcast;
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+pub const MESSAGE_CONSUMER_KAMU_GQL_EVENT_HUB: &str = "dev.kamu.adapter.graphql.GqlEventHub";
+
+/// Number of events a slow subscriber may fall behind before it starts
+/// missing them
+const EVENT_HUB_CAPACITY: usize = 1024;
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+/// Fans out flow, task and dataset head changes to the active GQL
+/// subscriptions.
+///
+/// Events are enriched with everything subscribers need while the outbox
+/// transaction is still open, so streaming them does not touch the database,
+/// but are published only once that transaction is committed.
+pub struct GqlEventHub {
+    sender: broadcast::Sender<GqlEvent>,
+}
+
+#[component(pub)]
+#[scope(Singleton)]
+#[interface(dyn MessageConsumer)]
+#[interface(dyn MessageConsumerT<FlowProgressMessage>)]
+#[interface(dyn MessageConsumerT<TaskProgressMessage>)]
+#[interface(dyn MessageConsumerT<DatasetReferenceMessage>)]
+#[meta(MessageConsumerMeta {
+    consumer_name: MESSAGE_CONSUMER_KAMU_GQL_EVENT_HUB,
+    feeding_producers: &[
+        MESSAGE_PRODUCER_KAMU_FLOW_PROGRESS_SERVICE,
+        MESSAGE_PRODUCER_KAMU_TASK_AGENT,
+        MESSAGE_PRODUCER_KAMU_DATASET_REFERENCE_SERVICE,
+    ],
+    delivery: MessageDeliveryMechanism::Transactional,
+})]
+impl GqlEventHub {
+    pub fn new() -> Self {
+        let (sender, _) = broadcast::channel(EVENT_HUB_CAPACITY);
+        Self { sender }
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<GqlEvent> {
+        self.sender.subscribe()
+    }
+
+    pub fn publish(&self, event: GqlEvent) {
+        // An error only means there are no subscribers at the moment
+        let _ = self.sender.send(event);
+    }
+
+    fn publish_after_commit(&self, target_catalog: &Catalog, event: GqlEvent) {
+        match target_catalog.get_one::<OutboxPostCommitActions>() {
+            Ok(post_commit_actions) => {
+                let sender = self.sender.clone();
+                post_commit_actions.defer(move || {
+                    let _ = sender.send(event);
+                });
+            }
+            // Messages delivered outside of the outbox agent (e.g. immediately
+            // in tests) have no transaction of their own to wait for
+            Err(_) => self.publish(event),
+        }
+    }
+
+    async fn handle_flow_progress_message(
+        &self,
+        target_catalog: &Catalog,
+        message: &FlowProgressMessage,
+    ) -> Result<(), InternalError> {
+        let (flow_id, event_time) = match message {
+            FlowProgressMessage::Scheduled(m) => (m.flow_id, m.event_time),
+            FlowProgressMessage::Running(m) => (m.flow_id, m.event_time),
+            FlowProgressMessage::Finished(m) => (m.flow_id, m.event_time),
+            FlowProgressMessage::Cancelled(m) => (m.flow_id, m.event_time),
+        };
+
+        let flow_query_service = target_catalog.get_one::<dyn FlowQueryService>().unwrap();
+        let flow = match flow_query_service.get_flow(flow_id).await {
+            Ok(flow) => flow,
+            Err(GetFlowError::NotFound(_)) => return Ok(()),
+            Err(GetFlowError::Internal(e)) => return Err(e),
+        };
+
+        // System flows are not visible via dataset subscriptions
+        let FlowKey::Dataset(flow_key) = flow.flow_key else {
+            return Ok(());
+        };
+
+        let (status, outcome) = match message {
+            FlowProgressMessage::Scheduled(_) => (fs::FlowStatus::Waiting, None),
+            FlowProgressMessage::Running(_) => (fs::FlowStatus::Running, None),
+            FlowProgressMessage::Finished(m) => (fs::FlowStatus::Finished, Some(m.outcome.clone())),
+            FlowProgressMessage::Cancelled(_) => {
+                (fs::FlowStatus::Finished, Some(FlowOutcome::Aborted))
+            }
+        };
+
+        self.publish_after_commit(
+            target_catalog,
+            GqlEvent::FlowRunUpdated(FlowRunUpdatedEvent {
+                event_time,
+                flow_id,
+                dataset_id: flow_key.dataset_id,
+                flow_type: flow_key.flow_type,
+                status,
+                outcome,
+            }),
+        );
+
+        Ok(())
+    }
+
+    async fn handle_task_progress_message(
+        &self,
+        target_catalog: &Catalog,
+        message: &TaskProgressMessage,
+    ) -> Result<(), InternalError> {
+        let (task_id, event_time, status, outcome) = match message {
+            TaskProgressMessage::Running(m) => {
+                (m.task_id, m.event_time, ts::TaskStatus::Running, None)
+            }
+            TaskProgressMessage::Finished(m) => (
+                m.task_id,
+                m.event_time,
+                ts::TaskStatus::Finished,
+                Some(m.outcome.clone()),
+            ),
+        };
+
+        let task_scheduler = target_catalog.get_one::<dyn TaskScheduler>().unwrap();
+        let dataset_id = match task_scheduler.get_task(task_id).await {
+            Ok(task) => task.logical_plan.dataset_id().cloned(),
+            Err(GetTaskError::NotFound(_)) => None,
+            Err(GetTaskError::Internal(e)) => return Err(e),
+        };
+
+        // Tasks that are not related to a dataset can't be subscribed to
+        let Some(dataset_id) = dataset_id else {
+            return Ok(());
+        };
+
+        self.publish_after_commit(
+            target_catalog,
+            GqlEvent::TaskUpdated(TaskUpdatedEvent {
+                event_time,
+                task_id,
+                dataset_id,
+                status,
+                outcome,
+            }),
+        );
+
+        Ok(())
+    }
+}
+
+impl Default for GqlEventHub {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+impl MessageConsumer for GqlEventHub {}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[async_trait::async_trait]
+impl MessageConsumerT<FlowProgressMessage> for GqlEventHub {
+    #[tracing::instrument(level = "debug", skip_all, name = "GqlEventHub[FlowProgressMessage]")]
+    async fn consume_message(
+        &self,
+        target_catalog: &Catalog,
+        message: &FlowProgressMessage,
+    ) -> Result<(), InternalError> {
+        tracing::debug!(received_message = ?message, "Received flow progress message");
+
+        self.handle_flow_progress_message(target_catalog, message)
+            .await
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[async_trait::async_trait]
+impl MessageConsumerT<TaskProgressMessage> for GqlEventHub {
+    #[tracing::instrument(level = "debug", skip_all, name = "GqlEventHub[TaskProgressMessage]")]
+    async fn consume_message(
+        &self,
+        target_catalog: &Catalog,
+        message: &TaskProgressMessage,
+    ) -> Result<(), InternalError> {
+        tracing::debug!(received_message = ?message, "Received task progress message");
+
+        self.handle_task_progress_message(target_catalog, message)
+            .await
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[async_trait::async_trait]
+impl MessageConsumerT<DatasetReferenceMessage> for GqlEventHub {
+    #[tracing::instrument(
+        level = "debug",
+        skip_all,
+        name = "GqlEventHub[DatasetReferenceMessage]"
+    )]
+    async fn consume_message(
+        &self,
+        target_catalog: &Catalog,
+        message: &DatasetReferenceMessage,
+    ) -> Result<(), InternalError> {
+        tracing::debug!(received_message = ?message, "Received dataset reference message");
+
+        match message {
+            DatasetReferenceMessage::Updated(m) => self.publish_after_commit(
+                target_catalog,
+                GqlEvent::DatasetHeadUpdated(DatasetHeadUpdatedEvent {
+                    event_time: m.event_time,
+                    dataset_id: m.dataset_id.clone(),
+                    old_head: m.maybe_prev_block_hash.clone(),
+                    new_head: m.new_block_hash.clone(),
+                }),
+            ),
+        }
+
+        Ok(())
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[derive(Debug, Clone)]
+pub enum GqlEvent {
+    FlowRunUpdated(FlowRunUpdatedEvent),
+    TaskUpdated(TaskUpdatedEvent),
+    DatasetHeadUpdated(DatasetHeadUpdatedEvent),
+}
+
+impl GqlEvent {
+    pub fn dataset_id(&self) -> &odf::DatasetID {
+        match self {
+            Self::FlowRunUpdated(e) => &e.dataset_id,
+            Self::TaskUpdated(e) => &e.dataset_id,
+            Self::DatasetHeadUpdated(e) => &e.dataset_id,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct FlowRunUpdatedEvent {
+    pub event_time: DateTime<Utc>,
+    pub flow_id: fs::FlowID,
+    pub dataset_id: odf::DatasetID,
+    pub flow_type: fs::DatasetFlowType,
+    pub status: fs::FlowStatus,
+    pub outcome: Option<FlowOutcome>,
+}
+
+#[derive(Debug, Clone)]
+pub struct TaskUpdatedEvent {
+    pub event_time: DateTime<Utc>,
+    pub task_id: ts::TaskID,
+    pub dataset_id: odf::DatasetID,
+    pub status: ts::TaskStatus,
+    pub outcome: Option<ts::TaskOutcome>,
+}
+
+#[derive(Debug, Clone)]
+pub struct DatasetHeadUpdatedEvent {
+    pub event_time: DateTime<Utc>,
+    pub dataset_id: odf::DatasetID,
+    pub old_head: Option<odf::Multihash>,
+    pub new_head: odf::Multihash,
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
@@ -0,0 +1,190 @@
+// Copyright Kamu Data, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::sync::Arc;
+
+use chrono::{DateTime, Utc};
+use database_common::DatabaseTransactionRunner;
+use futures::{Stream, StreamExt};
+use kamu_datasets::{ViewDatasetUseCase, ViewDatasetUseCaseError};
+use kamu_flow_system as fs;
+use tokio::sync::broadcast;
+
+use super::{
+    DatasetHeadUpdatedEvent,
+    FlowRunUpdatedEvent,
+    GqlEvent,
+    GqlEventHub,
+    TaskUpdatedEvent,
+};
+use crate::prelude::*;
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+/// Resolves the dataset the subscription is interested in, applying the same
+/// access rules as `Datasets::by_id`.
+///
+/// Subscriptions are served outside of the request transaction, so the check
+/// opens a short one of its own.
+pub(crate) async fn resolve_subscribed_dataset(
+    ctx: &Context<'_>,
+    dataset_id: DatasetID<'_>,
+) -> Result<odf::DatasetHandle> {
+    let catalog = ctx.data::<dill::Catalog>().unwrap();
+    let dataset_id: odf::DatasetID = dataset_id.into();
+
+    let dataset_ref = dataset_id.as_local_ref();
+    let maybe_handle = DatabaseTransactionRunner::new(catalog.clone())
+        .transactional_with(
+            |view_dataset_use_case: Arc<dyn ViewDatasetUseCase>| async move {
+                match view_dataset_use_case.execute(&dataset_ref).await {
+                    Ok(handle) => Ok(Some(handle)),
+                    Err(
+                        ViewDatasetUseCaseError::NotFound(_) | ViewDatasetUseCaseError::Access(_),
+                    ) => Ok(None),
+                    Err(e) => Err(e.int_err()),
+                }
+            },
+        )
+        .await?;
+
+    maybe_handle.ok_or_else(|| {
+        GqlError::Gql(
+            Error::new("Dataset not found")
+                .extend_with(|_, eev| eev.set("datasetId", dataset_id.to_string())),
+        )
+    })
+}
+
+/// Turns the hub feed into a stream of events of a single dataset
+pub(crate) fn dataset_events<T, F>(
+    hub: &GqlEventHub,
+    dataset_id: odf::DatasetID,
+    select: F,
+) -> impl Stream<Item = T> + Send + 'static
+where
+    T: Send + 'static,
+    F: Fn(GqlEvent) -> Option<T> + Send + 'static,
+{
+    let receiver = hub.subscribe();
+
+    futures::stream::unfold(receiver, |mut receiver| async move {
+        loop {
+            match receiver.recv().await {
+                Ok(event) => return Some((event, receiver)),
+                Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                    tracing::warn!(skipped, "Subscriber is lagging behind, events were dropped");
+                }
+                Err(broadcast::error::RecvError::Closed) => return None,
+            }
+        }
+    })
+    .filter_map(move |event| {
+        let selected = if *event.dataset_id() == dataset_id {
+            select(event)
+        } else {
+            None
+        };
+        futures::future::ready(selected)
+    })
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+/// Status transition of a dataset flow run
+#[derive(SimpleObject, Debug, Clone)]
+pub(crate) struct FlowRunUpdate {
+    pub flow_id: FlowID,
+    pub dataset_id: DatasetID<'static>,
+    pub flow_type: DatasetFlowType,
+    pub status: FlowStatus,
+    /// Present only when the flow has finished
+    pub outcome: Option<FlowRunOutcome>,
+    pub event_time: DateTime<Utc>,
+}
+
+impl From<FlowRunUpdatedEvent> for FlowRunUpdate {
+    fn from(value: FlowRunUpdatedEvent) -> Self {
+        Self {
+            flow_id: value.flow_id.into(),
+            dataset_id: value.dataset_id.into(),
+            flow_type: value.flow_type.into(),
+            status: value.status.into(),
+            outcome: value.outcome.as_ref().map(Into::into),
+            event_time: value.event_time,
+        }
+    }
+}
+
+/// Simplified final outcome of a flow run
+#[derive(Enum, Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum FlowRunOutcome {
+    Success,
+    Failed,
+    Aborted,
+}
+
+impl From<&fs::FlowOutcome> for FlowRunOutcome {
+    fn from(value: &fs::FlowOutcome) -> Self {
+        match value {
+            fs::FlowOutcome::Success(_) => Self::Success,
+            fs::FlowOutcome::Failed(_) => Self::Failed,
+            fs::FlowOutcome::Aborted => Self::Aborted,
+        }
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+/// Progress of a task executed on behalf of a dataset
+#[derive(SimpleObject, Debug, Clone)]
+pub(crate) struct TaskUpdate {
+    pub task_id: TaskID,
+    pub dataset_id: DatasetID<'static>,
+    pub status: TaskStatus,
+    /// Present only when the task has finished
+    pub outcome: Option<TaskOutcome>,
+    pub event_time: DateTime<Utc>,
+}
+
+impl From<TaskUpdatedEvent> for TaskUpdate {
+    fn from(value: TaskUpdatedEvent) -> Self {
+        Self {
+            task_id: value.task_id.into(),
+            dataset_id: value.dataset_id.into(),
+            status: (&value.status).into(),
+            outcome: value.outcome.as_ref().map(Into::into),
+            event_time: value.event_time,
+        }
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+/// New blocks were committed to a dataset
+#[derive(SimpleObject, Debug, Clone)]
+pub(crate) struct DatasetHeadUpdate {
+    pub dataset_id: DatasetID<'static>,
+    pub old_head: Option<Multihash<'static>>,
+    pub new_head: Multihash<'static>,
+    pub event_time: DateTime<Utc>,
+}
+
+impl From<DatasetHeadUpdatedEvent> for DatasetHeadUpdate {
+    fn from(value: DatasetHeadUpdatedEvent) -> Self {
+        Self {
+            dataset_id: value.dataset_id.into(),
+            old_head: value.old_head.map(Into::into),
+            new_head: value.new_head.into(),
+            event_time: value.event_time,
+        }
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
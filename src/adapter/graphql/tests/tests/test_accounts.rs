@@ -10,6 +10,10 @@
 use async_graphql::value;
 use database_common::NoOpDatabasePlugin;
 use kamu_accounts::{DEFAULT_ACCOUNT_ID, DEFAULT_ACCOUNT_NAME_STR, DUMMY_EMAIL_ADDRESS};
+use kamu_accounts_inmem::InMemoryAccessTokenRepository;
+use kamu_accounts_services::AccountManagementServiceImpl;
+use messaging_outbox::DummyOutboxImpl;
+use time_source::SystemTimeSourceDefault;
 
 use crate::utils::authentication_catalogs;
 
@@ -417,6 +421,10 @@ struct GraphQLAccountsHarness {
 impl GraphQLAccountsHarness {
     pub async fn new() -> Self {
         let mut b = dill::CatalogBuilder::new();
+        b.add::<SystemTimeSourceDefault>()
+            .add::<DummyOutboxImpl>()
+            .add::<InMemoryAccessTokenRepository>()
+            .add::<AccountManagementServiceImpl>();
         NoOpDatabasePlugin::init_database_components(&mut b);
 
         let catalog = b.build();
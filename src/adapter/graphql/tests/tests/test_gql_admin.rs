@@ -0,0 +1,496 @@
+// Copyright Kamu Data, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use async_graphql::value;
+use database_common::NoOpDatabasePlugin;
+use kamu_accounts::*;
+use kamu_accounts_inmem::{InMemoryAccessTokenRepository, InMemoryAccountRepository};
+use kamu_accounts_services::{
+    AccountManagementServiceImpl,
+    AccountServiceImpl,
+    LoginPasswordAuthProvider,
+    PasswordHashingMode,
+};
+use kamu_adapter_graphql::STAFF_ONLY_MESSAGE;
+use kamu_auth_rebac_inmem::InMemoryRebacRepository;
+use kamu_auth_rebac_services::RebacServiceImpl;
+use kamu_core::TenancyConfig;
+use kamu_datasets_inmem::InMemoryDatasetEntryRepository;
+use kamu_datasets_services::DatasetEntryServiceImpl;
+use kamu_messaging_outbox_inmem::{
+    InMemoryOutboxMessageConsumptionRepository,
+    InMemoryOutboxMessageRepository,
+};
+use messaging_outbox::*;
+use time_source::SystemTimeSourceDefault;
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[test_log::test(tokio::test)]
+async fn test_admin_api_requires_admin() {
+    let harness = GraphQLAdminHarness::new().await;
+
+    let res = harness
+        .execute_user_query(
+            r#"
+            query {
+                admin {
+                    accounts {
+                        totalCount
+                    }
+                }
+            }
+            "#,
+        )
+        .await;
+
+    assert!(res.is_err(), "{res:?}");
+    assert_eq!(res.errors[0].message, STAFF_ONLY_MESSAGE);
+
+    let res = harness
+        .execute_user_query(
+            r#"
+            mutation {
+                admin {
+                    createAccount(accountName: "wasya", email: "wasya@example.com", password: "pwd") {
+                        message
+                    }
+                }
+            }
+            "#,
+        )
+        .await;
+
+    assert!(res.is_err(), "{res:?}");
+    assert_eq!(res.errors[0].message, STAFF_ONLY_MESSAGE);
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[test_log::test(tokio::test)]
+async fn test_create_and_list_accounts() {
+    let harness = GraphQLAdminHarness::new().await;
+
+    let res = harness.create_account("wasya", "wasya@example.com").await;
+    assert!(res.is_ok(), "{res:?}");
+    assert_eq!(
+        res.data,
+        value!({
+            "admin": {
+                "createAccount": {
+                    "message": "Success",
+                    "account": {
+                        "accountName": "wasya",
+                    }
+                }
+            }
+        })
+    );
+
+    let res = harness.create_account("petya", "wasya@example.com").await;
+    assert!(res.is_ok(), "{res:?}");
+    assert_eq!(
+        res.data,
+        value!({
+            "admin": {
+                "createAccount": {
+                    "message": "Account with duplicate email already exists",
+                }
+            }
+        })
+    );
+
+    let res = harness.create_account("petya", "petya#example.com").await;
+    assert!(res.is_ok(), "{res:?}");
+    assert_eq!(
+        res.data,
+        value!({
+            "admin": {
+                "createAccount": {
+                    "message": "Invalid email",
+                }
+            }
+        })
+    );
+
+    let res = harness
+        .execute_admin_query(
+            r#"
+            query {
+                admin {
+                    accounts {
+                        totalCount
+                        nodes {
+                            accountName
+                            isDisabled
+                        }
+                    }
+                }
+            }
+            "#,
+        )
+        .await;
+
+    assert!(res.is_ok(), "{res:?}");
+
+    let json = res.data.into_json().unwrap();
+    let accounts = &json["admin"]["accounts"];
+    assert_eq!(accounts["totalCount"], 2);
+
+    let mut nodes: Vec<_> = accounts["nodes"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|node| {
+            (
+                node["accountName"].as_str().unwrap().to_string(),
+                node["isDisabled"].as_bool().unwrap(),
+            )
+        })
+        .collect();
+    nodes.sort();
+
+    assert_eq!(
+        nodes,
+        vec![
+            (DEFAULT_ACCOUNT_NAME_STR.to_string(), false),
+            ("wasya".to_string(), false),
+        ]
+    );
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[test_log::test(tokio::test)]
+async fn test_manage_account() {
+    let harness = GraphQLAdminHarness::new().await;
+
+    let res = harness.create_account("wasya", "wasya@example.com").await;
+    assert!(res.is_ok(), "{res:?}");
+
+    let res = harness
+        .execute_admin_query(
+            r#"
+            mutation {
+                admin {
+                    account(accountName: "wasya") {
+                        setDisabled(disabled: true)
+                        resetPassword(newPassword: "new-password") {
+                            message
+                        }
+                        revokeAccessTokens
+                        rename(newName: "petya") {
+                            message
+                        }
+                    }
+                }
+            }
+            "#,
+        )
+        .await;
+
+    assert!(res.is_ok(), "{res:?}");
+    assert_eq!(
+        res.data,
+        value!({
+            "admin": {
+                "account": {
+                    "setDisabled": true,
+                    "resetPassword": {
+                        "message": "Success",
+                    },
+                    "revokeAccessTokens": 0,
+                    "rename": {
+                        "message": "Success",
+                    },
+                }
+            }
+        })
+    );
+
+    let res = harness
+        .execute_admin_query(
+            r#"
+            query {
+                accounts {
+                    byName(name: "petya") {
+                        isDisabled
+                    }
+                }
+            }
+            "#,
+        )
+        .await;
+
+    assert!(res.is_ok(), "{res:?}");
+    assert_eq!(
+        res.data,
+        value!({
+            "accounts": {
+                "byName": {
+                    "isDisabled": true,
+                }
+            }
+        })
+    );
+
+    let res = harness
+        .execute_admin_query(
+            r#"
+            mutation {
+                admin {
+                    account(accountName: "petya") {
+                        delete {
+                            message
+                        }
+                    }
+                }
+            }
+            "#,
+        )
+        .await;
+
+    assert!(res.is_ok(), "{res:?}");
+    assert_eq!(
+        res.data,
+        value!({
+            "admin": {
+                "account": {
+                    "delete": {
+                        "message": "Success",
+                    }
+                }
+            }
+        })
+    );
+
+    let account_service = harness
+        .catalog_admin
+        .get_one::<dyn AccountService>()
+        .unwrap();
+    assert!(account_service
+        .account_by_name(&odf::AccountName::new_unchecked("petya"))
+        .await
+        .unwrap()
+        .is_none());
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[test_log::test(tokio::test)]
+async fn test_admin_cannot_disable_self() {
+    let harness = GraphQLAdminHarness::new().await;
+
+    let res = harness
+        .execute_admin_query(format!(
+            r#"
+            mutation {{
+                admin {{
+                    account(accountName: "{DEFAULT_ACCOUNT_NAME_STR}") {{
+                        setDisabled(disabled: true)
+                    }}
+                }}
+            }}
+            "#,
+        ))
+        .await;
+
+    assert!(res.is_err(), "{res:?}");
+    assert_eq!(
+        res.errors[0].message,
+        "Administrators cannot perform this operation on their own account"
+    );
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[test_log::test(tokio::test)]
+async fn test_outbox_consumers_lag() {
+    let harness = GraphQLAdminHarness::new().await;
+
+    let outbox_message_repo = harness
+        .catalog_admin
+        .get_one::<dyn OutboxMessageRepository>()
+        .unwrap();
+    for _ in 0..3 {
+        outbox_message_repo
+            .push_message(NewOutboxMessage {
+                producer_name: "producer".to_string(),
+                content_json: serde_json::json!({}),
+                occurred_on: chrono::Utc::now(),
+                version: 1,
+            })
+            .await
+            .unwrap();
+    }
+
+    let outbox_consumption_repo = harness
+        .catalog_admin
+        .get_one::<dyn OutboxMessageConsumptionRepository>()
+        .unwrap();
+    outbox_consumption_repo
+        .create_consumption_boundary(OutboxMessageConsumptionBoundary {
+            producer_name: "producer".to_string(),
+            consumer_name: "consumer".to_string(),
+            last_consumed_message_id: OutboxMessageID::new(1),
+        })
+        .await
+        .unwrap();
+
+    let res = harness
+        .execute_admin_query(
+            r#"
+            query {
+                admin {
+                    outboxConsumers {
+                        producerName
+                        consumerName
+                        lastConsumedMessageId
+                        latestMessageId
+                        lag
+                    }
+                }
+            }
+            "#,
+        )
+        .await;
+
+    assert!(res.is_ok(), "{res:?}");
+    assert_eq!(
+        res.data,
+        value!({
+            "admin": {
+                "outboxConsumers": [
+                    {
+                        "producerName": "producer",
+                        "consumerName": "consumer",
+                        "lastConsumedMessageId": 1,
+                        "latestMessageId": 3,
+                        "lag": 2,
+                    }
+                ]
+            }
+        })
+    );
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+struct GraphQLAdminHarness {
+    _tempdir: tempfile::TempDir,
+    catalog_admin: dill::Catalog,
+    catalog_user: dill::Catalog,
+}
+
+impl GraphQLAdminHarness {
+    async fn new() -> Self {
+        let tempdir = tempfile::tempdir().unwrap();
+
+        let base_catalog = {
+            let mut b = dill::CatalogBuilder::new();
+
+            b.add::<SystemTimeSourceDefault>()
+                .add::<DummyOutboxImpl>()
+                .add_value(TenancyConfig::MultiTenant)
+                .add_builder(
+                    odf::dataset::DatasetStorageUnitLocalFs::builder()
+                        .with_root(tempdir.path().to_path_buf()),
+                )
+                .bind::<dyn odf::DatasetStorageUnit, odf::dataset::DatasetStorageUnitLocalFs>()
+                .add::<InMemoryAccountRepository>()
+                .add::<InMemoryAccessTokenRepository>()
+                .add::<LoginPasswordAuthProvider>()
+                .add_value(PasswordHashingMode::Minimal)
+                .add::<AccountServiceImpl>()
+                .add::<AccountManagementServiceImpl>()
+                .add::<RebacServiceImpl>()
+                .add_value(kamu_auth_rebac_services::DefaultAccountProperties { is_admin: false })
+                .add_value(kamu_auth_rebac_services::DefaultDatasetProperties {
+                    allows_anonymous_read: false,
+                    allows_public_read: false,
+                })
+                .add::<InMemoryRebacRepository>()
+                .add::<DatasetEntryServiceImpl>()
+                .add::<InMemoryDatasetEntryRepository>()
+                .add::<InMemoryOutboxMessageRepository>()
+                .add::<InMemoryOutboxMessageConsumptionRepository>();
+
+            NoOpDatabasePlugin::init_database_components(&mut b);
+
+            b.build()
+        };
+
+        let catalog_admin = dill::CatalogBuilder::new_chained(&base_catalog)
+            .add_value(CurrentAccountSubject::logged(
+                DEFAULT_ACCOUNT_ID.clone(),
+                DEFAULT_ACCOUNT_NAME.clone(),
+                true,
+            ))
+            .build();
+        let catalog_user = dill::CatalogBuilder::new_chained(&base_catalog)
+            .add_value(CurrentAccountSubject::new_test())
+            .build();
+
+        // The administrator account itself
+        let account_repo = catalog_admin.get_one::<dyn AccountRepository>().unwrap();
+        account_repo
+            .create_account(&Account::test(
+                DEFAULT_ACCOUNT_ID.clone(),
+                DEFAULT_ACCOUNT_NAME_STR,
+            ))
+            .await
+            .unwrap();
+
+        Self {
+            _tempdir: tempdir,
+            catalog_admin,
+            catalog_user,
+        }
+    }
+
+    async fn create_account(&self, account_name: &str, email: &str) -> async_graphql::Response {
+        self.execute_admin_query(format!(
+            r#"
+            mutation {{
+                admin {{
+                    createAccount(accountName: "{account_name}", email: "{email}", password: "pwd") {{
+                        message
+                        ... on CreateAccountSuccess {{
+                            account {{
+                                accountName
+                            }}
+                        }}
+                    }}
+                }}
+            }}
+            "#,
+        ))
+        .await
+    }
+
+    async fn execute_admin_query(
+        &self,
+        query: impl Into<async_graphql::Request>,
+    ) -> async_graphql::Response {
+        kamu_adapter_graphql::schema_quiet()
+            .execute(query.into().data(self.catalog_admin.clone()))
+            .await
+    }
+
+    async fn execute_user_query(
+        &self,
+        query: impl Into<async_graphql::Request>,
+    ) -> async_graphql::Response {
+        kamu_adapter_graphql::schema_quiet()
+            .execute(query.into().data(self.catalog_user.clone()))
+            .await
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
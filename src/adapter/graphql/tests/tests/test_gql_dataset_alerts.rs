@@ -0,0 +1,333 @@
+// Copyright Kamu Data, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use async_graphql::value;
+use database_common::{DatabaseTransactionRunner, NoOpDatabasePlugin};
+use indoc::indoc;
+use kamu_alerts_inmem::InMemoryDatasetAlertSettingsRepository;
+use kamu_alerts_services::DatasetAlertsServiceImpl;
+use kamu_core::{auth, DidGeneratorDefault, TenancyConfig};
+use kamu_datasets::{CreateDatasetFromSnapshotUseCase, CreateDatasetResult};
+use kamu_datasets_inmem::{InMemoryDatasetDependencyRepository, InMemoryDatasetEntryRepository};
+use kamu_datasets_services::{
+    CreateDatasetFromSnapshotUseCaseImpl,
+    CreateDatasetUseCaseImpl,
+    DatasetEntryServiceImpl,
+    DependencyGraphServiceImpl,
+    ViewDatasetUseCaseImpl,
+};
+use messaging_outbox::DummyOutboxImpl;
+use odf::metadata::testing::MetadataFactory;
+use time_source::SystemTimeSourceDefault;
+
+use crate::utils::authentication_catalogs;
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[test_log::test(tokio::test)]
+async fn test_set_and_remove_dataset_alert_settings() {
+    let harness = DatasetAlertsHarness::new().await;
+    let created_dataset = harness.create_dataset().await;
+    let dataset_id = created_dataset.dataset_handle.id.to_string();
+
+    let res = harness
+        .execute(&DatasetAlertsHarness::get_settings(&dataset_id))
+        .await;
+    assert!(res.is_ok(), "{res:?}");
+    assert_eq!(
+        res.data,
+        value!({
+            "datasets": {
+                "byId": {
+                    "alerts": {
+                        "settings": null
+                    }
+                }
+            }
+        })
+    );
+
+    let res = harness
+        .execute(&DatasetAlertsHarness::set_settings(
+            &dataset_id,
+            r#"["ops@example.com", "ops@example.com"]"#,
+            "{ every: 2, unit: HOURS }",
+        ))
+        .await;
+    assert!(res.is_ok(), "{res:?}");
+    assert_eq!(
+        res.data,
+        value!({
+            "datasets": {
+                "byId": {
+                    "alerts": {
+                        "setSettings": {
+                            "message": "Success",
+                            "settings": {
+                                "notifyOwner": false,
+                                "recipients": ["ops@example.com"],
+                                "notifyOnFlowFailure": true,
+                                "staleAfter": {
+                                    "every": 2,
+                                    "unit": "HOURS",
+                                },
+                                "staleNotifiedAt": null,
+                            }
+                        }
+                    }
+                }
+            }
+        })
+    );
+
+    let res = harness
+        .execute(&DatasetAlertsHarness::get_settings(&dataset_id))
+        .await;
+    let json = res.data.into_json().unwrap();
+    assert_eq!(
+        json["datasets"]["byId"]["alerts"]["settings"]["recipients"],
+        serde_json::json!(["ops@example.com"])
+    );
+
+    let res = harness
+        .execute(&DatasetAlertsHarness::remove_settings(&dataset_id))
+        .await;
+    assert!(res.is_ok(), "{res:?}");
+
+    let res = harness
+        .execute(&DatasetAlertsHarness::get_settings(&dataset_id))
+        .await;
+    assert_eq!(
+        res.data,
+        value!({
+            "datasets": {
+                "byId": {
+                    "alerts": {
+                        "settings": null
+                    }
+                }
+            }
+        })
+    );
+}
+
+#[test_log::test(tokio::test)]
+async fn test_set_dataset_alert_settings_invalid() {
+    let harness = DatasetAlertsHarness::new().await;
+    let created_dataset = harness.create_dataset().await;
+    let dataset_id = created_dataset.dataset_handle.id.to_string();
+
+    let res = harness
+        .execute(&DatasetAlertsHarness::set_settings(
+            &dataset_id,
+            r#"["not-an-email"]"#,
+            "null",
+        ))
+        .await;
+    assert!(res.is_ok(), "{res:?}");
+    assert_eq!(
+        res.data,
+        value!({
+            "datasets": {
+                "byId": {
+                    "alerts": {
+                        "setSettings": {
+                            "message": "Invalid dataset alert settings: Recipient 'not-an-email' is not a valid email",
+                        }
+                    }
+                }
+            }
+        })
+    );
+
+    let res = harness
+        .execute(&DatasetAlertsHarness::set_settings(
+            &dataset_id,
+            "[]",
+            "null",
+        ))
+        .await;
+    assert!(res.is_ok(), "{res:?}");
+    assert_eq!(
+        res.data,
+        value!({
+            "datasets": {
+                "byId": {
+                    "alerts": {
+                        "setSettings": {
+                            "message": "Invalid dataset alert settings: At least one recipient is required",
+                        }
+                    }
+                }
+            }
+        })
+    );
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+struct DatasetAlertsHarness {
+    _tempdir: tempfile::TempDir,
+    catalog_authorized: dill::Catalog,
+}
+
+impl DatasetAlertsHarness {
+    async fn new() -> Self {
+        let tempdir = tempfile::tempdir().unwrap();
+        let datasets_dir = tempdir.path().join("datasets");
+        std::fs::create_dir(&datasets_dir).unwrap();
+
+        let catalog_base = {
+            let mut b = dill::CatalogBuilder::new();
+
+            b.add::<DummyOutboxImpl>()
+                .add::<DidGeneratorDefault>()
+                .add_value(TenancyConfig::SingleTenant)
+                .add_builder(
+                    odf::dataset::DatasetStorageUnitLocalFs::builder().with_root(datasets_dir),
+                )
+                .bind::<dyn odf::DatasetStorageUnit, odf::dataset::DatasetStorageUnitLocalFs>()
+                .bind::<dyn odf::DatasetStorageUnitWriter, odf::dataset::DatasetStorageUnitLocalFs>(
+                )
+                .add::<CreateDatasetFromSnapshotUseCaseImpl>()
+                .add::<CreateDatasetUseCaseImpl>()
+                .add::<ViewDatasetUseCaseImpl>()
+                .add::<SystemTimeSourceDefault>()
+                .add::<auth::AlwaysHappyDatasetActionAuthorizer>()
+                .add::<DependencyGraphServiceImpl>()
+                .add::<InMemoryDatasetDependencyRepository>()
+                .add::<DatabaseTransactionRunner>()
+                .add::<DatasetEntryServiceImpl>()
+                .add::<InMemoryDatasetEntryRepository>()
+                .add::<DatasetAlertsServiceImpl>()
+                .add::<InMemoryDatasetAlertSettingsRepository>();
+
+            NoOpDatabasePlugin::init_database_components(&mut b);
+
+            b.build()
+        };
+
+        let (_, catalog_authorized) = authentication_catalogs(&catalog_base).await;
+
+        Self {
+            _tempdir: tempdir,
+            catalog_authorized,
+        }
+    }
+
+    async fn create_dataset(&self) -> CreateDatasetResult {
+        let create_dataset_from_snapshot = self
+            .catalog_authorized
+            .get_one::<dyn CreateDatasetFromSnapshotUseCase>()
+            .unwrap();
+
+        create_dataset_from_snapshot
+            .execute(
+                MetadataFactory::dataset_snapshot()
+                    .kind(odf::DatasetKind::Root)
+                    .name("foo")
+                    .push_event(MetadataFactory::set_polling_source().build())
+                    .build(),
+                Default::default(),
+            )
+            .await
+            .unwrap()
+    }
+
+    async fn execute(&self, query: &str) -> async_graphql::Response {
+        kamu_adapter_graphql::schema_quiet()
+            .execute(async_graphql::Request::new(query).data(self.catalog_authorized.clone()))
+            .await
+    }
+
+    fn get_settings(dataset_id: &str) -> String {
+        indoc!(
+            r#"
+            query {
+                datasets {
+                    byId(datasetId: "<dataset_id>") {
+                        alerts {
+                            settings {
+                                notifyOwner
+                                recipients
+                                notifyOnFlowFailure
+                                staleAfter {
+                                    every
+                                    unit
+                                }
+                                staleNotifiedAt
+                            }
+                        }
+                    }
+                }
+            }
+            "#
+        )
+        .replace("<dataset_id>", dataset_id)
+    }
+
+    fn set_settings(dataset_id: &str, recipients: &str, stale_after: &str) -> String {
+        indoc!(
+            r#"
+            mutation {
+                datasets {
+                    byId(datasetId: "<dataset_id>") {
+                        alerts {
+                            setSettings(
+                                settings: {
+                                    notifyOwner: false,
+                                    recipients: <recipients>,
+                                    notifyOnFlowFailure: true,
+                                    staleAfter: <stale_after>
+                                }
+                            ) {
+                                message
+                                ... on SetDatasetAlertSettingsResultSuccess {
+                                    settings {
+                                        notifyOwner
+                                        recipients
+                                        notifyOnFlowFailure
+                                        staleAfter {
+                                            every
+                                            unit
+                                        }
+                                        staleNotifiedAt
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+            "#
+        )
+        .replace("<dataset_id>", dataset_id)
+        .replace("<recipients>", recipients)
+        .replace("<stale_after>", stale_after)
+    }
+
+    fn remove_settings(dataset_id: &str) -> String {
+        indoc!(
+            r#"
+            mutation {
+                datasets {
+                    byId(datasetId: "<dataset_id>") {
+                        alerts {
+                            removeSettings
+                        }
+                    }
+                }
+            }
+            "#
+        )
+        .replace("<dataset_id>", dataset_id)
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
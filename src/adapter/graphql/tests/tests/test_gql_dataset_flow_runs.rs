@@ -65,7 +65,7 @@ use kamu_flow_system_services::{
 };
 use kamu_task_system::{self as ts, TaskMetadata};
 use kamu_task_system_inmem::InMemoryTaskEventStore;
-use kamu_task_system_services::TaskSchedulerImpl;
+use kamu_task_system_services::{TaskLogStoreImpl, TaskSchedulerImpl};
 use messaging_outbox::{register_message_dispatcher, Outbox, OutboxExt, OutboxImmediateImpl};
 use odf::metadata::testing::MetadataFactory;
 use time_source::SystemTimeSourceDefault;
@@ -2615,6 +2615,142 @@ async fn test_history_of_completed_flow() {
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
 
+#[test_log::test(tokio::test)]
+async fn test_task_logs_of_failed_flow() {
+    let harness = FlowRunsHarness::with_overrides(FlowRunsHarnessOverrides {
+        dataset_changes_mock: None,
+    })
+    .await;
+
+    let create_result = harness.create_root_dataset().await;
+
+    let mutation_code =
+        FlowRunsHarness::trigger_flow_mutation(&create_result.dataset_handle.id, "INGEST");
+
+    let schema = kamu_adapter_graphql::schema_quiet();
+    let response = schema
+        .execute(
+            async_graphql::Request::new(mutation_code.clone())
+                .data(harness.catalog_authorized.clone()),
+        )
+        .await;
+
+    assert!(response.is_ok(), "{response:?}");
+    let response_json = response.data.into_json().unwrap();
+    let flow_id = FlowRunsHarness::extract_flow_id_from_trigger_response(&response_json);
+    let flow_task_metadata = TaskMetadata::from(vec![(METADATA_TASK_FLOW_ID, flow_id)]);
+
+    let flow_task_id = harness.mimic_flow_scheduled(flow_id, Utc::now()).await;
+    harness
+        .mimic_task_running(flow_task_id, flow_task_metadata.clone(), Utc::now())
+        .await;
+
+    let mut task_log = ts::TaskRunLog::new();
+    task_log.message(Utc::now(), "Error: Process exited with code 1");
+    task_log.message(Utc::now(), "Caused by: Engine crashed");
+    task_log.message(Utc::now(), "Task finished: Failed(Empty)");
+    harness
+        .catalog_anonymous
+        .get_one::<dyn ts::TaskLogStore>()
+        .unwrap()
+        .append_task_log(flow_task_id, task_log.entries())
+        .await
+        .unwrap();
+
+    harness
+        .mimic_task_completed(
+            flow_task_id,
+            flow_task_metadata,
+            Utc::now(),
+            ts::TaskOutcome::Failed(ts::TaskError::Empty),
+        )
+        .await;
+
+    let query = indoc!(
+        r#"
+        {
+            datasets {
+                byId (datasetId: "<id>") {
+                    flows {
+                        runs {
+                            getFlow(flowId: "<flowId>") {
+                                ... on GetFlowSuccess {
+                                    flow {
+                                        tasks {
+                                            taskId
+                                            logs(page: 1, perPage: 2) {
+                                                totalCount
+                                                nodes {
+                                                    source
+                                                    message
+                                                }
+                                                pageInfo {
+                                                    hasPreviousPage
+                                                    hasNextPage
+                                                    currentPage
+                                                    totalPages
+                                                }
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        "#
+    )
+    .replace("<id>", &create_result.dataset_handle.id.to_string())
+    .replace("<flowId>", flow_id);
+
+    let response = schema
+        .execute(async_graphql::Request::new(query).data(harness.catalog_authorized.clone()))
+        .await;
+
+    assert!(response.is_ok(), "{response:?}");
+    assert_eq!(
+        response.data,
+        value!({
+            "datasets": {
+                "byId": {
+                    "flows": {
+                        "runs": {
+                            "getFlow": {
+                                "flow": {
+                                    "tasks": [
+                                        {
+                                            "taskId": "0",
+                                            "logs": {
+                                                "totalCount": 3,
+                                                "nodes": [
+                                                    {
+                                                        "source": "task",
+                                                        "message": "Task finished: Failed(Empty)",
+                                                    }
+                                                ],
+                                                "pageInfo": {
+                                                    "hasPreviousPage": true,
+                                                    "hasNextPage": false,
+                                                    "currentPage": 1,
+                                                    "totalPages": 2,
+                                                }
+                                            }
+                                        }
+                                    ]
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        })
+    );
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
 #[test_log::test(tokio::test)]
 async fn test_execute_transfrom_flow_error_after_compaction() {
     let harness = FlowRunsHarness::with_overrides(FlowRunsHarnessOverrides {
@@ -3134,6 +3270,13 @@ impl FlowRunsHarness {
             ))
             .add::<TaskSchedulerImpl>()
             .add::<InMemoryTaskEventStore>()
+            .add::<TaskLogStoreImpl>()
+            .add_value(ts::TaskLogsConfig {
+                storage_url: url::Url::from_directory_path(tempdir.path().join("task-logs"))
+                    .unwrap(),
+                retention_period: None,
+                cleanup_interval: Duration::hours(1),
+            })
             .add::<DatasetEntryServiceImpl>()
             .add::<InMemoryDatasetEntryRepository>()
             .add::<DatabaseTransactionRunner>();
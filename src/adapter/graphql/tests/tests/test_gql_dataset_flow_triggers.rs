@@ -466,6 +466,151 @@ async fn test_crud_cron_root_dataset() {
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
 
+#[test_log::test(tokio::test)]
+async fn test_cron_trigger_with_time_zone() {
+    let harness = FlowTriggerHarness::make().await;
+
+    let create_result = harness.create_root_dataset().await;
+
+    let schema = kamu_adapter_graphql::schema_quiet();
+
+    let mutation_code = FlowTriggerHarness::set_cron_trigger_in_time_zone_mutation(
+        &create_result.dataset_handle.id,
+        "0 6 * * *",
+        "Europe/Berlin",
+    );
+
+    let res = schema
+        .execute(
+            async_graphql::Request::new(mutation_code.clone())
+                .data(harness.catalog_authorized.clone()),
+        )
+        .await;
+
+    assert!(res.is_ok(), "{res:?}");
+    assert_eq!(
+        res.data,
+        value!({
+            "datasets": {
+                "byId": {
+                    "flows": {
+                        "triggers": {
+                            "setTrigger": {
+                                "__typename": "SetFlowTriggerSuccess",
+                                "message": "Success",
+                                "trigger": {
+                                    "schedule": {
+                                        "__typename": "Cron5ComponentExpression",
+                                        "cron5ComponentExpression": "0 6 * * *",
+                                        "timeZone": "Europe/Berlin",
+                                    },
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        })
+    );
+
+    // Plain cron expressions are evaluated in UTC
+    let mutation_code = FlowTriggerHarness::set_cron_trigger_mutation(
+        &create_result.dataset_handle.id,
+        "INGEST",
+        false,
+        "0 6 * * *",
+    );
+
+    let res = schema
+        .execute(
+            async_graphql::Request::new(mutation_code.clone())
+                .data(harness.catalog_authorized.clone()),
+        )
+        .await;
+    assert!(res.is_ok(), "{res:?}");
+
+    let request_code = indoc!(
+        r#"
+        {
+            datasets {
+                byId (datasetId: "<id>") {
+                    flows {
+                        triggers {
+                            byType (datasetFlowType: "INGEST") {
+                                schedule {
+                                    ... on Cron5ComponentExpression {
+                                        timeZone
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        "#
+    )
+    .replace("<id>", &create_result.dataset_handle.id.to_string());
+
+    let res = schema
+        .execute(async_graphql::Request::new(request_code).data(harness.catalog_authorized.clone()))
+        .await;
+
+    assert!(res.is_ok(), "{res:?}");
+    assert_eq!(
+        res.data,
+        value!({
+            "datasets": {
+                "byId": {
+                    "flows": {
+                        "triggers": {
+                            "byType": {
+                                "schedule": {
+                                    "timeZone": "UTC",
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        })
+    );
+
+    // Try to pass unknown time zone
+    let mutation_code = FlowTriggerHarness::set_cron_trigger_in_time_zone_mutation(
+        &create_result.dataset_handle.id,
+        "0 6 * * *",
+        "Mars/Olympus",
+    );
+
+    let res = schema
+        .execute(
+            async_graphql::Request::new(mutation_code.clone())
+                .data(harness.catalog_authorized.clone()),
+        )
+        .await;
+    assert!(res.is_ok(), "{res:?}");
+    assert_eq!(
+        res.data,
+        value!({
+            "datasets": {
+                "byId": {
+                    "flows": {
+                        "triggers": {
+                            "setTrigger": {
+                                "__typename": "FlowInvalidTriggerInputError",
+                                "message": "Time zone Mars/Olympus is unknown",
+                            }
+                        }
+                    }
+                }
+            }
+        })
+    );
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
 #[test_log::test(tokio::test)]
 async fn test_crud_batching_derived_dataset() {
     let harness = FlowTriggerHarness::make().await;
@@ -644,6 +789,122 @@ async fn test_batching_trigger_validation() {
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
 
+#[test_log::test(tokio::test)]
+async fn test_batching_trigger_with_conditions() {
+    let harness = FlowTriggerHarness::make().await;
+
+    harness.create_root_dataset().await;
+    let create_derived_result = harness.create_derived_dataset().await;
+
+    let schema = kamu_adapter_graphql::schema_quiet();
+
+    let mutation_code = FlowTriggerHarness::set_trigger_batching_with_conditions_mutation(
+        &create_derived_result.dataset_handle.id,
+        "22:00",
+        "02:30",
+        "Europe/Berlin",
+    );
+
+    let res = schema
+        .execute(
+            async_graphql::Request::new(mutation_code.clone())
+                .data(harness.catalog_authorized.clone()),
+        )
+        .await;
+
+    assert!(res.is_ok(), "{res:?}");
+    assert_eq!(
+        res.data,
+        value!({
+            "datasets": {
+                "byId": {
+                    "flows": {
+                        "triggers": {
+                            "setTrigger": {
+                                "__typename": "SetFlowTriggerSuccess",
+                                "message": "Success",
+                                "trigger": {
+                                    "batching": {
+                                        "minRecordsToAwait": 10,
+                                        "awaitAllInputs": true,
+                                        "timeWindow": {
+                                            "start": "22:00",
+                                            "end": "02:30",
+                                            "timeZone": "Europe/Berlin"
+                                        }
+                                    },
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        })
+    );
+
+    for (start, end, time_zone, expected_error) in [
+        (
+            "06:00",
+            "06:00",
+            "UTC",
+            "Time window start and end should differ",
+        ),
+        (
+            "6am",
+            "09:00",
+            "UTC",
+            "Time 6am is invalid, expected HH:MM format",
+        ),
+        (
+            "06:00",
+            "24:00",
+            "UTC",
+            "Time 24:00 is invalid, expected HH:MM format",
+        ),
+        (
+            "06:00",
+            "09:00",
+            "Mars/Olympus",
+            "Time zone Mars/Olympus is unknown",
+        ),
+    ] {
+        let mutation_code = FlowTriggerHarness::set_trigger_batching_with_conditions_mutation(
+            &create_derived_result.dataset_handle.id,
+            start,
+            end,
+            time_zone,
+        );
+
+        let res = schema
+            .execute(
+                async_graphql::Request::new(mutation_code.clone())
+                    .data(harness.catalog_authorized.clone()),
+            )
+            .await;
+
+        assert!(res.is_ok(), "{res:?}");
+        assert_eq!(
+            res.data,
+            value!({
+                "datasets": {
+                    "byId": {
+                        "flows": {
+                            "triggers": {
+                                "setTrigger": {
+                                    "__typename": "FlowInvalidTriggerInputError",
+                                    "message": expected_error,
+                                }
+                            }
+                        }
+                    }
+                }
+            })
+        );
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
 #[test_log::test(tokio::test)]
 async fn test_pause_resume_dataset_flows() {
     async fn check_flow_config_status(
@@ -1292,6 +1553,110 @@ impl FlowTriggerHarness {
         .replace("<cron_expression>", cron_expression)
     }
 
+    fn set_cron_trigger_in_time_zone_mutation(
+        id: &odf::DatasetID,
+        cron_expression: &str,
+        time_zone: &str,
+    ) -> String {
+        indoc!(
+            r#"
+            mutation {
+                datasets {
+                    byId (datasetId: "<id>") {
+                        flows {
+                            triggers {
+                                setTrigger (
+                                    datasetFlowType: "INGEST",
+                                    paused: false,
+                                    triggerInput: {
+                                        schedule: {
+                                            cron: {
+                                                cron5ComponentExpression: "<cron_expression>",
+                                                timeZone: "<time_zone>"
+                                            }
+                                        }
+                                    }
+                                ) {
+                                    __typename,
+                                    message
+                                    ... on SetFlowTriggerSuccess {
+                                        trigger {
+                                            schedule {
+                                                __typename
+                                                ... on Cron5ComponentExpression {
+                                                    cron5ComponentExpression
+                                                    timeZone
+                                                }
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+            "#
+        )
+        .replace("<id>", &id.to_string())
+        .replace("<cron_expression>", cron_expression)
+        .replace("<time_zone>", time_zone)
+    }
+
+    fn set_trigger_batching_with_conditions_mutation(
+        id: &odf::DatasetID,
+        window_start: &str,
+        window_end: &str,
+        time_zone: &str,
+    ) -> String {
+        indoc!(
+            r#"
+            mutation {
+                datasets {
+                    byId (datasetId: "<id>") {
+                        flows {
+                            triggers {
+                                setTrigger (
+                                    datasetFlowType: "EXECUTE_TRANSFORM",
+                                    paused: false,
+                                    triggerInput: {
+                                        batching: {
+                                            minRecordsToAwait: 10,
+                                            maxBatchingInterval: { every: 1, unit: "HOURS" },
+                                            awaitAllInputs: true,
+                                            timeWindow: { start: "<start>", end: "<end>", timeZone: "<time_zone>" }
+                                        }
+                                    }
+                                ) {
+                                    __typename,
+                                    message
+                                    ... on SetFlowTriggerSuccess {
+                                        trigger {
+                                            batching {
+                                                minRecordsToAwait
+                                                awaitAllInputs
+                                                timeWindow {
+                                                    start
+                                                    end
+                                                    timeZone
+                                                }
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+            "#
+        )
+        .replace("<id>", &id.to_string())
+        .replace("<start>", window_start)
+        .replace("<end>", window_end)
+        .replace("<time_zone>", time_zone)
+    }
+
     fn set_trigger_batching_mutation(
         id: &odf::DatasetID,
         dataset_flow_type: &str,
@@ -0,0 +1,354 @@
+// Copyright Kamu Data, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use async_graphql::value;
+use database_common::{DatabaseTransactionRunner, NoOpDatabasePlugin};
+use indoc::indoc;
+use kamu_core::{auth, DidGeneratorDefault, TenancyConfig};
+use kamu_datasets::{CreateDatasetFromSnapshotUseCase, CreateDatasetResult};
+use kamu_datasets_inmem::{InMemoryDatasetDependencyRepository, InMemoryDatasetEntryRepository};
+use kamu_datasets_services::{
+    CreateDatasetFromSnapshotUseCaseImpl,
+    CreateDatasetUseCaseImpl,
+    DatasetEntryServiceImpl,
+    DependencyGraphServiceImpl,
+    ViewDatasetUseCaseImpl,
+};
+use kamu_webhooks_inmem::{
+    InMemoryWebhookDeliveryRepository,
+    InMemoryWebhookSubscriptionRepository,
+};
+use kamu_webhooks_services::WebhookSubscriptionServiceImpl;
+use messaging_outbox::DummyOutboxImpl;
+use odf::metadata::testing::MetadataFactory;
+use time_source::SystemTimeSourceDefault;
+
+use crate::utils::authentication_catalogs;
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[test_log::test(tokio::test)]
+async fn test_create_and_list_dataset_webhooks() {
+    let harness = DatasetWebhooksHarness::new().await;
+    let created_dataset = harness.create_dataset().await;
+    let dataset_id = created_dataset.dataset_handle.id.to_string();
+
+    let res = harness
+        .execute(&DatasetWebhooksHarness::create_subscription(
+            &dataset_id,
+            "https://example.com/hooks",
+            "[DATASET_UPDATED, FLOW_FAILED]",
+        ))
+        .await;
+    assert!(res.is_ok(), "{res:?}");
+
+    let json = res.data.into_json().unwrap();
+    let create_result = &json["datasets"]["byId"]["webhooks"]["createSubscription"];
+    assert_eq!(create_result["message"], "Success");
+    assert_eq!(create_result["secret"].as_str().unwrap().len(), 64);
+    let subscription_id = create_result["subscription"]["id"]
+        .as_str()
+        .unwrap()
+        .to_string();
+
+    let res = harness
+        .execute(&DatasetWebhooksHarness::list_subscriptions(&dataset_id))
+        .await;
+    assert!(res.is_ok(), "{res:?}");
+    assert_eq!(
+        res.data,
+        value!({
+            "datasets": {
+                "byId": {
+                    "webhooks": {
+                        "subscriptions": [{
+                            "id": subscription_id.clone(),
+                            "datasetId": dataset_id.clone(),
+                            "targetUrl": "https://example.com/hooks",
+                            "label": "dashboard",
+                            "eventTypes": ["DATASET_UPDATED", "FLOW_FAILED"],
+                            "status": "ENABLED",
+                            "deliveries": {
+                                "totalCount": 0,
+                            }
+                        }]
+                    }
+                }
+            }
+        })
+    );
+
+    let res = harness
+        .execute(&DatasetWebhooksHarness::modify_subscription(
+            &dataset_id,
+            "pauseSubscription",
+            &subscription_id,
+        ))
+        .await;
+    assert!(res.is_ok(), "{res:?}");
+    assert_eq!(
+        res.data,
+        value!({
+            "datasets": {
+                "byId": {
+                    "webhooks": {
+                        "pauseSubscription": {
+                            "message": "Success",
+                        }
+                    }
+                }
+            }
+        })
+    );
+
+    let res = harness
+        .execute(&DatasetWebhooksHarness::list_subscriptions(&dataset_id))
+        .await;
+    let json = res.data.into_json().unwrap();
+    assert_eq!(
+        json["datasets"]["byId"]["webhooks"]["subscriptions"][0]["status"],
+        "PAUSED"
+    );
+
+    let res = harness
+        .execute(&DatasetWebhooksHarness::modify_subscription(
+            &dataset_id,
+            "removeSubscription",
+            &subscription_id,
+        ))
+        .await;
+    assert!(res.is_ok(), "{res:?}");
+
+    let res = harness
+        .execute(&DatasetWebhooksHarness::list_subscriptions(&dataset_id))
+        .await;
+    assert_eq!(
+        res.data,
+        value!({
+            "datasets": {
+                "byId": {
+                    "webhooks": {
+                        "subscriptions": []
+                    }
+                }
+            }
+        })
+    );
+
+    let res = harness
+        .execute(&DatasetWebhooksHarness::modify_subscription(
+            &dataset_id,
+            "removeSubscription",
+            &subscription_id,
+        ))
+        .await;
+    assert_eq!(
+        res.data,
+        value!({
+            "datasets": {
+                "byId": {
+                    "webhooks": {
+                        "removeSubscription": {
+                            "message": format!("Webhook subscription '{subscription_id}' not found"),
+                        }
+                    }
+                }
+            }
+        })
+    );
+}
+
+#[test_log::test(tokio::test)]
+async fn test_create_dataset_webhook_invalid() {
+    let harness = DatasetWebhooksHarness::new().await;
+    let created_dataset = harness.create_dataset().await;
+    let dataset_id = created_dataset.dataset_handle.id.to_string();
+
+    let res = harness
+        .execute(&DatasetWebhooksHarness::create_subscription(
+            &dataset_id,
+            "https://example.com/hooks",
+            "[]",
+        ))
+        .await;
+    assert!(res.is_ok(), "{res:?}");
+    assert_eq!(
+        res.data,
+        value!({
+            "datasets": {
+                "byId": {
+                    "webhooks": {
+                        "createSubscription": {
+                            "message": "Invalid webhook subscription: At least one event type is required",
+                        }
+                    }
+                }
+            }
+        })
+    );
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+struct DatasetWebhooksHarness {
+    _tempdir: tempfile::TempDir,
+    catalog_authorized: dill::Catalog,
+}
+
+impl DatasetWebhooksHarness {
+    async fn new() -> Self {
+        let tempdir = tempfile::tempdir().unwrap();
+        let datasets_dir = tempdir.path().join("datasets");
+        std::fs::create_dir(&datasets_dir).unwrap();
+
+        let catalog_base = {
+            let mut b = dill::CatalogBuilder::new();
+
+            b.add::<DummyOutboxImpl>()
+                .add::<DidGeneratorDefault>()
+                .add_value(TenancyConfig::SingleTenant)
+                .add_builder(
+                    odf::dataset::DatasetStorageUnitLocalFs::builder().with_root(datasets_dir),
+                )
+                .bind::<dyn odf::DatasetStorageUnit, odf::dataset::DatasetStorageUnitLocalFs>()
+                .bind::<dyn odf::DatasetStorageUnitWriter, odf::dataset::DatasetStorageUnitLocalFs>(
+                )
+                .add::<CreateDatasetFromSnapshotUseCaseImpl>()
+                .add::<CreateDatasetUseCaseImpl>()
+                .add::<ViewDatasetUseCaseImpl>()
+                .add::<SystemTimeSourceDefault>()
+                .add::<auth::AlwaysHappyDatasetActionAuthorizer>()
+                .add::<DependencyGraphServiceImpl>()
+                .add::<InMemoryDatasetDependencyRepository>()
+                .add::<DatabaseTransactionRunner>()
+                .add::<DatasetEntryServiceImpl>()
+                .add::<InMemoryDatasetEntryRepository>()
+                .add::<WebhookSubscriptionServiceImpl>()
+                .add::<InMemoryWebhookSubscriptionRepository>()
+                .add::<InMemoryWebhookDeliveryRepository>();
+
+            NoOpDatabasePlugin::init_database_components(&mut b);
+
+            b.build()
+        };
+
+        let (_, catalog_authorized) = authentication_catalogs(&catalog_base).await;
+
+        Self {
+            _tempdir: tempdir,
+            catalog_authorized,
+        }
+    }
+
+    async fn create_dataset(&self) -> CreateDatasetResult {
+        let create_dataset_from_snapshot = self
+            .catalog_authorized
+            .get_one::<dyn CreateDatasetFromSnapshotUseCase>()
+            .unwrap();
+
+        create_dataset_from_snapshot
+            .execute(
+                MetadataFactory::dataset_snapshot()
+                    .kind(odf::DatasetKind::Root)
+                    .name("foo")
+                    .push_event(MetadataFactory::set_polling_source().build())
+                    .build(),
+                Default::default(),
+            )
+            .await
+            .unwrap()
+    }
+
+    async fn execute(&self, query: &str) -> async_graphql::Response {
+        kamu_adapter_graphql::schema_quiet()
+            .execute(async_graphql::Request::new(query).data(self.catalog_authorized.clone()))
+            .await
+    }
+
+    fn create_subscription(dataset_id: &str, target_url: &str, event_types: &str) -> String {
+        indoc!(
+            r#"
+            mutation {
+                datasets {
+                    byId(datasetId: "<dataset_id>") {
+                        webhooks {
+                            createSubscription(
+                                targetUrl: "<target_url>",
+                                label: "dashboard",
+                                eventTypes: <event_types>
+                            ) {
+                                message
+                                ... on CreateWebhookSubscriptionResultSuccess {
+                                    secret
+                                    subscription {
+                                        id
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+            "#
+        )
+        .replace("<dataset_id>", dataset_id)
+        .replace("<target_url>", target_url)
+        .replace("<event_types>", event_types)
+    }
+
+    fn list_subscriptions(dataset_id: &str) -> String {
+        indoc!(
+            r#"
+            query {
+                datasets {
+                    byId(datasetId: "<dataset_id>") {
+                        webhooks {
+                            subscriptions {
+                                id
+                                datasetId
+                                targetUrl
+                                label
+                                eventTypes
+                                status
+                                deliveries {
+                                    totalCount
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+            "#
+        )
+        .replace("<dataset_id>", dataset_id)
+    }
+
+    fn modify_subscription(dataset_id: &str, operation: &str, subscription_id: &str) -> String {
+        indoc!(
+            r#"
+            mutation {
+                datasets {
+                    byId(datasetId: "<dataset_id>") {
+                        webhooks {
+                            <operation>(id: "<subscription_id>") {
+                                message
+                            }
+                        }
+                    }
+                }
+            }
+            "#
+        )
+        .replace("<dataset_id>", dataset_id)
+        .replace("<operation>", operation)
+        .replace("<subscription_id>", subscription_id)
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
@@ -179,6 +179,161 @@ async fn test_current_set_transform() {
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
 
+#[test_log::test(tokio::test)]
+async fn test_freshness_sla() {
+    let harness = DatasetMetadataHarness::new().await;
+    let create_result = harness.create_root_dataset().await;
+
+    let freshness_request_code = indoc!(
+        r#"
+        {
+            datasets {
+                byId (datasetId: "<id>") {
+                    metadata {
+                        freshness {
+                            sla {
+                                maxUpdateInterval { every unit }
+                                maxWatermarkLag { every unit }
+                            }
+                            status
+                            lastUpdatedAt
+                        }
+                    }
+                }
+            }
+        }
+        "#
+    )
+    .replace("<id>", &create_result.dataset_handle.id.to_string());
+
+    let schema = kamu_adapter_graphql::schema_quiet();
+    let res = schema
+        .execute(
+            Request::new(freshness_request_code.clone()).data(harness.catalog_authorized.clone()),
+        )
+        .await;
+    assert!(res.is_ok(), "{res:?}");
+    assert_eq!(
+        res.data,
+        value!({
+            "datasets": {
+                "byId": {
+                    "metadata": {
+                        "freshness": {
+                            "sla": null,
+                            "status": "UNDEFINED",
+                            "lastUpdatedAt": null,
+                        }
+                    }
+                }
+            }
+        })
+    );
+
+    let set_sla_request_code = indoc!(
+        r#"
+        mutation {
+            datasets {
+                byId (datasetId: "<id>") {
+                    metadata {
+                        setFreshnessSla(sla: <sla>) {
+                            __typename
+                            message
+                        }
+                    }
+                }
+            }
+        }
+        "#
+    )
+    .replace("<id>", &create_result.dataset_handle.id.to_string());
+
+    let res = schema
+        .execute(
+            Request::new(set_sla_request_code.replace(
+                "<sla>",
+                "{ maxUpdateInterval: { every: 1, unit: DAYS }, maxWatermarkLag: { every: 2, \
+                 unit: DAYS } }",
+            ))
+            .data(harness.catalog_authorized.clone()),
+        )
+        .await;
+    assert!(res.is_ok(), "{res:?}");
+    assert_eq!(
+        res.data,
+        value!({
+            "datasets": {
+                "byId": {
+                    "metadata": {
+                        "setFreshnessSla": {
+                            "__typename": "SetFreshnessSlaResultSuccess",
+                            "message": "Success",
+                        }
+                    }
+                }
+            }
+        })
+    );
+
+    // Dataset that never received any data is stale
+    let res = schema
+        .execute(
+            Request::new(freshness_request_code.clone()).data(harness.catalog_authorized.clone()),
+        )
+        .await;
+    assert!(res.is_ok(), "{res:?}");
+    assert_eq!(
+        res.data,
+        value!({
+            "datasets": {
+                "byId": {
+                    "metadata": {
+                        "freshness": {
+                            "sla": {
+                                "maxUpdateInterval": { "every": 1, "unit": "DAYS" },
+                                "maxWatermarkLag": { "every": 2, "unit": "DAYS" },
+                            },
+                            "status": "STALE",
+                            "lastUpdatedAt": null,
+                        }
+                    }
+                }
+            }
+        })
+    );
+
+    let res = schema
+        .execute(
+            Request::new(set_sla_request_code.replace("<sla>", "null"))
+                .data(harness.catalog_authorized.clone()),
+        )
+        .await;
+    assert!(res.is_ok(), "{res:?}");
+
+    let res = schema
+        .execute(Request::new(freshness_request_code).data(harness.catalog_authorized.clone()))
+        .await;
+    assert!(res.is_ok(), "{res:?}");
+    assert_eq!(
+        res.data,
+        value!({
+            "datasets": {
+                "byId": {
+                    "metadata": {
+                        "freshness": {
+                            "sla": null,
+                            "status": "UNDEFINED",
+                            "lastUpdatedAt": null,
+                        }
+                    }
+                }
+            }
+        })
+    );
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
 struct DatasetMetadataHarness {
     _tempdir: tempfile::TempDir,
     _catalog_anonymous: dill::Catalog,
@@ -202,6 +357,7 @@ impl DatasetMetadataHarness {
                 .add::<ViewDatasetUseCaseImpl>()
                 .add::<InMemoryDatasetDependencyRepository>()
                 .add::<MetadataQueryServiceImpl>()
+                .add::<DatasetFreshnessServiceImpl>()
                 .add::<EngineProvisionerNull>()
                 .add::<ObjectStoreRegistryImpl>()
                 .add::<DataFormatRegistryImpl>()
@@ -235,6 +235,13 @@ impl RemoteStatusService for FakeRemoteStatusService {
             ],
         })
     }
+
+    async fn check_pull_remotes_status(
+        &self,
+        _dataset_handle: &odf::DatasetHandle,
+    ) -> std::result::Result<DatasetPullStatuses, InternalError> {
+        Ok(DatasetPullStatuses { statuses: vec![] })
+    }
 }
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
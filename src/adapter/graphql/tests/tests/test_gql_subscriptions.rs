@@ -0,0 +1,328 @@
+// Copyright Kamu Data, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::assert_matches::assert_matches;
+
+use async_graphql::*;
+use chrono::{TimeZone, Utc};
+use database_common::NoOpDatabasePlugin;
+use dill::*;
+use futures::StreamExt;
+use indoc::indoc;
+use kamu::*;
+use kamu_accounts::testing::MockAuthenticationService;
+use kamu_accounts::{AuthenticationService, DEFAULT_ACCOUNT_NAME};
+use kamu_adapter_graphql::subscriptions::*;
+use kamu_core::*;
+use kamu_datasets::{
+    CreateDatasetFromSnapshotUseCase,
+    CreateDatasetResult,
+    DatasetReferenceMessage,
+};
+use kamu_datasets_inmem::{InMemoryDatasetDependencyRepository, InMemoryDatasetEntryRepository};
+use kamu_datasets_services::{
+    CreateDatasetFromSnapshotUseCaseImpl,
+    CreateDatasetUseCaseImpl,
+    DatasetEntryServiceImpl,
+    DependencyGraphServiceImpl,
+    ViewDatasetUseCaseImpl,
+};
+use kamu_flow_system as fs;
+use messaging_outbox::{DummyOutboxImpl, MessageConsumerT};
+use odf::metadata::testing::MetadataFactory;
+use time_source::SystemTimeSourceDefault;
+
+use crate::utils::authentication_catalogs;
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[test_log::test(tokio::test)]
+async fn test_flow_run_updates_of_subscribed_dataset_only() {
+    let harness = SubscriptionsHarness::new().await;
+    let foo = harness.create_root_dataset("foo").await;
+    let bar = harness.create_root_dataset("bar").await;
+
+    let request_code = indoc!(
+        r#"
+        subscription {
+            flowRunUpdates(datasetId: "<id>") {
+                flowId
+                datasetId
+                flowType
+                status
+                outcome
+                eventTime
+            }
+        }
+        "#
+    )
+    .replace("<id>", &foo.dataset_handle.id.to_string());
+
+    let event_time = Utc.with_ymd_and_hms(2050, 1, 1, 12, 0, 0).unwrap();
+    let make_event = |flow_id: u64, dataset_id: &odf::DatasetID| {
+        GqlEvent::FlowRunUpdated(FlowRunUpdatedEvent {
+            event_time,
+            flow_id: fs::FlowID::new(flow_id),
+            dataset_id: dataset_id.clone(),
+            flow_type: fs::DatasetFlowType::Ingest,
+            status: fs::FlowStatus::Finished,
+            outcome: Some(fs::FlowOutcome::Aborted),
+        })
+    };
+
+    let res = harness
+        .first_response(
+            &request_code,
+            vec![
+                make_event(1, &bar.dataset_handle.id),
+                make_event(2, &foo.dataset_handle.id),
+            ],
+        )
+        .await;
+
+    assert!(res.is_ok(), "{res:?}");
+    assert_eq!(
+        res.data,
+        value!({
+            "flowRunUpdates": {
+                "flowId": "2",
+                "datasetId": foo.dataset_handle.id.to_string(),
+                "flowType": "INGEST",
+                "status": "FINISHED",
+                "outcome": "ABORTED",
+                "eventTime": "2050-01-01T12:00:00+00:00",
+            }
+        })
+    );
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[test_log::test(tokio::test)]
+async fn test_dataset_head_updates() {
+    let harness = SubscriptionsHarness::new().await;
+    let foo = harness.create_root_dataset("foo").await;
+
+    let request_code = indoc!(
+        r#"
+        subscription {
+            datasetHeadUpdates(datasetId: "<id>") {
+                oldHead
+                newHead
+            }
+        }
+        "#
+    )
+    .replace("<id>", &foo.dataset_handle.id.to_string());
+
+    let old_head = odf::Multihash::from_digest_sha3_256(b"old");
+    let new_head = odf::Multihash::from_digest_sha3_256(b"new");
+
+    let res = harness
+        .first_response(
+            &request_code,
+            vec![
+                // Other kinds of events are not delivered to this subscription
+                GqlEvent::TaskUpdated(TaskUpdatedEvent {
+                    event_time: Utc::now(),
+                    task_id: kamu_task_system::TaskID::new(1),
+                    dataset_id: foo.dataset_handle.id.clone(),
+                    status: kamu_task_system::TaskStatus::Running,
+                    outcome: None,
+                }),
+                GqlEvent::DatasetHeadUpdated(DatasetHeadUpdatedEvent {
+                    event_time: Utc::now(),
+                    dataset_id: foo.dataset_handle.id.clone(),
+                    old_head: Some(old_head.clone()),
+                    new_head: new_head.clone(),
+                }),
+            ],
+        )
+        .await;
+
+    assert!(res.is_ok(), "{res:?}");
+    assert_eq!(
+        res.data,
+        value!({
+            "datasetHeadUpdates": {
+                "oldHead": old_head.to_string(),
+                "newHead": new_head.to_string(),
+            }
+        })
+    );
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[test_log::test(tokio::test)]
+async fn test_dataset_head_updates_from_reference_messages() {
+    let harness = SubscriptionsHarness::new().await;
+    let foo = harness.create_root_dataset("foo").await;
+
+    let hub = harness.catalog_authorized.get_one::<GqlEventHub>().unwrap();
+    let mut receiver = hub.subscribe();
+
+    // Head moves are reported no matter who moved it, e.g. a push ingest
+    let new_head = odf::Multihash::from_digest_sha3_256(b"new");
+    hub.consume_message(
+        &harness.catalog_authorized,
+        &DatasetReferenceMessage::updated(
+            Utc::now(),
+            foo.dataset_handle.id.clone(),
+            Some(foo.head.clone()),
+            new_head.clone(),
+        ),
+    )
+    .await
+    .unwrap();
+
+    assert_matches!(
+        receiver.recv().await,
+        Ok(GqlEvent::DatasetHeadUpdated(DatasetHeadUpdatedEvent {
+            dataset_id,
+            old_head: Some(old_head),
+            new_head: event_new_head,
+            ..
+        })) if dataset_id == foo.dataset_handle.id
+            && old_head == foo.head
+            && event_new_head == new_head
+    );
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[test_log::test(tokio::test)]
+async fn test_subscription_to_unknown_dataset() {
+    let harness = SubscriptionsHarness::new().await;
+
+    let request_code = indoc!(
+        r#"
+        subscription {
+            taskUpdates(datasetId: "<id>") {
+                taskId
+            }
+        }
+        "#
+    )
+    .replace(
+        "<id>",
+        &odf::DatasetID::new_seeded_ed25519(b"unknown").to_string(),
+    );
+
+    let res = harness.first_response(&request_code, vec![]).await;
+
+    assert!(res.is_err(), "{res:?}");
+    assert_eq!(
+        res.errors
+            .into_iter()
+            .map(|e| e.message)
+            .collect::<Vec<_>>(),
+        vec!["Dataset not found".to_string()]
+    );
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+// Harness
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+struct SubscriptionsHarness {
+    _tempdir: tempfile::TempDir,
+    catalog_authorized: dill::Catalog,
+}
+
+impl SubscriptionsHarness {
+    async fn new() -> Self {
+        let tempdir = tempfile::tempdir().unwrap();
+        let datasets_dir = tempdir.path().join("datasets");
+        std::fs::create_dir(&datasets_dir).unwrap();
+
+        let catalog_base = {
+            let mut b = dill::CatalogBuilder::new();
+
+            b.add::<SystemTimeSourceDefault>()
+                .add::<DidGeneratorDefault>()
+                .add::<DummyOutboxImpl>()
+                .add::<GqlEventHub>()
+                .add::<CreateDatasetFromSnapshotUseCaseImpl>()
+                .add::<CreateDatasetUseCaseImpl>()
+                .add::<ViewDatasetUseCaseImpl>()
+                .add::<InMemoryDatasetDependencyRepository>()
+                .add_value(TenancyConfig::MultiTenant)
+                .add_builder(
+                    odf::dataset::DatasetStorageUnitLocalFs::builder().with_root(datasets_dir),
+                )
+                .bind::<dyn odf::DatasetStorageUnit, odf::dataset::DatasetStorageUnitLocalFs>()
+                .bind::<dyn odf::DatasetStorageUnitWriter, odf::dataset::DatasetStorageUnitLocalFs>(
+                )
+                .add_value(MockAuthenticationService::built_in())
+                .bind::<dyn AuthenticationService, MockAuthenticationService>()
+                .add::<auth::AlwaysHappyDatasetActionAuthorizer>()
+                .add::<DependencyGraphServiceImpl>()
+                .add::<DatasetEntryServiceImpl>()
+                .add::<InMemoryDatasetEntryRepository>();
+
+            NoOpDatabasePlugin::init_database_components(&mut b);
+
+            b.build()
+        };
+
+        let (_, catalog_authorized) = authentication_catalogs(&catalog_base).await;
+
+        Self {
+            _tempdir: tempdir,
+            catalog_authorized,
+        }
+    }
+
+    async fn create_root_dataset(&self, name: &str) -> CreateDatasetResult {
+        let create_dataset_from_snapshot = self
+            .catalog_authorized
+            .get_one::<dyn CreateDatasetFromSnapshotUseCase>()
+            .unwrap();
+
+        create_dataset_from_snapshot
+            .execute(
+                MetadataFactory::dataset_snapshot()
+                    .kind(odf::DatasetKind::Root)
+                    .name(odf::DatasetAlias::new(
+                        Some(DEFAULT_ACCOUNT_NAME.clone()),
+                        odf::DatasetName::new_unchecked(name),
+                    ))
+                    .build(),
+                Default::default(),
+            )
+            .await
+            .unwrap()
+    }
+
+    /// Subscribes and keeps publishing the events until the first response
+    /// arrives, as the subscription is established only once the stream is
+    /// polled
+    async fn first_response(&self, request_code: &str, events: Vec<GqlEvent>) -> Response {
+        let hub = self.catalog_authorized.get_one::<GqlEventHub>().unwrap();
+        let publisher = tokio::spawn(async move {
+            loop {
+                for event in &events {
+                    hub.publish(event.clone());
+                }
+                tokio::task::yield_now().await;
+            }
+        });
+
+        let schema = kamu_adapter_graphql::schema_quiet();
+        let mut stream =
+            schema.execute_stream(Request::new(request_code).data(self.catalog_authorized.clone()));
+        let res = stream.next().await.unwrap();
+
+        publisher.abort();
+        res
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
@@ -0,0 +1,269 @@
+// Copyright Kamu Data, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::sync::{Arc, Mutex};
+
+use axum::response::{IntoResponse, Response};
+use datafusion::arrow::array::RecordBatch;
+use datafusion::arrow::datatypes::SchemaRef;
+use datafusion::arrow::ipc::writer::StreamWriter;
+use datafusion::execution::SendableRecordBatchStream;
+use datafusion::parquet::arrow::ArrowWriter;
+use datafusion::prelude::DataFrame;
+use futures::StreamExt;
+use http_common::{ApiError, IntoApiError};
+use internal_error::*;
+use odf::utils::data::format::{CsvWriter, CsvWriterOptions, RecordsWriter};
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+// TODO: Externalize
+/// Parquet row group is flushed to the client once its in-memory size reaches
+/// this threshold
+const MAX_PARQUET_ROW_GROUP_BUFFER_SIZE: usize = 16 * 1024 * 1024;
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+/// Binary encodings of the resulting data that are selected via the `Accept`
+/// header. Unlike JSON formats, these are streamed to the client batch by
+/// batch without buffering the entire result in memory.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BinaryDataFormat {
+    ArrowIpc,
+    Parquet,
+    Csv,
+}
+
+impl BinaryDataFormat {
+    pub const MEDIA_TYPE_ARROW_IPC: &'static str = "application/vnd.apache.arrow.stream";
+    pub const MEDIA_TYPE_PARQUET: &'static str = "application/vnd.apache.parquet";
+    pub const MEDIA_TYPE_CSV: &'static str = "text/csv";
+    const MEDIA_TYPE_JSON: &'static str = "application/json";
+
+    /// Picks the most preferred of the supported formats listed in the
+    /// `Accept` header, honoring the quality values of media ranges. Returns
+    /// `None` when JSON response should be used, which is also the case when
+    /// the header is absent or the client accepts any type. Fails when none of
+    /// the listed media ranges can be served.
+    pub fn from_accept_header(
+        headers: &http::HeaderMap,
+    ) -> Result<Option<Self>, NotAcceptableError> {
+        let mut media_ranges: Vec<(&str, f32)> = headers
+            .get_all(http::header::ACCEPT)
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .flat_map(|value| value.split(','))
+            .filter_map(parse_media_range)
+            .collect();
+
+        if media_ranges.is_empty() {
+            return Ok(None);
+        }
+
+        // Stable sort keeps the order of the header for the ranges of equal weight
+        media_ranges.sort_by(|(_, lhs), (_, rhs)| rhs.total_cmp(lhs));
+
+        media_ranges
+            .into_iter()
+            .filter(|(_, quality)| *quality > 0.0)
+            .find_map(
+                |(media_range, _)| match media_range.to_ascii_lowercase().as_str() {
+                    Self::MEDIA_TYPE_JSON | "application/*" | "*/*" => Some(None),
+                    "text/*" => Some(Some(Self::Csv)),
+                    media_type => Self::from_media_type(media_type).map(Some),
+                },
+            )
+            .ok_or(NotAcceptableError {})
+    }
+
+    pub fn from_media_type(media_type: &str) -> Option<Self> {
+        match media_type.to_ascii_lowercase().as_str() {
+            Self::MEDIA_TYPE_ARROW_IPC => Some(Self::ArrowIpc),
+            Self::MEDIA_TYPE_PARQUET | "application/x-parquet" => Some(Self::Parquet),
+            Self::MEDIA_TYPE_CSV => Some(Self::Csv),
+            _ => None,
+        }
+    }
+
+    pub fn media_type(self) -> &'static str {
+        match self {
+            Self::ArrowIpc => Self::MEDIA_TYPE_ARROW_IPC,
+            Self::Parquet => Self::MEDIA_TYPE_PARQUET,
+            Self::Csv => Self::MEDIA_TYPE_CSV,
+        }
+    }
+}
+
+/// Splits the media range from its parameters and extracts the quality value,
+/// skipping malformed entries
+fn parse_media_range(value: &str) -> Option<(&str, f32)> {
+    let mut parts = value.split(';').map(str::trim);
+
+    let media_range = parts.next().filter(|media_range| !media_range.is_empty())?;
+
+    let mut quality = 1.0;
+    for param in parts {
+        if let Some((name, value)) = param.split_once('=')
+            && name.trim().eq_ignore_ascii_case("q")
+        {
+            quality = value.trim().parse::<f32>().ok()?.clamp(0.0, 1.0);
+        }
+    }
+
+    Some((media_range, quality))
+}
+
+#[derive(Debug, thiserror::Error)]
+#[error(
+    "None of the accepted media types is supported, expected one of: application/json, {}, {}, {}",
+    BinaryDataFormat::MEDIA_TYPE_ARROW_IPC,
+    BinaryDataFormat::MEDIA_TYPE_PARQUET,
+    BinaryDataFormat::MEDIA_TYPE_CSV
+)]
+pub struct NotAcceptableError {}
+
+impl IntoApiError for NotAcceptableError {
+    fn api_err(self) -> ApiError {
+        ApiError::new(self, http::StatusCode::NOT_ACCEPTABLE)
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+/// Executes the data frame and streams the encoded record batches as the
+/// response body
+pub(crate) async fn data_stream_response(
+    df: DataFrame,
+    format: BinaryDataFormat,
+) -> Result<Response, ApiError> {
+    let schema: SchemaRef = df.schema().inner().clone();
+    let batches = df.execute_stream().await.int_err().api_err()?;
+
+    let buffer = SharedBuffer::default();
+    let encoder = BatchEncoder::new(format, schema, buffer.clone()).api_err()?;
+
+    let state = EncodingState {
+        batches,
+        encoder: Some(encoder),
+        buffer,
+    };
+
+    let body_stream = futures::stream::try_unfold(state, |mut state| async move {
+        loop {
+            let Some(encoder) = state.encoder.as_mut() else {
+                return Ok::<_, InternalError>(None);
+            };
+
+            match state.batches.next().await {
+                Some(batch) => encoder.write(&batch.int_err()?)?,
+                None => state.encoder.take().unwrap().finish()?,
+            }
+
+            let chunk = state.buffer.take();
+            if !chunk.is_empty() {
+                return Ok(Some((chunk, state)));
+            }
+        }
+    });
+
+    Ok((
+        [(http::header::CONTENT_TYPE, format.media_type())],
+        axum::body::Body::from_stream(body_stream),
+    )
+        .into_response())
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+struct EncodingState {
+    batches: SendableRecordBatchStream,
+    encoder: Option<BatchEncoder>,
+    buffer: SharedBuffer,
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+enum BatchEncoder {
+    ArrowIpc(StreamWriter<SharedBuffer>),
+    Parquet(ArrowWriter<SharedBuffer>),
+    Csv(CsvWriter<SharedBuffer>),
+}
+
+impl BatchEncoder {
+    fn new(
+        format: BinaryDataFormat,
+        schema: SchemaRef,
+        buffer: SharedBuffer,
+    ) -> Result<Self, InternalError> {
+        match format {
+            BinaryDataFormat::ArrowIpc => Ok(Self::ArrowIpc(
+                StreamWriter::try_new(buffer, &schema).int_err()?,
+            )),
+            BinaryDataFormat::Parquet => Ok(Self::Parquet(
+                ArrowWriter::try_new(buffer, schema, None).int_err()?,
+            )),
+            BinaryDataFormat::Csv => {
+                let mut writer = CsvWriter::new(buffer, CsvWriterOptions::default());
+                // Writes the header even if the result turns out to be empty
+                writer
+                    .write_batch(&RecordBatch::new_empty(schema))
+                    .int_err()?;
+                Ok(Self::Csv(writer))
+            }
+        }
+    }
+
+    fn write(&mut self, batch: &RecordBatch) -> Result<(), InternalError> {
+        match self {
+            Self::ArrowIpc(writer) => writer.write(batch).int_err(),
+            Self::Parquet(writer) => {
+                writer.write(batch).int_err()?;
+                if writer.in_progress_size() >= MAX_PARQUET_ROW_GROUP_BUFFER_SIZE {
+                    writer.flush().int_err()?;
+                }
+                Ok(())
+            }
+            Self::Csv(writer) => writer.write_batch(batch).int_err(),
+        }
+    }
+
+    fn finish(self) -> Result<(), InternalError> {
+        match self {
+            Self::ArrowIpc(mut writer) => writer.finish().int_err(),
+            Self::Parquet(writer) => writer.close().map(|_| ()).int_err(),
+            Self::Csv(mut writer) => writer.finish().int_err(),
+        }
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+/// Accumulates the output of the encoders until it's taken as the next chunk
+/// of the response body
+#[derive(Debug, Default, Clone)]
+struct SharedBuffer(Arc<Mutex<Vec<u8>>>);
+
+impl SharedBuffer {
+    fn take(&self) -> bytes::Bytes {
+        std::mem::take(&mut *self.0.lock().unwrap()).into()
+    }
+}
+
+impl std::io::Write for SharedBuffer {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.lock().unwrap().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
@@ -0,0 +1,285 @@
+// Copyright Kamu Data, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::convert::Infallible;
+use std::sync::Arc;
+
+use axum::extract::{Extension, Query};
+use axum::response::sse::{Event, KeepAlive, Sse};
+use axum::response::{IntoResponse, Response};
+use database_common::DatabaseTransactionRunner;
+use dill::Catalog;
+use http_common::*;
+use internal_error::{ErrorIntoInternal, InternalError, ResultIntoInternal};
+use kamu_core::*;
+use time_source::SystemTimeSource;
+
+use super::query_types::DataFormat;
+use crate::DatasetAliasInPath;
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+const CHANGES_POLL_INTERVAL_MS: i64 = 1000;
+const MAX_RECORDS_PER_EVENT: u64 = 10_000;
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+/// Stream records as they are committed to a dataset
+///
+/// Responds with a stream of server-sent events (`text/event-stream`). Every
+/// `changes` event carries a batch of new records ordered by offset, including
+/// the `op` column, so that retractions and corrections can be applied by the
+/// consumer. The `id` of the event is the offset of the last record in the
+/// batch - to resume after a disconnect pass it back via the `Last-Event-ID`
+/// header (`EventSource` clients do this automatically) or the `sinceOffset`
+/// parameter. If reading changes fails the stream is terminated with an
+/// `error` event.
+#[utoipa::path(
+    get,
+    path = "/changes",
+    params(DatasetChangesParams, DatasetAliasInPath),
+    responses((status = OK, content(
+        (() = "text/event-stream")
+    ))),
+    tag = "odf-query",
+    security(
+        (),
+        ("api_key" = [])
+    )
+)]
+#[tracing::instrument(level = "info", skip_all, fields(%dataset_ref))]
+pub async fn dataset_changes_handler(
+    Extension(catalog): Extension<Catalog>,
+    Extension(dataset_ref): Extension<odf::DatasetRef>,
+    headers: http::HeaderMap,
+    Query(params): Query<DatasetChangesParams>,
+) -> Result<Response, ApiError> {
+    tracing::debug!(request = ?params, "Changes");
+
+    if params.max_records_per_event == 0 || params.max_records_per_event > MAX_RECORDS_PER_EVENT {
+        return Err(ApiError::bad_request(InvalidMaxRecordsPerEvent));
+    }
+
+    let since_offset = match headers.get("last-event-id") {
+        Some(value) => Some(
+            value
+                .to_str()
+                .ok()
+                .and_then(|s| s.parse::<u64>().ok())
+                .ok_or_else(|| ApiError::bad_request(InvalidLastEventId))?,
+        ),
+        None => params.since_offset,
+    };
+
+    // Read the first chunk eagerly, so that the missing dataset or lack of access
+    // are reported with a proper status code instead of an error event
+    let first_chunk = read_changes(
+        &catalog,
+        &dataset_ref,
+        &ChangeFeedCursor::since_offset(since_offset),
+        params.max_records_per_event,
+    )
+    .await
+    .map_err(|e| match e {
+        QueryError::DatasetNotFound(e) => ApiError::not_found(e),
+        QueryError::Access(_) => ApiError::not_found_without_reason(),
+        QueryError::DatasetBlockNotFound(_)
+        | QueryError::DatasetSchemaNotAvailable(_)
+        | QueryError::DataFusionError(_) => e.int_err().api_err(),
+        QueryError::Internal(e) => e.api_err(),
+    })?;
+
+    let state = ChangesStreamState {
+        time_source: catalog.get_one().int_err().api_err()?,
+        catalog,
+        dataset_ref,
+        max_records: params.max_records_per_event,
+        data_format: params.data_format,
+        next_chunk: Some(first_chunk),
+        cursor: ChangeFeedCursor::default(),
+        caught_up: false,
+        terminated: false,
+    };
+
+    let events = futures::stream::unfold(state, |mut state| async move {
+        let event = state.next_event().await?;
+        Some((Ok::<_, Infallible>(event), state))
+    });
+
+    Ok(Sse::new(events)
+        .keep_alive(KeepAlive::default())
+        .into_response())
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+async fn read_changes(
+    catalog: &Catalog,
+    dataset_ref: &odf::DatasetRef,
+    cursor: &ChangeFeedCursor,
+    max_records: u64,
+) -> Result<DatasetChangesChunk, QueryError> {
+    // Every read runs in its own transaction, as the stream can stay open for an
+    // arbitrarily long time
+    DatabaseTransactionRunner::new(catalog.clone())
+        .transactional_with(
+            |change_feed_service: Arc<dyn DatasetChangeFeedService>| async move {
+                change_feed_service
+                    .read_changes(dataset_ref, cursor, max_records)
+                    .await
+            },
+        )
+        .await
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+struct ChangesStreamState {
+    catalog: Catalog,
+    time_source: Arc<dyn SystemTimeSource>,
+    dataset_ref: odf::DatasetRef,
+    max_records: u64,
+    data_format: DataFormat,
+    next_chunk: Option<DatasetChangesChunk>,
+    cursor: ChangeFeedCursor,
+    caught_up: bool,
+    terminated: bool,
+}
+
+impl ChangesStreamState {
+    async fn next_event(&mut self) -> Option<Event> {
+        if self.terminated {
+            return None;
+        }
+
+        loop {
+            let chunk = match self.next_chunk.take() {
+                Some(chunk) => chunk,
+                None => {
+                    if self.caught_up {
+                        self.time_source
+                            .sleep(chrono::Duration::milliseconds(CHANGES_POLL_INTERVAL_MS))
+                            .await;
+                    }
+
+                    match read_changes(
+                        &self.catalog,
+                        &self.dataset_ref,
+                        &self.cursor,
+                        self.max_records,
+                    )
+                    .await
+                    {
+                        Ok(chunk) => chunk,
+                        Err(err) => {
+                            tracing::error!(error = ?err, error_msg = %err, "Reading changes failed");
+                            self.terminated = true;
+                            return Some(Event::default().event("error").data(err.to_string()));
+                        }
+                    }
+                }
+            };
+
+            self.cursor = chunk.cursor.clone();
+            self.caught_up = chunk.caught_up;
+
+            if chunk.num_records == 0 {
+                continue;
+            }
+
+            match self.changes_event(chunk) {
+                Ok(event) => return Some(event),
+                Err(err) => {
+                    tracing::error!(error = ?err, error_msg = %err, "Encoding changes failed");
+                    self.terminated = true;
+                    return Some(Event::default().event("error").data(err.to_string()));
+                }
+            }
+        }
+    }
+
+    fn changes_event(&self, chunk: DatasetChangesChunk) -> Result<Event, InternalError> {
+        let json = super::query_types::serialize_data(&chunk.record_batches, self.data_format)?;
+        let data = serde_json::value::RawValue::from_string(json).int_err()?;
+
+        // Safety: Non-empty chunk always advances the offset
+        let last_offset = chunk.cursor.last_offset.unwrap();
+
+        let payload = serde_json::to_string(&DatasetChangesEvent {
+            data,
+            data_format: self.data_format,
+            num_records: chunk.num_records,
+            last_offset,
+        })
+        .int_err()?;
+
+        Ok(Event::default()
+            .event("changes")
+            .id(last_offset.to_string())
+            .data(payload))
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[derive(Debug, serde::Deserialize, utoipa::IntoParams)]
+#[serde(rename_all = "camelCase")]
+#[into_params(parameter_in = Query)]
+pub struct DatasetChangesParams {
+    /// Offset of the last record already seen by the consumer - only records
+    /// with greater offsets will be streamed. Ignored when `Last-Event-ID`
+    /// header is present. If not specified the stream starts from the very
+    /// first record.
+    pub since_offset: Option<u64>,
+
+    /// Maximum number of records to send in one event
+    #[serde(default = "DatasetChangesParams::default_max_records_per_event")]
+    pub max_records_per_event: u64,
+
+    /// How the records should be encoded
+    #[serde(default)]
+    pub data_format: DataFormat,
+}
+
+impl DatasetChangesParams {
+    fn default_max_records_per_event() -> u64 {
+        1000
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+/// Payload of the `changes` event
+#[derive(Debug, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DatasetChangesEvent {
+    /// New records
+    pub data: Box<serde_json::value::RawValue>,
+
+    /// How data is laid out in the event
+    pub data_format: DataFormat,
+
+    /// Number of records in this event
+    pub num_records: u64,
+
+    /// Offset of the last record in this event
+    pub last_offset: u64,
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[derive(Debug, thiserror::Error)]
+#[error("maxRecordsPerEvent must be between 1 and {MAX_RECORDS_PER_EVENT}")]
+struct InvalidMaxRecordsPerEvent;
+
+#[derive(Debug, thiserror::Error)]
+#[error("Last-Event-ID must be a record offset")]
+struct InvalidLastEventId;
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
@@ -16,6 +16,8 @@ use http::HeaderMap;
 use http_common::*;
 use internal_error::ErrorIntoInternal;
 use kamu_core::*;
+use kamu_datasets::{DatasetReferenceMessage, MESSAGE_PRODUCER_KAMU_DATASET_REFERENCE_SERVICE};
+use messaging_outbox::{Outbox, OutboxExt};
 use time_source::SystemTimeSource;
 use tokio::io::AsyncRead;
 
@@ -160,6 +162,8 @@ pub async fn dataset_ingest_handler(
             PushIngestPlanningError::Internal(e) => e.api_err(),
         })?;
 
+    let dataset_id = target.get_id().clone();
+
     let push_ingest_executor = catalog.get_one::<dyn PushIngestExecutor>().unwrap();
     match push_ingest_executor
         .ingest_from_stream(target, ingest_plan, arguments.data_stream, None)
@@ -167,8 +171,28 @@ pub async fn dataset_ingest_handler(
     {
         // Per note above, we're not including any extra information about the result
         // of the ingest operation at this point to accommodate async execution
-        Ok(_) => Ok(()),
+        Ok(PushIngestResult::UpToDate) => Ok(()),
+        Ok(PushIngestResult::Updated {
+            old_head, new_head, ..
+        }) => {
+            let time_source = catalog.get_one::<dyn SystemTimeSource>().unwrap();
+            let outbox = catalog.get_one::<dyn Outbox>().unwrap();
+            outbox
+                .post_message(
+                    MESSAGE_PRODUCER_KAMU_DATASET_REFERENCE_SERVICE,
+                    DatasetReferenceMessage::updated(
+                        time_source.now(),
+                        dataset_id,
+                        Some(old_head),
+                        new_head,
+                    ),
+                )
+                .await
+                .api_err()?;
+            Ok(())
+        }
         Err(PushIngestError::ReadError(e)) => Err(ApiError::bad_request(e)),
+        Err(PushIngestError::ExpectationsFailed(e)) => Err(ApiError::bad_request(e)),
         Err(PushIngestError::UnsupportedMediaType(_)) => {
             Err(ApiError::new_unsupported_media_type())
         }
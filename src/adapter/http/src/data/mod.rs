@@ -7,6 +7,8 @@
 // the Business Source License, use of this software will be governed
 // by the Apache License, Version 2.0.
 
+pub mod binary_format;
+pub mod changes_handler;
 mod ingest_handler;
 pub mod metadata_handler;
 mod query_handler;
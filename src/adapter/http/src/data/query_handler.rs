@@ -8,13 +8,14 @@
 // by the Apache License, Version 2.0.
 
 use axum::extract::{Extension, Query};
-use axum::response::Json;
+use axum::response::{IntoResponse, Json, Response};
 use database_common_macros::transactional_handler;
 use dill::Catalog;
 use http_common::*;
 use internal_error::*;
 use kamu_core::*;
 
+use super::binary_format::{data_stream_response, BinaryDataFormat};
 use super::query_types::{QueryResponse, *};
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
@@ -127,11 +128,29 @@ use super::query_types::{QueryResponse, *};
 /// [`/verify`](#tag/odf-query/POST/verify) endpoint.
 ///
 /// See [commitments documentation](https://docs.kamu.dev/node/commitments) for details.
+///
+/// ### Binary Formats
+/// Large results can be fetched without the JSON overhead by requesting one
+/// of the following formats via the `Accept` header:
+/// - `application/vnd.apache.arrow.stream` - Arrow IPC stream
+/// - `application/vnd.apache.parquet` - Parquet file
+/// - `text/csv` - CSV with a header row
+///
+/// Data is then streamed in the response body batch by batch, while
+/// `dataFormat` and `schemaFormat` parameters are ignored. Inputs and proofs
+/// can only be included into JSON responses. Quality values of the listed
+/// media types are respected, and `406 Not Acceptable` is returned when none
+/// of them is supported.
 #[utoipa::path(
     post,
     path = "/query",
     request_body = QueryRequest,
-    responses((status = OK, body = QueryResponse)),
+    responses((status = OK, content(
+        (QueryResponse = "application/json"),
+        (() = "application/vnd.apache.arrow.stream"),
+        (() = "application/vnd.apache.parquet"),
+        (() = "text/csv")
+    ))),
     tag = "odf-query",
     security(
         (),
@@ -141,9 +160,10 @@ use super::query_types::{QueryResponse, *};
 #[transactional_handler]
 pub async fn query_handler_post(
     Extension(catalog): Extension<Catalog>,
+    headers: http::HeaderMap,
     Json(body): Json<QueryRequest>,
-) -> Result<Json<QueryResponse>, ApiError> {
-    query_handler_impl(catalog, body).await
+) -> Result<Response, ApiError> {
+    query_handler_negotiated(catalog, body, &headers).await
 }
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
@@ -156,7 +176,12 @@ pub async fn query_handler_post(
     get,
     path = "/query",
     params(QueryParams),
-    responses((status = OK, body = QueryResponse)),
+    responses((status = OK, content(
+        (QueryResponse = "application/json"),
+        (() = "application/vnd.apache.arrow.stream"),
+        (() = "application/vnd.apache.parquet"),
+        (() = "text/csv")
+    ))),
     tag = "odf-query",
     security(
         (),
@@ -166,30 +191,47 @@ pub async fn query_handler_post(
 #[transactional_handler]
 pub async fn query_handler(
     Extension(catalog): Extension<Catalog>,
+    headers: http::HeaderMap,
     Query(params): Query<QueryParams>,
-) -> Result<Json<QueryResponse>, ApiError> {
-    query_handler_impl(catalog, params.into()).await
+) -> Result<Response, ApiError> {
+    query_handler_negotiated(catalog, params.into(), &headers).await
 }
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
 
-pub async fn query_handler_impl(
+async fn query_handler_negotiated(
     catalog: Catalog,
-    mut body: QueryRequest,
-) -> Result<Json<QueryResponse>, ApiError> {
-    tracing::debug!(request = ?body, "Query");
-
-    // Automatically add `Input` if proof is requested, as proof depends on input
-    // for verifiability
-    if body.include.contains(&Include::Proof) {
-        body.include.insert(Include::Input);
+    body: QueryRequest,
+    headers: &http::HeaderMap,
+) -> Result<Response, ApiError> {
+    match BinaryDataFormat::from_accept_header(headers).api_err()? {
+        Some(binary_format) => query_handler_binary_impl(catalog, body, binary_format).await,
+        None => Ok(query_handler_impl(catalog, body).await?.into_response()),
     }
-    // Automatically add `Schema` if user specified schema format
-    if body.schema_format.is_some() {
-        body.include.insert(Include::Schema);
+}
+
+async fn query_handler_binary_impl(
+    catalog: Catalog,
+    body: QueryRequest,
+    binary_format: BinaryDataFormat,
+) -> Result<Response, ApiError> {
+    tracing::debug!(request = ?body, ?binary_format, "Query");
+
+    if body.include.contains(&Include::Input) || body.include.contains(&Include::Proof) {
+        return Err(ApiError::bad_request(InputsNotSupportedInBinaryFormats));
     }
 
-    let identity = catalog.get_one::<IdentityConfig>().ok();
+    let (df, _) = execute_query(&catalog, &body).await?;
+
+    data_stream_response(df, binary_format).await
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+async fn execute_query(
+    catalog: &Catalog,
+    body: &QueryRequest,
+) -> Result<(datafusion::prelude::DataFrame, QueryState), ApiError> {
     let query_svc = catalog.get_one::<dyn QueryService>().unwrap();
 
     let res = query_svc
@@ -207,6 +249,31 @@ pub async fn query_handler_impl(
         .int_err()
         .api_err()?;
 
+    Ok((df, res.state))
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+pub async fn query_handler_impl(
+    catalog: Catalog,
+    mut body: QueryRequest,
+) -> Result<Json<QueryResponse>, ApiError> {
+    tracing::debug!(request = ?body, "Query");
+
+    // Automatically add `Input` if proof is requested, as proof depends on input
+    // for verifiability
+    if body.include.contains(&Include::Proof) {
+        body.include.insert(Include::Input);
+    }
+    // Automatically add `Schema` if user specified schema format
+    if body.schema_format.is_some() {
+        body.include.insert(Include::Schema);
+    }
+
+    let identity = catalog.get_one::<IdentityConfig>().ok();
+
+    let (df, state) = execute_query(&catalog, &body).await?;
+
     let (schema, schema_format) = if body.include.contains(&Include::Schema) {
         let schema_format = body.schema_format.unwrap_or_default();
         (
@@ -236,7 +303,7 @@ pub async fn query_handler_impl(
         if !body.include.contains(&Include::Input) && !body.include.contains(&Include::Proof) {
             None
         } else {
-            body.datasets = Some(QueryRequest::query_state_to_datasets(res.state));
+            body.datasets = Some(QueryRequest::query_state_to_datasets(state));
             body.schema_format = schema_format;
             Some(body)
         };
@@ -291,4 +358,8 @@ pub async fn query_handler_impl(
 #[error("Response signing is not enabled by the node operator")]
 struct ResponseSigningNotConfigured;
 
+#[derive(Debug, thiserror::Error)]
+#[error("Inputs and proofs can only be included into JSON responses")]
+struct InputsNotSupportedInBinaryFormats;
+
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
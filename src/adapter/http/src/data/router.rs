@@ -26,6 +26,7 @@ pub fn root_router() -> OpenApiRouter {
 pub fn dataset_router() -> OpenApiRouter {
     OpenApiRouter::new()
         .routes(routes!(super::tail_handler::dataset_tail_handler))
+        .routes(routes!(super::changes_handler::dataset_changes_handler))
         .routes(routes!(super::metadata_handler::dataset_metadata_handler))
         .routes(routes!(super::ingest_handler::dataset_ingest_handler))
 }
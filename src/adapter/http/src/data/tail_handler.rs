@@ -8,24 +8,35 @@
 // by the Apache License, Version 2.0.
 
 use axum::extract::{Extension, Query};
-use axum::response::Json;
+use axum::response::{IntoResponse, Json, Response};
 use database_common_macros::transactional_handler;
 use dill::Catalog;
 use http_common::*;
 use internal_error::{ErrorIntoInternal, ResultIntoInternal};
 use kamu_core::*;
 
+use super::binary_format::{data_stream_response, BinaryDataFormat};
 use super::query_types::{DataFormat, Schema, SchemaFormat};
 use crate::DatasetAliasInPath;
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
 
 /// Get a sample of latest events
+///
+/// Besides JSON, the data can be streamed as Arrow IPC
+/// (`application/vnd.apache.arrow.stream`), Parquet
+/// (`application/vnd.apache.parquet`) or CSV (`text/csv`) when requested via
+/// the `Accept` header.
 #[utoipa::path(
     get,
     path = "/tail",
     params(DatasetTailParams, DatasetAliasInPath),
-    responses((status = OK, body = DatasetTailResponse)),
+    responses((status = OK, content(
+        (DatasetTailResponse = "application/json"),
+        (() = "application/vnd.apache.arrow.stream"),
+        (() = "application/vnd.apache.parquet"),
+        (() = "text/csv")
+    ))),
     tag = "odf-query",
     security(
         (),
@@ -37,8 +48,9 @@ use crate::DatasetAliasInPath;
 pub async fn dataset_tail_handler(
     Extension(catalog): Extension<Catalog>,
     Extension(dataset_ref): Extension<odf::DatasetRef>,
+    headers: http::HeaderMap,
     Query(params): Query<DatasetTailParams>,
-) -> Result<Json<DatasetTailResponse>, ApiError> {
+) -> Result<Response, ApiError> {
     tracing::debug!(request = ?params, "Tail");
 
     let query_svc = catalog.get_one::<dyn QueryService>().unwrap();
@@ -56,6 +68,10 @@ pub async fn dataset_tail_handler(
             QueryError::Internal(e) => e.api_err(),
         })?;
 
+    if let Some(binary_format) = BinaryDataFormat::from_accept_header(&headers).api_err()? {
+        return data_stream_response(df, binary_format).await;
+    }
+
     let schema = params
         .schema_format
         .map(|fmt| Schema::new(df.schema().inner().clone(), fmt));
@@ -69,7 +85,8 @@ pub async fn dataset_tail_handler(
         data_format: params.data_format,
         schema,
         schema_format: params.schema_format,
-    }))
+    })
+    .into_response())
 }
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
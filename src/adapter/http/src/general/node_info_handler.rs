@@ -11,7 +11,7 @@ use axum::extract::Extension;
 use axum::response::Json;
 use dill::Catalog;
 use http_common::*;
-use kamu_core::TenancyConfig;
+use kamu_core::{ServerUrlConfig, TenancyConfig};
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
 
@@ -19,6 +19,9 @@ use kamu_core::TenancyConfig;
 #[serde(rename_all = "camelCase")]
 pub struct NodeInfoResponse {
     pub is_multi_tenant: bool,
+    /// Address of the Flight SQL endpoint, if the node exposes one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub flight_sql_url: Option<String>,
 }
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
@@ -44,9 +47,12 @@ pub async fn node_info_handler(
 
 fn get_node_info(catalog: &Catalog) -> Json<NodeInfoResponse> {
     let tenancy_config = catalog.get_one::<TenancyConfig>().unwrap();
+    let server_url_config = catalog.get_one::<ServerUrlConfig>().ok();
 
     Json(NodeInfoResponse {
         is_multi_tenant: *tenancy_config == TenancyConfig::MultiTenant,
+        flight_sql_url: server_url_config
+            .map(|config| config.protocols.base_url_flightsql.to_string()),
     })
 }
 
@@ -129,6 +129,7 @@ pub async fn platform_login_handler(
             kamu_accounts::LoginError::InvalidCredentials(e) => ApiError::new_unauthorized_from(e),
             kamu_accounts::LoginError::RejectedCredentials(e) => ApiError::new_unauthorized_from(e),
             kamu_accounts::LoginError::NoPrimaryEmail(e) => ApiError::new_unauthorized_from(e),
+            kamu_accounts::LoginError::EmailNotVerified(e) => ApiError::new_unauthorized_from(e),
             kamu_accounts::LoginError::DuplicateCredentials => ApiError::bad_request(e),
             kamu_accounts::LoginError::Internal(e) => e.api_err(),
         }),
@@ -137,6 +138,117 @@ pub async fn platform_login_handler(
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
 
+#[derive(Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct RegisterRequestBody {
+    #[schema(value_type = String)]
+    pub account_name: odf::AccountName,
+    pub email: String,
+    pub password: String,
+}
+
+#[derive(Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct RegisterResponseBody {
+    #[schema(value_type = String)]
+    pub account_id: odf::AccountID,
+    #[schema(value_type = String)]
+    pub account_name: odf::AccountName,
+}
+
+/// Register a new account authenticated by password
+///
+/// The account can be used only after its email is confirmed via
+/// `/platform/verify-email` with the token sent to the specified email.
+#[utoipa::path(
+    post,
+    path = "/platform/register",
+    request_body = RegisterRequestBody,
+    responses(
+        (status = OK, body = RegisterResponseBody),
+        (status = BAD_REQUEST, body = ApiErrorResponse),
+        (status = FORBIDDEN, body = ApiErrorResponse),
+        (status = CONFLICT, body = ApiErrorResponse),
+    ),
+    tag = "kamu",
+    security(())
+)]
+#[transactional_handler]
+pub async fn platform_register_handler(
+    Extension(catalog): Extension<Catalog>,
+    Json(payload): Json<RegisterRequestBody>,
+) -> Result<Json<RegisterResponseBody>, ApiError> {
+    let authentication_service = catalog
+        .get_one::<dyn kamu_accounts::AuthenticationService>()
+        .unwrap();
+
+    let email = email_utils::Email::parse(&payload.email).map_err(ApiError::bad_request)?;
+
+    match authentication_service
+        .register_password_account(&payload.account_name, email, payload.password)
+        .await
+    {
+        Ok(account) => Ok(Json(RegisterResponseBody {
+            account_id: account.id,
+            account_name: account.account_name,
+        })),
+        Err(e) => Err(match e {
+            kamu_accounts::RegisterAccountError::RegistrationDisabled(e) => {
+                ApiError::new(e, http::StatusCode::FORBIDDEN)
+            }
+            kamu_accounts::RegisterAccountError::Duplicate(e) => {
+                ApiError::new(e, http::StatusCode::CONFLICT)
+            }
+            kamu_accounts::RegisterAccountError::Internal(e) => e.api_err(),
+        }),
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[derive(Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct VerifyEmailRequestBody {
+    pub verification_token: String,
+}
+
+/// Confirm the email of a registered account and authenticate with the node
+#[utoipa::path(
+    post,
+    path = "/platform/verify-email",
+    request_body = VerifyEmailRequestBody,
+    responses(
+        (status = OK, body = LoginResponseBody),
+        (status = BAD_REQUEST, body = ApiErrorResponse),
+    ),
+    tag = "kamu",
+    security(())
+)]
+#[transactional_handler]
+pub async fn platform_verify_email_handler(
+    Extension(catalog): Extension<Catalog>,
+    Json(payload): Json<VerifyEmailRequestBody>,
+) -> Result<Json<LoginResponseBody>, ApiError> {
+    let authentication_service = catalog
+        .get_one::<dyn kamu_accounts::AuthenticationService>()
+        .unwrap();
+
+    match authentication_service
+        .verify_email(&payload.verification_token)
+        .await
+    {
+        Ok(login_response) => Ok(Json(LoginResponseBody {
+            access_token: login_response.access_token,
+        })),
+        Err(e) => Err(match e {
+            kamu_accounts::VerifyEmailError::InvalidToken(e) => ApiError::bad_request(e),
+            kamu_accounts::VerifyEmailError::Internal(e) => e.api_err(),
+        }),
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
 /// Validate auth token
 #[utoipa::path(
     get,
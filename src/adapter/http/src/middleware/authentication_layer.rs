@@ -16,6 +16,7 @@ use axum::response::Response;
 use axum::RequestExt;
 use database_common::DatabaseTransactionRunner;
 use futures::Future;
+use internal_error::InternalError;
 use kamu_accounts::{
     AccessTokenError,
     AnonymousAccountReason,
@@ -61,59 +62,19 @@ impl<Svc> AuthenticationMiddleware<Svc> {
     ) -> Result<CurrentAccountSubject, Response> {
         use tracing::Instrument;
 
-        if let Some(access_token) = maybe_access_token {
-            let account_res = DatabaseTransactionRunner::new(base_catalog.clone())
-                .transactional_with(
-                    |authentication_service: Arc<dyn AuthenticationService>| async move {
-                        authentication_service
-                            .account_by_token(access_token.token)
-                            .await
-                    },
-                )
-                .instrument(tracing::debug_span!(
-                    "AuthenticationMiddleware::current_account_subject"
-                ))
-                .await;
-
-            // TODO: PERF: Getting the full account info here is expensive while all we need
-            // is the caller identity
-            match account_res {
-                Ok(account) => Ok(CurrentAccountSubject::logged(
-                    account.id,
-                    account.account_name,
-                    account.is_admin,
-                )),
-                Err(GetAccountInfoError::AccessToken(e)) => match e {
-                    AccessTokenError::Expired => Ok(CurrentAccountSubject::anonymous(
-                        AnonymousAccountReason::AuthenticationExpired,
-                    )),
-                    AccessTokenError::Invalid(err) => {
-                        tracing::warn!(error = err, "Ignoring invalid auth token",);
-                        Ok(CurrentAccountSubject::anonymous(
-                            AnonymousAccountReason::AuthenticationInvalid,
-                        ))
-                    }
-                },
-                Err(GetAccountInfoError::AccountUnresolved) => {
-                    tracing::warn!("Ignoring auth token pointing to non-existing account");
-                    Ok(CurrentAccountSubject::anonymous(
-                        AnonymousAccountReason::AuthenticationInvalid,
-                    ))
-                }
-                Err(GetAccountInfoError::Internal(err)) => {
-                    tracing::error!(
-                        error = ?err,
-                        error_msg = %err,
-                        "Internal error during authentication",
-                    );
-                    Err(internal_server_error_response())
-                }
-            }
-        } else {
-            Ok(CurrentAccountSubject::anonymous(
-                AnonymousAccountReason::NoAuthenticationProvided,
+        authenticate_access_token(base_catalog, maybe_access_token)
+            .instrument(tracing::debug_span!(
+                "AuthenticationMiddleware::current_account_subject"
             ))
-        }
+            .await
+            .map_err(|err| {
+                tracing::error!(
+                    error = ?err,
+                    error_msg = %err,
+                    "Internal error during authentication",
+                );
+                internal_server_error_response()
+            })
     }
 }
 
@@ -170,3 +131,61 @@ where
         })
     }
 }
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+/// Resolves the subject behind an access token. Expired, invalid and dangling
+/// tokens result in an anonymous subject, like for the regular requests.
+///
+/// Exposed for the transports that can't pass the token in the request headers,
+/// e.g. the GraphQL subscriptions over WebSocket.
+pub async fn authenticate_access_token(
+    base_catalog: &dill::Catalog,
+    maybe_access_token: Option<AccessToken>,
+) -> Result<CurrentAccountSubject, InternalError> {
+    let Some(access_token) = maybe_access_token else {
+        return Ok(CurrentAccountSubject::anonymous(
+            AnonymousAccountReason::NoAuthenticationProvided,
+        ));
+    };
+
+    let account_res = DatabaseTransactionRunner::new(base_catalog.clone())
+        .transactional_with(
+            |authentication_service: Arc<dyn AuthenticationService>| async move {
+                authentication_service
+                    .account_by_token(access_token.token)
+                    .await
+            },
+        )
+        .await;
+
+    // TODO: PERF: Getting the full account info here is expensive while all we need
+    // is the caller identity
+    match account_res {
+        Ok(account) => Ok(CurrentAccountSubject::logged(
+            account.id,
+            account.account_name,
+            account.is_admin,
+        )),
+        Err(GetAccountInfoError::AccessToken(e)) => match e {
+            AccessTokenError::Expired => Ok(CurrentAccountSubject::anonymous(
+                AnonymousAccountReason::AuthenticationExpired,
+            )),
+            AccessTokenError::Invalid(err) => {
+                tracing::warn!(error = err, "Ignoring invalid auth token",);
+                Ok(CurrentAccountSubject::anonymous(
+                    AnonymousAccountReason::AuthenticationInvalid,
+                ))
+            }
+        },
+        Err(GetAccountInfoError::AccountUnresolved) => {
+            tracing::warn!("Ignoring auth token pointing to non-existing account");
+            Ok(CurrentAccountSubject::anonymous(
+                AnonymousAccountReason::AuthenticationInvalid,
+            ))
+        }
+        Err(GetAccountInfoError::Internal(err)) => Err(err),
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
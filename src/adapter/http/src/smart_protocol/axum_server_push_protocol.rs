@@ -13,16 +13,20 @@ use std::time::Duration;
 
 use database_common::DatabaseTransactionRunner;
 use dill::Catalog;
-use internal_error::ErrorIntoInternal;
-use kamu_core::CorruptedSourceError;
+use internal_error::{ErrorIntoInternal, InternalError, ResultIntoInternal};
+use kamu_core::{CorruptedSourceError, DatasetRegistry};
 use kamu_datasets::{
     AppendDatasetMetadataBatchUseCase,
     AppendDatasetMetadataBatchUseCaseOptions,
     CreateDatasetError,
     CreateDatasetUseCase,
     CreateDatasetUseCaseOptions,
+    DatasetReferenceMessage,
     SetRefCheckRefMode,
+    MESSAGE_PRODUCER_KAMU_DATASET_REFERENCE_SERVICE,
 };
+use messaging_outbox::{Outbox, OutboxExt};
+use time_source::SystemTimeSource;
 use tracing::Instrument;
 use url::Url;
 
@@ -469,18 +473,66 @@ impl AxumServerPushProtocolInstance {
         tracing::debug!("Push client sent a complete request. Committing the dataset");
 
         let dataset = self.maybe_dataset.clone().unwrap();
+        let dataset_ref = self.dataset_ref.clone();
         DatabaseTransactionRunner::new(self.catalog.clone())
-            .transactional_with(
-                |append_dataset_metadata_batch: Arc<dyn AppendDatasetMetadataBatchUseCase>| async move {
-                    append_dataset_metadata_batch
-                        .execute(dataset.as_ref(), Box::new(new_blocks.into_iter()), AppendDatasetMetadataBatchUseCaseOptions {
-                            set_ref_check_ref_mode: Some(SetRefCheckRefMode::ForceUpdateIfDiverged(force_update_if_diverged)),
+            .transactional(|transactional_catalog| async move {
+                let append_dataset_metadata_batch = transactional_catalog
+                    .get_one::<dyn AppendDatasetMetadataBatchUseCase>()
+                    .unwrap();
+
+                let maybe_prev_block_hash = dataset
+                    .as_metadata_chain()
+                    .try_get_ref(&odf::BlockRef::Head)
+                    .await
+                    .int_err()?;
+                let maybe_new_block_hash = new_blocks.back().map(|(hash, _)| hash.clone());
+
+                append_dataset_metadata_batch
+                    .execute(
+                        dataset.as_ref(),
+                        Box::new(new_blocks.into_iter()),
+                        AppendDatasetMetadataBatchUseCaseOptions {
+                            set_ref_check_ref_mode: Some(
+                                SetRefCheckRefMode::ForceUpdateIfDiverged(force_update_if_diverged),
+                            ),
                             ..Default::default()
-                        })
+                        },
+                    )
+                    .await
+                    .int_err()?;
+
+                // Let the subscribers know the head has moved
+                if let Some(new_block_hash) = maybe_new_block_hash {
+                    let dataset_registry = transactional_catalog
+                        .get_one::<dyn DatasetRegistry>()
+                        .unwrap();
+                    let dataset_handle = dataset_registry
+                        .resolve_dataset_handle_by_ref(&dataset_ref)
                         .await
-                },
-            )
-            .instrument(tracing::debug_span!("AxumServerPushProtocolInstance::try_handle_push_complete"))
+                        .int_err()?;
+
+                    let time_source = transactional_catalog
+                        .get_one::<dyn SystemTimeSource>()
+                        .unwrap();
+                    let outbox = transactional_catalog.get_one::<dyn Outbox>().unwrap();
+                    outbox
+                        .post_message(
+                            MESSAGE_PRODUCER_KAMU_DATASET_REFERENCE_SERVICE,
+                            DatasetReferenceMessage::updated(
+                                time_source.now(),
+                                dataset_handle.id,
+                                maybe_prev_block_hash,
+                                new_block_hash,
+                            ),
+                        )
+                        .await?;
+                }
+
+                Ok::<_, InternalError>(())
+            })
+            .instrument(tracing::debug_span!(
+                "AxumServerPushProtocolInstance::try_handle_push_complete"
+            ))
             .await
             .protocol_int_err(PushPhase::CompleteRequest)?;
 
@@ -7,11 +7,13 @@
 // the Business Source License, use of this software will be governed
 // by the Apache License, Version 2.0.
 
+mod upload_cleanup_agent;
 mod upload_handler;
 mod upload_service;
 mod upload_service_local;
 mod upload_service_s3;
 
+pub use upload_cleanup_agent::*;
 pub use upload_handler::*;
 pub use upload_service::*;
 pub use upload_service_local::*;
@@ -0,0 +1,65 @@
+// Copyright Kamu Data, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::sync::Arc;
+
+use dill::*;
+use internal_error::InternalError;
+use time_source::SystemTimeSource;
+
+use crate::{FileUploadLimitConfig, UploadService};
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+/// Periodically removes incomplete multipart uploads that have expired
+pub struct UploadCleanupAgent {
+    upload_service: Arc<dyn UploadService>,
+    upload_config: Arc<FileUploadLimitConfig>,
+    time_source: Arc<dyn SystemTimeSource>,
+}
+
+#[component(pub)]
+#[scope(Singleton)]
+impl UploadCleanupAgent {
+    pub fn new(
+        upload_service: Arc<dyn UploadService>,
+        upload_config: Arc<FileUploadLimitConfig>,
+        time_source: Arc<dyn SystemTimeSource>,
+    ) -> Self {
+        Self {
+            upload_service,
+            upload_config,
+            time_source,
+        }
+    }
+
+    pub async fn run(&self) -> Result<(), InternalError> {
+        loop {
+            // A failed cleanup is not fatal for the server, it will be retried
+            if let Err(e) = self.run_cleanup().await {
+                tracing::error!(error = ?e, error_msg = %e, "Expired uploads cleanup failed");
+            }
+
+            self.time_source
+                .sleep(self.upload_config.multipart().cleanup_interval)
+                .await;
+        }
+    }
+
+    #[tracing::instrument(level = "debug", skip_all)]
+    pub async fn run_cleanup(&self) -> Result<usize, InternalError> {
+        let num_removed = self.upload_service.cleanup_expired_uploads().await?;
+        if num_removed > 0 {
+            tracing::info!(num_removed, "Removed expired incomplete uploads");
+        }
+        Ok(num_removed)
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
@@ -8,6 +8,7 @@
 // by the Apache License, Version 2.0.
 
 use bytes::Bytes;
+use futures::TryStreamExt;
 use http_common::{ApiError, IntoApiError, ResultIntoApiError};
 use internal_error::{ErrorIntoInternal, ResultIntoInternal};
 use kamu_core::MediaType;
@@ -15,9 +16,22 @@ use serde::de::IntoDeserializer as _;
 use serde::Deserialize as _;
 use thiserror::Error;
 
-use super::{UploadContext, UploadTokenBase64Json, UploadTokenIntoStreamError};
+use super::{
+    MultipartUploadContext,
+    UploadContext,
+    UploadToken,
+    UploadTokenBase64Json,
+    UploadTokenIntoStreamError,
+};
 use crate::axum_utils::ensure_authenticated_account;
-use crate::{MakeUploadContextError, SaveUploadError, UploadService};
+use crate::{
+    CompleteMultipartUploadError,
+    MakeUploadContextError,
+    MultipartUploadError,
+    SaveUploadError,
+    SaveUploadPartError,
+    UploadService,
+};
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
 
@@ -64,6 +78,7 @@ pub async fn platform_file_upload_prepare_post_handler(
         Ok(upload_context) => Ok(axum::Json(upload_context)),
         Err(e) => match e {
             MakeUploadContextError::TooLarge(e) => Err(ApiError::bad_request(e)),
+            MakeUploadContextError::InvalidFileName(e) => Err(ApiError::bad_request(e)),
             MakeUploadContextError::Internal(e) => Err(e.api_err()),
         },
     }
@@ -96,12 +111,7 @@ pub async fn platform_file_upload_post_handler(
     axum::extract::Path(upload_param): axum::extract::Path<UploadFromPath>,
     mut multipart: axum::extract::Multipart,
 ) -> Result<(), ApiError> {
-    let account_id = ensure_authenticated_account(&catalog).api_err()?;
-    if account_id.as_multibase().to_stack_string().as_str()
-        != upload_param.upload_token.0.owner_account_id.as_str()
-    {
-        return Err(ApiError::new_forbidden());
-    }
+    ensure_upload_owner(&catalog, &upload_param.upload_token.0)?;
 
     let file_data = match find_correct_multi_part_field(&mut multipart).await {
         Ok(file_data) => file_data,
@@ -159,6 +169,216 @@ pub async fn platform_file_upload_get_handler(
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
 
+/// Prepare resumable file upload in parts
+///
+/// Returns the size of the parts the file should be split into and where
+/// each part should be uploaded. Parts can be uploaded in any order and
+/// retried independently. Incomplete uploads expire after a configured
+/// period of time.
+#[utoipa::path(
+    post,
+    path = "/platform/file/upload/prepare-multipart",
+    params(PlatformFileUploadQuery),
+    responses((status = OK, body = MultipartUploadContext)),
+    tag = "kamu",
+    security(
+        ("api_key" = []),
+    )
+)]
+pub async fn platform_file_upload_prepare_multipart_post_handler(
+    catalog: axum::extract::Extension<dill::Catalog>,
+    axum::extract::Query(query): axum::extract::Query<PlatformFileUploadQuery>,
+) -> Result<axum::Json<MultipartUploadContext>, ApiError> {
+    let account_id = ensure_authenticated_account(&catalog).api_err()?;
+
+    let upload_service = catalog.get_one::<dyn UploadService>().unwrap();
+    match upload_service
+        .make_multipart_upload_context(
+            &account_id,
+            query.file_name,
+            query.content_type,
+            query.content_length,
+        )
+        .await
+    {
+        Ok(upload_context) => Ok(axum::Json(upload_context)),
+        Err(e) => match e {
+            MakeUploadContextError::TooLarge(e) => Err(ApiError::bad_request(e)),
+            MakeUploadContextError::InvalidFileName(e) => Err(ApiError::bad_request(e)),
+            MakeUploadContextError::Internal(e) => Err(e.api_err()),
+        },
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+/// Get state of a multipart file upload
+///
+/// Lists parts that were already received and provides upload locations for
+/// the pending ones, allowing an interrupted upload to be resumed.
+#[utoipa::path(
+    get,
+    path = "/platform/file/upload/{upload_token}/parts",
+    params(UploadFromPath),
+    responses((status = OK, body = MultipartUploadContext)),
+    tag = "kamu",
+    security(
+        ("api_key" = []),
+    )
+)]
+pub async fn platform_file_upload_parts_get_handler(
+    catalog: axum::extract::Extension<dill::Catalog>,
+    axum::extract::Path(upload_param): axum::extract::Path<UploadFromPath>,
+) -> Result<axum::Json<MultipartUploadContext>, ApiError> {
+    ensure_upload_owner(&catalog, &upload_param.upload_token.0)?;
+
+    let upload_service = catalog.get_one::<dyn UploadService>().unwrap();
+    let upload_context = upload_service
+        .multipart_upload_status(&upload_param.upload_token.0)
+        .await
+        .map_err(|e| match e {
+            MultipartUploadError::NotFound(e) => ApiError::not_found(e),
+            MultipartUploadError::Internal(e) => e.api_err(),
+        })?;
+
+    Ok(axum::Json(upload_context))
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[derive(serde::Deserialize, utoipa::IntoParams)]
+#[into_params(parameter_in = Path)]
+pub struct UploadPartFromPath {
+    #[param(value_type = String)]
+    upload_token: UploadTokenBase64Json,
+
+    /// 1-based number of the part
+    part_number: usize,
+}
+
+/// Upload a single part of a multipart file upload
+///
+/// The request body is the raw content of the part. Re-uploading a part
+/// replaces its previous content.
+#[utoipa::path(
+    put,
+    path = "/platform/file/upload/{upload_token}/parts/{part_number}",
+    params(UploadPartFromPath),
+    request_body(content = Vec<u8>, content_type = "application/octet-stream"),
+    responses((status = OK)),
+    tag = "kamu",
+    security(
+        ("api_key" = []),
+    )
+)]
+pub async fn platform_file_upload_part_put_handler(
+    catalog: axum::extract::Extension<dill::Catalog>,
+    axum::extract::Path(upload_param): axum::extract::Path<UploadPartFromPath>,
+    body: axum::body::Body,
+) -> Result<(), ApiError> {
+    ensure_upload_owner(&catalog, &upload_param.upload_token.0)?;
+
+    // Parts are streamed into storage without buffering them in memory
+    let part_data =
+        tokio_util::io::StreamReader::new(body.into_data_stream().map_err(std::io::Error::other));
+
+    let upload_service = catalog.get_one::<dyn UploadService>().unwrap();
+    upload_service
+        .save_upload_part(
+            &upload_param.upload_token.0,
+            upload_param.part_number,
+            Box::new(part_data),
+        )
+        .await
+        .map_err(|e| match e {
+            SaveUploadPartError::NotFound(e) => ApiError::not_found(e),
+            SaveUploadPartError::NotSupported(e) => ApiError::bad_request(e),
+            SaveUploadPartError::InvalidPartNumber(e) => ApiError::bad_request(e),
+            SaveUploadPartError::ContentLengthMismatch(e) => ApiError::bad_request(e),
+            SaveUploadPartError::Internal(e) => e.api_err(),
+        })
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+/// Complete multipart file upload
+///
+/// Assembles all uploaded parts into a single file, after which the upload
+/// token can be used the same way as for a file uploaded in one request.
+#[utoipa::path(
+    post,
+    path = "/platform/file/upload/{upload_token}/complete",
+    params(UploadFromPath),
+    responses((status = OK)),
+    tag = "kamu",
+    security(
+        ("api_key" = []),
+    )
+)]
+pub async fn platform_file_upload_complete_post_handler(
+    catalog: axum::extract::Extension<dill::Catalog>,
+    axum::extract::Path(upload_param): axum::extract::Path<UploadFromPath>,
+) -> Result<(), ApiError> {
+    ensure_upload_owner(&catalog, &upload_param.upload_token.0)?;
+
+    let upload_service = catalog.get_one::<dyn UploadService>().unwrap();
+    upload_service
+        .complete_multipart_upload(&upload_param.upload_token.0)
+        .await
+        .map_err(|e| match e {
+            CompleteMultipartUploadError::NotFound(e) => ApiError::not_found(e),
+            CompleteMultipartUploadError::MissingParts(e) => ApiError::bad_request(e),
+            CompleteMultipartUploadError::ContentLengthMismatch(e) => ApiError::bad_request(e),
+            CompleteMultipartUploadError::Internal(e) => e.api_err(),
+        })
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+/// Abort multipart file upload
+///
+/// Discards all parts uploaded so far.
+#[utoipa::path(
+    delete,
+    path = "/platform/file/upload/{upload_token}",
+    params(UploadFromPath),
+    responses((status = OK)),
+    tag = "kamu",
+    security(
+        ("api_key" = []),
+    )
+)]
+pub async fn platform_file_upload_delete_handler(
+    catalog: axum::extract::Extension<dill::Catalog>,
+    axum::extract::Path(upload_param): axum::extract::Path<UploadFromPath>,
+) -> Result<(), ApiError> {
+    ensure_upload_owner(&catalog, &upload_param.upload_token.0)?;
+
+    let upload_service = catalog.get_one::<dyn UploadService>().unwrap();
+    upload_service
+        .abort_multipart_upload(&upload_param.upload_token.0)
+        .await
+        .map_err(|e| match e {
+            MultipartUploadError::NotFound(e) => ApiError::not_found(e),
+            MultipartUploadError::Internal(e) => e.api_err(),
+        })
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+fn ensure_upload_owner(
+    catalog: &dill::Catalog,
+    upload_token: &UploadToken,
+) -> Result<(), ApiError> {
+    let account_id = ensure_authenticated_account(catalog).api_err()?;
+    if account_id.as_multibase().to_stack_string().as_str()
+        != upload_token.owner_account_id.as_str()
+    {
+        return Err(ApiError::new_forbidden());
+    }
+    Ok(())
+}
+
 async fn find_correct_multi_part_field(
     multipart: &mut axum::extract::Multipart,
 ) -> Result<Bytes, ApiError> {
@@ -9,6 +9,7 @@
 
 use base64::Engine;
 use bytes::Bytes;
+use chrono::{DateTime, Duration, Utc};
 use internal_error::{InternalError, ResultIntoInternal};
 use kamu_core::MediaType;
 use serde::{Deserialize, Serialize};
@@ -48,6 +49,48 @@ pub trait UploadService: Send + Sync {
         file_data: Bytes,
     ) -> Result<(), SaveUploadError>;
 
+    /// Starts an upload of a file that is transferred in several parts. Parts
+    /// can be uploaded in any order and re-uploaded after failures, and the
+    /// state of the upload is kept in the storage until it is completed,
+    /// aborted, or expires.
+    async fn make_multipart_upload_context(
+        &self,
+        owner_account_id: &odf::AccountID,
+        file_name: String,
+        content_type: Option<MediaType>,
+        content_length: usize,
+    ) -> Result<MultipartUploadContext, MakeUploadContextError>;
+
+    /// Returns the state of a multipart upload, allowing clients to resume it
+    /// by uploading only the parts that are still pending
+    async fn multipart_upload_status(
+        &self,
+        upload_token: &UploadToken,
+    ) -> Result<MultipartUploadContext, MultipartUploadError>;
+
+    async fn save_upload_part(
+        &self,
+        upload_token: &UploadToken,
+        part_number: usize,
+        part_data: Box<dyn AsyncRead + Send + Unpin>,
+    ) -> Result<(), SaveUploadPartError>;
+
+    /// Assembles uploaded parts into a file that can be referenced by the
+    /// upload token just like a file uploaded in one request
+    async fn complete_multipart_upload(
+        &self,
+        upload_token: &UploadToken,
+    ) -> Result<(), CompleteMultipartUploadError>;
+
+    async fn abort_multipart_upload(
+        &self,
+        upload_token: &UploadToken,
+    ) -> Result<(), MultipartUploadError>;
+
+    /// Removes incomplete multipart uploads that outlived their TTL. Returns
+    /// the number of uploads removed.
+    async fn cleanup_expired_uploads(&self) -> Result<usize, InternalError>;
+
     async fn upload_token_into_stream(
         &self,
         upload_token: &UploadToken,
@@ -100,11 +143,72 @@ pub struct UploadContext {
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
 
+#[derive(Debug, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct MultipartUploadContext {
+    #[schema(value_type = String)]
+    pub upload_token: UploadTokenBase64Json,
+
+    /// Size of every part except the last one, which holds the remainder
+    pub part_size: usize,
+    pub parts_count: usize,
+
+    /// Time after which an incomplete upload is discarded
+    #[schema(value_type = String)]
+    pub expires_at: DateTime<Utc>,
+
+    /// Parts that were already received
+    pub uploaded_parts: Vec<UploadedPart>,
+
+    /// Parts that still need to be uploaded
+    pub pending_parts: Vec<UploadPartContext>,
+
+    /// URL to `POST` to once all parts were uploaded
+    pub complete_url: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct UploadPartContext {
+    /// 1-based number of the part
+    pub part_number: usize,
+    pub upload_url: String,
+    pub method: String,
+    pub headers: Vec<(String, String)>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct UploadedPart {
+    pub part_number: usize,
+    pub size: usize,
+}
+
+/// Splits the declared content into parts of the configured size, growing
+/// the part size when needed to stay within [`MAX_UPLOAD_PARTS`]
+pub(crate) fn plan_upload_parts(
+    content_length: usize,
+    preferred_part_size: usize,
+) -> (usize, usize) {
+    let part_size = preferred_part_size
+        .max(content_length.div_ceil(MAX_UPLOAD_PARTS))
+        .max(1);
+    let parts_count = content_length.div_ceil(part_size).max(1);
+    (part_size, parts_count)
+}
+
+/// Maximal number of parts a file can be split into (matches S3 limits)
+pub const MAX_UPLOAD_PARTS: usize = 10_000;
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
 #[derive(Debug, Error)]
 pub enum MakeUploadContextError {
     #[error(transparent)]
     TooLarge(ContentTooLargeError),
     #[error(transparent)]
+    InvalidFileName(InvalidFileNameError),
+    #[error(transparent)]
     Internal(#[from] InternalError),
 }
 
@@ -130,10 +234,50 @@ pub enum UploadTokenIntoStreamError {
     Internal(#[from] InternalError),
 }
 
+#[derive(Debug, Error)]
+pub enum MultipartUploadError {
+    #[error(transparent)]
+    NotFound(MultipartUploadNotFoundError),
+    #[error(transparent)]
+    Internal(#[from] InternalError),
+}
+
+#[derive(Debug, Error)]
+pub enum SaveUploadPartError {
+    #[error(transparent)]
+    NotSupported(UploadNotSupportedError),
+    #[error(transparent)]
+    NotFound(MultipartUploadNotFoundError),
+    #[error(transparent)]
+    InvalidPartNumber(InvalidPartNumberError),
+    #[error(transparent)]
+    ContentLengthMismatch(ContentLengthMismatchError),
+    #[error(transparent)]
+    Internal(#[from] InternalError),
+}
+
+#[derive(Debug, Error)]
+pub enum CompleteMultipartUploadError {
+    #[error(transparent)]
+    NotFound(MultipartUploadNotFoundError),
+    #[error(transparent)]
+    MissingParts(MissingUploadPartsError),
+    #[error(transparent)]
+    ContentLengthMismatch(ContentLengthMismatchError),
+    #[error(transparent)]
+    Internal(#[from] InternalError),
+}
+
 #[derive(Debug, Error)]
 #[error("Content too large")]
 pub struct ContentTooLargeError {}
 
+#[derive(Debug, Error)]
+#[error("Invalid file name: {file_name:?}")]
+pub struct InvalidFileNameError {
+    pub file_name: String,
+}
+
 #[derive(Debug, Error)]
 #[error("Actual content length {actual} does not match the initially declared length {declared}")]
 pub struct ContentLengthMismatchError {
@@ -149,11 +293,29 @@ pub struct UploadNotSupportedError {}
 #[error("Uploaded file not found on the server")]
 pub struct ContentNotFoundError {}
 
+#[derive(Debug, Error)]
+#[error("Multipart upload not found or expired")]
+pub struct MultipartUploadNotFoundError {}
+
+#[derive(Debug, Error)]
+#[error("Part number {part_number} is outside of the range 1..={parts_count}")]
+pub struct InvalidPartNumberError {
+    pub part_number: usize,
+    pub parts_count: usize,
+}
+
+#[derive(Debug, Error)]
+#[error("Upload is missing parts: {missing_parts:?}")]
+pub struct MissingUploadPartsError {
+    pub missing_parts: Vec<usize>,
+}
+
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
 
 #[derive(Debug)]
 pub struct FileUploadLimitConfig {
     max_file_size_in_bytes: usize,
+    multipart: MultipartUploadConfig,
 }
 
 impl FileUploadLimitConfig {
@@ -161,14 +323,24 @@ impl FileUploadLimitConfig {
     pub fn new_in_bytes(max_file_size_in_bytes: usize) -> Self {
         Self {
             max_file_size_in_bytes,
+            multipart: MultipartUploadConfig::default(),
         }
     }
 
     #[inline]
     pub fn new_in_mb(max_file_size_in_mb: usize) -> Self {
-        Self {
-            max_file_size_in_bytes: max_file_size_in_mb * 1024 * 1024,
-        }
+        Self::new_in_bytes(max_file_size_in_mb * 1024 * 1024)
+    }
+
+    #[inline]
+    pub fn with_multipart(mut self, multipart: MultipartUploadConfig) -> Self {
+        self.multipart = multipart;
+        self
+    }
+
+    #[inline]
+    pub fn multipart(&self) -> &MultipartUploadConfig {
+        &self.multipart
     }
 
     #[inline]
@@ -184,15 +356,83 @@ impl FileUploadLimitConfig {
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
 
+#[derive(Debug, Clone)]
+pub struct MultipartUploadConfig {
+    /// Preferred size of a single part. Storages like S3 require all parts
+    /// except the last one to be at least 5 MiB.
+    pub part_size_in_bytes: usize,
+
+    /// Maximal size of a file uploaded in parts
+    pub max_file_size_in_bytes: usize,
+
+    /// How long an incomplete upload is kept before being cleaned up
+    pub incomplete_upload_ttl: Duration,
+
+    /// How often expired incomplete uploads are cleaned up
+    pub cleanup_interval: Duration,
+}
+
+impl Default for MultipartUploadConfig {
+    fn default() -> Self {
+        Self {
+            part_size_in_bytes: 16 * 1024 * 1024,
+            max_file_size_in_bytes: 10 * 1024 * 1024 * 1024,
+            incomplete_upload_ttl: Duration::hours(24),
+            cleanup_interval: Duration::hours(1),
+        }
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
 #[serde_with::serde_as]
 #[serde_with::skip_serializing_none]
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct UploadToken {
     pub upload_id: String,
     pub file_name: String,
     pub owner_account_id: String,
     pub content_length: usize,
     pub content_type: Option<MediaType>,
+    pub multipart: Option<MultipartUploadInfo>,
+}
+
+/// Details of an upload that is transferred in parts
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MultipartUploadInfo {
+    pub part_size: usize,
+    pub parts_count: usize,
+    pub expires_at: DateTime<Utc>,
+    /// Identifier assigned to the upload by the storage, if any
+    pub storage_upload_id: Option<String>,
+}
+
+impl UploadToken {
+    /// Upload tokens are supplied by clients and their fields end up in
+    /// storage paths, so they are checked before being used
+    pub fn validate(&self) -> Result<(), UploadTokenBase64JsonDecodeError> {
+        uuid::Uuid::try_parse(&self.upload_id).map_err(UploadTokenBase64JsonDecodeError::new)?;
+        validate_upload_file_name(&self.file_name)
+            .map_err(UploadTokenBase64JsonDecodeError::new)?;
+        odf::AccountID::from_multibase_string(&self.owner_account_id)
+            .map_err(UploadTokenBase64JsonDecodeError::new)?;
+        Ok(())
+    }
+}
+
+/// Ensures that a file name can be safely used as a single component of a
+/// storage path
+pub fn validate_upload_file_name(file_name: &str) -> Result<(), InvalidFileNameError> {
+    if file_name.is_empty()
+        || file_name == "."
+        || file_name == ".."
+        || file_name.contains(['/', '\\', '\0'])
+    {
+        return Err(InvalidFileNameError {
+            file_name: file_name.to_string(),
+        });
+    }
+    Ok(())
 }
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
@@ -217,8 +457,9 @@ impl std::str::FromStr for UploadTokenBase64Json {
             .map_err(UploadTokenBase64JsonDecodeError::new)?;
         let payload_json = std::str::from_utf8(&payload_json_bytes)
             .map_err(UploadTokenBase64JsonDecodeError::new)?;
-        let upload_token =
+        let upload_token: UploadToken =
             serde_json::from_str(payload_json).map_err(UploadTokenBase64JsonDecodeError::new)?;
+        upload_token.validate()?;
         Ok(Self(upload_token))
     }
 }
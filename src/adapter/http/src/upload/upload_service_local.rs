@@ -7,31 +7,60 @@
 // the Business Source License, use of this software will be governed
 // by the Apache License, Version 2.0.
 
+use std::collections::BTreeSet;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
 use bytes::Bytes;
+use chrono::{DateTime, Utc};
 use dill::*;
 use internal_error::{ErrorIntoInternal, InternalError, ResultIntoInternal};
 use kamu_core::{CacheDir, MediaType, ServerUrlConfig};
+use serde::{Deserialize, Serialize};
 use thiserror::Error;
-use tokio::io::AsyncRead;
+use time_source::SystemTimeSource;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWriteExt};
 use uuid::Uuid;
 
-use super::{ContentNotFoundError, UploadToken, UploadTokenBase64Json, UploadTokenIntoStreamError};
+use super::{
+    plan_upload_parts,
+    ContentNotFoundError,
+    UploadToken,
+    UploadTokenBase64Json,
+    UploadTokenIntoStreamError,
+};
 use crate::{
+    validate_upload_file_name,
     AccessToken,
+    CompleteMultipartUploadError,
     ContentLengthMismatchError,
     ContentTooLargeError,
     FileUploadLimitConfig,
+    InvalidPartNumberError,
     MakeUploadContextError,
+    MissingUploadPartsError,
+    MultipartUploadContext,
+    MultipartUploadError,
+    MultipartUploadInfo,
+    MultipartUploadNotFoundError,
     SaveUploadError,
+    SaveUploadPartError,
     UploadContext,
+    UploadPartContext,
     UploadService,
+    UploadedPart,
 };
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
 
+// State of a multipart upload is kept next to its parts, so that uploads can
+// be resumed after the server restarts
+const MULTIPART_MANIFEST_FILE_NAME: &str = ".multipart.json";
+const MULTIPART_PARTS_DIR_NAME: &str = ".parts";
+const PARTIAL_PART_EXTENSION: &str = "partial";
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
 #[component(pub)]
 #[interface(dyn UploadService)]
 pub struct UploadServiceLocal {
@@ -39,6 +68,7 @@ pub struct UploadServiceLocal {
     uploads_config: Arc<FileUploadLimitConfig>,
     maybe_access_token: Option<Arc<AccessToken>>,
     cache_dir: Arc<CacheDir>,
+    time_source: Arc<dyn SystemTimeSource>,
 }
 
 impl UploadServiceLocal {
@@ -47,17 +77,134 @@ impl UploadServiceLocal {
         uploads_config: Arc<FileUploadLimitConfig>,
         maybe_access_token: Option<Arc<AccessToken>>,
         cache_dir: Arc<CacheDir>,
+        time_source: Arc<dyn SystemTimeSource>,
     ) -> Self {
         Self {
             server_url_config,
             uploads_config,
             maybe_access_token,
             cache_dir,
+            time_source,
         }
     }
 
+    fn make_uploads_root_path(&self) -> PathBuf {
+        self.cache_dir.join("uploads")
+    }
+
     fn make_account_folder_path(&self, account_id_str: &str) -> PathBuf {
-        self.cache_dir.join("uploads").join(account_id_str)
+        self.make_uploads_root_path().join(account_id_str)
+    }
+
+    fn make_upload_folder_path(&self, upload_token: &UploadToken) -> PathBuf {
+        self.make_account_folder_path(&upload_token.owner_account_id)
+            .join(&upload_token.upload_id)
+    }
+
+    fn authorization_headers(&self) -> Vec<(String, String)> {
+        vec![(
+            String::from("Authorization"),
+            format!(
+                "Bearer {}",
+                self.maybe_access_token
+                    .as_ref()
+                    .expect("access token must be present")
+                    .token
+            ),
+        )]
+    }
+
+    /// Reads the state of a multipart upload, treating expired uploads as
+    /// non-existing ones
+    async fn read_multipart_manifest(
+        &self,
+        upload_folder_path: &Path,
+    ) -> Result<MultipartUploadManifest, MultipartUploadError> {
+        let manifest_path = upload_folder_path.join(MULTIPART_MANIFEST_FILE_NAME);
+
+        let manifest_json = match tokio::fs::read(&manifest_path).await {
+            Ok(manifest_json) => manifest_json,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                return Err(MultipartUploadError::NotFound(
+                    MultipartUploadNotFoundError {},
+                ));
+            }
+            Err(e) => return Err(MultipartUploadError::Internal(e.int_err())),
+        };
+
+        let manifest: MultipartUploadManifest = serde_json::from_slice(&manifest_json).int_err()?;
+        if manifest.expires_at <= self.time_source.now() {
+            return Err(MultipartUploadError::NotFound(
+                MultipartUploadNotFoundError {},
+            ));
+        }
+
+        Ok(manifest)
+    }
+
+    async fn list_uploaded_parts(
+        upload_folder_path: &Path,
+    ) -> Result<Vec<UploadedPart>, InternalError> {
+        let mut uploaded_parts = Vec::new();
+
+        let mut read_dir = tokio::fs::read_dir(upload_folder_path.join(MULTIPART_PARTS_DIR_NAME))
+            .await
+            .int_err()?;
+        while let Some(entry) = read_dir.next_entry().await.int_err()? {
+            // Skip parts that are still being written
+            let Some(part_number) = entry
+                .file_name()
+                .to_str()
+                .and_then(|file_name| file_name.parse::<usize>().ok())
+            else {
+                continue;
+            };
+
+            let metadata = entry.metadata().await.int_err()?;
+            uploaded_parts.push(UploadedPart {
+                part_number,
+                size: usize::try_from(metadata.len()).unwrap(),
+            });
+        }
+
+        uploaded_parts.sort_by_key(|part| part.part_number);
+        Ok(uploaded_parts)
+    }
+
+    fn build_multipart_upload_context(
+        &self,
+        upload_token: UploadToken,
+        manifest: &MultipartUploadManifest,
+        uploaded_parts: Vec<UploadedPart>,
+    ) -> MultipartUploadContext {
+        let upload_token = UploadTokenBase64Json(upload_token);
+        let base_url = format!(
+            "{}platform/file/upload/{upload_token}",
+            self.server_url_config.protocols.base_url_rest,
+        );
+
+        let uploaded_part_numbers: BTreeSet<_> =
+            uploaded_parts.iter().map(|part| part.part_number).collect();
+
+        let pending_parts = (1..=manifest.parts_count)
+            .filter(|part_number| !uploaded_part_numbers.contains(part_number))
+            .map(|part_number| UploadPartContext {
+                part_number,
+                upload_url: format!("{base_url}/parts/{part_number}"),
+                method: String::from("PUT"),
+                headers: self.authorization_headers(),
+            })
+            .collect();
+
+        MultipartUploadContext {
+            part_size: manifest.part_size,
+            parts_count: manifest.parts_count,
+            expires_at: manifest.expires_at,
+            uploaded_parts,
+            pending_parts,
+            complete_url: format!("{base_url}/complete"),
+            upload_token,
+        }
     }
 
     async fn file_to_stream(
@@ -83,6 +230,8 @@ impl UploadService for UploadServiceLocal {
             return Err(MakeUploadContextError::TooLarge(ContentTooLargeError {}));
         }
 
+        validate_upload_file_name(&file_name).map_err(MakeUploadContextError::InvalidFileName)?;
+
         let upload_id = Uuid::new_v4().simple().to_string();
 
         let owner_account_id_mb = owner_account_id.as_multibase().to_stack_string();
@@ -98,6 +247,7 @@ impl UploadService for UploadServiceLocal {
             owner_account_id: owner_account_id_mb.to_string(),
             content_length,
             content_type,
+            multipart: None,
         });
 
         let upload_url = format!(
@@ -110,16 +260,7 @@ impl UploadService for UploadServiceLocal {
             upload_token,
             method: "POST".to_string(),
             use_multipart: true,
-            headers: vec![(
-                String::from("Authorization"),
-                format!(
-                    "Bearer {}",
-                    self.maybe_access_token
-                        .as_ref()
-                        .expect("access token must be present")
-                        .token
-                ),
-            )],
+            headers: self.authorization_headers(),
             fields: vec![],
         };
         Ok(context)
@@ -184,9 +325,7 @@ impl UploadService for UploadServiceLocal {
             ));
         }
 
-        let upload_folder_path = self
-            .make_account_folder_path(&upload_token.owner_account_id)
-            .join(upload_token.upload_id.clone());
+        let upload_folder_path = self.make_upload_folder_path(upload_token);
         if !upload_folder_path.is_dir() {
             return Err(SaveUploadError::Internal(
                 SaveUploadFailure {
@@ -209,6 +348,299 @@ impl UploadService for UploadServiceLocal {
 
         Ok(())
     }
+
+    async fn make_multipart_upload_context(
+        &self,
+        owner_account_id: &odf::AccountID,
+        file_name: String,
+        content_type: Option<MediaType>,
+        content_length: usize,
+    ) -> Result<MultipartUploadContext, MakeUploadContextError> {
+        assert!(self.maybe_access_token.is_some());
+
+        let multipart_config = self.uploads_config.multipart();
+        if content_length > multipart_config.max_file_size_in_bytes {
+            return Err(MakeUploadContextError::TooLarge(ContentTooLargeError {}));
+        }
+
+        validate_upload_file_name(&file_name).map_err(MakeUploadContextError::InvalidFileName)?;
+
+        let (part_size, parts_count) =
+            plan_upload_parts(content_length, multipart_config.part_size_in_bytes);
+
+        let created_at = self.time_source.now();
+        let manifest = MultipartUploadManifest {
+            content_length,
+            part_size,
+            parts_count,
+            created_at,
+            expires_at: created_at + multipart_config.incomplete_upload_ttl,
+        };
+
+        let upload_token = UploadToken {
+            upload_id: Uuid::new_v4().simple().to_string(),
+            file_name,
+            owner_account_id: owner_account_id.as_multibase().to_string(),
+            content_length,
+            content_type,
+            multipart: Some(MultipartUploadInfo {
+                part_size,
+                parts_count,
+                expires_at: manifest.expires_at,
+                storage_upload_id: None,
+            }),
+        };
+
+        let upload_folder_path = self.make_upload_folder_path(&upload_token);
+        tokio::fs::create_dir_all(upload_folder_path.join(MULTIPART_PARTS_DIR_NAME))
+            .await
+            .int_err()?;
+        tokio::fs::write(
+            upload_folder_path.join(MULTIPART_MANIFEST_FILE_NAME),
+            serde_json::to_vec(&manifest).int_err()?,
+        )
+        .await
+        .int_err()?;
+
+        Ok(self.build_multipart_upload_context(upload_token, &manifest, Vec::new()))
+    }
+
+    async fn multipart_upload_status(
+        &self,
+        upload_token: &UploadToken,
+    ) -> Result<MultipartUploadContext, MultipartUploadError> {
+        let upload_folder_path = self.make_upload_folder_path(upload_token);
+        let manifest = self.read_multipart_manifest(&upload_folder_path).await?;
+        let uploaded_parts = Self::list_uploaded_parts(&upload_folder_path).await?;
+
+        // The manifest is the source of truth for the upload parameters
+        let upload_token = UploadToken {
+            content_length: manifest.content_length,
+            multipart: Some(MultipartUploadInfo {
+                part_size: manifest.part_size,
+                parts_count: manifest.parts_count,
+                expires_at: manifest.expires_at,
+                storage_upload_id: None,
+            }),
+            ..upload_token.clone()
+        };
+
+        Ok(self.build_multipart_upload_context(upload_token, &manifest, uploaded_parts))
+    }
+
+    async fn save_upload_part(
+        &self,
+        upload_token: &UploadToken,
+        part_number: usize,
+        part_data: Box<dyn AsyncRead + Send + Unpin>,
+    ) -> Result<(), SaveUploadPartError> {
+        let upload_folder_path = self.make_upload_folder_path(upload_token);
+        let manifest = self
+            .read_multipart_manifest(&upload_folder_path)
+            .await
+            .map_err(|e| match e {
+                MultipartUploadError::NotFound(e) => SaveUploadPartError::NotFound(e),
+                MultipartUploadError::Internal(e) => SaveUploadPartError::Internal(e),
+            })?;
+
+        if part_number == 0 || part_number > manifest.parts_count {
+            return Err(SaveUploadPartError::InvalidPartNumber(
+                InvalidPartNumberError {
+                    part_number,
+                    parts_count: manifest.parts_count,
+                },
+            ));
+        }
+
+        // A part is written under a temporary name first, so that a dropped
+        // connection never leaves a truncated part that looks complete
+        let parts_dir_path = upload_folder_path.join(MULTIPART_PARTS_DIR_NAME);
+        let part_path = parts_dir_path.join(part_number.to_string());
+        let partial_part_path = parts_dir_path.join(format!(
+            "{part_number}.{}.{PARTIAL_PART_EXTENSION}",
+            Uuid::new_v4().simple()
+        ));
+
+        let expected_size = manifest.part_size_of(part_number);
+
+        let mut partial_part_file = tokio::fs::File::create(&partial_part_path)
+            .await
+            .int_err()?;
+
+        // Read one byte over the expected size to detect oversized parts
+        // without consuming the entire body
+        let mut limited_part_data = part_data.take(u64::try_from(expected_size).unwrap() + 1);
+        let write_result = async {
+            let bytes_written =
+                tokio::io::copy(&mut limited_part_data, &mut partial_part_file).await?;
+            partial_part_file.flush().await?;
+            Ok::<_, std::io::Error>(bytes_written)
+        }
+        .await;
+
+        let actual_size = match write_result {
+            Ok(bytes_written) => usize::try_from(bytes_written).unwrap(),
+            Err(e) => {
+                tokio::fs::remove_file(&partial_part_path).await.ok();
+                return Err(SaveUploadPartError::Internal(e.int_err()));
+            }
+        };
+
+        if actual_size != expected_size {
+            tokio::fs::remove_file(&partial_part_path).await.int_err()?;
+            return Err(SaveUploadPartError::ContentLengthMismatch(
+                ContentLengthMismatchError {
+                    actual: actual_size,
+                    declared: expected_size,
+                },
+            ));
+        }
+
+        tokio::fs::rename(&partial_part_path, &part_path)
+            .await
+            .int_err()?;
+
+        Ok(())
+    }
+
+    async fn complete_multipart_upload(
+        &self,
+        upload_token: &UploadToken,
+    ) -> Result<(), CompleteMultipartUploadError> {
+        let upload_folder_path = self.make_upload_folder_path(upload_token);
+        let manifest = self
+            .read_multipart_manifest(&upload_folder_path)
+            .await
+            .map_err(|e| match e {
+                MultipartUploadError::NotFound(e) => CompleteMultipartUploadError::NotFound(e),
+                MultipartUploadError::Internal(e) => CompleteMultipartUploadError::Internal(e),
+            })?;
+
+        let uploaded_parts = Self::list_uploaded_parts(&upload_folder_path).await?;
+        let uploaded_part_numbers: BTreeSet<_> =
+            uploaded_parts.iter().map(|part| part.part_number).collect();
+
+        let missing_parts: Vec<_> = (1..=manifest.parts_count)
+            .filter(|part_number| !uploaded_part_numbers.contains(part_number))
+            .collect();
+        if !missing_parts.is_empty() {
+            return Err(CompleteMultipartUploadError::MissingParts(
+                MissingUploadPartsError { missing_parts },
+            ));
+        }
+
+        let parts_dir_path = upload_folder_path.join(MULTIPART_PARTS_DIR_NAME);
+
+        // Assembling is idempotent: if the server restarts midway, the
+        // manifest is still in place and completion can be retried
+        let file_path = upload_folder_path.join(&upload_token.file_name);
+        let mut file = tokio::fs::File::create(&file_path).await.int_err()?;
+        for part_number in 1..=manifest.parts_count {
+            let mut part_file = tokio::fs::File::open(parts_dir_path.join(part_number.to_string()))
+                .await
+                .int_err()?;
+            tokio::io::copy(&mut part_file, &mut file).await.int_err()?;
+        }
+        file.flush().await.int_err()?;
+
+        tokio::fs::remove_dir_all(&parts_dir_path).await.int_err()?;
+        tokio::fs::remove_file(upload_folder_path.join(MULTIPART_MANIFEST_FILE_NAME))
+            .await
+            .int_err()?;
+
+        Ok(())
+    }
+
+    async fn abort_multipart_upload(
+        &self,
+        upload_token: &UploadToken,
+    ) -> Result<(), MultipartUploadError> {
+        let upload_folder_path = self.make_upload_folder_path(upload_token);
+        self.read_multipart_manifest(&upload_folder_path).await?;
+
+        tokio::fs::remove_dir_all(&upload_folder_path)
+            .await
+            .int_err()?;
+
+        Ok(())
+    }
+
+    async fn cleanup_expired_uploads(&self) -> Result<usize, InternalError> {
+        let uploads_root_path = self.make_uploads_root_path();
+        if !uploads_root_path.is_dir() {
+            return Ok(0);
+        }
+
+        let now = self.time_source.now();
+        let mut num_removed = 0;
+
+        let mut accounts_read_dir = tokio::fs::read_dir(&uploads_root_path).await.int_err()?;
+        while let Some(account_entry) = accounts_read_dir.next_entry().await.int_err()? {
+            if !account_entry.file_type().await.int_err()?.is_dir() {
+                continue;
+            }
+
+            let mut uploads_read_dir = tokio::fs::read_dir(account_entry.path()).await.int_err()?;
+            while let Some(upload_entry) = uploads_read_dir.next_entry().await.int_err()? {
+                let upload_folder_path = upload_entry.path();
+
+                // Only incomplete multipart uploads have a manifest
+                let Ok(manifest_json) =
+                    tokio::fs::read(upload_folder_path.join(MULTIPART_MANIFEST_FILE_NAME)).await
+                else {
+                    continue;
+                };
+
+                let manifest: MultipartUploadManifest = match serde_json::from_slice(&manifest_json)
+                {
+                    Ok(manifest) => manifest,
+                    Err(e) => {
+                        tracing::warn!(
+                            error = ?e,
+                            upload_folder_path = %upload_folder_path.display(),
+                            "Skipping upload with unreadable manifest"
+                        );
+                        continue;
+                    }
+                };
+
+                if manifest.expires_at <= now {
+                    tracing::info!(
+                        upload_folder_path = %upload_folder_path.display(),
+                        created_at = %manifest.created_at,
+                        expires_at = %manifest.expires_at,
+                        "Removing expired incomplete upload"
+                    );
+                    tokio::fs::remove_dir_all(&upload_folder_path)
+                        .await
+                        .int_err()?;
+                    num_removed += 1;
+                }
+            }
+        }
+
+        Ok(num_removed)
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[derive(Debug, Serialize, Deserialize)]
+struct MultipartUploadManifest {
+    content_length: usize,
+    part_size: usize,
+    parts_count: usize,
+    created_at: DateTime<Utc>,
+    expires_at: DateTime<Utc>,
+}
+
+impl MultipartUploadManifest {
+    /// Size of the given 1-based part: all parts have the same size, except
+    /// the last one which holds the remainder
+    fn part_size_of(&self, part_number: usize) -> usize {
+        let part_start = (part_number - 1) * self.part_size;
+        self.part_size.min(self.content_length - part_start)
+    }
 }
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
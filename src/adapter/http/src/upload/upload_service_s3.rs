@@ -7,28 +7,48 @@
 // the Business Source License, use of this software will be governed
 // by the Apache License, Version 2.0.
 
+use std::collections::BTreeSet;
 use std::sync::Arc;
 
 use aws_sdk_s3::error::SdkError;
 use aws_sdk_s3::presigning::PresigningConfig;
-use aws_sdk_s3::types::ObjectCannedAcl;
+use aws_sdk_s3::types::{CompletedPart, ObjectCannedAcl};
 use bytes::Bytes;
+use chrono::{DateTime, Utc};
 use dill::*;
-use internal_error::ErrorIntoInternal;
-use kamu_core::MediaType;
+use internal_error::{ErrorIntoInternal, InternalError, ResultIntoInternal};
+use kamu_core::{MediaType, ServerUrlConfig};
 use s3_utils::{PutObjectOptions, S3Context};
+use time_source::SystemTimeSource;
 use tokio::io::AsyncRead;
 use uuid::Uuid;
 
-use super::{ContentNotFoundError, UploadToken, UploadTokenBase64Json, UploadTokenIntoStreamError};
+use super::{
+    plan_upload_parts,
+    ContentNotFoundError,
+    UploadToken,
+    UploadTokenBase64Json,
+    UploadTokenIntoStreamError,
+};
 use crate::{
+    validate_upload_file_name,
+    CompleteMultipartUploadError,
+    ContentLengthMismatchError,
     ContentTooLargeError,
     FileUploadLimitConfig,
     MakeUploadContextError,
+    MissingUploadPartsError,
+    MultipartUploadContext,
+    MultipartUploadError,
+    MultipartUploadInfo,
+    MultipartUploadNotFoundError,
     SaveUploadError,
+    SaveUploadPartError,
     UploadContext,
     UploadNotSupportedError,
+    UploadPartContext,
     UploadService,
+    UploadedPart,
 };
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
@@ -38,13 +58,22 @@ use crate::{
 pub struct UploadServiceS3 {
     s3_upload_context: S3Context,
     upload_config: Arc<FileUploadLimitConfig>,
+    server_url_config: Arc<ServerUrlConfig>,
+    time_source: Arc<dyn SystemTimeSource>,
 }
 
 impl UploadServiceS3 {
-    pub fn new(s3_upload_context: S3Context, upload_config: Arc<FileUploadLimitConfig>) -> Self {
+    pub fn new(
+        s3_upload_context: S3Context,
+        upload_config: Arc<FileUploadLimitConfig>,
+        server_url_config: Arc<ServerUrlConfig>,
+        time_source: Arc<dyn SystemTimeSource>,
+    ) -> Self {
         Self {
             s3_upload_context,
             upload_config,
+            server_url_config,
+            time_source,
         }
     }
 
@@ -56,6 +85,144 @@ impl UploadServiceS3 {
     ) -> String {
         format!("{}/{}/{}", account_id.as_multibase(), upload_id, file_name,)
     }
+
+    fn make_upload_token_file_key(upload_token: &UploadToken) -> String {
+        format!(
+            "{}/{}/{}",
+            upload_token.owner_account_id, upload_token.upload_id, upload_token.file_name
+        )
+    }
+
+    /// Returns details of a multipart upload that has not yet expired
+    fn active_multipart_info<'a>(
+        &self,
+        upload_token: &'a UploadToken,
+    ) -> Result<(&'a MultipartUploadInfo, &'a str), MultipartUploadNotFoundError> {
+        upload_token
+            .multipart
+            .as_ref()
+            .filter(|multipart| multipart.expires_at > self.time_source.now())
+            .and_then(|multipart| {
+                multipart
+                    .storage_upload_id
+                    .as_deref()
+                    .map(|storage_upload_id| (multipart, storage_upload_id))
+            })
+            .ok_or(MultipartUploadNotFoundError {})
+    }
+
+    async fn list_uploaded_parts(
+        &self,
+        file_key: &str,
+        storage_upload_id: &str,
+    ) -> Result<Vec<(UploadedPart, Option<String>)>, MultipartUploadError> {
+        let parts = self
+            .s3_upload_context
+            .list_all_parts(file_key, storage_upload_id)
+            .await
+            .map_err(|e| {
+                tracing::error!(error = ?e, "S3 list parts failed");
+                if let SdkError::ServiceError(_) = e {
+                    MultipartUploadError::NotFound(MultipartUploadNotFoundError {})
+                } else {
+                    MultipartUploadError::Internal(e.int_err())
+                }
+            })?;
+
+        let mut uploaded_parts = parts
+            .into_iter()
+            .map(|part| {
+                let part_number = part
+                    .part_number
+                    .ok_or_else(|| "S3 did not return part number".int_err())?;
+                let size = part
+                    .size
+                    .ok_or_else(|| "S3 did not return part size".int_err())?;
+                Ok((
+                    UploadedPart {
+                        part_number: usize::try_from(part_number).int_err()?,
+                        size: usize::try_from(size).int_err()?,
+                    },
+                    part.e_tag,
+                ))
+            })
+            .collect::<Result<Vec<_>, InternalError>>()?;
+
+        uploaded_parts.sort_by_key(|(part, _)| part.part_number);
+        Ok(uploaded_parts)
+    }
+
+    async fn build_multipart_upload_context(
+        &self,
+        upload_token: UploadToken,
+        uploaded_parts: Vec<UploadedPart>,
+    ) -> Result<MultipartUploadContext, InternalError> {
+        let multipart = upload_token
+            .multipart
+            .clone()
+            .expect("multipart upload details must be present");
+        let storage_upload_id = multipart
+            .storage_upload_id
+            .as_deref()
+            .expect("storage upload id must be present");
+        let file_key = Self::make_upload_token_file_key(&upload_token);
+
+        // Part URLs stay valid for as long as the upload itself, but presigned
+        // URLs cannot be valid for longer than a week
+        let presigned_ttl =
+            (multipart.expires_at - self.time_source.now()).min(chrono::Duration::days(7));
+        let presigned_config = PresigningConfig::builder()
+            .expires_in(presigned_ttl.to_std().int_err()?)
+            .build()
+            .int_err()?;
+
+        let uploaded_part_numbers: BTreeSet<_> =
+            uploaded_parts.iter().map(|part| part.part_number).collect();
+
+        let mut pending_parts = Vec::new();
+        for part_number in 1..=multipart.parts_count {
+            if uploaded_part_numbers.contains(&part_number) {
+                continue;
+            }
+
+            let presigned_request = self
+                .s3_upload_context
+                .upload_part_presigned_request(
+                    file_key.clone(),
+                    storage_upload_id,
+                    i32::try_from(part_number).int_err()?,
+                    presigned_config.clone(),
+                )
+                .await
+                .int_err()?;
+
+            pending_parts.push(UploadPartContext {
+                part_number,
+                upload_url: String::from(presigned_request.uri()),
+                method: String::from("PUT"),
+                headers: presigned_request
+                    .headers()
+                    .map(|(key, value)| (key.to_string(), value.to_string()))
+                    .collect(),
+            });
+        }
+
+        let upload_token = UploadTokenBase64Json(upload_token);
+        let complete_url = format!(
+            "{}platform/file/upload/{upload_token}/complete",
+            self.server_url_config.protocols.base_url_rest,
+        );
+
+        Ok(MultipartUploadContext {
+            part_size: multipart.part_size,
+            parts_count: multipart.parts_count,
+            expires_at: multipart.expires_at,
+            uploaded_parts,
+            pending_parts,
+            complete_url,
+            upload_token,
+        })
+    }
 }
 
 #[async_trait::async_trait]
@@ -71,6 +238,8 @@ impl UploadService for UploadServiceS3 {
             return Err(MakeUploadContextError::TooLarge(ContentTooLargeError {}));
         }
 
+        validate_upload_file_name(&file_name).map_err(MakeUploadContextError::InvalidFileName)?;
+
         let upload_id = Uuid::new_v4().simple().to_string();
         let file_key = self.make_file_key(owner_account_id, &upload_id, &file_name);
 
@@ -99,6 +268,7 @@ impl UploadService for UploadServiceS3 {
             owner_account_id: owner_account_id_mb.to_string(),
             content_length,
             content_type,
+            multipart: None,
         });
 
         Ok(UploadContext {
@@ -174,6 +344,217 @@ impl UploadService for UploadServiceS3 {
     ) -> Result<(), SaveUploadError> {
         Err(SaveUploadError::NotSupported(UploadNotSupportedError {}))
     }
+
+    async fn make_multipart_upload_context(
+        &self,
+        owner_account_id: &odf::AccountID,
+        file_name: String,
+        content_type: Option<MediaType>,
+        content_length: usize,
+    ) -> Result<MultipartUploadContext, MakeUploadContextError> {
+        let multipart_config = self.upload_config.multipart();
+        if content_length > multipart_config.max_file_size_in_bytes {
+            return Err(MakeUploadContextError::TooLarge(ContentTooLargeError {}));
+        }
+
+        validate_upload_file_name(&file_name).map_err(MakeUploadContextError::InvalidFileName)?;
+
+        let upload_id = Uuid::new_v4().simple().to_string();
+        let file_key = self.make_file_key(owner_account_id, &upload_id, &file_name);
+
+        // S3 keeps the state of the upload, so it survives server restarts
+        let create_multipart_upload_output = self
+            .s3_upload_context
+            .create_multipart_upload(file_key, Some(ObjectCannedAcl::Private))
+            .await
+            .int_err()?;
+        let storage_upload_id = create_multipart_upload_output
+            .upload_id
+            .ok_or_else(|| "S3 did not return multipart upload id".int_err())?;
+
+        let (part_size, parts_count) =
+            plan_upload_parts(content_length, multipart_config.part_size_in_bytes);
+
+        let upload_token = UploadToken {
+            upload_id,
+            file_name,
+            owner_account_id: owner_account_id.as_multibase().to_string(),
+            content_length,
+            content_type,
+            multipart: Some(MultipartUploadInfo {
+                part_size,
+                parts_count,
+                expires_at: self.time_source.now() + multipart_config.incomplete_upload_ttl,
+                storage_upload_id: Some(storage_upload_id),
+            }),
+        };
+
+        Ok(self
+            .build_multipart_upload_context(upload_token, Vec::new())
+            .await?)
+    }
+
+    async fn multipart_upload_status(
+        &self,
+        upload_token: &UploadToken,
+    ) -> Result<MultipartUploadContext, MultipartUploadError> {
+        let (_, storage_upload_id) = self
+            .active_multipart_info(upload_token)
+            .map_err(MultipartUploadError::NotFound)?;
+
+        let file_key = Self::make_upload_token_file_key(upload_token);
+        let uploaded_parts = self
+            .list_uploaded_parts(&file_key, storage_upload_id)
+            .await?
+            .into_iter()
+            .map(|(part, _)| part)
+            .collect();
+
+        Ok(self
+            .build_multipart_upload_context(upload_token.clone(), uploaded_parts)
+            .await?)
+    }
+
+    async fn save_upload_part(
+        &self,
+        _: &UploadToken,
+        _: usize,
+        _: Box<dyn AsyncRead + Send + Unpin>,
+    ) -> Result<(), SaveUploadPartError> {
+        // Parts are uploaded directly to S3 via presigned URLs
+        Err(SaveUploadPartError::NotSupported(
+            UploadNotSupportedError {},
+        ))
+    }
+
+    async fn complete_multipart_upload(
+        &self,
+        upload_token: &UploadToken,
+    ) -> Result<(), CompleteMultipartUploadError> {
+        let (multipart, storage_upload_id) = self
+            .active_multipart_info(upload_token)
+            .map_err(CompleteMultipartUploadError::NotFound)?;
+
+        let file_key = Self::make_upload_token_file_key(upload_token);
+        let uploaded_parts = self
+            .list_uploaded_parts(&file_key, storage_upload_id)
+            .await
+            .map_err(|e| match e {
+                MultipartUploadError::NotFound(e) => CompleteMultipartUploadError::NotFound(e),
+                MultipartUploadError::Internal(e) => CompleteMultipartUploadError::Internal(e),
+            })?;
+
+        let uploaded_part_numbers: BTreeSet<_> = uploaded_parts
+            .iter()
+            .map(|(part, _)| part.part_number)
+            .collect();
+        let missing_parts: Vec<_> = (1..=multipart.parts_count)
+            .filter(|part_number| !uploaded_part_numbers.contains(part_number))
+            .collect();
+        if !missing_parts.is_empty() {
+            return Err(CompleteMultipartUploadError::MissingParts(
+                MissingUploadPartsError { missing_parts },
+            ));
+        }
+
+        let actual_size: usize = uploaded_parts.iter().map(|(part, _)| part.size).sum();
+        if actual_size != upload_token.content_length {
+            return Err(CompleteMultipartUploadError::ContentLengthMismatch(
+                ContentLengthMismatchError {
+                    actual: actual_size,
+                    declared: upload_token.content_length,
+                },
+            ));
+        }
+
+        let completed_parts = uploaded_parts
+            .into_iter()
+            .map(|(part, e_tag)| {
+                Ok(CompletedPart::builder()
+                    .part_number(i32::try_from(part.part_number).int_err()?)
+                    .set_e_tag(e_tag)
+                    .build())
+            })
+            .collect::<Result<Vec<_>, InternalError>>()?;
+
+        self.s3_upload_context
+            .complete_multipart_upload(file_key, storage_upload_id.to_string(), completed_parts)
+            .await
+            .int_err()?;
+
+        Ok(())
+    }
+
+    async fn abort_multipart_upload(
+        &self,
+        upload_token: &UploadToken,
+    ) -> Result<(), MultipartUploadError> {
+        let (_, storage_upload_id) = self
+            .active_multipart_info(upload_token)
+            .map_err(MultipartUploadError::NotFound)?;
+
+        self.s3_upload_context
+            .abort_multipart_upload(
+                Self::make_upload_token_file_key(upload_token),
+                storage_upload_id.to_string(),
+            )
+            .await
+            .map_err(|e| {
+                tracing::error!(error = ?e, "S3 abort multipart upload failed");
+                if let SdkError::ServiceError(_) = e {
+                    MultipartUploadError::NotFound(MultipartUploadNotFoundError {})
+                } else {
+                    MultipartUploadError::Internal(e.int_err())
+                }
+            })?;
+
+        Ok(())
+    }
+
+    async fn cleanup_expired_uploads(&self) -> Result<usize, InternalError> {
+        // The bucket is dedicated to uploads, so every incomplete multipart
+        // upload in it belongs to this service
+        let expired_before =
+            self.time_source.now() - self.upload_config.multipart().incomplete_upload_ttl;
+
+        let mut num_removed = 0;
+        for upload in self.s3_upload_context.list_all_multipart_uploads().await? {
+            let (Some(key), Some(storage_upload_id), Some(initiated)) =
+                (upload.key, upload.upload_id, upload.initiated)
+            else {
+                continue;
+            };
+
+            let initiated_at =
+                DateTime::<Utc>::from_timestamp(initiated.secs(), initiated.subsec_nanos())
+                    .ok_or_else(|| "S3 returned invalid upload initiation time".int_err())?;
+            if initiated_at > expired_before {
+                continue;
+            }
+
+            tracing::info!(
+                %key,
+                %storage_upload_id,
+                %initiated_at,
+                "Aborting expired incomplete upload"
+            );
+
+            match self
+                .s3_upload_context
+                .abort_multipart_upload(key, storage_upload_id)
+                .await
+            {
+                Ok(_) => num_removed += 1,
+                // Could have been completed or aborted concurrently
+                Err(SdkError::ServiceError(e)) => {
+                    tracing::warn!(error = ?e, "Failed to abort expired upload");
+                }
+                Err(e) => return Err(e.int_err()),
+            }
+        }
+
+        Ok(num_removed)
+    }
 }
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
@@ -137,6 +137,7 @@ impl ServerSideLocalFsHarness {
                 .add::<DatasetEntryServiceImpl>()
                 .add::<InMemoryDatasetEntryRepository>()
                 .add::<AuthenticationServiceImpl>()
+                .add::<email_utils::DummyEmailSender>()
                 .add::<AccountServiceImpl>()
                 .add::<InMemoryAccountRepository>()
                 .add::<AccessTokenServiceImpl>()
@@ -121,6 +121,7 @@ impl ServerSideS3Harness {
                 .add::<DatasetEntryServiceImpl>()
                 .add::<InMemoryDatasetEntryRepository>()
                 .add::<AuthenticationServiceImpl>()
+                .add::<email_utils::DummyEmailSender>()
                 .add::<AccountServiceImpl>()
                 .add::<InMemoryAccountRepository>()
                 .add::<AccessTokenServiceImpl>()
@@ -32,13 +32,28 @@ impl TestAPIServer {
     ) -> Self {
         let (router, _api) = OpenApiRouter::new()
             .routes(routes!(kamu_adapter_http::platform_login_handler))
+            .routes(routes!(kamu_adapter_http::platform_register_handler))
+            .routes(routes!(kamu_adapter_http::platform_verify_email_handler))
             .routes(routes!(kamu_adapter_http::platform_token_validate_handler))
             .routes(routes!(
                 kamu_adapter_http::platform_file_upload_prepare_post_handler
             ))
             .routes(routes!(
                 kamu_adapter_http::platform_file_upload_post_handler,
-                kamu_adapter_http::platform_file_upload_get_handler
+                kamu_adapter_http::platform_file_upload_get_handler,
+                kamu_adapter_http::platform_file_upload_delete_handler
+            ))
+            .routes(routes!(
+                kamu_adapter_http::platform_file_upload_prepare_multipart_post_handler
+            ))
+            .routes(routes!(
+                kamu_adapter_http::platform_file_upload_parts_get_handler
+            ))
+            .routes(routes!(
+                kamu_adapter_http::platform_file_upload_part_put_handler
+            ))
+            .routes(routes!(
+                kamu_adapter_http::platform_file_upload_complete_post_handler
             ))
             .merge(kamu_adapter_http::data::root_router())
             .merge(kamu_adapter_http::general::root_router())
@@ -305,6 +305,7 @@ async fn test_data_push_ingest_upload_token_no_initial_source() {
         owner_account_id: harness.server_account_id().as_multibase().to_string(),
         content_type: Some(MediaType(String::from("application/json"))),
         content_length: FILE_CONTENT.len(),
+        multipart: None,
     });
 
     let dataset_url = harness.dataset_http_url(&create_result.dataset_handle.alias);
@@ -393,6 +394,7 @@ async fn test_data_push_ingest_upload_token_with_initial_source() {
         owner_account_id: harness.server_account_id().as_multibase().to_string(),
         content_type: Some(MediaType(String::from("application/json"))),
         content_length: FILE_CONTENT.len(),
+        multipart: None,
     });
 
     let dataset_url = harness.dataset_http_url(&create_result.dataset_handle.alias);
@@ -473,6 +475,7 @@ async fn test_data_push_ingest_upload_content_type_not_specified() {
         owner_account_id: harness.server_account_id().as_multibase().to_string(),
         content_type: None,
         content_length: FILE_CONTENT.len(),
+        multipart: None,
     });
 
     let dataset_url = harness.dataset_http_url(&create_result.dataset_handle.alias);
@@ -561,6 +564,7 @@ async fn test_data_push_ingest_upload_token_actual_file_different_size() {
         owner_account_id: harness.server_account_id().as_multibase().to_string(),
         content_type: Some(MediaType(String::from("application/json"))),
         content_length: FILE_CONTENT.len() - 17, // Intentionally wrong file size
+        multipart: None,
     });
 
     let dataset_url = harness.dataset_http_url(&create_result.dataset_handle.alias);
@@ -31,6 +31,7 @@ struct Harness {
     server_harness: ServerSideLocalFsHarness,
     root_url: url::Url,
     dataset_handle: odf::DatasetHandle,
+    dataset: ResolvedDataset,
     dataset_url: url::Url,
     private_key: odf::metadata::PrivateKey,
 }
@@ -52,6 +53,8 @@ impl Harness {
             .add_value(identity_config)
             .add::<DataFormatRegistryImpl>()
             .add::<QueryServiceImpl>()
+            .add::<DatasetChangesServiceImpl>()
+            .add::<DatasetChangeFeedServiceImpl>()
             .add::<EngineProvisionerNull>()
             .build();
 
@@ -121,44 +124,16 @@ impl Harness {
                 .unwrap();
         }
 
-        let ctx = SessionContext::new();
-        let mut writer = DataWriterDataFusion::from_metadata_chain(
-            ctx.clone(),
-            ResolvedDataset::from_created(&create_result),
-            &odf::BlockRef::Head,
-            None,
-        )
-        .await
-        .unwrap();
+        let dataset = ResolvedDataset::from_created(&create_result);
 
-        writer
-            .write(
-                Some(
-                    ctx.read_batch(
-                        RecordBatch::try_new(
-                            Arc::new(Schema::new(vec![
-                                Field::new("city", DataType::Utf8, false),
-                                Field::new("population", DataType::UInt64, false),
-                            ])),
-                            vec![
-                                Arc::new(StringArray::from(vec!["A", "B"])),
-                                Arc::new(UInt64Array::from(vec![100, 200])),
-                            ],
-                        )
-                        .unwrap(),
-                    )
-                    .unwrap(),
-                ),
-                WriteDataOpts {
-                    system_time,
-                    source_event_time: system_time,
-                    new_watermark: None,
-                    new_source_state: None,
-                    data_staging_path: run_info_dir.path().join(".temp-data.parquet"),
-                },
-            )
-            .await
-            .unwrap();
+        write_population(
+            &dataset,
+            run_info_dir.path(),
+            system_time,
+            vec!["A", "B"],
+            vec![100, 200],
+        )
+        .await;
 
         let root_url = url::Url::parse(
             format!("http://{}", server_harness.api_server_addr()).trim_end_matches('/'),
@@ -173,6 +148,7 @@ impl Harness {
             server_harness,
             root_url,
             dataset_handle: create_result.dataset_handle,
+            dataset,
             dataset_url,
             private_key,
         }
@@ -181,6 +157,55 @@ impl Harness {
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
 
+async fn write_population(
+    dataset: &ResolvedDataset,
+    staging_dir: &std::path::Path,
+    system_time: chrono::DateTime<Utc>,
+    cities: Vec<&str>,
+    populations: Vec<u64>,
+) {
+    let ctx = SessionContext::new();
+    let mut writer = DataWriterDataFusion::from_metadata_chain(
+        ctx.clone(),
+        dataset.clone(),
+        &odf::BlockRef::Head,
+        None,
+    )
+    .await
+    .unwrap();
+
+    writer
+        .write(
+            Some(
+                ctx.read_batch(
+                    RecordBatch::try_new(
+                        Arc::new(Schema::new(vec![
+                            Field::new("city", DataType::Utf8, false),
+                            Field::new("population", DataType::UInt64, false),
+                        ])),
+                        vec![
+                            Arc::new(StringArray::from(cities)),
+                            Arc::new(UInt64Array::from(populations)),
+                        ],
+                    )
+                    .unwrap(),
+                )
+                .unwrap(),
+            ),
+            WriteDataOpts {
+                system_time,
+                source_event_time: system_time,
+                new_watermark: None,
+                new_source_state: None,
+                data_staging_path: staging_dir.join(".temp-data.parquet"),
+            },
+        )
+        .await
+        .unwrap();
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
 #[test_group::group(engine, datafusion)]
 #[test_log::test(tokio::test)]
 async fn test_data_tail_handler() {
@@ -334,6 +359,184 @@ async fn test_data_tail_handler() {
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
 
+/// Reads the next server-sent event from the response body, returning its
+/// type, id and data
+async fn read_sse_event(
+    body: &mut (impl futures::Stream<Item = reqwest::Result<bytes::Bytes>> + Unpin),
+    buffer: &mut String,
+) -> (String, Option<String>, String) {
+    use futures::StreamExt;
+
+    loop {
+        if let Some(end) = buffer.find("\n\n") {
+            let raw_event = buffer[..end].to_string();
+            buffer.drain(..end + 2);
+
+            let mut event_type = String::from("message");
+            let mut id = None;
+            let mut data = Vec::new();
+
+            for line in raw_event.lines() {
+                if let Some(v) = line.strip_prefix("event:") {
+                    event_type = v.trim_start().to_string();
+                } else if let Some(v) = line.strip_prefix("id:") {
+                    id = Some(v.trim_start().to_string());
+                } else if let Some(v) = line.strip_prefix("data:") {
+                    data.push(v.trim_start());
+                }
+            }
+
+            // Skip keep-alive comments
+            if data.is_empty() {
+                continue;
+            }
+
+            return (event_type, id, data.join("\n"));
+        }
+
+        let chunk = body.next().await.unwrap().unwrap();
+        buffer.push_str(std::str::from_utf8(&chunk).unwrap());
+    }
+}
+
+#[test_group::group(engine, datafusion)]
+#[test_log::test(tokio::test)]
+async fn test_data_changes_handler() {
+    let harness = Harness::new().await;
+
+    let dataset = harness.dataset.clone();
+    let changes_url = format!("{}/changes", harness.dataset_url);
+
+    let client = async move {
+        let cl = reqwest::Client::new();
+        let staging_dir = tempfile::tempdir().unwrap();
+
+        // Resume from the offset supplied by the client
+        let res = cl
+            .get(&changes_url)
+            .query(&[("sinceOffset", "0")])
+            .send()
+            .await
+            .unwrap()
+            .error_for_status()
+            .unwrap();
+
+        pretty_assertions::assert_eq!(
+            "text/event-stream",
+            res.headers()[http::header::CONTENT_TYPE]
+        );
+
+        let mut body = res.bytes_stream();
+        let mut buffer = String::new();
+
+        let (event_type, id, data) = read_sse_event(&mut body, &mut buffer).await;
+        pretty_assertions::assert_eq!(event_type, "changes");
+        pretty_assertions::assert_eq!(id.as_deref(), Some("1"));
+        pretty_assertions::assert_eq!(
+            json!({
+                "dataFormat": "JsonAoS",
+                "numRecords": 1,
+                "lastOffset": 1,
+                "data": [{
+                    "city": "B",
+                    "event_time": "2050-01-01T12:00:00Z",
+                    "offset": 1,
+                    "op": 0,
+                    "population": 200,
+                    "system_time": "2050-01-01T12:00:00Z"
+                }]
+            }),
+            serde_json::from_str::<serde_json::Value>(&data).unwrap()
+        );
+
+        // New records are streamed as they are committed
+        write_population(
+            &dataset,
+            staging_dir.path(),
+            Utc.with_ymd_and_hms(2050, 1, 2, 12, 0, 0).unwrap(),
+            vec!["C"],
+            vec![300],
+        )
+        .await;
+
+        let (event_type, id, data) = read_sse_event(&mut body, &mut buffer).await;
+        pretty_assertions::assert_eq!(event_type, "changes");
+        pretty_assertions::assert_eq!(id.as_deref(), Some("2"));
+        pretty_assertions::assert_eq!(
+            json!({
+                "dataFormat": "JsonAoS",
+                "numRecords": 1,
+                "lastOffset": 2,
+                "data": [{
+                    "city": "C",
+                    "event_time": "2050-01-02T12:00:00Z",
+                    "offset": 2,
+                    "op": 0,
+                    "population": 300,
+                    "system_time": "2050-01-02T12:00:00Z"
+                }]
+            }),
+            serde_json::from_str::<serde_json::Value>(&data).unwrap()
+        );
+
+        drop(body);
+
+        // Reconnecting with the last seen event id
+        let res = cl
+            .get(&changes_url)
+            .header("Last-Event-ID", "1")
+            .query(&[("dataFormat", "JsonSoA")])
+            .send()
+            .await
+            .unwrap()
+            .error_for_status()
+            .unwrap();
+
+        let mut body = res.bytes_stream();
+        let mut buffer = String::new();
+
+        let (_, id, data) = read_sse_event(&mut body, &mut buffer).await;
+        pretty_assertions::assert_eq!(id.as_deref(), Some("2"));
+        pretty_assertions::assert_eq!(
+            json!({
+                "dataFormat": "JsonSoA",
+                "numRecords": 1,
+                "lastOffset": 2,
+                "data": {
+                    "city": ["C"],
+                    "event_time": ["2050-01-02T12:00:00Z"],
+                    "offset": [2],
+                    "op": [0],
+                    "population": [300],
+                    "system_time": ["2050-01-02T12:00:00Z"]
+                }
+            }),
+            serde_json::from_str::<serde_json::Value>(&data).unwrap()
+        );
+
+        // Invalid parameters
+        let res = cl
+            .get(&changes_url)
+            .query(&[("maxRecordsPerEvent", "0")])
+            .send()
+            .await
+            .unwrap();
+        pretty_assertions::assert_eq!(http::StatusCode::BAD_REQUEST, res.status());
+
+        let res = cl
+            .get(&changes_url)
+            .header("Last-Event-ID", "foo")
+            .send()
+            .await
+            .unwrap();
+        pretty_assertions::assert_eq!(http::StatusCode::BAD_REQUEST, res.status());
+    };
+
+    await_client_server_flow!(harness.server_harness.api_server_run(), client);
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
 #[test_group::group(engine, datafusion)]
 #[test_log::test(tokio::test)]
 async fn test_data_query_handler() {
@@ -1190,6 +1393,179 @@ async fn test_data_query_handler_data_formats() {
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
 
+#[test_group::group(engine, datafusion)]
+#[test_log::test(tokio::test)]
+async fn test_data_query_handler_binary_formats() {
+    let harness = Harness::new().await;
+
+    let client = async move {
+        let cl = reqwest::Client::new();
+
+        let query = format!(
+            "select offset, city, population from \"{}\" order by offset desc",
+            harness.dataset_handle.alias
+        );
+        let query_url = format!("{}query", harness.root_url);
+
+        // CSV
+        let res = cl
+            .get(&query_url)
+            .query(&[("query", query.as_str())])
+            .header(http::header::ACCEPT, "text/csv")
+            .send()
+            .await
+            .unwrap()
+            .error_for_status()
+            .unwrap();
+
+        assert_eq!(res.headers()[http::header::CONTENT_TYPE], "text/csv");
+        pretty_assertions::assert_eq!(
+            "offset,city,population\n1,B,200\n0,A,100",
+            res.text().await.unwrap()
+        );
+
+        // Arrow IPC
+        let res = cl
+            .post(&query_url)
+            .json(&json!({
+                "query": query,
+            }))
+            .header(
+                http::header::ACCEPT,
+                "application/vnd.apache.arrow.stream, application/json;q=0.9",
+            )
+            .send()
+            .await
+            .unwrap()
+            .error_for_status()
+            .unwrap();
+
+        assert_eq!(
+            res.headers()[http::header::CONTENT_TYPE],
+            "application/vnd.apache.arrow.stream"
+        );
+        let body = res.bytes().await.unwrap();
+        let reader =
+            datafusion::arrow::ipc::reader::StreamReader::try_new(std::io::Cursor::new(body), None)
+                .unwrap();
+        let batches: Vec<_> = reader.map(Result::unwrap).collect();
+        let batch =
+            datafusion::arrow::compute::concat_batches(&batches[0].schema(), &batches).unwrap();
+
+        assert_eq!(batch.num_rows(), 2);
+        assert_eq!(
+            batch
+                .schema()
+                .fields()
+                .iter()
+                .map(|f| f.name().as_str())
+                .collect::<Vec<_>>(),
+            ["offset", "city", "population"]
+        );
+
+        // Parquet
+        let res = cl
+            .get(&query_url)
+            .query(&[("query", query.as_str())])
+            .header(http::header::ACCEPT, "application/vnd.apache.parquet")
+            .send()
+            .await
+            .unwrap()
+            .error_for_status()
+            .unwrap();
+
+        assert_eq!(
+            res.headers()[http::header::CONTENT_TYPE],
+            "application/vnd.apache.parquet"
+        );
+        let body = res.bytes().await.unwrap();
+        let reader =
+            datafusion::parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder::try_new(
+                body,
+            )
+            .unwrap()
+            .build()
+            .unwrap();
+        let num_rows: usize = reader.map(|b| b.unwrap().num_rows()).sum();
+
+        assert_eq!(num_rows, 2);
+
+        // Proofs can't accompany binary data
+        let res = cl
+            .get(&query_url)
+            .query(&[("query", query.as_str()), ("include", "proof")])
+            .header(http::header::ACCEPT, "text/csv")
+            .send()
+            .await
+            .unwrap();
+
+        assert_eq!(res.status(), http::StatusCode::BAD_REQUEST);
+
+        // Preferred format wins regardless of the order
+        let res = cl
+            .get(&query_url)
+            .query(&[("query", query.as_str())])
+            .header(
+                http::header::ACCEPT,
+                "text/csv;q=0.5, application/vnd.apache.parquet;q=0.8, application/json;q=0.2",
+            )
+            .send()
+            .await
+            .unwrap()
+            .error_for_status()
+            .unwrap();
+
+        assert_eq!(
+            res.headers()[http::header::CONTENT_TYPE],
+            "application/vnd.apache.parquet"
+        );
+
+        let res = cl
+            .get(&query_url)
+            .query(&[("query", query.as_str())])
+            .header(http::header::ACCEPT, "text/csv;q=0.1, application/json")
+            .send()
+            .await
+            .unwrap()
+            .error_for_status()
+            .unwrap();
+
+        assert_eq!(
+            res.headers()[http::header::CONTENT_TYPE],
+            "application/json"
+        );
+
+        // Excluded and unsupported formats
+        let res = cl
+            .get(&query_url)
+            .query(&[("query", query.as_str())])
+            .header(http::header::ACCEPT, "text/csv;q=0, application/xml")
+            .send()
+            .await
+            .unwrap();
+
+        assert_eq!(res.status(), http::StatusCode::NOT_ACCEPTABLE);
+
+        // Tail
+        let res = cl
+            .get(format!("{}/tail", harness.dataset_url))
+            .header(http::header::ACCEPT, "text/csv")
+            .send()
+            .await
+            .unwrap()
+            .error_for_status()
+            .unwrap();
+
+        let csv = res.text().await.unwrap();
+        assert_eq!(csv.lines().count(), 3, "{csv}");
+        assert!(csv.contains("city,population"), "{csv}");
+    };
+
+    await_client_server_flow!(harness.server_harness.api_server_run(), client);
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
 #[test_group::group(engine, datafusion)]
 #[test_log::test(tokio::test)]
 async fn test_data_query_handler_schema_formats() {
@@ -32,7 +32,8 @@ async fn test_node_info_single_tenant() {
         pretty_assertions::assert_eq!(
             res.json::<serde_json::Value>().await.unwrap(),
             json!({
-                "isMultiTenant": false
+                "isMultiTenant": false,
+                "flightSqlUrl": "grpc://example.com:50050"
             })
         );
     };
@@ -58,7 +59,8 @@ async fn test_node_info_multi_tenant() {
         pretty_assertions::assert_eq!(
             res.json::<serde_json::Value>().await.unwrap(),
             json!({
-                "isMultiTenant": true
+                "isMultiTenant": true,
+                "flightSqlUrl": "grpc://example.com:50050"
             })
         );
     };
@@ -64,6 +64,7 @@ impl Harness {
             let mut b = dill::CatalogBuilder::new();
 
             b.add::<AuthenticationServiceImpl>()
+                .add::<email_utils::DummyEmailSender>()
                 .add_value(predefined_accounts_config)
                 .add::<InMemoryAccountRepository>()
                 .add_value(SystemTimeSourceStub::new())
@@ -29,8 +29,13 @@ use kamu_accounts_services::{
     PredefinedAccountsRegistrator,
 };
 use kamu_adapter_http::{
+    AccessToken,
     FileUploadLimitConfig,
+    MultipartUploadConfig,
+    MultipartUploadContext,
+    MultipartUploadError,
     UploadContext,
+    UploadService,
     UploadServiceLocal,
     UploadToken,
     UploadTokenBase64Json,
@@ -38,7 +43,8 @@ use kamu_adapter_http::{
 use kamu_core::{MediaType, TenancyConfig};
 use messaging_outbox::DummyOutboxImpl;
 use serde_json::json;
-use time_source::SystemTimeSourceDefault;
+use time_source::{SystemTimeSourceDefault, SystemTimeSourceStub};
+use uuid::Uuid;
 
 use crate::harness::{await_client_server_flow, TestAPIServer};
 
@@ -77,6 +83,7 @@ impl Harness {
             b.add_value(CacheDir::new(cache_dir.clone()))
                 .add_value(predefined_account_configs)
                 .add::<AuthenticationServiceImpl>()
+                .add::<email_utils::DummyEmailSender>()
                 .add::<InMemoryAccountRepository>()
                 .add::<AccessTokenServiceImpl>()
                 .add::<InMemoryAccessTokenRepository>()
@@ -84,7 +91,13 @@ impl Harness {
                 .add::<LoginPasswordAuthProvider>()
                 .add_value(JwtAuthenticationConfig::default())
                 .add_value(ServerUrlConfig::new_test(Some(&api_server_address)))
-                .add_value(FileUploadLimitConfig::new_in_bytes(100))
+                .add_value(FileUploadLimitConfig::new_in_bytes(100).with_multipart(
+                    MultipartUploadConfig {
+                        part_size_in_bytes: 4,
+                        max_file_size_in_bytes: 100,
+                        ..Default::default()
+                    },
+                ))
                 .add::<UploadServiceLocal>()
                 .add::<PredefinedAccountsRegistrator>()
                 .add::<DummyOutboxImpl>();
@@ -121,11 +134,12 @@ impl Harness {
 
     fn mock_upload_token(&self) -> UploadTokenBase64Json {
         UploadTokenBase64Json(UploadToken {
-            upload_id: "123".to_string(),
+            upload_id: Uuid::new_v4().simple().to_string(),
             file_name: "someFile.json".to_string(),
             owner_account_id: DEFAULT_ACCOUNT_ID.as_multibase().to_string(),
             content_length: 123,
             content_type: Some(MediaType::JSON.to_owned()),
+            multipart: None,
         })
     }
 
@@ -136,6 +150,18 @@ impl Harness {
         )
     }
 
+    fn upload_prepare_multipart_url(
+        &self,
+        file_name: &str,
+        content_type: &str,
+        file_size: usize,
+    ) -> String {
+        format!(
+            "http://{}/platform/file/upload/prepare-multipart?fileName={file_name}&contentType={content_type}&contentLength={file_size}",
+            self.api_server_addr(),
+        )
+    }
+
     fn upload_main_url(&self) -> String {
         format!("http://{}/platform/file/upload", self.api_server_addr(),)
     }
@@ -417,6 +443,115 @@ async fn test_attempt_upload_file_that_is_too_large() {
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
 
+#[test_log::test(tokio::test)]
+async fn test_attempt_upload_file_with_invalid_file_name() {
+    let harness = Harness::new().await;
+    let upload_prepare_url = harness.upload_prepare_url("..", "text/plain", 100);
+    let upload_prepare_multipart_url =
+        harness.upload_prepare_multipart_url("..", "text/plain", 100);
+    let access_token = harness.make_access_token(&DEFAULT_ACCOUNT_ID);
+
+    let client = async move {
+        let client = reqwest::Client::new();
+
+        for url in [upload_prepare_url, upload_prepare_multipart_url] {
+            let upload_prepare_response = client
+                .post(url)
+                .bearer_auth(access_token.clone())
+                .send()
+                .await
+                .unwrap();
+
+            pretty_assertions::assert_eq!(
+                http::StatusCode::BAD_REQUEST,
+                upload_prepare_response.status()
+            );
+            pretty_assertions::assert_eq!(
+                json!({
+                    "message": "Invalid file name: \"..\""
+                }),
+                upload_prepare_response
+                    .json::<serde_json::Value>()
+                    .await
+                    .unwrap()
+            );
+        }
+    };
+
+    await_client_server_flow!(harness.api_server_run(), client);
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[test_log::test(tokio::test)]
+async fn test_attempt_upload_file_with_tampered_token() {
+    let harness = Harness::new().await;
+    let upload_main_url = harness.upload_main_url();
+    let access_token = harness.make_access_token(&DEFAULT_ACCOUNT_ID);
+
+    let valid_token = harness.mock_upload_token().0;
+    let tampered_tokens = [
+        UploadToken {
+            upload_id: "../../outside".to_string(),
+            ..valid_token.clone()
+        },
+        UploadToken {
+            file_name: "../outside.json".to_string(),
+            ..valid_token.clone()
+        },
+        UploadToken {
+            owner_account_id: "..".to_string(),
+            ..valid_token
+        },
+    ]
+    .map(|upload_token| UploadTokenBase64Json(upload_token).to_string());
+
+    for upload_token in &tampered_tokens {
+        assert!(upload_token.parse::<UploadTokenBase64Json>().is_err());
+    }
+
+    let client = async move {
+        let client = reqwest::Client::new();
+
+        for upload_token in tampered_tokens {
+            let upload_main_response = client
+                .post(format!("{upload_main_url}/{upload_token}"))
+                .bearer_auth(access_token.clone())
+                .multipart(
+                    reqwest::multipart::Form::new().part(
+                        "file",
+                        reqwest::multipart::Part::text("some file")
+                            .file_name("test.txt")
+                            .mime_str("text/plain")
+                            .unwrap(),
+                    ),
+                )
+                .send()
+                .await
+                .unwrap();
+            pretty_assertions::assert_eq!(
+                http::StatusCode::BAD_REQUEST,
+                upload_main_response.status()
+            );
+
+            let upload_abort_response = client
+                .delete(format!("{upload_main_url}/{upload_token}"))
+                .bearer_auth(access_token.clone())
+                .send()
+                .await
+                .unwrap();
+            pretty_assertions::assert_eq!(
+                http::StatusCode::BAD_REQUEST,
+                upload_abort_response.status()
+            );
+        }
+    };
+
+    await_client_server_flow!(harness.api_server_run(), client);
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
 #[test_log::test(tokio::test)]
 async fn test_attempt_upload_file_that_has_different_length_than_declared() {
     const FILE_BODY: &str = "Some text";
@@ -555,3 +690,365 @@ async fn test_upload_then_read_file() {
 }
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[test_log::test(tokio::test)]
+async fn test_multipart_upload_resumed_after_interruption() {
+    const FILE_BODY: &str = "0123456789";
+
+    let harness = Harness::new().await;
+    let upload_prepare_url =
+        harness.upload_prepare_multipart_url("test.txt", "text/plain", FILE_BODY.len());
+    let upload_main_url = harness.upload_main_url();
+    let access_token = harness.make_access_token(&DEFAULT_ACCOUNT_ID);
+    let cache_dir = harness.cache_dir.clone();
+
+    let client = async move {
+        let client = reqwest::Client::new();
+
+        let upload_prepare_response = client
+            .post(upload_prepare_url)
+            .bearer_auth(access_token.clone())
+            .send()
+            .await
+            .unwrap();
+
+        pretty_assertions::assert_eq!(http::StatusCode::OK, upload_prepare_response.status());
+        let upload_context = upload_prepare_response
+            .json::<MultipartUploadContext>()
+            .await
+            .unwrap();
+        pretty_assertions::assert_eq!(4, upload_context.part_size);
+        pretty_assertions::assert_eq!(3, upload_context.parts_count);
+        assert!(upload_context.uploaded_parts.is_empty());
+        pretty_assertions::assert_eq!(
+            vec![1, 2, 3],
+            upload_context
+                .pending_parts
+                .iter()
+                .map(|part| part.part_number)
+                .collect::<Vec<_>>()
+        );
+
+        // Upload only the first and the last parts, as if the connection dropped
+        for part in [
+            &upload_context.pending_parts[0],
+            &upload_context.pending_parts[2],
+        ] {
+            pretty_assertions::assert_eq!(http::method::Method::PUT.as_str(), part.method);
+
+            let part_start = (part.part_number - 1) * upload_context.part_size;
+            let part_end = FILE_BODY.len().min(part_start + upload_context.part_size);
+
+            let upload_part_response = client
+                .put(part.upload_url.clone())
+                .bearer_auth(access_token.clone())
+                .body(&FILE_BODY[part_start..part_end])
+                .send()
+                .await
+                .unwrap();
+            pretty_assertions::assert_eq!(http::StatusCode::OK, upload_part_response.status());
+        }
+
+        let upload_token = upload_context.upload_token.to_string();
+
+        // Completion is rejected while parts are missing
+        let upload_complete_response = client
+            .post(upload_context.complete_url.clone())
+            .bearer_auth(access_token.clone())
+            .send()
+            .await
+            .unwrap();
+        pretty_assertions::assert_eq!(
+            http::StatusCode::BAD_REQUEST,
+            upload_complete_response.status()
+        );
+        pretty_assertions::assert_eq!(
+            json!({
+                "message": "Upload is missing parts: [2]"
+            }),
+            upload_complete_response
+                .json::<serde_json::Value>()
+                .await
+                .unwrap()
+        );
+
+        // Resume: find out which parts are still pending
+        let upload_status_response = client
+            .get(format!("{upload_main_url}/{upload_token}/parts"))
+            .bearer_auth(access_token.clone())
+            .send()
+            .await
+            .unwrap();
+        pretty_assertions::assert_eq!(http::StatusCode::OK, upload_status_response.status());
+        let upload_status = upload_status_response
+            .json::<MultipartUploadContext>()
+            .await
+            .unwrap();
+        pretty_assertions::assert_eq!(
+            vec![(1, 4), (3, 2)],
+            upload_status
+                .uploaded_parts
+                .iter()
+                .map(|part| (part.part_number, part.size))
+                .collect::<Vec<_>>()
+        );
+        pretty_assertions::assert_eq!(1, upload_status.pending_parts.len());
+        pretty_assertions::assert_eq!(2, upload_status.pending_parts[0].part_number);
+
+        let upload_part_response = client
+            .put(upload_status.pending_parts[0].upload_url.clone())
+            .bearer_auth(access_token.clone())
+            .body(&FILE_BODY[4..8])
+            .send()
+            .await
+            .unwrap();
+        pretty_assertions::assert_eq!(http::StatusCode::OK, upload_part_response.status());
+
+        let upload_complete_response = client
+            .post(upload_status.complete_url)
+            .bearer_auth(access_token.clone())
+            .send()
+            .await
+            .unwrap();
+        pretty_assertions::assert_eq!(http::StatusCode::OK, upload_complete_response.status());
+
+        // The assembled file is available just like a file uploaded at once
+        let expected_upload_path = Harness::target_path_from_upload_url(
+            &cache_dir,
+            &format!("{upload_main_url}/{upload_token}"),
+        );
+        let file_body = std::fs::read_to_string(expected_upload_path).unwrap();
+        pretty_assertions::assert_eq!(FILE_BODY, file_body);
+
+        let read_file_response = client
+            .get(format!("{upload_main_url}/{upload_token}"))
+            .send()
+            .await
+            .unwrap();
+        pretty_assertions::assert_eq!(http::StatusCode::OK, read_file_response.status());
+        pretty_assertions::assert_eq!(FILE_BODY, read_file_response.text().await.unwrap());
+    };
+
+    await_client_server_flow!(harness.api_server_run(), client);
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[test_log::test(tokio::test)]
+async fn test_multipart_upload_invalid_parts() {
+    const FILE_BODY: &str = "0123456789";
+
+    let harness = Harness::new().await;
+    let upload_prepare_url =
+        harness.upload_prepare_multipart_url("test.txt", "text/plain", FILE_BODY.len());
+    let upload_main_url = harness.upload_main_url();
+    let access_token = harness.make_access_token(&DEFAULT_ACCOUNT_ID);
+    let different_access_token = harness.make_access_token(&harness.another_account_id);
+
+    let client = async move {
+        let client = reqwest::Client::new();
+
+        let upload_context = client
+            .post(upload_prepare_url)
+            .bearer_auth(access_token.clone())
+            .send()
+            .await
+            .unwrap()
+            .json::<MultipartUploadContext>()
+            .await
+            .unwrap();
+        let upload_token = upload_context.upload_token.to_string();
+
+        let upload_part_response = client
+            .put(upload_context.pending_parts[0].upload_url.clone())
+            .bearer_auth(access_token.clone())
+            .body("012")
+            .send()
+            .await
+            .unwrap();
+        pretty_assertions::assert_eq!(http::StatusCode::BAD_REQUEST, upload_part_response.status());
+        pretty_assertions::assert_eq!(
+            json!({
+                "message": "Actual content length 3 does not match the initially declared length 4"
+            }),
+            upload_part_response
+                .json::<serde_json::Value>()
+                .await
+                .unwrap()
+        );
+
+        let upload_part_response = client
+            .put(format!("{upload_main_url}/{upload_token}/parts/4"))
+            .bearer_auth(access_token.clone())
+            .body("89")
+            .send()
+            .await
+            .unwrap();
+        pretty_assertions::assert_eq!(http::StatusCode::BAD_REQUEST, upload_part_response.status());
+        pretty_assertions::assert_eq!(
+            json!({
+                "message": "Part number 4 is outside of the range 1..=3"
+            }),
+            upload_part_response
+                .json::<serde_json::Value>()
+                .await
+                .unwrap()
+        );
+
+        let upload_part_response = client
+            .put(upload_context.pending_parts[0].upload_url.clone())
+            .bearer_auth(different_access_token)
+            .body("0123")
+            .send()
+            .await
+            .unwrap();
+        pretty_assertions::assert_eq!(http::StatusCode::FORBIDDEN, upload_part_response.status());
+
+        // Rejected parts are not counted as uploaded
+        let upload_status = client
+            .get(format!("{upload_main_url}/{upload_token}/parts"))
+            .bearer_auth(access_token.clone())
+            .send()
+            .await
+            .unwrap()
+            .json::<MultipartUploadContext>()
+            .await
+            .unwrap();
+        assert!(upload_status.uploaded_parts.is_empty());
+        pretty_assertions::assert_eq!(3, upload_status.pending_parts.len());
+    };
+
+    await_client_server_flow!(harness.api_server_run(), client);
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[test_log::test(tokio::test)]
+async fn test_multipart_upload_abort() {
+    const FILE_BODY: &str = "0123456789";
+
+    let harness = Harness::new().await;
+    let upload_prepare_url =
+        harness.upload_prepare_multipart_url("test.txt", "text/plain", FILE_BODY.len());
+    let upload_main_url = harness.upload_main_url();
+    let access_token = harness.make_access_token(&DEFAULT_ACCOUNT_ID);
+
+    let client = async move {
+        let client = reqwest::Client::new();
+
+        let upload_context = client
+            .post(upload_prepare_url)
+            .bearer_auth(access_token.clone())
+            .send()
+            .await
+            .unwrap()
+            .json::<MultipartUploadContext>()
+            .await
+            .unwrap();
+        let upload_token = upload_context.upload_token.to_string();
+
+        let upload_abort_response = client
+            .delete(format!("{upload_main_url}/{upload_token}"))
+            .bearer_auth(access_token.clone())
+            .send()
+            .await
+            .unwrap();
+        pretty_assertions::assert_eq!(http::StatusCode::OK, upload_abort_response.status());
+
+        let upload_status_response = client
+            .get(format!("{upload_main_url}/{upload_token}/parts"))
+            .bearer_auth(access_token.clone())
+            .send()
+            .await
+            .unwrap();
+        pretty_assertions::assert_eq!(http::StatusCode::NOT_FOUND, upload_status_response.status());
+        pretty_assertions::assert_eq!(
+            json!({
+                "message": "Multipart upload not found or expired"
+            }),
+            upload_status_response
+                .json::<serde_json::Value>()
+                .await
+                .unwrap()
+        );
+    };
+
+    await_client_server_flow!(harness.api_server_run(), client);
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[test_log::test(tokio::test)]
+async fn test_multipart_upload_expiration_and_cleanup() {
+    let tempdir = tempfile::tempdir().unwrap();
+    let cache_dir = tempdir.path().join("cache");
+    let t0 = chrono::Utc::now();
+    let time_source = SystemTimeSourceStub::new_set(t0);
+
+    let catalog = dill::CatalogBuilder::new()
+        .add_value(CacheDir::new(cache_dir.clone()))
+        .add_value(ServerUrlConfig::new_test(None))
+        .add_value(AccessToken::new("some-token"))
+        .add_value(
+            FileUploadLimitConfig::new_in_bytes(100).with_multipart(MultipartUploadConfig {
+                part_size_in_bytes: 4,
+                max_file_size_in_bytes: 100,
+                incomplete_upload_ttl: chrono::Duration::hours(1),
+                ..Default::default()
+            }),
+        )
+        .add_value(time_source.clone())
+        .bind::<dyn time_source::SystemTimeSource, SystemTimeSourceStub>()
+        .add::<UploadServiceLocal>()
+        .build();
+
+    let upload_service = catalog.get_one::<dyn UploadService>().unwrap();
+
+    let upload_context = upload_service
+        .make_multipart_upload_context(
+            &DEFAULT_ACCOUNT_ID,
+            "test.txt".to_string(),
+            Some(MediaType::JSON.to_owned()),
+            10,
+        )
+        .await
+        .unwrap();
+    let upload_token = upload_context.upload_token.0;
+    pretty_assertions::assert_eq!(t0 + chrono::Duration::hours(1), upload_context.expires_at);
+
+    upload_service
+        .save_upload_part(&upload_token, 1, Box::new(&b"0123"[..]))
+        .await
+        .unwrap();
+
+    let upload_folder_path = cache_dir
+        .join("uploads")
+        .join(DEFAULT_ACCOUNT_ID.as_multibase().to_string())
+        .join(&upload_token.upload_id);
+    assert!(upload_folder_path.is_dir());
+
+    // Not expired yet
+    pretty_assertions::assert_eq!(0, upload_service.cleanup_expired_uploads().await.unwrap());
+    assert!(upload_folder_path.is_dir());
+
+    // The state is kept in the storage, so another instance of the service
+    // (e.g. after the server restarts) can resume the upload
+    let restarted_upload_service = catalog.get_one::<dyn UploadService>().unwrap();
+    let upload_status = restarted_upload_service
+        .multipart_upload_status(&upload_token)
+        .await
+        .unwrap();
+    pretty_assertions::assert_eq!(1, upload_status.uploaded_parts.len());
+    pretty_assertions::assert_eq!(2, upload_status.pending_parts.len());
+
+    time_source.set(t0 + chrono::Duration::hours(2));
+
+    assert!(matches!(
+        upload_service.multipart_upload_status(&upload_token).await,
+        Err(MultipartUploadError::NotFound(_))
+    ));
+    pretty_assertions::assert_eq!(1, upload_service.cleanup_expired_uploads().await.unwrap());
+    assert!(!upload_folder_path.exists());
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
@@ -58,6 +58,7 @@ impl Harness {
 
             b.add_value(PredefinedAccountsConfig::single_tenant())
                 .add::<AuthenticationServiceImpl>()
+                .add::<email_utils::DummyEmailSender>()
                 .add::<InMemoryAccountRepository>()
                 .add::<AccessTokenServiceImpl>()
                 .add::<InMemoryAccessTokenRepository>()
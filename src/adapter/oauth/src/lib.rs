@@ -8,5 +8,9 @@
 // by the Apache License, Version 2.0.
 
 mod oauth_github;
+mod oauth_oidc;
+mod oidc_client;
 
 pub use oauth_github::*;
+pub use oauth_oidc::*;
+pub use oidc_client::*;
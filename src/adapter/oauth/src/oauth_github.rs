@@ -206,6 +206,7 @@ impl AuthenticationProvider for OAuthGithub {
             avatar_url: github_account_info.avatar_url,
             // Use GitHub ID as an identity key
             provider_identity_key: github_account_info.id.to_string(),
+            is_admin: None,
         })
     }
 }
@@ -323,6 +324,7 @@ impl AuthenticationProvider for DummyOAuthGithub {
             display_name: account.clone(),
             avatar_url: None,
             provider_identity_key: account,
+            is_admin: None,
         })
     }
 }
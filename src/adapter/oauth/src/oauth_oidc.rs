@@ -0,0 +1,429 @@
+// Copyright Kamu Data, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::sync::Arc;
+
+use dill::*;
+use email_utils::Email;
+use internal_error::{ErrorIntoInternal, InternalError, ResultIntoInternal};
+use jsonwebtoken::jwk::{Jwk, JwkSet};
+use jsonwebtoken::{Algorithm, DecodingKey, Validation};
+use kamu_accounts::*;
+use serde::Deserialize;
+use thiserror::Error;
+use tokio::sync::{OnceCell, RwLock};
+use url::Url;
+
+use crate::{OidcClient, OidcClientError};
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+pub const PROVIDER_OIDC: &str = "oidc";
+pub const ENV_VAR_KAMU_AUTH_OIDC_CLIENT_SECRET: &str = "KAMU_AUTH_OIDC_CLIENT_SECRET";
+
+const CLAIM_NONCE: &str = "nonce";
+const CLAIM_EMAIL_VERIFIED: &str = "email_verified";
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+/// Authentication via a generic OpenID Connect provider (Keycloak, Okta, Azure
+/// AD, ...). Accounts are identified by the `sub` claim of the ID token
+pub struct OAuthOidc {
+    config: Arc<OidcAuthenticationConfig>,
+    client: OnceCell<OidcClient>,
+    jwks: RwLock<Option<JwkSet>>,
+}
+
+#[component(pub)]
+#[interface(dyn AuthenticationProvider)]
+#[scope(Singleton)]
+impl OAuthOidc {
+    pub fn new(config: Arc<OidcAuthenticationConfig>) -> Self {
+        Self {
+            config,
+            client: OnceCell::new(),
+            jwks: RwLock::new(None),
+        }
+    }
+
+    async fn get_client(&self) -> Result<&OidcClient, InternalError> {
+        self.client
+            .get_or_try_init(|| async {
+                OidcClient::discover(
+                    &self.config.issuer_url,
+                    self.config.client_id.clone(),
+                    self.config.client_secret.clone(),
+                )
+                .await
+                .int_err()
+            })
+            .await
+    }
+
+    async fn find_jwk(
+        &self,
+        client: &OidcClient,
+        key_id: Option<&str>,
+    ) -> Result<Option<Jwk>, InternalError> {
+        let find_in = |jwks: &JwkSet| match key_id {
+            Some(key_id) => jwks.find(key_id).cloned(),
+            None if jwks.keys.len() == 1 => jwks.keys.first().cloned(),
+            None => None,
+        };
+
+        if let Some(jwk) = self.jwks.read().await.as_ref().and_then(find_in) {
+            return Ok(Some(jwk));
+        }
+
+        // Unknown key: the provider might have rotated its keys since the last fetch
+        let jwks = client.fetch_jwks().await.int_err()?;
+        let maybe_jwk = find_in(&jwks);
+        *self.jwks.write().await = Some(jwks);
+
+        Ok(maybe_jwk)
+    }
+
+    async fn validate_id_token(
+        &self,
+        client: &OidcClient,
+        id_token: &str,
+    ) -> Result<OidcIdTokenClaims, ProviderLoginError> {
+        let header = jsonwebtoken::decode_header(id_token).map_err(|e| {
+            ProviderLoginError::InvalidCredentials(InvalidCredentialsError::new(Box::new(e)))
+        })?;
+
+        // Keys are published by the provider, so only asymmetric algorithms are
+        // acceptable
+        if matches!(
+            header.alg,
+            Algorithm::HS256 | Algorithm::HS384 | Algorithm::HS512
+        ) {
+            tracing::warn!(alg = ?header.alg, "ID token signed with a symmetric algorithm");
+            return Err(ProviderLoginError::RejectedCredentials(
+                RejectedCredentialsError {},
+            ));
+        }
+
+        let Some(jwk) = self.find_jwk(client, header.kid.as_deref()).await? else {
+            tracing::warn!(kid = ?header.kid, "ID token signed with an unknown key");
+            return Err(ProviderLoginError::RejectedCredentials(
+                RejectedCredentialsError {},
+            ));
+        };
+        let decoding_key = DecodingKey::from_jwk(&jwk).int_err()?;
+
+        let mut validation = Validation::new(header.alg);
+        validation.set_issuer(&[&client.metadata().issuer]);
+        validation.set_audience(&[&self.config.client_id]);
+        validation.set_required_spec_claims(&["exp", "iss", "aud", "sub"]);
+
+        let token_data =
+            jsonwebtoken::decode::<OidcIdTokenClaims>(id_token, &decoding_key, &validation)
+                .map_err(|e| {
+                    tracing::warn!(error = %e, "ID token rejected");
+                    ProviderLoginError::RejectedCredentials(RejectedCredentialsError {})
+                })?;
+
+        Ok(token_data.claims)
+    }
+
+    /// ID tokens passed directly can be replayed by anyone who got hold of
+    /// them, so only freshly issued tokens of the device flow are accepted:
+    /// tokens of the code flow carry a nonce and are never passed directly
+    fn validate_device_flow_claims(
+        &self,
+        claims: &OidcIdTokenClaims,
+    ) -> Result<(), ProviderLoginError> {
+        if claims.other.contains_key(CLAIM_NONCE) {
+            tracing::warn!("ID token issued for the authorization code flow passed directly");
+            return Err(ProviderLoginError::RejectedCredentials(
+                RejectedCredentialsError {},
+            ));
+        }
+
+        // Tokens without `iat` are treated as too old
+        let issued_at = claims.iat.unwrap_or_default();
+        let age = jsonwebtoken::get_current_timestamp().saturating_sub(issued_at);
+        if age > self.config.id_token_max_age.as_secs() {
+            tracing::warn!(issued_at, age, "ID token is too old");
+            return Err(ProviderLoginError::RejectedCredentials(
+                RejectedCredentialsError {},
+            ));
+        }
+
+        Ok(())
+    }
+
+    fn map_claims(
+        &self,
+        claims: &OidcIdTokenClaims,
+    ) -> Result<ProviderLoginResponse, ProviderLoginError> {
+        let mapping = &self.config.claims;
+
+        let account_name = claims
+            .get_str(&mapping.account_name)
+            .and_then(|name| odf::AccountName::try_from(name).ok())
+            .ok_or_else(|| invalid_claim(&mapping.account_name))?;
+
+        let email = match claims.get_str(&mapping.email) {
+            Some(email) => Email::parse(email).map_err(|_| invalid_claim(&mapping.email))?,
+            None => return Err(ProviderLoginError::NoPrimaryEmail(NoPrimaryEmailError {})),
+        };
+
+        // Accounts are matched by email, so the provider has to vouch for it
+        if !claims.is_email_verified() {
+            return Err(ProviderLoginError::InvalidCredentials(
+                InvalidCredentialsError::new(Box::new(OidcUnverifiedEmailError {
+                    email: email.as_ref().to_string(),
+                })),
+            ));
+        }
+
+        let display_name = claims
+            .get_str(&mapping.display_name)
+            .map_or_else(|| account_name.to_string(), ToString::to_string);
+
+        let is_admin = mapping
+            .admin_group
+            .as_ref()
+            .map(|admin_group| claims.has_group(&mapping.groups, admin_group));
+
+        Ok(ProviderLoginResponse {
+            account_name,
+            account_type: AccountType::User,
+            email,
+            display_name,
+            avatar_url: claims.get_str("picture").map(ToString::to_string),
+            provider_identity_key: claims.sub.clone(),
+            is_admin,
+        })
+    }
+}
+
+fn invalid_claim(claim: &str) -> ProviderLoginError {
+    ProviderLoginError::InvalidCredentials(InvalidCredentialsError::new(Box::new(
+        OidcInvalidClaimError {
+            claim: claim.to_string(),
+        },
+    )))
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[async_trait::async_trait]
+impl AuthenticationProvider for OAuthOidc {
+    fn provider_name(&self) -> &'static str {
+        PROVIDER_OIDC
+    }
+
+    fn generate_id(&self, _: &odf::AccountName) -> odf::AccountID {
+        // Random DID, the provider's subject identifier is not a public key
+        odf::AccountID::new_generated_ed25519().1
+    }
+
+    async fn login(
+        &self,
+        login_credentials_json: String,
+    ) -> Result<ProviderLoginResponse, ProviderLoginError> {
+        // Decode credentials
+        let oidc_login_credentials = serde_json::from_str::<OidcLoginCredentials>(
+            login_credentials_json.as_str(),
+        )
+        .map_err(|e| {
+            ProviderLoginError::InvalidCredentials(InvalidCredentialsError::new(Box::new(e)))
+        })?;
+
+        let client = self.get_client().await?;
+
+        // 2 types of login:
+        //  - we have an authorization code (with PKCE verifier) obtained by the UI,
+        //    which we exchange for the ID token bound to the nonce of the UI's
+        //    authorization request
+        //  - we have an ID token already, obtained by CLI via the device flow
+        let claims = if let Some(code) = oidc_login_credentials.code {
+            let Some(nonce) = oidc_login_credentials.nonce else {
+                return Err(ProviderLoginError::InvalidCredentials(
+                    InvalidCredentialsError::new(Box::new(OidcInvalidCredentialsError {})),
+                ));
+            };
+
+            let token_response = client
+                .exchange_authorization_code(
+                    &code,
+                    oidc_login_credentials.code_verifier.as_deref(),
+                    oidc_login_credentials.redirect_uri.as_deref(),
+                )
+                .await
+                .map_err(|e| match e {
+                    OidcClientError::TokenRequestRejected(e) => {
+                        ProviderLoginError::InvalidCredentials(InvalidCredentialsError::new(
+                            Box::new(e),
+                        ))
+                    }
+                    e => ProviderLoginError::Internal(e.int_err()),
+                })?;
+
+            let id_token = token_response.id_token.ok_or_else(|| {
+                ProviderLoginError::Internal("Token response contains no ID token".int_err())
+            })?;
+
+            let claims = self.validate_id_token(client, &id_token).await?;
+            if claims.get_str(CLAIM_NONCE) != Some(nonce.as_str()) {
+                tracing::warn!("ID token nonce does not match the authorization request");
+                return Err(ProviderLoginError::RejectedCredentials(
+                    RejectedCredentialsError {},
+                ));
+            }
+            claims
+        } else if let Some(id_token) = oidc_login_credentials.id_token {
+            let claims = self.validate_id_token(client, &id_token).await?;
+            self.validate_device_flow_claims(&claims)?;
+            claims
+        } else {
+            // Either "code" with "nonce" or "idToken" are expected in the query
+            return Err(ProviderLoginError::InvalidCredentials(
+                InvalidCredentialsError::new(Box::new(OidcInvalidCredentialsError {})),
+            ));
+        };
+
+        self.map_claims(&claims)
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OidcLoginCredentials {
+    pub code: Option<String>,
+    pub code_verifier: Option<String>,
+    pub redirect_uri: Option<String>,
+    /// Nonce of the authorization request the code was obtained with
+    pub nonce: Option<String>,
+    pub id_token: Option<String>,
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[derive(Debug, Clone, Deserialize)]
+struct OidcIdTokenClaims {
+    sub: String,
+    iat: Option<u64>,
+    #[serde(flatten)]
+    other: serde_json::Map<String, serde_json::Value>,
+}
+
+impl OidcIdTokenClaims {
+    fn get_str(&self, claim: &str) -> Option<&str> {
+        self.other.get(claim).and_then(serde_json::Value::as_str)
+    }
+
+    fn is_email_verified(&self) -> bool {
+        match self.other.get(CLAIM_EMAIL_VERIFIED) {
+            Some(serde_json::Value::Bool(verified)) => *verified,
+            // Some providers emit booleans as strings
+            Some(serde_json::Value::String(verified)) => verified == "true",
+            _ => false,
+        }
+    }
+
+    fn has_group(&self, groups_claim: &str, group: &str) -> bool {
+        match self.other.get(groups_claim) {
+            Some(serde_json::Value::Array(groups)) => groups
+                .iter()
+                .any(|g| g.as_str().is_some_and(|g| g == group)),
+            // Some providers emit a single group as a plain string
+            Some(serde_json::Value::String(g)) => g == group,
+            _ => false,
+        }
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[derive(Debug, Error)]
+#[error("Invalid credentials: pass either authorization code with nonce or ID token")]
+struct OidcInvalidCredentialsError {}
+
+#[derive(Debug, Error)]
+#[error("Email '{email}' is not verified by the identity provider")]
+struct OidcUnverifiedEmailError {
+    email: String,
+}
+
+#[derive(Debug, Error)]
+#[error("ID token has no valid '{claim}' claim")]
+struct OidcInvalidClaimError {
+    claim: String,
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[derive(Debug, Clone)]
+pub struct OidcAuthenticationConfig {
+    pub issuer_url: Url,
+    pub client_id: String,
+    /// Not needed for public clients using PKCE
+    pub client_secret: Option<String>,
+    pub claims: OidcClaimsMapping,
+    /// How long after issuing an ID token obtained via the device flow can be
+    /// exchanged for a login
+    pub id_token_max_age: std::time::Duration,
+}
+
+impl OidcAuthenticationConfig {
+    pub const DEFAULT_ID_TOKEN_MAX_AGE: std::time::Duration = std::time::Duration::from_secs(300);
+
+    pub fn new(issuer_url: Url, client_id: String, client_secret: Option<String>) -> Self {
+        Self {
+            issuer_url,
+            client_id,
+            client_secret,
+            claims: OidcClaimsMapping::default(),
+            id_token_max_age: Self::DEFAULT_ID_TOKEN_MAX_AGE,
+        }
+    }
+
+    pub fn with_claims(self, claims: OidcClaimsMapping) -> Self {
+        Self { claims, ..self }
+    }
+
+    pub fn with_id_token_max_age(self, id_token_max_age: std::time::Duration) -> Self {
+        Self {
+            id_token_max_age,
+            ..self
+        }
+    }
+}
+
+/// Names of ID token claims the account fields are taken from
+#[derive(Debug, Clone)]
+pub struct OidcClaimsMapping {
+    pub account_name: String,
+    pub email: String,
+    pub display_name: String,
+    pub groups: String,
+    /// Members of this group become administrators. When not specified, the
+    /// admin flag is not managed by the provider
+    pub admin_group: Option<String>,
+}
+
+impl Default for OidcClaimsMapping {
+    fn default() -> Self {
+        Self {
+            account_name: String::from("preferred_username"),
+            email: String::from("email"),
+            display_name: String::from("name"),
+            groups: String::from("groups"),
+            admin_group: None,
+        }
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
@@ -0,0 +1,348 @@
+// Copyright Kamu Data, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::time::Duration;
+
+use internal_error::{ErrorIntoInternal, InternalError, ResultIntoInternal};
+use jsonwebtoken::jwk::JwkSet;
+use serde::Deserialize;
+use thiserror::Error;
+use url::Url;
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+const DEVICE_CODE_GRANT_TYPE: &str = "urn:ietf:params:oauth:grant-type:device_code";
+
+/// Polling interval suggested by RFC 8628 when the provider specifies none
+const DEFAULT_DEVICE_POLLING_INTERVAL_SECS: u64 = 5;
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+/// Client of an OpenID Connect provider, configured from the provider's
+/// discovery document
+pub struct OidcClient {
+    http_client: reqwest::Client,
+    metadata: OidcProviderMetadata,
+    client_id: String,
+    client_secret: Option<String>,
+}
+
+impl OidcClient {
+    pub async fn discover(
+        issuer_url: &Url,
+        client_id: String,
+        client_secret: Option<String>,
+    ) -> Result<Self, OidcClientError> {
+        let http_client = reqwest::Client::builder()
+            .user_agent(concat!(
+                env!("CARGO_PKG_NAME"),
+                "/",
+                env!("CARGO_PKG_VERSION"),
+            ))
+            .build()
+            .int_err()?;
+
+        let discovery_url = Url::parse(&format!(
+            "{}/.well-known/openid-configuration",
+            issuer_url.as_str().trim_end_matches('/')
+        ))
+        .int_err()?;
+
+        let metadata = http_client
+            .get(discovery_url)
+            .header(http::header::ACCEPT, "application/json")
+            .send()
+            .await
+            .int_err()?
+            .error_for_status()
+            .int_err()?
+            .json::<OidcProviderMetadata>()
+            .await
+            .int_err()?;
+
+        // Issuer identifier must exactly match the one discovery was requested for,
+        // modulo the trailing slash
+        if metadata.issuer.trim_end_matches('/') != issuer_url.as_str().trim_end_matches('/') {
+            return Err(OidcClientError::Internal(
+                format!(
+                    "Discovered issuer '{}' does not match the configured '{issuer_url}'",
+                    metadata.issuer
+                )
+                .int_err(),
+            ));
+        }
+
+        Ok(Self {
+            http_client,
+            metadata,
+            client_id,
+            client_secret,
+        })
+    }
+
+    pub fn metadata(&self) -> &OidcProviderMetadata {
+        &self.metadata
+    }
+
+    pub fn client_id(&self) -> &str {
+        &self.client_id
+    }
+
+    pub async fn fetch_jwks(&self) -> Result<JwkSet, OidcClientError> {
+        let jwks = self
+            .http_client
+            .get(self.metadata.jwks_uri.clone())
+            .header(http::header::ACCEPT, "application/json")
+            .send()
+            .await
+            .int_err()?
+            .error_for_status()
+            .int_err()?
+            .json::<JwkSet>()
+            .await
+            .int_err()?;
+
+        Ok(jwks)
+    }
+
+    /// Exchanges the code obtained by the authorization code flow. The code
+    /// verifier has to be passed when the flow was started with a PKCE
+    /// challenge
+    pub async fn exchange_authorization_code(
+        &self,
+        code: &str,
+        code_verifier: Option<&str>,
+        redirect_uri: Option<&str>,
+    ) -> Result<OidcTokenResponse, OidcClientError> {
+        let mut params = vec![("grant_type", "authorization_code"), ("code", code)];
+        if let Some(code_verifier) = code_verifier {
+            params.push(("code_verifier", code_verifier));
+        }
+        if let Some(redirect_uri) = redirect_uri {
+            params.push(("redirect_uri", redirect_uri));
+        }
+
+        match self.request_token(&params).await? {
+            Ok(token_response) => Ok(token_response),
+            Err(error_response) => Err(OidcClientError::TokenRequestRejected(
+                OidcTokenRequestRejectedError {
+                    error: error_response.error,
+                    error_description: error_response.error_description,
+                },
+            )),
+        }
+    }
+
+    /// Starts the device authorization flow (RFC 8628) used on machines
+    /// without a browser
+    pub async fn start_device_authorization(
+        &self,
+        scopes: &[String],
+    ) -> Result<OidcDeviceAuthorization, OidcClientError> {
+        let Some(device_authorization_endpoint) = &self.metadata.device_authorization_endpoint
+        else {
+            return Err(OidcClientError::DeviceFlowNotSupported(
+                OidcDeviceFlowNotSupportedError {},
+            ));
+        };
+
+        let scope = scopes.join(" ");
+        let mut params = vec![
+            ("client_id", self.client_id.as_str()),
+            ("scope", scope.as_str()),
+        ];
+        if let Some(client_secret) = &self.client_secret {
+            params.push(("client_secret", client_secret.as_str()));
+        }
+
+        let device_authorization = self
+            .http_client
+            .post(device_authorization_endpoint.clone())
+            .header(http::header::ACCEPT, "application/json")
+            .form(&params)
+            .send()
+            .await
+            .int_err()?
+            .error_for_status()
+            .int_err()?
+            .json::<OidcDeviceAuthorization>()
+            .await
+            .int_err()?;
+
+        Ok(device_authorization)
+    }
+
+    /// Polls the token endpoint until the user completes or declines the
+    /// device authorization
+    pub async fn poll_device_token(
+        &self,
+        device_authorization: &OidcDeviceAuthorization,
+    ) -> Result<OidcTokenResponse, OidcClientError> {
+        let mut interval = Duration::from_secs(
+            device_authorization
+                .interval
+                .unwrap_or(DEFAULT_DEVICE_POLLING_INTERVAL_SECS),
+        );
+        let deadline =
+            tokio::time::Instant::now() + Duration::from_secs(device_authorization.expires_in);
+
+        loop {
+            let params = [
+                ("grant_type", DEVICE_CODE_GRANT_TYPE),
+                ("device_code", device_authorization.device_code.as_str()),
+            ];
+
+            match self.request_token(&params).await? {
+                Ok(token_response) => return Ok(token_response),
+                Err(error_response) => match error_response.error.as_str() {
+                    "authorization_pending" => {}
+                    "slow_down" => {
+                        interval += Duration::from_secs(DEFAULT_DEVICE_POLLING_INTERVAL_SECS);
+                    }
+                    "access_denied" => {
+                        return Err(OidcClientError::AuthorizationDenied(
+                            OidcAuthorizationDeniedError {},
+                        ))
+                    }
+                    "expired_token" => {
+                        return Err(OidcClientError::AuthorizationExpired(
+                            OidcAuthorizationExpiredError {},
+                        ))
+                    }
+                    _ => {
+                        return Err(OidcClientError::TokenRequestRejected(
+                            OidcTokenRequestRejectedError {
+                                error: error_response.error,
+                                error_description: error_response.error_description,
+                            },
+                        ))
+                    }
+                },
+            }
+
+            if tokio::time::Instant::now() + interval > deadline {
+                return Err(OidcClientError::AuthorizationExpired(
+                    OidcAuthorizationExpiredError {},
+                ));
+            }
+            tokio::time::sleep(interval).await;
+        }
+    }
+
+    async fn request_token(
+        &self,
+        params: &[(&str, &str)],
+    ) -> Result<Result<OidcTokenResponse, OidcTokenErrorResponse>, InternalError> {
+        let mut params = params.to_vec();
+        params.push(("client_id", self.client_id.as_str()));
+        if let Some(client_secret) = &self.client_secret {
+            params.push(("client_secret", client_secret.as_str()));
+        }
+
+        let response = self
+            .http_client
+            .post(self.metadata.token_endpoint.clone())
+            .header(http::header::ACCEPT, "application/json")
+            .form(&params)
+            .send()
+            .await
+            .int_err()?;
+
+        let status = response.status();
+        let body = response.text().await.int_err()?;
+
+        if status.is_success() {
+            serde_json::from_str::<OidcTokenResponse>(&body)
+                .int_err()
+                .map(Ok)
+        } else if let Ok(error_response) = serde_json::from_str::<OidcTokenErrorResponse>(&body) {
+            Ok(Err(error_response))
+        } else {
+            Err(format!("Token endpoint responded with {status}: {body}").int_err())
+        }
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+/// Subset of the OpenID provider metadata used by the client
+#[derive(Debug, Clone, Deserialize)]
+pub struct OidcProviderMetadata {
+    pub issuer: String,
+    pub authorization_endpoint: Url,
+    pub token_endpoint: Url,
+    pub jwks_uri: Url,
+    pub userinfo_endpoint: Option<Url>,
+    pub device_authorization_endpoint: Option<Url>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct OidcTokenResponse {
+    pub access_token: String,
+    pub token_type: String,
+    pub id_token: Option<String>,
+    pub expires_in: Option<u64>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct OidcTokenErrorResponse {
+    error: String,
+    error_description: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct OidcDeviceAuthorization {
+    pub device_code: String,
+    pub user_code: String,
+    pub verification_uri: Url,
+    pub verification_uri_complete: Option<Url>,
+    pub expires_in: u64,
+    pub interval: Option<u64>,
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[derive(Debug, Error)]
+pub enum OidcClientError {
+    #[error(transparent)]
+    DeviceFlowNotSupported(OidcDeviceFlowNotSupportedError),
+
+    #[error(transparent)]
+    AuthorizationDenied(OidcAuthorizationDeniedError),
+
+    #[error(transparent)]
+    AuthorizationExpired(OidcAuthorizationExpiredError),
+
+    #[error(transparent)]
+    TokenRequestRejected(OidcTokenRequestRejectedError),
+
+    #[error(transparent)]
+    Internal(#[from] InternalError),
+}
+
+#[derive(Debug, Error)]
+#[error("Identity provider does not support the device authorization flow")]
+pub struct OidcDeviceFlowNotSupportedError {}
+
+#[derive(Debug, Error)]
+#[error("Authorization was denied by the user")]
+pub struct OidcAuthorizationDeniedError {}
+
+#[derive(Debug, Error)]
+#[error("Authorization request has expired")]
+pub struct OidcAuthorizationExpiredError {}
+
+#[derive(Debug, Error)]
+#[error("Token request rejected: {error}")]
+pub struct OidcTokenRequestRejectedError {
+    pub error: String,
+    pub error_description: Option<String>,
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
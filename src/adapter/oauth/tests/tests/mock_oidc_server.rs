@@ -0,0 +1,241 @@
+// Copyright Kamu Data, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::collections::HashMap;
+use std::future::IntoFuture;
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use axum::extract::{Form, State};
+use axum::http::StatusCode;
+use axum::Json;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use jsonwebtoken::{Algorithm, EncodingKey, Header};
+use ring::signature::KeyPair;
+use serde_json::json;
+use url::Url;
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+pub const MOCK_AUTHORIZATION_CODE: &str = "mock-authorization-code";
+pub const MOCK_CODE_VERIFIER: &str = "mock-code-verifier";
+pub const MOCK_DEVICE_CODE: &str = "mock-device-code";
+pub const MOCK_USER_CODE: &str = "ABCD-EFGH";
+
+const MOCK_KEY_ID: &str = "mock-key";
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+/// Minimal OpenID provider: discovery, JWKS, token and device authorization
+/// endpoints. ID tokens carry the claims set by the test
+pub struct MockOidcServer {
+    issuer_url: Url,
+    state: Arc<MockOidcState>,
+}
+
+struct MockOidcState {
+    issuer: String,
+    client_id: String,
+    signing_key: MockSigningKey,
+    claims: Mutex<serde_json::Map<String, serde_json::Value>>,
+    pending_device_polls: AtomicU32,
+}
+
+struct MockSigningKey {
+    encoding_key: EncodingKey,
+    public_key: String,
+}
+
+impl MockSigningKey {
+    fn generate() -> Self {
+        let rng = ring::rand::SystemRandom::new();
+        let pkcs8 = ring::signature::Ed25519KeyPair::generate_pkcs8(&rng).unwrap();
+        let key_pair = ring::signature::Ed25519KeyPair::from_pkcs8(pkcs8.as_ref()).unwrap();
+
+        Self {
+            encoding_key: EncodingKey::from_ed_der(pkcs8.as_ref()),
+            public_key: URL_SAFE_NO_PAD.encode(key_pair.public_key().as_ref()),
+        }
+    }
+}
+
+impl MockOidcServer {
+    pub async fn start(client_id: &str) -> Self {
+        let addr = SocketAddr::from((IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 0));
+        let listener = tokio::net::TcpListener::bind(&addr)
+            .await
+            .expect("Error binding TCP listener");
+        let issuer_url = Url::parse(&format!("http://{}", listener.local_addr().unwrap())).unwrap();
+
+        let state = Arc::new(MockOidcState {
+            issuer: issuer_url.as_str().trim_end_matches('/').to_string(),
+            client_id: client_id.to_string(),
+            signing_key: MockSigningKey::generate(),
+            claims: Mutex::new(serde_json::Map::new()),
+            pending_device_polls: AtomicU32::new(0),
+        });
+
+        let app = axum::Router::new()
+            .route(
+                "/.well-known/openid-configuration",
+                axum::routing::get(discovery_handler),
+            )
+            .route("/jwks", axum::routing::get(jwks_handler))
+            .route("/token", axum::routing::post(token_handler))
+            .route("/device", axum::routing::post(device_handler))
+            .with_state(state.clone());
+
+        tokio::spawn(axum::serve(listener, app).into_future());
+
+        Self { issuer_url, state }
+    }
+
+    pub fn issuer_url(&self) -> &Url {
+        &self.issuer_url
+    }
+
+    /// Claims of the ID tokens issued from now on, in addition to `iss`,
+    /// `aud`, `exp` and `iat` (unless set explicitly)
+    pub fn set_claims(&self, claims: serde_json::Value) {
+        let serde_json::Value::Object(claims) = claims else {
+            panic!("Claims must be an object");
+        };
+        *self.state.claims.lock().unwrap() = claims;
+    }
+
+    /// Number of device token polls answered with `authorization_pending`
+    pub fn set_pending_device_polls(&self, count: u32) {
+        self.state
+            .pending_device_polls
+            .store(count, Ordering::SeqCst);
+    }
+
+    pub fn issue_id_token(&self, audience: &str) -> String {
+        self.state
+            .issue_id_token(audience, &self.state.signing_key.encoding_key)
+    }
+
+    pub fn issue_id_token_signed_by_unknown_key(&self, audience: &str) -> String {
+        let unknown_key = MockSigningKey::generate();
+        self.state
+            .issue_id_token(audience, &unknown_key.encoding_key)
+    }
+}
+
+impl MockOidcState {
+    fn issue_id_token(&self, audience: &str, encoding_key: &EncodingKey) -> String {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+
+        let mut claims = self.claims.lock().unwrap().clone();
+        claims.insert("iss".to_string(), json!(self.issuer));
+        claims.insert("aud".to_string(), json!(audience));
+        claims.entry("iat").or_insert_with(|| json!(now));
+        claims.insert("exp".to_string(), json!(now + 300));
+
+        let mut header = Header::new(Algorithm::EdDSA);
+        header.kid = Some(MOCK_KEY_ID.to_string());
+
+        jsonwebtoken::encode(&header, &claims, encoding_key).unwrap()
+    }
+
+    fn token_response(&self) -> (StatusCode, Json<serde_json::Value>) {
+        (
+            StatusCode::OK,
+            Json(json!({
+                "access_token": "mock-access-token",
+                "token_type": "Bearer",
+                "id_token": self.issue_id_token(&self.client_id, &self.signing_key.encoding_key),
+                "expires_in": 300,
+            })),
+        )
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+async fn discovery_handler(State(state): State<Arc<MockOidcState>>) -> Json<serde_json::Value> {
+    Json(json!({
+        "issuer": state.issuer,
+        "authorization_endpoint": format!("{}/authorize", state.issuer),
+        "token_endpoint": format!("{}/token", state.issuer),
+        "jwks_uri": format!("{}/jwks", state.issuer),
+        "device_authorization_endpoint": format!("{}/device", state.issuer),
+    }))
+}
+
+async fn jwks_handler(State(state): State<Arc<MockOidcState>>) -> Json<serde_json::Value> {
+    Json(json!({
+        "keys": [{
+            "kty": "OKP",
+            "crv": "Ed25519",
+            "x": state.signing_key.public_key,
+            "kid": MOCK_KEY_ID,
+            "alg": "EdDSA",
+            "use": "sig",
+        }]
+    }))
+}
+
+async fn device_handler(State(state): State<Arc<MockOidcState>>) -> Json<serde_json::Value> {
+    Json(json!({
+        "device_code": MOCK_DEVICE_CODE,
+        "user_code": MOCK_USER_CODE,
+        "verification_uri": format!("{}/device/verify", state.issuer),
+        "expires_in": 60,
+        "interval": 0,
+    }))
+}
+
+async fn token_handler(
+    State(state): State<Arc<MockOidcState>>,
+    Form(params): Form<HashMap<String, String>>,
+) -> (StatusCode, Json<serde_json::Value>) {
+    let param = |name: &str| params.get(name).map(String::as_str);
+    let error = |status: StatusCode, error: &str| (status, Json(json!({ "error": error })));
+
+    if param("client_id") != Some(state.client_id.as_str()) {
+        return error(StatusCode::UNAUTHORIZED, "invalid_client");
+    }
+
+    match param("grant_type") {
+        Some("authorization_code") => {
+            if param("code") == Some(MOCK_AUTHORIZATION_CODE)
+                && param("code_verifier") == Some(MOCK_CODE_VERIFIER)
+            {
+                state.token_response()
+            } else {
+                error(StatusCode::BAD_REQUEST, "invalid_grant")
+            }
+        }
+        Some("urn:ietf:params:oauth:grant-type:device_code") => {
+            if param("device_code") != Some(MOCK_DEVICE_CODE) {
+                return error(StatusCode::BAD_REQUEST, "invalid_grant");
+            }
+
+            let still_pending = state
+                .pending_device_polls
+                .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |n| n.checked_sub(1))
+                .is_ok();
+            if still_pending {
+                error(StatusCode::BAD_REQUEST, "authorization_pending")
+            } else {
+                state.token_response()
+            }
+        }
+        _ => error(StatusCode::BAD_REQUEST, "unsupported_grant_type"),
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
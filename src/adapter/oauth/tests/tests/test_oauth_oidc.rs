@@ -0,0 +1,378 @@
+// Copyright Kamu Data, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::assert_matches::assert_matches;
+use std::sync::Arc;
+
+use kamu_accounts::{AuthenticationProvider, ProviderLoginError};
+use kamu_adapter_oauth::{
+    OAuthOidc,
+    OidcAuthenticationConfig,
+    OidcClaimsMapping,
+    OidcClient,
+    OidcClientError,
+};
+use serde_json::json;
+
+use super::mock_oidc_server::*;
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+const CLIENT_ID: &str = "kamu";
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[test_log::test(tokio::test)]
+async fn test_login_via_authorization_code() {
+    let server = MockOidcServer::start(CLIENT_ID).await;
+    server.set_claims(with_nonce(wasya_claims(), "n-0S6_WzA2Mj"));
+
+    let provider = make_provider(&server, None);
+
+    let response = provider
+        .login(
+            json!({
+                "code": MOCK_AUTHORIZATION_CODE,
+                "codeVerifier": MOCK_CODE_VERIFIER,
+                "redirectUri": "http://localhost:4200/oidc-callback",
+                "nonce": "n-0S6_WzA2Mj",
+            })
+            .to_string(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.account_name.as_str(), "wasya");
+    assert_eq!(response.email.as_ref(), "wasya@example.com");
+    assert_eq!(response.display_name, "Wasya Pupkin");
+    assert_eq!(response.provider_identity_key, "f1e2d3c4");
+    assert_eq!(response.is_admin, None);
+
+    // PKCE verifier has to match the challenge the flow was started with
+    assert_matches!(
+        provider
+            .login(
+                json!({
+                    "code": MOCK_AUTHORIZATION_CODE,
+                    "codeVerifier": "wrong-verifier",
+                    "nonce": "n-0S6_WzA2Mj",
+                })
+                .to_string(),
+            )
+            .await,
+        Err(ProviderLoginError::InvalidCredentials(_))
+    );
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[test_log::test(tokio::test)]
+async fn test_authorization_code_nonce() {
+    let server = MockOidcServer::start(CLIENT_ID).await;
+    server.set_claims(with_nonce(wasya_claims(), "n-0S6_WzA2Mj"));
+
+    let provider = make_provider(&server, None);
+
+    // Nonce of the authorization request is mandatory
+    assert_matches!(
+        provider
+            .login(
+                json!({
+                    "code": MOCK_AUTHORIZATION_CODE,
+                    "codeVerifier": MOCK_CODE_VERIFIER,
+                })
+                .to_string(),
+            )
+            .await,
+        Err(ProviderLoginError::InvalidCredentials(_))
+    );
+
+    // ID token has to be issued for the same authorization request
+    assert_matches!(
+        provider
+            .login(
+                json!({
+                    "code": MOCK_AUTHORIZATION_CODE,
+                    "codeVerifier": MOCK_CODE_VERIFIER,
+                    "nonce": "another-nonce",
+                })
+                .to_string(),
+            )
+            .await,
+        Err(ProviderLoginError::RejectedCredentials(_))
+    );
+
+    // ID tokens of the code flow cannot be passed directly
+    assert_matches!(
+        provider
+            .login(json!({ "idToken": server.issue_id_token(CLIENT_ID) }).to_string())
+            .await,
+        Err(ProviderLoginError::RejectedCredentials(_))
+    );
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[test_log::test(tokio::test)]
+async fn test_login_via_device_flow() {
+    let server = MockOidcServer::start(CLIENT_ID).await;
+    server.set_claims(wasya_claims());
+    server.set_pending_device_polls(2);
+
+    let client = OidcClient::discover(server.issuer_url(), CLIENT_ID.to_string(), None)
+        .await
+        .unwrap();
+
+    let device_authorization = client
+        .start_device_authorization(&["openid".to_string(), "email".to_string()])
+        .await
+        .unwrap();
+    assert_eq!(device_authorization.user_code, MOCK_USER_CODE);
+
+    let token_response = client
+        .poll_device_token(&device_authorization)
+        .await
+        .unwrap();
+
+    let provider = make_provider(&server, None);
+    let response = provider
+        .login(json!({ "idToken": token_response.id_token.unwrap() }).to_string())
+        .await
+        .unwrap();
+
+    assert_eq!(response.account_name.as_str(), "wasya");
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[test_log::test(tokio::test)]
+async fn test_device_flow_unknown_client() {
+    let server = MockOidcServer::start(CLIENT_ID).await;
+
+    let client = OidcClient::discover(server.issuer_url(), "unknown".to_string(), None)
+        .await
+        .unwrap();
+    let device_authorization = client.start_device_authorization(&[]).await.unwrap();
+
+    assert_matches!(
+        client.poll_device_token(&device_authorization).await,
+        Err(OidcClientError::TokenRequestRejected(e)) if e.error == "invalid_client"
+    );
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[test_log::test(tokio::test)]
+async fn test_admin_flag_from_group_claim() {
+    let server = MockOidcServer::start(CLIENT_ID).await;
+    let provider = make_provider(&server, Some("kamu-admins"));
+
+    server.set_claims(json!({
+        "sub": "f1e2d3c4",
+        "preferred_username": "wasya",
+        "email": "wasya@example.com",
+        "email_verified": true,
+        "groups": ["kamu-users", "kamu-admins"],
+    }));
+    let response = provider
+        .login(json!({ "idToken": server.issue_id_token(CLIENT_ID) }).to_string())
+        .await
+        .unwrap();
+    assert_eq!(response.is_admin, Some(true));
+
+    server.set_claims(json!({
+        "sub": "f1e2d3c4",
+        "preferred_username": "wasya",
+        "email": "wasya@example.com",
+        "email_verified": true,
+        "groups": ["kamu-users"],
+    }));
+    let response = provider
+        .login(json!({ "idToken": server.issue_id_token(CLIENT_ID) }).to_string())
+        .await
+        .unwrap();
+    assert_eq!(response.is_admin, Some(false));
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[test_log::test(tokio::test)]
+async fn test_id_token_validation() {
+    let server = MockOidcServer::start(CLIENT_ID).await;
+    server.set_claims(wasya_claims());
+
+    let provider = make_provider(&server, None);
+
+    assert_matches!(
+        provider
+            .login(json!({ "idToken": server.issue_id_token("another-client") }).to_string())
+            .await,
+        Err(ProviderLoginError::RejectedCredentials(_))
+    );
+    assert_matches!(
+        provider
+            .login(
+                json!({ "idToken": server.issue_id_token_signed_by_unknown_key(CLIENT_ID) })
+                    .to_string()
+            )
+            .await,
+        Err(ProviderLoginError::RejectedCredentials(_))
+    );
+    assert_matches!(
+        provider
+            .login(json!({ "idToken": "garbage" }).to_string())
+            .await,
+        Err(ProviderLoginError::InvalidCredentials(_))
+    );
+    assert_matches!(
+        provider.login(json!({}).to_string()).await,
+        Err(ProviderLoginError::InvalidCredentials(_))
+    );
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[test_log::test(tokio::test)]
+async fn test_id_token_max_age() {
+    let server = MockOidcServer::start(CLIENT_ID).await;
+    let provider = make_provider(&server, None);
+
+    let now = jsonwebtoken::get_current_timestamp();
+    let max_age = OidcAuthenticationConfig::DEFAULT_ID_TOKEN_MAX_AGE.as_secs();
+
+    let mut claims = wasya_claims();
+    claims["iat"] = json!(now - max_age + 60);
+    server.set_claims(claims.clone());
+    assert_matches!(
+        provider
+            .login(json!({ "idToken": server.issue_id_token(CLIENT_ID) }).to_string())
+            .await,
+        Ok(_)
+    );
+
+    claims["iat"] = json!(now - max_age - 60);
+    server.set_claims(claims);
+    assert_matches!(
+        provider
+            .login(json!({ "idToken": server.issue_id_token(CLIENT_ID) }).to_string())
+            .await,
+        Err(ProviderLoginError::RejectedCredentials(_))
+    );
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[test_log::test(tokio::test)]
+async fn test_unverified_email() {
+    let server = MockOidcServer::start(CLIENT_ID).await;
+    let provider = make_provider(&server, None);
+
+    let mut claims = wasya_claims();
+    claims["email_verified"] = json!(false);
+    server.set_claims(claims.clone());
+    assert_matches!(
+        provider
+            .login(json!({ "idToken": server.issue_id_token(CLIENT_ID) }).to_string())
+            .await,
+        Err(ProviderLoginError::InvalidCredentials(_))
+    );
+
+    claims.as_object_mut().unwrap().remove("email_verified");
+    server.set_claims(claims);
+    assert_matches!(
+        provider
+            .login(json!({ "idToken": server.issue_id_token(CLIENT_ID) }).to_string())
+            .await,
+        Err(ProviderLoginError::InvalidCredentials(_))
+    );
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[test_log::test(tokio::test)]
+async fn test_claims_mapping() {
+    let server = MockOidcServer::start(CLIENT_ID).await;
+
+    let provider = OAuthOidc::new(Arc::new(
+        OidcAuthenticationConfig::new(server.issuer_url().clone(), CLIENT_ID.to_string(), None)
+            .with_claims(OidcClaimsMapping {
+                account_name: "nickname".to_string(),
+                email: "upn".to_string(),
+                ..Default::default()
+            }),
+    ));
+
+    server.set_claims(json!({
+        "sub": "f1e2d3c4",
+        "nickname": "wasya",
+        "upn": "wasya@example.com",
+        "email_verified": true,
+    }));
+    let response = provider
+        .login(json!({ "idToken": server.issue_id_token(CLIENT_ID) }).to_string())
+        .await
+        .unwrap();
+    assert_eq!(response.account_name.as_str(), "wasya");
+    assert_eq!(response.email.as_ref(), "wasya@example.com");
+    // Falls back to the account name
+    assert_eq!(response.display_name, "wasya");
+
+    server.set_claims(json!({
+        "sub": "f1e2d3c4",
+        "nickname": "wasya",
+    }));
+    assert_matches!(
+        provider
+            .login(json!({ "idToken": server.issue_id_token(CLIENT_ID) }).to_string())
+            .await,
+        Err(ProviderLoginError::NoPrimaryEmail(_))
+    );
+
+    server.set_claims(json!({
+        "sub": "f1e2d3c4",
+        "nickname": "not a valid name",
+        "upn": "wasya@example.com",
+        "email_verified": true,
+    }));
+    assert_matches!(
+        provider
+            .login(json!({ "idToken": server.issue_id_token(CLIENT_ID) }).to_string())
+            .await,
+        Err(ProviderLoginError::InvalidCredentials(_))
+    );
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+fn make_provider(server: &MockOidcServer, admin_group: Option<&str>) -> OAuthOidc {
+    OAuthOidc::new(Arc::new(
+        OidcAuthenticationConfig::new(server.issuer_url().clone(), CLIENT_ID.to_string(), None)
+            .with_claims(OidcClaimsMapping {
+                admin_group: admin_group.map(ToString::to_string),
+                ..Default::default()
+            }),
+    ))
+}
+
+fn wasya_claims() -> serde_json::Value {
+    json!({
+        "sub": "f1e2d3c4",
+        "preferred_username": "wasya",
+        "email": "wasya@example.com",
+        "email_verified": true,
+        "name": "Wasya Pupkin",
+    })
+}
+
+fn with_nonce(mut claims: serde_json::Value, nonce: &str) -> serde_json::Value {
+    claims["nonce"] = json!(nonce);
+    claims
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
@@ -21,7 +21,12 @@ use kamu::domain::*;
 use kamu::*;
 use kamu_accounts::*;
 use kamu_accounts_services::PredefinedAccountsRegistrator;
-use kamu_adapter_http::{FileUploadLimitConfig, UploadServiceLocal};
+use kamu_adapter_http::{
+    FileUploadLimitConfig,
+    MultipartUploadConfig,
+    UploadCleanupAgent,
+    UploadServiceLocal,
+};
 use kamu_adapter_oauth::GithubAuthenticationConfig;
 use kamu_flow_system_inmem::domain::{
     FlowConfigurationUpdatedMessage,
@@ -112,7 +117,18 @@ pub async fn run(workspace_layout: WorkspaceLayout, args: cli::Cli) -> Result<()
         (_, true) => WorkspaceStatus::Created(tenancy_config),
     };
 
-    let config = load_config(&workspace_layout);
+    let mut config = load_config(&workspace_layout);
+
+    // Standalone task workers may override the concurrency of the task agent
+    if let Command::System(c) = &args.command
+        && let cli::SystemSubCommand::TaskWorker(w) = &c.subcommand
+        && let Some(max_concurrent_tasks) = w.max_concurrent_tasks
+    {
+        let flow_system_config = config.flow_system.as_mut().unwrap();
+        let task_agent_config = flow_system_config.task_agent.as_mut().unwrap();
+        task_agent_config.max_concurrent_tasks = Some(max_concurrent_tasks);
+    }
+
     let current_account = AccountService::current_account_indication(
         args.account.clone(),
         tenancy_config,
@@ -174,6 +190,7 @@ pub async fn run(workspace_layout: WorkspaceLayout, args: cli::Cli) -> Result<()
         register_config_in_catalog(
             &config,
             &mut base_catalog_builder,
+            &workspace_layout,
             workspace_status,
             args.password_hashing_mode,
             is_e2e_testing,
@@ -449,10 +466,17 @@ pub fn configure_base_catalog(
 
     b.add::<ResetPlannerImpl>();
     b.add::<ResetExecutorImpl>();
+    b.add::<ReprocessPlannerImpl>();
+    b.add::<ReprocessExecutorImpl>();
 
     b.add::<ProvenanceServiceImpl>();
 
     b.add::<QueryServiceImpl>();
+    b.add::<DatasetChangeFeedServiceImpl>();
+
+    b.add::<DatasetDiffServiceImpl>();
+    b.add::<DatasetExpectationsServiceImpl>();
+    b.add::<DatasetFreshnessServiceImpl>();
 
     b.add::<ExportServiceImpl>();
 
@@ -481,6 +505,8 @@ pub fn configure_base_catalog(
     b.add::<kamu_datasets_services::CreateDatasetUseCaseImpl>();
     b.add::<kamu_datasets_services::DeleteDatasetUseCaseImpl>();
     b.add::<kamu_datasets_services::RenameDatasetUseCaseImpl>();
+    b.add::<kamu_datasets_services::TransferDatasetOwnershipUseCaseImpl>();
+    b.add::<kamu_datasets_services::UpdateDatasetFromSnapshotUseCaseImpl>();
     b.add::<kamu_datasets_services::ViewDatasetUseCaseImpl>();
 
     b.add::<kamu_accounts_services::LoginPasswordAuthProvider>();
@@ -497,6 +523,10 @@ pub fn configure_base_catalog(
     b.add::<kamu_accounts_services::AuthenticationServiceImpl>();
     b.add::<kamu_accounts_services::AccessTokenServiceImpl>();
     b.add::<kamu_accounts_services::AccountServiceImpl>();
+    b.add::<kamu_accounts_services::AccountManagementServiceImpl>();
+    b.add::<kamu_accounts_services::AccessTokenAccountLifecycleMessageConsumer>();
+    b.add::<kamu_accounts_services::PasswordAccountLifecycleMessageConsumer>();
+    b.add::<kamu_accounts_services::EmailVerificationNotifier>();
     b.add::<PredefinedAccountsRegistrator>();
 
     // Give both CLI and server access to stored repo access tokens
@@ -508,6 +538,7 @@ pub fn configure_base_catalog(
     b.add::<DatabaseTransactionRunner>();
 
     b.add::<kamu_auth_rebac_services::RebacDatasetLifecycleMessageConsumer>();
+    b.add::<kamu_auth_rebac_services::RebacAccountLifecycleMessageConsumer>();
     b.add::<kamu_auth_rebac_services::RebacServiceImpl>();
     b.add_value(kamu_auth_rebac_services::DefaultAccountProperties { is_admin: false });
     b.add_value(kamu_auth_rebac_services::DefaultDatasetProperties {
@@ -526,6 +557,8 @@ pub fn configure_base_catalog(
     b.add::<kamu_adapter_flight_sql::KamuFlightSqlService>();
 
     b.add::<kamu_datasets_services::DatasetEntryServiceImpl>();
+    b.add::<kamu_datasets_services::DatasetAccountLifecycleMessageConsumer>();
+    b.add::<kamu_datasets_services::DatasetAccountDeletionGuard>();
     b.add::<kamu_datasets_services::DependencyGraphServiceImpl>();
 
     b.add_builder(
@@ -537,6 +570,7 @@ pub fn configure_base_catalog(
     b.bind::<dyn Outbox, OutboxDispatchingImpl>();
     b.add::<messaging_outbox::OutboxAgent>();
     b.add::<messaging_outbox::OutboxAgentMetrics>();
+    b.add::<kamu_datasets_services::DatasetFreshnessMetrics>();
 
     b.add::<crate::explore::FlightSqlServiceFactory>();
     b.add::<crate::explore::SparkLivyServerFactory>();
@@ -546,6 +580,10 @@ pub fn configure_base_catalog(
         &mut b,
         kamu_datasets::MESSAGE_PRODUCER_KAMU_DATASET_SERVICE,
     );
+    register_message_dispatcher::<kamu_datasets::DatasetReferenceMessage>(
+        &mut b,
+        kamu_datasets::MESSAGE_PRODUCER_KAMU_DATASET_REFERENCE_SERVICE,
+    );
 
     b
 }
@@ -573,12 +611,20 @@ pub fn configure_server_catalog(base_catalog: &Catalog) -> CatalogBuilder {
     let mut b = CatalogBuilder::new_chained(base_catalog);
 
     b.add::<DatasetChangesServiceImpl>();
+    b.add::<kamu_datasets_services::DatasetFreshnessAgentImpl>();
 
     kamu_task_system_services::register_dependencies(&mut b);
 
     kamu_flow_system_services::register_dependencies(&mut b);
 
+    kamu_webhooks_services::register_dependencies(&mut b);
+
+    kamu_alerts_services::register_dependencies(&mut b);
+
     b.add::<UploadServiceLocal>();
+    b.add::<UploadCleanupAgent>();
+
+    b.add::<kamu_adapter_graphql::subscriptions::GqlEventHub>();
 
     register_message_dispatcher::<FlowProgressMessage>(
         &mut b,
@@ -637,6 +683,7 @@ fn load_config(workspace_layout: &WorkspaceLayout) -> config::CLIConfig {
 pub fn register_config_in_catalog(
     config: &config::CLIConfig,
     catalog_builder: &mut CatalogBuilder,
+    workspace_layout: &WorkspaceLayout,
     workspace_status: WorkspaceStatus,
     password_hashing_mode: Option<cli::PasswordHashingMode>,
     is_e2e_testing: bool,
@@ -670,6 +717,14 @@ pub fn register_config_in_catalog(
             .shutdown_timeout
             .unwrap()
             .into(),
+        pool: config
+            .engine
+            .as_ref()
+            .unwrap()
+            .pool
+            .as_ref()
+            .unwrap()
+            .to_infra_cfg(),
         spark_image: config
             .engine
             .as_ref()
@@ -711,6 +766,7 @@ pub fn register_config_in_catalog(
             .clone()
             .unwrap(),
     });
+    catalog_builder.add_value(config.engine.as_ref().unwrap().to_registry_cfg());
     //
 
     catalog_builder.add_value(config.source.as_ref().unwrap().to_infra_cfg());
@@ -816,9 +872,19 @@ pub fn register_config_in_catalog(
 
     // Uploads configuration
     let uploads_config = config.uploads.as_ref().unwrap();
-    catalog_builder.add_value(FileUploadLimitConfig::new_in_mb(
-        uploads_config.max_file_size_in_mb.unwrap(),
-    ));
+    catalog_builder.add_value(
+        FileUploadLimitConfig::new_in_mb(uploads_config.max_file_size_in_mb.unwrap())
+            .with_multipart(MultipartUploadConfig {
+                part_size_in_bytes: uploads_config.multipart_part_size_in_mb.unwrap() * 1024 * 1024,
+                max_file_size_in_bytes: uploads_config.multipart_max_file_size_in_mb.unwrap()
+                    * 1024
+                    * 1024,
+                incomplete_upload_ttl: Duration::seconds(
+                    uploads_config.incomplete_upload_ttl_secs.unwrap(),
+                ),
+                cleanup_interval: Duration::seconds(uploads_config.cleanup_interval_secs.unwrap()),
+            }),
+    );
     //
 
     // Dataset env vars configuration
@@ -879,14 +945,161 @@ pub fn register_config_in_catalog(
     let kamu_flow_system_config = config.flow_system.as_ref().unwrap();
     let flow_agent_config = kamu_flow_system_config.flow_agent.as_ref().unwrap();
 
-    catalog_builder.add_value(kamu_flow_system_inmem::domain::FlowAgentConfig::new(
+    let mut flow_agent_domain_config = kamu_flow_system_inmem::domain::FlowAgentConfig::new(
         Duration::seconds(flow_agent_config.awaiting_step_secs.unwrap()),
         Duration::seconds(flow_agent_config.mandatory_throttling_period_secs.unwrap()),
-    ));
+    );
+    let remote_upstream_polling_interval_secs = flow_agent_config
+        .remote_upstream_polling_interval_secs
+        .unwrap();
+    if remote_upstream_polling_interval_secs > 0 {
+        flow_agent_domain_config = flow_agent_domain_config.with_remote_upstream_polling_interval(
+            Duration::seconds(remote_upstream_polling_interval_secs),
+        );
+    }
+    catalog_builder.add_value(flow_agent_domain_config);
 
     let task_agent_config = kamu_flow_system_config.task_agent.as_ref().unwrap();
-    catalog_builder.add_value(kamu_task_system_inmem::domain::TaskAgentConfig::new(
-        Duration::seconds(task_agent_config.task_checking_interval_secs.unwrap()),
+    catalog_builder.add_value(
+        kamu_task_system_inmem::domain::TaskAgentConfig::new(Duration::seconds(
+            task_agent_config.task_checking_interval_secs.unwrap(),
+        ))
+        .with_max_concurrent_tasks(
+            task_agent_config.max_concurrent_tasks.unwrap(),
+            task_agent_config
+                .max_concurrent_tasks_per_plan_kind
+                .clone()
+                .unwrap()
+                .into_iter()
+                .collect(),
+        )
+        .with_task_lease(
+            Duration::seconds(task_agent_config.task_lease_duration_secs.unwrap()),
+            Duration::seconds(task_agent_config.task_heartbeat_interval_secs.unwrap()),
+        ),
+    );
+    //
+
+    // Task logs configuration
+    let task_logs_config = config.task_logs.as_ref().unwrap();
+    let task_logs_retention_period_secs = task_logs_config.retention_period_secs.unwrap();
+    catalog_builder.add_value(kamu_task_system_inmem::domain::TaskLogsConfig {
+        storage_url: task_logs_config.storage_url.clone().unwrap_or_else(|| {
+            url::Url::from_directory_path(workspace_layout.root_dir.join("task-logs")).unwrap()
+        }),
+        retention_period: (task_logs_retention_period_secs > 0)
+            .then(|| Duration::seconds(task_logs_retention_period_secs)),
+        cleanup_interval: Duration::seconds(task_logs_config.cleanup_interval_secs.unwrap()),
+    });
+    //
+
+    // Webhooks configuration
+    let webhooks_config = config.webhooks.as_ref().unwrap();
+    catalog_builder.add_value(kamu_webhooks_inmem::domain::WebhookDeliveryConfig::new(
+        Duration::seconds(webhooks_config.awaiting_step_secs.unwrap()),
+        webhooks_config.batch_size.unwrap(),
+        webhooks_config.max_attempts.unwrap(),
+        Duration::seconds(webhooks_config.initial_backoff_secs.unwrap()),
+        Duration::seconds(webhooks_config.max_backoff_secs.unwrap()),
+        Duration::seconds(webhooks_config.request_timeout_secs.unwrap()),
+    ));
+    //
+
+    // Alerts configuration
+    let alerts_config = config.alerts.as_ref().unwrap();
+    catalog_builder.add_value(kamu_alerts_inmem::domain::DatasetAlertsConfig::new(
+        Duration::seconds(alerts_config.staleness_check_interval_secs.unwrap()),
+    ));
+    match alerts_config.email.as_ref().unwrap() {
+        config::EmailConfig::Dummy => {
+            catalog_builder.add::<email_utils::DummyEmailSender>();
+        }
+        config::EmailConfig::File(file_config) => {
+            catalog_builder.add::<email_utils::FileEmailSender>();
+            catalog_builder.add_value(email_utils::FileEmailSenderConfig {
+                output_dir: file_config.output_dir.clone().into(),
+            });
+        }
+        config::EmailConfig::Smtp(smtp_config) => {
+            let sender_address = email_utils::Email::parse(&smtp_config.sender_address)
+                .unwrap_or_else(|e| panic!("Invalid alerts sender address: {e}"));
+
+            catalog_builder.add::<email_utils::SmtpEmailSender>();
+            catalog_builder.add_value(email_utils::SmtpEmailSenderConfig {
+                host: smtp_config.host.clone(),
+                port: smtp_config.port,
+                username: smtp_config.username.clone(),
+                password: smtp_config.password.clone(),
+                implicit_tls: smtp_config.implicit_tls.unwrap_or(false),
+                sender_address,
+                sender_name: smtp_config.sender_name.clone(),
+            });
+        }
+    }
+    //
+
+    // Account registration configuration
+    let account_registration_config = config.account_registration.as_ref().unwrap();
+    catalog_builder.add_value(kamu_accounts::AccountRegistrationConfig {
+        enabled: account_registration_config.enabled.unwrap(),
+        email_verification_url: account_registration_config.email_verification_url.clone(),
+        email_verification_token_ttl: Duration::seconds(
+            account_registration_config
+                .email_verification_token_ttl_secs
+                .unwrap(),
+        ),
+    });
+    //
+
+    // External identity providers configuration
+    if let Some(oidc_config) = config.auth.as_ref().unwrap().oidc.as_ref() {
+        use kamu_adapter_oauth::{OidcAuthenticationConfig, OidcClaimsMapping};
+
+        let default_claims = OidcClaimsMapping::default();
+        let client_secret = oidc_config.client_secret.clone().or_else(|| {
+            std::env::var(kamu_adapter_oauth::ENV_VAR_KAMU_AUTH_OIDC_CLIENT_SECRET).ok()
+        });
+
+        catalog_builder.add::<kamu_adapter_oauth::OAuthOidc>();
+        catalog_builder.add_value(
+            OidcAuthenticationConfig::new(
+                oidc_config.issuer_url.clone(),
+                oidc_config.client_id.clone(),
+                client_secret,
+            )
+            .with_claims(OidcClaimsMapping {
+                account_name: oidc_config
+                    .account_name_claim
+                    .clone()
+                    .unwrap_or(default_claims.account_name),
+                email: oidc_config
+                    .email_claim
+                    .clone()
+                    .unwrap_or(default_claims.email),
+                display_name: oidc_config
+                    .display_name_claim
+                    .clone()
+                    .unwrap_or(default_claims.display_name),
+                groups: oidc_config
+                    .groups_claim
+                    .clone()
+                    .unwrap_or(default_claims.groups),
+                admin_group: oidc_config.admin_group.clone(),
+            })
+            .with_id_token_max_age(
+                oidc_config
+                    .id_token_max_age_secs
+                    .map(std::time::Duration::from_secs)
+                    .unwrap_or(OidcAuthenticationConfig::DEFAULT_ID_TOKEN_MAX_AGE),
+            ),
+        );
+    }
+    //
+
+    // Freshness configuration
+    let freshness_config = config.freshness.as_ref().unwrap();
+    catalog_builder.add_value(kamu::domain::DatasetFreshnessConfig::new(
+        Duration::seconds(freshness_config.check_interval_secs.unwrap()),
     ));
     //
 }
@@ -942,6 +1155,7 @@ fn configure_logging(
     use tracing_log::LogTracer;
     use tracing_subscriber::fmt::format::FmtSpan;
     use tracing_subscriber::layer::SubscriberExt;
+    use tracing_subscriber::util::SubscriberInitExt;
     use tracing_subscriber::EnvFilter;
 
     if no_color_output {
@@ -986,6 +1200,8 @@ fn configure_logging(
             .with_writer(std::io::stderr)
             .pretty()
             .with_ansi(!no_color_output)
+            .finish()
+            .with(kamu_task_system_services::TaskLogCaptureLayer)
             .init();
 
         return Guards::default();
@@ -1017,6 +1233,7 @@ fn configure_logging(
         .with(env_filter)
         .with(JsonStorageLayer)
         .with(maybe_perfetto_layer)
+        .with(kamu_task_system_services::TaskLogCaptureLayer)
         .with(BunyanFormattingLayer::new(BINARY_NAME.to_owned(), appender));
 
     // Redirect all standard logging to tracing events
@@ -95,7 +95,10 @@ pub enum Command {
     Completions(Completions),
     Config(Config),
     Delete(Delete),
+    Diff(Diff),
+    Expectations(Expectations),
     Export(Export),
+    Freshness(Freshness),
     Ingest(Ingest),
     Init(Init),
     Inspect(Inspect),
@@ -124,6 +127,7 @@ pub enum Command {
 impl Cli {
     pub fn tabular_output_format(&self) -> Option<OutputFormat> {
         match &self.command {
+            Command::Diff(c) => c.output_format,
             Command::List(c) => c.output_format,
             Command::Repo(c) => match &c.subcommand {
                 RepoSubCommand::Alias(sc) => match &sc.subcommand {
@@ -150,7 +154,7 @@ This command creates a new dataset from the provided DatasetSnapshot manifest.
 
 Note that after kamu creates a dataset the changes in the source file will not have any effect unless you run the add command again. When you are experimenting with adding new dataset you currently may need to delete and re-add it multiple times until you get your parameters and schema right.
 
-In future versions the add command will allow you to modify the structure of already existing datasets (e.g. changing schema in a compatible way).
+Many similar datasets can be created from a single templated manifest. The template is expanded once for every row of a CSV file, whose header contains the variable names. Templates can reference variables as `${{ vars.<name> }}` and repeat blocks of lines with `${{ for <item> in vars.<name> }}` ... `${{ end }}` where items of the variable are separated by `;`. Values are inserted as plain YAML scalars, and a line can't combine `vars` with `env` expressions. Datasets that already exist are updated by committing only the metadata events that changed.
 
 **Examples:**
 
@@ -166,6 +170,10 @@ Add a dataset from manifest hosted externally (e.g. on GihHub):
 
     kamu add https://raw.githubusercontent.com/kamu-data/kamu-contrib/master/ca.bankofcanada/ca.bankofcanada.exchange-rates.daily.yaml
 
+Preview datasets that would be created or updated from a template:
+
+    kamu add --template weather.yaml --vars cities.csv --dry-run
+
 To add dataset from a repository see `kamu pull` command.
 "#)]
 pub struct Add {
@@ -189,6 +197,24 @@ pub struct Add {
     #[arg(long, value_name = "VIS", value_enum)]
     pub visibility: Option<parsers::DatasetVisibility>,
 
+    /// Templated manifest reference (path, or URL) to create or update
+    /// datasets from
+    #[arg(
+        long,
+        value_name = "TPL",
+        requires = "vars",
+        conflicts_with_all = ["manifest", "recursive", "replace", "stdin", "name"]
+    )]
+    pub template: Option<String>,
+
+    /// CSV file with template variables, one set of values per row
+    #[arg(long, value_name = "CSV", requires = "template")]
+    pub vars: Option<PathBuf>,
+
+    /// Show the changes that a template would make without applying them
+    #[arg(long, requires = "template")]
+    pub dry_run: bool,
+
     /// Dataset manifest reference(s) (path, or URL)
     #[arg()]
     pub manifest: Vec<String>,
@@ -374,6 +400,113 @@ pub struct Delete {
     #[arg(value_parser = parsers::dataset_ref_pattern)]
     pub dataset: Vec<odf::DatasetRefPattern>,
 }
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+/// Shows changes in a dataset between two blocks
+#[derive(Debug, clap::Args)]
+#[command(after_help = r#"
+This command summarizes metadata blocks added between two points in the dataset history and shows the data records that were written in between.
+
+The `--from` block is exclusive and defaults to the beginning of the chain, while the `--to` block is inclusive and defaults to the current head. For datasets that use the snapshot merge strategy the changed records are grouped by the primary key, so that retractions and corrections of the same entity appear next to each other.
+
+**Examples:**
+
+Show all changes since a certain block:
+
+    kamu diff --from f16205...2a9f org.example.data
+
+Show changes between two blocks without listing the data records:
+
+    kamu diff --from f16205...2a9f --to f16205...b1c4 --metadata-only org.example.data
+"#)]
+pub struct Diff {
+    /// Format to display the changed records in
+    #[arg(long, short = 'o', value_name = "FMT", value_enum)]
+    pub output_format: Option<OutputFormat>,
+
+    /// Hash of the block to compare from (exclusive)
+    #[arg(long, value_name = "HASH", value_parser = parsers::multihash)]
+    pub from: Option<odf::Multihash>,
+
+    /// Hash of the block to compare to (inclusive)
+    #[arg(long, value_name = "HASH", value_parser = parsers::multihash)]
+    pub to: Option<odf::Multihash>,
+
+    /// Only summarize the changes without displaying the data records
+    #[arg(long)]
+    pub metadata_only: bool,
+
+    /// Local dataset reference
+    #[arg(value_parser = parsers::dataset_ref)]
+    pub dataset: odf::DatasetRef,
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+/// Manage data quality expectations of a dataset
+#[derive(Debug, clap::Args)]
+#[command(after_help = r#"
+Expectations are rules that the new data is checked against during ingest and transform before being committed. Depending on the `onFailure` action a violated expectation will either reject the whole commit (`Fail`), exclude the violating records (`Quarantine`, ingest only), or just be recorded (`Warn`). Results of the checks are displayed by `kamu log` for every data block.
+
+Expectations are declared using a YAML manifest:
+
+    kind: DatasetExpectations
+    version: 1
+    content:
+      expectations:
+        - name: city-present
+          rule:
+            kind: NotNull
+            column: city
+        - name: population-positive
+          rule:
+            kind: ValueRange
+            column: population
+            min: 0
+          onFailure: Quarantine
+
+**Examples:**
+
+Show expectations of a dataset:
+
+    kamu expectations get org.example.data
+
+Replace expectations of a dataset with ones from a manifest:
+
+    kamu expectations set org.example.data expectations.yaml
+"#)]
+pub struct Expectations {
+    #[command(subcommand)]
+    pub subcommand: ExpectationsSubCommand,
+}
+
+#[derive(Debug, clap::Subcommand)]
+pub enum ExpectationsSubCommand {
+    Get(ExpectationsGet),
+    Set(ExpectationsSet),
+}
+
+/// Shows expectations declared on a dataset as a manifest
+#[derive(Debug, clap::Args)]
+pub struct ExpectationsGet {
+    /// Local dataset reference
+    #[arg(index = 1, value_parser = parsers::dataset_ref)]
+    pub dataset: odf::DatasetRef,
+}
+
+/// Replaces expectations of a dataset with ones from a manifest
+#[derive(Debug, clap::Args)]
+pub struct ExpectationsSet {
+    /// Local dataset reference
+    #[arg(index = 1, value_parser = parsers::dataset_ref)]
+    pub dataset: odf::DatasetRef,
+
+    /// Path to the DatasetExpectations manifest
+    #[arg(index = 2)]
+    pub manifest: PathBuf,
+}
+
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
 
 /// Exports a dataset
@@ -388,13 +521,26 @@ In all other cases a path is considered as a directory. Examples:
  - `export/dataset.csv/` is a directory path
  - `export/dataset/` is a directory path
  - `export/dataset` is a directory path
+
+The `iceberg` format maintains an Apache Iceberg table in the output directory, which can also be an S3 URL. Every block that added data to the dataset becomes a table snapshot carrying the block hash in its summary. Exporting into an existing table only appends the blocks added since the previous export, so the command can be run periodically to keep the table up-to-date. When the table is located on the same storage as the dataset, its data files are referenced in place instead of being copied.
+
+**Examples:**
+
+Export dataset into a local Iceberg table:
+
+    kamu export ca.covid19.daily-cases --output-format iceberg --output-path tables/covid19/
+
+Keep an Iceberg table on S3 up-to-date:
+
+    kamu export ca.covid19.daily-cases --output-format iceberg --output-path s3://my-bucket/tables/covid19/
 "#)]
 pub struct Export {
     /// Local dataset reference
     #[arg(index = 1, value_parser = parsers::dataset_ref)]
     pub dataset: odf::DatasetRef,
 
-    /// Export destination. Dafault is `<current workdir>/<dataset name>`
+    /// Export destination. Dafault is `<current workdir>/<dataset name>`.
+    /// Iceberg tables can also be exported to an S3 URL
     #[arg(long)]
     pub output_path: Option<PathBuf>,
 
@@ -411,6 +557,78 @@ pub struct Export {
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
 
+/// Manage freshness SLA of a dataset
+#[derive(Debug, clap::Args)]
+#[command(after_help = r#"
+Freshness SLA declares how often a dataset is expected to receive new data. A dataset becomes stale when no new data was added within the expected update interval, or, optionally, when its watermark lags behind the current time by more than allowed.
+
+Datasets that declare an SLA display their status in `kamu list`. When running `kamu system api-server` the SLAs are periodically evaluated and reported as Prometheus metrics.
+
+**Examples:**
+
+Expect a dataset to be updated at least daily:
+
+    kamu freshness set org.example.data --max-update-interval 1d
+
+Also require the watermark to be no older than two days:
+
+    kamu freshness set org.example.data --max-update-interval 1d --max-watermark-lag 2d
+
+Show the SLA and the current freshness status of a dataset:
+
+    kamu freshness get org.example.data
+
+Remove the SLA:
+
+    kamu freshness clear org.example.data
+"#)]
+pub struct Freshness {
+    #[command(subcommand)]
+    pub subcommand: FreshnessSubCommand,
+}
+
+#[derive(Debug, clap::Subcommand)]
+pub enum FreshnessSubCommand {
+    Clear(FreshnessClear),
+    Get(FreshnessGet),
+    Set(FreshnessSet),
+}
+
+/// Removes the freshness SLA of a dataset
+#[derive(Debug, clap::Args)]
+pub struct FreshnessClear {
+    /// Local dataset reference
+    #[arg(index = 1, value_parser = parsers::dataset_ref)]
+    pub dataset: odf::DatasetRef,
+}
+
+/// Shows the freshness SLA and the current freshness status of a dataset
+#[derive(Debug, clap::Args)]
+pub struct FreshnessGet {
+    /// Local dataset reference
+    #[arg(index = 1, value_parser = parsers::dataset_ref)]
+    pub dataset: odf::DatasetRef,
+}
+
+/// Declares the freshness SLA of a dataset
+#[derive(Debug, clap::Args)]
+pub struct FreshnessSet {
+    /// Local dataset reference
+    #[arg(index = 1, value_parser = parsers::dataset_ref)]
+    pub dataset: odf::DatasetRef,
+
+    /// Maximal time allowed to pass since new data was last added (e.g. `6h`,
+    /// `1d`)
+    #[arg(long, value_name = "DUR", value_parser = parsers::duration)]
+    pub max_update_interval: chrono::Duration,
+
+    /// Maximal allowed lag of the watermark behind the current time
+    #[arg(long, value_name = "DUR", value_parser = parsers::duration)]
+    pub max_watermark_lag: Option<chrono::Duration>,
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
 /// Adds data to the root dataset according to its push source configuration
 #[derive(Debug, clap::Args)]
 #[command(after_help = r#"
@@ -710,7 +928,10 @@ pub struct Login {
 #[derive(Debug, clap::Subcommand)]
 pub enum LoginSubCommand {
     Oauth(LoginOauth),
+    Oidc(LoginOidc),
     Password(LoginPassword),
+    Register(LoginRegister),
+    VerifyEmail(LoginVerifyEmail),
 }
 
 /// Performs non-interactive login to a remote Kamu server via OAuth provider
@@ -730,6 +951,41 @@ pub struct LoginOauth {
     pub server: Option<parsers::UrlHttps>,
 }
 
+/// Performs login to a remote Kamu server via OpenID Connect provider using
+/// the device authorization flow
+#[derive(Debug, clap::Args)]
+#[command(after_help = r#"
+Suitable for machines without a browser: the command displays a URL and a code to enter on any other device. The client has to have the device authorization grant enabled in the identity provider.
+
+**Examples:**
+
+Log in via Keycloak realm:
+
+    kamu login oidc https://keycloak.example.com/realms/kamu kamu-cli https://api.example.com
+"#)]
+pub struct LoginOidc {
+    /// Issuer URL of the OpenID Connect provider
+    #[arg(index = 1)]
+    pub issuer_url: url::Url,
+
+    /// Client ID registered in the OpenID Connect provider
+    #[arg(index = 2)]
+    pub client_id: String,
+
+    /// ODF backend server URL (defaults to kamu.dev)
+    #[arg(index = 3)]
+    pub server: Option<parsers::UrlHttps>,
+
+    /// Scopes to request, the ID token has to contain the claims the server
+    /// maps to account fields
+    #[arg(
+        long = "scope",
+        value_name = "SCOPE",
+        default_values = ["openid", "profile", "email"]
+    )]
+    pub scopes: Vec<String>,
+}
+
 /// Performs non-interactive login to a remote Kamu server via login and
 /// password
 #[derive(Debug, clap::Args)]
@@ -747,6 +1003,51 @@ pub struct LoginPassword {
     pub server: Option<parsers::UrlHttps>,
 }
 
+/// Registers a new account on a remote Kamu server
+#[derive(Debug, clap::Args)]
+#[command(after_help = r#"
+The server sends a confirmation token to the specified email. The new account can be used once the email is confirmed with the `kamu login verify-email` command.
+
+**Examples:**
+
+Register an account on the server:
+
+    kamu login register alice alice@example.com my-password https://api.example.com
+
+Confirm the email and log in:
+
+    kamu login verify-email <token> https://api.example.com
+"#)]
+pub struct LoginRegister {
+    /// Name of the new account
+    #[arg(index = 1, value_parser = parsers::account_name)]
+    pub account_name: odf::AccountName,
+
+    /// Email of the new account
+    #[arg(index = 2)]
+    pub email: String,
+
+    /// Password of the new account
+    #[arg(index = 3)]
+    pub password: String,
+
+    /// ODF backend server URL (defaults to kamu.dev)
+    #[arg(index = 4)]
+    pub server: Option<parsers::UrlHttps>,
+}
+
+/// Confirms the email of a registered account and logs into it
+#[derive(Debug, clap::Args)]
+pub struct LoginVerifyEmail {
+    /// Token received in the confirmation email
+    #[arg(index = 1)]
+    pub verification_token: String,
+
+    /// ODF backend server URL (defaults to kamu.dev)
+    #[arg(index = 2)]
+    pub server: Option<parsers::UrlHttps>,
+}
+
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
 
 /// Logs out from a remote Kamu server
@@ -1239,13 +1540,25 @@ Execute SQL command and return its output in CSV format:
 
     kamu sql -c 'SELECT * FROM `org.example.data` LIMIT 10' -o csv
 
+Execute SQL command using a custom engine registered in the `engine.custom` section of the config (the query has to read data directly from its sources):
+
+    kamu sql --engine duckdb -c "SELECT * FROM read_parquet('https://example.com/data.parquet')"
+
 Run SQL server to use with external data processing tools:
 
     kamu sql server --address 0.0.0.0 --port 8080
 
-Connect to a remote SQL server:
+Query a remote kamu node over Flight SQL using the access token saved by `kamu login`:
+
+    kamu sql --url odf+https://node.example.com -c 'SELECT * FROM `org.example.data` LIMIT 10'
+
+Open an interactive shell against a Flight SQL endpoint directly:
+
+    kamu sql --url grpc+tls://node.example.com:50050
 
-    kamu sql --url jdbc:hive2://example.com:10000
+Connect to a remote Spark SQL server:
+
+    kamu sql --engine spark --url jdbc:hive2://example.com:10000
 
 Note: Currently when connecting to a remote SQL kamu server you will need to manually instruct it to load datasets from the data files. This can be done using the following command:
 
@@ -1259,11 +1572,15 @@ pub struct Sql {
     #[command(subcommand)]
     pub subcommand: Option<SqlSubCommand>,
 
-    /// Engine type to use for this SQL session
-    #[arg(long, value_name = "ENG", value_enum)]
-    pub engine: Option<SqlShellEngine>,
+    /// Engine to use for this SQL session: `datafusion` (default), `spark`
+    /// or an identifier of a custom engine registered in the config
+    #[arg(long, value_name = "ENG")]
+    pub engine: Option<String>,
 
-    /// URL of a running JDBC server (e.g. jdbc:hive2://example.com:10000)
+    /// URL of a remote node (e.g. odf+https://node.example.com) or its Flight
+    /// SQL endpoint (e.g. grpc+tls://node.example.com:50050) to run queries
+    /// on, or of a running JDBC server when using Spark engine (e.g.
+    /// jdbc:hive2://example.com:10000)
     #[arg(long)]
     pub url: Option<String>,
 
@@ -1356,6 +1673,8 @@ pub enum SystemSubCommand {
     Gc(SystemGc),
     Info(SystemInfo),
     Ipfs(SystemIpfs),
+    Task(SystemTask),
+    TaskWorker(SystemTaskWorker),
     UpgradeWorkspace(SystemUpgradeWorkspace),
 }
 
@@ -1431,15 +1750,29 @@ For datasets that get frequent small appends the number of data slices can grow
 
 There are two types of compactions: soft and hard.
 
-Soft compactions produce new files while leaving the old blocks intact. This allows for faster queries, while still preserving the accurate history of how dataset evolved over time.
+Soft compactions produce new files while leaving the old blocks intact. This allows for faster queries, while still preserving the accurate history of how dataset evolved over time. Dependent datasets are not affected, and repeated soft compactions only merge slices that were not merged before.
 
 Hard compactions rewrite the history of the dataset as if data was originally written in big batches. They allow to shrink the history of a dataset to just a few blocks, reclaim the space used by old data files, but at the expense of history loss. Hard compactions will rewrite the metadata chain, changing block hashes. Therefore, they will **break all downstream datasets** that depend on them.
 
 **Examples:**
 
+Perform a soft compaction that keeps the history intact:
+
+    kamu system compact my.dataset
+
 Perform a history-altering hard compaction:
 
     kamu system compact --hard my.dataset
+
+Compact only the blocks with records starting from offset 1000000, merging up to 4 batches of slices concurrently:
+
+    kamu system compact --hard --start-offset 1000000 --parallelism 4 my.dataset
+
+Re-sort records of merged slices by event time, so that queries filtering by it can skip more data:
+
+    kamu system compact --hard --sort-by-event-time my.dataset
+
+Note that re-sorting reassigns offsets of records within each merged slice.
 "#)]
 pub struct SystemCompact {
     /// Maximum size of a single data slice file in bytes
@@ -1455,9 +1788,30 @@ pub struct SystemCompact {
     pub hard: bool,
 
     /// Perform compaction without saving data blocks
-    #[arg(long)]
+    #[arg(long, requires = "hard")]
     pub keep_metadata_only: bool,
 
+    /// Only compact blocks containing records at or after this offset
+    #[arg(
+        long,
+        value_name = "OFFSET",
+        requires = "hard",
+        conflicts_with = "keep_metadata_only"
+    )]
+    pub start_offset: Option<u64>,
+
+    /// Maximum number of batches of data slices to merge concurrently
+    #[arg(long, default_value_t = 1, value_name = "NUM")]
+    pub parallelism: usize,
+
+    /// Sort records of merged data slices by event time
+    #[arg(long, requires = "hard", conflicts_with = "sort_by")]
+    pub sort_by_event_time: bool,
+
+    /// Sort records of merged data slices by the specified column(s)
+    #[arg(long, value_name = "COLUMN", value_delimiter = ',', requires = "hard")]
+    pub sort_by: Vec<String>,
+
     /// Perform verification of the dataset before running a compaction
     #[arg(long)]
     pub verify: bool,
@@ -1574,6 +1928,68 @@ pub struct SystemIpfsAdd {
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
 
+/// Inspect tasks executed by the API server and task workers
+#[derive(Debug, clap::Args)]
+pub struct SystemTask {
+    #[command(subcommand)]
+    pub subcommand: SystemTaskSubCommand,
+}
+
+#[derive(Debug, clap::Subcommand)]
+pub enum SystemTaskSubCommand {
+    Logs(SystemTaskLogs),
+}
+
+/// Print the log captured while running a task
+#[derive(Debug, clap::Args)]
+#[command(after_help = r#"
+Logs include messages of the task system, errors with their causes, and output of engine and fetch containers of failed tasks. Logs are kept in the storage configured in the `taskLogs` section of the config.
+
+**Examples:**
+
+Print the whole log of a task:
+
+    kamu system task logs 42
+
+Print the last 20 lines:
+
+    kamu system task logs 42 --tail 20
+"#)]
+pub struct SystemTaskLogs {
+    /// Task identifier
+    #[arg(value_name = "TASK_ID")]
+    pub task_id: u64,
+
+    /// Print only the specified number of last entries
+    #[arg(long, value_name = "N")]
+    pub tail: Option<usize>,
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+/// Run a standalone worker executing tasks from the shared task queue
+#[derive(Debug, clap::Args)]
+#[command(after_help = r#"
+Task worker claims queued tasks from the task store shared with API servers and executes them, without serving any API. Running several workers against the same Postgres or SQLite database allows scaling task execution horizontally.
+
+Claimed tasks are leased to the worker. While a task is running the worker periodically extends the lease. If a worker crashes, its tasks are re-queued once their leases expire.
+
+Concurrency, per-plan-kind limits and lease timings are configured in the `flowSystem.taskAgent` section of the config. To delegate all task execution to workers, set `maxConcurrentTasks` of API servers to `0`.
+
+**Examples:**
+
+Run a worker executing up to 4 tasks simultaneously:
+
+    kamu system task-worker --max-concurrent-tasks 4
+"#)]
+pub struct SystemTaskWorker {
+    /// Maximum number of tasks executed simultaneously (overrides the config)
+    #[arg(long, value_name = "N")]
+    pub max_concurrent_tasks: Option<usize>,
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
 /// Upgrade the layout of a local workspace to the latest version
 #[derive(Debug, clap::Args)]
 pub struct SystemUpgradeWorkspace {}
@@ -1586,6 +2002,18 @@ pub struct SystemUpgradeWorkspace {}
 This command can be thought of as a shortcut for:
 
     kamu sql --engine datafusion --command 'select * from "{dataset}" order by {offset_col} desc limit {num_records}'
+
+With `--follow` the command keeps running and displays new records as they are committed to the dataset. Retractions and corrections are displayed along with their operation type, so the output can be used to maintain a derived view of the data.
+
+**Examples:**
+
+Display the latest records and keep streaming new ones:
+
+    kamu tail --follow org.example.data
+
+Stream all records added after the offset 100:
+
+    kamu tail --follow --since-offset 100 -o ndjson org.example.data
 "#)]
 pub struct Tail {
     /// Format to display the results in
@@ -1597,9 +2025,23 @@ pub struct Tail {
     pub num_records: u64,
 
     /// Number of initial records to skip before applying the limit
-    #[arg(long, short = 's', default_value_t = 0, value_name = "SKP")]
+    #[arg(
+        long,
+        short = 's',
+        default_value_t = 0,
+        value_name = "SKP",
+        conflicts_with = "follow"
+    )]
     pub skip_records: u64,
 
+    /// Keep displaying new records as they are committed to the dataset
+    #[arg(long, short = 'f')]
+    pub follow: bool,
+
+    /// Display only the records with offsets greater than this value
+    #[arg(long, value_name = "OFFSET", requires = "follow")]
+    pub since_offset: Option<u64>,
+
     /// Local dataset reference
     #[arg(value_parser = parsers::dataset_ref)]
     pub dataset: odf::DatasetRef,
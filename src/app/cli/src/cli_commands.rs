@@ -9,7 +9,7 @@
 
 use clap::CommandFactory as _;
 use dill::Catalog;
-use kamu::domain::TenancyConfig;
+use kamu::domain::{CompactionSortBy, TenancyConfig};
 use kamu_accounts::CurrentAccountSubject;
 
 use crate::cli::SystemApiServerSubCommand;
@@ -36,11 +36,15 @@ pub fn get_command(
             cli_catalog.get_one()?,
             cli_catalog.get_one()?,
             cli_catalog.get_one()?,
+            cli_catalog.get_one()?,
             c.manifest,
             c.name,
             c.recursive,
             c.replace,
             c.stdin,
+            c.template,
+            c.vars,
+            c.dry_run,
             c.visibility.map(Into::into),
             cli_catalog.get_one()?,
             tenancy_config,
@@ -103,7 +107,33 @@ pub fn get_command(
             c.all,
             c.recursive,
         )),
+        cli::Command::Diff(c) => Box::new(DiffCommand::new(
+            cli_catalog.get_one()?,
+            cli_catalog.get_one()?,
+            cli_catalog.get_one()?,
+            cli_catalog.get_one()?,
+            validate_dataset_ref(cli_catalog, c.dataset)?,
+            c.from,
+            c.to,
+            c.metadata_only,
+        )),
+        cli::Command::Expectations(c) => match c.subcommand {
+            cli::ExpectationsSubCommand::Get(sc) => Box::new(ExpectationsGetCommand::new(
+                cli_catalog.get_one()?,
+                cli_catalog.get_one()?,
+                cli_catalog.get_one()?,
+                validate_dataset_ref(cli_catalog, sc.dataset)?,
+            )),
+            cli::ExpectationsSubCommand::Set(sc) => Box::new(ExpectationsSetCommand::new(
+                cli_catalog.get_one()?,
+                cli_catalog.get_one()?,
+                cli_catalog.get_one()?,
+                validate_dataset_ref(cli_catalog, sc.dataset)?,
+                sc.manifest,
+            )),
+        },
         cli::Command::Export(c) => Box::new(ExportCommand::new(
+            cli_catalog.get_one()?,
             cli_catalog.get_one()?,
             cli_catalog.get_one()?,
             c.dataset,
@@ -112,6 +142,28 @@ pub fn get_command(
             c.records_per_file,
             args.quiet,
         )),
+        cli::Command::Freshness(c) => match c.subcommand {
+            cli::FreshnessSubCommand::Clear(sc) => Box::new(FreshnessClearCommand::new(
+                cli_catalog.get_one()?,
+                cli_catalog.get_one()?,
+                cli_catalog.get_one()?,
+                validate_dataset_ref(cli_catalog, sc.dataset)?,
+            )),
+            cli::FreshnessSubCommand::Get(sc) => Box::new(FreshnessGetCommand::new(
+                cli_catalog.get_one()?,
+                cli_catalog.get_one()?,
+                cli_catalog.get_one()?,
+                validate_dataset_ref(cli_catalog, sc.dataset)?,
+            )),
+            cli::FreshnessSubCommand::Set(sc) => Box::new(FreshnessSetCommand::new(
+                cli_catalog.get_one()?,
+                cli_catalog.get_one()?,
+                cli_catalog.get_one()?,
+                validate_dataset_ref(cli_catalog, sc.dataset)?,
+                sc.max_update_interval,
+                sc.max_watermark_lag,
+            )),
+        },
         cli::Command::Ingest(c) => Box::new(IngestCommand::new(
             cli_catalog.get_one()?,
             cli_catalog.get_one()?,
@@ -133,6 +185,7 @@ pub fn get_command(
                     cli_catalog.get_one()?,
                     cli_catalog.get_one()?,
                     cli_catalog.get_one()?,
+                    cli_catalog.get_one()?,
                     c.list_only,
                 ))
             } else {
@@ -174,6 +227,7 @@ pub fn get_command(
                 cli_catalog.get_one()?,
                 cli_catalog.get_one()?,
                 cli_catalog.get_one()?,
+                cli_catalog.get_one()?,
                 accounts::AccountService::current_account_indication(
                     args.account,
                     tenancy_config,
@@ -189,6 +243,7 @@ pub fn get_command(
             ))
         }
         cli::Command::Log(c) => Box::new(LogCommand::new(
+            cli_catalog.get_one()?,
             cli_catalog.get_one()?,
             cli_catalog.get_one()?,
             validate_dataset_ref(cli_catalog, c.dataset)?,
@@ -212,6 +267,21 @@ pub fn get_command(
                     access_token: sc.access_token,
                 }),
             )),
+            Some(cli::LoginSubCommand::Oidc(sc)) => Box::new(LoginSilentCommand::new(
+                cli_catalog.get_one()?,
+                cli_catalog.get_one()?,
+                if c.user {
+                    odf_server::AccessTokenStoreScope::User
+                } else {
+                    odf_server::AccessTokenStoreScope::Workspace
+                },
+                sc.server.map(Into::into),
+                LoginSilentMode::Oidc(LoginSilentModeOidc {
+                    issuer_url: sc.issuer_url,
+                    client_id: sc.client_id,
+                    scopes: sc.scopes,
+                }),
+            )),
             Some(cli::LoginSubCommand::Password(sc)) => Box::new(LoginSilentCommand::new(
                 cli_catalog.get_one()?,
                 cli_catalog.get_one()?,
@@ -226,6 +296,26 @@ pub fn get_command(
                     password: sc.password,
                 }),
             )),
+            Some(cli::LoginSubCommand::Register(sc)) => Box::new(LoginRegisterCommand::new(
+                cli_catalog.get_one()?,
+                sc.server.map(Into::into),
+                sc.account_name,
+                sc.email,
+                sc.password,
+            )),
+            Some(cli::LoginSubCommand::VerifyEmail(sc)) => Box::new(LoginSilentCommand::new(
+                cli_catalog.get_one()?,
+                cli_catalog.get_one()?,
+                if c.user {
+                    odf_server::AccessTokenStoreScope::User
+                } else {
+                    odf_server::AccessTokenStoreScope::Workspace
+                },
+                sc.server.map(Into::into),
+                LoginSilentMode::VerifyEmail(LoginSilentModeVerifyEmail {
+                    verification_token: sc.verification_token,
+                }),
+            )),
             None => Box::new(LoginCommand::new(
                 cli_catalog.get_one()?,
                 cli_catalog.get_one()?,
@@ -383,6 +473,9 @@ pub fn get_command(
                 cli_catalog.get_one()?,
                 cli_catalog.get_one()?,
                 cli_catalog.get_one()?,
+                cli_catalog.get_one()?,
+                cli_catalog.get_one()?,
+                cli_catalog.get_one()?,
                 c.command,
                 c.url,
                 c.engine,
@@ -438,6 +531,15 @@ pub fn get_command(
                 sc.hard,
                 sc.verify,
                 sc.keep_metadata_only,
+                sc.start_offset,
+                sc.parallelism,
+                if sc.sort_by_event_time {
+                    Some(CompactionSortBy::EventTime)
+                } else if !sc.sort_by.is_empty() {
+                    Some(CompactionSortBy::Columns(sc.sort_by))
+                } else {
+                    None
+                },
             )),
             cli::SystemSubCommand::Diagnose(_) => Box::new(SystemDiagnoseCommand::new(
                 cli_catalog.get_one()?,
@@ -479,17 +581,42 @@ pub fn get_command(
                     ssc.dataset,
                 )),
             },
+            cli::SystemSubCommand::Task(sc) => match sc.subcommand {
+                cli::SystemTaskSubCommand::Logs(ssc) => Box::new(SystemTaskLogsCommand::new(
+                    cli_catalog.get_one()?,
+                    ssc.task_id,
+                    ssc.tail,
+                )),
+            },
+            cli::SystemSubCommand::TaskWorker(_) => Box::new(SystemTaskWorkerCommand::new(
+                cli_catalog.get_one()?,
+                cli_catalog.get_one()?,
+                cli_catalog.get_one().ok(),
+            )),
             cli::SystemSubCommand::UpgradeWorkspace(_) => {
                 Box::new(UpgradeWorkspaceCommand::new(cli_catalog.get_one()?))
             }
         },
-        cli::Command::Tail(c) => Box::new(TailCommand::new(
-            cli_catalog.get_one()?,
-            validate_dataset_ref(cli_catalog, c.dataset)?,
-            c.skip_records,
-            c.num_records,
-            cli_catalog.get_one()?,
-        )),
+        cli::Command::Tail(c) => {
+            if c.follow {
+                Box::new(TailFollowCommand::new(
+                    cli_catalog.clone(),
+                    cli_catalog.get_one()?,
+                    validate_dataset_ref(cli_catalog, c.dataset)?,
+                    c.num_records,
+                    c.since_offset,
+                    cli_catalog.get_one()?,
+                ))
+            } else {
+                Box::new(TailCommand::new(
+                    cli_catalog.get_one()?,
+                    validate_dataset_ref(cli_catalog, c.dataset)?,
+                    c.skip_records,
+                    c.num_records,
+                    cli_catalog.get_one()?,
+                ))
+            }
+        }
         cli::Command::Ui(c) => {
             let current_account_subject = cli_catalog.get_one::<CurrentAccountSubject>()?;
 
@@ -536,9 +663,11 @@ pub fn get_command(
 pub fn command_needs_transaction(args: &cli::Cli) -> bool {
     match &args.command {
         cli::Command::System(c) => match &c.subcommand {
-            cli::SystemSubCommand::ApiServer(_) => false,
+            cli::SystemSubCommand::ApiServer(_) | cli::SystemSubCommand::TaskWorker(_) => false,
             _ => true,
         },
+        // Following a dataset opens a new transaction for every read
+        cli::Command::Tail(c) => !c.follow,
         cli::Command::Ui(_) => false,
         _ => true,
     }
@@ -587,7 +716,9 @@ pub fn command_needs_startup_jobs(args: &cli::Cli) -> bool {
 pub fn command_needs_server_components(args: &cli::Cli) -> bool {
     match &args.command {
         cli::Command::System(c) => match &c.subcommand {
-            cli::SystemSubCommand::ApiServer(_) => true,
+            cli::SystemSubCommand::ApiServer(_)
+            | cli::SystemSubCommand::Task(_)
+            | cli::SystemSubCommand::TaskWorker(_) => true,
             _ => false,
         },
         cli::Command::Ui(_) => true,
@@ -99,6 +99,15 @@ pub(crate) fn repo_name(s: &str) -> Result<odf::RepoName, String> {
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
 
+pub(crate) fn account_name(s: &str) -> Result<odf::AccountName, String> {
+    match odf::AccountName::try_from(s) {
+        Ok(v) => Ok(v),
+        Err(_) => Err("Account name can only contain alphanumerics, dashes, and dots".to_string()),
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
 pub(crate) fn multihash(s: &str) -> Result<odf::Multihash, String> {
     match odf::Multihash::from_multibase(s) {
         Ok(v) => Ok(v),
@@ -128,6 +137,7 @@ pub(crate) fn export_format(format: &str) -> Result<ExportFormat, String> {
         "parquet" => Ok(ExportFormat::Parquet),
         "ndjson" => Ok(ExportFormat::NdJson),
         "csv" => Ok(ExportFormat::Csv),
+        "iceberg" => Ok(ExportFormat::Iceberg),
         _ => {
             let supported_formats = ExportFormat::iter()
                 .map(|f| format!("'{f}'"))
@@ -140,6 +150,15 @@ pub(crate) fn export_format(format: &str) -> Result<ExportFormat, String> {
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
 
+pub(crate) fn duration(s: &str) -> Result<chrono::Duration, String> {
+    match duration_string::DurationString::from_string(s.to_string()) {
+        Ok(v) => chrono::Duration::from_std(v.into()).map_err(|e| e.to_string()),
+        Err(_) => Err("Duration should be in form: `30s`, `15m`, `6h`, `1d`, or `2w`".to_string()),
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
 #[derive(Debug, Clone, Copy)]
 pub struct DateTimeRfc3339(chrono::DateTime<chrono::Utc>);
 
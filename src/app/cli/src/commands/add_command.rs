@@ -8,8 +8,10 @@
 // by the Apache License, Version 2.0.
 
 use std::collections::{HashSet, LinkedList};
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
+use internal_error::{BoxedError, ResultIntoInternal};
 use kamu::domain::*;
 use kamu_datasets::{
     CreateDatasetFromSnapshotError,
@@ -17,6 +19,8 @@ use kamu_datasets::{
     CreateDatasetResult,
     CreateDatasetUseCaseOptions,
     DeleteDatasetUseCase,
+    UpdateDatasetFromSnapshotUseCase,
+    UpdateDatasetFromSnapshotUseCaseOptions,
 };
 
 use super::{BatchError, CLIError, Command};
@@ -28,12 +32,16 @@ pub struct AddCommand {
     resource_loader: Arc<dyn ResourceLoader>,
     dataset_registry: Arc<dyn DatasetRegistry>,
     create_dataset_from_snapshot: Arc<dyn CreateDatasetFromSnapshotUseCase>,
+    update_dataset_from_snapshot: Arc<dyn UpdateDatasetFromSnapshotUseCase>,
     delete_dataset: Arc<dyn DeleteDatasetUseCase>,
     snapshot_refs: Vec<String>,
     name: Option<odf::DatasetAlias>,
     recursive: bool,
     replace: bool,
     stdin: bool,
+    template: Option<String>,
+    vars_path: Option<PathBuf>,
+    dry_run: bool,
     dataset_visibility: odf::DatasetVisibility,
     output_config: Arc<OutputConfig>,
     confirm_delete_service: Arc<ConfirmDeleteService>,
@@ -44,12 +52,16 @@ impl AddCommand {
         resource_loader: Arc<dyn ResourceLoader>,
         dataset_registry: Arc<dyn DatasetRegistry>,
         create_dataset_from_snapshot: Arc<dyn CreateDatasetFromSnapshotUseCase>,
+        update_dataset_from_snapshot: Arc<dyn UpdateDatasetFromSnapshotUseCase>,
         delete_dataset: Arc<dyn DeleteDatasetUseCase>,
         snapshot_refs_iter: I,
         name: Option<odf::DatasetAlias>,
         recursive: bool,
         replace: bool,
         stdin: bool,
+        template: Option<String>,
+        vars_path: Option<PathBuf>,
+        dry_run: bool,
         maybe_dataset_visibility: Option<odf::DatasetVisibility>,
         output_config: Arc<OutputConfig>,
         tenancy_config: TenancyConfig,
@@ -66,12 +78,16 @@ impl AddCommand {
             resource_loader,
             dataset_registry,
             create_dataset_from_snapshot,
+            update_dataset_from_snapshot,
             delete_dataset,
             snapshot_refs: snapshot_refs_iter.into_iter().map(Into::into).collect(),
             name,
             recursive,
             replace,
             stdin,
+            template,
+            vars_path,
+            dry_run,
             dataset_visibility,
             output_config,
             confirm_delete_service,
@@ -126,6 +142,30 @@ impl AddCommand {
         }
     }
 
+    fn load_template_vars(&self, path: &Path) -> Result<Vec<TemplateVars>, CLIError> {
+        let mut reader = csv::ReaderBuilder::new()
+            .trim(csv::Trim::All)
+            .from_path(path)
+            .map_err(CLIError::usage_error_from)?;
+
+        let headers = reader
+            .headers()
+            .map_err(CLIError::usage_error_from)?
+            .clone();
+
+        reader
+            .records()
+            .map(|record| {
+                let record = record.map_err(CLIError::usage_error_from)?;
+                Ok(headers
+                    .iter()
+                    .zip(record.iter())
+                    .map(|(name, value)| (name.to_string(), value.to_string()))
+                    .collect())
+            })
+            .collect()
+    }
+
     fn is_snapshot_file(&self, path: &std::path::Path) -> Result<bool, std::io::Error> {
         use std::io::BufRead;
         let file = std::fs::File::open(path)?;
@@ -217,6 +257,217 @@ impl AddCommand {
         }
         ordered
     }
+
+    async fn run_template(&self, template: &str, vars_path: &Path) -> Result<(), CLIError> {
+        let vars = self.load_template_vars(vars_path)?;
+
+        let snapshots = self
+            .resource_loader
+            .load_dataset_snapshots_from_template(template, &vars)
+            .await
+            .map_err(CLIError::failure)?;
+
+        let mut names = HashSet::new();
+        for s in &snapshots {
+            if !names.insert(&s.name) {
+                return Err(CLIError::usage_error(format!(
+                    "Template produces dataset {} more than once",
+                    s.name
+                )));
+            }
+        }
+
+        // Datasets that don't exist yet are created first, as updated ones may depend
+        // on them
+        let mut new_snapshots = Vec::new();
+        let mut existing = Vec::new();
+        for s in snapshots {
+            match self
+                .dataset_registry
+                .try_resolve_dataset_handle_by_ref(&s.name.as_local_ref())
+                .await?
+            {
+                Some(hdl) => existing.push((hdl, s)),
+                None => new_snapshots.push(s),
+            }
+        }
+
+        let mut num_added = 0;
+        let mut num_updated = 0;
+        let mut errors_with_contexts: Vec<(BoxedError, String)> = Vec::new();
+
+        if self.dry_run {
+            for s in &new_snapshots {
+                self.print_new_snapshot_diff(s)?;
+                if !self.output_config.quiet {
+                    eprintln!("{}: {}", console::style("Would add").green(), s.name);
+                }
+                num_added += 1;
+            }
+        } else {
+            let create_options = CreateDatasetUseCaseOptions {
+                dataset_visibility: self.dataset_visibility,
+            };
+            for (alias, res) in self
+                .create_datasets_from_snapshots(new_snapshots, create_options)
+                .await
+            {
+                match res {
+                    Ok(_) => {
+                        num_added += 1;
+                        if !self.output_config.quiet {
+                            eprintln!("{}: {}", console::style("Added").green(), alias);
+                        }
+                    }
+                    Err(err) => {
+                        errors_with_contexts
+                            .push((err.into(), format!("Failed to add dataset {alias}")));
+                    }
+                }
+            }
+        }
+
+        let update_options = UpdateDatasetFromSnapshotUseCaseOptions {
+            dry_run: self.dry_run,
+        };
+        for (hdl, s) in existing {
+            match self
+                .update_dataset_from_snapshot
+                .execute(&hdl, s, update_options)
+                .await
+            {
+                Ok(res) if res.changes.is_empty() => {
+                    if !self.output_config.quiet {
+                        eprintln!("{}: {}", console::style("Unchanged").yellow(), hdl.alias);
+                    }
+                }
+                Ok(res) => {
+                    num_updated += 1;
+                    if self.dry_run {
+                        self.print_event_changes_diff(&hdl.alias, &res.changes)?;
+                    }
+                    if !self.output_config.quiet {
+                        eprintln!(
+                            "{}: {}: {} event(s)",
+                            console::style(if self.dry_run {
+                                "Would update"
+                            } else {
+                                "Updated"
+                            })
+                            .green(),
+                            hdl.alias,
+                            res.changes.len()
+                        );
+                    }
+                }
+                Err(err) => {
+                    errors_with_contexts.push((
+                        err.into(),
+                        format!("Failed to update dataset {}", hdl.alias),
+                    ));
+                }
+            }
+        }
+
+        if !errors_with_contexts.is_empty() {
+            return Err(BatchError::new(
+                format!("Failed to apply {} manifest(s)", errors_with_contexts.len()),
+                errors_with_contexts,
+            )
+            .into());
+        }
+
+        if !self.output_config.quiet {
+            let summary = if self.dry_run {
+                format!("Would add {num_added} and update {num_updated} dataset(s)")
+            } else {
+                format!("Added {num_added} and updated {num_updated} dataset(s)")
+            };
+            eprintln!("{}", console::style(summary).green().bold());
+        }
+
+        Ok(())
+    }
+
+    fn print_new_snapshot_diff(&self, snapshot: &odf::DatasetSnapshot) -> Result<(), CLIError> {
+        let manifest = odf::serde::yaml::YamlDatasetSnapshotSerializer
+            .write_manifest_str(snapshot)
+            .int_err()?;
+
+        println!("--- /dev/null");
+        println!("+++ {}", snapshot.name);
+        for line in manifest.lines() {
+            println!("+{line}");
+        }
+
+        Ok(())
+    }
+
+    fn print_event_changes_diff(
+        &self,
+        alias: &odf::DatasetAlias,
+        changes: &[kamu_datasets::DatasetSnapshotChange],
+    ) -> Result<(), CLIError> {
+        use odf::serde::yaml::YamlMetadataEventSerializer;
+
+        println!("--- {alias}");
+        println!("+++ {alias}");
+        for change in changes {
+            let old_manifest = match &change.old_event {
+                Some(event) => YamlMetadataEventSerializer
+                    .write_manifest_str(event)
+                    .int_err()?,
+                None => String::new(),
+            };
+            let new_manifest = YamlMetadataEventSerializer
+                .write_manifest_str(&change.new_event)
+                .int_err()?;
+
+            let old_lines: Vec<_> = old_manifest.lines().collect();
+            let new_lines: Vec<_> = new_manifest.lines().collect();
+            for (tag, line) in diff_lines(&old_lines, &new_lines) {
+                println!("{tag}{line}");
+            }
+        }
+
+        Ok(())
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+/// Line diff based on the longest common subsequence, lines are tagged with
+/// ` `, `-` or `+` like in the unified diff format
+fn diff_lines<'a>(old: &[&'a str], new: &[&'a str]) -> Vec<(char, &'a str)> {
+    let mut lcs = vec![vec![0_usize; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lcs[i][j] = if old[i] == new[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut res = Vec::with_capacity(old.len().max(new.len()));
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() && j < new.len() {
+        if old[i] == new[j] {
+            res.push((' ', old[i]));
+            i += 1;
+            j += 1;
+        } else if lcs[i + 1][j] >= lcs[i][j + 1] {
+            res.push(('-', old[i]));
+            i += 1;
+        } else {
+            res.push(('+', new[j]));
+            j += 1;
+        }
+    }
+    res.extend(old[i..].iter().map(|line| ('-', *line)));
+    res.extend(new[j..].iter().map(|line| ('+', *line)));
+    res
 }
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
@@ -229,7 +480,7 @@ impl Command for AddCommand {
                 "Cannot specify --stdin and positional arguments at the same time",
             ));
         }
-        if !self.stdin && self.snapshot_refs.is_empty() {
+        if !self.stdin && self.snapshot_refs.is_empty() && self.template.is_none() {
             return Err(CLIError::usage_error(
                 "No manifest references or paths were provided",
             ));
@@ -245,6 +496,11 @@ impl Command for AddCommand {
     }
 
     async fn run(&mut self) -> Result<(), CLIError> {
+        if let Some(template) = &self.template {
+            let vars_path = self.vars_path.as_ref().expect("--vars is required by clap");
+            return self.run_template(template, vars_path).await;
+        }
+
         let load_results = if self.recursive {
             self.load_recursive().await
         } else if self.stdin {
@@ -10,7 +10,7 @@
 use std::sync::{Arc, Mutex};
 use std::time::Duration;
 
-use kamu::domain::PullImageListener;
+use kamu::domain::{EngineProvisioningListener, PullImageListener};
 
 use crate::OutputConfig;
 
@@ -56,4 +56,10 @@ impl PullImageListener for PullImageProgress {
     }
 }
 
+impl EngineProvisioningListener for PullImageProgress {
+    fn get_pull_image_listener(self: Arc<Self>) -> Option<Arc<dyn PullImageListener>> {
+        Some(self)
+    }
+}
+
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
@@ -12,7 +12,9 @@ use std::sync::Arc;
 use futures::TryStreamExt as _;
 use kamu::domain::{
     CompactDatasetUseCase,
+    CompactionMode,
     CompactionOptions,
+    CompactionSortBy,
     DatasetRegistry,
     VerificationMultiListener,
     VerificationOptions,
@@ -40,6 +42,9 @@ pub struct CompactCommand {
     is_hard: bool,
     is_verify: bool,
     keep_metadata_only: bool,
+    start_offset: Option<u64>,
+    parallelism: usize,
+    sort_by: Option<CompactionSortBy>,
 }
 
 impl CompactCommand {
@@ -54,6 +59,9 @@ impl CompactCommand {
         is_hard: bool,
         is_verify: bool,
         keep_metadata_only: bool,
+        start_offset: Option<u64>,
+        parallelism: usize,
+        sort_by: Option<CompactionSortBy>,
     ) -> Self {
         Self {
             interact,
@@ -66,6 +74,9 @@ impl CompactCommand {
             is_hard,
             is_verify,
             keep_metadata_only,
+            start_offset,
+            parallelism,
+            sort_by,
         }
     }
 
@@ -102,12 +113,6 @@ impl Command for CompactCommand {
             return Err(CLIError::usage_error("Specify a dataset or a pattern"));
         }
 
-        if !self.is_hard {
-            return Err(CLIError::usage_error(
-                "Soft compactions are not yet supported",
-            ));
-        }
-
         let dataset_handles: Vec<odf::DatasetHandle> = {
             kamu::utils::datasets_filtering::filter_datasets_by_local_pattern(
                 self.dataset_registry.as_ref(),
@@ -117,15 +122,18 @@ impl Command for CompactCommand {
             .await?
         };
 
-        self.interact.require_confirmation(format!(
-            "{}\n  {}\n{}",
-            console::style(
-                "You are about to perform a hard compaction of the following dataset(s):"
-            )
-            .yellow(),
-            itertools::join(dataset_handles.iter().map(|h| &h.alias), "\n  "),
-            console::style("This operation is history-altering and irreversible!").yellow(),
-        ))?;
+        // Soft compactions leave the metadata chain intact
+        if self.is_hard {
+            self.interact.require_confirmation(format!(
+                "{}\n  {}\n{}",
+                console::style(
+                    "You are about to perform a hard compaction of the following dataset(s):"
+                )
+                .yellow(),
+                itertools::join(dataset_handles.iter().map(|h| &h.alias), "\n  "),
+                console::style("This operation is history-altering and irreversible!").yellow(),
+            ))?;
+        }
 
         if self.is_verify {
             for hdl in &dataset_handles {
@@ -155,6 +163,14 @@ impl Command for CompactCommand {
                     max_slice_size: Some(self.max_slice_size),
                     max_slice_records: Some(self.max_slice_records),
                     keep_metadata_only: self.keep_metadata_only,
+                    start_offset: self.start_offset,
+                    merge_parallelism: Some(self.parallelism),
+                    sort_by: self.sort_by.clone(),
+                    mode: if self.is_hard {
+                        CompactionMode::Hard
+                    } else {
+                        CompactionMode::Soft
+                    },
                 },
                 Some(listener.clone()),
             )
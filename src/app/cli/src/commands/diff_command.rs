@@ -0,0 +1,182 @@
+// Copyright Kamu Data, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::sync::Arc;
+
+use chrono::SecondsFormat;
+use console::style;
+use kamu::domain::*;
+
+use super::{operation_type_column_format, CLIError, Command};
+use crate::output::*;
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+pub struct DiffCommand {
+    dataset_registry: Arc<dyn DatasetRegistry>,
+    dataset_action_authorizer: Arc<dyn auth::DatasetActionAuthorizer>,
+    dataset_diff_svc: Arc<dyn DatasetDiffService>,
+    output_cfg: Arc<OutputConfig>,
+    dataset_ref: odf::DatasetRef,
+    from: Option<odf::Multihash>,
+    to: Option<odf::Multihash>,
+    metadata_only: bool,
+}
+
+impl DiffCommand {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        dataset_registry: Arc<dyn DatasetRegistry>,
+        dataset_action_authorizer: Arc<dyn auth::DatasetActionAuthorizer>,
+        dataset_diff_svc: Arc<dyn DatasetDiffService>,
+        output_cfg: Arc<OutputConfig>,
+        dataset_ref: odf::DatasetRef,
+        from: Option<odf::Multihash>,
+        to: Option<odf::Multihash>,
+        metadata_only: bool,
+    ) -> Self {
+        Self {
+            dataset_registry,
+            dataset_action_authorizer,
+            dataset_diff_svc,
+            output_cfg,
+            dataset_ref,
+            from,
+            to,
+            metadata_only,
+        }
+    }
+
+    fn event_kind(event: &odf::MetadataEvent) -> &'static str {
+        match event {
+            odf::MetadataEvent::AddData(_) => "AddData",
+            odf::MetadataEvent::ExecuteTransform(_) => "ExecuteTransform",
+            odf::MetadataEvent::Seed(_) => "Seed",
+            odf::MetadataEvent::SetPollingSource(_) => "SetPollingSource",
+            odf::MetadataEvent::SetTransform(_) => "SetTransform",
+            odf::MetadataEvent::SetVocab(_) => "SetVocab",
+            odf::MetadataEvent::SetAttachments(_) => "SetAttachments",
+            odf::MetadataEvent::SetInfo(_) => "SetInfo",
+            odf::MetadataEvent::SetLicense(_) => "SetLicense",
+            odf::MetadataEvent::SetDataSchema(_) => "SetDataSchema",
+            odf::MetadataEvent::AddPushSource(_) => "AddPushSource",
+            odf::MetadataEvent::DisablePushSource(_) => "DisablePushSource",
+            odf::MetadataEvent::DisablePollingSource(_) => "DisablePollingSource",
+        }
+    }
+
+    fn render_metadata_changes(&self, diff: &DatasetDiff) {
+        eprintln!(
+            "{} {}",
+            style("Metadata changes:").bold(),
+            diff.metadata_blocks.len()
+        );
+
+        // Show the blocks in the order they were added
+        for (hash, block) in diff.metadata_blocks.iter().rev() {
+            eprintln!(
+                "  {} {} {} {}",
+                style(format!("#{}", block.sequence_number)).dim(),
+                style(hash).yellow(),
+                block
+                    .system_time
+                    .to_rfc3339_opts(SecondsFormat::AutoSi, true),
+                Self::event_kind(&block.event),
+            );
+        }
+    }
+
+    fn render_data_summary(&self, data: &DatasetDataDiff) {
+        let Some(offset_interval) = &data.offset_interval else {
+            eprintln!("{} none", style("Data changes:").bold());
+            return;
+        };
+
+        eprintln!(
+            "{} offsets [{}, {}]: {} appended, {} retracted, {} corrected",
+            style("Data changes:").bold(),
+            offset_interval.start,
+            offset_interval.end,
+            style(format!("+{}", data.num_appended)).green(),
+            style(format!("-{}", data.num_retracted)).red(),
+            style(format!("~{}", data.num_corrected)).yellow(),
+        );
+
+        if !data.primary_key.is_empty() {
+            eprintln!(
+                "{} {}",
+                style("Grouped by primary key:").dim(),
+                data.primary_key.join(", ")
+            );
+        }
+    }
+}
+
+#[async_trait::async_trait(?Send)]
+impl Command for DiffCommand {
+    async fn run(&mut self) -> Result<(), CLIError> {
+        let dataset_handle = self
+            .dataset_registry
+            .resolve_dataset_handle_by_ref(&self.dataset_ref)
+            .await?;
+
+        self.dataset_action_authorizer
+            .check_action_allowed(&dataset_handle.id, auth::DatasetAction::Read)
+            .await
+            .map_err(|e| match e {
+                auth::DatasetActionUnauthorizedError::Access(e) => CLIError::failure(e),
+                auth::DatasetActionUnauthorizedError::Internal(e) => CLIError::critical(e),
+            })?;
+
+        let target = self
+            .dataset_registry
+            .get_dataset_by_handle(&dataset_handle)
+            .await;
+
+        let diff = self
+            .dataset_diff_svc
+            .diff(target, self.from.as_ref(), self.to.as_ref())
+            .await
+            .map_err(|e| match e {
+                DatasetDiffError::BlockNotFound(e) => CLIError::failure(e),
+                DatasetDiffError::InvalidInterval(e) => CLIError::failure(e),
+                DatasetDiffError::Access(e) => CLIError::failure(e),
+                DatasetDiffError::Internal(e) => CLIError::critical(e),
+            })?;
+
+        if !self.output_cfg.quiet {
+            self.render_metadata_changes(&diff);
+            self.render_data_summary(&diff.data);
+        }
+
+        if self.metadata_only {
+            return Ok(());
+        }
+
+        let Some(df) = diff.data.records else {
+            return Ok(());
+        };
+
+        let mut writer = self.output_cfg.get_records_writer(
+            df.schema().as_arrow(),
+            RecordsFormat::default().with_column_formats(vec![
+                ColumnFormat::default(),
+                operation_type_column_format(),
+            ]),
+        );
+
+        let record_batches = df.collect().await.map_err(CLIError::failure)?;
+        writer.write_batches(&record_batches)?;
+        writer.finish()?;
+
+        Ok(())
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
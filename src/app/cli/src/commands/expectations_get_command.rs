@@ -0,0 +1,81 @@
+// Copyright Kamu Data, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::sync::Arc;
+
+use internal_error::ResultIntoInternal;
+use kamu::domain::*;
+use odf::metadata::serde::yaml::Manifest;
+
+use super::{CLIError, Command};
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+pub struct ExpectationsGetCommand {
+    dataset_registry: Arc<dyn DatasetRegistry>,
+    dataset_action_authorizer: Arc<dyn auth::DatasetActionAuthorizer>,
+    dataset_expectations_svc: Arc<dyn DatasetExpectationsService>,
+    dataset_ref: odf::DatasetRef,
+}
+
+impl ExpectationsGetCommand {
+    pub fn new(
+        dataset_registry: Arc<dyn DatasetRegistry>,
+        dataset_action_authorizer: Arc<dyn auth::DatasetActionAuthorizer>,
+        dataset_expectations_svc: Arc<dyn DatasetExpectationsService>,
+        dataset_ref: odf::DatasetRef,
+    ) -> Self {
+        Self {
+            dataset_registry,
+            dataset_action_authorizer,
+            dataset_expectations_svc,
+            dataset_ref,
+        }
+    }
+}
+
+#[async_trait::async_trait(?Send)]
+impl Command for ExpectationsGetCommand {
+    async fn run(&mut self) -> Result<(), CLIError> {
+        let dataset_handle = self
+            .dataset_registry
+            .resolve_dataset_handle_by_ref(&self.dataset_ref)
+            .await?;
+
+        self.dataset_action_authorizer
+            .check_action_allowed(&dataset_handle.id, auth::DatasetAction::Read)
+            .await
+            .map_err(|e| match e {
+                auth::DatasetActionUnauthorizedError::Access(e) => CLIError::failure(e),
+                auth::DatasetActionUnauthorizedError::Internal(e) => CLIError::critical(e),
+            })?;
+
+        let target = self
+            .dataset_registry
+            .get_dataset_by_handle(&dataset_handle)
+            .await;
+
+        let expectations = self
+            .dataset_expectations_svc
+            .get_expectations(&target)
+            .await?;
+
+        let manifest = Manifest {
+            kind: "DatasetExpectations".to_string(),
+            version: 1,
+            content: DatasetExpectations { expectations },
+        };
+
+        print!("{}", serde_yaml::to_string(&manifest).int_err()?);
+
+        Ok(())
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
@@ -0,0 +1,129 @@
+// Copyright Kamu Data, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use console::style;
+use kamu::domain::*;
+use kamu_datasets::CommitDatasetEventUseCase;
+use odf::metadata::serde::yaml::Manifest;
+
+use super::{CLIError, Command};
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+pub struct ExpectationsSetCommand {
+    dataset_registry: Arc<dyn DatasetRegistry>,
+    dataset_expectations_svc: Arc<dyn DatasetExpectationsService>,
+    commit_dataset_event: Arc<dyn CommitDatasetEventUseCase>,
+    dataset_ref: odf::DatasetRef,
+    manifest_path: PathBuf,
+}
+
+impl ExpectationsSetCommand {
+    pub fn new(
+        dataset_registry: Arc<dyn DatasetRegistry>,
+        dataset_expectations_svc: Arc<dyn DatasetExpectationsService>,
+        commit_dataset_event: Arc<dyn CommitDatasetEventUseCase>,
+        dataset_ref: odf::DatasetRef,
+        manifest_path: PathBuf,
+    ) -> Self {
+        Self {
+            dataset_registry,
+            dataset_expectations_svc,
+            commit_dataset_event,
+            dataset_ref,
+            manifest_path,
+        }
+    }
+
+    fn read_manifest(&self) -> Result<DatasetExpectations, CLIError> {
+        let file = std::fs::File::open(&self.manifest_path).map_err(|e| {
+            CLIError::usage_error(format!(
+                "Unable to read manifest {}: {e}",
+                self.manifest_path.display()
+            ))
+        })?;
+
+        let manifest: Manifest<DatasetExpectations> = serde_yaml::from_reader(file)
+            .map_err(|e| CLIError::usage_error(format!("Invalid manifest: {e}")))?;
+
+        if manifest.kind != "DatasetExpectations" {
+            return Err(CLIError::usage_error(format!(
+                "Expected a DatasetExpectations manifest but got {}",
+                manifest.kind
+            )));
+        }
+
+        Ok(manifest.content)
+    }
+}
+
+#[async_trait::async_trait(?Send)]
+impl Command for ExpectationsSetCommand {
+    async fn run(&mut self) -> Result<(), CLIError> {
+        let expectations = self.read_manifest()?.expectations;
+
+        let dataset_handle = self
+            .dataset_registry
+            .resolve_dataset_handle_by_ref(&self.dataset_ref)
+            .await?;
+
+        let target = self
+            .dataset_registry
+            .get_dataset_by_handle(&dataset_handle)
+            .await;
+
+        let num_expectations = expectations.len();
+
+        let event = self
+            .dataset_expectations_svc
+            .prepare_set_expectations(&target, expectations)
+            .await
+            .map_err(|e| match e {
+                SetDataExpectationsError::Invalid(e) => CLIError::failure(e),
+                SetDataExpectationsError::Internal(e) => CLIError::critical(e),
+            })?;
+
+        let Some(event) = event else {
+            eprintln!(
+                "{}",
+                style(format!(
+                    "Expectations of {} are up to date",
+                    dataset_handle.alias
+                ))
+                .yellow()
+            );
+            return Ok(());
+        };
+
+        self.commit_dataset_event
+            .execute(&dataset_handle, event)
+            .await
+            .map_err(|e| match e {
+                odf::dataset::CommitError::Access(e) => CLIError::failure(e),
+                odf::dataset::CommitError::MetadataAppendError(e) => CLIError::failure(e),
+                e => CLIError::critical(e),
+            })?;
+
+        eprintln!(
+            "{}",
+            style(format!(
+                "Set {num_expectations} expectation(s) on {}",
+                dataset_handle.alias
+            ))
+            .green()
+        );
+
+        Ok(())
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
@@ -10,7 +10,15 @@
 use std::path::PathBuf;
 use std::sync::Arc;
 
-use kamu::domain::{ExportFormat, ExportOptions, ExportService, QueryService};
+use kamu::domain::{
+    DatasetRegistry,
+    DatasetRegistryExt,
+    ExportFormat,
+    ExportOptions,
+    ExportService,
+    QueryService,
+};
+use url::Url;
 
 use crate::{CLIError, Command};
 
@@ -19,6 +27,7 @@ use crate::{CLIError, Command};
 pub struct ExportCommand {
     export_service: Arc<dyn ExportService>,
     query_service: Arc<dyn QueryService>,
+    dataset_registry: Arc<dyn DatasetRegistry>,
     dataset_ref: odf::DatasetRef,
     output_path: Option<PathBuf>,
     output_format: ExportFormat,
@@ -30,6 +39,7 @@ impl ExportCommand {
     pub fn new(
         export_service: Arc<dyn ExportService>,
         query_service: Arc<dyn QueryService>,
+        dataset_registry: Arc<dyn DatasetRegistry>,
         dataset_ref: odf::DatasetRef,
         output_path: Option<PathBuf>,
         output_format: ExportFormat,
@@ -39,6 +49,7 @@ impl ExportCommand {
         Self {
             export_service,
             query_service,
+            dataset_registry,
             dataset_ref,
             output_path,
             output_format,
@@ -46,21 +57,91 @@ impl ExportCommand {
             quiet,
         }
     }
+
+    fn default_output_path(&self) -> PathBuf {
+        let mut default_path: PathBuf = PathBuf::new();
+        default_path.push(self.dataset_ref.to_string());
+        default_path.push(""); // ensure trailing slash to have it as a dir
+        default_path
+    }
+
+    /// Table location can be either an S3 URL or a local directory
+    fn iceberg_table_url(&self) -> Result<Url, CLIError> {
+        let output_path = self
+            .output_path
+            .clone()
+            .unwrap_or_else(|| self.default_output_path());
+
+        let output_path_str = output_path.to_string_lossy();
+        if let Ok(url) = Url::parse(&output_path_str)
+            && matches!(url.scheme(), "file" | "s3" | "s3+http" | "s3+https")
+        {
+            return Ok(url);
+        }
+
+        let output_path = std::path::absolute(&output_path).map_err(|e| {
+            CLIError::usage_error(format!("Invalid path {}: {e}", output_path.display()))
+        })?;
+        Url::from_directory_path(&output_path)
+            .map_err(|_| CLIError::usage_error(format!("Invalid path {}", output_path.display())))
+    }
+
+    async fn export_iceberg(&self) -> Result<(), CLIError> {
+        if self.records_per_file.is_some() {
+            return Err(CLIError::usage_error(
+                "Iceberg tables contain one data file per block, --records-per-file is not \
+                 supported",
+            ));
+        }
+
+        let table_url = self.iceberg_table_url()?;
+
+        let target = self
+            .dataset_registry
+            .get_dataset_by_ref(&self.dataset_ref)
+            .await?;
+
+        let result = self
+            .export_service
+            .export_to_iceberg(target, &table_url)
+            .await?;
+
+        if !self.quiet {
+            if result.num_snapshots == 0 {
+                eprintln!("{}", console::style("Iceberg table is up-to-date").yellow());
+            } else {
+                eprintln!(
+                    "{} {} {} {} {}",
+                    console::style("Exported").green(),
+                    console::style(format!("{}", result.num_records))
+                        .green()
+                        .bold(),
+                    console::style("rows in").green(),
+                    console::style(format!("{}", result.num_snapshots))
+                        .green()
+                        .bold(),
+                    console::style("snapshots").green()
+                );
+            }
+        }
+        Ok(())
+    }
 }
 
 #[async_trait::async_trait(?Send)]
 impl Command for ExportCommand {
     async fn run(&mut self) -> Result<(), CLIError> {
+        if let ExportFormat::Iceberg = self.output_format {
+            return self.export_iceberg().await;
+        }
+
         let df = self
             .query_service
             .get_data(&self.dataset_ref)
             .await
             .map_err(CLIError::failure)?;
 
-        let mut default_path: PathBuf = PathBuf::new();
-        default_path.push(self.dataset_ref.to_string());
-        default_path.push(""); // ensure trailing slash to have it as a dir
-
+        let default_path = self.default_output_path();
         let output_path = match self.output_path {
             Some(ref path) => path,
             None => &default_path,
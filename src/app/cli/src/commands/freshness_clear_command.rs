@@ -0,0 +1,84 @@
+// Copyright Kamu Data, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::sync::Arc;
+
+use console::style;
+use kamu::domain::*;
+
+use super::{CLIError, Command};
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+pub struct FreshnessClearCommand {
+    dataset_registry: Arc<dyn DatasetRegistry>,
+    dataset_action_authorizer: Arc<dyn auth::DatasetActionAuthorizer>,
+    dataset_freshness_svc: Arc<dyn DatasetFreshnessService>,
+    dataset_ref: odf::DatasetRef,
+}
+
+impl FreshnessClearCommand {
+    pub fn new(
+        dataset_registry: Arc<dyn DatasetRegistry>,
+        dataset_action_authorizer: Arc<dyn auth::DatasetActionAuthorizer>,
+        dataset_freshness_svc: Arc<dyn DatasetFreshnessService>,
+        dataset_ref: odf::DatasetRef,
+    ) -> Self {
+        Self {
+            dataset_registry,
+            dataset_action_authorizer,
+            dataset_freshness_svc,
+            dataset_ref,
+        }
+    }
+}
+
+#[async_trait::async_trait(?Send)]
+impl Command for FreshnessClearCommand {
+    async fn run(&mut self) -> Result<(), CLIError> {
+        let dataset_handle = self
+            .dataset_registry
+            .resolve_dataset_handle_by_ref(&self.dataset_ref)
+            .await?;
+
+        self.dataset_action_authorizer
+            .check_action_allowed(&dataset_handle.id, auth::DatasetAction::Write)
+            .await
+            .map_err(|e| match e {
+                auth::DatasetActionUnauthorizedError::Access(e) => CLIError::failure(e),
+                auth::DatasetActionUnauthorizedError::Internal(e) => CLIError::critical(e),
+            })?;
+
+        let target = self
+            .dataset_registry
+            .get_dataset_by_handle(&dataset_handle)
+            .await;
+
+        self.dataset_freshness_svc
+            .set_sla(&target, None)
+            .await
+            .map_err(|e| match e {
+                SetFreshnessSlaError::Invalid(e) => CLIError::usage_error_from(e),
+                SetFreshnessSlaError::Internal(e) => CLIError::critical(e),
+            })?;
+
+        eprintln!(
+            "{}",
+            style(format!(
+                "Removed freshness SLA from {}",
+                dataset_handle.alias
+            ))
+            .green()
+        );
+
+        Ok(())
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
@@ -0,0 +1,106 @@
+// Copyright Kamu Data, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::sync::Arc;
+
+use chrono::{DateTime, Duration, Utc};
+use chrono_humanize::{Accuracy, HumanTime, Tense};
+use kamu::domain::*;
+
+use super::{CLIError, Command};
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+pub struct FreshnessGetCommand {
+    dataset_registry: Arc<dyn DatasetRegistry>,
+    dataset_action_authorizer: Arc<dyn auth::DatasetActionAuthorizer>,
+    dataset_freshness_svc: Arc<dyn DatasetFreshnessService>,
+    dataset_ref: odf::DatasetRef,
+}
+
+impl FreshnessGetCommand {
+    pub fn new(
+        dataset_registry: Arc<dyn DatasetRegistry>,
+        dataset_action_authorizer: Arc<dyn auth::DatasetActionAuthorizer>,
+        dataset_freshness_svc: Arc<dyn DatasetFreshnessService>,
+        dataset_ref: odf::DatasetRef,
+    ) -> Self {
+        Self {
+            dataset_registry,
+            dataset_action_authorizer,
+            dataset_freshness_svc,
+            dataset_ref,
+        }
+    }
+
+    fn humanize_duration(duration: Duration) -> String {
+        HumanTime::from(duration).to_text_en(Accuracy::Precise, Tense::Present)
+    }
+
+    fn humanize_time(time: Option<DateTime<Utc>>) -> String {
+        match time {
+            Some(time) => format!(
+                "{} ({})",
+                time.to_rfc3339(),
+                HumanTime::from(time - Utc::now())
+            ),
+            None => "-".to_string(),
+        }
+    }
+}
+
+#[async_trait::async_trait(?Send)]
+impl Command for FreshnessGetCommand {
+    async fn run(&mut self) -> Result<(), CLIError> {
+        let dataset_handle = self
+            .dataset_registry
+            .resolve_dataset_handle_by_ref(&self.dataset_ref)
+            .await?;
+
+        self.dataset_action_authorizer
+            .check_action_allowed(&dataset_handle.id, auth::DatasetAction::Read)
+            .await
+            .map_err(|e| match e {
+                auth::DatasetActionUnauthorizedError::Access(e) => CLIError::failure(e),
+                auth::DatasetActionUnauthorizedError::Internal(e) => CLIError::critical(e),
+            })?;
+
+        let target = self
+            .dataset_registry
+            .get_dataset_by_handle(&dataset_handle)
+            .await;
+
+        let freshness = self.dataset_freshness_svc.get_freshness(&target).await?;
+
+        let Some(sla) = freshness.sla else {
+            eprintln!("Dataset {} has no freshness SLA", dataset_handle.alias);
+            return Ok(());
+        };
+
+        println!(
+            "Max update interval: {}",
+            Self::humanize_duration(sla.max_update_interval)
+        );
+        println!(
+            "Max watermark lag: {}",
+            sla.max_watermark_lag
+                .map_or_else(|| "-".to_string(), Self::humanize_duration)
+        );
+        println!(
+            "Last updated: {}",
+            Self::humanize_time(freshness.last_updated_at)
+        );
+        println!("Watermark: {}", Self::humanize_time(freshness.watermark));
+        println!("Status: {}", freshness.status);
+
+        Ok(())
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
@@ -26,6 +26,7 @@ pub struct ListCommand {
     dataset_registry: Arc<dyn DatasetRegistry>,
     remote_alias_reg: Arc<dyn RemoteAliasesRegistry>,
     rebac_service: Arc<dyn kamu_auth_rebac::RebacService>,
+    freshness_service: Arc<dyn DatasetFreshnessService>,
     current_account: accounts::CurrentAccountIndication,
     related_account: accounts::RelatedAccountIndication,
     output_config: Arc<OutputConfig>,
@@ -38,6 +39,7 @@ impl ListCommand {
         dataset_registry: Arc<dyn DatasetRegistry>,
         remote_alias_reg: Arc<dyn RemoteAliasesRegistry>,
         rebac_service: Arc<dyn kamu_auth_rebac::RebacService>,
+        freshness_service: Arc<dyn DatasetFreshnessService>,
         current_account: accounts::CurrentAccountIndication,
         related_account: accounts::RelatedAccountIndication,
         output_config: Arc<OutputConfig>,
@@ -48,6 +50,7 @@ impl ListCommand {
             dataset_registry,
             remote_alias_reg,
             rebac_service,
+            freshness_service,
             current_account,
             related_account,
             output_config,
@@ -95,7 +98,12 @@ impl ListCommand {
         })
     }
 
-    fn column_formats(&self, show_owners: bool, show_visibility: bool) -> Vec<ColumnFormat> {
+    fn column_formats(
+        &self,
+        show_owners: bool,
+        show_visibility: bool,
+        show_freshness: bool,
+    ) -> Vec<ColumnFormat> {
         let mut cols: Vec<ColumnFormat> = Vec::new();
         if self.detail_level > 0 {
             cols.push(ColumnFormat::new().with_style_spec("l")); // id
@@ -145,6 +153,9 @@ impl ListCommand {
                     .with_value_fmt_t(Self::humanize_relative_date),
             );
         }
+        if show_freshness {
+            cols.push(ColumnFormat::new().with_style_spec("c")); // freshness
+        }
         cols
     }
 
@@ -152,6 +163,7 @@ impl ListCommand {
         &self,
         show_owners: bool,
         show_visibility: bool,
+        show_freshness: bool,
     ) -> Vec<datafusion::arrow::datatypes::Field> {
         use datafusion::arrow::datatypes::{DataType, Field, TimeUnit};
 
@@ -189,6 +201,9 @@ impl ListCommand {
                 true,
             ));
         }
+        if show_freshness {
+            fields.push(Field::new("Freshness", DataType::Utf8, true));
+        }
         fields
     }
 
@@ -237,19 +252,6 @@ impl Command for ListCommand {
         let show_owners = self.current_account.is_explicit() || self.related_account.is_explicit();
         let show_visibility = self.tenancy_config == TenancyConfig::MultiTenant;
 
-        // ToDo use Output writer trait
-        let records_format = RecordsFormat::new()
-            .with_default_column_format(ColumnFormat::default().with_null_value("-"))
-            .with_column_formats(self.column_formats(show_owners, show_visibility));
-
-        let schema = Arc::new(Schema::new(
-            self.schema_fields(show_owners, show_visibility),
-        ));
-
-        let mut writer = self
-            .output_config
-            .get_records_writer(&schema, records_format);
-
         let mut id: Vec<String> = Vec::new();
         let mut name: Vec<String> = Vec::new();
         let mut owner: Vec<String> = Vec::new();
@@ -261,6 +263,7 @@ impl Command for ListCommand {
         let mut blocks: Vec<u64> = Vec::new();
         let mut size: Vec<u64> = Vec::new();
         let mut watermark: Vec<Option<i64>> = Vec::new();
+        let mut freshness: Vec<Option<String>> = Vec::new();
 
         let mut datasets: Vec<_> = self.stream_datasets().try_collect().await?;
         datasets.sort_by(|a, b| a.alias.cmp(&b.alias));
@@ -279,6 +282,16 @@ impl Command for ListCommand {
 
         for hdl in &datasets {
             let resolved_dataset = self.dataset_registry.get_dataset_by_handle(hdl).await;
+
+            let dataset_freshness = self
+                .freshness_service
+                .get_freshness(&resolved_dataset)
+                .await?;
+            freshness.push(match dataset_freshness.status {
+                FreshnessStatus::Undefined => None,
+                status => Some(status.to_string()),
+            });
+
             let current_head = resolved_dataset
                 .as_metadata_chain()
                 .resolve_ref(&odf::BlockRef::Head)
@@ -335,6 +348,24 @@ impl Command for ListCommand {
             }
         }
 
+        // Freshness column is only shown when at least one dataset declares an SLA
+        let show_freshness = freshness.iter().any(Option::is_some);
+
+        // ToDo use Output writer trait
+        let records_format = RecordsFormat::new()
+            .with_default_column_format(ColumnFormat::default().with_null_value("-"))
+            .with_column_formats(self.column_formats(show_owners, show_visibility, show_freshness));
+
+        let schema = Arc::new(Schema::new(self.schema_fields(
+            show_owners,
+            show_visibility,
+            show_freshness,
+        )));
+
+        let mut writer = self
+            .output_config
+            .get_records_writer(&schema, records_format);
+
         let mut columns: Vec<ArrayRef> = Vec::new();
 
         if self.detail_level > 0 {
@@ -364,6 +395,9 @@ impl Command for ListCommand {
                 TimestampMicrosecondArray::from(watermark).with_timezone_utc(),
             ));
         }
+        if show_freshness {
+            columns.push(Arc::new(StringArray::from(freshness)));
+        }
 
         let records = RecordBatch::try_new(schema, columns).unwrap();
 
@@ -35,6 +35,7 @@ pub enum MetadataLogOutputFormat {
 pub struct LogCommand {
     dataset_registry: Arc<dyn DatasetRegistry>,
     dataset_action_authorizer: Arc<dyn auth::DatasetActionAuthorizer>,
+    dataset_expectations_svc: Arc<dyn DatasetExpectationsService>,
     dataset_ref: odf::DatasetRef,
     output_format: Option<MetadataLogOutputFormat>,
     filter: Option<String>,
@@ -46,6 +47,7 @@ impl LogCommand {
     pub fn new(
         dataset_registry: Arc<dyn DatasetRegistry>,
         dataset_action_authorizer: Arc<dyn auth::DatasetActionAuthorizer>,
+        dataset_expectations_svc: Arc<dyn DatasetExpectationsService>,
         dataset_ref: odf::DatasetRef,
         output_format: Option<MetadataLogOutputFormat>,
         filter: Option<String>,
@@ -55,6 +57,7 @@ impl LogCommand {
         Self {
             dataset_registry,
             dataset_action_authorizer,
+            dataset_expectations_svc,
             dataset_ref,
             output_format,
             filter,
@@ -99,22 +102,6 @@ impl Command for LogCommand {
             .try_collect()
             .await?;
 
-        let mut renderer: Box<dyn MetadataRenderer> = match (
-            self.output_format,
-            self.output_config.is_tty && self.output_config.verbosity_level == 0,
-        ) {
-            (None | Some(MetadataLogOutputFormat::Shell), true) => {
-                Box::new(PagedAsciiRenderer::new(id_to_alias_lookup, self.limit))
-            }
-            (None | Some(MetadataLogOutputFormat::Shell), false) => {
-                Box::new(AsciiRenderer::new(id_to_alias_lookup, self.limit))
-            }
-            (Some(MetadataLogOutputFormat::Yaml), true) => {
-                Box::new(PagedYamlRenderer::new(self.limit))
-            }
-            (Some(MetadataLogOutputFormat::Yaml), false) => Box::new(YamlRenderer::new(self.limit)),
-        };
-
         let dataset_handle = self
             .dataset_registry
             .resolve_dataset_handle_by_ref(&self.dataset_ref)
@@ -133,6 +120,29 @@ impl Command for LogCommand {
             .get_dataset_by_handle(&dataset_handle)
             .await;
 
+        let expectation_reports = ExpectationReportsLookup {
+            dataset_expectations_svc: self.dataset_expectations_svc.clone(),
+            target: resolved_dataset.clone(),
+        };
+
+        let mut renderer: Box<dyn MetadataRenderer> = match (
+            self.output_format,
+            self.output_config.is_tty && self.output_config.verbosity_level == 0,
+        ) {
+            (None | Some(MetadataLogOutputFormat::Shell), true) => Box::new(
+                PagedAsciiRenderer::new(id_to_alias_lookup, expectation_reports, self.limit),
+            ),
+            (None | Some(MetadataLogOutputFormat::Shell), false) => Box::new(AsciiRenderer::new(
+                id_to_alias_lookup,
+                expectation_reports,
+                self.limit,
+            )),
+            (Some(MetadataLogOutputFormat::Yaml), true) => {
+                Box::new(PagedYamlRenderer::new(self.limit))
+            }
+            (Some(MetadataLogOutputFormat::Yaml), false) => Box::new(YamlRenderer::new(self.limit)),
+        };
+
         use odf::dataset::{MetadataChainExt, TryStreamExtExt};
 
         let blocks = Box::pin(
@@ -161,15 +171,45 @@ trait MetadataRenderer {
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
 
+#[derive(Clone)]
+struct ExpectationReportsLookup {
+    dataset_expectations_svc: Arc<dyn DatasetExpectationsService>,
+    target: ResolvedDataset,
+}
+
+impl ExpectationReportsLookup {
+    async fn get(
+        &self,
+        hash: &odf::Multihash,
+        block: &odf::MetadataBlock,
+    ) -> Result<Option<DataExpectationsReport>, CLIError> {
+        match &block.event {
+            odf::MetadataEvent::AddData(_) | odf::MetadataEvent::ExecuteTransform(_) => Ok(self
+                .dataset_expectations_svc
+                .get_report(&self.target, hash)
+                .await?),
+            _ => Ok(None),
+        }
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
 struct AsciiRenderer {
     id_to_name_lookup: BTreeMap<odf::DatasetID, odf::DatasetAlias>,
+    expectation_reports: ExpectationReportsLookup,
     limit: usize,
 }
 
 impl AsciiRenderer {
-    fn new(id_to_name_lookup: BTreeMap<odf::DatasetID, odf::DatasetAlias>, limit: usize) -> Self {
+    fn new(
+        id_to_name_lookup: BTreeMap<odf::DatasetID, odf::DatasetAlias>,
+        expectation_reports: ExpectationReportsLookup,
+        limit: usize,
+    ) -> Self {
         Self {
             id_to_name_lookup,
+            expectation_reports,
             limit,
         }
     }
@@ -186,8 +226,10 @@ impl AsciiRenderer {
         let mut buf = Vec::new();
 
         while let Some((hash, block)) = blocks.try_next().await? {
+            let expectations_report = self.expectation_reports.get(&hash, &block).await?;
+
             buf.clear();
-            self.render_block(&mut buf, &hash, &block)?;
+            self.render_block(&mut buf, &hash, &block, expectations_report.as_ref())?;
             writeln!(buf)?;
 
             output.write_all(&buf)?;
@@ -203,6 +245,7 @@ impl AsciiRenderer {
         output: &mut impl Write,
         hash: &odf::Multihash,
         block: &odf::MetadataBlock,
+        expectations_report: Option<&DataExpectationsReport>,
     ) -> Result<(), std::io::Error> {
         self.render_header(output, hash, block)?;
         self.render_property(
@@ -408,6 +451,57 @@ impl AsciiRenderer {
             }
         }
 
+        if let Some(report) = expectations_report {
+            self.render_expectations_report(output, 0, report)?;
+        }
+
+        Ok(())
+    }
+
+    fn render_expectations_report(
+        &self,
+        output: &mut impl Write,
+        indent: i32,
+        report: &DataExpectationsReport,
+    ) -> Result<(), std::io::Error> {
+        let num_failed = report.num_failed();
+
+        self.render_section(output, indent, "Expectations")?;
+        self.render_property(
+            output,
+            indent + 1,
+            "Passed",
+            style(report.results.len() - num_failed).green(),
+        )?;
+        self.render_property(
+            output,
+            indent + 1,
+            "Failed",
+            if num_failed == 0 {
+                style(num_failed)
+            } else {
+                style(num_failed).red()
+            },
+        )?;
+        if report.num_quarantined != 0 {
+            self.render_property(
+                output,
+                indent + 1,
+                "NumQuarantined",
+                style(report.num_quarantined).yellow(),
+            )?;
+        }
+        if let Some(quarantined_data) = &report.quarantined_data {
+            self.render_property(output, indent + 1, "QuarantinedData", quarantined_data)?;
+        }
+        for res in report.results.iter().filter(|r| !r.passed) {
+            self.render_property(
+                output,
+                indent + 1,
+                &res.name,
+                format!("{} violations ({:?})", res.num_violations, res.action),
+            )?;
+        }
         Ok(())
     }
 
@@ -506,13 +600,19 @@ impl MetadataRenderer for AsciiRenderer {
 
 struct PagedAsciiRenderer {
     id_to_name_lookup: BTreeMap<odf::DatasetID, odf::DatasetAlias>,
+    expectation_reports: ExpectationReportsLookup,
     limit: usize,
 }
 
 impl PagedAsciiRenderer {
-    fn new(id_to_name_lookup: BTreeMap<odf::DatasetID, odf::DatasetAlias>, limit: usize) -> Self {
+    fn new(
+        id_to_name_lookup: BTreeMap<odf::DatasetID, odf::DatasetAlias>,
+        expectation_reports: ExpectationReportsLookup,
+        limit: usize,
+    ) -> Self {
         Self {
             id_to_name_lookup,
+            expectation_reports,
             limit,
         }
     }
@@ -531,7 +631,11 @@ impl MetadataRenderer for PagedAsciiRenderer {
             .unwrap();
         pager.set_prompt(dataset_handle.alias.to_string()).unwrap();
 
-        let renderer = AsciiRenderer::new(self.id_to_name_lookup.clone(), self.limit);
+        let renderer = AsciiRenderer::new(
+            self.id_to_name_lookup.clone(),
+            self.expectation_reports.clone(),
+            self.limit,
+        );
         let mut write = WritePager(&mut pager);
         renderer.render_blocks(&mut write, blocks).await?;
 
@@ -0,0 +1,83 @@
+// Copyright Kamu Data, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::sync::Arc;
+
+use url::Url;
+
+use crate::{odf_server, CLIError, Command};
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+pub struct LoginRegisterCommand {
+    login_service: Arc<odf_server::LoginService>,
+    server: Option<Url>,
+    account_name: odf::AccountName,
+    email: String,
+    password: String,
+}
+
+impl LoginRegisterCommand {
+    pub fn new(
+        login_service: Arc<odf_server::LoginService>,
+        server: Option<Url>,
+        account_name: odf::AccountName,
+        email: String,
+        password: String,
+    ) -> Self {
+        Self {
+            login_service,
+            server,
+            account_name,
+            email,
+            password,
+        }
+    }
+
+    fn get_server_url(&self) -> Url {
+        self.server
+            .clone()
+            .unwrap_or_else(|| Url::parse(odf_server::DEFAULT_ODF_BACKEND_URL).unwrap())
+    }
+}
+
+#[async_trait::async_trait(?Send)]
+impl Command for LoginRegisterCommand {
+    async fn run(&mut self) -> Result<(), CLIError> {
+        let odf_server_backend_url = self.get_server_url();
+
+        self.login_service
+            .register(
+                &odf_server_backend_url,
+                &self.account_name,
+                &self.email,
+                &self.password,
+            )
+            .await
+            .map_err(|e| match e {
+                odf_server::LoginError::AccessFailed(e) => CLIError::usage_error(e.to_string()),
+                odf_server::LoginError::Internal(e) => CLIError::failure(e),
+            })?;
+
+        eprintln!(
+            "{}: {}",
+            console::style("Account registered").green().bold(),
+            odf_server_backend_url
+        );
+        eprintln!(
+            "Confirmation email was sent to {}, complete the registration with: kamu login \
+             verify-email <token>",
+            self.email
+        );
+
+        Ok(())
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
@@ -19,7 +19,9 @@ use crate::{odf_server, CLIError, Command};
 #[derive(Debug)]
 pub enum LoginSilentMode {
     OAuth(LoginSilentModeOAuth),
+    Oidc(LoginSilentModeOidc),
     Password(LoginSilentModePassword),
+    VerifyEmail(LoginSilentModeVerifyEmail),
 }
 
 #[derive(Debug)]
@@ -28,12 +30,24 @@ pub struct LoginSilentModeOAuth {
     pub access_token: String,
 }
 
+#[derive(Debug)]
+pub struct LoginSilentModeOidc {
+    pub issuer_url: Url,
+    pub client_id: String,
+    pub scopes: Vec<String>,
+}
+
 #[derive(Debug)]
 pub struct LoginSilentModePassword {
     pub login: String,
     pub password: String,
 }
 
+#[derive(Debug)]
+pub struct LoginSilentModeVerifyEmail {
+    pub verification_token: String,
+}
+
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
 
 pub struct LoginSilentCommand {
@@ -92,6 +106,38 @@ impl LoginSilentCommand {
                     })?
             }
 
+            LoginSilentMode::Oidc(oidc_mode) => self
+                .login_service
+                .login_oidc_device(
+                    &odf_server_backend_url,
+                    &oidc_mode.issuer_url,
+                    &oidc_mode.client_id,
+                    &oidc_mode.scopes,
+                    |device_authorization| {
+                        let verification_uri = device_authorization
+                            .verification_uri_complete
+                            .as_ref()
+                            .unwrap_or(&device_authorization.verification_uri);
+                        eprintln!(
+                            "{}\n  {}",
+                            console::style("Please open this URL on any device to login:")
+                                .green()
+                                .bold(),
+                            verification_uri,
+                        );
+                        eprintln!(
+                            "{} {}",
+                            console::style("and enter the code:").green().bold(),
+                            console::style(&device_authorization.user_code).bold(),
+                        );
+                    },
+                )
+                .await
+                .map_err(|e| match e {
+                    odf_server::LoginError::AccessFailed(e) => CLIError::usage_error(e.to_string()),
+                    odf_server::LoginError::Internal(e) => CLIError::failure(e),
+                })?,
+
             LoginSilentMode::Password(password_mode) => self
                 .login_service
                 .login_password(
@@ -104,6 +150,18 @@ impl LoginSilentCommand {
                     odf_server::LoginError::AccessFailed(e) => CLIError::usage_error(e.to_string()),
                     odf_server::LoginError::Internal(e) => CLIError::failure(e),
                 })?,
+
+            LoginSilentMode::VerifyEmail(verify_email_mode) => self
+                .login_service
+                .verify_email(
+                    &odf_server_backend_url,
+                    &verify_email_mode.verification_token,
+                )
+                .await
+                .map_err(|e| match e {
+                    odf_server::LoginError::AccessFailed(e) => CLIError::usage_error(e.to_string()),
+                    odf_server::LoginError::Internal(e) => CLIError::failure(e),
+                })?,
         };
 
         // Save access token and associate it with backend URL only,
@@ -171,6 +229,11 @@ impl Command for LoginSilentCommand {
     async fn run(&mut self) -> Result<(), CLIError> {
         let odf_server_backend_url = self.get_server_url();
 
+        // Confirming an email always issues a fresh token for the new account
+        if let LoginSilentMode::VerifyEmail(_) = &self.mode {
+            return self.new_login(odf_server_backend_url).await;
+        }
+
         // Validate token and trigger browser login flow if needed
         if let Some(token_find_report) = self
             .access_token_registry_service
@@ -17,7 +17,13 @@ mod complete_command;
 mod completions_command;
 mod config_command;
 mod delete_command;
+mod diff_command;
+mod expectations_get_command;
+mod expectations_set_command;
 mod export_command;
+mod freshness_clear_command;
+mod freshness_get_command;
+mod freshness_set_command;
 mod gc_command;
 mod ingest_command;
 mod init_command;
@@ -27,6 +33,7 @@ mod inspect_schema_command;
 mod list_command;
 mod log_command;
 mod login_command;
+mod login_register_command;
 mod login_silent_command;
 mod logout_command;
 mod new_dataset_command;
@@ -53,6 +60,8 @@ mod system_e2e_command;
 mod system_generate_token_command;
 mod system_info_command;
 mod system_ipfs_add_command;
+mod system_task_logs_command;
+mod system_task_worker_command;
 mod tail_command;
 mod ui_command;
 mod upgrade_workspace_command;
@@ -67,7 +76,13 @@ pub use complete_command::*;
 pub use completions_command::*;
 pub use config_command::*;
 pub use delete_command::*;
+pub use diff_command::*;
+pub use expectations_get_command::*;
+pub use expectations_set_command::*;
 pub use export_command::*;
+pub use freshness_clear_command::*;
+pub use freshness_get_command::*;
+pub use freshness_set_command::*;
 pub use gc_command::*;
 pub use ingest_command::*;
 pub use init_command::*;
@@ -77,6 +92,7 @@ pub use inspect_schema_command::*;
 pub use list_command::*;
 pub use log_command::*;
 pub use login_command::*;
+pub use login_register_command::*;
 pub use login_silent_command::*;
 pub use logout_command::*;
 pub use new_dataset_command::*;
@@ -103,6 +119,8 @@ pub use system_e2e_command::*;
 pub use system_generate_token_command::*;
 pub use system_info_command::*;
 pub use system_ipfs_add_command::*;
+pub use system_task_logs_command::*;
+pub use system_task_worker_command::*;
 pub use tail_command::*;
 pub use ui_command::*;
 pub use upgrade_workspace_command::*;
@@ -10,6 +10,7 @@
 use std::sync::Arc;
 
 use container_runtime::ContainerRuntime;
+use kamu::domain::EngineRegistryConfig;
 use kamu::EngineProvisionerLocalConfig;
 
 use super::{CLIError, Command};
@@ -18,6 +19,7 @@ use crate::config::JupyterConfig;
 pub struct PullImagesCommand {
     container_runtime: Arc<ContainerRuntime>,
     engine_config: Arc<EngineProvisionerLocalConfig>,
+    engine_registry_config: Arc<EngineRegistryConfig>,
     jupyter_config: Arc<JupyterConfig>,
     list_only: bool,
 }
@@ -26,12 +28,14 @@ impl PullImagesCommand {
     pub fn new(
         container_runtime: Arc<ContainerRuntime>,
         engine_config: Arc<EngineProvisionerLocalConfig>,
+        engine_registry_config: Arc<EngineRegistryConfig>,
         jupyter_config: Arc<JupyterConfig>,
         list_only: bool,
     ) -> Self {
         Self {
             container_runtime,
             engine_config,
+            engine_registry_config,
             jupyter_config,
             list_only,
         }
@@ -49,6 +53,13 @@ impl Command for PullImagesCommand {
             self.jupyter_config.image.as_ref().unwrap().as_str(),
             self.jupyter_config.livy_image.as_ref().unwrap().as_str(),
         ];
+        images.extend(
+            self.engine_registry_config
+                .custom_engines
+                .iter()
+                .filter(|spec| !spec.shadows_builtin_engine())
+                .map(|spec| spec.image.as_str()),
+        );
 
         images.sort_unstable();
         images.dedup();
@@ -12,21 +12,34 @@ use std::sync::Arc;
 use std::time::Duration;
 
 use container_runtime::ContainerRuntime;
+use datafusion::common::instant::Instant;
+use datafusion::datasource::MemTable;
+use datafusion::prelude::SessionContext;
 use domain::ExportOptions;
+use futures::TryStreamExt;
 use internal_error::*;
 use itertools::Itertools;
-use kamu::domain::{ExportFormat, ExportService, QueryOptions, QueryService};
+use kamu::domain::engine::RawQueryRequestExt;
+use kamu::domain::{
+    EngineProvisioner,
+    EngineRegistryConfig,
+    ExportFormat,
+    ExportService,
+    QueryOptions,
+    QueryService,
+};
 use kamu::*;
 use kamu_datafusion_cli::exec;
 use kamu_datafusion_cli::print_format::PrintFormat;
 use kamu_datafusion_cli::print_options::{MaxRows, PrintOptions};
 use lazy_static::lazy_static;
+use url::Url;
 
 use super::common::PullImageProgress;
 use super::{CLIError, Command};
-use crate::explore::SqlShellImpl;
+use crate::explore::{RemoteQueryResult, RemoteSqlClient, SqlShellImpl};
 use crate::output::*;
-use crate::WorkspaceLayout;
+use crate::{odf_server, WorkspaceLayout};
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
 
@@ -55,9 +68,12 @@ pub struct SqlShellCommand {
     output_config: Arc<OutputConfig>,
     container_runtime: Arc<ContainerRuntime>,
     export_service: Arc<dyn ExportService>,
+    engine_provisioner: Arc<dyn EngineProvisioner>,
+    engine_registry_config: Arc<EngineRegistryConfig>,
+    access_token_registry_service: Arc<odf_server::AccessTokenRegistryService>,
     command: Option<String>,
     url: Option<String>,
-    engine: Option<SqlShellEngine>,
+    engine: Option<String>,
     output_path: Option<PathBuf>,
     records_per_file: Option<usize>,
 }
@@ -70,9 +86,12 @@ impl SqlShellCommand {
         output_config: Arc<OutputConfig>,
         container_runtime: Arc<ContainerRuntime>,
         export_service: Arc<dyn ExportService>,
+        engine_provisioner: Arc<dyn EngineProvisioner>,
+        engine_registry_config: Arc<EngineRegistryConfig>,
+        access_token_registry_service: Arc<odf_server::AccessTokenRegistryService>,
         command: Option<String>,
         url: Option<String>,
-        engine: Option<SqlShellEngine>,
+        engine: Option<String>,
         output_path: Option<PathBuf>,
         records_per_file: Option<usize>,
     ) -> Self {
@@ -83,6 +102,9 @@ impl SqlShellCommand {
             output_config,
             container_runtime,
             export_service,
+            engine_provisioner,
+            engine_registry_config,
+            access_token_registry_service,
             command,
             url,
             engine,
@@ -91,6 +113,14 @@ impl SqlShellCommand {
         }
     }
 
+    /// Returns `None` when a custom engine is selected
+    fn builtin_engine(&self) -> Option<SqlShellEngine> {
+        match &self.engine {
+            None => Some(SqlShellEngine::Datafusion),
+            Some(engine_id) => clap::ValueEnum::from_str(engine_id, true).ok(),
+        }
+    }
+
     async fn run_spark_shell(&self) -> Result<(), CLIError> {
         let sql_shell = SqlShellImpl::new(
             self.container_runtime.clone(),
@@ -165,6 +195,62 @@ impl SqlShellCommand {
         Ok(())
     }
 
+    async fn run_custom_engine_command(
+        &self,
+        engine_id: &str,
+        command: &str,
+    ) -> Result<(), CLIError> {
+        let pull_progress = Arc::new(PullImageProgress::new(self.output_config.clone(), "engine"));
+
+        let engine = self
+            .engine_provisioner
+            .provision_engine(engine_id, Some(pull_progress))
+            .await
+            .map_err(CLIError::failure)?;
+
+        // The query is executed over an empty input, so it is expected to read
+        // the data directly from the sources it references
+        let ctx = self
+            .query_svc
+            .create_session()
+            .await
+            .map_err(CLIError::failure)?;
+        let response = engine
+            .execute_raw_query(RawQueryRequestExt {
+                operation_id: random_names::get_random_name(None, 10),
+                ctx: ctx.clone(),
+                input_data: ctx.read_empty().int_err()?,
+                transform: odf::metadata::Transform::Sql(odf::metadata::TransformSql {
+                    engine: engine_id.to_string(),
+                    version: None,
+                    query: None,
+                    queries: Some(vec![odf::metadata::SqlQueryStep {
+                        alias: None,
+                        query: command.to_string(),
+                    }]),
+                    temporal_tables: None,
+                }),
+            })
+            .await
+            .map_err(CLIError::failure)?;
+
+        let Some(df) = response.output_data else {
+            eprintln!("{}", console::style("Query returned no records").yellow());
+            return Ok(());
+        };
+
+        let mut writer = self
+            .output_config
+            .get_records_writer(df.schema().as_arrow(), RecordsFormat::default());
+
+        let records = df.collect().await.map_err(CLIError::failure)?;
+
+        writer.write_batches(&records)?;
+        writer.finish()?;
+
+        Ok(())
+    }
+
     async fn run_datafusion_cli_command(&self) -> Result<(), CLIError> {
         let mut print_options = PrintOptions {
             format: PrintFormat::Table,
@@ -187,33 +273,229 @@ impl SqlShellCommand {
         Ok(())
     }
 
+    /// Remote Flight SQL endpoint is used when `--url` is specified with the
+    /// DataFusion engine
+    fn remote_url(&self) -> Option<Result<Url, CLIError>> {
+        match (self.builtin_engine(), &self.url) {
+            (Some(SqlShellEngine::Datafusion), Some(url)) => Some(
+                Url::parse(url)
+                    .map_err(|e| CLIError::usage_error(format!("Invalid URL '{url}': {e}"))),
+            ),
+            _ => None,
+        }
+    }
+
+    /// Resolves the Flight SQL endpoint and an access token to use with it.
+    /// ODF node URLs are looked up in the token registry, and the node is then
+    /// asked for the location of its Flight SQL endpoint. The token is only
+    /// passed on if that endpoint is served by the node host over TLS whenever
+    /// the node itself is.
+    async fn resolve_remote_endpoint(&self, url: &Url) -> Result<(Url, Option<String>), CLIError> {
+        match url.scheme() {
+            "grpc" | "grpc+tls" => Ok((url.clone(), None)),
+            "odf+http" | "odf+https" => {
+                let node_url = Url::parse(&url.as_str()["odf+".len()..]).int_err()?;
+
+                let (node_url, access_token) = match self
+                    .access_token_registry_service
+                    .find_by_frontend_or_backend_url(&node_url)
+                {
+                    Some(report) => (report.backend_url, Some(report.access_token.access_token)),
+                    None => (node_url, None),
+                };
+
+                let mut request = reqwest::Client::new().get(node_url.join("info").int_err()?);
+                if let Some(access_token) = &access_token {
+                    request = request.bearer_auth(access_token);
+                }
+
+                let node_info = request
+                    .send()
+                    .await
+                    .and_then(reqwest::Response::error_for_status)
+                    .map_err(CLIError::failure)?
+                    .json::<kamu_adapter_http::general::NodeInfoResponse>()
+                    .await
+                    .map_err(CLIError::failure)?;
+
+                let Some(flight_sql_url) = node_info.flight_sql_url else {
+                    return Err(CLIError::usage_error(format!(
+                        "Node {node_url} does not expose a Flight SQL endpoint"
+                    )));
+                };
+
+                let flight_sql_url = Url::parse(&flight_sql_url).int_err()?;
+
+                // The node's token must not leak to an endpoint on another host or
+                // over a connection less secure than the one to the node itself
+                let forward_access_token = flight_sql_url.host_str() == node_url.host_str()
+                    && (node_url.scheme() != "https" || flight_sql_url.scheme() == "grpc+tls");
+                if access_token.is_some() && !forward_access_token {
+                    tracing::warn!(
+                        %node_url,
+                        %flight_sql_url,
+                        "Not sending the access token to a Flight SQL endpoint that is not served \
+                         securely by the node host",
+                    );
+                }
+                let access_token = access_token.filter(|_| forward_access_token);
+
+                Ok((flight_sql_url, access_token))
+            }
+            scheme => Err(CLIError::usage_error(format!(
+                "Unsupported URL scheme '{scheme}', expected 'odf+http', 'odf+https', 'grpc' or \
+                 'grpc+tls'"
+            ))),
+        }
+    }
+
+    async fn connect_remote(&self, url: &Url) -> Result<RemoteSqlClient, CLIError> {
+        let (flight_sql_url, access_token) = self.resolve_remote_endpoint(url).await?;
+        tracing::info!(%url, %flight_sql_url, "Connecting to remote Flight SQL endpoint");
+
+        RemoteSqlClient::connect(&flight_sql_url, access_token)
+            .await
+            .map_err(CLIError::failure)
+    }
+
+    async fn run_remote_command(&self, url: &Url, command: &str) -> Result<(), CLIError> {
+        let mut client = self.connect_remote(url).await?;
+
+        let RemoteQueryResult {
+            schema,
+            mut batches,
+        } = client.query(command).await.map_err(CLIError::failure)?;
+
+        if let Some(output_path) = &self.output_path {
+            // TODO: Stream records into the export as well
+            let batches: Vec<_> = batches.try_collect().await.map_err(CLIError::failure)?;
+            let table = MemTable::try_new(schema, vec![batches]).int_err()?;
+            let df = SessionContext::new()
+                .read_table(Arc::new(table))
+                .int_err()?;
+
+            let rows_exported = self
+                .export_service
+                .export_to_fs(df, output_path, self.export_options())
+                .await?;
+            eprintln!("Exported {rows_exported} rows");
+        } else {
+            let mut writer = self
+                .output_config
+                .get_records_writer(&schema, RecordsFormat::default());
+
+            while let Some(batch) = batches.try_next().await.map_err(CLIError::failure)? {
+                writer.write_batch(&batch)?;
+            }
+            writer.finish()?;
+        }
+
+        Ok(())
+    }
+
+    async fn run_remote_shell(&self, url: &Url) -> Result<(), CLIError> {
+        let mut client = self.connect_remote(url).await?;
+
+        let print_options = PrintOptions {
+            format: PrintFormat::Table,
+            quiet: false,
+            color: true,
+            maxrows: MaxRows::Limited(DEFAULT_MAX_ROWS_FOR_OUTPUT),
+        };
+
+        eprintln!(
+            "{}",
+            console::style(format!(
+                "Kamu remote SQL shell connected to {url}. Type \\q to exit."
+            ))
+            .dim()
+        );
+
+        let mut editor = rustyline::DefaultEditor::new().int_err()?;
+        let mut statement = String::new();
+
+        loop {
+            let prompt = if statement.is_empty() { "> " } else { "  " };
+
+            let line = match editor.readline(prompt) {
+                Ok(line) => line,
+                Err(rustyline::error::ReadlineError::Interrupted) => {
+                    statement.clear();
+                    continue;
+                }
+                Err(rustyline::error::ReadlineError::Eof) => break,
+                Err(e) => return Err(CLIError::critical(e)),
+            };
+
+            if statement.is_empty() && line.trim() == "\\q" {
+                break;
+            }
+
+            statement.push_str(&line);
+            statement.push('\n');
+
+            // Statements can span multiple lines and are terminated by a semicolon
+            if !line.trim_end().ends_with(';') {
+                continue;
+            }
+
+            let sql = std::mem::take(&mut statement);
+            let sql = sql.trim_end().trim_end_matches(';');
+            editor.add_history_entry(sql).int_err()?;
+
+            let query_start_time = Instant::now();
+            // Table format needs all records to lay out the columns
+            let res = match client.query(sql).await {
+                Ok(RemoteQueryResult { schema, batches }) => batches
+                    .try_collect::<Vec<_>>()
+                    .await
+                    .map(|batches| (schema, batches)),
+                Err(e) => Err(e),
+            };
+            match res {
+                Ok((schema, batches)) => {
+                    if let Err(e) = print_options.print_batches(schema, &batches, query_start_time)
+                    {
+                        eprintln!("{e}");
+                    }
+                }
+                Err(e) => eprintln!("{}", console::style(e).red()),
+            }
+        }
+
+        Ok(())
+    }
+
+    fn export_options(&self) -> ExportOptions {
+        let format = match self.output_config.format {
+            OutputFormat::Csv => ExportFormat::Csv,
+            OutputFormat::NdJson => ExportFormat::NdJson,
+            OutputFormat::Parquet => ExportFormat::Parquet,
+            not_supported => {
+                // Normally should be unreachable, as the case should be caught by
+                // `validate_args` function.
+                unimplemented!("Format {:?} is not supported.", &not_supported)
+            }
+        };
+
+        ExportOptions {
+            format,
+            records_per_file: self.records_per_file,
+        }
+    }
+
     async fn run_datafusion_export_command(&self) -> Result<(), CLIError> {
         match (&self.command, &self.output_path) {
             (Some(command), Some(output_path)) => {
-                let format = match self.output_config.format {
-                    OutputFormat::Csv => ExportFormat::Csv,
-                    OutputFormat::NdJson => ExportFormat::NdJson,
-                    OutputFormat::Parquet => ExportFormat::Parquet,
-                    not_supported => {
-                        // Normally should be unreachable, as the case should be caught by
-                        // `validate_args` function.
-                        unimplemented!("Format {:?} is not supported.", &not_supported)
-                    }
-                };
-
                 let res = self
                     .query_svc
                     .sql_statement(command, QueryOptions::default())
                     .await
                     .map_err(CLIError::failure)?;
 
-                let options = ExportOptions {
-                    format,
-                    records_per_file: self.records_per_file,
-                };
                 let rows_exported = self
                     .export_service
-                    .export_to_fs(res.df, output_path, options)
+                    .export_to_fs(res.df, output_path, self.export_options())
                     .await?;
                 eprintln!("Exported {rows_exported} rows");
             }
@@ -226,10 +508,49 @@ impl SqlShellCommand {
 #[async_trait::async_trait(?Send)]
 impl Command for SqlShellCommand {
     async fn validate_args(&self) -> Result<(), CLIError> {
+        if let (None, Some(engine_id)) = (self.builtin_engine(), &self.engine) {
+            if self
+                .engine_registry_config
+                .get_custom_engine(engine_id)
+                .is_none()
+            {
+                return Err(CLIError::usage_error(format!(
+                    "Unknown engine '{engine_id}'. Supported engines: {}",
+                    ["datafusion", "spark"]
+                        .into_iter()
+                        .chain(
+                            self.engine_registry_config
+                                .custom_engines
+                                .iter()
+                                .filter(|spec| !spec.shadows_builtin_engine())
+                                .map(|spec| spec.name.as_str())
+                        )
+                        .map(|id| format!("'{id}'"))
+                        .join(", ")
+                )));
+            }
+
+            if self.command.is_none() || self.url.is_some() || self.output_path.is_some() {
+                return Err(CLIError::usage_error(
+                    "Custom engines can only be used to execute a single --command",
+                ));
+            }
+        }
+
+        if let Some(url) = self.remote_url() {
+            url?;
+
+            if self.output_path.is_some() && self.command.is_none() {
+                return Err(CLIError::usage_error(
+                    "Data export from a remote node requires a --command",
+                ));
+            }
+        }
+
         // Bulk export related checks
         if self.output_path.is_some() {
-            match self.engine {
-                None | Some(SqlShellEngine::Datafusion) => (),
+            match self.builtin_engine() {
+                Some(SqlShellEngine::Datafusion) => (),
                 _ => {
                     return Err(CLIError::usage_error(
                         "Data export to file(s) is available with DataFusion (default) engine only",
@@ -257,17 +578,34 @@ impl Command for SqlShellCommand {
     }
 
     async fn run(&mut self) -> Result<(), CLIError> {
-        let engine = self.engine.unwrap_or(SqlShellEngine::Datafusion);
+        if let Some(url) = self.remote_url() {
+            let url = url?;
+            return match &self.command {
+                Some(command) => self.run_remote_command(&url, command).await,
+                None => self.run_remote_shell(&url).await,
+            };
+        }
 
-        match (engine, &self.command, &self.url, &self.output_path) {
-            (SqlShellEngine::Datafusion, None, None, None) => {
+        match (
+            self.builtin_engine(),
+            &self.command,
+            &self.url,
+            &self.output_path,
+        ) {
+            (Some(SqlShellEngine::Datafusion), None, None, None) => {
                 self.run_datafusion_cli_command().await
             }
-            (SqlShellEngine::Datafusion, Some(_), None, Some(_)) => {
+            (Some(SqlShellEngine::Datafusion), Some(_), None, Some(_)) => {
                 self.run_datafusion_export_command().await
             }
-            (SqlShellEngine::Datafusion, Some(_), None, _) => self.run_datafusion_command().await,
-            (SqlShellEngine::Spark, _, _, _) => self.run_spark_shell().await,
+            (Some(SqlShellEngine::Datafusion), Some(_), None, _) => {
+                self.run_datafusion_command().await
+            }
+            (Some(SqlShellEngine::Spark), _, _, _) => self.run_spark_shell().await,
+            (None, Some(command), None, None) => {
+                let engine_id = self.engine.as_deref().unwrap();
+                self.run_custom_engine_command(engine_id, command).await
+            }
             _ => unreachable!(),
         }
     }
@@ -126,7 +126,14 @@ impl APIServerRunCommand {
 #[async_trait::async_trait(?Send)]
 impl Command for APIServerRunCommand {
     async fn validate_args(&self) -> Result<(), CLIError> {
-        if self.tenancy_config == TenancyConfig::MultiTenant {
+        // GitHub credentials are not needed when users log in via an OpenID
+        // Connect provider instead
+        let oidc_configured = self
+            .base_catalog
+            .get_one::<OidcAuthenticationConfig>()
+            .is_ok();
+
+        if self.tenancy_config == TenancyConfig::MultiTenant && !oidc_configured {
             if self.github_auth_config.client_id.is_empty() {
                 return Err(CLIError::missed_env_var(ENV_VAR_KAMU_AUTH_GITHUB_CLIENT_ID));
             }
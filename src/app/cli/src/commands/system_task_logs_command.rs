@@ -0,0 +1,97 @@
+// Copyright Kamu Data, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::sync::Arc;
+
+use console::style as s;
+use database_common::PaginationOpts;
+use kamu_task_system_inmem::domain::{TaskID, TaskLogStore};
+
+use super::{CLIError, Command};
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+const PAGE_SIZE: usize = 1000;
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+pub struct SystemTaskLogsCommand {
+    task_log_store: Arc<dyn TaskLogStore>,
+    task_id: TaskID,
+    tail: Option<usize>,
+}
+
+impl SystemTaskLogsCommand {
+    pub fn new(task_log_store: Arc<dyn TaskLogStore>, task_id: u64, tail: Option<usize>) -> Self {
+        Self {
+            task_log_store,
+            task_id: TaskID::new(task_id),
+            tail,
+        }
+    }
+}
+
+#[async_trait::async_trait(?Send)]
+impl Command for SystemTaskLogsCommand {
+    async fn run(&mut self) -> Result<(), CLIError> {
+        let mut offset = 0;
+
+        if let Some(tail) = self.tail {
+            let total_count = self
+                .task_log_store
+                .get_task_log(self.task_id, PaginationOpts { offset, limit: 0 })
+                .await?
+                .total_count;
+
+            offset = total_count.saturating_sub(tail);
+        }
+
+        loop {
+            let listing = self
+                .task_log_store
+                .get_task_log(
+                    self.task_id,
+                    PaginationOpts {
+                        offset,
+                        limit: PAGE_SIZE,
+                    },
+                )
+                .await?;
+
+            if offset == 0 && listing.total_count == 0 {
+                eprintln!(
+                    "{}",
+                    s(format!("No logs captured for task {}", self.task_id)).yellow()
+                );
+                return Ok(());
+            }
+
+            for entry in &listing.list {
+                println!(
+                    "{} {} {}",
+                    s(entry
+                        .event_time
+                        .to_rfc3339_opts(chrono::SecondsFormat::Millis, true))
+                    .dim(),
+                    s(format!("[{}]", entry.source)).cyan(),
+                    entry.message,
+                );
+            }
+
+            offset += listing.list.len();
+            if listing.list.is_empty() || offset >= listing.total_count {
+                break;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
@@ -0,0 +1,82 @@
+// Copyright Kamu Data, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::sync::Arc;
+
+use console::style as s;
+use database_common::{DatabaseConnectionSettings, DatabaseProvider};
+use internal_error::ResultIntoInternal;
+use kamu_task_system_inmem::domain::{TaskAgent, TaskAgentConfig};
+
+use super::{CLIError, Command};
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+pub struct SystemTaskWorkerCommand {
+    task_agent: Arc<dyn TaskAgent>,
+    task_agent_config: Arc<TaskAgentConfig>,
+    maybe_db_connection_settings: Option<Arc<DatabaseConnectionSettings>>,
+}
+
+impl SystemTaskWorkerCommand {
+    pub fn new(
+        task_agent: Arc<dyn TaskAgent>,
+        task_agent_config: Arc<TaskAgentConfig>,
+        maybe_db_connection_settings: Option<Arc<DatabaseConnectionSettings>>,
+    ) -> Self {
+        Self {
+            task_agent,
+            task_agent_config,
+            maybe_db_connection_settings,
+        }
+    }
+}
+
+#[async_trait::async_trait(?Send)]
+impl Command for SystemTaskWorkerCommand {
+    async fn validate_args(&self) -> Result<(), CLIError> {
+        // Tasks can only be shared between processes via a persistent task store
+        match self
+            .maybe_db_connection_settings
+            .as_ref()
+            .map(|s| s.provider)
+        {
+            Some(DatabaseProvider::Postgres | DatabaseProvider::Sqlite) => {}
+            _ => {
+                return Err(CLIError::usage_error(
+                    "Task worker requires a Postgres or SQLite database shared with API servers",
+                ));
+            }
+        }
+
+        if self.task_agent_config.max_concurrent_tasks == 0 {
+            return Err(CLIError::usage_error(
+                "Task worker requires a non-zero number of concurrent tasks",
+            ));
+        }
+
+        Ok(())
+    }
+
+    async fn run(&mut self) -> Result<(), CLIError> {
+        eprintln!(
+            "{} {} {}",
+            s("Task worker is running, executing up to").green().bold(),
+            s(self.task_agent_config.max_concurrent_tasks).bold(),
+            s("tasks simultaneously").green().bold(),
+        );
+        eprintln!("{}", s("Use Ctrl+C to stop the worker").yellow());
+
+        self.task_agent.run().await.int_err()?;
+
+        Ok(())
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
@@ -9,9 +9,19 @@
 
 use std::sync::Arc;
 
+use database_common::DatabaseTransactionRunner;
 use datafusion::arrow::array::{Int32Array, UInt8Array};
 use datafusion::arrow::datatypes::DataType;
-use kamu::domain::QueryService;
+use internal_error::ResultIntoInternal;
+use kamu::domain::{
+    ChangeFeedCursor,
+    DatasetChangeFeedService,
+    DatasetChangesChunk,
+    DatasetRegistry,
+    DatasetRegistryExt,
+    QueryService,
+};
+use time_source::SystemTimeSource;
 
 use super::{CLIError, Command};
 use crate::output::*;
@@ -59,32 +69,7 @@ impl Command for TailCommand {
                 // TODO: `RecordsFormat` should allow specifying column formats by name, not
                 // only positionally
                 ColumnFormat::default(),
-                ColumnFormat::default().with_value_fmt(|array, row, _| {
-                    let err = Err(odf::metadata::InvalidOperationType(0));
-                    let op = match array.data_type() {
-                        DataType::UInt8 => array
-                            .as_any()
-                            .downcast_ref::<UInt8Array>()
-                            .map(|a| a.value(row))
-                            .map_or(err, odf::metadata::OperationType::try_from),
-                        // Compatibility fallback
-                        DataType::Int32 => array
-                            .as_any()
-                            .downcast_ref::<Int32Array>()
-                            .and_then(|a| u8::try_from(a.value(row)).ok())
-                            .map(odf::metadata::OperationType::try_from)
-                            .unwrap_or(err),
-                        _ => err,
-                    };
-                    match op {
-                        Ok(odf::metadata::OperationType::Append) => "+A",
-                        Ok(odf::metadata::OperationType::Retract) => "-R",
-                        Ok(odf::metadata::OperationType::CorrectFrom) => "-C",
-                        Ok(odf::metadata::OperationType::CorrectTo) => "+C",
-                        _ => "??",
-                    }
-                    .to_string()
-                }),
+                operation_type_column_format(),
             ]),
         );
 
@@ -96,3 +81,154 @@ impl Command for TailCommand {
 }
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+const FOLLOW_POLL_INTERVAL_MS: i64 = 1000;
+const FOLLOW_MAX_RECORDS_PER_READ: u64 = 1000;
+
+/// Streams records as they are committed to a dataset, until interrupted
+pub struct TailFollowCommand {
+    catalog: dill::Catalog,
+    time_source: Arc<dyn SystemTimeSource>,
+    dataset_ref: odf::DatasetRef,
+    limit: u64,
+    since_offset: Option<u64>,
+    output_cfg: Arc<OutputConfig>,
+}
+
+impl TailFollowCommand {
+    pub fn new(
+        catalog: dill::Catalog,
+        time_source: Arc<dyn SystemTimeSource>,
+        dataset_ref: odf::DatasetRef,
+        limit: u64,
+        since_offset: Option<u64>,
+        output_cfg: Arc<OutputConfig>,
+    ) -> Self {
+        Self {
+            catalog,
+            time_source,
+            dataset_ref,
+            limit,
+            since_offset,
+            output_cfg,
+        }
+    }
+
+    async fn initial_cursor(&self) -> Result<ChangeFeedCursor, CLIError> {
+        if let Some(since_offset) = self.since_offset {
+            return Ok(ChangeFeedCursor::since_offset(Some(since_offset)));
+        }
+
+        let dataset_ref = self.dataset_ref.clone();
+
+        let last_offset = DatabaseTransactionRunner::new(self.catalog.clone())
+            .transactional_with(|dataset_registry: Arc<dyn DatasetRegistry>| async move {
+                let resolved_dataset = dataset_registry.get_dataset_by_ref(&dataset_ref).await?;
+
+                use odf::dataset::MetadataChainExt;
+                let last_offset = resolved_dataset
+                    .as_metadata_chain()
+                    .last_data_block_with_new_data()
+                    .await
+                    .int_err()?
+                    .into_event()
+                    .and_then(|e| e.new_data)
+                    .map(|new_data| new_data.offset_interval.end);
+
+                Ok::<_, CLIError>(last_offset)
+            })
+            .await?;
+
+        // Similarly to `tail -f` start with displaying the latest records
+        Ok(ChangeFeedCursor::since_offset(
+            last_offset.and_then(|end| end.checked_sub(self.limit)),
+        ))
+    }
+
+    async fn read_changes(
+        &self,
+        cursor: &ChangeFeedCursor,
+    ) -> Result<DatasetChangesChunk, CLIError> {
+        // Every read runs in its own transaction, as following a dataset can take an
+        // arbitrarily long time
+        DatabaseTransactionRunner::new(self.catalog.clone())
+            .transactional_with(
+                |change_feed_svc: Arc<dyn DatasetChangeFeedService>| async move {
+                    change_feed_svc
+                        .read_changes(&self.dataset_ref, cursor, FOLLOW_MAX_RECORDS_PER_READ)
+                        .await
+                        .map_err(CLIError::failure)
+                },
+            )
+            .await
+    }
+}
+
+#[async_trait::async_trait(?Send)]
+impl Command for TailFollowCommand {
+    async fn run(&mut self) -> Result<(), CLIError> {
+        let mut cursor = self.initial_cursor().await?;
+
+        loop {
+            let chunk = self.read_changes(&cursor).await?;
+
+            if let Some(schema) = &chunk.schema
+                && chunk.num_records != 0
+            {
+                // Records are displayed in batches as they arrive
+                let mut writer = self.output_cfg.get_records_writer(
+                    schema.as_ref(),
+                    RecordsFormat::default().with_column_formats(vec![
+                        ColumnFormat::default(),
+                        operation_type_column_format(),
+                    ]),
+                );
+                writer.write_batches(&chunk.record_batches)?;
+                writer.finish()?;
+            }
+
+            cursor = chunk.cursor;
+
+            if chunk.caught_up {
+                self.time_source
+                    .sleep(chrono::Duration::milliseconds(FOLLOW_POLL_INTERVAL_MS))
+                    .await;
+            }
+        }
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+/// Renders the `op` column of a dataset using short symbolic names of the
+/// operation types
+pub(crate) fn operation_type_column_format() -> ColumnFormat {
+    ColumnFormat::default().with_value_fmt(|array, row, _| {
+        let err = Err(odf::metadata::InvalidOperationType(0));
+        let op = match array.data_type() {
+            DataType::UInt8 => array
+                .as_any()
+                .downcast_ref::<UInt8Array>()
+                .map(|a| a.value(row))
+                .map_or(err, odf::metadata::OperationType::try_from),
+            // Compatibility fallback
+            DataType::Int32 => array
+                .as_any()
+                .downcast_ref::<Int32Array>()
+                .and_then(|a| u8::try_from(a.value(row)).ok())
+                .map(odf::metadata::OperationType::try_from)
+                .unwrap_or(err),
+            _ => err,
+        };
+        match op {
+            Ok(odf::metadata::OperationType::Append) => "+A",
+            Ok(odf::metadata::OperationType::Retract) => "-R",
+            Ok(odf::metadata::OperationType::CorrectFrom) => "-C",
+            Ok(odf::metadata::OperationType::CorrectTo) => "+C",
+            _ => "??",
+        }
+        .to_string()
+    })
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
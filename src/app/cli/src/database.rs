@@ -124,6 +124,11 @@ pub fn configure_database_components(
             b.add::<kamu_messaging_outbox_postgres::PostgresOutboxMessageConsumptionRepository>();
 
             b.add::<kamu_auth_rebac_postgres::PostgresRebacRepository>();
+
+            b.add::<kamu_webhooks_postgres::PostgresWebhookSubscriptionRepository>();
+            b.add::<kamu_webhooks_postgres::PostgresWebhookDeliveryRepository>();
+            b.add::<kamu_alerts_postgres::PostgresDatasetAlertSettingsRepository>();
+            b.add::<kamu_alerts_postgres::PostgresDatasetPendingAlertRepository>();
         }
         DatabaseProvider::MySql | DatabaseProvider::MariaDB => {
             MySqlPlugin::init_database_components(b);
@@ -148,6 +153,11 @@ pub fn configure_database_components(
             b.add::<kamu_messaging_outbox_inmem::InMemoryOutboxMessageConsumptionRepository>();
 
             b.add::<kamu_auth_rebac_inmem::InMemoryRebacRepository>();
+
+            b.add::<kamu_webhooks_inmem::InMemoryWebhookSubscriptionRepository>();
+            b.add::<kamu_webhooks_inmem::InMemoryWebhookDeliveryRepository>();
+            b.add::<kamu_alerts_inmem::InMemoryDatasetAlertSettingsRepository>();
+            b.add::<kamu_alerts_inmem::InMemoryDatasetPendingAlertRepository>();
         }
         DatabaseProvider::Sqlite => {
             SqlitePlugin::init_database_components(b);
@@ -169,6 +179,11 @@ pub fn configure_database_components(
             b.add::<kamu_messaging_outbox_sqlite::SqliteOutboxMessageConsumptionRepository>();
 
             b.add::<kamu_auth_rebac_sqlite::SqliteRebacRepository>();
+
+            b.add::<kamu_webhooks_sqlite::SqliteWebhookSubscriptionRepository>();
+            b.add::<kamu_webhooks_sqlite::SqliteWebhookDeliveryRepository>();
+            b.add::<kamu_alerts_sqlite::SqliteDatasetAlertSettingsRepository>();
+            b.add::<kamu_alerts_sqlite::SqliteDatasetPendingAlertRepository>();
         }
     }
 
@@ -193,6 +208,10 @@ pub fn configure_in_memory_components(b: &mut CatalogBuilder) {
     b.add::<kamu_datasets_inmem::InMemoryDatasetEntryRepository>();
     b.add::<kamu_datasets_inmem::InMemoryDatasetDependencyRepository>();
     b.add::<kamu_auth_rebac_inmem::InMemoryRebacRepository>();
+    b.add::<kamu_webhooks_inmem::InMemoryWebhookSubscriptionRepository>();
+    b.add::<kamu_webhooks_inmem::InMemoryWebhookDeliveryRepository>();
+    b.add::<kamu_alerts_inmem::InMemoryDatasetAlertSettingsRepository>();
+    b.add::<kamu_alerts_inmem::InMemoryDatasetPendingAlertRepository>();
 
     NoOpDatabasePlugin::init_database_components(b);
 }
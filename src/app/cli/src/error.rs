@@ -228,7 +228,10 @@ impl From<ExportError> for CLIError {
     fn from(e: ExportError) -> Self {
         match e {
             ExportError::Internal(_) => Self::critical(e),
-            ExportError::DataFusionError(_) => Self::failure(e),
+            ExportError::DataFusionError(_)
+            | ExportError::IcebergTableOutOfSync(_)
+            | ExportError::IcebergTableDatasetMismatch(_)
+            | ExportError::UnsupportedDataType(_) => Self::failure(e),
         }
     }
 }
@@ -20,11 +20,14 @@ use dill::{Catalog, CatalogBuilder};
 use http_common::ApiError;
 use indoc::indoc;
 use internal_error::*;
-use kamu::domain::{Protocols, ServerUrlConfig, TenancyConfig};
+use kamu::domain::{DatasetFreshnessAgent, Protocols, ServerUrlConfig, TenancyConfig};
 use kamu_adapter_http::e2e::e2e_router;
-use kamu_adapter_http::{DatasetAuthorizationLayer, FileUploadLimitConfig};
+use kamu_adapter_http::{DatasetAuthorizationLayer, FileUploadLimitConfig, UploadCleanupAgent};
+use kamu_alerts_inmem::domain::DatasetAlertsAgent;
 use kamu_flow_system_inmem::domain::FlowAgent;
 use kamu_task_system_inmem::domain::TaskAgent;
+use kamu_task_system_services::TaskLogCleanupAgent;
+use kamu_webhooks_inmem::domain::WebhookDeliveryAgent;
 use messaging_outbox::OutboxAgent;
 use observability::axum::unknown_fallback_handler;
 use tokio::sync::Notify;
@@ -42,6 +45,11 @@ pub struct APIServer {
     task_agent: Arc<dyn TaskAgent>,
     flow_agent: Arc<dyn FlowAgent>,
     outbox_agent: Arc<OutboxAgent>,
+    webhook_delivery_agent: Arc<dyn WebhookDeliveryAgent>,
+    dataset_alerts_agent: Arc<dyn DatasetAlertsAgent>,
+    dataset_freshness_agent: Arc<dyn DatasetFreshnessAgent>,
+    upload_cleanup_agent: Arc<UploadCleanupAgent>,
+    task_log_cleanup_agent: Arc<TaskLogCleanupAgent>,
 }
 
 impl APIServer {
@@ -64,6 +72,16 @@ impl APIServer {
 
         let outbox_agent = cli_catalog.get_one().unwrap();
 
+        let webhook_delivery_agent = cli_catalog.get_one().unwrap();
+
+        let dataset_alerts_agent = cli_catalog.get_one().unwrap();
+
+        let dataset_freshness_agent = cli_catalog.get_one().unwrap();
+
+        let upload_cleanup_agent = cli_catalog.get_one().unwrap();
+
+        let task_log_cleanup_agent = cli_catalog.get_one().unwrap();
+
         let gql_schema = kamu_adapter_graphql::schema();
 
         let addr = SocketAddr::from((
@@ -141,14 +159,33 @@ impl APIServer {
             "/graphql",
             axum::routing::get(graphql_playground_handler).post(graphql_handler),
         )
+        .route(
+            "/graphql/ws",
+            axum::routing::get(graphql_subscription_handler),
+        )
         .routes(routes!(kamu_adapter_http::platform_login_handler))
+        .routes(routes!(kamu_adapter_http::platform_register_handler))
+        .routes(routes!(kamu_adapter_http::platform_verify_email_handler))
         .routes(routes!(kamu_adapter_http::platform_token_validate_handler))
         .routes(routes!(
             kamu_adapter_http::platform_file_upload_prepare_post_handler
         ))
         .routes(routes!(
             kamu_adapter_http::platform_file_upload_post_handler,
-            kamu_adapter_http::platform_file_upload_get_handler
+            kamu_adapter_http::platform_file_upload_get_handler,
+            kamu_adapter_http::platform_file_upload_delete_handler
+        ))
+        .routes(routes!(
+            kamu_adapter_http::platform_file_upload_prepare_multipart_post_handler
+        ))
+        .routes(routes!(
+            kamu_adapter_http::platform_file_upload_parts_get_handler
+        ))
+        .routes(routes!(
+            kamu_adapter_http::platform_file_upload_part_put_handler
+        ))
+        .routes(routes!(
+            kamu_adapter_http::platform_file_upload_complete_post_handler
         ))
         .merge(kamu_adapter_http::data::root_router())
         .merge(kamu_adapter_http::general::root_router())
@@ -241,6 +278,11 @@ impl APIServer {
             task_agent,
             flow_agent,
             outbox_agent,
+            webhook_delivery_agent,
+            dataset_alerts_agent,
+            dataset_freshness_agent,
+            upload_cleanup_agent,
+            task_log_cleanup_agent,
         })
     }
 
@@ -253,7 +295,12 @@ impl APIServer {
             res = self.server_future => { res.int_err() },
             res = self.outbox_agent.run() => { res.int_err() },
             res = self.task_agent.run() => { res.int_err() },
-            res = self.flow_agent.run() => { res.int_err() }
+            res = self.flow_agent.run() => { res.int_err() },
+            res = self.webhook_delivery_agent.run() => { res.int_err() },
+            res = self.dataset_alerts_agent.run() => { res.int_err() },
+            res = self.dataset_freshness_agent.run() => { res.int_err() },
+            res = self.upload_cleanup_agent.run() => { res.int_err() },
+            res = self.task_log_cleanup_agent.run() => { res.int_err() }
         }
     }
 }
@@ -298,9 +345,71 @@ async fn graphql_handler(
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
 
+async fn graphql_subscription_handler(
+    Extension(schema): Extension<kamu_adapter_graphql::Schema>,
+    Extension(catalog): Extension<Catalog>,
+    protocol: async_graphql_axum::GraphQLProtocol,
+    websocket: axum::extract::WebSocketUpgrade,
+) -> axum::response::Response {
+    use axum::response::IntoResponse;
+
+    websocket
+        .protocols(async_graphql::http::ALL_WEBSOCKET_PROTOCOLS)
+        .on_upgrade(move |stream| {
+            async_graphql_axum::GraphQLWebSocket::new(stream, schema, protocol)
+                .on_connection_init(move |payload| graphql_subscription_init(catalog, payload))
+                .serve()
+        })
+        .into_response()
+}
+
+/// Browsers can't set headers on a WebSocket handshake, so the clients pass the
+/// access token in the `connection_init` payload instead:
+/// `{"Authorization": "Bearer <token>"}`. Connections without a token keep the
+/// subject that was resolved from the handshake request.
+async fn graphql_subscription_init(
+    catalog: Catalog,
+    payload: serde_json::Value,
+) -> async_graphql::Result<async_graphql::Data> {
+    let maybe_access_token = payload
+        .get("Authorization")
+        .or_else(|| payload.get("authorization"))
+        .and_then(serde_json::Value::as_str)
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .map(kamu_adapter_http::AccessToken::new);
+
+    let catalog = if let Some(access_token) = maybe_access_token {
+        let current_account_subject =
+            kamu_adapter_http::authenticate_access_token(&catalog, Some(access_token.clone()))
+                .await
+                .map_err(|err| {
+                    tracing::error!(
+                        error = ?err,
+                        error_msg = %err,
+                        "Internal error during authentication",
+                    );
+                    async_graphql::Error::new("Internal error")
+                })?;
+
+        CatalogBuilder::new_chained(&catalog)
+            .add_value(current_account_subject)
+            .add_value(access_token)
+            .build()
+    } else {
+        catalog
+    };
+
+    let mut data = async_graphql::Data::default();
+    data.insert(catalog);
+    Ok(data)
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
 async fn graphql_playground_handler() -> impl axum::response::IntoResponse {
     axum::response::Html(async_graphql::http::playground_source(
-        async_graphql::http::GraphQLPlaygroundConfig::new("/graphql"),
+        async_graphql::http::GraphQLPlaygroundConfig::new("/graphql")
+            .subscription_endpoint("/graphql/ws"),
     ))
 }
 
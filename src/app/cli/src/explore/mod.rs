@@ -10,6 +10,7 @@
 mod api_server;
 mod flight_sql_service_factory;
 mod notebook_server_factory;
+mod remote_sql_client;
 mod spark_livy_server_factory;
 mod sql_shell_impl;
 mod trace_server;
@@ -20,6 +21,7 @@ mod web_ui_server;
 pub use api_server::*;
 pub use flight_sql_service_factory::*;
 pub use notebook_server_factory::*;
+pub use remote_sql_client::*;
 pub use spark_livy_server_factory::*;
 pub use sql_shell_impl::*;
 pub use trace_server::*;
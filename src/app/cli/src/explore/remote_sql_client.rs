@@ -0,0 +1,155 @@
+// Copyright Kamu Data, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::sync::Arc;
+
+use arrow_flight::sql::client::FlightSqlServiceClient;
+use datafusion::arrow::array::RecordBatch;
+use datafusion::arrow::datatypes::SchemaRef;
+use datafusion::arrow::error::ArrowError;
+use futures::stream::BoxStream;
+use futures::{StreamExt, TryStreamExt};
+use thiserror::Error;
+use tonic::transport::{Channel, ClientTlsConfig, Endpoint};
+use url::Url;
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+/// Executes SQL queries against a Flight SQL endpoint of a remote node
+pub struct RemoteSqlClient {
+    client: FlightSqlServiceClient<Channel>,
+}
+
+impl RemoteSqlClient {
+    /// Connects to the endpoint specified using `grpc://` (or `http://`) and
+    /// `grpc+tls://` (or `https://`) schemes, authenticating all requests with
+    /// the access token if provided
+    pub async fn connect(
+        flight_sql_url: &Url,
+        access_token: Option<String>,
+    ) -> Result<Self, RemoteSqlConnectError> {
+        let endpoint = Self::to_channel_endpoint(flight_sql_url)?;
+
+        let channel = endpoint
+            .connect()
+            .await
+            .map_err(|e| RemoteSqlConnectError::Transport {
+                url: flight_sql_url.clone(),
+                source: e,
+            })?;
+
+        let mut client = FlightSqlServiceClient::new(channel);
+        if let Some(access_token) = access_token {
+            client.set_token(access_token);
+        }
+
+        Ok(Self { client })
+    }
+
+    fn to_channel_endpoint(flight_sql_url: &Url) -> Result<Endpoint, RemoteSqlConnectError> {
+        let use_tls = match flight_sql_url.scheme() {
+            "grpc" | "http" => false,
+            "grpc+tls" | "https" => true,
+            scheme => {
+                return Err(RemoteSqlConnectError::UnsupportedScheme {
+                    scheme: scheme.to_string(),
+                })
+            }
+        };
+
+        let Some(host) = flight_sql_url.host_str() else {
+            return Err(RemoteSqlConnectError::InvalidUrl {
+                url: flight_sql_url.clone(),
+            });
+        };
+
+        let uri = format!(
+            "{}://{}:{}",
+            if use_tls { "https" } else { "http" },
+            host,
+            flight_sql_url
+                .port()
+                .unwrap_or(if use_tls { 443 } else { 80 })
+        );
+
+        let endpoint =
+            Endpoint::from_shared(uri).map_err(|e| RemoteSqlConnectError::Transport {
+                url: flight_sql_url.clone(),
+                source: e,
+            })?;
+
+        if !use_tls {
+            return Ok(endpoint);
+        }
+
+        endpoint
+            .tls_config(ClientTlsConfig::new().with_native_roots())
+            .map_err(|e| RemoteSqlConnectError::Transport {
+                url: flight_sql_url.clone(),
+                source: e,
+            })
+    }
+
+    /// Executes a single statement. Resulting records are fetched from the
+    /// endpoints one by one as the returned stream is consumed.
+    pub async fn query(&mut self, sql: &str) -> Result<RemoteQueryResult, ArrowError> {
+        let flight_info = self.client.execute(sql.to_string(), None).await?;
+        let schema = Arc::new(flight_info.try_decode_schema()?);
+
+        let tickets: Vec<_> = flight_info
+            .endpoint
+            .into_iter()
+            .filter_map(|e| e.ticket)
+            .collect();
+
+        let client = self.client.clone();
+        let batches = futures::stream::iter(tickets)
+            .then(move |ticket| {
+                let mut client = client.clone();
+                async move {
+                    let stream = client.do_get(ticket).await?;
+                    Ok::<_, ArrowError>(stream.map_err(ArrowError::from))
+                }
+            })
+            .try_flatten()
+            .boxed();
+
+        Ok(RemoteQueryResult { schema, batches })
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+pub struct RemoteQueryResult {
+    pub schema: SchemaRef,
+    pub batches: BoxStream<'static, Result<RecordBatch, ArrowError>>,
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[derive(Debug, Error)]
+pub enum RemoteSqlConnectError {
+    #[error(
+        "Unsupported Flight SQL endpoint scheme '{scheme}', expected 'grpc', 'grpc+tls', 'http' \
+         or 'https'"
+    )]
+    UnsupportedScheme { scheme: String },
+
+    #[error("Invalid Flight SQL endpoint URL {url}")]
+    InvalidUrl { url: Url },
+
+    #[error("Failed to connect to Flight SQL endpoint {url}")]
+    Transport {
+        url: Url,
+        #[source]
+        source: tonic::transport::Error,
+    },
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
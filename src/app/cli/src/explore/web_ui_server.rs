@@ -165,7 +165,20 @@ impl WebUIServer {
         ))
         .routes(routes!(
             kamu_adapter_http::platform_file_upload_post_handler,
-            kamu_adapter_http::platform_file_upload_get_handler
+            kamu_adapter_http::platform_file_upload_get_handler,
+            kamu_adapter_http::platform_file_upload_delete_handler
+        ))
+        .routes(routes!(
+            kamu_adapter_http::platform_file_upload_prepare_multipart_post_handler
+        ))
+        .routes(routes!(
+            kamu_adapter_http::platform_file_upload_parts_get_handler
+        ))
+        .routes(routes!(
+            kamu_adapter_http::platform_file_upload_part_put_handler
+        ))
+        .routes(routes!(
+            kamu_adapter_http::platform_file_upload_complete_post_handler
         ))
         .nest(
             "/odata",
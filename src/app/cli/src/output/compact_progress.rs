@@ -127,6 +127,20 @@ impl CompactionListener for CompactionProgress {
                     ),
                 );
             }
+            CompactionResult::DataSlicesMerged {
+                num_merged_slices,
+                num_new_files,
+            } => {
+                self.curr_progress.finish_with_message(
+                    self.spinner_message(
+                        console::style(format!(
+                            "Dataset compacted successfully ({num_merged_slices} data slices \
+                             merged into {num_new_files} files)"
+                        ))
+                        .green(),
+                    ),
+                );
+            }
         }
     }
 
@@ -135,6 +149,7 @@ impl CompactionListener for CompactionProgress {
             CompactionPhase::GatherChainInfo => "Gathering chain information",
             CompactionPhase::MergeDataslices => "Merging dataslices",
             CompactionPhase::CommitNewBlocks => "Committing new blocks",
+            CompactionPhase::SaveMergedDataSlices => "Saving merged dataslices",
         };
         self.curr_progress.set_message(message);
     }
@@ -7,11 +7,13 @@
 // the Business Source License, use of this software will be governed
 // by the Apache License, Version 2.0.
 
+use std::collections::BTreeMap;
 use std::path::Path;
 
 use container_runtime::{ContainerRuntimeType, NetworkNamespaceType};
 use database_common::DatabaseProvider;
 use duration_string::DurationString;
+use kamu::domain::{EngineIoStrategyKind, QueryDialect};
 use kamu::utils::docker_images;
 use kamu_accounts::*;
 use kamu_datasets::DatasetEnvVarsConfig;
@@ -57,6 +59,10 @@ pub struct CLIConfig {
     #[merge(strategy = merge_recursive)]
     pub flow_system: Option<FlowSystemConfig>,
 
+    /// Storage of logs captured while running tasks
+    #[merge(strategy = merge_recursive)]
+    pub task_logs: Option<TaskLogsConfig>,
+
     /// Source configuration
     #[merge(strategy = merge_recursive)]
     pub source: Option<SourceConfig>,
@@ -68,6 +74,26 @@ pub struct CLIConfig {
     /// Uploads configuration
     #[merge(strategy = merge_recursive)]
     pub uploads: Option<UploadsConfig>,
+
+    /// Webhooks configuration
+    #[merge(strategy = merge_recursive)]
+    pub webhooks: Option<WebhooksConfig>,
+
+    /// Dataset alerts configuration
+    #[merge(strategy = merge_recursive)]
+    pub alerts: Option<AlertsConfig>,
+
+    /// Dataset freshness configuration
+    #[merge(strategy = merge_recursive)]
+    pub freshness: Option<FreshnessConfig>,
+
+    /// Account self-registration configuration
+    #[merge(strategy = merge_recursive)]
+    pub account_registration: Option<AccountRegistrationConfig>,
+
+    /// External identity providers configuration
+    #[merge(strategy = merge_recursive)]
+    pub auth: Option<AuthConfig>,
 }
 
 impl CLIConfig {
@@ -84,6 +110,12 @@ impl CLIConfig {
             users: None,
             uploads: None,
             flow_system: None,
+            task_logs: None,
+            webhooks: None,
+            alerts: None,
+            freshness: None,
+            account_registration: None,
+            auth: None,
         }
     }
 
@@ -104,6 +136,12 @@ impl CLIConfig {
             users: Some(PredefinedAccountsConfig::sample()),
             uploads: Some(UploadsConfig::sample()),
             flow_system: Some(FlowSystemConfig::sample()),
+            task_logs: Some(TaskLogsConfig::sample()),
+            webhooks: Some(WebhooksConfig::sample()),
+            alerts: Some(AlertsConfig::sample()),
+            freshness: Some(FreshnessConfig::sample()),
+            account_registration: Some(AccountRegistrationConfig::sample()),
+            auth: Some(AuthConfig::sample()),
         }
     }
 }
@@ -122,6 +160,12 @@ impl Default for CLIConfig {
             users: Some(PredefinedAccountsConfig::default()),
             uploads: Some(UploadsConfig::default()),
             flow_system: Some(FlowSystemConfig::default()),
+            task_logs: Some(TaskLogsConfig::default()),
+            webhooks: Some(WebhooksConfig::default()),
+            alerts: Some(AlertsConfig::default()),
+            freshness: Some(FreshnessConfig::default()),
+            account_registration: Some(AccountRegistrationConfig::default()),
+            auth: Some(AuthConfig::default()),
         }
     }
 }
@@ -145,9 +189,15 @@ pub struct EngineConfig {
     pub start_timeout: Option<DurationString>,
     /// Timeout for waiting the engine container to stop gracefully
     pub shutdown_timeout: Option<DurationString>,
+    /// UNSTABLE: Warm engine containers reused across operations
+    #[merge(strategy = merge_recursive)]
+    pub pool: Option<EnginePoolConfig>,
     /// UNSTABLE: Default engine images
     #[merge(strategy = merge_recursive)]
     pub images: Option<EngineImagesConfig>,
+    /// UNSTABLE: Additional engines implementing the ODF engine protocol,
+    /// keyed by the engine identifier used in transformations and queries
+    pub custom: Option<BTreeMap<String, CustomEngineConfig>>,
 }
 
 impl EngineConfig {
@@ -158,17 +208,40 @@ impl EngineConfig {
             network_ns: None,
             start_timeout: None,
             shutdown_timeout: None,
+            pool: None,
             images: None,
+            custom: None,
         }
     }
 
     fn sample() -> Self {
         Self {
             max_concurrency: Some(0),
+            pool: Some(EnginePoolConfig::sample()),
             images: Some(EngineImagesConfig::sample()),
+            custom: Some(BTreeMap::from([(
+                "duckdb".to_string(),
+                CustomEngineConfig::sample(),
+            )])),
             ..Self::default()
         }
     }
+
+    pub fn to_registry_cfg(&self) -> kamu::domain::EngineRegistryConfig {
+        kamu::domain::EngineRegistryConfig {
+            custom_engines: self
+                .custom
+                .iter()
+                .flatten()
+                .map(|(name, engine)| kamu::domain::CustomEngineSpec {
+                    name: name.clone(),
+                    image: engine.image.clone(),
+                    dialect: engine.dialect,
+                    io_strategy: engine.io_strategy.unwrap_or_default(),
+                })
+                .collect(),
+        }
+    }
 }
 
 impl Default for EngineConfig {
@@ -179,7 +252,55 @@ impl Default for EngineConfig {
             network_ns: Some(NetworkNamespaceType::Private),
             start_timeout: Some(DurationString::from_string("30s".to_owned()).unwrap()),
             shutdown_timeout: Some(DurationString::from_string("5s".to_owned()).unwrap()),
+            pool: Some(EnginePoolConfig::default()),
             images: Some(EngineImagesConfig::default()),
+            custom: Some(BTreeMap::new()),
+        }
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[skip_serializing_none]
+#[derive(Debug, Clone, Merge, Serialize, Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+pub struct EnginePoolConfig {
+    /// Maximum number of idle engine containers kept running for reuse, zero
+    /// disables pooling
+    pub max_size: Option<usize>,
+    /// For how long an idle engine container is kept before it is stopped
+    pub idle_timeout: Option<DurationString>,
+}
+
+impl EnginePoolConfig {
+    pub fn new() -> Self {
+        Self {
+            max_size: None,
+            idle_timeout: None,
+        }
+    }
+
+    fn sample() -> Self {
+        Self {
+            max_size: Some(4),
+            ..Self::default()
+        }
+    }
+
+    pub fn to_infra_cfg(&self) -> kamu::EnginePoolConfig {
+        kamu::EnginePoolConfig {
+            max_size: self.max_size.unwrap(),
+            idle_timeout: (*self.idle_timeout.as_ref().unwrap()).into(),
+        }
+    }
+}
+
+impl Default for EnginePoolConfig {
+    fn default() -> Self {
+        let infra_cfg = kamu::EnginePoolConfig::default();
+        Self {
+            max_size: Some(infra_cfg.max_size),
+            idle_timeout: Some(DurationString::from(infra_cfg.idle_timeout)),
         }
     }
 }
@@ -226,6 +347,30 @@ impl Default for EngineImagesConfig {
     }
 }
 
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[skip_serializing_none]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+pub struct CustomEngineConfig {
+    /// Engine image implementing the ODF engine protocol
+    pub image: String,
+    /// Language and dialect the engine is using for queries
+    pub dialect: QueryDialect,
+    /// How data is passed to the engine container (`Auto` by default)
+    pub io_strategy: Option<EngineIoStrategyKind>,
+}
+
+impl CustomEngineConfig {
+    fn sample() -> Self {
+        Self {
+            image: "ghcr.io/example/engine-duckdb:0.1.0".to_string(),
+            dialect: QueryDialect::SqlDataFusion,
+            io_strategy: Some(EngineIoStrategyKind::Auto),
+        }
+    }
+}
+
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
 // Source
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
@@ -846,6 +991,18 @@ impl IdentityConfig {
 #[serde(rename_all = "camelCase")]
 pub struct UploadsConfig {
     pub max_file_size_in_mb: Option<usize>,
+
+    /// Preferred size of a part in resumable multipart uploads
+    pub multipart_part_size_in_mb: Option<usize>,
+
+    /// Maximal size of a file uploaded in parts
+    pub multipart_max_file_size_in_mb: Option<usize>,
+
+    /// Time after which incomplete multipart uploads are cleaned up
+    pub incomplete_upload_ttl_secs: Option<i64>,
+
+    /// Interval between cleanups of expired incomplete uploads
+    pub cleanup_interval_secs: Option<i64>,
 }
 
 impl UploadsConfig {
@@ -858,6 +1015,10 @@ impl Default for UploadsConfig {
     fn default() -> Self {
         Self {
             max_file_size_in_mb: Some(50),
+            multipart_part_size_in_mb: Some(16),
+            multipart_max_file_size_in_mb: Some(10 * 1024),
+            incomplete_upload_ttl_secs: Some(24 * 60 * 60),
+            cleanup_interval_secs: Some(60 * 60),
         }
     }
 }
@@ -890,6 +1051,251 @@ impl Default for OutboxConfig {
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
 
+#[skip_serializing_none]
+#[derive(Debug, Clone, Merge, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+#[serde(rename_all = "camelCase")]
+pub struct WebhooksConfig {
+    /// Interval between checks for pending deliveries
+    pub awaiting_step_secs: Option<i64>,
+    /// Maximum number of deliveries attempted in one iteration
+    pub batch_size: Option<usize>,
+    /// Number of attempts after which a delivery is considered failed
+    pub max_attempts: Option<u32>,
+    /// Delay before the first retry, doubled after every failed attempt
+    pub initial_backoff_secs: Option<i64>,
+    /// Upper bound of the delay between retries
+    pub max_backoff_secs: Option<i64>,
+    /// Time the endpoint has to respond in
+    pub request_timeout_secs: Option<i64>,
+}
+
+impl WebhooksConfig {
+    pub fn sample() -> Self {
+        Default::default()
+    }
+}
+
+impl Default for WebhooksConfig {
+    fn default() -> Self {
+        Self {
+            awaiting_step_secs: Some(1),
+            batch_size: Some(20),
+            max_attempts: Some(5),
+            initial_backoff_secs: Some(10),
+            max_backoff_secs: Some(3600),
+            request_timeout_secs: Some(10),
+        }
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[skip_serializing_none]
+#[derive(Debug, Clone, Merge, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+#[serde(rename_all = "camelCase")]
+pub struct AlertsConfig {
+    /// Interval between checks whether any datasets became stale
+    pub staleness_check_interval_secs: Option<i64>,
+    /// How alert emails are delivered
+    pub email: Option<EmailConfig>,
+}
+
+impl AlertsConfig {
+    pub fn sample() -> Self {
+        Self {
+            staleness_check_interval_secs: Some(60),
+            email: Some(EmailConfig::sample()),
+        }
+    }
+}
+
+impl Default for AlertsConfig {
+    fn default() -> Self {
+        Self {
+            staleness_check_interval_secs: Some(60),
+            email: Some(EmailConfig::Dummy),
+        }
+    }
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+#[serde(rename_all = "camelCase")]
+#[serde(tag = "kind")]
+pub enum EmailConfig {
+    /// Emails are only logged
+    Dummy,
+    /// Every email is written into a separate file in the directory
+    File(FileEmailConfig),
+    Smtp(SmtpEmailConfig),
+}
+
+impl EmailConfig {
+    pub fn sample() -> Self {
+        Self::Smtp(SmtpEmailConfig {
+            host: String::from("smtp.example.com"),
+            port: Some(587),
+            username: Some(String::from("kamu")),
+            password: Some(String::from("p455w0rd")),
+            implicit_tls: Some(false),
+            sender_address: String::from("noreply@example.com"),
+            sender_name: Some(String::from("Kamu")),
+        })
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+#[serde(rename_all = "camelCase")]
+pub struct FileEmailConfig {
+    pub output_dir: String,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+#[serde(rename_all = "camelCase")]
+pub struct SmtpEmailConfig {
+    pub host: String,
+    pub port: Option<u16>,
+    pub username: Option<String>,
+    pub password: Option<String>,
+    /// Use implicit TLS instead of `STARTTLS`
+    pub implicit_tls: Option<bool>,
+    pub sender_address: String,
+    pub sender_name: Option<String>,
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[skip_serializing_none]
+#[derive(Debug, Clone, Merge, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountRegistrationConfig {
+    /// Whether new accounts can register themselves with a password.
+    /// Confirmation emails are delivered the same way as alert emails
+    pub enabled: Option<bool>,
+    /// Page the email verification token is passed to as a `token` query
+    /// parameter
+    pub email_verification_url: Option<Url>,
+    /// Time the email verification token stays valid
+    pub email_verification_token_ttl_secs: Option<i64>,
+}
+
+impl AccountRegistrationConfig {
+    pub fn sample() -> Self {
+        Self {
+            email_verification_url: Some(Url::parse("http://localhost:4200/verify-email").unwrap()),
+            ..Default::default()
+        }
+    }
+}
+
+impl Default for AccountRegistrationConfig {
+    fn default() -> Self {
+        Self {
+            enabled: Some(false),
+            email_verification_url: None,
+            email_verification_token_ttl_secs: Some(24 * 60 * 60),
+        }
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[skip_serializing_none]
+#[derive(Debug, Default, Clone, Merge, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+#[serde(rename_all = "camelCase")]
+pub struct AuthConfig {
+    /// Generic OpenID Connect provider, e.g. Keycloak, Okta or Azure AD
+    pub oidc: Option<OidcConfig>,
+}
+
+impl AuthConfig {
+    pub fn sample() -> Self {
+        Self {
+            oidc: Some(OidcConfig::sample()),
+        }
+    }
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+#[serde(rename_all = "camelCase")]
+pub struct OidcConfig {
+    /// Issuer the provider metadata is discovered from
+    pub issuer_url: Url,
+    pub client_id: String,
+    /// Can also be passed via `KAMU_AUTH_OIDC_CLIENT_SECRET` environment
+    /// variable. Not needed for public clients
+    pub client_secret: Option<String>,
+    /// ID token claim the account name is taken from
+    pub account_name_claim: Option<String>,
+    /// ID token claim the email is taken from
+    pub email_claim: Option<String>,
+    /// ID token claim the display name is taken from
+    pub display_name_claim: Option<String>,
+    /// ID token claim listing groups of the user
+    pub groups_claim: Option<String>,
+    /// Members of this group become administrators. When not specified,
+    /// administrators are not managed by the provider
+    pub admin_group: Option<String>,
+    /// How long ID tokens obtained via the device flow (`kamu login oidc`)
+    /// stay valid for logging in
+    pub id_token_max_age_secs: Option<u64>,
+}
+
+impl OidcConfig {
+    pub fn sample() -> Self {
+        Self {
+            issuer_url: Url::parse("https://keycloak.example.com/realms/kamu").unwrap(),
+            client_id: String::from("kamu"),
+            client_secret: None,
+            account_name_claim: Some(String::from("preferred_username")),
+            email_claim: Some(String::from("email")),
+            display_name_claim: Some(String::from("name")),
+            groups_claim: Some(String::from("groups")),
+            admin_group: Some(String::from("kamu-admins")),
+            id_token_max_age_secs: Some(300),
+        }
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[skip_serializing_none]
+#[derive(Debug, Clone, Merge, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+#[serde(rename_all = "camelCase")]
+pub struct FreshnessConfig {
+    /// Interval between evaluations of dataset freshness SLAs
+    pub check_interval_secs: Option<i64>,
+}
+
+impl FreshnessConfig {
+    pub fn sample() -> Self {
+        Self {
+            check_interval_secs: Some(60),
+        }
+    }
+}
+
+impl Default for FreshnessConfig {
+    fn default() -> Self {
+        Self {
+            check_interval_secs: Some(60),
+        }
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
 #[skip_serializing_none]
 #[derive(Debug, Clone, Merge, Serialize, Deserialize)]
 #[serde(deny_unknown_fields)]
@@ -926,6 +1332,9 @@ impl Default for FlowSystemConfig {
 pub struct FlowAgentConfig {
     pub awaiting_step_secs: Option<i64>,
     pub mandatory_throttling_period_secs: Option<i64>,
+    /// Interval between checks of pull remotes of upstream datasets for new
+    /// data. Set to 0 to disable tracking of upstreams on remote nodes
+    pub remote_upstream_polling_interval_secs: Option<i64>,
 }
 
 impl FlowAgentConfig {
@@ -933,6 +1342,7 @@ impl FlowAgentConfig {
         Self {
             awaiting_step_secs: None,
             mandatory_throttling_period_secs: None,
+            remote_upstream_polling_interval_secs: None,
         }
     }
 
@@ -946,6 +1356,7 @@ impl Default for FlowAgentConfig {
         Self {
             awaiting_step_secs: Some(1),
             mandatory_throttling_period_secs: Some(60),
+            remote_upstream_polling_interval_secs: Some(300),
         }
     }
 }
@@ -954,13 +1365,32 @@ impl Default for FlowAgentConfig {
 #[derive(Debug, Clone, Merge, Serialize, Deserialize)]
 #[serde(deny_unknown_fields, rename_all = "camelCase")]
 pub struct TaskAgentConfig {
+    /// Interval between checks for new queued tasks
     pub task_checking_interval_secs: Option<i64>,
+    /// Maximum number of tasks executed simultaneously by a single process.
+    /// Set to zero to leave task execution to standalone
+    /// `kamu system task-worker` processes.
+    pub max_concurrent_tasks: Option<usize>,
+    /// Additional limits of simultaneously executed tasks per logical plan
+    /// kind (`UpdateDataset`, `HardCompactDataset`, `ResetDataset`,
+    /// `ReprocessDataset`, `Probe`)
+    pub max_concurrent_tasks_per_plan_kind: Option<BTreeMap<String, usize>>,
+    /// For how long a running task stays claimed without a heartbeat before
+    /// it is re-queued
+    pub task_lease_duration_secs: Option<i64>,
+    /// Interval between heartbeats extending the claim of running tasks,
+    /// must be shorter than the lease duration
+    pub task_heartbeat_interval_secs: Option<i64>,
 }
 
 impl TaskAgentConfig {
     pub fn new() -> Self {
         Self {
             task_checking_interval_secs: None,
+            max_concurrent_tasks: None,
+            max_concurrent_tasks_per_plan_kind: None,
+            task_lease_duration_secs: None,
+            task_heartbeat_interval_secs: None,
         }
     }
 
@@ -973,6 +1403,45 @@ impl Default for TaskAgentConfig {
     fn default() -> Self {
         Self {
             task_checking_interval_secs: Some(1),
+            max_concurrent_tasks: Some(1),
+            max_concurrent_tasks_per_plan_kind: Some(BTreeMap::new()),
+            task_lease_duration_secs: Some(60),
+            task_heartbeat_interval_secs: Some(15),
+        }
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[skip_serializing_none]
+#[derive(Debug, Clone, Merge, Serialize, Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+pub struct TaskLogsConfig {
+    /// Location of stored logs: a `file://` directory or an `s3://` prefix.
+    /// Defaults to the `task-logs` directory of the workspace
+    pub storage_url: Option<Url>,
+    /// For how long logs are kept after they were last written. Zero keeps
+    /// them forever
+    pub retention_period_secs: Option<i64>,
+    /// Interval between removals of expired logs
+    pub cleanup_interval_secs: Option<i64>,
+}
+
+impl TaskLogsConfig {
+    pub fn sample() -> Self {
+        Self {
+            storage_url: Some(Url::parse("s3://bucket/task-logs/").unwrap()),
+            ..Self::default()
+        }
+    }
+}
+
+impl Default for TaskLogsConfig {
+    fn default() -> Self {
+        Self {
+            storage_url: None,
+            retention_period_secs: Some(30 * 24 * 60 * 60),
+            cleanup_interval_secs: Some(60 * 60),
         }
     }
 }
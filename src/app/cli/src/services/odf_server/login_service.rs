@@ -14,7 +14,8 @@ use std::sync::Arc;
 use dill::component;
 use internal_error::{InternalError, ResultIntoInternal};
 use kamu_accounts::PROVIDER_PASSWORD;
-use kamu_adapter_http::LoginRequestBody;
+use kamu_adapter_http::{LoginRequestBody, RegisterRequestBody, VerifyEmailRequestBody};
+use kamu_adapter_oauth::{OidcClient, OidcClientError, OidcDeviceAuthorization, PROVIDER_OIDC};
 use serde::Deserialize;
 use serde_json::json;
 use thiserror::Error;
@@ -177,6 +178,49 @@ impl LoginService {
         .await
     }
 
+    /// Obtains an ID token from the OpenID Connect provider via the device
+    /// authorization flow and exchanges it for an access token of the backend
+    pub async fn login_oidc_device(
+        &self,
+        odf_server_backend_url: &Url,
+        issuer_url: &Url,
+        client_id: &str,
+        scopes: &[String],
+        device_authorization_started_callback: impl Fn(&OidcDeviceAuthorization),
+    ) -> Result<BackendLoginResponse, LoginError> {
+        let oidc_client = OidcClient::discover(issuer_url, client_id.to_string(), None)
+            .await
+            .map_err(map_oidc_client_error)?;
+
+        let device_authorization = oidc_client
+            .start_device_authorization(scopes)
+            .await
+            .map_err(map_oidc_client_error)?;
+        device_authorization_started_callback(&device_authorization);
+
+        let token_response = oidc_client
+            .poll_device_token(&device_authorization)
+            .await
+            .map_err(map_oidc_client_error)?;
+        let Some(id_token) = token_response.id_token else {
+            return Err(LoginError::AccessFailed(LoginErrorAccessFailed {
+                reason: String::from("Identity provider issued no ID token, check the scopes"),
+            }));
+        };
+
+        let login_credentials_json = json!({
+            "idToken": id_token,
+        })
+        .to_string();
+
+        self.invoke_login_method(
+            odf_server_backend_url,
+            PROVIDER_OIDC,
+            login_credentials_json,
+        )
+        .await
+    }
+
     pub async fn login_password(
         &self,
         odf_server_backend_url: &Url,
@@ -230,6 +274,74 @@ impl LoginService {
         }
     }
 
+    pub async fn register(
+        &self,
+        odf_server_backend_url: &Url,
+        account_name: &odf::AccountName,
+        email: &str,
+        password: &str,
+    ) -> Result<(), LoginError> {
+        let client = reqwest::Client::new();
+
+        let register_url = odf_server_backend_url.join("platform/register").unwrap();
+        tracing::info!(?register_url, "Register request");
+
+        let response = client
+            .post(register_url)
+            .json(&RegisterRequestBody {
+                account_name: account_name.clone(),
+                email: String::from(email),
+                password: String::from(password),
+            })
+            .send()
+            .await
+            .int_err()?;
+
+        match response.status() {
+            http::StatusCode::OK => Ok(()),
+            _ => Err(LoginError::AccessFailed(LoginErrorAccessFailed {
+                reason: format!(
+                    "Status {} {}",
+                    response.status().as_str(),
+                    response.text().await.unwrap()
+                ),
+            })),
+        }
+    }
+
+    pub async fn verify_email(
+        &self,
+        odf_server_backend_url: &Url,
+        verification_token: &str,
+    ) -> Result<BackendLoginResponse, LoginError> {
+        let client = reqwest::Client::new();
+
+        let verify_email_url = odf_server_backend_url
+            .join("platform/verify-email")
+            .unwrap();
+        tracing::info!(?verify_email_url, "Email verification request");
+
+        let response = client
+            .post(verify_email_url)
+            .json(&VerifyEmailRequestBody {
+                verification_token: String::from(verification_token),
+            })
+            .send()
+            .await
+            .int_err()?;
+
+        match response.status() {
+            http::StatusCode::OK => Ok(response.json::<BackendLoginResponse>().await.int_err()?),
+            _ => Err(LoginError::AccessFailed(LoginErrorAccessFailed {
+                reason: format!(
+                    "Status {} {}",
+                    response.status().as_str(),
+                    response.text().await.unwrap()
+                ),
+            })),
+        }
+    }
+
     pub async fn validate_access_token(
         &self,
         odf_server_backend_url: &Url,
@@ -311,6 +423,15 @@ impl From<InternalError> for LoginError {
     }
 }
 
+fn map_oidc_client_error(e: OidcClientError) -> LoginError {
+    match e {
+        OidcClientError::Internal(e) => LoginError::Internal(e),
+        e => LoginError::AccessFailed(LoginErrorAccessFailed {
+            reason: e.to_string(),
+        }),
+    }
+}
+
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
 
 #[derive(Debug, Error)]
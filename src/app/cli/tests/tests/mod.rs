@@ -12,5 +12,6 @@ mod test_di_graph;
 mod test_generate_cli_markdown;
 mod test_new_dataset_command;
 mod test_output;
+mod test_remote_sql_client;
 mod test_system_info_command;
 mod test_workspace_svc;
@@ -56,6 +56,7 @@ fn test_di_cli_graph_validates(tenancy_config: TenancyConfig) {
     kamu_cli::register_config_in_catalog(
         &kamu_cli::config::CLIConfig::default(),
         &mut base_catalog_builder,
+        &workspace_layout,
         WorkspaceStatus::Created(tenancy_config),
         None,
         false,
@@ -94,6 +95,7 @@ fn test_di_server_graph_validates(tenancy_config: TenancyConfig) {
     kamu_cli::register_config_in_catalog(
         &config,
         &mut base_catalog_builder,
+        &workspace_layout,
         WorkspaceStatus::Created(tenancy_config),
         None,
         false,
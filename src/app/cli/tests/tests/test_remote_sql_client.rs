@@ -0,0 +1,211 @@
+// Copyright Kamu Data, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::assert_matches::assert_matches;
+use std::net::SocketAddr;
+
+use arrow_flight::flight_service_server::FlightServiceServer;
+use datafusion::arrow::util::pretty::pretty_format_batches;
+use datafusion::prelude::*;
+use futures::TryStreamExt;
+use indoc::indoc;
+use kamu::domain::{MockQueryService, QueryService};
+use kamu_accounts::testing::MockAuthenticationService;
+use kamu_accounts::{Account, AuthenticationService, GetAccountInfoError};
+use kamu_adapter_flight_sql::*;
+use kamu_cli::explore::{RemoteSqlClient, RemoteSqlConnectError};
+use tokio::net::TcpListener;
+use tonic::service::interceptor;
+use tonic::transport::Server;
+use url::Url;
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[test_log::test(tokio::test)]
+async fn test_remote_query_with_access_token() {
+    let server = FlightServer::run().await;
+
+    let mut client = RemoteSqlClient::connect(&server.url(), Some("valid-token".to_string()))
+        .await
+        .unwrap();
+
+    let res = client
+        .query("select * from test order by id")
+        .await
+        .unwrap();
+
+    assert_eq!(res.schema.fields().len(), 2);
+
+    let batches: Vec<_> = res.batches.try_collect().await.unwrap();
+
+    pretty_assertions::assert_eq!(
+        pretty_format_batches(&batches).unwrap().to_string(),
+        indoc!(
+            "
+            +----+------+
+            | id | name |
+            +----+------+
+            | 1  | a    |
+            | 2  | b    |
+            +----+------+
+            "
+        )
+        .trim()
+    );
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[test_log::test(tokio::test)]
+async fn test_remote_query_empty_result_keeps_schema() {
+    let server = FlightServer::run().await;
+
+    let mut client = RemoteSqlClient::connect(&server.url(), Some("valid-token".to_string()))
+        .await
+        .unwrap();
+
+    let res = client
+        .query("select name from test where id > 100")
+        .await
+        .unwrap();
+
+    assert_eq!(
+        res.schema
+            .fields()
+            .iter()
+            .map(|f| f.name().as_str())
+            .collect::<Vec<_>>(),
+        ["name"]
+    );
+
+    let batches: Vec<_> = res.batches.try_collect().await.unwrap();
+    assert_eq!(batches.iter().map(|b| b.num_rows()).sum::<usize>(), 0);
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[test_log::test(tokio::test)]
+async fn test_remote_query_invalid_access_token() {
+    let server = FlightServer::run().await;
+
+    let mut client = RemoteSqlClient::connect(&server.url(), Some("invalid-token".to_string()))
+        .await
+        .unwrap();
+
+    assert_matches!(client.query("select * from test").await, Err(_));
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[test_log::test(tokio::test)]
+async fn test_remote_connect_unsupported_scheme() {
+    assert_matches!(
+        RemoteSqlClient::connect(&Url::parse("jdbc:hive2://example.com:10000").unwrap(), None)
+            .await,
+        Err(RemoteSqlConnectError::UnsupportedScheme { scheme }) if scheme == "jdbc"
+    );
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+// Harness
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+struct FlightServer {
+    addr: SocketAddr,
+    task: tokio::task::JoinHandle<Result<(), tonic::transport::Error>>,
+}
+
+impl Drop for FlightServer {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+impl FlightServer {
+    async fn run() -> Self {
+        let ctx = SessionContext::new_with_config(
+            SessionConfig::new()
+                .with_information_schema(true)
+                .with_default_catalog_and_schema("test", "public"),
+        );
+
+        ctx.sql(indoc!(
+            "
+            create table test (id int not null, name string not null)
+            as values (1, 'a'), (2, 'b')
+            ",
+        ))
+        .await
+        .unwrap();
+
+        let mut mock_authentication_service = MockAuthenticationService::new();
+        mock_authentication_service
+            .expect_account_by_token()
+            .with(mockall::predicate::eq("valid-token".to_string()))
+            .returning(|_| Ok(Account::dummy()));
+        mock_authentication_service
+            .expect_account_by_token()
+            .with(mockall::predicate::eq("invalid-token".to_string()))
+            .returning(|_| {
+                Err(GetAccountInfoError::AccessToken(
+                    kamu_accounts::AccessTokenError::Invalid("foo".into()),
+                ))
+            });
+
+        let mut query_svc = MockQueryService::new();
+        query_svc
+            .expect_create_session()
+            .return_once(move || Ok(ctx));
+
+        let mut b = dill::Catalog::builder();
+
+        b.add::<SessionAuthAnonymous>()
+            .add_value(SessionAuthConfig {
+                allow_anonymous: false,
+            })
+            .add_value(mock_authentication_service)
+            .bind::<dyn AuthenticationService, MockAuthenticationService>()
+            .add_value(query_svc)
+            .bind::<dyn QueryService, MockQueryService>()
+            .add::<SessionManagerSingleton>()
+            .add::<SessionManagerSingletonState>()
+            .add_value(
+                kamu_adapter_flight_sql::sql_info::default_sql_info()
+                    .build()
+                    .unwrap(),
+            )
+            .add::<KamuFlightSqlService>();
+
+        database_common::NoOpDatabasePlugin::init_database_components(&mut b);
+
+        let catalog = b.build();
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        let service = Server::builder()
+            .layer(interceptor(move |mut req: tonic::Request<()>| {
+                req.extensions_mut().insert(catalog.clone());
+                Ok(req)
+            }))
+            .layer(AuthenticationLayer::new())
+            .add_service(FlightServiceServer::new(KamuFlightSqlServiceWrapper))
+            .serve_with_incoming(tokio_stream::wrappers::TcpListenerStream::new(listener));
+
+        let task = tokio::task::spawn(service);
+
+        Self { addr, task }
+    }
+
+    fn url(&self) -> Url {
+        Url::parse(&format!("grpc://{}", self.addr)).unwrap()
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
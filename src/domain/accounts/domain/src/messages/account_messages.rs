@@ -26,6 +26,8 @@ const ACCOUNT_LIFECYCLE_OUTBOX_VERSION: u32 = 1;
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum AccountLifecycleMessage {
     Created(AccountLifecycleMessageCreated),
+    EmailChanged(AccountLifecycleMessageEmailChanged),
+    Deleted(AccountLifecycleMessageDeleted),
 }
 impl AccountLifecycleMessage {
     pub fn created(
@@ -39,6 +41,25 @@ impl AccountLifecycleMessage {
             display_name,
         })
     }
+
+    pub fn email_changed(account_id: odf::AccountID, new_email: Email) -> Self {
+        Self::EmailChanged(AccountLifecycleMessageEmailChanged {
+            account_id,
+            new_email,
+        })
+    }
+
+    pub fn deleted(
+        account_id: odf::AccountID,
+        account_name: odf::AccountName,
+        email: Email,
+    ) -> Self {
+        Self::Deleted(AccountLifecycleMessageDeleted {
+            account_id,
+            account_name,
+            email,
+        })
+    }
 }
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
@@ -57,3 +78,18 @@ pub struct AccountLifecycleMessageCreated {
     pub email: Email,
     pub display_name: AccountDisplayName,
 }
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AccountLifecycleMessageEmailChanged {
+    pub account_id: odf::AccountID,
+    pub new_email: Email,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AccountLifecycleMessageDeleted {
+    pub account_id: odf::AccountID,
+    pub account_name: odf::AccountName,
+    pub email: Email,
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
@@ -48,6 +48,12 @@ pub trait AccessTokenRepository: Send + Sync {
         token_id: &Uuid,
         token_hash: [u8; 32],
     ) -> Result<Account, FindAccountByTokenError>;
+
+    /// Removes all tokens of the account, including the revoked ones
+    async fn delete_access_tokens_by_account_id(
+        &self,
+        account_id: &odf::AccountID,
+    ) -> Result<(), DeleteAccessTokensError>;
 }
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
@@ -113,3 +119,11 @@ pub enum RevokeTokenError {
 }
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[derive(Error, Debug)]
+pub enum DeleteAccessTokensError {
+    #[error(transparent)]
+    Internal(#[from] InternalError),
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
@@ -59,6 +59,33 @@ pub trait AccountRepository: Send + Sync {
         account_id: &odf::AccountID,
         new_email: Email,
     ) -> Result<(), UpdateAccountError>;
+
+    async fn set_account_disabled(
+        &self,
+        account_id: &odf::AccountID,
+        disabled: bool,
+    ) -> Result<(), UpdateAccountError>;
+
+    async fn is_account_disabled(
+        &self,
+        account_id: &odf::AccountID,
+    ) -> Result<bool, GetAccountByIdError>;
+
+    /// Accounts are considered verified unless they were self-registered and
+    /// the ownership of their email was not confirmed yet
+    async fn set_account_email_verified(
+        &self,
+        account_id: &odf::AccountID,
+        email_verified: bool,
+    ) -> Result<(), UpdateAccountError>;
+
+    async fn is_account_email_verified(
+        &self,
+        account_id: &odf::AccountID,
+    ) -> Result<bool, GetAccountByIdError>;
+
+    /// Deletes the account together with its password hash and access tokens
+    async fn delete_account(&self, account_id: &odf::AccountID) -> Result<(), DeleteAccountError>;
 }
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
@@ -238,3 +265,14 @@ pub enum UpdateAccountError {
 }
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[derive(Debug, Error)]
+pub enum DeleteAccountError {
+    #[error(transparent)]
+    NotFound(AccountNotFoundByIdError),
+
+    #[error(transparent)]
+    Internal(#[from] InternalError),
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
@@ -20,10 +20,22 @@ pub trait PasswordHashRepository: Send + Sync {
         password_hash: String,
     ) -> Result<(), SavePasswordHashError>;
 
+    /// Replaces the password hash of the account, creating it if missing
+    async fn update_password_hash(
+        &self,
+        account_name: &odf::AccountName,
+        password_hash: String,
+    ) -> Result<(), SavePasswordHashError>;
+
     async fn find_password_hash_by_account_name(
         &self,
         account_name: &odf::AccountName,
     ) -> Result<Option<String>, FindPasswordHashError>;
+
+    async fn delete_password_hash(
+        &self,
+        account_name: &odf::AccountName,
+    ) -> Result<(), DeletePasswordHashError>;
 }
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
@@ -43,3 +55,11 @@ pub enum FindPasswordHashError {
 }
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[derive(Error, Debug)]
+pub enum DeletePasswordHashError {
+    #[error(transparent)]
+    Internal(#[from] InternalError),
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
@@ -0,0 +1,173 @@
+// Copyright Kamu Data, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use email_utils::Email;
+use internal_error::InternalError;
+use thiserror::Error;
+
+use crate::{Account, AccountErrorDuplicate};
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+/// Administrative operations over accounts, which are not available to
+/// regular users, except for the deletion of their own account
+#[async_trait::async_trait]
+pub trait AccountManagementService: Send + Sync {
+    async fn create_password_account(
+        &self,
+        account_name: &odf::AccountName,
+        email: Email,
+        password: String,
+    ) -> Result<Account, CreatePasswordAccountError>;
+
+    async fn rename_account(
+        &self,
+        account: &Account,
+        new_name: &odf::AccountName,
+    ) -> Result<Account, RenameAccountError>;
+
+    /// Changes the email of the account. The new email is unconfirmed until
+    /// the account follows the verification link sent to it
+    async fn update_account_email(
+        &self,
+        account: &Account,
+        new_email: Email,
+    ) -> Result<(), UpdateAccountEmailError>;
+
+    /// Disabled accounts can neither log in nor use previously issued tokens
+    async fn set_account_disabled(
+        &self,
+        account: &Account,
+        disabled: bool,
+    ) -> Result<(), InternalError>;
+
+    async fn is_account_disabled(&self, account: &Account) -> Result<bool, InternalError>;
+
+    /// Deletes the account once all [`AccountDeletionGuard`]s agree.
+    /// Credentials, access tokens, datasets, permissions and flows of the
+    /// account are cleaned up by the consumers of
+    /// [`crate::AccountLifecycleMessage::Deleted`]
+    async fn delete_account(&self, account: &Account) -> Result<(), AccountDeletionError>;
+
+    async fn reset_password(
+        &self,
+        account: &Account,
+        new_password: String,
+    ) -> Result<(), ResetPasswordError>;
+
+    /// Revokes all active access tokens of the account, returns their number.
+    /// Session tokens issued at login are not stored, so they stay valid until
+    /// they expire, unless the account is disabled
+    async fn revoke_access_tokens(&self, account: &Account) -> Result<usize, InternalError>;
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+/// Lets other domains veto the deletion of an account, as the cleanup that
+/// follows the deletion can no longer be rejected
+#[async_trait::async_trait]
+pub trait AccountDeletionGuard: Send + Sync {
+    async fn check_account_deletion(&self, account: &Account) -> Result<(), AccountDeletionError>;
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+// Errors
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[derive(Debug, Error)]
+pub enum CreatePasswordAccountError {
+    #[error(transparent)]
+    Duplicate(AccountErrorDuplicate),
+
+    #[error(transparent)]
+    Internal(#[from] InternalError),
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[derive(Debug, Error)]
+pub enum RenameAccountError {
+    #[error(transparent)]
+    Duplicate(AccountErrorDuplicate),
+
+    #[error(transparent)]
+    Internal(#[from] InternalError),
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[derive(Debug, Error)]
+pub enum UpdateAccountEmailError {
+    #[error(transparent)]
+    Duplicate(AccountErrorDuplicate),
+
+    #[error(transparent)]
+    Internal(#[from] InternalError),
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[derive(Debug, Error)]
+pub enum AccountDeletionError {
+    #[error(transparent)]
+    Blocked(AccountDeletionBlockedError),
+
+    #[error(transparent)]
+    Internal(#[from] InternalError),
+}
+
+#[derive(Debug, Error)]
+#[error("Account '{account_name}' can't be deleted: {reason}")]
+pub struct AccountDeletionBlockedError {
+    pub account_name: odf::AccountName,
+    pub reason: AccountDeletionBlockedReason,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AccountDeletionBlockedReason {
+    /// Datasets of other accounts depend on datasets owned by the account
+    ForeignDownstreamDatasets {
+        downstream_datasets_count: usize,
+    },
+    Other(String),
+}
+
+impl std::fmt::Display for AccountDeletionBlockedReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::ForeignDownstreamDatasets {
+                downstream_datasets_count,
+            } => write!(
+                f,
+                "{downstream_datasets_count} dataset(s) of other accounts depend on datasets of \
+                 this account"
+            ),
+            Self::Other(reason) => write!(f, "{reason}"),
+        }
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[derive(Debug, Error)]
+pub enum ResetPasswordError {
+    #[error(transparent)]
+    NotPasswordAccount(NotPasswordAccountError),
+
+    #[error(transparent)]
+    Internal(#[from] InternalError),
+}
+
+#[derive(Debug, Error)]
+#[error("Account '{account_name}' does not use password authentication")]
+pub struct NotPasswordAccountError {
+    pub account_name: odf::AccountName,
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
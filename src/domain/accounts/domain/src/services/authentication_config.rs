@@ -37,3 +37,25 @@ impl JwtAuthenticationConfig {
 }
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+/// Self-registration of accounts authenticated by password
+#[derive(Debug, Clone)]
+pub struct AccountRegistrationConfig {
+    pub enabled: bool,
+    /// Page the verification token is passed to as a `token` query parameter.
+    /// When not specified, the emails contain only the token itself
+    pub email_verification_url: Option<url::Url>,
+    pub email_verification_token_ttl: chrono::Duration,
+}
+
+impl Default for AccountRegistrationConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            email_verification_url: None,
+            email_verification_token_ttl: chrono::Duration::hours(24),
+        }
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
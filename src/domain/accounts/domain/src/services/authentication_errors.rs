@@ -38,3 +38,9 @@ pub struct RejectedCredentialsError {}
 pub struct NoPrimaryEmailError {}
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[derive(Debug, Error)]
+#[error("The email of the account is not verified yet")]
+pub struct EmailNotVerifiedError {}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
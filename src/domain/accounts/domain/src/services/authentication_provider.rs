@@ -38,6 +38,9 @@ pub struct ProviderLoginResponse {
     pub account_type: AccountType,
     pub avatar_url: Option<String>,
     pub provider_identity_key: String,
+    /// Admin flag, when administrators are managed by the identity provider.
+    /// Applied on every login, so revoking it in the provider takes effect
+    pub is_admin: Option<bool>,
 }
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
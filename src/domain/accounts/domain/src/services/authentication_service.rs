@@ -7,11 +7,23 @@
 // the Business Source License, use of this software will be governed
 // by the Apache License, Version 2.0.
 
+use email_utils::Email;
 use internal_error::{BoxedError, InternalError};
 use thiserror::Error;
 
-use super::{InvalidCredentialsError, NoPrimaryEmailError, RejectedCredentialsError};
-use crate::{Account, FindAccountIdByProviderIdentityKeyError, ProviderLoginError};
+use super::{
+    EmailNotVerifiedError,
+    InvalidCredentialsError,
+    NoPrimaryEmailError,
+    NotPasswordAccountError,
+    RejectedCredentialsError,
+};
+use crate::{
+    Account,
+    AccountErrorDuplicate,
+    FindAccountIdByProviderIdentityKeyError,
+    ProviderLoginError,
+};
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
 
@@ -26,6 +38,37 @@ pub trait AuthenticationService: Sync + Send {
     ) -> Result<LoginResponse, LoginError>;
 
     async fn account_by_token(&self, access_token: String) -> Result<Account, GetAccountInfoError>;
+
+    /// Registers a new account authenticated by password. The account cannot
+    /// log in until the ownership of the email is confirmed via the
+    /// verification token sent to it
+    async fn register_password_account(
+        &self,
+        account_name: &odf::AccountName,
+        email: Email,
+        password: String,
+    ) -> Result<Account, RegisterAccountError>;
+
+    /// Sends a verification token to the current email of the account,
+    /// unless the email is already confirmed. Called once the registration
+    /// or the email change is committed
+    async fn send_email_verification(
+        &self,
+        account_id: &odf::AccountID,
+    ) -> Result<(), InternalError>;
+
+    /// Confirms the email of a registered account and logs it in
+    async fn verify_email(
+        &self,
+        verification_token: &str,
+    ) -> Result<LoginResponse, VerifyEmailError>;
+
+    async fn change_password(
+        &self,
+        account: &Account,
+        old_password: String,
+        new_password: String,
+    ) -> Result<(), ChangePasswordError>;
 }
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
@@ -69,6 +112,13 @@ pub enum LoginError {
         NoPrimaryEmailError,
     ),
 
+    #[error(transparent)]
+    EmailNotVerified(
+        #[from]
+        #[backtrace]
+        EmailNotVerifiedError,
+    ),
+
     #[error("Credentials are already used by an existing account")]
     DuplicateCredentials,
 
@@ -107,6 +157,65 @@ impl From<FindAccountIdByProviderIdentityKeyError> for LoginError {
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
 
+#[derive(Debug, Error)]
+pub enum RegisterAccountError {
+    #[error(transparent)]
+    RegistrationDisabled(AccountRegistrationDisabledError),
+
+    #[error(transparent)]
+    Duplicate(AccountErrorDuplicate),
+
+    #[error(transparent)]
+    Internal(
+        #[from]
+        #[backtrace]
+        InternalError,
+    ),
+}
+
+#[derive(Debug, Error)]
+#[error("Registration of new accounts is disabled")]
+pub struct AccountRegistrationDisabledError {}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[derive(Debug, Error)]
+pub enum VerifyEmailError {
+    #[error(transparent)]
+    InvalidToken(InvalidEmailVerificationTokenError),
+
+    #[error(transparent)]
+    Internal(
+        #[from]
+        #[backtrace]
+        InternalError,
+    ),
+}
+
+#[derive(Debug, Error)]
+#[error("Invalid or expired email verification token")]
+pub struct InvalidEmailVerificationTokenError {}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[derive(Debug, Error)]
+pub enum ChangePasswordError {
+    #[error(transparent)]
+    NotPasswordAccount(NotPasswordAccountError),
+
+    #[error(transparent)]
+    WrongPassword(RejectedCredentialsError),
+
+    #[error(transparent)]
+    Internal(
+        #[from]
+        #[backtrace]
+        InternalError,
+    ),
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
 #[derive(Debug, Error)]
 pub enum GetAccountInfoError {
     #[error(transparent)]
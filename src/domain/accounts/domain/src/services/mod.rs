@@ -8,6 +8,7 @@
 // by the Apache License, Version 2.0.
 
 mod access_token_service;
+mod account_management_service;
 mod account_service;
 mod authentication_config;
 mod authentication_errors;
@@ -15,6 +16,7 @@ mod authentication_provider;
 mod authentication_service;
 
 pub use access_token_service::*;
+pub use account_management_service::*;
 pub use account_service::*;
 pub use authentication_config::*;
 pub use authentication_errors::*;
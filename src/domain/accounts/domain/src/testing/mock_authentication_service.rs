@@ -9,6 +9,8 @@
 
 use std::fmt::Display;
 
+use email_utils::Email;
+use internal_error::InternalError;
 use mockall::predicate::{always, eq};
 use thiserror::Error;
 
@@ -16,10 +18,13 @@ use crate::{
     AccessTokenError,
     Account,
     AuthenticationService,
+    ChangePasswordError,
     GetAccountInfoError,
     LoginError,
     LoginResponse,
+    RegisterAccountError,
     UnsupportedLoginMethodError,
+    VerifyEmailError,
     DEFAULT_ACCOUNT_ID,
     DEFAULT_ACCOUNT_NAME,
     DUMMY_ACCESS_TOKEN,
@@ -47,6 +52,30 @@ mockall::mock! {
             &self,
             access_token: String,
         ) -> Result<Account, GetAccountInfoError>;
+
+        async fn register_password_account(
+            &self,
+            account_name: &odf::AccountName,
+            email: Email,
+            password: String,
+        ) -> Result<Account, RegisterAccountError>;
+
+        async fn send_email_verification(
+            &self,
+            account_id: &odf::AccountID,
+        ) -> Result<(), InternalError>;
+
+        async fn verify_email(
+            &self,
+            verification_token: &str,
+        ) -> Result<LoginResponse, VerifyEmailError>;
+
+        async fn change_password(
+            &self,
+            account: &Account,
+            old_password: String,
+            new_password: String,
+        ) -> Result<(), ChangePasswordError>;
     }
 }
 
@@ -12,6 +12,8 @@
 // Re-exports
 pub use kamu_accounts as domain;
 
+mod messages;
 mod services;
 
+pub use messages::*;
 pub use services::*;
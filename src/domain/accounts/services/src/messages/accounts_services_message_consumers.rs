@@ -0,0 +1,21 @@
+// Copyright Kamu Data, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+pub const MESSAGE_CONSUMER_KAMU_ACCESS_TOKEN_SERVICE: &str =
+    "dev.kamu.domain.accounts.AccessTokenService";
+
+pub const MESSAGE_CONSUMER_KAMU_EMAIL_VERIFICATION_NOTIFIER: &str =
+    "dev.kamu.domain.accounts.EmailVerificationNotifier";
+
+pub const MESSAGE_CONSUMER_KAMU_LOGIN_PASSWORD_AUTH_PROVIDER: &str =
+    "dev.kamu.domain.accounts.LoginPasswordAuthProvider";
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
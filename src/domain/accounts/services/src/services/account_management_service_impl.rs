@@ -0,0 +1,277 @@
+// Copyright Kamu Data, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::sync::Arc;
+
+use database_common::PaginationOpts;
+use dill::*;
+use email_utils::Email;
+use internal_error::{ErrorIntoInternal, InternalError, ResultIntoInternal};
+use kamu_accounts::*;
+use messaging_outbox::{Outbox, OutboxExt};
+use time_source::SystemTimeSource;
+
+use crate::LoginPasswordAuthProvider;
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+pub struct AccountManagementServiceImpl {
+    account_repo: Arc<dyn AccountRepository>,
+    access_token_repo: Arc<dyn AccessTokenRepository>,
+    login_password_auth_provider: Arc<LoginPasswordAuthProvider>,
+    time_source: Arc<dyn SystemTimeSource>,
+    outbox: Arc<dyn Outbox>,
+    deletion_guards: Vec<Arc<dyn AccountDeletionGuard>>,
+}
+
+#[component(pub)]
+#[interface(dyn AccountManagementService)]
+impl AccountManagementServiceImpl {
+    pub fn new(
+        account_repo: Arc<dyn AccountRepository>,
+        access_token_repo: Arc<dyn AccessTokenRepository>,
+        login_password_auth_provider: Arc<LoginPasswordAuthProvider>,
+        time_source: Arc<dyn SystemTimeSource>,
+        outbox: Arc<dyn Outbox>,
+        deletion_guards: Vec<Arc<dyn AccountDeletionGuard>>,
+    ) -> Self {
+        Self {
+            account_repo,
+            access_token_repo,
+            login_password_auth_provider,
+            time_source,
+            outbox,
+            deletion_guards,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl AccountManagementService for AccountManagementServiceImpl {
+    #[tracing::instrument(level = "info", skip_all, fields(%account_name))]
+    async fn create_password_account(
+        &self,
+        account_name: &odf::AccountName,
+        email: Email,
+        password: String,
+    ) -> Result<Account, CreatePasswordAccountError> {
+        // Names can be freed by renaming, so IDs must not be derived from them
+        let (_, account_id) = odf::AccountID::new_generated_ed25519();
+
+        let account = Account {
+            id: account_id,
+            account_name: account_name.clone(),
+            email,
+            display_name: account_name.to_string(),
+            account_type: AccountType::User,
+            avatar_url: None,
+            registered_at: self.time_source.now(),
+            is_admin: false,
+            provider: String::from(PROVIDER_PASSWORD),
+            provider_identity_key: account_name.to_string(),
+        };
+
+        self.account_repo
+            .create_account(&account)
+            .await
+            .map_err(|e| match e {
+                CreateAccountError::Duplicate(e) => CreatePasswordAccountError::Duplicate(e),
+                CreateAccountError::Internal(e) => CreatePasswordAccountError::Internal(e),
+            })?;
+
+        self.login_password_auth_provider
+            .save_password(account_name, password)
+            .await?;
+
+        self.outbox
+            .post_message(
+                MESSAGE_PRODUCER_KAMU_ACCOUNTS_SERVICE,
+                AccountLifecycleMessage::created(
+                    account.id.clone(),
+                    account.email.clone(),
+                    account.display_name.clone(),
+                ),
+            )
+            .await?;
+
+        Ok(account)
+    }
+
+    #[tracing::instrument(level = "info", skip_all, fields(account_id = %account.id, %new_name))]
+    async fn rename_account(
+        &self,
+        account: &Account,
+        new_name: &odf::AccountName,
+    ) -> Result<Account, RenameAccountError> {
+        // Password accounts are identified by their name
+        let provider_identity_key = if account.provider == PROVIDER_PASSWORD {
+            new_name.to_string()
+        } else {
+            account.provider_identity_key.clone()
+        };
+
+        let renamed_account = Account {
+            account_name: new_name.clone(),
+            provider_identity_key,
+            ..account.clone()
+        };
+
+        self.account_repo
+            .update_account(renamed_account.clone())
+            .await
+            .map_err(|e| match e {
+                UpdateAccountError::Duplicate(e) => RenameAccountError::Duplicate(e),
+                UpdateAccountError::NotFound(e) => RenameAccountError::Internal(e.int_err()),
+                UpdateAccountError::Internal(e) => RenameAccountError::Internal(e),
+            })?;
+
+        Ok(renamed_account)
+    }
+
+    #[tracing::instrument(level = "info", skip_all, fields(account_id = %account.id))]
+    async fn update_account_email(
+        &self,
+        account: &Account,
+        new_email: Email,
+    ) -> Result<(), UpdateAccountEmailError> {
+        if account.email == new_email {
+            return Ok(());
+        }
+
+        self.account_repo
+            .update_account_email(&account.id, new_email.clone())
+            .await
+            .map_err(|e| match e {
+                UpdateAccountError::Duplicate(e) => UpdateAccountEmailError::Duplicate(e),
+                UpdateAccountError::NotFound(e) => UpdateAccountEmailError::Internal(e.int_err()),
+                UpdateAccountError::Internal(e) => UpdateAccountEmailError::Internal(e),
+            })?;
+
+        // Ownership of the new email has to be confirmed again, the
+        // verification email is sent once this change is committed
+        self.account_repo
+            .set_account_email_verified(&account.id, false)
+            .await
+            .int_err()?;
+
+        self.outbox
+            .post_message(
+                MESSAGE_PRODUCER_KAMU_ACCOUNTS_SERVICE,
+                AccountLifecycleMessage::email_changed(account.id.clone(), new_email),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    #[tracing::instrument(level = "info", skip_all, fields(account_id = %account.id, disabled))]
+    async fn set_account_disabled(
+        &self,
+        account: &Account,
+        disabled: bool,
+    ) -> Result<(), InternalError> {
+        self.account_repo
+            .set_account_disabled(&account.id, disabled)
+            .await
+            .int_err()
+    }
+
+    async fn is_account_disabled(&self, account: &Account) -> Result<bool, InternalError> {
+        self.account_repo
+            .is_account_disabled(&account.id)
+            .await
+            .int_err()
+    }
+
+    #[tracing::instrument(level = "info", skip_all, fields(account_id = %account.id))]
+    async fn delete_account(&self, account: &Account) -> Result<(), AccountDeletionError> {
+        for deletion_guard in &self.deletion_guards {
+            deletion_guard.check_account_deletion(account).await?;
+        }
+
+        // Access tokens and other records reference the account, so the
+        // immediate consumers must remove them before the account itself
+        self.outbox
+            .post_message(
+                MESSAGE_PRODUCER_KAMU_ACCOUNTS_SERVICE,
+                AccountLifecycleMessage::deleted(
+                    account.id.clone(),
+                    account.account_name.clone(),
+                    account.email.clone(),
+                ),
+            )
+            .await?;
+
+        self.account_repo
+            .delete_account(&account.id)
+            .await
+            .int_err()?;
+
+        Ok(())
+    }
+
+    #[tracing::instrument(level = "info", skip_all, fields(account_id = %account.id))]
+    async fn reset_password(
+        &self,
+        account: &Account,
+        new_password: String,
+    ) -> Result<(), ResetPasswordError> {
+        if account.provider != PROVIDER_PASSWORD {
+            return Err(ResetPasswordError::NotPasswordAccount(
+                NotPasswordAccountError {
+                    account_name: account.account_name.clone(),
+                },
+            ));
+        }
+
+        self.login_password_auth_provider
+            .update_password(&account.account_name, new_password)
+            .await?;
+
+        Ok(())
+    }
+
+    #[tracing::instrument(level = "info", skip_all, fields(account_id = %account.id))]
+    async fn revoke_access_tokens(&self, account: &Account) -> Result<usize, InternalError> {
+        let tokens_count = self
+            .access_token_repo
+            .get_access_tokens_count_by_account_id(&account.id)
+            .await
+            .int_err()?;
+        if tokens_count == 0 {
+            return Ok(0);
+        }
+
+        let tokens = self
+            .access_token_repo
+            .get_access_tokens_by_account_id(
+                &account.id,
+                &PaginationOpts {
+                    limit: tokens_count,
+                    offset: 0,
+                },
+            )
+            .await
+            .int_err()?;
+
+        let revoke_time = self.time_source.now();
+        let mut revoked_count = 0;
+        for token in tokens.iter().filter(|token| token.revoked_at.is_none()) {
+            self.access_token_repo
+                .mark_revoked(&token.id, revoke_time)
+                .await
+                .int_err()?;
+            revoked_count += 1;
+        }
+
+        Ok(revoked_count)
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
@@ -13,20 +13,25 @@ use std::sync::Arc;
 
 use chrono::Utc;
 use dill::*;
+use email_utils::{Email, EmailMessage, EmailSender};
 use internal_error::*;
 use jsonwebtoken::errors::ErrorKind;
 use jsonwebtoken::{decode, encode, Algorithm, DecodingKey, EncodingKey, Header, Validation};
 use kamu_accounts::*;
 use messaging_outbox::{Outbox, OutboxExt};
 use odf::dataset::DUMMY_ODF_ACCESS_TOKEN;
+use serde::{Deserialize, Serialize};
 use thiserror::Error;
 use time_source::SystemTimeSource;
 
+use crate::LoginPasswordAuthProvider;
+
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
 
 const KAMU_JWT_ISSUER: &str = "dev.kamu";
 const KAMU_JWT_ALGORITHM: Algorithm = Algorithm::HS384;
 const EXPIRATION_TIME_SEC: usize = 24 * 60 * 60; // 1 day in seconds
+const EMAIL_VERIFICATION_AUDIENCE: &str = "dev.kamu.email-verification";
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
 
@@ -39,6 +44,9 @@ pub struct AuthenticationServiceImpl {
     access_token_svc: Arc<dyn AccessTokenService>,
     outbox: Arc<dyn Outbox>,
     maybe_dummy_token_account: Option<Account>,
+    maybe_login_password_auth_provider: Option<Arc<LoginPasswordAuthProvider>>,
+    email_sender: Arc<dyn EmailSender>,
+    registration_config: AccountRegistrationConfig,
 }
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
@@ -54,6 +62,9 @@ impl AuthenticationServiceImpl {
         time_source: Arc<dyn SystemTimeSource>,
         config: Arc<JwtAuthenticationConfig>,
         outbox: Arc<dyn Outbox>,
+        maybe_login_password_auth_provider: Option<Arc<LoginPasswordAuthProvider>>,
+        email_sender: Arc<dyn EmailSender>,
+        maybe_registration_config: Option<Arc<AccountRegistrationConfig>>,
     ) -> Self {
         let mut authentication_providers_by_method = HashMap::new();
 
@@ -78,6 +89,11 @@ impl AuthenticationServiceImpl {
             access_token_svc,
             outbox,
             maybe_dummy_token_account: config.maybe_dummy_token_account.clone(),
+            maybe_login_password_auth_provider,
+            email_sender,
+            registration_config: maybe_registration_config
+                .map(|config| config.as_ref().clone())
+                .unwrap_or_default(),
         }
     }
 
@@ -159,7 +175,7 @@ impl AuthenticationServiceImpl {
             .decode_access_token(access_token)
             .map_err(GetAccountInfoError::AccessToken)?;
 
-        match decoded_access_token {
+        let account = match decoded_access_token {
             AccessTokenType::JWTToken(token_data) => {
                 let account_id = odf::AccountID::from_did_str(&token_data.claims.sub)
                     .map_err(|e| GetAccountInfoError::Internal(e.int_err()))?;
@@ -186,10 +202,154 @@ impl AuthenticationServiceImpl {
                     }
                     FindAccountByTokenError::Internal(err) => GetAccountInfoError::Internal(err),
                 }),
-            AccessTokenType::DummyToken(dummy_account) => Ok(dummy_account),
+            AccessTokenType::DummyToken(dummy_account) => return Ok(dummy_account),
+        }?;
+
+        // Tokens of disabled accounts are not honored
+        if self.is_account_disabled(&account.id).await? {
+            return Err(GetAccountInfoError::AccountUnresolved);
+        }
+
+        Ok(account)
+    }
+
+    async fn is_account_disabled(
+        &self,
+        account_id: &odf::AccountID,
+    ) -> Result<bool, InternalError> {
+        match self
+            .account_repository
+            .is_account_disabled(account_id)
+            .await
+        {
+            Ok(disabled) => Ok(disabled),
+            Err(GetAccountByIdError::NotFound(_)) => Ok(false),
+            Err(GetAccountByIdError::Internal(e)) => Err(e),
+        }
+    }
+
+    fn make_email_verification_token(&self, account: &Account) -> Result<String, InternalError> {
+        let iat = usize::try_from(self.time_source.now().timestamp()).unwrap();
+        let exp = iat
+            + usize::try_from(
+                self.registration_config
+                    .email_verification_token_ttl
+                    .num_seconds(),
+            )
+            .int_err()?;
+
+        let claims = EmailVerificationClaims {
+            iat,
+            exp,
+            iss: String::from(KAMU_JWT_ISSUER),
+            aud: String::from(EMAIL_VERIFICATION_AUDIENCE),
+            sub: account.id.to_string(),
+            email: account.email.as_ref().to_string(),
+        };
+
+        encode(
+            &Header::new(KAMU_JWT_ALGORITHM),
+            &claims,
+            &self.encoding_key,
+        )
+        .int_err()
+    }
+
+    fn decode_email_verification_token(
+        &self,
+        verification_token: &str,
+    ) -> Result<(odf::AccountID, Email), InvalidEmailVerificationTokenError> {
+        let mut validation = Validation::new(KAMU_JWT_ALGORITHM);
+        validation.set_issuer(&[KAMU_JWT_ISSUER]);
+        validation.set_audience(&[EMAIL_VERIFICATION_AUDIENCE]);
+        validation.set_required_spec_claims(&["exp", "aud"]);
+
+        let token_data =
+            decode::<EmailVerificationClaims>(verification_token, &self.decoding_key, &validation)
+                .map_err(|_| InvalidEmailVerificationTokenError {})?;
+
+        let account_id = odf::AccountID::from_did_str(&token_data.claims.sub)
+            .map_err(|_| InvalidEmailVerificationTokenError {})?;
+        let email = Email::parse(&token_data.claims.email)
+            .map_err(|_| InvalidEmailVerificationTokenError {})?;
+
+        Ok((account_id, email))
+    }
+
+    async fn send_email_verification_impl(&self, account: &Account) -> Result<(), InternalError> {
+        let verification_token = self.make_email_verification_token(account)?;
+
+        let verification_instructions =
+            if let Some(verification_url) = &self.registration_config.email_verification_url {
+                let mut verification_url = verification_url.clone();
+                verification_url
+                    .query_pairs_mut()
+                    .append_pair("token", &verification_token);
+                format!("Open the following link to confirm your email:\n\n{verification_url}")
+            } else {
+                format!("Use the following token to confirm your email:\n\n{verification_token}")
+            };
+
+        self.email_sender
+            .send_email(EmailMessage {
+                to: vec![account.email.clone()],
+                subject: String::from("Confirm your email"),
+                body: format!(
+                    "Welcome, {}!\n\n{verification_instructions}\n\nThe confirmation expires in \
+                     {} hour(s).\n",
+                    account.account_name,
+                    self.registration_config
+                        .email_verification_token_ttl
+                        .num_hours(),
+                ),
+            })
+            .await
+    }
+
+    fn password_auth_provider(&self) -> Option<&LoginPasswordAuthProvider> {
+        self.maybe_login_password_auth_provider.as_deref()
+    }
+
+    async fn is_account_email_verified(
+        &self,
+        account_id: &odf::AccountID,
+    ) -> Result<bool, InternalError> {
+        match self
+            .account_repository
+            .is_account_email_verified(account_id)
+            .await
+        {
+            Ok(email_verified) => Ok(email_verified),
+            Err(GetAccountByIdError::NotFound(_)) => Ok(true),
+            Err(GetAccountByIdError::Internal(e)) => Err(e),
         }
     }
 
+    async fn sync_account_admin_flag(
+        &self,
+        account_id: &odf::AccountID,
+        is_admin: bool,
+    ) -> Result<(), InternalError> {
+        let account = self
+            .account_repository
+            .get_account_by_id(account_id)
+            .await
+            .int_err()?;
+        if account.is_admin == is_admin {
+            return Ok(());
+        }
+
+        tracing::info!(%account_id, is_admin, "Updating admin flag reported by identity provider");
+
+        self.account_repository
+            .update_account(Account {
+                is_admin,
+                ..account
+            })
+            .await
+            .int_err()
+    }
+
     async fn notify_account_created(&self, new_account: &Account) -> Result<(), InternalError> {
         self.outbox
             .post_message(
@@ -233,8 +393,19 @@ impl AuthenticationService for AuthenticationServiceImpl {
             .await?;
 
         let account_id = match maybe_account_id {
-            // Account already exists
-            Some(account_id) => account_id,
+            // Account already exists, unless disabled by an administrator
+            Some(account_id) => {
+                if self.is_account_disabled(&account_id).await? {
+                    return Err(LoginError::RejectedCredentials(RejectedCredentialsError {}));
+                }
+                if !self.is_account_email_verified(&account_id).await? {
+                    return Err(LoginError::EmailNotVerified(EmailNotVerifiedError {}));
+                }
+                if let Some(is_admin) = provider_response.is_admin {
+                    self.sync_account_admin_flag(&account_id, is_admin).await?;
+                }
+                account_id
+            }
 
             // Account does not exist and needs to be created
             None => {
@@ -250,7 +421,7 @@ impl AuthenticationService for AuthenticationServiceImpl {
                     account_type: provider_response.account_type,
                     avatar_url: provider_response.avatar_url,
                     registered_at: Utc::now(),
-                    is_admin: false,
+                    is_admin: provider_response.is_admin.unwrap_or(false),
                     provider: String::from(login_method),
                     provider_identity_key: provider_response.provider_identity_key,
                 };
@@ -282,4 +453,171 @@ impl AuthenticationService for AuthenticationServiceImpl {
     async fn account_by_token(&self, access_token: String) -> Result<Account, GetAccountInfoError> {
         self.account_by_token_impl(&access_token).await
     }
+
+    #[tracing::instrument(level = "info", skip_all, fields(%account_name))]
+    async fn register_password_account(
+        &self,
+        account_name: &odf::AccountName,
+        email: Email,
+        password: String,
+    ) -> Result<Account, RegisterAccountError> {
+        let Some(password_auth_provider) = self.password_auth_provider() else {
+            return Err(RegisterAccountError::RegistrationDisabled(
+                AccountRegistrationDisabledError {},
+            ));
+        };
+        if !self.registration_config.enabled {
+            return Err(RegisterAccountError::RegistrationDisabled(
+                AccountRegistrationDisabledError {},
+            ));
+        }
+
+        // Names can be freed by renaming, so IDs must not be derived from them
+        let (_, account_id) = odf::AccountID::new_generated_ed25519();
+
+        let new_account = Account {
+            id: account_id,
+            account_name: account_name.clone(),
+            email,
+            display_name: account_name.to_string(),
+            account_type: AccountType::User,
+            avatar_url: None,
+            registered_at: self.time_source.now(),
+            is_admin: false,
+            provider: String::from(PROVIDER_PASSWORD),
+            provider_identity_key: account_name.to_string(),
+        };
+
+        self.account_repository
+            .create_account(&new_account)
+            .await
+            .map_err(|e| match e {
+                CreateAccountError::Duplicate(e) => RegisterAccountError::Duplicate(e),
+                CreateAccountError::Internal(e) => RegisterAccountError::Internal(e),
+            })?;
+
+        // The account stays unusable until the email is confirmed
+        self.account_repository
+            .set_account_email_verified(&new_account.id, false)
+            .await
+            .int_err()?;
+
+        password_auth_provider
+            .save_password(account_name, password)
+            .await?;
+
+        // The verification email is sent by `EmailVerificationNotifier` once
+        // the account is committed
+        self.notify_account_created(&new_account).await?;
+
+        Ok(new_account)
+    }
+
+    #[tracing::instrument(level = "info", skip_all, fields(%account_id))]
+    async fn send_email_verification(
+        &self,
+        account_id: &odf::AccountID,
+    ) -> Result<(), InternalError> {
+        let account = match self.account_repository.get_account_by_id(account_id).await {
+            Ok(account) => account,
+            Err(GetAccountByIdError::NotFound(_)) => {
+                tracing::warn!("Account no longer exists, skipping email verification");
+                return Ok(());
+            }
+            Err(GetAccountByIdError::Internal(e)) => return Err(e),
+        };
+
+        if self.is_account_email_verified(&account.id).await? {
+            return Ok(());
+        }
+
+        self.send_email_verification_impl(&account).await
+    }
+
+    #[tracing::instrument(level = "info", skip_all)]
+    async fn verify_email(
+        &self,
+        verification_token: &str,
+    ) -> Result<LoginResponse, VerifyEmailError> {
+        let (account_id, email) = self
+            .decode_email_verification_token(verification_token)
+            .map_err(VerifyEmailError::InvalidToken)?;
+
+        let account = match self.account_repository.get_account_by_id(&account_id).await {
+            Ok(account) => account,
+            Err(GetAccountByIdError::NotFound(_)) => {
+                return Err(VerifyEmailError::InvalidToken(
+                    InvalidEmailVerificationTokenError {},
+                ))
+            }
+            Err(GetAccountByIdError::Internal(e)) => return Err(VerifyEmailError::Internal(e)),
+        };
+
+        // Tokens issued for a previous email or a disabled account are not honored
+        if account.email != email || self.is_account_disabled(&account.id).await? {
+            return Err(VerifyEmailError::InvalidToken(
+                InvalidEmailVerificationTokenError {},
+            ));
+        }
+
+        self.account_repository
+            .set_account_email_verified(&account.id, true)
+            .await
+            .int_err()?;
+
+        Ok(LoginResponse {
+            access_token: self.make_access_token(&account.id, EXPIRATION_TIME_SEC)?,
+            account_id: account.id,
+            account_name: account.account_name,
+        })
+    }
+
+    #[tracing::instrument(level = "info", skip_all, fields(account_id = %account.id))]
+    async fn change_password(
+        &self,
+        account: &Account,
+        old_password: String,
+        new_password: String,
+    ) -> Result<(), ChangePasswordError> {
+        let Some(password_auth_provider) = self.password_auth_provider() else {
+            return Err(ChangePasswordError::NotPasswordAccount(
+                NotPasswordAccountError {
+                    account_name: account.account_name.clone(),
+                },
+            ));
+        };
+        if account.provider != PROVIDER_PASSWORD {
+            return Err(ChangePasswordError::NotPasswordAccount(
+                NotPasswordAccountError {
+                    account_name: account.account_name.clone(),
+                },
+            ));
+        }
+
+        password_auth_provider
+            .verify_password(&account.account_name, old_password)
+            .await
+            .map_err(|e| match e {
+                ProviderLoginError::RejectedCredentials(e) => ChangePasswordError::WrongPassword(e),
+                e => ChangePasswordError::Internal(e.int_err()),
+            })?;
+
+        password_auth_provider
+            .update_password(&account.account_name, new_password)
+            .await?;
+
+        Ok(())
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[derive(Debug, Serialize, Deserialize)]
+struct EmailVerificationClaims {
+    exp: usize,
+    iat: usize,
+    iss: String,
+    aud: String,
+    sub: String,
+    email: String,
 }
@@ -0,0 +1,80 @@
+// Copyright Kamu Data, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use dill::{component, interface, meta, Catalog};
+use internal_error::InternalError;
+use messaging_outbox::{
+    MessageConsumer,
+    MessageConsumerMeta,
+    MessageConsumerT,
+    MessageDeliveryMechanism,
+};
+
+use crate::domain::*;
+use crate::MESSAGE_CONSUMER_KAMU_EMAIL_VERIFICATION_NOTIFIER;
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+/// Sends verification emails to registered accounts and to accounts that
+/// changed their email, after the change is committed
+pub struct EmailVerificationNotifier {}
+
+#[component(pub)]
+#[interface(dyn MessageConsumer)]
+#[interface(dyn MessageConsumerT<AccountLifecycleMessage>)]
+#[meta(MessageConsumerMeta {
+    consumer_name: MESSAGE_CONSUMER_KAMU_EMAIL_VERIFICATION_NOTIFIER,
+    feeding_producers: &[
+        MESSAGE_PRODUCER_KAMU_ACCOUNTS_SERVICE,
+    ],
+    delivery: MessageDeliveryMechanism::Transactional,
+})]
+impl EmailVerificationNotifier {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+impl MessageConsumer for EmailVerificationNotifier {}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[async_trait::async_trait]
+impl MessageConsumerT<AccountLifecycleMessage> for EmailVerificationNotifier {
+    #[tracing::instrument(
+        level = "debug",
+        skip_all,
+        name = "EmailVerificationNotifier[AccountLifecycleMessage]"
+    )]
+    async fn consume_message(
+        &self,
+        target_catalog: &Catalog,
+        message: &AccountLifecycleMessage,
+    ) -> Result<(), InternalError> {
+        tracing::debug!(received_message = ?message, "Received account lifecycle message");
+
+        let account_id = match message {
+            AccountLifecycleMessage::Created(message) => &message.account_id,
+            AccountLifecycleMessage::EmailChanged(message) => &message.account_id,
+            AccountLifecycleMessage::Deleted(_) => return Ok(()),
+        };
+
+        // Accounts with an already confirmed email are skipped by the service
+        let authentication_service = target_catalog
+            .get_one::<dyn AuthenticationService>()
+            .unwrap();
+        authentication_service
+            .send_email_verification(account_id)
+            .await
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
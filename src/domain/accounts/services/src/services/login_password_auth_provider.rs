@@ -50,12 +50,92 @@ impl LoginPasswordAuthProvider {
         account_name: &odf::AccountName,
         password: String,
     ) -> Result<(), InternalError> {
+        let password_hash = self.make_password_hash(password).await?;
+
+        // Save hash in the repository
+        self.password_hash_repository
+            .save_password_hash(account_name, password_hash)
+            .await
+            .int_err()?;
+
+        Ok(())
+    }
+
+    pub async fn update_password(
+        &self,
+        account_name: &odf::AccountName,
+        password: String,
+    ) -> Result<(), InternalError> {
+        let password_hash = self.make_password_hash(password).await?;
+
+        // Replace hash in the repository
+        self.password_hash_repository
+            .update_password_hash(account_name, password_hash)
+            .await
+            .int_err()?;
+
+        Ok(())
+    }
+
+    /// Checks the password against the hash stored for the account
+    pub async fn verify_password(
+        &self,
+        account_name: &odf::AccountName,
+        password: String,
+    ) -> Result<(), ProviderLoginError> {
+        // Locate password hash associated with this account name
+        let password_hash = match self
+            .password_hash_repository
+            .find_password_hash_by_account_name(account_name)
+            .await
+        {
+            // Found
+            Ok(Some(password_hash)) => password_hash,
+
+            // Not found => error
+            Ok(None) => {
+                return Err(ProviderLoginError::RejectedCredentials(
+                    RejectedCredentialsError {},
+                ));
+            }
+
+            // Internal issue => error
+            Err(e) => match e {
+                FindPasswordHashError::Internal(e) => return Err(ProviderLoginError::Internal(e)),
+            },
+        };
+
+        // Copy hashing mode
+        let hashing_mode = self.password_hashing_mode;
+
+        // Verify password hash: this is a compute-intensive operation,
+        // so spawn a blocking task
+        tokio::task::spawn_blocking(move || {
+            tracing::info_span!("Verify password hash").in_scope(|| {
+                let password_hash = PasswordHash::new(password_hash.as_str()).unwrap();
+
+                // Setup Argon2 matching the hashing mode
+                let argon2 = Self::setup_argon2(hashing_mode);
+                argon2
+                    .verify_password(password.as_bytes(), &password_hash)
+                    .map_err(|_| {
+                        ProviderLoginError::RejectedCredentials(RejectedCredentialsError {})
+                    })
+            })
+        })
+        .await
+        .int_err()??;
+
+        Ok(())
+    }
+
+    async fn make_password_hash(&self, password: String) -> Result<String, InternalError> {
         // Copy hashing mod
         let hashing_mode = self.password_hashing_mode;
 
         // Generate password hash: this is a compute-intensive operation,
         // so spawn a blocking task
-        let password_hash = tokio::task::spawn_blocking(move || {
+        tokio::task::spawn_blocking(move || {
             tracing::info_span!("Generate password hash").in_scope(|| {
                 // Generate random salt string
                 let salt = SaltString::generate(&mut OsRng);
@@ -72,15 +152,7 @@ impl LoginPasswordAuthProvider {
             })
         })
         .await
-        .int_err()?;
-
-        // Save hash in the repository
-        self.password_hash_repository
-            .save_password_hash(account_name, password_hash)
-            .await
-            .int_err()?;
-
-        Ok(())
+        .int_err()
     }
 
     fn setup_argon2<'a>(password_hashing_mode: PasswordHashingMode) -> argon2::Argon2<'a> {
@@ -135,51 +207,9 @@ impl AuthenticationProvider for LoginPasswordAuthProvider {
         let account_name =
             odf::AccountName::from_str(&password_login_credentials.login).int_err()?;
 
-        // Locate password hash associated with this account name
-        let password_hash = match self
-            .password_hash_repository
-            .find_password_hash_by_account_name(&account_name)
-            .await
-        {
-            // Found
-            Ok(Some(password_hash)) => password_hash,
-
-            // Not found => error
-            Ok(None) => {
-                return Err(ProviderLoginError::RejectedCredentials(
-                    RejectedCredentialsError {},
-                ));
-            }
-
-            // Internal issue => error
-            Err(e) => match e {
-                FindPasswordHashError::Internal(e) => return Err(ProviderLoginError::Internal(e)),
-            },
-        };
-
-        // Copy hashing mode
-        let hashing_mode = self.password_hashing_mode;
-
-        // Verify password hash: this is a compute-intensive operation,
-        // so spawn a blocking task
-        tokio::task::spawn_blocking(move || {
-            tracing::info_span!("Verify password hash").in_scope(|| {
-                let password_hash = PasswordHash::new(password_hash.as_str()).unwrap();
-
-                // Setup Argon2 matching the hashing mode
-                let argon2 = Self::setup_argon2(hashing_mode);
-                argon2
-                    .verify_password(
-                        password_login_credentials.password.as_bytes(),
-                        &password_hash,
-                    )
-                    .map_err(|_| {
-                        ProviderLoginError::RejectedCredentials(RejectedCredentialsError {})
-                    })
-            })
-        })
-        .await
-        .int_err()??;
+        // Verify password against the stored hash
+        self.verify_password(&account_name, password_login_credentials.password)
+            .await?;
 
         // Extract known account data
         let account = self
@@ -195,6 +225,7 @@ impl AuthenticationProvider for LoginPasswordAuthProvider {
             account_type: account.account_type,
             avatar_url: account.avatar_url.clone(),
             provider_identity_key: password_login_credentials.login.to_ascii_lowercase(),
+            is_admin: None,
         })
     }
 }
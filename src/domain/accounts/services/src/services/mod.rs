@@ -7,14 +7,22 @@
 // the Business Source License, use of this software will be governed
 // by the Apache License, Version 2.0.
 
+mod access_token_account_lifecycle_message_consumer;
 mod access_token_service_impl;
+mod account_management_service_impl;
 mod account_service_impl;
 mod authentication_service_impl;
+mod email_verification_notifier;
 mod login_password_auth_provider;
+mod password_account_lifecycle_message_consumer;
 mod predefined_accounts_registrator;
 
+pub use access_token_account_lifecycle_message_consumer::*;
 pub use access_token_service_impl::*;
+pub use account_management_service_impl::*;
 pub use account_service_impl::*;
 pub use authentication_service_impl::*;
+pub use email_verification_notifier::*;
 pub use login_password_auth_provider::*;
+pub use password_account_lifecycle_message_consumer::*;
 pub use predefined_accounts_registrator::*;
@@ -0,0 +1,81 @@
+// Copyright Kamu Data, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::sync::Arc;
+
+use dill::{component, interface, meta, Catalog};
+use internal_error::{InternalError, ResultIntoInternal};
+use messaging_outbox::{
+    MessageConsumer,
+    MessageConsumerMeta,
+    MessageConsumerT,
+    MessageDeliveryMechanism,
+};
+
+use crate::domain::*;
+use crate::MESSAGE_CONSUMER_KAMU_LOGIN_PASSWORD_AUTH_PROVIDER;
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+/// Removes password hashes of deleted accounts
+pub struct PasswordAccountLifecycleMessageConsumer {
+    password_hash_repo: Arc<dyn PasswordHashRepository>,
+}
+
+#[component(pub)]
+#[interface(dyn MessageConsumer)]
+#[interface(dyn MessageConsumerT<AccountLifecycleMessage>)]
+#[meta(MessageConsumerMeta {
+    consumer_name: MESSAGE_CONSUMER_KAMU_LOGIN_PASSWORD_AUTH_PROVIDER,
+    feeding_producers: &[
+        MESSAGE_PRODUCER_KAMU_ACCOUNTS_SERVICE,
+    ],
+    delivery: MessageDeliveryMechanism::Immediate,
+})]
+impl PasswordAccountLifecycleMessageConsumer {
+    pub fn new(password_hash_repo: Arc<dyn PasswordHashRepository>) -> Self {
+        Self { password_hash_repo }
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+impl MessageConsumer for PasswordAccountLifecycleMessageConsumer {}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[async_trait::async_trait]
+impl MessageConsumerT<AccountLifecycleMessage> for PasswordAccountLifecycleMessageConsumer {
+    #[tracing::instrument(
+        level = "debug",
+        skip_all,
+        name = "PasswordAccountLifecycleMessageConsumer[AccountLifecycleMessage]"
+    )]
+    async fn consume_message(
+        &self,
+        _: &Catalog,
+        message: &AccountLifecycleMessage,
+    ) -> Result<(), InternalError> {
+        tracing::debug!(received_message = ?message, "Received account lifecycle message");
+
+        match message {
+            AccountLifecycleMessage::Created(_) | AccountLifecycleMessage::EmailChanged(_) => {
+                Ok(())
+            }
+
+            AccountLifecycleMessage::Deleted(message) => self
+                .password_hash_repo
+                .delete_password_hash(&message.account_name)
+                .await
+                .int_err(),
+        }
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
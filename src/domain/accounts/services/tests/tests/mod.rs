@@ -9,5 +9,7 @@
 
 mod test_access_token;
 mod test_access_token_service_impl;
+mod test_account_management_service;
+mod test_account_self_service;
 mod test_account_service;
 mod test_authentication_service;
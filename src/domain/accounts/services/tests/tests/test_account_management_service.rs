@@ -0,0 +1,359 @@
+// Copyright Kamu Data, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::assert_matches::assert_matches;
+
+use database_common::{DatabaseTransactionRunner, NoOpDatabasePlugin};
+use email_utils::Email;
+use kamu_accounts::*;
+use kamu_accounts_inmem::{InMemoryAccessTokenRepository, InMemoryAccountRepository};
+use kamu_accounts_services::{
+    AccessTokenAccountLifecycleMessageConsumer,
+    AccessTokenServiceImpl,
+    AccountManagementServiceImpl,
+    AuthenticationServiceImpl,
+    LoginPasswordAuthProvider,
+    PasswordAccountLifecycleMessageConsumer,
+    PasswordHashingMode,
+    PasswordLoginCredentials,
+};
+use messaging_outbox::{register_message_dispatcher, Outbox, OutboxImmediateImpl};
+use time_source::{SystemTimeSource, SystemTimeSourceStub};
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+const WASYA: &str = "wasya";
+const PASSWORD: &str = "password";
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[test_log::test(tokio::test)]
+async fn test_create_password_account() {
+    let harness = AccountManagementHarness::new();
+
+    let account = harness.create_wasya().await;
+    assert_eq!(account.provider, PROVIDER_PASSWORD);
+    assert_eq!(account.provider_identity_key, WASYA);
+
+    assert_matches!(harness.login(WASYA, PASSWORD).await, Ok(r) if r.account_id == account.id);
+    assert_matches!(
+        harness.login(WASYA, "wrong").await,
+        Err(LoginError::RejectedCredentials(_))
+    );
+
+    assert_matches!(
+        harness
+            .account_management_svc
+            .create_password_account(
+                &odf::AccountName::new_unchecked(WASYA),
+                Email::parse("another@example.com").unwrap(),
+                PASSWORD.to_string(),
+            )
+            .await,
+        Err(CreatePasswordAccountError::Duplicate(_))
+    );
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[test_log::test(tokio::test)]
+async fn test_rename_password_account() {
+    let harness = AccountManagementHarness::new();
+
+    let account = harness.create_wasya().await;
+
+    let renamed_account = harness
+        .account_management_svc
+        .rename_account(&account, &odf::AccountName::new_unchecked("vasily"))
+        .await
+        .unwrap();
+    assert_eq!(renamed_account.id, account.id);
+    assert_eq!(renamed_account.provider_identity_key, "vasily");
+
+    assert_matches!(
+        harness.login(WASYA, PASSWORD).await,
+        Err(LoginError::RejectedCredentials(_))
+    );
+    assert_matches!(harness.login("vasily", PASSWORD).await, Ok(r) if r.account_id == account.id);
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[test_log::test(tokio::test)]
+async fn test_reuse_name_of_renamed_password_account() {
+    let harness = AccountManagementHarness::new();
+
+    let account = harness.create_wasya().await;
+    harness
+        .account_management_svc
+        .rename_account(&account, &odf::AccountName::new_unchecked("vasily"))
+        .await
+        .unwrap();
+
+    // The freed name can be taken by a new, unrelated account
+    let new_account = harness
+        .account_management_svc
+        .create_password_account(
+            &odf::AccountName::new_unchecked(WASYA),
+            Email::parse("another-wasya@example.com").unwrap(),
+            PASSWORD.to_string(),
+        )
+        .await
+        .unwrap();
+    assert_ne!(new_account.id, account.id);
+
+    assert_matches!(harness.login(WASYA, PASSWORD).await, Ok(r) if r.account_id == new_account.id);
+    assert_matches!(harness.login("vasily", PASSWORD).await, Ok(r) if r.account_id == account.id);
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[test_log::test(tokio::test)]
+async fn test_reset_password() {
+    let harness = AccountManagementHarness::new();
+
+    let account = harness.create_wasya().await;
+
+    harness
+        .account_management_svc
+        .reset_password(&account, "new-password".to_string())
+        .await
+        .unwrap();
+
+    assert_matches!(
+        harness.login(WASYA, PASSWORD).await,
+        Err(LoginError::RejectedCredentials(_))
+    );
+    assert_matches!(harness.login(WASYA, "new-password").await, Ok(_));
+
+    let github_account = Account {
+        provider: "oauth_github".to_string(),
+        ..account
+    };
+    assert_matches!(
+        harness
+            .account_management_svc
+            .reset_password(&github_account, "new-password".to_string())
+            .await,
+        Err(ResetPasswordError::NotPasswordAccount(_))
+    );
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[test_log::test(tokio::test)]
+async fn test_disable_and_delete_account() {
+    let harness = AccountManagementHarness::new();
+
+    let account = harness.create_wasya().await;
+
+    harness
+        .account_management_svc
+        .set_account_disabled(&account, true)
+        .await
+        .unwrap();
+    assert_matches!(
+        harness
+            .account_management_svc
+            .is_account_disabled(&account)
+            .await,
+        Ok(true)
+    );
+    assert_matches!(
+        harness.login(WASYA, PASSWORD).await,
+        Err(LoginError::RejectedCredentials(_))
+    );
+
+    harness
+        .access_token_svc
+        .create_access_token("token", &account.id)
+        .await
+        .unwrap();
+
+    harness
+        .account_management_svc
+        .delete_account(&account)
+        .await
+        .unwrap();
+    assert_matches!(
+        harness.login(WASYA, PASSWORD).await,
+        Err(LoginError::RejectedCredentials(_))
+    );
+
+    // Credentials are removed by the consumers of the deletion message
+    assert_matches!(
+        harness
+            .password_hash_repo
+            .find_password_hash_by_account_name(&account.account_name)
+            .await,
+        Ok(None)
+    );
+    assert_matches!(
+        harness
+            .access_token_repo
+            .get_access_tokens_count_by_account_id(&account.id)
+            .await,
+        Ok(0)
+    );
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[test_log::test(tokio::test)]
+async fn test_delete_account_blocked_by_guard() {
+    let harness = AccountManagementHarness::new_with_deletion_guard(RejectingAccountDeletionGuard);
+
+    let account = harness.create_wasya().await;
+
+    assert_matches!(
+        harness
+            .account_management_svc
+            .delete_account(&account)
+            .await,
+        Err(AccountDeletionError::Blocked(_))
+    );
+
+    // Nothing is deleted
+    assert_matches!(harness.login(WASYA, PASSWORD).await, Ok(_));
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[test_log::test(tokio::test)]
+async fn test_revoke_access_tokens() {
+    let harness = AccountManagementHarness::new();
+
+    let account = harness.create_wasya().await;
+
+    for token_name in ["token-1", "token-2"] {
+        harness
+            .access_token_svc
+            .create_access_token(token_name, &account.id)
+            .await
+            .unwrap();
+    }
+
+    assert_matches!(
+        harness
+            .account_management_svc
+            .revoke_access_tokens(&account)
+            .await,
+        Ok(2)
+    );
+    // Already revoked tokens are not counted twice
+    assert_matches!(
+        harness
+            .account_management_svc
+            .revoke_access_tokens(&account)
+            .await,
+        Ok(0)
+    );
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+struct RejectingAccountDeletionGuard;
+
+#[async_trait::async_trait]
+impl AccountDeletionGuard for RejectingAccountDeletionGuard {
+    async fn check_account_deletion(&self, account: &Account) -> Result<(), AccountDeletionError> {
+        Err(AccountDeletionError::Blocked(AccountDeletionBlockedError {
+            account_name: account.account_name.clone(),
+            reason: AccountDeletionBlockedReason::Other("rejected".to_string()),
+        }))
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+struct AccountManagementHarness {
+    account_management_svc: std::sync::Arc<dyn AccountManagementService>,
+    authentication_svc: std::sync::Arc<dyn AuthenticationService>,
+    access_token_svc: std::sync::Arc<dyn AccessTokenService>,
+    access_token_repo: std::sync::Arc<dyn AccessTokenRepository>,
+    password_hash_repo: std::sync::Arc<dyn PasswordHashRepository>,
+}
+
+impl AccountManagementHarness {
+    fn new() -> Self {
+        Self::build(dill::CatalogBuilder::new())
+    }
+
+    fn new_with_deletion_guard<G: AccountDeletionGuard + 'static>(deletion_guard: G) -> Self {
+        let mut b = dill::CatalogBuilder::new();
+        b.add_value(deletion_guard)
+            .bind::<dyn AccountDeletionGuard, G>();
+        Self::build(b)
+    }
+
+    fn build(mut b: dill::CatalogBuilder) -> Self {
+        b.add_builder(
+            OutboxImmediateImpl::builder()
+                .with_consumer_filter(messaging_outbox::ConsumerFilter::AllConsumers),
+        )
+        .bind::<dyn Outbox, OutboxImmediateImpl>()
+        .add::<AccountManagementServiceImpl>()
+        .add::<AccessTokenAccountLifecycleMessageConsumer>()
+        .add::<PasswordAccountLifecycleMessageConsumer>()
+        .add::<AuthenticationServiceImpl>()
+        .add::<email_utils::DummyEmailSender>()
+        .add::<LoginPasswordAuthProvider>()
+        .add_value(PasswordHashingMode::Minimal)
+        .add::<InMemoryAccountRepository>()
+        .add::<AccessTokenServiceImpl>()
+        .add::<InMemoryAccessTokenRepository>()
+        .add_value(SystemTimeSourceStub::new())
+        .bind::<dyn SystemTimeSource, SystemTimeSourceStub>()
+        .add_value(JwtAuthenticationConfig::default())
+        .add::<DatabaseTransactionRunner>();
+
+        register_message_dispatcher::<AccountLifecycleMessage>(
+            &mut b,
+            MESSAGE_PRODUCER_KAMU_ACCOUNTS_SERVICE,
+        );
+
+        NoOpDatabasePlugin::init_database_components(&mut b);
+
+        let catalog = b.build();
+
+        Self {
+            account_management_svc: catalog.get_one().unwrap(),
+            authentication_svc: catalog.get_one().unwrap(),
+            access_token_svc: catalog.get_one().unwrap(),
+            access_token_repo: catalog.get_one().unwrap(),
+            password_hash_repo: catalog.get_one().unwrap(),
+        }
+    }
+
+    async fn create_wasya(&self) -> Account {
+        self.account_management_svc
+            .create_password_account(
+                &odf::AccountName::new_unchecked(WASYA),
+                Email::parse("wasya@example.com").unwrap(),
+                PASSWORD.to_string(),
+            )
+            .await
+            .unwrap()
+    }
+
+    async fn login(&self, login: &str, password: &str) -> Result<LoginResponse, LoginError> {
+        self.authentication_svc
+            .login(
+                PROVIDER_PASSWORD,
+                serde_json::to_string(&PasswordLoginCredentials {
+                    login: login.to_string(),
+                    password: password.to_string(),
+                })
+                .unwrap(),
+            )
+            .await
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
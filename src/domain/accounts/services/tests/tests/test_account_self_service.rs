@@ -0,0 +1,406 @@
+// Copyright Kamu Data, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::assert_matches::assert_matches;
+use std::sync::Arc;
+
+use database_common::{DatabaseTransactionRunner, NoOpDatabasePlugin};
+use email_utils::{DummyEmailSender, Email};
+use kamu_accounts::*;
+use kamu_accounts_inmem::{InMemoryAccessTokenRepository, InMemoryAccountRepository};
+use kamu_accounts_services::{
+    AccessTokenServiceImpl,
+    AccountManagementServiceImpl,
+    AuthenticationServiceImpl,
+    EmailVerificationNotifier,
+    LoginPasswordAuthProvider,
+    PasswordHashingMode,
+    PasswordLoginCredentials,
+};
+use messaging_outbox::{MessageConsumerT, MockOutbox, Outbox};
+use mockall::predicate::{eq, function};
+use time_source::{SystemTimeSource, SystemTimeSourceStub};
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+const WASYA: &str = "wasya";
+const PASSWORD: &str = "password";
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[test_log::test(tokio::test)]
+async fn test_register_and_verify_email() {
+    let harness = SelfServiceHarness::new(true, accepting_outbox());
+
+    let account = harness.register_wasya().await.unwrap();
+    assert_eq!(account.provider, PROVIDER_PASSWORD);
+
+    // The account can't be used until the email is confirmed
+    assert_matches!(
+        harness.login(WASYA, PASSWORD).await,
+        Err(LoginError::EmailNotVerified(_))
+    );
+
+    let sent_emails = harness.email_sender.sent_emails();
+    assert_eq!(sent_emails.len(), 1);
+    assert_eq!(sent_emails[0].to, vec![account.email.clone()]);
+
+    let verification_token = harness.verification_token_from_last_email();
+    let login_response = harness
+        .authentication_svc
+        .verify_email(&verification_token)
+        .await
+        .unwrap();
+    assert_eq!(login_response.account_id, account.id);
+
+    assert_matches!(
+        harness
+            .authentication_svc
+            .account_by_token(login_response.access_token)
+            .await,
+        Ok(a) if a.id == account.id
+    );
+    assert_matches!(harness.login(WASYA, PASSWORD).await, Ok(r) if r.account_id == account.id);
+
+    assert_matches!(
+        harness.register_wasya().await,
+        Err(RegisterAccountError::Duplicate(_))
+    );
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[test_log::test(tokio::test)]
+async fn test_verification_email_is_sent_only_after_delivery() {
+    let harness = SelfServiceHarness::new(true, accepting_outbox());
+
+    let account = harness.register_wasya_without_delivery().await.unwrap();
+
+    // Nothing is sent while the registration transaction may still roll back
+    assert!(harness.email_sender.sent_emails().is_empty());
+
+    harness
+        .deliver_message(AccountLifecycleMessage::created(
+            account.id.clone(),
+            account.email.clone(),
+            account.display_name.clone(),
+        ))
+        .await;
+
+    let sent_emails = harness.email_sender.sent_emails();
+    assert_eq!(sent_emails.len(), 1);
+    assert_eq!(sent_emails[0].to, vec![account.email]);
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[test_log::test(tokio::test)]
+async fn test_changed_email_requires_verification() {
+    let harness = SelfServiceHarness::new(true, accepting_outbox());
+
+    let account = harness.register_wasya().await.unwrap();
+    let verification_token = harness.verification_token_from_last_email();
+    harness
+        .authentication_svc
+        .verify_email(&verification_token)
+        .await
+        .unwrap();
+
+    let new_email = Email::parse("vasily@example.com").unwrap();
+    harness
+        .account_management_svc
+        .update_account_email(&account, new_email.clone())
+        .await
+        .unwrap();
+
+    assert_matches!(
+        harness.login(WASYA, PASSWORD).await,
+        Err(LoginError::EmailNotVerified(_))
+    );
+
+    harness
+        .deliver_message(AccountLifecycleMessage::email_changed(
+            account.id.clone(),
+            new_email.clone(),
+        ))
+        .await;
+
+    let sent_emails = harness.email_sender.sent_emails();
+    assert_eq!(sent_emails.len(), 2);
+    assert_eq!(sent_emails[1].to, vec![new_email]);
+
+    let verification_token = harness.verification_token_from_last_email();
+    harness
+        .authentication_svc
+        .verify_email(&verification_token)
+        .await
+        .unwrap();
+    assert_matches!(harness.login(WASYA, PASSWORD).await, Ok(r) if r.account_id == account.id);
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[test_log::test(tokio::test)]
+async fn test_registration_disabled() {
+    let harness = SelfServiceHarness::new(false, MockOutbox::new());
+
+    assert_matches!(
+        harness.register_wasya().await,
+        Err(RegisterAccountError::RegistrationDisabled(_))
+    );
+    assert!(harness.email_sender.sent_emails().is_empty());
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[test_log::test(tokio::test)]
+async fn test_verify_email_invalid_token() {
+    let harness = SelfServiceHarness::new(true, accepting_outbox());
+
+    assert_matches!(
+        harness.authentication_svc.verify_email("garbage").await,
+        Err(VerifyEmailError::InvalidToken(_))
+    );
+
+    // Access tokens can't be used to confirm an email
+    harness.register_wasya().await.unwrap();
+    let verification_token = harness.verification_token_from_last_email();
+    let login_response = harness
+        .authentication_svc
+        .verify_email(&verification_token)
+        .await
+        .unwrap();
+    assert_matches!(
+        harness
+            .authentication_svc
+            .verify_email(&login_response.access_token)
+            .await,
+        Err(VerifyEmailError::InvalidToken(_))
+    );
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[test_log::test(tokio::test)]
+async fn test_change_password() {
+    let harness = SelfServiceHarness::new(true, accepting_outbox());
+
+    let account = harness.register_wasya().await.unwrap();
+    let verification_token = harness.verification_token_from_last_email();
+    harness
+        .authentication_svc
+        .verify_email(&verification_token)
+        .await
+        .unwrap();
+
+    assert_matches!(
+        harness
+            .authentication_svc
+            .change_password(&account, "wrong".to_string(), "new-password".to_string())
+            .await,
+        Err(ChangePasswordError::WrongPassword(_))
+    );
+
+    harness
+        .authentication_svc
+        .change_password(&account, PASSWORD.to_string(), "new-password".to_string())
+        .await
+        .unwrap();
+
+    assert_matches!(
+        harness.login(WASYA, PASSWORD).await,
+        Err(LoginError::RejectedCredentials(_))
+    );
+    assert_matches!(harness.login(WASYA, "new-password").await, Ok(_));
+
+    let github_account = Account {
+        provider: "oauth_github".to_string(),
+        ..account
+    };
+    assert_matches!(
+        harness
+            .authentication_svc
+            .change_password(
+                &github_account,
+                "new-password".to_string(),
+                "another-password".to_string()
+            )
+            .await,
+        Err(ChangePasswordError::NotPasswordAccount(_))
+    );
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[test_log::test(tokio::test)]
+async fn test_delete_account_notifies_consumers() {
+    let mut mock_outbox = MockOutbox::new();
+    mock_outbox
+        .expect_post_message_as_json()
+        .with(
+            eq(MESSAGE_PRODUCER_KAMU_ACCOUNTS_SERVICE),
+            function(|message_as_json: &serde_json::Value| {
+                matches!(
+                    serde_json::from_value::<AccountLifecycleMessage>(message_as_json.clone()),
+                    Ok(AccountLifecycleMessage::Created(_))
+                )
+            }),
+            eq(1),
+        )
+        .times(1)
+        .returning(|_, _, _| Ok(()));
+    mock_outbox
+        .expect_post_message_as_json()
+        .with(
+            eq(MESSAGE_PRODUCER_KAMU_ACCOUNTS_SERVICE),
+            function(|message_as_json: &serde_json::Value| {
+                matches!(
+                    serde_json::from_value::<AccountLifecycleMessage>(message_as_json.clone()),
+                    Ok(AccountLifecycleMessage::Deleted(AccountLifecycleMessageDeleted {
+                        account_name,
+                        ..
+                    })) if account_name.as_str() == WASYA
+                )
+            }),
+            eq(1),
+        )
+        .times(1)
+        .returning(|_, _, _| Ok(()));
+
+    let harness = SelfServiceHarness::new(true, mock_outbox);
+
+    let account = harness.register_wasya().await.unwrap();
+
+    harness
+        .account_management_svc
+        .delete_account(&account)
+        .await
+        .unwrap();
+
+    assert_matches!(
+        harness.account_repo.get_account_by_id(&account.id).await,
+        Err(GetAccountByIdError::NotFound(_))
+    );
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+fn accepting_outbox() -> MockOutbox {
+    let mut mock_outbox = MockOutbox::new();
+    mock_outbox
+        .expect_post_message_as_json()
+        .returning(|_, _, _| Ok(()));
+    mock_outbox
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+struct SelfServiceHarness {
+    catalog: dill::Catalog,
+    account_management_svc: Arc<dyn AccountManagementService>,
+    authentication_svc: Arc<dyn AuthenticationService>,
+    email_sender: Arc<DummyEmailSender>,
+    account_repo: Arc<dyn AccountRepository>,
+}
+
+impl SelfServiceHarness {
+    fn new(registration_enabled: bool, mock_outbox: MockOutbox) -> Self {
+        let mut b = dill::CatalogBuilder::new();
+        b.add::<AccountManagementServiceImpl>()
+            .add::<AuthenticationServiceImpl>()
+            .add::<EmailVerificationNotifier>()
+            .add::<DummyEmailSender>()
+            .add_value(AccountRegistrationConfig {
+                enabled: registration_enabled,
+                ..Default::default()
+            })
+            .add::<LoginPasswordAuthProvider>()
+            .add_value(PasswordHashingMode::Minimal)
+            .add::<InMemoryAccountRepository>()
+            .add::<AccessTokenServiceImpl>()
+            .add::<InMemoryAccessTokenRepository>()
+            .add_value(SystemTimeSourceStub::new())
+            .bind::<dyn SystemTimeSource, SystemTimeSourceStub>()
+            .add_value(JwtAuthenticationConfig::default())
+            .add::<DatabaseTransactionRunner>()
+            .add_value(mock_outbox)
+            .bind::<dyn Outbox, MockOutbox>();
+
+        NoOpDatabasePlugin::init_database_components(&mut b);
+
+        let catalog = b.build();
+
+        Self {
+            account_management_svc: catalog.get_one().unwrap(),
+            authentication_svc: catalog.get_one().unwrap(),
+            email_sender: catalog.get_one().unwrap(),
+            account_repo: catalog.get_one().unwrap(),
+            catalog,
+        }
+    }
+
+    async fn register_wasya(&self) -> Result<Account, RegisterAccountError> {
+        let account = self.register_wasya_without_delivery().await?;
+
+        // The outbox is mocked, so deliver the committed message by hand
+        self.deliver_message(AccountLifecycleMessage::created(
+            account.id.clone(),
+            account.email.clone(),
+            account.display_name.clone(),
+        ))
+        .await;
+
+        Ok(account)
+    }
+
+    async fn register_wasya_without_delivery(&self) -> Result<Account, RegisterAccountError> {
+        self.authentication_svc
+            .register_password_account(
+                &odf::AccountName::new_unchecked(WASYA),
+                Email::parse("wasya@example.com").unwrap(),
+                PASSWORD.to_string(),
+            )
+            .await
+    }
+
+    async fn deliver_message(&self, message: AccountLifecycleMessage) {
+        let notifier = self.catalog.get_one::<EmailVerificationNotifier>().unwrap();
+        notifier
+            .consume_message(&self.catalog, &message)
+            .await
+            .unwrap();
+    }
+
+    fn verification_token_from_last_email(&self) -> String {
+        let last_email = self.email_sender.sent_emails().pop().unwrap();
+
+        // Without a configured verification page the token is on its own line
+        last_email
+            .body
+            .lines()
+            .find(|line| line.split('.').count() == 3 && !line.contains(' '))
+            .unwrap()
+            .to_string()
+    }
+
+    async fn login(&self, login: &str, password: &str) -> Result<LoginResponse, LoginError> {
+        self.authentication_svc
+            .login(
+                PROVIDER_PASSWORD,
+                serde_json::to_string(&PasswordLoginCredentials {
+                    login: login.to_string(),
+                    password: password.to_string(),
+                })
+                .unwrap(),
+            )
+            .await
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
@@ -8,6 +8,8 @@
 // by the Apache License, Version 2.0.
 
 use std::assert_matches::assert_matches;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
 
 use database_common::{DatabaseTransactionRunner, NoOpDatabasePlugin};
 use kamu_accounts::*;
@@ -100,12 +102,105 @@ async fn test_use_bad_access_token() {
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
 
+#[test_log::test(tokio::test)]
+async fn test_disabled_account_is_rejected() {
+    let mut mock_outbox = MockOutbox::new();
+    expect_outbox_account_created(&mut mock_outbox);
+
+    let catalog = make_catalog(mock_outbox);
+    let authentication_service = catalog.get_one::<dyn AuthenticationService>().unwrap();
+    let account_repo = catalog.get_one::<dyn AccountRepository>().unwrap();
+
+    let login_response = authentication_service
+        .login("method-A", "dummy".to_string())
+        .await
+        .unwrap();
+
+    account_repo
+        .set_account_disabled(&login_response.account_id, true)
+        .await
+        .unwrap();
+
+    assert_matches!(
+        authentication_service
+            .account_by_token(login_response.access_token.clone())
+            .await,
+        Err(GetAccountInfoError::AccountUnresolved)
+    );
+    assert_matches!(
+        authentication_service
+            .login("method-A", "dummy".to_string())
+            .await,
+        Err(LoginError::RejectedCredentials(_))
+    );
+
+    account_repo
+        .set_account_disabled(&login_response.account_id, false)
+        .await
+        .unwrap();
+
+    assert_matches!(
+        authentication_service
+            .account_by_token(login_response.access_token)
+            .await,
+        Ok(_)
+    );
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[test_log::test(tokio::test)]
+async fn test_admin_flag_reported_by_provider() {
+    let mut mock_outbox = MockOutbox::new();
+    expect_outbox_account_created(&mut mock_outbox);
+
+    let admin_provider = DummyAdminAuthenticationProvider::default();
+
+    let mut b = make_catalog_builder(mock_outbox);
+    b.add_value(admin_provider.clone())
+        .bind::<dyn AuthenticationProvider, DummyAdminAuthenticationProvider>();
+    let catalog = b.build();
+
+    let authentication_service = catalog.get_one::<dyn AuthenticationService>().unwrap();
+    let account_repo = catalog.get_one::<dyn AccountRepository>().unwrap();
+
+    admin_provider.is_admin.store(true, Ordering::SeqCst);
+    let login_response = authentication_service
+        .login("method-admin", "dummy".to_string())
+        .await
+        .unwrap();
+    let account = account_repo
+        .get_account_by_id(&login_response.account_id)
+        .await
+        .unwrap();
+    assert!(account.is_admin);
+
+    // Revoked in the provider
+    admin_provider.is_admin.store(false, Ordering::SeqCst);
+    authentication_service
+        .login("method-admin", "dummy".to_string())
+        .await
+        .unwrap();
+    let account = account_repo
+        .get_account_by_id(&login_response.account_id)
+        .await
+        .unwrap();
+    assert!(!account.is_admin);
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
 fn make_catalog(mock_outbox: MockOutbox) -> dill::Catalog {
+    make_catalog_builder(mock_outbox).build()
+}
+
+fn make_catalog_builder(mock_outbox: MockOutbox) -> dill::CatalogBuilder {
     let mut b = dill::CatalogBuilder::new();
 
     b.add::<DummyAuthenticationProviderA>()
         .add::<DummyAuthenticationProviderB>()
         .add::<AuthenticationServiceImpl>()
+        .add::<email_utils::DummyEmailSender>()
         .add::<InMemoryAccountRepository>()
         .add::<AccessTokenServiceImpl>()
         .add::<InMemoryAccessTokenRepository>()
@@ -119,7 +214,7 @@ fn make_catalog(mock_outbox: MockOutbox) -> dill::Catalog {
 
     NoOpDatabasePlugin::init_database_components(&mut b);
 
-    b.build()
+    b
 }
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
@@ -183,6 +278,7 @@ impl AuthenticationProvider for DummyAuthenticationProviderA {
             account_type: AccountType::User,
             avatar_url: None,
             provider_identity_key: String::from(DEFAULT_ACCOUNT_NAME_STR),
+            is_admin: None,
         })
     }
 }
@@ -208,6 +304,40 @@ impl AuthenticationProvider for DummyAuthenticationProviderB {
             account_type: AccountType::User,
             avatar_url: None,
             provider_identity_key: String::from(DEFAULT_ACCOUNT_NAME_STR),
+            is_admin: None,
+        })
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[derive(Clone, Default)]
+struct DummyAdminAuthenticationProvider {
+    is_admin: Arc<AtomicBool>,
+}
+
+#[async_trait::async_trait]
+impl AuthenticationProvider for DummyAdminAuthenticationProvider {
+    fn provider_name(&self) -> &'static str {
+        "method-admin"
+    }
+
+    fn generate_id(&self, _: &odf::AccountName) -> odf::AccountID {
+        DEFAULT_ACCOUNT_ID.clone()
+    }
+
+    async fn login(
+        &self,
+        _login_credentials_json: String,
+    ) -> Result<ProviderLoginResponse, ProviderLoginError> {
+        Ok(ProviderLoginResponse {
+            account_name: DEFAULT_ACCOUNT_NAME.clone(),
+            email: DUMMY_EMAIL_ADDRESS.clone(),
+            display_name: String::from(DEFAULT_ACCOUNT_NAME_STR),
+            account_type: AccountType::User,
+            avatar_url: None,
+            provider_identity_key: String::from("admin-provider-user"),
+            is_admin: Some(self.is_admin.load(Ordering::SeqCst)),
         })
     }
 }
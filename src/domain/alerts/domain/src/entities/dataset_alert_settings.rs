@@ -0,0 +1,46 @@
+// Copyright Kamu Data, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use chrono::{DateTime, Duration, Utc};
+use email_utils::Email;
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DatasetAlertSettings {
+    pub dataset_id: odf::DatasetID,
+    /// Whether the dataset owner receives alerts along with the recipients
+    pub notify_owner: bool,
+    /// Additional addresses alerts are sent to
+    pub recipients: Vec<Email>,
+    /// Send an alert when a flow of the dataset fails
+    pub notify_on_flow_failure: bool,
+    /// Send an alert when the dataset has not received new data for this long
+    pub stale_after: Option<Duration>,
+    pub created_at: DateTime<Utc>,
+    /// Last time an update flow brought new data into the dataset, or the
+    /// time alerts were configured if it did not happen since then
+    pub last_updated_at: DateTime<Utc>,
+    /// Set when the staleness alert is sent, cleared when new data arrives
+    pub stale_notified_at: Option<DateTime<Utc>>,
+}
+
+impl DatasetAlertSettings {
+    pub fn is_stale(&self, now: DateTime<Utc>) -> bool {
+        self.stale_after
+            .is_some_and(|stale_after| now - self.last_updated_at >= stale_after)
+    }
+
+    /// Staleness alert is sent only once per period without updates
+    pub fn needs_stale_alert(&self, now: DateTime<Utc>) -> bool {
+        self.stale_notified_at.is_none() && self.is_stale(now)
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
@@ -0,0 +1,29 @@
+// Copyright Kamu Data, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+use chrono::{DateTime, Utc};
+use email_utils::Email;
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+/// Alert email composed within the transaction of the event that caused it and
+/// delivered only after that transaction is committed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DatasetPendingAlert {
+    /// Identifies the cause of the alert, so that the same cause never results
+    /// in more than one pending alert
+    pub alert_key: String,
+    pub dataset_id: odf::DatasetID,
+    pub recipients: Vec<Email>,
+    pub subject: String,
+    /// Plain text body
+    pub body: String,
+    pub created_at: DateTime<Utc>,
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
@@ -0,0 +1,48 @@
+// Copyright Kamu Data, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use chrono::{DateTime, Utc};
+use internal_error::InternalError;
+
+use crate::DatasetAlertSettings;
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[async_trait::async_trait]
+pub trait DatasetAlertSettingsRepository: Send + Sync {
+    /// Creates or fully replaces the settings of the dataset
+    async fn save_settings(&self, settings: &DatasetAlertSettings) -> Result<(), InternalError>;
+
+    async fn get_settings(
+        &self,
+        dataset_id: &odf::DatasetID,
+    ) -> Result<Option<DatasetAlertSettings>, InternalError>;
+
+    /// Returns settings of all datasets that have a staleness window defined
+    async fn get_settings_with_stale_after(
+        &self,
+    ) -> Result<Vec<DatasetAlertSettings>, InternalError>;
+
+    /// Records arrival of new data, does nothing if the dataset has no settings
+    async fn mark_dataset_updated(
+        &self,
+        dataset_id: &odf::DatasetID,
+        updated_at: DateTime<Utc>,
+    ) -> Result<(), InternalError>;
+
+    async fn mark_stale_notified(
+        &self,
+        dataset_id: &odf::DatasetID,
+        notified_at: DateTime<Utc>,
+    ) -> Result<(), InternalError>;
+
+    async fn delete_settings(&self, dataset_id: &odf::DatasetID) -> Result<(), InternalError>;
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
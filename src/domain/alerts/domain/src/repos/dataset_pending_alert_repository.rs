@@ -0,0 +1,29 @@
+// Copyright Kamu Data, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+use internal_error::InternalError;
+
+use crate::DatasetPendingAlert;
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[async_trait::async_trait]
+pub trait DatasetPendingAlertRepository: Send + Sync {
+    /// Does nothing if an alert with the same key is already pending
+    async fn save_pending_alert(&self, alert: &DatasetPendingAlert) -> Result<(), InternalError>;
+
+    /// Returns the oldest pending alerts first
+    async fn get_pending_alerts(
+        &self,
+        limit: usize,
+    ) -> Result<Vec<DatasetPendingAlert>, InternalError>;
+
+    async fn delete_pending_alert(&self, alert_key: &str) -> Result<(), InternalError>;
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
@@ -0,0 +1,51 @@
+// Copyright Kamu Data, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use internal_error::InternalError;
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[async_trait::async_trait]
+pub trait DatasetAlertsAgent: Send + Sync {
+    /// Runs the agent main loop
+    async fn run(&self) -> Result<(), InternalError>;
+
+    /// Queues alerts for all datasets that became stale (for tests only!)
+    async fn run_staleness_checks(&self) -> Result<(), InternalError>;
+
+    /// Sends queued alerts of committed transactions (for tests only!)
+    async fn deliver_pending_alerts(&self) -> Result<(), InternalError>;
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[derive(Debug)]
+pub struct DatasetAlertsConfig {
+    /// Interval between checks whether any datasets became stale, which are
+    /// followed by delivery of queued alerts
+    pub staleness_check_interval: chrono::Duration,
+}
+
+impl DatasetAlertsConfig {
+    pub fn new(staleness_check_interval: chrono::Duration) -> Self {
+        Self {
+            staleness_check_interval,
+        }
+    }
+}
+
+impl Default for DatasetAlertsConfig {
+    fn default() -> Self {
+        Self {
+            staleness_check_interval: chrono::Duration::minutes(1),
+        }
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
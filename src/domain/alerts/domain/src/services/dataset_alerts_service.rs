@@ -0,0 +1,65 @@
+// Copyright Kamu Data, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use chrono::Duration;
+use email_utils::Email;
+use internal_error::InternalError;
+use thiserror::Error;
+
+use crate::DatasetAlertSettings;
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[async_trait::async_trait]
+pub trait DatasetAlertsService: Send + Sync {
+    async fn get_settings(
+        &self,
+        dataset_id: &odf::DatasetID,
+    ) -> Result<Option<DatasetAlertSettings>, InternalError>;
+
+    /// Replaces alert settings of the dataset, the staleness tracking is
+    /// preserved unless the window itself changes
+    async fn set_settings(
+        &self,
+        dataset_id: &odf::DatasetID,
+        input: DatasetAlertSettingsInput,
+    ) -> Result<DatasetAlertSettings, SetDatasetAlertSettingsError>;
+
+    /// Disables all alerts of the dataset
+    async fn remove_settings(&self, dataset_id: &odf::DatasetID) -> Result<(), InternalError>;
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[derive(Debug, Clone)]
+pub struct DatasetAlertSettingsInput {
+    pub notify_owner: bool,
+    pub recipients: Vec<Email>,
+    pub notify_on_flow_failure: bool,
+    pub stale_after: Option<Duration>,
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[derive(Error, Debug)]
+pub enum SetDatasetAlertSettingsError {
+    #[error(transparent)]
+    Invalid(InvalidDatasetAlertSettingsError),
+
+    #[error(transparent)]
+    Internal(#[from] InternalError),
+}
+
+#[derive(Error, Debug)]
+#[error("Invalid dataset alert settings: {reason}")]
+pub struct InvalidDatasetAlertSettingsError {
+    pub reason: String,
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
@@ -0,0 +1,21 @@
+// Copyright Kamu Data, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+#![feature(let_chains)]
+
+// Re-exports
+pub use kamu_alerts as domain;
+
+mod dependencies;
+mod messages;
+mod services;
+
+pub use dependencies::*;
+pub use messages::*;
+pub use services::*;
@@ -0,0 +1,184 @@
+// Copyright Kamu Data, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::sync::Arc;
+
+use chrono::{DateTime, Duration, Utc};
+use dill::*;
+use email_utils::Email;
+use internal_error::InternalError;
+use kamu_accounts::AccountService;
+use kamu_alerts::{DatasetAlertSettings, DatasetPendingAlert, DatasetPendingAlertRepository};
+use kamu_datasets::{DatasetEntryService, GetDatasetEntryError};
+use kamu_flow_system::{DatasetFlowType, FlowID};
+use time_source::SystemTimeSource;
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[derive(Debug, Clone)]
+pub enum DatasetAlert {
+    FlowFailed(DatasetAlertFlowFailed),
+    Stale(DatasetAlertStale),
+}
+
+#[derive(Debug, Clone)]
+pub struct DatasetAlertFlowFailed {
+    pub flow_id: FlowID,
+    pub flow_type: DatasetFlowType,
+    pub failed_at: DateTime<Utc>,
+    pub error: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct DatasetAlertStale {
+    pub last_updated_at: DateTime<Utc>,
+    pub stale_after: Duration,
+}
+
+impl DatasetAlert {
+    /// Same cause of an alert always produces the same key
+    fn alert_key(&self, dataset_id: &odf::DatasetID) -> String {
+        match self {
+            DatasetAlert::FlowFailed(alert) => format!("flow-failed/{}", alert.flow_id),
+            DatasetAlert::Stale(alert) => format!(
+                "stale/{dataset_id}/{}",
+                alert.last_updated_at.timestamp_micros()
+            ),
+        }
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+/// Composes alert emails for the owner of the dataset and the configured
+/// recipients. Emails are only queued here, as the current transaction may
+/// still roll back, and are sent by [`crate::DatasetAlertsAgentImpl`]
+pub struct DatasetAlertMailer {
+    dataset_entry_service: Arc<dyn DatasetEntryService>,
+    account_service: Arc<dyn AccountService>,
+    pending_alert_repo: Arc<dyn DatasetPendingAlertRepository>,
+    time_source: Arc<dyn SystemTimeSource>,
+}
+
+#[component(pub)]
+impl DatasetAlertMailer {
+    pub fn new(
+        dataset_entry_service: Arc<dyn DatasetEntryService>,
+        account_service: Arc<dyn AccountService>,
+        pending_alert_repo: Arc<dyn DatasetPendingAlertRepository>,
+        time_source: Arc<dyn SystemTimeSource>,
+    ) -> Self {
+        Self {
+            dataset_entry_service,
+            account_service,
+            pending_alert_repo,
+            time_source,
+        }
+    }
+
+    #[tracing::instrument(level = "debug", skip_all, fields(dataset_id = %settings.dataset_id))]
+    pub async fn enqueue_alert(
+        &self,
+        settings: &DatasetAlertSettings,
+        alert: &DatasetAlert,
+    ) -> Result<(), InternalError> {
+        let entry = match self
+            .dataset_entry_service
+            .get_entry(&settings.dataset_id)
+            .await
+        {
+            Ok(entry) => entry,
+            Err(GetDatasetEntryError::NotFound(_)) => {
+                tracing::debug!("Skipping alert of unknown dataset");
+                return Ok(());
+            }
+            Err(GetDatasetEntryError::Internal(e)) => return Err(e),
+        };
+
+        let owner = self.account_service.account_by_id(&entry.owner_id).await?;
+
+        let mut recipients: Vec<Email> = Vec::new();
+        if settings.notify_owner
+            && let Some(owner) = &owner
+        {
+            recipients.push(owner.email.clone());
+        }
+        for recipient in &settings.recipients {
+            if !recipients.contains(recipient) {
+                recipients.push(recipient.clone());
+            }
+        }
+
+        if recipients.is_empty() {
+            tracing::debug!("Skipping alert without recipients");
+            return Ok(());
+        }
+
+        let dataset_alias =
+            odf::DatasetAlias::new(owner.map(|owner| owner.account_name), entry.name.clone());
+        let (subject, body) = Self::compose(&dataset_alias, alert);
+
+        self.pending_alert_repo
+            .save_pending_alert(&DatasetPendingAlert {
+                alert_key: alert.alert_key(&settings.dataset_id),
+                dataset_id: settings.dataset_id.clone(),
+                recipients,
+                subject,
+                body,
+                created_at: self.time_source.now(),
+            })
+            .await
+    }
+
+    fn compose(dataset_alias: &odf::DatasetAlias, alert: &DatasetAlert) -> (String, String) {
+        match alert {
+            DatasetAlert::FlowFailed(alert) => {
+                let flow_type = match alert.flow_type {
+                    DatasetFlowType::Ingest => "Ingest",
+                    DatasetFlowType::ExecuteTransform => "Transform",
+                    DatasetFlowType::HardCompaction => "Compaction",
+                    DatasetFlowType::Reset => "Reset",
+                    DatasetFlowType::Reprocess => "Reprocess",
+                };
+                (
+                    format!("[Kamu] {flow_type} flow of '{dataset_alias}' failed"),
+                    format!(
+                        "{flow_type} flow #{} of dataset '{dataset_alias}' failed at \
+                         {}.\n\nError: {}\n",
+                        alert.flow_id,
+                        alert.failed_at.to_rfc3339(),
+                        alert.error,
+                    ),
+                )
+            }
+            DatasetAlert::Stale(alert) => (
+                format!("[Kamu] Dataset '{dataset_alias}' is stale"),
+                format!(
+                    "Dataset '{dataset_alias}' has not received new data since {}, which exceeds \
+                     the expected update window of {}.\n",
+                    alert.last_updated_at.to_rfc3339(),
+                    Self::format_duration(alert.stale_after),
+                ),
+            ),
+        }
+    }
+
+    fn format_duration(duration: Duration) -> String {
+        let minutes = duration.num_minutes();
+        if minutes % (60 * 24) == 0 {
+            format!("{}d", minutes / (60 * 24))
+        } else if minutes % 60 == 0 {
+            format!("{}h", minutes / 60)
+        } else {
+            format!("{minutes}m")
+        }
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
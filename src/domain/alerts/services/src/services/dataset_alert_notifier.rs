@@ -0,0 +1,190 @@
+// Copyright Kamu Data, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::sync::Arc;
+
+use dill::*;
+use internal_error::InternalError;
+use kamu_alerts::*;
+use kamu_datasets::{DatasetLifecycleMessage, MESSAGE_PRODUCER_KAMU_DATASET_SERVICE};
+use kamu_flow_system::{
+    FlowError,
+    FlowKey,
+    FlowOutcome,
+    FlowProgressMessage,
+    FlowProgressMessageFinished,
+    FlowQueryService,
+    FlowResult,
+    FlowResultDatasetUpdate,
+    GetFlowError,
+};
+use kamu_flow_system_services::MESSAGE_PRODUCER_KAMU_FLOW_PROGRESS_SERVICE;
+use messaging_outbox::{
+    MessageConsumer,
+    MessageConsumerMeta,
+    MessageConsumerT,
+    MessageDeliveryMechanism,
+};
+
+use crate::{
+    DatasetAlert,
+    DatasetAlertFlowFailed,
+    DatasetAlertMailer,
+    MESSAGE_CONSUMER_KAMU_DATASET_ALERT_NOTIFIER,
+};
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+/// Queues flow failure alerts and tracks arrival of new data for the
+/// staleness checks
+pub struct DatasetAlertNotifier {
+    settings_repo: Arc<dyn DatasetAlertSettingsRepository>,
+}
+
+#[component(pub)]
+#[interface(dyn MessageConsumer)]
+#[interface(dyn MessageConsumerT<DatasetLifecycleMessage>)]
+#[interface(dyn MessageConsumerT<FlowProgressMessage>)]
+#[meta(MessageConsumerMeta {
+    consumer_name: MESSAGE_CONSUMER_KAMU_DATASET_ALERT_NOTIFIER,
+    feeding_producers: &[
+        MESSAGE_PRODUCER_KAMU_DATASET_SERVICE,
+        MESSAGE_PRODUCER_KAMU_FLOW_PROGRESS_SERVICE,
+    ],
+    delivery: MessageDeliveryMechanism::Transactional,
+})]
+impl DatasetAlertNotifier {
+    pub fn new(settings_repo: Arc<dyn DatasetAlertSettingsRepository>) -> Self {
+        Self { settings_repo }
+    }
+
+    async fn handle_flow_finished(
+        &self,
+        target_catalog: &Catalog,
+        message: &FlowProgressMessageFinished,
+    ) -> Result<(), InternalError> {
+        let flow_query_service = target_catalog.get_one::<dyn FlowQueryService>().unwrap();
+        let flow = match flow_query_service.get_flow(message.flow_id).await {
+            Ok(flow) => flow,
+            Err(GetFlowError::NotFound(_)) => return Ok(()),
+            Err(GetFlowError::Internal(e)) => return Err(e),
+        };
+
+        // System flows are not related to any dataset
+        let FlowKey::Dataset(flow_key) = flow.flow_key else {
+            return Ok(());
+        };
+
+        match &message.outcome {
+            FlowOutcome::Success(FlowResult::DatasetUpdate(FlowResultDatasetUpdate::Changed(
+                _,
+            ))) => {
+                self.settings_repo
+                    .mark_dataset_updated(&flow_key.dataset_id, message.event_time)
+                    .await
+            }
+            FlowOutcome::Failed(error) => {
+                let Some(settings) = self
+                    .settings_repo
+                    .get_settings(&flow_key.dataset_id)
+                    .await?
+                else {
+                    return Ok(());
+                };
+                if !settings.notify_on_flow_failure {
+                    return Ok(());
+                }
+
+                let mailer = target_catalog.get_one::<DatasetAlertMailer>().unwrap();
+                mailer
+                    .enqueue_alert(
+                        &settings,
+                        &DatasetAlert::FlowFailed(DatasetAlertFlowFailed {
+                            flow_id: message.flow_id,
+                            flow_type: flow_key.flow_type,
+                            failed_at: message.event_time,
+                            error: Self::describe_flow_error(error),
+                        }),
+                    )
+                    .await
+            }
+            FlowOutcome::Success(_) | FlowOutcome::Aborted => Ok(()),
+        }
+    }
+
+    fn describe_flow_error(error: &FlowError) -> String {
+        match error {
+            FlowError::Failed => "Flow task has failed".to_string(),
+            FlowError::InputDatasetCompacted(e) => {
+                format!("Input dataset {} was compacted", e.dataset_id)
+            }
+            FlowError::ResetHeadNotFound => "New head for reset not found".to_string(),
+        }
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+impl MessageConsumer for DatasetAlertNotifier {}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[async_trait::async_trait]
+impl MessageConsumerT<DatasetLifecycleMessage> for DatasetAlertNotifier {
+    #[tracing::instrument(
+        level = "debug",
+        skip_all,
+        name = "DatasetAlertNotifier[DatasetLifecycleMessage]"
+    )]
+    async fn consume_message(
+        &self,
+        _: &Catalog,
+        message: &DatasetLifecycleMessage,
+    ) -> Result<(), InternalError> {
+        tracing::debug!(received_message = ?message, "Received dataset lifecycle message");
+
+        match message {
+            DatasetLifecycleMessage::Created(_) => Ok(()),
+            DatasetLifecycleMessage::Deleted(message) => {
+                self.settings_repo
+                    .delete_settings(&message.dataset_id)
+                    .await
+            }
+        }
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[async_trait::async_trait]
+impl MessageConsumerT<FlowProgressMessage> for DatasetAlertNotifier {
+    #[tracing::instrument(
+        level = "debug",
+        skip_all,
+        name = "DatasetAlertNotifier[FlowProgressMessage]"
+    )]
+    async fn consume_message(
+        &self,
+        target_catalog: &Catalog,
+        message: &FlowProgressMessage,
+    ) -> Result<(), InternalError> {
+        tracing::debug!(received_message = ?message, "Received flow progress message");
+
+        match message {
+            FlowProgressMessage::Finished(message) => {
+                self.handle_flow_finished(target_catalog, message).await
+            }
+            FlowProgressMessage::Scheduled(_)
+            | FlowProgressMessage::Running(_)
+            | FlowProgressMessage::Cancelled(_) => Ok(()),
+        }
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
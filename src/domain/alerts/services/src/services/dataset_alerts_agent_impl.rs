@@ -0,0 +1,156 @@
+// Copyright Kamu Data, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::sync::Arc;
+
+use chrono::{DateTime, Utc};
+use database_common_macros::{transactional_method1, transactional_method2};
+use dill::*;
+use email_utils::{EmailMessage, EmailSender};
+use internal_error::InternalError;
+use kamu_alerts::*;
+use time_source::SystemTimeSource;
+
+use crate::{DatasetAlert, DatasetAlertMailer, DatasetAlertStale};
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+const PENDING_ALERTS_BATCH_SIZE: usize = 100;
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+pub struct DatasetAlertsAgentImpl {
+    catalog: Catalog,
+    email_sender: Arc<dyn EmailSender>,
+    time_source: Arc<dyn SystemTimeSource>,
+    config: Arc<DatasetAlertsConfig>,
+}
+
+#[component(pub)]
+#[interface(dyn DatasetAlertsAgent)]
+#[scope(Singleton)]
+impl DatasetAlertsAgentImpl {
+    pub fn new(
+        catalog: Catalog,
+        email_sender: Arc<dyn EmailSender>,
+        time_source: Arc<dyn SystemTimeSource>,
+        config: Arc<DatasetAlertsConfig>,
+    ) -> Self {
+        Self {
+            catalog,
+            email_sender,
+            time_source,
+            config,
+        }
+    }
+
+    #[transactional_method1(settings_repo: Arc<dyn DatasetAlertSettingsRepository>)]
+    async fn get_settings_with_stale_after(
+        &self,
+    ) -> Result<Vec<DatasetAlertSettings>, InternalError> {
+        settings_repo.get_settings_with_stale_after().await
+    }
+
+    #[transactional_method2(settings_repo: Arc<dyn DatasetAlertSettingsRepository>, mailer: Arc<DatasetAlertMailer>)]
+    async fn enqueue_stale_alert(
+        &self,
+        settings: DatasetAlertSettings,
+        now: DateTime<Utc>,
+    ) -> Result<(), InternalError> {
+        tracing::info!(dataset_id = %settings.dataset_id, last_updated_at = %settings.last_updated_at, "Dataset became stale");
+
+        mailer
+            .enqueue_alert(
+                &settings,
+                &DatasetAlert::Stale(DatasetAlertStale {
+                    last_updated_at: settings.last_updated_at,
+                    stale_after: settings.stale_after.unwrap(),
+                }),
+            )
+            .await?;
+
+        settings_repo
+            .mark_stale_notified(&settings.dataset_id, now)
+            .await
+    }
+
+    #[transactional_method1(pending_alert_repo: Arc<dyn DatasetPendingAlertRepository>)]
+    async fn get_pending_alerts(&self) -> Result<Vec<DatasetPendingAlert>, InternalError> {
+        pending_alert_repo
+            .get_pending_alerts(PENDING_ALERTS_BATCH_SIZE)
+            .await
+    }
+
+    #[transactional_method1(pending_alert_repo: Arc<dyn DatasetPendingAlertRepository>)]
+    async fn delete_pending_alert(&self, alert_key: &str) -> Result<(), InternalError> {
+        pending_alert_repo.delete_pending_alert(alert_key).await
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[async_trait::async_trait]
+impl DatasetAlertsAgent for DatasetAlertsAgentImpl {
+    async fn run(&self) -> Result<(), InternalError> {
+        loop {
+            self.run_staleness_checks().await?;
+            self.deliver_pending_alerts().await?;
+
+            self.time_source
+                .sleep(self.config.staleness_check_interval)
+                .await;
+        }
+    }
+
+    async fn run_staleness_checks(&self) -> Result<(), InternalError> {
+        let now = self.time_source.now();
+
+        for settings in self.get_settings_with_stale_after().await? {
+            if settings.needs_stale_alert(now) {
+                self.enqueue_stale_alert(settings, now).await?;
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn deliver_pending_alerts(&self) -> Result<(), InternalError> {
+        loop {
+            let pending_alerts = self.get_pending_alerts().await?;
+            if pending_alerts.is_empty() {
+                return Ok(());
+            }
+
+            for alert in pending_alerts {
+                if let Err(e) = self
+                    .email_sender
+                    .send_email(EmailMessage {
+                        to: alert.recipients,
+                        subject: alert.subject,
+                        body: alert.body,
+                    })
+                    .await
+                {
+                    // Alert stays pending until the next run
+                    tracing::error!(
+                        alert_key = %alert.alert_key,
+                        error = ?e,
+                        error_msg = %e,
+                        "Failed to send dataset alert"
+                    );
+                    return Ok(());
+                }
+
+                self.delete_pending_alert(&alert.alert_key).await?;
+            }
+        }
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
@@ -0,0 +1,136 @@
+// Copyright Kamu Data, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::sync::Arc;
+
+use chrono::Duration;
+use dill::*;
+use internal_error::InternalError;
+use kamu_alerts::*;
+use time_source::SystemTimeSource;
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+const MAX_RECIPIENTS: usize = 20;
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+pub struct DatasetAlertsServiceImpl {
+    settings_repo: Arc<dyn DatasetAlertSettingsRepository>,
+    time_source: Arc<dyn SystemTimeSource>,
+}
+
+#[component(pub)]
+#[interface(dyn DatasetAlertsService)]
+impl DatasetAlertsServiceImpl {
+    pub fn new(
+        settings_repo: Arc<dyn DatasetAlertSettingsRepository>,
+        time_source: Arc<dyn SystemTimeSource>,
+    ) -> Self {
+        Self {
+            settings_repo,
+            time_source,
+        }
+    }
+
+    fn validate(
+        input: &mut DatasetAlertSettingsInput,
+    ) -> Result<(), InvalidDatasetAlertSettingsError> {
+        let invalid = |reason: &str| InvalidDatasetAlertSettingsError {
+            reason: reason.to_string(),
+        };
+
+        let mut recipients = Vec::with_capacity(input.recipients.len());
+        for recipient in input.recipients.drain(..) {
+            if !recipients.contains(&recipient) {
+                recipients.push(recipient);
+            }
+        }
+        input.recipients = recipients;
+
+        if input.recipients.len() > MAX_RECIPIENTS {
+            return Err(invalid(&format!(
+                "At most {MAX_RECIPIENTS} recipients are allowed"
+            )));
+        }
+        if !input.notify_owner && input.recipients.is_empty() {
+            return Err(invalid("At least one recipient is required"));
+        }
+        if !input.notify_on_flow_failure && input.stale_after.is_none() {
+            return Err(invalid("At least one alert condition is required"));
+        }
+        if let Some(stale_after) = input.stale_after
+            && stale_after < Duration::minutes(1)
+        {
+            return Err(invalid("Staleness window must be at least one minute"));
+        }
+
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl DatasetAlertsService for DatasetAlertsServiceImpl {
+    async fn get_settings(
+        &self,
+        dataset_id: &odf::DatasetID,
+    ) -> Result<Option<DatasetAlertSettings>, InternalError> {
+        self.settings_repo.get_settings(dataset_id).await
+    }
+
+    #[tracing::instrument(level = "info", skip_all, fields(%dataset_id))]
+    async fn set_settings(
+        &self,
+        dataset_id: &odf::DatasetID,
+        mut input: DatasetAlertSettingsInput,
+    ) -> Result<DatasetAlertSettings, SetDatasetAlertSettingsError> {
+        Self::validate(&mut input).map_err(SetDatasetAlertSettingsError::Invalid)?;
+
+        let now = self.time_source.now();
+
+        let settings = match self.settings_repo.get_settings(dataset_id).await? {
+            Some(existing) => DatasetAlertSettings {
+                dataset_id: dataset_id.clone(),
+                notify_owner: input.notify_owner,
+                recipients: input.recipients,
+                notify_on_flow_failure: input.notify_on_flow_failure,
+                // Re-arm the staleness alert if the window was changed
+                stale_notified_at: if existing.stale_after == input.stale_after {
+                    existing.stale_notified_at
+                } else {
+                    None
+                },
+                stale_after: input.stale_after,
+                created_at: existing.created_at,
+                last_updated_at: existing.last_updated_at,
+            },
+            None => DatasetAlertSettings {
+                dataset_id: dataset_id.clone(),
+                notify_owner: input.notify_owner,
+                recipients: input.recipients,
+                notify_on_flow_failure: input.notify_on_flow_failure,
+                stale_after: input.stale_after,
+                created_at: now,
+                last_updated_at: now,
+                stale_notified_at: None,
+            },
+        };
+
+        self.settings_repo.save_settings(&settings).await?;
+
+        Ok(settings)
+    }
+
+    #[tracing::instrument(level = "info", skip_all, fields(%dataset_id))]
+    async fn remove_settings(&self, dataset_id: &odf::DatasetID) -> Result<(), InternalError> {
+        self.settings_repo.delete_settings(dataset_id).await
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
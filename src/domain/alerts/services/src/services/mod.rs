@@ -0,0 +1,18 @@
+// Copyright Kamu Data, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+mod dataset_alert_mailer;
+mod dataset_alert_notifier;
+mod dataset_alerts_agent_impl;
+mod dataset_alerts_service_impl;
+
+pub use dataset_alert_mailer::*;
+pub use dataset_alert_notifier::*;
+pub use dataset_alerts_agent_impl::*;
+pub use dataset_alerts_service_impl::*;
@@ -0,0 +1,242 @@
+// Copyright Kamu Data, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::sync::Arc;
+
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use database_common::NoOpDatabasePlugin;
+use dill::{Catalog, CatalogBuilder};
+use email_utils::{DummyEmailSender, Email};
+use kamu_accounts::{Account, AccountRepository, CurrentAccountSubject};
+use kamu_accounts_inmem::InMemoryAccountRepository;
+use kamu_accounts_services::AccountServiceImpl;
+use kamu_alerts::*;
+use kamu_alerts_inmem::{
+    InMemoryDatasetAlertSettingsRepository,
+    InMemoryDatasetPendingAlertRepository,
+};
+use kamu_alerts_services::{DatasetAlertMailer, DatasetAlertsAgentImpl, DatasetAlertsServiceImpl};
+use kamu_core::TenancyConfig;
+use kamu_datasets::{DatasetEntry, DatasetEntryRepository};
+use kamu_datasets_inmem::InMemoryDatasetEntryRepository;
+use kamu_datasets_services::DatasetEntryServiceImpl;
+use time_source::{SystemTimeSource, SystemTimeSourceStub};
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[test_log::test(tokio::test)]
+async fn test_stale_alert_sent_once_per_period() {
+    let harness = DatasetAlertsAgentHarness::new().await;
+    harness.set_settings(Duration::hours(1)).await;
+
+    // Within the window
+    harness.advance_to(harness.t0 + Duration::minutes(59));
+    harness.run_agent().await;
+    assert!(harness.email_sender.sent_emails().is_empty());
+
+    // Window exceeded, the alert is queued first and sent separately
+    harness.advance_to(harness.t0 + Duration::hours(1));
+    harness.agent.run_staleness_checks().await.unwrap();
+    assert!(harness.email_sender.sent_emails().is_empty());
+    assert_eq!(
+        harness
+            .pending_alert_repo
+            .get_pending_alerts(10)
+            .await
+            .unwrap()
+            .len(),
+        1
+    );
+
+    harness.agent.deliver_pending_alerts().await.unwrap();
+    assert!(harness
+        .pending_alert_repo
+        .get_pending_alerts(10)
+        .await
+        .unwrap()
+        .is_empty());
+
+    let sent_emails = harness.email_sender.sent_emails();
+    assert_eq!(sent_emails.len(), 1);
+    assert_eq!(
+        sent_emails[0].to,
+        vec![email("alice@example.com"), email("ops@example.com")]
+    );
+    assert_eq!(
+        sent_emails[0].subject,
+        "[Kamu] Dataset 'alice/foo' is stale"
+    );
+    assert_eq!(
+        sent_emails[0].body,
+        "Dataset 'alice/foo' has not received new data since 2025-01-01T12:00:00+00:00, which \
+         exceeds the expected update window of 1h.\n"
+    );
+
+    // Still stale, but the alert was already sent
+    harness.advance_to(harness.t0 + Duration::hours(5));
+    harness.run_agent().await;
+    assert_eq!(harness.email_sender.sent_emails().len(), 1);
+
+    // New data re-arms the alert
+    harness
+        .settings_repo
+        .mark_dataset_updated(&harness.dataset_id, harness.t0 + Duration::hours(6))
+        .await
+        .unwrap();
+
+    harness.advance_to(harness.t0 + Duration::hours(6) + Duration::minutes(30));
+    harness.run_agent().await;
+    assert_eq!(harness.email_sender.sent_emails().len(), 1);
+
+    harness.advance_to(harness.t0 + Duration::hours(7));
+    harness.run_agent().await;
+    assert_eq!(harness.email_sender.sent_emails().len(), 2);
+}
+
+#[test_log::test(tokio::test)]
+async fn test_no_stale_alert_without_window() {
+    let harness = DatasetAlertsAgentHarness::new().await;
+
+    harness
+        .alerts_service
+        .set_settings(
+            &harness.dataset_id,
+            DatasetAlertSettingsInput {
+                notify_owner: true,
+                recipients: vec![],
+                notify_on_flow_failure: true,
+                stale_after: None,
+            },
+        )
+        .await
+        .unwrap();
+
+    harness.advance_to(harness.t0 + Duration::days(30));
+    harness.run_agent().await;
+    assert!(harness.email_sender.sent_emails().is_empty());
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+fn email(s: &str) -> Email {
+    Email::parse(s).unwrap()
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+struct DatasetAlertsAgentHarness {
+    _tempdir: tempfile::TempDir,
+    _catalog: Catalog,
+    agent: Arc<dyn DatasetAlertsAgent>,
+    alerts_service: Arc<dyn DatasetAlertsService>,
+    settings_repo: Arc<dyn DatasetAlertSettingsRepository>,
+    pending_alert_repo: Arc<dyn DatasetPendingAlertRepository>,
+    email_sender: Arc<DummyEmailSender>,
+    time_source: Arc<SystemTimeSourceStub>,
+    dataset_id: odf::DatasetID,
+    t0: DateTime<Utc>,
+}
+
+impl DatasetAlertsAgentHarness {
+    async fn new() -> Self {
+        let t0 = Utc.with_ymd_and_hms(2025, 1, 1, 12, 0, 0).unwrap();
+
+        let tempdir = tempfile::tempdir().unwrap();
+        let datasets_dir = tempdir.path().join("datasets");
+        std::fs::create_dir(&datasets_dir).unwrap();
+
+        let catalog = {
+            let mut b = CatalogBuilder::new();
+
+            b.add::<DatasetAlertsAgentImpl>()
+                .add::<DatasetAlertsServiceImpl>()
+                .add::<DatasetAlertMailer>()
+                .add::<InMemoryDatasetAlertSettingsRepository>()
+                .add::<InMemoryDatasetPendingAlertRepository>()
+                .add::<DummyEmailSender>()
+                .add::<DatasetEntryServiceImpl>()
+                .add::<InMemoryDatasetEntryRepository>()
+                .add::<AccountServiceImpl>()
+                .add::<InMemoryAccountRepository>()
+                .add_value(CurrentAccountSubject::new_test())
+                .add_value(TenancyConfig::MultiTenant)
+                .add_builder(
+                    odf::dataset::DatasetStorageUnitLocalFs::builder().with_root(datasets_dir),
+                )
+                .bind::<dyn odf::DatasetStorageUnit, odf::dataset::DatasetStorageUnitLocalFs>()
+                .add_value(SystemTimeSourceStub::new_set(t0))
+                .bind::<dyn SystemTimeSource, SystemTimeSourceStub>()
+                .add_value(DatasetAlertsConfig::new(Duration::minutes(1)));
+
+            NoOpDatabasePlugin::init_database_components(&mut b);
+
+            b.build()
+        };
+
+        let owner = Account::test(odf::AccountID::new_seeded_ed25519(b"alice"), "alice");
+        catalog
+            .get_one::<dyn AccountRepository>()
+            .unwrap()
+            .create_account(&owner)
+            .await
+            .unwrap();
+
+        let dataset_id = odf::DatasetID::new_seeded_ed25519(b"foo");
+        catalog
+            .get_one::<dyn DatasetEntryRepository>()
+            .unwrap()
+            .save_dataset_entry(&DatasetEntry::new(
+                dataset_id.clone(),
+                owner.id.clone(),
+                odf::DatasetName::new_unchecked("foo"),
+                t0,
+            ))
+            .await
+            .unwrap();
+
+        Self {
+            agent: catalog.get_one().unwrap(),
+            alerts_service: catalog.get_one().unwrap(),
+            settings_repo: catalog.get_one().unwrap(),
+            pending_alert_repo: catalog.get_one().unwrap(),
+            email_sender: catalog.get_one().unwrap(),
+            time_source: catalog.get_one().unwrap(),
+            _tempdir: tempdir,
+            _catalog: catalog,
+            dataset_id,
+            t0,
+        }
+    }
+
+    async fn run_agent(&self) {
+        self.agent.run_staleness_checks().await.unwrap();
+        self.agent.deliver_pending_alerts().await.unwrap();
+    }
+
+    fn advance_to(&self, t: DateTime<Utc>) {
+        self.time_source.set(t);
+    }
+
+    async fn set_settings(&self, stale_after: Duration) {
+        self.alerts_service
+            .set_settings(
+                &self.dataset_id,
+                DatasetAlertSettingsInput {
+                    notify_owner: true,
+                    recipients: vec![email("ops@example.com")],
+                    notify_on_flow_failure: false,
+                    stale_after: Some(stale_after),
+                },
+            )
+            .await
+            .unwrap();
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
@@ -0,0 +1,218 @@
+// Copyright Kamu Data, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::assert_matches::assert_matches;
+use std::sync::Arc;
+
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use dill::{Catalog, CatalogBuilder};
+use email_utils::Email;
+use kamu_alerts::*;
+use kamu_alerts_inmem::InMemoryDatasetAlertSettingsRepository;
+use kamu_alerts_services::DatasetAlertsServiceImpl;
+use time_source::{SystemTimeSource, SystemTimeSourceStub};
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[test_log::test(tokio::test)]
+async fn test_set_and_get_settings() {
+    let harness = DatasetAlertsServiceHarness::new();
+    let dataset_id = odf::DatasetID::new_seeded_ed25519(b"foo");
+
+    assert_eq!(
+        harness.service.get_settings(&dataset_id).await.unwrap(),
+        None
+    );
+
+    let settings = harness
+        .service
+        .set_settings(
+            &dataset_id,
+            DatasetAlertSettingsInput {
+                notify_owner: true,
+                recipients: vec![email("ops@example.com"), email("ops@example.com")],
+                notify_on_flow_failure: true,
+                stale_after: Some(Duration::hours(1)),
+            },
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(
+        settings,
+        DatasetAlertSettings {
+            dataset_id: dataset_id.clone(),
+            notify_owner: true,
+            recipients: vec![email("ops@example.com")],
+            notify_on_flow_failure: true,
+            stale_after: Some(Duration::hours(1)),
+            created_at: harness.t0,
+            last_updated_at: harness.t0,
+            stale_notified_at: None,
+        }
+    );
+    assert_eq!(
+        harness.service.get_settings(&dataset_id).await.unwrap(),
+        Some(settings)
+    );
+
+    harness.service.remove_settings(&dataset_id).await.unwrap();
+    assert_eq!(
+        harness.service.get_settings(&dataset_id).await.unwrap(),
+        None
+    );
+}
+
+#[test_log::test(tokio::test)]
+async fn test_update_preserves_staleness_tracking() {
+    let harness = DatasetAlertsServiceHarness::new();
+    let dataset_id = odf::DatasetID::new_seeded_ed25519(b"foo");
+
+    harness
+        .service
+        .set_settings(&dataset_id, input(Some(Duration::hours(1))))
+        .await
+        .unwrap();
+    harness
+        .settings_repo
+        .mark_stale_notified(&dataset_id, harness.t0 + Duration::hours(2))
+        .await
+        .unwrap();
+
+    // Same window: the alert stays acknowledged
+    harness.time_source.set(harness.t0 + Duration::hours(3));
+    let settings = harness
+        .service
+        .set_settings(
+            &dataset_id,
+            DatasetAlertSettingsInput {
+                notify_owner: false,
+                ..input(Some(Duration::hours(1)))
+            },
+        )
+        .await
+        .unwrap();
+    assert!(!settings.notify_owner);
+    assert_eq!(settings.created_at, harness.t0);
+    assert_eq!(settings.last_updated_at, harness.t0);
+    assert_eq!(
+        settings.stale_notified_at,
+        Some(harness.t0 + Duration::hours(2))
+    );
+
+    // Changed window: the alert is re-armed
+    let settings = harness
+        .service
+        .set_settings(&dataset_id, input(Some(Duration::days(1))))
+        .await
+        .unwrap();
+    assert_eq!(settings.last_updated_at, harness.t0);
+    assert_eq!(settings.stale_notified_at, None);
+}
+
+#[test_log::test(tokio::test)]
+async fn test_invalid_settings() {
+    let harness = DatasetAlertsServiceHarness::new();
+    let dataset_id = odf::DatasetID::new_seeded_ed25519(b"foo");
+
+    let cases = [
+        (
+            DatasetAlertSettingsInput {
+                notify_owner: false,
+                recipients: vec![],
+                ..input(None)
+            },
+            "At least one recipient is required",
+        ),
+        (
+            DatasetAlertSettingsInput {
+                recipients: (0..21)
+                    .map(|i| email(&format!("user{i}@example.com")))
+                    .collect(),
+                ..input(None)
+            },
+            "At most 20 recipients are allowed",
+        ),
+        (
+            DatasetAlertSettingsInput {
+                notify_on_flow_failure: false,
+                ..input(None)
+            },
+            "At least one alert condition is required",
+        ),
+        (
+            input(Some(Duration::seconds(30))),
+            "Staleness window must be at least one minute",
+        ),
+    ];
+
+    for (settings_input, expected_reason) in cases {
+        assert_matches!(
+            harness.service.set_settings(&dataset_id, settings_input).await,
+            Err(SetDatasetAlertSettingsError::Invalid(e)) if e.reason == expected_reason
+        );
+    }
+
+    assert_eq!(
+        harness.service.get_settings(&dataset_id).await.unwrap(),
+        None
+    );
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+fn email(s: &str) -> Email {
+    Email::parse(s).unwrap()
+}
+
+fn input(stale_after: Option<Duration>) -> DatasetAlertSettingsInput {
+    DatasetAlertSettingsInput {
+        notify_owner: true,
+        recipients: vec![],
+        notify_on_flow_failure: true,
+        stale_after,
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+struct DatasetAlertsServiceHarness {
+    _catalog: Catalog,
+    service: Arc<dyn DatasetAlertsService>,
+    settings_repo: Arc<dyn DatasetAlertSettingsRepository>,
+    time_source: Arc<SystemTimeSourceStub>,
+    t0: DateTime<Utc>,
+}
+
+impl DatasetAlertsServiceHarness {
+    fn new() -> Self {
+        let t0 = Utc.with_ymd_and_hms(2025, 1, 1, 12, 0, 0).unwrap();
+
+        let catalog = {
+            let mut b = CatalogBuilder::new();
+
+            b.add::<DatasetAlertsServiceImpl>()
+                .add::<InMemoryDatasetAlertSettingsRepository>()
+                .add_value(SystemTimeSourceStub::new_set(t0))
+                .bind::<dyn SystemTimeSource, SystemTimeSourceStub>();
+
+            b.build()
+        };
+
+        Self {
+            service: catalog.get_one().unwrap(),
+            settings_repo: catalog.get_one().unwrap(),
+            time_source: catalog.get_one().unwrap(),
+            _catalog: catalog,
+            t0,
+        }
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
@@ -45,6 +45,11 @@ pub trait RebacService: Send + Sync {
         property_name: AccountPropertyName,
     ) -> Result<(), UnsetEntityPropertyError>;
 
+    async fn delete_account_properties(
+        &self,
+        account_id: &odf::AccountID,
+    ) -> Result<(), DeletePropertiesError>;
+
     async fn get_account_properties(
         &self,
         account_id: &odf::AccountID,
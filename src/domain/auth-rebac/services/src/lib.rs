@@ -12,12 +12,14 @@ pub use kamu_auth_rebac as domain;
 
 mod jobs;
 mod messages;
+mod rebac_account_lifecycle_message_consumer;
 mod rebac_dataset_lifecycle_message_consumer;
 mod rebac_indexer;
 mod rebac_service_impl;
 
 pub use jobs::*;
 pub use messages::*;
+pub use rebac_account_lifecycle_message_consumer::*;
 pub use rebac_dataset_lifecycle_message_consumer::*;
 pub use rebac_indexer::*;
 pub use rebac_service_impl::*;
@@ -0,0 +1,111 @@
+// Copyright Kamu Data, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::sync::Arc;
+
+use dill::{component, interface, meta, Catalog};
+use internal_error::{InternalError, ResultIntoInternal};
+use kamu_accounts::{
+    AccountLifecycleMessage,
+    AccountLifecycleMessageDeleted,
+    MESSAGE_PRODUCER_KAMU_ACCOUNTS_SERVICE,
+};
+use kamu_auth_rebac::{RebacService, Relation};
+use messaging_outbox::{
+    MessageConsumer,
+    MessageConsumerMeta,
+    MessageConsumerT,
+    MessageDeliveryMechanism,
+};
+
+use crate::MESSAGE_CONSUMER_KAMU_REBAC_SERVICE;
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+pub struct RebacAccountLifecycleMessageConsumer {
+    rebac_service: Arc<dyn RebacService>,
+}
+
+#[component(pub)]
+#[interface(dyn MessageConsumer)]
+#[interface(dyn MessageConsumerT<AccountLifecycleMessage>)]
+#[meta(MessageConsumerMeta {
+    consumer_name: MESSAGE_CONSUMER_KAMU_REBAC_SERVICE,
+    feeding_producers: &[
+        MESSAGE_PRODUCER_KAMU_ACCOUNTS_SERVICE,
+    ],
+    delivery: MessageDeliveryMechanism::Immediate,
+})]
+impl RebacAccountLifecycleMessageConsumer {
+    pub fn new(rebac_service: Arc<dyn RebacService>) -> Self {
+        Self { rebac_service }
+    }
+
+    async fn handle_account_lifecycle_deleted_message(
+        &self,
+        message: &AccountLifecycleMessageDeleted,
+    ) -> Result<(), InternalError> {
+        let dataset_relations = self
+            .rebac_service
+            .get_account_dataset_relations(&message.account_id)
+            .await
+            .int_err()?;
+
+        for entity_with_relation in dataset_relations {
+            let Relation::AccountToDataset(relation) = entity_with_relation.relation;
+            let dataset_id =
+                odf::DatasetID::from_did_str(&entity_with_relation.entity.entity_id).int_err()?;
+
+            self.rebac_service
+                .delete_account_dataset_relation(&message.account_id, relation, &dataset_id)
+                .await
+                .int_err()?;
+        }
+
+        self.rebac_service
+            .delete_account_properties(&message.account_id)
+            .await
+            .int_err()
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+impl MessageConsumer for RebacAccountLifecycleMessageConsumer {}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[async_trait::async_trait]
+impl MessageConsumerT<AccountLifecycleMessage> for RebacAccountLifecycleMessageConsumer {
+    #[tracing::instrument(
+        level = "debug",
+        skip_all,
+        name = "RebacAccountLifecycleMessageConsumer[AccountLifecycleMessage]"
+    )]
+    async fn consume_message(
+        &self,
+        _: &Catalog,
+        message: &AccountLifecycleMessage,
+    ) -> Result<(), InternalError> {
+        tracing::debug!(received_message = ?message, "Received account lifecycle message");
+
+        match message {
+            // Default account properties apply until set explicitly
+            AccountLifecycleMessage::Created(_) | AccountLifecycleMessage::EmailChanged(_) => {
+                Ok(())
+            }
+
+            AccountLifecycleMessage::Deleted(message) => {
+                self.handle_account_lifecycle_deleted_message(message).await
+            }
+        }
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
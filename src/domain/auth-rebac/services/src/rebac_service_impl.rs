@@ -104,6 +104,26 @@ impl RebacService for RebacServiceImpl {
             .await
     }
 
+    async fn delete_account_properties(
+        &self,
+        account_id: &odf::AccountID,
+    ) -> Result<(), DeletePropertiesError> {
+        let account_id = account_id.as_did_str().to_stack_string();
+        let account_entity = Entity::new_account(account_id.as_str());
+
+        match self
+            .rebac_repo
+            .delete_entity_properties(&account_entity)
+            .await
+        {
+            Ok(_) => Ok(()),
+            Err(err) => match err {
+                DeleteEntityPropertiesError::NotFound(_) => Ok(()),
+                DeleteEntityPropertiesError::Internal(e) => Err(DeletePropertiesError::Internal(e)),
+            },
+        }
+    }
+
     async fn get_account_properties(
         &self,
         account_id: &odf::AccountID,
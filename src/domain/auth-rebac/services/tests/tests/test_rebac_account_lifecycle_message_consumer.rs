@@ -0,0 +1,140 @@
+// Copyright Kamu Data, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::assert_matches::assert_matches;
+use std::sync::Arc;
+
+use dill::{Catalog, CatalogBuilder};
+use email_utils::Email;
+use kamu_accounts::AccountLifecycleMessage;
+use kamu_auth_rebac::{
+    AccountPropertyName,
+    AccountToDatasetRelation,
+    Entity,
+    RebacRepository,
+    RebacService,
+};
+use kamu_auth_rebac_inmem::InMemoryRebacRepository;
+use kamu_auth_rebac_services::{RebacAccountLifecycleMessageConsumer, RebacServiceImpl};
+use messaging_outbox::{consume_deserialized_message, ConsumerFilter, Message};
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[test_log::test(tokio::test)]
+async fn test_rebac_account_cleaned_up_on_deletion() {
+    let harness = RebacAccountLifecycleMessageConsumerHarness::new();
+
+    let (_, account_id) = odf::AccountID::new_generated_ed25519();
+    let (_, another_account_id) = odf::AccountID::new_generated_ed25519();
+    let (_, dataset_id) = odf::DatasetID::new_generated_ed25519();
+
+    for id in [&account_id, &another_account_id] {
+        let (name, value) = AccountPropertyName::is_admin(true);
+        harness
+            .rebac_service
+            .set_account_property(id, name, &value)
+            .await
+            .unwrap();
+        harness
+            .rebac_service
+            .insert_account_dataset_relation(id, AccountToDatasetRelation::Editor, &dataset_id)
+            .await
+            .unwrap();
+    }
+
+    harness
+        .mimic(AccountLifecycleMessage::deleted(
+            account_id.clone(),
+            odf::AccountName::new_unchecked("wasya"),
+            Email::parse("wasya@example.com").unwrap(),
+        ))
+        .await;
+
+    let account_entity = Entity::new_account(account_id.to_string());
+    assert_matches!(
+        harness
+            .rebac_repo
+            .get_entity_properties(&account_entity)
+            .await
+            .as_deref(),
+        Ok([])
+    );
+    assert_matches!(
+        harness
+            .rebac_service
+            .get_account_dataset_relations(&account_id)
+            .await
+            .as_deref(),
+        Ok([])
+    );
+
+    // Other accounts are not affected
+    assert_matches!(
+        harness
+            .rebac_service
+            .get_account_properties(&another_account_id)
+            .await,
+        Ok(properties) if properties.is_admin
+    );
+    assert_matches!(
+        harness
+            .rebac_service
+            .get_account_dataset_relations(&another_account_id)
+            .await
+            .as_deref(),
+        Ok([_])
+    );
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+struct RebacAccountLifecycleMessageConsumerHarness {
+    catalog: Catalog,
+    rebac_repo: Arc<dyn RebacRepository>,
+    rebac_service: Arc<dyn RebacService>,
+}
+
+impl RebacAccountLifecycleMessageConsumerHarness {
+    pub fn new() -> Self {
+        let mut catalog_builder = CatalogBuilder::new();
+
+        catalog_builder
+            .add::<RebacAccountLifecycleMessageConsumer>()
+            .add::<RebacServiceImpl>()
+            .add_value(kamu_auth_rebac_services::DefaultAccountProperties { is_admin: false })
+            .add_value(kamu_auth_rebac_services::DefaultDatasetProperties {
+                allows_anonymous_read: false,
+                allows_public_read: false,
+            })
+            .add::<InMemoryRebacRepository>();
+
+        let catalog = catalog_builder.build();
+
+        Self {
+            rebac_repo: catalog.get_one().unwrap(),
+            rebac_service: catalog.get_one().unwrap(),
+            catalog,
+        }
+    }
+
+    pub async fn mimic<TMessage: Message + 'static>(&self, message: TMessage) {
+        let content_json = serde_json::to_string(&message).unwrap();
+
+        consume_deserialized_message::<TMessage>(
+            &self.catalog,
+            ConsumerFilter::AllConsumers,
+            &content_json,
+            TMessage::version(),
+        )
+        .await
+        .unwrap();
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
@@ -298,6 +298,16 @@ impl std::fmt::Display for InternalEngineError {
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
 
 impl EngineError {
+    /// Log files of the engine that may explain the error
+    pub fn log_files(&self) -> &[PathBuf] {
+        match self {
+            EngineError::InvalidQuery(e) => &e.log_files,
+            EngineError::ProcessError(e) => &e.log_files,
+            EngineError::ContractError(e) => &e.log_files,
+            EngineError::InternalError(e) => &e.log_files,
+        }
+    }
+
     pub fn invalid_query(message: impl Into<String>, log_files: Vec<PathBuf>) -> Self {
         EngineError::InvalidQuery(InvalidQueryError {
             message: message.into(),
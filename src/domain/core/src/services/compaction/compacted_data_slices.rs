@@ -0,0 +1,35 @@
+// Copyright Kamu Data, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use serde::{Deserialize, Serialize};
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+/// Data files produced by soft compactions, kept alongside the dataset
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+pub struct CompactedDataSlices {
+    pub slices: Vec<CompactedDataSlice>,
+}
+
+/// File in the data repository holding the records of all data slices within
+/// the offset interval. The slice is only valid for chains that contain its
+/// last block, as offsets can be reused after history was rewritten
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+pub struct CompactedDataSlice {
+    pub physical_hash: odf::Multihash,
+    pub size: u64,
+    pub start_offset: u64,
+    pub end_offset: u64,
+    /// Newest block whose data is included in the slice
+    pub last_block_hash: odf::Multihash,
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
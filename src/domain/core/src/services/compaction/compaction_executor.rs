@@ -38,6 +38,11 @@ pub enum CompactionResult {
         old_num_blocks: usize,
         new_num_blocks: usize,
     },
+    /// Result of a soft compaction, which leaves the metadata chain intact
+    DataSlicesMerged {
+        num_merged_slices: usize,
+        num_new_files: usize,
+    },
 }
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
@@ -48,6 +48,7 @@ pub enum CompactionPhase {
     GatherChainInfo,
     MergeDataslices,
     CommitNewBlocks,
+    SaveMergedDataSlices,
 }
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
@@ -15,12 +15,13 @@ use serde::{Deserialize, Serialize};
 use thiserror::Error;
 use url::Url;
 
-use crate::{CompactionListener, ResolvedDataset};
+use crate::{CompactedDataSlice, CompactionListener, ResolvedDataset};
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
 
 pub const DEFAULT_MAX_SLICE_SIZE: u64 = 300_000_000;
 pub const DEFAULT_MAX_SLICE_RECORDS: u64 = 10_000;
+pub const DEFAULT_MERGE_PARALLELISM: usize = 1;
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
 
@@ -41,6 +42,16 @@ pub struct CompactionOptions {
     pub max_slice_size: Option<u64>,
     pub max_slice_records: Option<u64>,
     pub keep_metadata_only: bool,
+    /// When specified only the blocks containing records at or after this
+    /// offset are compacted, leaving the older part of the chain intact.
+    /// Ignored when `keep_metadata_only` is set
+    pub start_offset: Option<u64>,
+    /// How many batches of data slices can be merged concurrently
+    pub merge_parallelism: Option<usize>,
+    /// Order of records in merged data slices. By default records are kept in
+    /// the order of their offsets. Hard compaction only
+    pub sort_by: Option<CompactionSortBy>,
+    pub mode: CompactionMode,
 }
 
 impl Default for CompactionOptions {
@@ -49,25 +60,67 @@ impl Default for CompactionOptions {
             max_slice_size: Some(DEFAULT_MAX_SLICE_SIZE),
             max_slice_records: Some(DEFAULT_MAX_SLICE_RECORDS),
             keep_metadata_only: false,
+            start_offset: None,
+            merge_parallelism: None,
+            sort_by: None,
+            mode: CompactionMode::Hard,
         }
     }
 }
 
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum CompactionMode {
+    /// Rewrites the metadata chain as if data was originally written in
+    /// bigger slices
+    #[default]
+    Hard,
+    /// Merges data slices into separate files, leaving the metadata chain
+    /// intact. Queries read the merged files in place of the slices they cover
+    Soft,
+}
+
+/// Re-sorting records of a merged slice reassigns their offsets within the
+/// offset interval of the slice, so that offsets keep growing monotonically
+/// through the file
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum CompactionSortBy {
+    EventTime,
+    Columns(Vec<String>),
+}
+
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
 
 #[derive(Debug)]
 pub struct CompactionPlan {
-    pub seed: odf::Multihash,
+    /// Block on top of which the compacted blocks are committed: the seed
+    /// block, or the last block preceding the compacted range. Soft
+    /// compactions commit no blocks and keep the head here
+    pub base_block: odf::Multihash,
     pub old_head: odf::Multihash,
+    /// Number of blocks in the compacted range, including the base block
     pub old_num_blocks: usize,
     pub offset_column_name: String,
+    /// Columns to sort merged records by, empty when records are kept in the
+    /// offset order
+    pub sort_columns: Vec<String>,
+    pub merge_parallelism: usize,
+    pub mode: CompactionMode,
     pub data_slice_batches: Vec<CompactionDataSliceBatch>,
+    /// Previously merged slices that are still valid for the current chain.
+    /// Soft compaction only
+    pub compacted_data_slices: Vec<CompactedDataSlice>,
+    /// All merged slices recorded at the time of planning, used to detect
+    /// concurrent modifications. Soft compaction only
+    pub indexed_data_slices: Vec<CompactedDataSlice>,
 }
 
 impl CompactionPlan {
     pub fn has_no_effect(&self) -> bool {
-        // slices amount +1(seed block) eq to amount of blocks we should not compact
-        self.data_slice_batches.len() + 1 == self.old_num_blocks
+        match self.mode {
+            // slices amount +1(base block) eq to amount of blocks we should not compact
+            CompactionMode::Hard => self.data_slice_batches.len() + 1 == self.old_num_blocks,
+            CompactionMode::Soft => self.data_slice_batches.is_empty(),
+        }
     }
 }
 
@@ -94,6 +147,8 @@ pub struct CompactionDataSliceBatchUpperBound {
     pub new_watermark: Option<DateTime<Utc>>,
     pub new_checkpoint: Option<odf::Checkpoint>,
     pub end_offset: u64,
+    /// Newest block of the batch
+    pub block_hash: Option<odf::Multihash>,
 }
 
 #[derive(Debug, Default, Clone)]
@@ -114,6 +169,13 @@ pub enum CompactionPlanningError {
         InvalidDatasetKindError,
     ),
 
+    #[error(transparent)]
+    UnknownSortColumn(
+        #[from]
+        #[backtrace]
+        UnknownSortColumnError,
+    ),
+
     #[error(transparent)]
     Access(
         #[from]
@@ -133,6 +195,13 @@ pub struct InvalidDatasetKindError {
     pub dataset_alias: odf::DatasetAlias,
 }
 
+#[derive(Error, Debug)]
+#[error("Dataset '{dataset_alias}' has no column '{column}' to sort by")]
+pub struct UnknownSortColumnError {
+    pub dataset_alias: odf::DatasetAlias,
+    pub column: String,
+}
+
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
 
 impl From<odf::GetRefError> for CompactionPlanningError {
@@ -7,10 +7,12 @@
 // the Business Source License, use of this software will be governed
 // by the Apache License, Version 2.0.
 
+mod compacted_data_slices;
 mod compaction_executor;
 mod compaction_listener;
 mod compaction_planner;
 
+pub use compacted_data_slices::*;
 pub use compaction_executor::*;
 pub use compaction_listener::*;
 pub use compaction_planner::*;
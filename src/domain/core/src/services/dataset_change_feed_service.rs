@@ -0,0 +1,77 @@
+// Copyright Kamu Data, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use datafusion::arrow::datatypes::SchemaRef;
+use datafusion::arrow::record_batch::RecordBatch;
+
+use crate::QueryError;
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+/// Allows consumers to incrementally read records as they are being committed
+/// to a dataset, without re-querying the entire dataset on every update.
+///
+/// The service is stateless - the progress of a consumer is fully described
+/// by the [`ChangeFeedCursor`] which is returned with every chunk and should be
+/// passed back to read the next one.
+#[async_trait::async_trait]
+pub trait DatasetChangeFeedService: Send + Sync {
+    /// Reads the next chunk of records following the cursor position.
+    ///
+    /// Returns at most `max_records` records ordered by offset. When the
+    /// returned chunk is marked as caught up the consumer has seen all the
+    /// records up to the current head of the dataset and should wait before
+    /// polling again.
+    async fn read_changes(
+        &self,
+        dataset_ref: &odf::DatasetRef,
+        cursor: &ChangeFeedCursor,
+        max_records: u64,
+    ) -> Result<DatasetChangesChunk, QueryError>;
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ChangeFeedCursor {
+    /// Offset of the last record that was delivered to the consumer
+    pub last_offset: Option<u64>,
+    /// Head of the dataset at the moment the consumer has caught up with all
+    /// changes. Used to cheaply skip polls when nothing was committed, or
+    /// when new blocks did not contain any data.
+    pub caught_up_head: Option<odf::Multihash>,
+}
+
+impl ChangeFeedCursor {
+    /// Starts reading changes after the specified offset, or from the very
+    /// beginning of the dataset if `None`
+    pub fn since_offset(last_offset: Option<u64>) -> Self {
+        Self {
+            last_offset,
+            caught_up_head: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct DatasetChangesChunk {
+    /// Schema of the records, `None` if the dataset doesn't have any data yet
+    pub schema: Option<SchemaRef>,
+    /// New records ordered by offset, including the `op` column that
+    /// distinguishes appends from retractions and corrections
+    pub record_batches: Vec<RecordBatch>,
+    /// Number of records in this chunk
+    pub num_records: u64,
+    /// Cursor to use when reading the next chunk
+    pub cursor: ChangeFeedCursor,
+    /// Whether all records up to the current head have been read
+    pub caught_up: bool,
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
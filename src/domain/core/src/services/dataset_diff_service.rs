@@ -0,0 +1,103 @@
+// Copyright Kamu Data, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use datafusion::prelude::DataFrame;
+use internal_error::InternalError;
+use thiserror::Error;
+
+use crate::ResolvedDataset;
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[async_trait::async_trait]
+pub trait DatasetDiffService: Send + Sync {
+    /// Computes metadata and data changes that happened in the dataset between
+    /// two blocks. The `from` block is exclusive and defaults to the beginning
+    /// of the chain, the `to` block is inclusive and defaults to the current
+    /// head.
+    async fn diff(
+        &self,
+        target: ResolvedDataset,
+        from: Option<&odf::Multihash>,
+        to: Option<&odf::Multihash>,
+    ) -> Result<DatasetDiff, DatasetDiffError>;
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[derive(Debug)]
+pub struct DatasetDiff {
+    pub from_block: Option<odf::Multihash>,
+    pub to_block: odf::Multihash,
+    /// Blocks added after `from` up to and including `to`, in reverse
+    /// chronological order
+    pub metadata_blocks: Vec<odf::dataset::HashedMetadataBlock>,
+    pub data: DatasetDataDiff,
+}
+
+#[derive(Debug, Default)]
+pub struct DatasetDataDiff {
+    /// Offsets of all records written within the interval
+    pub offset_interval: Option<odf::metadata::OffsetInterval>,
+    pub num_appended: u64,
+    pub num_retracted: u64,
+    /// Number of corrected records, where every correction is counted once
+    /// even though it is represented by a pair of `-C` / `+C` records
+    pub num_corrected: u64,
+    /// Primary key of the snapshot merge strategy, when the dataset uses one.
+    /// Changed records are grouped by these columns.
+    pub primary_key: Vec<String>,
+    /// Records written within the interval, ordered by the primary key (if
+    /// known) and offset. `None` when no new data was added.
+    pub records: Option<DataFrame>,
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+// Errors
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[derive(Debug, Error)]
+pub enum DatasetDiffError {
+    #[error(transparent)]
+    BlockNotFound(
+        #[from]
+        #[backtrace]
+        odf::storage::BlockNotFoundError,
+    ),
+
+    #[error(transparent)]
+    InvalidInterval(
+        #[from]
+        #[backtrace]
+        InvalidDiffIntervalError,
+    ),
+
+    #[error(transparent)]
+    Access(
+        #[from]
+        #[backtrace]
+        odf::AccessError,
+    ),
+
+    #[error(transparent)]
+    Internal(
+        #[from]
+        #[backtrace]
+        InternalError,
+    ),
+}
+
+#[derive(Error, Clone, PartialEq, Eq, Debug)]
+#[error("Block {from} is not an ancestor of block {to}")]
+pub struct InvalidDiffIntervalError {
+    pub from: odf::Multihash,
+    pub to: odf::Multihash,
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
@@ -0,0 +1,189 @@
+// Copyright Kamu Data, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use internal_error::InternalError;
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+use thiserror::Error;
+
+use crate::ResolvedDataset;
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+/// Manages data quality expectations declared on a dataset and the reports
+/// produced when they are evaluated during ingest and transform
+#[async_trait::async_trait]
+pub trait DatasetExpectationsService: Send + Sync {
+    /// Returns expectations currently declared on the dataset
+    async fn get_expectations(
+        &self,
+        target: &ResolvedDataset,
+    ) -> Result<Vec<DataExpectation>, InternalError>;
+
+    /// Validates the expectations and prepares the `SetAttachments` event that
+    /// replaces all expectations of the dataset with them, preserving other
+    /// attachments. Returns `None` when the expectations are unchanged.
+    async fn prepare_set_expectations(
+        &self,
+        target: &ResolvedDataset,
+        expectations: Vec<DataExpectation>,
+    ) -> Result<Option<odf::MetadataEvent>, SetDataExpectationsError>;
+
+    /// Returns the report of the expectations that were evaluated when the
+    /// specified block was committed, if any. Reports are recorded in the
+    /// metadata chain by the block that immediately follows the checked one.
+    async fn get_report(
+        &self,
+        target: &ResolvedDataset,
+        block_hash: &odf::Multihash,
+    ) -> Result<Option<DataExpectationsReport>, InternalError>;
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+pub struct DatasetExpectations {
+    pub expectations: Vec<DataExpectation>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+pub struct DataExpectation {
+    /// Unique name of the expectation within a dataset
+    pub name: String,
+    pub rule: DataExpectationRule,
+    /// What to do with the new data when the expectation is not met
+    #[serde(default)]
+    pub on_failure: DataExpectationAction,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", deny_unknown_fields)]
+pub enum DataExpectationRule {
+    /// Column must not contain null values
+    NotNull { column: String },
+    /// Combination of columns must be unique among the new records
+    Unique { columns: Vec<String> },
+    /// Numeric column values must fall within the inclusive range
+    ValueRange {
+        column: String,
+        min: Option<f64>,
+        max: Option<f64>,
+    },
+    /// String column values must match the regular expression
+    RegexMatch { column: String, pattern: String },
+    /// Number of new records must fall within the inclusive range
+    RowCount { min: Option<u64>, max: Option<u64> },
+}
+
+impl DataExpectationRule {
+    /// Row-level rules can be evaluated for every record individually, which
+    /// makes it possible to quarantine only the records that violate them
+    pub fn is_row_level(&self) -> bool {
+        match self {
+            Self::NotNull { .. } | Self::ValueRange { .. } | Self::RegexMatch { .. } => true,
+            Self::Unique { .. } | Self::RowCount { .. } => false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum DataExpectationAction {
+    /// Reject the whole commit
+    #[default]
+    Fail,
+    /// Exclude violating records from the commit. Only applicable to row-level
+    /// rules during ingest - in all other cases behaves like [`Self::Fail`]
+    Quarantine,
+    /// Commit the data as is and record the violation in the report
+    Warn,
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[skip_serializing_none]
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+pub struct DataExpectationsReport {
+    pub results: Vec<DataExpectationResult>,
+    /// Number of records that were excluded from the commit
+    pub num_quarantined: u64,
+    /// Physical hash of the Parquet file in the data repository holding the
+    /// excluded records
+    pub quarantined_data: Option<odf::Multihash>,
+}
+
+impl DataExpectationsReport {
+    pub fn num_failed(&self) -> usize {
+        self.results.iter().filter(|r| !r.passed).count()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+pub struct DataExpectationResult {
+    pub name: String,
+    /// Action that was applied, which may differ from the declared one when
+    /// quarantine was not applicable
+    pub action: DataExpectationAction,
+    pub passed: bool,
+    /// Number of records violating the expectation. For the row count rule this
+    /// is the total number of records when the bounds were not met.
+    pub num_violations: u64,
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+// Errors
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[derive(Debug, Error)]
+pub enum SetDataExpectationsError {
+    #[error(transparent)]
+    Invalid(
+        #[from]
+        #[backtrace]
+        InvalidDataExpectationError,
+    ),
+
+    #[error(transparent)]
+    Internal(
+        #[from]
+        #[backtrace]
+        InternalError,
+    ),
+}
+
+#[derive(Error, Clone, PartialEq, Eq, Debug)]
+#[error("Invalid expectation '{name}': {reason}")]
+pub struct InvalidDataExpectationError {
+    pub name: String,
+    pub reason: String,
+}
+
+#[derive(Error, Clone, PartialEq, Eq, Debug)]
+pub struct DataExpectationsFailedError {
+    pub failed: Vec<DataExpectationResult>,
+}
+
+impl std::fmt::Display for DataExpectationsFailedError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Data quality expectations failed: ")?;
+        for (i, res) in self.failed.iter().enumerate() {
+            if i != 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{} ({} violations)", res.name, res.num_violations)?;
+        }
+        Ok(())
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
@@ -0,0 +1,45 @@
+// Copyright Kamu Data, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use chrono::Duration;
+use internal_error::InternalError;
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+/// Periodically evaluates freshness of all datasets that declare an SLA and
+/// reports it via metrics
+#[async_trait::async_trait]
+pub trait DatasetFreshnessAgent: Send + Sync {
+    async fn run(&self) -> Result<(), InternalError>;
+
+    /// Evaluates freshness of all datasets once
+    async fn run_freshness_checks(&self) -> Result<(), InternalError>;
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[derive(Debug, Clone)]
+pub struct DatasetFreshnessConfig {
+    /// How often freshness of datasets is evaluated
+    pub check_interval: Duration,
+}
+
+impl DatasetFreshnessConfig {
+    pub fn new(check_interval: Duration) -> Self {
+        Self { check_interval }
+    }
+}
+
+impl Default for DatasetFreshnessConfig {
+    fn default() -> Self {
+        Self::new(Duration::minutes(1))
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
@@ -0,0 +1,138 @@
+// Copyright Kamu Data, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use chrono::{DateTime, Duration, Utc};
+use internal_error::InternalError;
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+use thiserror::Error;
+
+use crate::ResolvedDataset;
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+/// Manages freshness SLAs declared on datasets and evaluates whether datasets
+/// receive new data as often as expected
+#[async_trait::async_trait]
+pub trait DatasetFreshnessService: Send + Sync {
+    /// Returns the SLA declared on the dataset, if any
+    async fn get_sla(
+        &self,
+        target: &ResolvedDataset,
+    ) -> Result<Option<DatasetFreshnessSla>, InternalError>;
+
+    /// Declares the SLA of the dataset, or removes it if `None` is specified
+    async fn set_sla(
+        &self,
+        target: &ResolvedDataset,
+        sla: Option<DatasetFreshnessSla>,
+    ) -> Result<(), SetFreshnessSlaError>;
+
+    /// Evaluates freshness of the dataset against its SLA at the current time
+    async fn get_freshness(
+        &self,
+        target: &ResolvedDataset,
+    ) -> Result<DatasetFreshness, InternalError>;
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[serde_with::serde_as]
+#[skip_serializing_none]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+pub struct DatasetFreshnessSla {
+    /// New data is expected to be added at least this often
+    #[serde(rename = "maxUpdateIntervalSecs")]
+    #[serde_as(as = "serde_with::DurationSeconds<i64>")]
+    pub max_update_interval: Duration,
+
+    /// Watermark is expected to lag behind the current time by at most this
+    /// much
+    #[serde(default, rename = "maxWatermarkLagSecs")]
+    #[serde_as(as = "Option<serde_with::DurationSeconds<i64>>")]
+    pub max_watermark_lag: Option<Duration>,
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DatasetFreshness {
+    pub sla: Option<DatasetFreshnessSla>,
+    /// System time of the last `AddData` or `ExecuteTransform` block that
+    /// added new data
+    pub last_updated_at: Option<DateTime<Utc>>,
+    pub watermark: Option<DateTime<Utc>>,
+    pub status: FreshnessStatus,
+}
+
+impl DatasetFreshness {
+    pub fn evaluate(
+        sla: Option<DatasetFreshnessSla>,
+        last_updated_at: Option<DateTime<Utc>>,
+        watermark: Option<DateTime<Utc>>,
+        now: DateTime<Utc>,
+    ) -> Self {
+        let status = match &sla {
+            None => FreshnessStatus::Undefined,
+            Some(sla) => {
+                // Datasets that never received any data are considered stale
+                let update_overdue =
+                    last_updated_at.is_none_or(|t| now - t > sla.max_update_interval);
+
+                let watermark_overdue = sla
+                    .max_watermark_lag
+                    .is_some_and(|max_lag| watermark.is_none_or(|t| now - t > max_lag));
+
+                if update_overdue || watermark_overdue {
+                    FreshnessStatus::Stale
+                } else {
+                    FreshnessStatus::Fresh
+                }
+            }
+        };
+
+        Self {
+            sla,
+            last_updated_at,
+            watermark,
+            status,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, strum::Display)]
+pub enum FreshnessStatus {
+    /// Dataset does not declare an SLA
+    Undefined,
+    /// Dataset satisfies its SLA
+    Fresh,
+    /// Dataset did not receive new data within the expected interval, or its
+    /// watermark lags behind more than allowed
+    Stale,
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[derive(Error, Debug)]
+pub enum SetFreshnessSlaError {
+    #[error(transparent)]
+    Invalid(#[from] InvalidFreshnessSlaError),
+
+    #[error(transparent)]
+    Internal(#[from] InternalError),
+}
+
+#[derive(Error, Debug)]
+#[error("Invalid freshness SLA: {reason}")]
+pub struct InvalidFreshnessSlaError {
+    pub reason: String,
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
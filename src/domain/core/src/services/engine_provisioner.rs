@@ -14,6 +14,7 @@ use internal_error::InternalError;
 use thiserror::Error;
 
 use crate::entities::engine::Engine;
+use crate::QueryDialect;
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
 // EngineProvisioner
@@ -28,6 +29,61 @@ pub trait EngineProvisioner: Send + Sync {
     ) -> Result<Arc<dyn Engine>, EngineProvisioningError>;
 }
 
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+// Registry
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+/// Identifiers of engines that are always available
+pub const BUILTIN_ENGINE_IDS: [&str; 4] = ["spark", "flink", "datafusion", "risingwave"];
+
+/// Engines that can be provisioned in addition to the built-in ones
+#[derive(Debug, Clone, Default)]
+pub struct EngineRegistryConfig {
+    pub custom_engines: Vec<CustomEngineSpec>,
+}
+
+impl EngineRegistryConfig {
+    /// Looks up a custom engine by its case-insensitive identifier
+    pub fn get_custom_engine(&self, engine_id: &str) -> Option<&CustomEngineSpec> {
+        self.custom_engines
+            .iter()
+            .find(|e| e.name.eq_ignore_ascii_case(engine_id) && !e.shadows_builtin_engine())
+    }
+}
+
+/// User-defined engine that implements the ODF engine protocol
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CustomEngineSpec {
+    /// Identifier used to refer to the engine, e.g. in `TransformSql.engine`
+    pub name: String,
+    /// OCI image repository and a tag of the engine image
+    pub image: String,
+    /// Language and dialect this engine is using for queries
+    pub dialect: QueryDialect,
+    /// How input and output data is passed to the engine container
+    pub io_strategy: EngineIoStrategyKind,
+}
+
+impl CustomEngineSpec {
+    /// Built-in engines cannot be overridden, only their images can be
+    /// changed, so such specs are ignored
+    pub fn shadows_builtin_engine(&self) -> bool {
+        BUILTIN_ENGINE_IDS
+            .iter()
+            .any(|id| id.eq_ignore_ascii_case(&self.name))
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, ::serde::Serialize, ::serde::Deserialize)]
+pub enum EngineIoStrategyKind {
+    /// Mounts datasets from the local file system as volumes and proxies the
+    /// remote ones
+    #[default]
+    Auto,
+    /// Always proxies the data, even for datasets stored locally
+    RemoteProxy,
+}
+
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
 // Listener
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
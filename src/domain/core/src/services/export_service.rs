@@ -12,6 +12,7 @@ use std::path::Path;
 use datafusion::dataframe::DataFrame;
 use internal_error::{BoxedError, InternalError};
 use thiserror::Error;
+use url::Url;
 
 use crate::*;
 
@@ -27,6 +28,16 @@ pub trait ExportService: Send + Sync {
         path: &Path,
         options: ExportOptions,
     ) -> Result<u64, ExportError>;
+
+    /// Maintains an Apache Iceberg table at the given location (local FS or
+    /// S3) mirroring the dataset. Every block that added data becomes a
+    /// separate table snapshot, so repeated exports only append the blocks
+    /// that appeared since the previous one
+    async fn export_to_iceberg(
+        &self,
+        target: ResolvedDataset,
+        table_url: &Url,
+    ) -> Result<IcebergExportResult, ExportError>;
 }
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
@@ -41,6 +52,9 @@ pub enum ExportFormat {
 
     #[strum(to_string = "ndjson")]
     NdJson,
+
+    #[strum(to_string = "iceberg")]
+    Iceberg,
 }
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
@@ -59,6 +73,17 @@ impl Default for ExportOptions {
     }
 }
 
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IcebergExportResult {
+    /// Last block exported into the table before and after the export
+    pub old_head: Option<odf::Multihash>,
+    pub new_head: Option<odf::Multihash>,
+    pub num_snapshots: usize,
+    pub num_records: u64,
+}
+
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
 // Errors
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
@@ -81,6 +106,36 @@ pub enum ExportError {
     ),
     #[error(transparent)]
     DataFusionError(DataFusionError),
+    #[error(transparent)]
+    IcebergTableOutOfSync(IcebergTableOutOfSyncError),
+    #[error(transparent)]
+    IcebergTableDatasetMismatch(IcebergTableDatasetMismatchError),
+    #[error(transparent)]
+    UnsupportedDataType(ExportUnsupportedDataTypeError),
+}
+
+#[derive(Debug, Error)]
+#[error(
+    "Iceberg table is out of sync with dataset {dataset_id}: it was last exported from block \
+     {last_exported_block}, which is not in the dataset history"
+)]
+pub struct IcebergTableOutOfSyncError {
+    pub dataset_id: odf::DatasetID,
+    pub last_exported_block: odf::Multihash,
+}
+
+#[derive(Debug, Error)]
+#[error("Iceberg table mirrors dataset {table_dataset_id}, not {dataset_id}")]
+pub struct IcebergTableDatasetMismatchError {
+    pub dataset_id: odf::DatasetID,
+    pub table_dataset_id: String,
+}
+
+#[derive(Debug, Error)]
+#[error("Column '{column}' has type {data_type} that can't be exported")]
+pub struct ExportUnsupportedDataTypeError {
+    pub column: String,
+    pub data_type: String,
 }
 
 impl From<datafusion::error::DataFusionError> for ExportError {
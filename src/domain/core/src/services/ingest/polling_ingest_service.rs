@@ -182,6 +182,9 @@ pub enum TemplateError {
 
     #[error(transparent)]
     InvalidPattern(TemplateInvalidPatternError),
+
+    #[error(transparent)]
+    InvalidDocument(TemplateInvalidDocumentError),
 }
 
 #[derive(Error, Debug)]
@@ -212,6 +215,20 @@ impl TemplateInvalidPatternError {
     }
 }
 
+#[derive(Error, Debug)]
+#[error("Template is not a valid YAML document: {message}")]
+pub struct TemplateInvalidDocumentError {
+    pub message: String,
+}
+
+impl TemplateInvalidDocumentError {
+    pub fn new(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+        }
+    }
+}
+
 // TODO: Revisit error granularity
 #[derive(Debug, Error)]
 pub enum PollingIngestError {
@@ -320,6 +337,13 @@ pub enum PollingIngestError {
         TemplateError,
     ),
 
+    #[error(transparent)]
+    ExpectationsFailed(
+        #[from]
+        #[backtrace]
+        DataExpectationsFailedError,
+    ),
+
     #[error(transparent)]
     Internal(
         #[from]
@@ -355,6 +379,18 @@ impl PollingIngestError {
     }
 }
 
+impl PollingIngestError {
+    /// Log files of fetch and engine containers that may explain the error
+    pub fn log_files(&self) -> &[PathBuf] {
+        match self {
+            PollingIngestError::ProcessError(e) => &e.log_files,
+            PollingIngestError::PipeError(e) => e.log_files(),
+            PollingIngestError::EngineError(e) => e.log_files(),
+            _ => &[],
+        }
+    }
+}
+
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
 
 #[derive(Debug, Error)]
@@ -378,6 +414,10 @@ impl PipeError {
             backtrace: Backtrace::capture(),
         }
     }
+
+    pub fn log_files(&self) -> &[PathBuf] {
+        &self.log_files
+    }
 }
 
 impl std::fmt::Display for PipeError {
@@ -153,6 +153,13 @@ pub enum PushIngestError {
         odf::metadata::AccessError,
     ),
 
+    #[error(transparent)]
+    ExpectationsFailed(
+        #[from]
+        #[backtrace]
+        DataExpectationsFailedError,
+    ),
+
     #[error(transparent)]
     Internal(
         #[from]
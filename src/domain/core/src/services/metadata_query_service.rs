@@ -51,6 +51,21 @@ pub trait MetadataQueryService: Send + Sync {
         &self,
         dataset: ResolvedDataset,
     ) -> Result<Option<DateTime<Utc>>, InternalError>;
+
+    /// Attempt reading watermark of the last `AddData` or `ExecuteTransform`
+    /// block. Unlike [`Self::try_get_current_watermark`], which only considers
+    /// `AddData` events, this also covers derivative datasets.
+    async fn try_get_last_data_watermark(
+        &self,
+        dataset: ResolvedDataset,
+    ) -> Result<Option<DateTime<Utc>>, InternalError>;
+
+    /// Returns system time of the last `AddData` or `ExecuteTransform` block
+    /// that added new data to a dataset
+    async fn try_get_last_data_update_time(
+        &self,
+        dataset: ResolvedDataset,
+    ) -> Result<Option<DateTime<Utc>>, InternalError>;
 }
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
@@ -12,17 +12,24 @@ pub use container_runtime::{NullPullImageListener, PullImageListener};
 
 pub mod compaction;
 pub mod ingest;
+pub mod reprocess;
 pub mod reset;
 pub mod transform;
 pub mod watermark;
 
 pub use compaction::*;
 pub use ingest::*;
+pub use reprocess::*;
 pub use reset::*;
 pub use transform::*;
 pub use watermark::*;
 
+pub mod dataset_change_feed_service;
 pub mod dataset_changes_service;
+pub mod dataset_diff_service;
+pub mod dataset_expectations_service;
+pub mod dataset_freshness_agent;
+pub mod dataset_freshness_service;
 pub mod dataset_registry;
 pub mod dependency_graph_service;
 mod did_generator;
@@ -44,7 +51,12 @@ pub mod server_url_config;
 pub mod sync_service;
 pub mod verification_service;
 
+pub use dataset_change_feed_service::*;
 pub use dataset_changes_service::*;
+pub use dataset_diff_service::*;
+pub use dataset_expectations_service::*;
+pub use dataset_freshness_agent::*;
+pub use dataset_freshness_service::*;
 pub use dataset_registry::*;
 pub use dependency_graph_service::*;
 pub use did_generator::*;
@@ -46,6 +46,31 @@ pub trait QueryService: Send + Sync {
         limit: u64,
     ) -> Result<DataFrame, QueryError>;
 
+    /// Returns up to the specified number of records that were added to the
+    /// dataset after the given offset, ordered by offset. Records keep all
+    /// system columns, including the operation type, so retractions and
+    /// corrections can be applied by the consumer. This is equivalent to SQL
+    /// query like:
+    ///
+    /// ```text
+    /// select
+    ///   *
+    /// from dataset
+    /// where offset > since_offset
+    /// order by offset
+    /// limit lim
+    /// ```
+    ///
+    /// When `as_of` block hash is specified only the data committed up to
+    /// this block will be considered.
+    async fn get_changes(
+        &self,
+        dataset_ref: &odf::DatasetRef,
+        since_offset: Option<u64>,
+        as_of: Option<odf::Multihash>,
+        limit: u64,
+    ) -> Result<DataFrame, QueryError>;
+
     /// Prepares an execution plan for the SQL statement and returns a
     /// [DataFrame] that can be used to get schema and data, and the state
     /// information that can be used for reproducibility.
@@ -117,6 +142,12 @@ pub struct DatasetQueryHints {
     /// file contains 150 records - only this file will be considered for the
     /// query and the rest of data will be completely ignored.
     pub last_records_to_consider: Option<u64>,
+    /// Offset of the last record that is already known to the caller. Setting
+    /// this value allows engine to skip all part files that contain only the
+    /// records with offsets less than or equal to this value. Note that
+    /// records from the partially overlapping file will still be returned,
+    /// so the query has to filter them out explicitly.
+    pub since_offset: Option<u64>,
 }
 
 #[derive(Debug, Clone)]
@@ -21,6 +21,12 @@ pub trait RemoteStatusService: Send + Sync {
         &self,
         dataset_handle: &odf::DatasetHandle,
     ) -> Result<DatasetPushStatuses, InternalError>;
+
+    /// Returns sync status of all pull remotes connected with a given dataset
+    async fn check_pull_remotes_status(
+        &self,
+        dataset_handle: &odf::DatasetHandle,
+    ) -> Result<DatasetPullStatuses, InternalError>;
 }
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
@@ -35,6 +41,16 @@ pub struct DatasetPushStatuses {
     pub statuses: Vec<PushStatus>,
 }
 
+#[derive(Debug)]
+pub struct PullStatus {
+    pub remote: odf::DatasetRefRemote,
+    pub check_result: Result<CompareChainsResult, StatusCheckError>,
+}
+
+pub struct DatasetPullStatuses {
+    pub statuses: Vec<PullStatus>,
+}
+
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
 
 #[derive(Debug, Error)]
@@ -0,0 +1,101 @@
+// Copyright Kamu Data, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use internal_error::{ErrorIntoInternal, InternalError};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::{
+    DataExpectationsFailedError,
+    EngineError,
+    EngineProvisioningError,
+    ReprocessPlan,
+    ResolvedDataset,
+};
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[async_trait::async_trait]
+pub trait ReprocessExecutor: Send + Sync {
+    async fn execute(
+        &self,
+        target: ResolvedDataset,
+        plan: ReprocessPlan,
+    ) -> Result<ReprocessResult, ReprocessExecutionError>;
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ReprocessResult {
+    /// Recomputed output matches the existing one
+    UpToDate,
+    Updated {
+        old_head: odf::Multihash,
+        new_head: odf::Multihash,
+        /// Number of retractions, corrections and appends committed
+        num_records: u64,
+    },
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[derive(Debug, Error)]
+pub enum ReprocessExecutionError {
+    #[error(transparent)]
+    EngineProvisioningError(
+        #[from]
+        #[backtrace]
+        EngineProvisioningError,
+    ),
+
+    #[error(transparent)]
+    EngineError(
+        #[from]
+        #[backtrace]
+        EngineError,
+    ),
+
+    #[error(transparent)]
+    CommitError(
+        #[from]
+        #[backtrace]
+        odf::dataset::CommitError,
+    ),
+
+    #[error(transparent)]
+    ExpectationsFailed(
+        #[from]
+        #[backtrace]
+        DataExpectationsFailedError,
+    ),
+
+    #[error(transparent)]
+    Internal(
+        #[from]
+        #[backtrace]
+        InternalError,
+    ),
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+impl From<odf::dataset::SetChainRefError> for ReprocessExecutionError {
+    fn from(v: odf::dataset::SetChainRefError) -> Self {
+        match v {
+            odf::dataset::SetChainRefError::CASFailed(e) => {
+                Self::CommitError(odf::dataset::CommitError::MetadataAppendError(e.into()))
+            }
+            odf::dataset::SetChainRefError::Internal(e) => Self::Internal(e),
+            _ => Self::Internal(v.int_err()),
+        }
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
@@ -0,0 +1,162 @@
+// Copyright Kamu Data, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use chrono::{DateTime, Utc};
+use internal_error::InternalError;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::engine::TransformRequestExt;
+use crate::{
+    InputSchemaNotDefinedError,
+    InvalidInputIntervalError,
+    ResolvedDataset,
+    ResolvedDatasetsMap,
+    TransformNotDefinedError,
+};
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[async_trait::async_trait]
+pub trait ReprocessPlanner: Send + Sync {
+    async fn plan_reprocess(
+        &self,
+        target: ResolvedDataset,
+        options: ReprocessOptions,
+    ) -> Result<ReprocessPlan, ReprocessPlanningError>;
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[derive(Debug, Clone, Default)]
+pub struct ReprocessOptions {
+    /// Point of history to recompute the output from, the entire history is
+    /// recomputed if not specified
+    pub start: Option<ReprocessStart>,
+    /// Transform replacing the current one, if not specified the current
+    /// transform is executed again
+    pub new_transform: Option<odf::metadata::Transform>,
+    /// Columns that identify a record. When specified, records that changed
+    /// are committed as correction pairs, otherwise as a retraction of the old
+    /// record followed by an append of the new one.
+    pub primary_key: Vec<String>,
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ReprocessStart {
+    /// `ExecuteTransform` block to start from
+    Block(odf::Multihash),
+    /// Output record offset, reprocessing starts from the `ExecuteTransform`
+    /// block that produced this record
+    Offset(u64),
+}
+
+impl std::fmt::Display for ReprocessStart {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Block(block_hash) => write!(f, "block {block_hash}"),
+            Self::Offset(offset) => write!(f, "offset {offset}"),
+        }
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+pub struct ReprocessPlan {
+    /// Transform to commit before the recomputed data, if it changes
+    pub new_set_transform: Option<odf::metadata::SetTransform>,
+    /// Request recomputing the output of all reprocessed blocks at once,
+    /// absent when the dataset did not execute any transform yet
+    pub request: Option<TransformRequestExt>,
+    pub datasets_map: ResolvedDatasetsMap,
+    /// Output data slices produced by the reprocessed blocks
+    pub replaced_data_slices: Vec<odf::Multihash>,
+    pub primary_key: Vec<String>,
+    /// System time to stamp the recomputed records with
+    pub system_time: DateTime<Utc>,
+    /// Output state as of the current head, the recomputed data will be
+    /// committed on top of it
+    pub prev_state: ReprocessPrevState,
+}
+
+impl std::fmt::Debug for ReprocessPlan {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ReprocessPlan")
+            .field("new_set_transform", &self.new_set_transform)
+            .field("request", &self.request)
+            .field("replaced_data_slices", &self.replaced_data_slices)
+            .field("primary_key", &self.primary_key)
+            .field("system_time", &self.system_time)
+            .field("prev_state", &self.prev_state)
+            .finish_non_exhaustive()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ReprocessPrevState {
+    pub head: odf::Multihash,
+    pub vocab: odf::metadata::DatasetVocabulary,
+    pub offset: Option<u64>,
+    pub checkpoint: Option<odf::Multihash>,
+    pub watermark: Option<DateTime<Utc>>,
+    /// Positions of inputs that were processed last
+    pub query_inputs: Vec<odf::metadata::ExecuteTransformInput>,
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[derive(Debug, Error)]
+pub enum ReprocessPlanningError {
+    #[error(transparent)]
+    TransformNotDefined(
+        #[from]
+        #[backtrace]
+        TransformNotDefinedError,
+    ),
+
+    #[error(transparent)]
+    StartNotFound(
+        #[from]
+        #[backtrace]
+        ReprocessStartNotFoundError,
+    ),
+
+    #[error(transparent)]
+    InputSchemaNotDefined(
+        #[from]
+        #[backtrace]
+        InputSchemaNotDefinedError,
+    ),
+
+    #[error(transparent)]
+    InvalidInputInterval(
+        #[from]
+        #[backtrace]
+        InvalidInputIntervalError,
+    ),
+
+    #[error(transparent)]
+    Internal(
+        #[from]
+        #[backtrace]
+        InternalError,
+    ),
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[derive(Error, Debug)]
+#[error("Reprocessing start {start} does not correspond to any transform block of the dataset")]
+pub struct ReprocessStartNotFoundError {
+    pub start: ReprocessStart,
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
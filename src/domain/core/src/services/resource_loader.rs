@@ -8,10 +8,17 @@
 // by the Apache License, Version 2.0.
 
 use std::backtrace::Backtrace;
+use std::collections::BTreeMap;
 
 use internal_error::{BoxedError, InternalError};
 use thiserror::Error;
 
+use crate::TemplateError;
+
+/// Values of `${{ vars.<name> }}` placeholders used to expand a snapshot
+/// template
+pub type TemplateVars = BTreeMap<String, String>;
+
 #[async_trait::async_trait]
 pub trait ResourceLoader: Send + Sync {
     async fn load_dataset_snapshot_from_path(
@@ -28,6 +35,15 @@ pub trait ResourceLoader: Send + Sync {
         &self,
         sref: &str,
     ) -> Result<odf::DatasetSnapshot, ResourceError>;
+
+    /// Loads a templated snapshot manifest and expands it once per every set
+    /// of variables. A single expansion may produce several snapshots when
+    /// the template contains multiple YAML documents.
+    async fn load_dataset_snapshots_from_template(
+        &self,
+        sref: &str,
+        vars: &[TemplateVars],
+    ) -> Result<Vec<odf::DatasetSnapshot>, ResourceError>;
 }
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
@@ -54,6 +70,12 @@ pub enum ResourceError {
         source: BoxedError,
         backtrace: Backtrace,
     },
+    #[error("Could not expand template with variables #{index}")]
+    TemplateError {
+        index: usize,
+        #[source]
+        source: TemplateError,
+    },
     #[error(transparent)]
     InternalError(
         #[from]
@@ -71,6 +93,10 @@ impl ResourceError {
         Self::NotFound { path, source }
     }
 
+    pub fn template(index: usize, source: TemplateError) -> Self {
+        Self::TemplateError { index, source }
+    }
+
     pub fn serde(e: impl std::error::Error + Send + Sync + 'static) -> Self {
         Self::SerdeError {
             source: e.into(),
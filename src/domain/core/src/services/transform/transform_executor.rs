@@ -15,6 +15,7 @@ use thiserror::Error;
 use super::TransformPlan;
 use crate::engine::EngineError;
 use crate::{
+    DataExpectationsFailedError,
     DataNotReproducible,
     EngineProvisioningError,
     ResolvedDataset,
@@ -69,6 +70,12 @@ pub enum TransformExecuteError {
         odf::dataset::CommitError,
     ),
     #[error(transparent)]
+    ExpectationsFailed(
+        #[from]
+        #[backtrace]
+        DataExpectationsFailedError,
+    ),
+    #[error(transparent)]
     Internal(
         #[from]
         #[backtrace]
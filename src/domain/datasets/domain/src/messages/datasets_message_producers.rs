@@ -11,4 +11,7 @@
 
 pub const MESSAGE_PRODUCER_KAMU_DATASET_SERVICE: &str = "dev.kamu.domain.datasets.DatasetService";
 
+pub const MESSAGE_PRODUCER_KAMU_DATASET_REFERENCE_SERVICE: &str =
+    "dev.kamu.domain.datasets.DatasetReferenceService";
+
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
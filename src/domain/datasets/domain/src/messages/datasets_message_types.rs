@@ -7,12 +7,14 @@
 // the Business Source License, use of this software will be governed
 // by the Apache License, Version 2.0.
 
+use chrono::{DateTime, Utc};
 use messaging_outbox::Message;
 use serde::{Deserialize, Serialize};
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
 
 const DATASET_LIFECYCLE_OUTBOX_VERSION: u32 = 1;
+const DATASET_REFERENCE_OUTBOX_VERSION: u32 = 1;
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
 
@@ -67,3 +69,45 @@ pub struct DatasetLifecycleMessageDeleted {
 }
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+/// Sent whenever the `HEAD` reference of a dataset is moved, regardless of
+/// whether it was done by a flow, a push ingest, a commit of an event or a
+/// sync
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DatasetReferenceMessage {
+    Updated(DatasetReferenceMessageUpdated),
+}
+
+impl DatasetReferenceMessage {
+    pub fn updated(
+        event_time: DateTime<Utc>,
+        dataset_id: odf::DatasetID,
+        maybe_prev_block_hash: Option<odf::Multihash>,
+        new_block_hash: odf::Multihash,
+    ) -> Self {
+        Self::Updated(DatasetReferenceMessageUpdated {
+            event_time,
+            dataset_id,
+            maybe_prev_block_hash,
+            new_block_hash,
+        })
+    }
+}
+
+impl Message for DatasetReferenceMessage {
+    fn version() -> u32 {
+        DATASET_REFERENCE_OUTBOX_VERSION
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DatasetReferenceMessageUpdated {
+    pub event_time: DateTime<Utc>,
+    pub dataset_id: odf::DatasetID,
+    pub maybe_prev_block_hash: Option<odf::Multihash>,
+    pub new_block_hash: odf::Multihash,
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
@@ -65,6 +65,12 @@ pub trait DatasetEntryRepository: Send + Sync {
         new_name: &odf::DatasetName,
     ) -> Result<(), UpdateDatasetEntryNameError>;
 
+    async fn update_dataset_entry_owner(
+        &self,
+        dataset_id: &odf::DatasetID,
+        new_owner_id: &odf::AccountID,
+    ) -> Result<(), UpdateDatasetEntryOwnerError>;
+
     async fn delete_dataset_entry(
         &self,
         dataset_id: &odf::DatasetID,
@@ -216,6 +222,20 @@ impl DatasetEntryNameCollisionError {
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
 
+#[derive(Error, Debug)]
+pub enum UpdateDatasetEntryOwnerError {
+    #[error(transparent)]
+    NotFound(#[from] DatasetEntryNotFoundError),
+
+    #[error(transparent)]
+    NameCollision(#[from] DatasetEntryNameCollisionError),
+
+    #[error(transparent)]
+    Internal(#[from] InternalError),
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
 #[derive(Error, Debug)]
 pub enum DeleteEntryDatasetError {
     #[error(transparent)]
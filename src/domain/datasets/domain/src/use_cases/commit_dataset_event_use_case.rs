@@ -16,6 +16,15 @@ pub trait CommitDatasetEventUseCase: Send + Sync {
         dataset_handle: &odf::DatasetHandle,
         event: odf::MetadataEvent,
     ) -> Result<odf::dataset::CommitResult, odf::dataset::CommitError>;
+
+    /// Commits several events as a whole: all of them are validated and
+    /// appended before the head is moved, so a rejected event leaves the
+    /// dataset unchanged
+    async fn execute_multi(
+        &self,
+        dataset_handle: &odf::DatasetHandle,
+        events: Vec<odf::MetadataEvent>,
+    ) -> Result<odf::dataset::CommitResult, odf::dataset::CommitError>;
 }
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
@@ -14,6 +14,8 @@ mod create_dataset_use_case;
 mod delete_dataset_use_case;
 mod edit_dataset_use_case;
 mod rename_dataset_use_case;
+mod transfer_dataset_ownership_use_case;
+mod update_dataset_from_snapshot_use_case;
 mod view_dataset_use_case;
 
 pub use append_dataset_metadata_batch_use_case::*;
@@ -23,4 +25,6 @@ pub use create_dataset_use_case::*;
 pub use delete_dataset_use_case::*;
 pub use edit_dataset_use_case::*;
 pub use rename_dataset_use_case::*;
+pub use transfer_dataset_ownership_use_case::*;
+pub use update_dataset_from_snapshot_use_case::*;
 pub use view_dataset_use_case::*;
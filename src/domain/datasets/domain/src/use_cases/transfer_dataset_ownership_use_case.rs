@@ -0,0 +1,60 @@
+// Copyright Kamu Data, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use internal_error::InternalError;
+use thiserror::Error;
+
+use crate::NameCollisionError;
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[async_trait::async_trait]
+pub trait TransferDatasetOwnershipUseCase: Send + Sync {
+    async fn execute(
+        &self,
+        dataset_ref: &odf::DatasetRef,
+        new_owner_name: &odf::AccountName,
+    ) -> Result<odf::DatasetHandle, TransferDatasetOwnershipError>;
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[derive(Error, Debug)]
+pub enum TransferDatasetOwnershipError {
+    #[error(transparent)]
+    NotFound(#[from] odf::DatasetNotFoundError),
+
+    #[error(transparent)]
+    NewOwnerNotFound(#[from] NewOwnerNotFoundError),
+
+    #[error(transparent)]
+    NameCollision(#[from] NameCollisionError),
+
+    #[error(transparent)]
+    Access(
+        #[from]
+        #[backtrace]
+        odf::AccessError,
+    ),
+
+    #[error(transparent)]
+    Internal(
+        #[from]
+        #[backtrace]
+        InternalError,
+    ),
+}
+
+#[derive(Error, Debug)]
+#[error("Account '{account_name}' not found")]
+pub struct NewOwnerNotFoundError {
+    pub account_name: odf::AccountName,
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
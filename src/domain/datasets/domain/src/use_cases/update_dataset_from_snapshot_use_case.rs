@@ -0,0 +1,103 @@
+// Copyright Kamu Data, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use internal_error::{ErrorIntoInternal, InternalError};
+use thiserror::Error;
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+/// Brings metadata of an existing dataset in line with a snapshot by
+/// committing only the events that differ from the current state of the
+/// dataset
+#[async_trait::async_trait]
+pub trait UpdateDatasetFromSnapshotUseCase: Send + Sync {
+    async fn execute(
+        &self,
+        dataset_handle: &odf::DatasetHandle,
+        snapshot: odf::DatasetSnapshot,
+        options: UpdateDatasetFromSnapshotUseCaseOptions,
+    ) -> Result<UpdateDatasetFromSnapshotResult, UpdateDatasetFromSnapshotError>;
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[derive(Debug, Default, Clone, Copy)]
+pub struct UpdateDatasetFromSnapshotUseCaseOptions {
+    /// Only compute the changes without committing them
+    pub dry_run: bool,
+}
+
+#[derive(Debug)]
+pub struct UpdateDatasetFromSnapshotResult {
+    pub changes: Vec<DatasetSnapshotChange>,
+    /// New head of the dataset, `None` when nothing was committed
+    pub new_head: Option<odf::Multihash>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DatasetSnapshotChange {
+    /// Currently effective event of the same kind, if any
+    pub old_event: Option<odf::MetadataEvent>,
+    pub new_event: odf::MetadataEvent,
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[derive(Error, Debug)]
+pub enum UpdateDatasetFromSnapshotError {
+    #[error(transparent)]
+    InvalidSnapshot(#[from] odf::dataset::InvalidSnapshotError),
+
+    #[error(transparent)]
+    MissingInputs(#[from] odf::dataset::MissingInputsError),
+
+    #[error(transparent)]
+    Access(
+        #[from]
+        #[backtrace]
+        odf::AccessError,
+    ),
+
+    #[error(transparent)]
+    Internal(
+        #[from]
+        #[backtrace]
+        InternalError,
+    ),
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+impl From<odf::dataset::ValidateDatasetSnapshotError> for UpdateDatasetFromSnapshotError {
+    fn from(v: odf::dataset::ValidateDatasetSnapshotError) -> Self {
+        match v {
+            odf::dataset::ValidateDatasetSnapshotError::InvalidSnapshot(e) => {
+                Self::InvalidSnapshot(e)
+            }
+            odf::dataset::ValidateDatasetSnapshotError::MissingInputs(e) => Self::MissingInputs(e),
+            odf::dataset::ValidateDatasetSnapshotError::Internal(e) => Self::Internal(e),
+        }
+    }
+}
+
+impl From<odf::dataset::CommitError> for UpdateDatasetFromSnapshotError {
+    fn from(v: odf::dataset::CommitError) -> Self {
+        match v {
+            odf::dataset::CommitError::MetadataAppendError(
+                odf::dataset::AppendError::InvalidBlock(e),
+            ) => Self::InvalidSnapshot(odf::dataset::InvalidSnapshotError::new(e.to_string())),
+            odf::dataset::CommitError::Access(e) => Self::Access(e),
+            odf::dataset::CommitError::Internal(e) => Self::Internal(e),
+            odf::dataset::CommitError::ObjectNotFound(_)
+            | odf::dataset::CommitError::MetadataAppendError(_) => Self::Internal(v.int_err()),
+        }
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
@@ -0,0 +1,93 @@
+// Copyright Kamu Data, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use dill::{component, interface};
+use internal_error::ResultIntoInternal;
+use kamu_accounts::{
+    Account,
+    AccountDeletionBlockedError,
+    AccountDeletionBlockedReason,
+    AccountDeletionError,
+    AccountDeletionGuard,
+};
+use kamu_core::DependencyGraphService;
+use kamu_datasets::{DatasetEntryService, DatasetEntryServiceExt};
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+/// Datasets of a deleted account are deleted with it, which is refused while
+/// datasets of other accounts depend on them
+pub struct DatasetAccountDeletionGuard {
+    dataset_entry_service: Arc<dyn DatasetEntryService>,
+    dependency_graph_service: Arc<dyn DependencyGraphService>,
+}
+
+#[component(pub)]
+#[interface(dyn AccountDeletionGuard)]
+impl DatasetAccountDeletionGuard {
+    pub fn new(
+        dataset_entry_service: Arc<dyn DatasetEntryService>,
+        dependency_graph_service: Arc<dyn DependencyGraphService>,
+    ) -> Self {
+        Self {
+            dataset_entry_service,
+            dependency_graph_service,
+        }
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[async_trait::async_trait]
+impl AccountDeletionGuard for DatasetAccountDeletionGuard {
+    async fn check_account_deletion(&self, account: &Account) -> Result<(), AccountDeletionError> {
+        use tokio_stream::StreamExt;
+
+        let owned_dataset_ids: HashSet<_> = self
+            .dataset_entry_service
+            .get_owned_dataset_ids(&account.id)
+            .await
+            .int_err()?
+            .into_iter()
+            .collect();
+
+        let mut foreign_downstream_dataset_ids = HashSet::new();
+        for dataset_id in &owned_dataset_ids {
+            let downstream_dataset_ids: Vec<_> = self
+                .dependency_graph_service
+                .get_downstream_dependencies(dataset_id)
+                .await
+                .int_err()?
+                .collect()
+                .await;
+
+            foreign_downstream_dataset_ids.extend(
+                downstream_dataset_ids
+                    .into_iter()
+                    .filter(|id| !owned_dataset_ids.contains(id)),
+            );
+        }
+
+        if !foreign_downstream_dataset_ids.is_empty() {
+            return Err(AccountDeletionError::Blocked(AccountDeletionBlockedError {
+                account_name: account.account_name.clone(),
+                reason: AccountDeletionBlockedReason::ForeignDownstreamDatasets {
+                    downstream_datasets_count: foreign_downstream_dataset_ids.len(),
+                },
+            }));
+        }
+
+        Ok(())
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
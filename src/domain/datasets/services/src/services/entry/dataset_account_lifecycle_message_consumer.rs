@@ -0,0 +1,177 @@
+// Copyright Kamu Data, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use dill::{component, interface, meta, Catalog};
+use internal_error::{InternalError, ResultIntoInternal};
+use kamu_accounts::{
+    AccountLifecycleMessage,
+    AccountLifecycleMessageDeleted,
+    MESSAGE_PRODUCER_KAMU_ACCOUNTS_SERVICE,
+};
+use kamu_core::{DependencyGraphService, DependencyOrder};
+use kamu_datasets::{
+    DatasetEntryService,
+    DatasetLifecycleMessage,
+    MESSAGE_PRODUCER_KAMU_DATASET_SERVICE,
+};
+use messaging_outbox::{
+    MessageConsumer,
+    MessageConsumerMeta,
+    MessageConsumerT,
+    MessageDeliveryMechanism,
+    Outbox,
+    OutboxExt,
+};
+
+use crate::{
+    DatasetEntryWriter,
+    DependencyGraphWriter,
+    MESSAGE_CONSUMER_KAMU_DATASET_ENTRY_SERVICE,
+};
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+/// Deletes all datasets owned by an account once the account itself is
+/// deleted. Authorization is not re-checked: the account deletion has been
+/// already authorized by whoever initiated it, and checked by
+/// [`crate::DatasetAccountDeletionGuard`]
+pub struct DatasetAccountLifecycleMessageConsumer {
+    dataset_entry_service: Arc<dyn DatasetEntryService>,
+    dataset_entry_writer: Arc<dyn DatasetEntryWriter>,
+    dataset_storage_unit_writer: Arc<dyn odf::DatasetStorageUnitWriter>,
+    dependency_graph_service: Arc<dyn DependencyGraphService>,
+    dependency_graph_writer: Arc<dyn DependencyGraphWriter>,
+    outbox: Arc<dyn Outbox>,
+}
+
+#[component(pub)]
+#[interface(dyn MessageConsumer)]
+#[interface(dyn MessageConsumerT<AccountLifecycleMessage>)]
+#[meta(MessageConsumerMeta {
+    consumer_name: MESSAGE_CONSUMER_KAMU_DATASET_ENTRY_SERVICE,
+    feeding_producers: &[
+        MESSAGE_PRODUCER_KAMU_ACCOUNTS_SERVICE,
+    ],
+    delivery: MessageDeliveryMechanism::Immediate,
+})]
+impl DatasetAccountLifecycleMessageConsumer {
+    pub fn new(
+        dataset_entry_service: Arc<dyn DatasetEntryService>,
+        dataset_entry_writer: Arc<dyn DatasetEntryWriter>,
+        dataset_storage_unit_writer: Arc<dyn odf::DatasetStorageUnitWriter>,
+        dependency_graph_service: Arc<dyn DependencyGraphService>,
+        dependency_graph_writer: Arc<dyn DependencyGraphWriter>,
+        outbox: Arc<dyn Outbox>,
+    ) -> Self {
+        Self {
+            dataset_entry_service,
+            dataset_entry_writer,
+            dataset_storage_unit_writer,
+            dependency_graph_service,
+            dependency_graph_writer,
+            outbox,
+        }
+    }
+
+    async fn handle_account_lifecycle_deleted_message(
+        &self,
+        message: &AccountLifecycleMessageDeleted,
+    ) -> Result<(), InternalError> {
+        use futures::TryStreamExt;
+
+        let owned_entries: HashMap<_, _> = self
+            .dataset_entry_service
+            .entries_owned_by(&message.account_id)
+            .map_ok(|entry| (entry.id.clone(), entry))
+            .try_collect()
+            .await?;
+        if owned_entries.is_empty() {
+            return Ok(());
+        }
+
+        // Downstream datasets go first
+        let ordered_dataset_ids = self
+            .dependency_graph_service
+            .in_dependency_order(
+                owned_entries.keys().cloned().collect(),
+                DependencyOrder::DepthFirst,
+            )
+            .await
+            .int_err()?;
+
+        for dataset_id in ordered_dataset_ids {
+            let entry = &owned_entries[&dataset_id];
+            let dataset_handle = odf::DatasetHandle::new(
+                dataset_id.clone(),
+                odf::DatasetAlias::new(Some(message.account_name.clone()), entry.name.clone()),
+            );
+
+            tracing::info!(%dataset_handle, "Deleting dataset of the deleted account");
+
+            self.dataset_entry_writer
+                .remove_entry(&dataset_handle)
+                .await?;
+
+            self.dataset_storage_unit_writer
+                .delete_dataset(&dataset_id)
+                .await
+                .int_err()?;
+
+            self.dependency_graph_writer
+                .remove_dataset_node(&dataset_id)
+                .await?;
+
+            self.outbox
+                .post_message(
+                    MESSAGE_PRODUCER_KAMU_DATASET_SERVICE,
+                    DatasetLifecycleMessage::deleted(dataset_id),
+                )
+                .await?;
+        }
+
+        Ok(())
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+impl MessageConsumer for DatasetAccountLifecycleMessageConsumer {}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[async_trait::async_trait]
+impl MessageConsumerT<AccountLifecycleMessage> for DatasetAccountLifecycleMessageConsumer {
+    #[tracing::instrument(
+        level = "debug",
+        skip_all,
+        name = "DatasetAccountLifecycleMessageConsumer[AccountLifecycleMessage]"
+    )]
+    async fn consume_message(
+        &self,
+        _: &Catalog,
+        message: &AccountLifecycleMessage,
+    ) -> Result<(), InternalError> {
+        tracing::debug!(received_message = ?message, "Received account lifecycle message");
+
+        match message {
+            AccountLifecycleMessage::Created(_) | AccountLifecycleMessage::EmailChanged(_) => {
+                Ok(())
+            }
+
+            AccountLifecycleMessage::Deleted(message) => {
+                self.handle_account_lifecycle_deleted_message(message).await
+            }
+        }
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
@@ -30,7 +30,12 @@ use kamu_datasets::*;
 use thiserror::Error;
 use time_source::SystemTimeSource;
 
-use super::{CreateDatasetEntryError, DatasetEntryWriter, RenameDatasetEntryError};
+use super::{
+    CreateDatasetEntryError,
+    DatasetEntryWriter,
+    RenameDatasetEntryError,
+    TransferDatasetEntryError,
+};
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
 
@@ -618,6 +623,26 @@ impl DatasetEntryWriter for DatasetEntryServiceImpl {
             })
     }
 
+    async fn transfer_entry(
+        &self,
+        dataset_handle: &odf::DatasetHandle,
+        new_owner_account_id: &odf::AccountID,
+    ) -> Result<(), TransferDatasetEntryError> {
+        self.dataset_entry_repo
+            .update_dataset_entry_owner(&dataset_handle.id, new_owner_account_id)
+            .await
+            .map_err(|e| match e {
+                UpdateDatasetEntryOwnerError::Internal(e) => TransferDatasetEntryError::Internal(e),
+                UpdateDatasetEntryOwnerError::NotFound(e) => {
+                    // should not happen normally, since we resolved the handle earlier
+                    TransferDatasetEntryError::Internal(e.int_err())
+                }
+                UpdateDatasetEntryOwnerError::NameCollision(e) => {
+                    TransferDatasetEntryError::NameCollision(e)
+                }
+            })
+    }
+
     async fn remove_entry(&self, dataset_handle: &odf::DatasetHandle) -> Result<(), InternalError> {
         match self
             .dataset_entry_repo
@@ -29,6 +29,12 @@ pub trait DatasetEntryWriter: Send + Sync {
         new_dataset_name: &odf::DatasetName,
     ) -> Result<(), RenameDatasetEntryError>;
 
+    async fn transfer_entry(
+        &self,
+        dataset_handle: &odf::DatasetHandle,
+        new_owner_account_id: &odf::AccountID,
+    ) -> Result<(), TransferDatasetEntryError>;
+
     async fn remove_entry(&self, dataset_handle: &odf::DatasetHandle) -> Result<(), InternalError>;
 }
 
@@ -66,3 +72,18 @@ pub enum RenameDatasetEntryError {
 }
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[derive(Debug, Error)]
+pub enum TransferDatasetEntryError {
+    #[error(transparent)]
+    NameCollision(#[from] DatasetEntryNameCollisionError),
+
+    #[error(transparent)]
+    Internal(
+        #[from]
+        #[backtrace]
+        InternalError,
+    ),
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
@@ -7,10 +7,14 @@
 // the Business Source License, use of this software will be governed
 // by the Apache License, Version 2.0.
 
+mod dataset_account_deletion_guard;
+mod dataset_account_lifecycle_message_consumer;
 mod dataset_entry_indexer;
 mod dataset_entry_service_impl;
 mod dataset_entry_writer;
 
+pub use dataset_account_deletion_guard::*;
+pub use dataset_account_lifecycle_message_consumer::*;
 pub use dataset_entry_indexer::*;
 pub use dataset_entry_service_impl::*;
 pub use dataset_entry_writer::*;
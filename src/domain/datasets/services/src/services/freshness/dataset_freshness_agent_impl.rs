@@ -0,0 +1,188 @@
+// Copyright Kamu Data, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use database_common_macros::transactional_method2;
+use dill::*;
+use futures::TryStreamExt;
+use internal_error::InternalError;
+use kamu_core::{
+    DatasetFreshness,
+    DatasetFreshnessAgent,
+    DatasetFreshnessConfig,
+    DatasetFreshnessService,
+    DatasetRegistry,
+    FreshnessStatus,
+};
+use time_source::SystemTimeSource;
+
+use crate::DatasetFreshnessMetrics;
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+pub struct DatasetFreshnessAgentImpl {
+    catalog: Catalog,
+    time_source: Arc<dyn SystemTimeSource>,
+    metrics: Arc<DatasetFreshnessMetrics>,
+    config: Arc<DatasetFreshnessConfig>,
+    state: Mutex<State>,
+}
+
+#[derive(Default)]
+struct State {
+    /// Datasets reported in the last check, along with the alias used in the
+    /// metric labels and whether they were stale
+    reported: HashMap<odf::DatasetID, (String, bool)>,
+}
+
+#[component(pub)]
+#[interface(dyn DatasetFreshnessAgent)]
+#[scope(Singleton)]
+impl DatasetFreshnessAgentImpl {
+    pub fn new(
+        catalog: Catalog,
+        time_source: Arc<dyn SystemTimeSource>,
+        metrics: Arc<DatasetFreshnessMetrics>,
+        config: Arc<DatasetFreshnessConfig>,
+    ) -> Self {
+        Self {
+            catalog,
+            time_source,
+            metrics,
+            config,
+            state: Mutex::new(State::default()),
+        }
+    }
+
+    /// Evaluates freshness of all datasets that declare an SLA. Datasets that
+    /// fail to be evaluated are skipped to not affect reporting of others.
+    #[transactional_method2(dataset_registry: Arc<dyn DatasetRegistry>, freshness_service: Arc<dyn DatasetFreshnessService>)]
+    async fn evaluate_datasets(
+        &self,
+    ) -> Result<Vec<(odf::DatasetHandle, DatasetFreshness)>, InternalError> {
+        let dataset_handles: Vec<_> = dataset_registry.all_dataset_handles().try_collect().await?;
+
+        let mut evaluated = Vec::new();
+        for dataset_handle in dataset_handles {
+            let target = dataset_registry
+                .get_dataset_by_handle(&dataset_handle)
+                .await;
+
+            let freshness = match freshness_service.get_sla(&target).await {
+                Ok(None) => continue,
+                Ok(Some(_)) => freshness_service.get_freshness(&target).await,
+                Err(e) => Err(e),
+            };
+
+            match freshness {
+                Ok(freshness) => evaluated.push((dataset_handle, freshness)),
+                Err(e) => {
+                    tracing::error!(
+                        dataset = %dataset_handle,
+                        error = ?e,
+                        error_msg = %e,
+                        "Failed to evaluate dataset freshness",
+                    );
+                }
+            }
+        }
+
+        Ok(evaluated)
+    }
+
+    fn report(&self, evaluated: Vec<(odf::DatasetHandle, DatasetFreshness)>) {
+        let now = self.time_source.now();
+        let mut state = self.state.lock().unwrap();
+        let mut reported = HashMap::with_capacity(evaluated.len());
+
+        for (dataset_handle, freshness) in evaluated {
+            let dataset_id = dataset_handle.id.to_string();
+            let dataset_alias = dataset_handle.alias.to_string();
+            let labels = [dataset_id.as_str(), dataset_alias.as_str()];
+
+            let is_stale = freshness.status == FreshnessStatus::Stale;
+            let was_stale = state
+                .reported
+                .get(&dataset_handle.id)
+                .is_some_and(|(_, was_stale)| *was_stale);
+
+            if is_stale && !was_stale {
+                tracing::warn!(
+                    dataset = %dataset_handle,
+                    last_updated_at = ?freshness.last_updated_at,
+                    watermark = ?freshness.watermark,
+                    "Dataset became stale",
+                );
+            } else if !is_stale && was_stale {
+                tracing::info!(dataset = %dataset_handle, "Dataset is fresh again");
+            }
+
+            self.metrics
+                .stale
+                .with_label_values(&labels)
+                .set(i64::from(is_stale));
+
+            match freshness.last_updated_at {
+                Some(last_updated_at) => self
+                    .metrics
+                    .update_lag_seconds
+                    .with_label_values(&labels)
+                    .set((now - last_updated_at).num_seconds()),
+                None => {
+                    let _ = self.metrics.update_lag_seconds.remove_label_values(&labels);
+                }
+            }
+
+            reported.insert(dataset_handle.id, (dataset_alias, is_stale));
+        }
+
+        // Drop metrics of datasets that were deleted, renamed, or lost their SLA
+        for (dataset_id, (dataset_alias, _)) in &state.reported {
+            if reported
+                .get(dataset_id)
+                .is_some_and(|(alias, _)| alias == dataset_alias)
+            {
+                continue;
+            }
+
+            let dataset_id = dataset_id.to_string();
+            let labels = [dataset_id.as_str(), dataset_alias.as_str()];
+            let _ = self.metrics.stale.remove_label_values(&labels);
+            let _ = self.metrics.update_lag_seconds.remove_label_values(&labels);
+        }
+
+        state.reported = reported;
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[async_trait::async_trait]
+impl DatasetFreshnessAgent for DatasetFreshnessAgentImpl {
+    async fn run(&self) -> Result<(), InternalError> {
+        loop {
+            self.run_freshness_checks().await?;
+
+            self.time_source.sleep(self.config.check_interval).await;
+        }
+    }
+
+    #[tracing::instrument(level = "debug", skip_all)]
+    async fn run_freshness_checks(&self) -> Result<(), InternalError> {
+        let evaluated = self.evaluate_datasets().await?;
+
+        self.report(evaluated);
+
+        Ok(())
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
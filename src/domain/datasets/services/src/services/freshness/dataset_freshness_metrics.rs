@@ -0,0 +1,58 @@
+// Copyright Kamu Data, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use dill::*;
+use observability::metrics::MetricsProvider;
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[derive(Debug)]
+pub struct DatasetFreshnessMetrics {
+    pub stale: prometheus::IntGaugeVec,
+    pub update_lag_seconds: prometheus::IntGaugeVec,
+}
+
+#[component(pub)]
+#[interface(dyn MetricsProvider)]
+#[scope(Singleton)]
+impl DatasetFreshnessMetrics {
+    pub fn new() -> Self {
+        use prometheus::*;
+
+        Self {
+            stale: IntGaugeVec::new(
+                Opts::new(
+                    "dataset_freshness_stale",
+                    "Whether a dataset violates its freshness SLA (1) or satisfies it (0)",
+                ),
+                &["dataset_id", "dataset_alias"],
+            )
+            .unwrap(),
+            update_lag_seconds: IntGaugeVec::new(
+                Opts::new(
+                    "dataset_freshness_update_lag_seconds",
+                    "Number of seconds since new data was last added to a dataset that declares a \
+                     freshness SLA",
+                ),
+                &["dataset_id", "dataset_alias"],
+            )
+            .unwrap(),
+        }
+    }
+}
+
+impl MetricsProvider for DatasetFreshnessMetrics {
+    fn register(&self, reg: &prometheus::Registry) -> prometheus::Result<()> {
+        reg.register(Box::new(self.stale.clone()))?;
+        reg.register(Box::new(self.update_lag_seconds.clone()))?;
+        Ok(())
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
@@ -9,8 +9,10 @@
 
 mod entry;
 mod env;
+mod freshness;
 mod graph;
 
 pub use entry::*;
 pub use env::*;
+pub use freshness::*;
 pub use graph::*;
@@ -7,7 +7,12 @@
 // the Business Source License, use of this software will be governed
 // by the Apache License, Version 2.0.
 
-use kamu_datasets::{DatasetLifecycleMessage, MESSAGE_PRODUCER_KAMU_DATASET_SERVICE};
+use kamu_datasets::{
+    DatasetLifecycleMessage,
+    DatasetReferenceMessage,
+    MESSAGE_PRODUCER_KAMU_DATASET_REFERENCE_SERVICE,
+    MESSAGE_PRODUCER_KAMU_DATASET_SERVICE,
+};
 use messaging_outbox::MockOutbox;
 use mockall::predicate::{always, eq, function};
 
@@ -50,3 +55,22 @@ pub fn expect_outbox_dataset_deleted(mock_outbox: &mut MockOutbox, times: usize)
 }
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+pub fn expect_outbox_dataset_reference_updated(mock_outbox: &mut MockOutbox, times: usize) {
+    mock_outbox
+        .expect_post_message_as_json()
+        .with(
+            eq(MESSAGE_PRODUCER_KAMU_DATASET_REFERENCE_SERVICE),
+            function(|message_as_json: &serde_json::Value| {
+                matches!(
+                    serde_json::from_value::<DatasetReferenceMessage>(message_as_json.clone()),
+                    Ok(DatasetReferenceMessage::Updated(_))
+                )
+            }),
+            always(),
+        )
+        .times(times)
+        .returning(|_, _, _| Ok(()));
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
@@ -10,12 +10,19 @@
 use std::sync::Arc;
 
 use dill::{component, interface, Catalog};
-use internal_error::ErrorIntoInternal;
+use internal_error::{ErrorIntoInternal, InternalError, ResultIntoInternal};
 use kamu_core::auth::{DatasetAction, DatasetActionAuthorizer};
 use kamu_core::DatasetRegistry;
-use kamu_datasets::{CommitDatasetEventUseCase, ViewMultiResponse};
-use odf::dataset::{AppendError, InvalidEventError};
+use kamu_datasets::{
+    CommitDatasetEventUseCase,
+    DatasetReferenceMessage,
+    ViewMultiResponse,
+    MESSAGE_PRODUCER_KAMU_DATASET_REFERENCE_SERVICE,
+};
+use messaging_outbox::{Outbox, OutboxExt};
+use odf::dataset::{AppendError, CommitError, InvalidEventError};
 use odf::metadata::EnumWithVariants;
+use time_source::SystemTimeSource;
 
 use crate::utils::access_dataset_helper::{AccessDatasetHelper, DatasetAccessError};
 use crate::DependencyGraphWriter;
@@ -29,6 +36,8 @@ pub struct CommitDatasetEventUseCaseImpl {
     dataset_registry: Arc<dyn DatasetRegistry>,
     dataset_action_authorizer: Arc<dyn DatasetActionAuthorizer>,
     dependency_graph_writer: Arc<dyn DependencyGraphWriter>,
+    outbox: Arc<dyn Outbox>,
+    time_source: Arc<dyn SystemTimeSource>,
 }
 
 impl CommitDatasetEventUseCaseImpl {
@@ -37,20 +46,57 @@ impl CommitDatasetEventUseCaseImpl {
         dataset_registry: Arc<dyn DatasetRegistry>,
         dataset_action_authorizer: Arc<dyn DatasetActionAuthorizer>,
         dependency_graph_writer: Arc<dyn DependencyGraphWriter>,
+        outbox: Arc<dyn Outbox>,
+        time_source: Arc<dyn SystemTimeSource>,
     ) -> Self {
         Self {
             catalog,
             dataset_registry,
             dataset_action_authorizer,
             dependency_graph_writer,
+            outbox,
+            time_source,
         }
     }
 
+    async fn notify_head_updated(
+        &self,
+        dataset_handle: &odf::DatasetHandle,
+        commit_result: &odf::dataset::CommitResult,
+    ) -> Result<(), InternalError> {
+        self.outbox
+            .post_message(
+                MESSAGE_PRODUCER_KAMU_DATASET_REFERENCE_SERVICE,
+                DatasetReferenceMessage::updated(
+                    self.time_source.now(),
+                    dataset_handle.id.clone(),
+                    commit_result.old_head.clone(),
+                    commit_result.new_head.clone(),
+                ),
+            )
+            .await
+    }
+
+    async fn check_write_access(
+        access_dataset_helper: &AccessDatasetHelper<'_>,
+        dataset_handle: &odf::DatasetHandle,
+    ) -> Result<(), CommitError> {
+        access_dataset_helper
+            .access_dataset(&dataset_handle.as_local_ref(), DatasetAction::Write)
+            .await
+            .map_err(|e| match e {
+                DatasetAccessError::Access(e) => CommitError::Access(e),
+                unexpected_error => CommitError::Internal(unexpected_error.int_err()),
+            })?;
+
+        Ok(())
+    }
+
     async fn validate_event(
         &self,
-        access_dataset_helper: AccessDatasetHelper<'_>,
+        access_dataset_helper: &AccessDatasetHelper<'_>,
         event: odf::MetadataEvent,
-    ) -> Result<odf::MetadataEvent, odf::dataset::CommitError> {
+    ) -> Result<odf::MetadataEvent, CommitError> {
         if let Some(set_transform) = event.as_variant::<odf::metadata::SetTransform>() {
             let inputs_dataset_refs = set_transform
                 .inputs
@@ -95,18 +141,9 @@ impl CommitDatasetEventUseCase for CommitDatasetEventUseCaseImpl {
         let access_dataset_helper =
             AccessDatasetHelper::new(&self.dataset_registry, &self.dataset_action_authorizer);
 
-        access_dataset_helper
-            .access_dataset(&dataset_handle.as_local_ref(), DatasetAction::Write)
-            .await
-            .map_err(|e| {
-                use odf::dataset::CommitError;
-                match e {
-                    DatasetAccessError::Access(e) => CommitError::Access(e),
-                    unexpected_error => CommitError::Internal(unexpected_error.int_err()),
-                }
-            })?;
+        Self::check_write_access(&access_dataset_helper, dataset_handle).await?;
 
-        let event = self.validate_event(access_dataset_helper, event).await?;
+        let event = self.validate_event(&access_dataset_helper, event).await?;
 
         let mut new_upstream_ids: Vec<odf::DatasetID> = vec![];
         let mut dependencies_modified = false;
@@ -145,6 +182,118 @@ impl CommitDatasetEventUseCase for CommitDatasetEventUseCaseImpl {
                 .await?;
         }
 
+        self.notify_head_updated(dataset_handle, &commit_result)
+            .await?;
+
+        Ok(commit_result)
+    }
+
+    #[tracing::instrument(
+        level = "info",
+        name = "CommitDatasetEventUseCase::execute_multi",
+        skip_all,
+        fields(dataset_handle, num_events = events.len())
+    )]
+    async fn execute_multi(
+        &self,
+        dataset_handle: &odf::DatasetHandle,
+        events: Vec<odf::MetadataEvent>,
+    ) -> Result<odf::dataset::CommitResult, odf::dataset::CommitError> {
+        let access_dataset_helper =
+            AccessDatasetHelper::new(&self.dataset_registry, &self.dataset_action_authorizer);
+
+        Self::check_write_access(&access_dataset_helper, dataset_handle).await?;
+
+        let mut validated_events = Vec::with_capacity(events.len());
+        for event in events {
+            validated_events.push(self.validate_event(&access_dataset_helper, event).await?);
+        }
+
+        let resolved_dataset = self
+            .dataset_registry
+            .get_dataset_by_handle(dataset_handle)
+            .await;
+
+        let old_head = resolved_dataset
+            .as_metadata_chain()
+            .resolve_ref(&odf::BlockRef::Head)
+            .await
+            .int_err()?;
+
+        // Blocks are appended without moving the head, so the chain validates
+        // each of them against the previous one, while the dataset stays
+        // unchanged until all of them are in place
+        let mut new_head = old_head.clone();
+        let mut new_upstream_ids = None;
+        for event in validated_events {
+            if let odf::MetadataEvent::SetTransform(transform) = &event {
+                new_upstream_ids = Some(
+                    transform
+                        .inputs
+                        .iter()
+                        .filter_map(|input| input.dataset_ref.id().cloned())
+                        .collect(),
+                );
+            }
+
+            new_head = resolved_dataset
+                .commit_event(
+                    event,
+                    odf::dataset::CommitOpts {
+                        prev_block_hash: Some(Some(&new_head)),
+                        update_block_ref: false,
+                        ..odf::dataset::CommitOpts::default()
+                    },
+                )
+                .await?
+                .new_head;
+        }
+
+        if new_head != old_head {
+            resolved_dataset
+                .as_metadata_chain()
+                .set_ref(
+                    &odf::BlockRef::Head,
+                    &new_head,
+                    odf::dataset::SetRefOpts {
+                        validate_block_present: true,
+                        check_ref_is: Some(Some(&old_head)),
+                    },
+                )
+                .await
+                .map_err(|err| match err {
+                    odf::dataset::SetChainRefError::CASFailed(e) => {
+                        CommitError::MetadataAppendError(e.into())
+                    }
+                    odf::dataset::SetChainRefError::Access(e) => CommitError::Access(e),
+                    odf::dataset::SetChainRefError::Internal(e) => CommitError::Internal(e),
+                    odf::dataset::SetChainRefError::BlockNotFound(_) => {
+                        CommitError::Internal(err.int_err())
+                    }
+                })?;
+        }
+
+        // Note: modify dependencies only after the head was moved
+        if let Some(new_upstream_ids) = new_upstream_ids {
+            self.dependency_graph_writer
+                .update_dataset_node_dependencies(
+                    &self.catalog,
+                    &dataset_handle.id,
+                    new_upstream_ids,
+                )
+                .await?;
+        }
+
+        let commit_result = odf::dataset::CommitResult {
+            old_head: Some(old_head),
+            new_head,
+        };
+
+        if commit_result.old_head.as_ref() != Some(&commit_result.new_head) {
+            self.notify_head_updated(dataset_handle, &commit_result)
+                .await?;
+        }
+
         Ok(commit_result)
     }
 }
@@ -14,6 +14,8 @@ mod create_dataset_use_case_impl;
 mod delete_dataset_use_case_impl;
 mod edit_dataset_use_case_impl;
 mod rename_dataset_use_case_impl;
+mod transfer_dataset_ownership_use_case_impl;
+mod update_dataset_from_snapshot_use_case_impl;
 mod view_dataset_use_case_impl;
 
 pub use append_dataset_metadata_batch_use_case_impl::*;
@@ -23,4 +25,6 @@ pub use create_dataset_use_case_impl::*;
 pub use delete_dataset_use_case_impl::*;
 pub use edit_dataset_use_case_impl::*;
 pub use rename_dataset_use_case_impl::*;
+pub use transfer_dataset_ownership_use_case_impl::*;
+pub use update_dataset_from_snapshot_use_case_impl::*;
 pub use view_dataset_use_case_impl::*;
@@ -0,0 +1,133 @@
+// Copyright Kamu Data, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::sync::Arc;
+
+use dill::{component, interface};
+use kamu_accounts::AccountService;
+use kamu_core::auth::{DatasetAction, DatasetActionAuthorizer, DatasetActionUnauthorizedError};
+use kamu_core::DatasetRegistry;
+use kamu_datasets::{
+    NameCollisionError,
+    NewOwnerNotFoundError,
+    TransferDatasetOwnershipError,
+    TransferDatasetOwnershipUseCase,
+};
+
+use crate::{DatasetEntryWriter, TransferDatasetEntryError};
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+pub struct TransferDatasetOwnershipUseCaseImpl {
+    dataset_registry: Arc<dyn DatasetRegistry>,
+    dataset_entry_writer: Arc<dyn DatasetEntryWriter>,
+    dataset_action_authorizer: Arc<dyn DatasetActionAuthorizer>,
+    account_service: Arc<dyn AccountService>,
+}
+
+#[component(pub)]
+#[interface(dyn TransferDatasetOwnershipUseCase)]
+impl TransferDatasetOwnershipUseCaseImpl {
+    pub fn new(
+        dataset_registry: Arc<dyn DatasetRegistry>,
+        dataset_entry_writer: Arc<dyn DatasetEntryWriter>,
+        dataset_action_authorizer: Arc<dyn DatasetActionAuthorizer>,
+        account_service: Arc<dyn AccountService>,
+    ) -> Self {
+        Self {
+            dataset_registry,
+            dataset_entry_writer,
+            dataset_action_authorizer,
+            account_service,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl TransferDatasetOwnershipUseCase for TransferDatasetOwnershipUseCaseImpl {
+    #[tracing::instrument(
+        level = "info",
+        name = "TransferDatasetOwnershipUseCase::execute",
+        skip_all,
+        fields(dataset_ref, new_owner_name)
+    )]
+    async fn execute(
+        &self,
+        dataset_ref: &odf::DatasetRef,
+        new_owner_name: &odf::AccountName,
+    ) -> Result<odf::DatasetHandle, TransferDatasetOwnershipError> {
+        // Locate dataset
+        let dataset_handle = match self
+            .dataset_registry
+            .resolve_dataset_handle_by_ref(dataset_ref)
+            .await
+        {
+            Ok(h) => Ok(h),
+            Err(odf::DatasetRefUnresolvedError::NotFound(e)) => {
+                Err(TransferDatasetOwnershipError::NotFound(e))
+            }
+            Err(odf::DatasetRefUnresolvedError::Internal(e)) => {
+                Err(TransferDatasetOwnershipError::Internal(e))
+            }
+        }?;
+
+        // Ensure write permissions
+        self.dataset_action_authorizer
+            .check_action_allowed(&dataset_handle.id, DatasetAction::Write)
+            .await
+            .map_err(|e| match e {
+                DatasetActionUnauthorizedError::Access(e) => {
+                    TransferDatasetOwnershipError::Access(e)
+                }
+                DatasetActionUnauthorizedError::Internal(e) => {
+                    TransferDatasetOwnershipError::Internal(e)
+                }
+            })?;
+
+        // Locate new owner
+        let new_owner_id = self
+            .account_service
+            .find_account_id_by_name(new_owner_name)
+            .await?
+            .ok_or_else(|| NewOwnerNotFoundError {
+                account_name: new_owner_name.clone(),
+            })?;
+
+        let new_alias = odf::DatasetAlias::new(
+            Some(new_owner_name.clone()),
+            dataset_handle.alias.dataset_name.clone(),
+        );
+
+        // Re-assign entry in the entry repository
+        self.dataset_entry_writer
+            .transfer_entry(&dataset_handle, &new_owner_id)
+            .await
+            .map_err(|e| match e {
+                TransferDatasetEntryError::Internal(e) => {
+                    TransferDatasetOwnershipError::Internal(e)
+                }
+                TransferDatasetEntryError::NameCollision(_) => {
+                    TransferDatasetOwnershipError::NameCollision(NameCollisionError {
+                        alias: new_alias.clone(),
+                    })
+                }
+            })?;
+
+        // Update stored alias file
+        let target = self
+            .dataset_registry
+            .get_dataset_by_handle(&dataset_handle)
+            .await;
+        odf::dataset::write_dataset_alias(target.as_ref(), &new_alias).await?;
+
+        Ok(odf::DatasetHandle::new(dataset_handle.id, new_alias))
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
@@ -0,0 +1,259 @@
+// Copyright Kamu Data, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use dill::{component, interface};
+use internal_error::{ErrorIntoInternal, ResultIntoInternal};
+use kamu_core::auth::{DatasetAction, DatasetActionAuthorizer};
+use kamu_core::DatasetRegistry;
+use kamu_datasets::{
+    CommitDatasetEventUseCase,
+    DatasetSnapshotChange,
+    UpdateDatasetFromSnapshotError,
+    UpdateDatasetFromSnapshotResult,
+    UpdateDatasetFromSnapshotUseCase,
+    UpdateDatasetFromSnapshotUseCaseOptions,
+};
+
+use crate::utils::access_dataset_helper::{AccessDatasetHelper, DatasetAccessError};
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[component(pub)]
+#[interface(dyn UpdateDatasetFromSnapshotUseCase)]
+pub struct UpdateDatasetFromSnapshotUseCaseImpl {
+    dataset_registry: Arc<dyn DatasetRegistry>,
+    dataset_action_authorizer: Arc<dyn DatasetActionAuthorizer>,
+    commit_dataset_event_use_case: Arc<dyn CommitDatasetEventUseCase>,
+}
+
+impl UpdateDatasetFromSnapshotUseCaseImpl {
+    pub fn new(
+        dataset_registry: Arc<dyn DatasetRegistry>,
+        dataset_action_authorizer: Arc<dyn DatasetActionAuthorizer>,
+        commit_dataset_event_use_case: Arc<dyn CommitDatasetEventUseCase>,
+    ) -> Self {
+        Self {
+            dataset_registry,
+            dataset_action_authorizer,
+            commit_dataset_event_use_case,
+        }
+    }
+
+    /// Events that replace each other share the same key, e.g. a new
+    /// `SetPollingSource` replaces the previous one, while push sources are
+    /// tracked per source name
+    fn event_key(event: &odf::MetadataEvent) -> Option<String> {
+        match event {
+            odf::MetadataEvent::SetPollingSource(_)
+            | odf::MetadataEvent::DisablePollingSource(_) => Some("SetPollingSource".to_string()),
+            odf::MetadataEvent::AddPushSource(e) => {
+                Some(format!("AddPushSource/{}", e.source_name))
+            }
+            odf::MetadataEvent::DisablePushSource(e) => {
+                Some(format!("AddPushSource/{}", e.source_name))
+            }
+            odf::MetadataEvent::SetTransform(_) => Some("SetTransform".to_string()),
+            odf::MetadataEvent::SetDataSchema(_) => Some("SetDataSchema".to_string()),
+            odf::MetadataEvent::SetAttachments(_) => Some("SetAttachments".to_string()),
+            odf::MetadataEvent::SetInfo(_) => Some("SetInfo".to_string()),
+            odf::MetadataEvent::SetLicense(_) => Some("SetLicense".to_string()),
+            odf::MetadataEvent::SetVocab(_) => Some("SetVocab".to_string()),
+            odf::MetadataEvent::Seed(_)
+            | odf::MetadataEvent::AddData(_)
+            | odf::MetadataEvent::ExecuteTransform(_) => None,
+        }
+    }
+
+    /// A snapshot can only update the dataset it describes: the name and kind
+    /// have to match the target dataset
+    async fn check_snapshot_matches_dataset(
+        &self,
+        dataset_handle: &odf::DatasetHandle,
+        snapshot: &odf::DatasetSnapshot,
+    ) -> Result<(), UpdateDatasetFromSnapshotError> {
+        use odf::dataset::MetadataChainExt;
+
+        let name_matches = snapshot.name.dataset_name == dataset_handle.alias.dataset_name
+            && (snapshot.name.account_name.is_none()
+                || snapshot.name.account_name == dataset_handle.alias.account_name);
+        if !name_matches {
+            return Err(odf::dataset::InvalidSnapshotError::new(format!(
+                "Snapshot of dataset {} can't be applied to dataset {}",
+                snapshot.name, dataset_handle.alias
+            ))
+            .into());
+        }
+
+        let resolved_dataset = self
+            .dataset_registry
+            .get_dataset_by_handle(dataset_handle)
+            .await;
+
+        let seed = resolved_dataset
+            .as_metadata_chain()
+            .accept_one(odf::dataset::SearchSeedVisitor::new())
+            .await
+            .int_err()?
+            .into_event()
+            .ok_or_else(|| format!("Dataset {} has no seed", dataset_handle.alias).int_err())?;
+
+        if seed.dataset_kind != snapshot.kind {
+            return Err(odf::dataset::InvalidSnapshotError::new(format!(
+                "Snapshot of a {:?} dataset can't be applied to {} which is a {:?} dataset",
+                snapshot.kind, dataset_handle.alias, seed.dataset_kind
+            ))
+            .into());
+        }
+
+        Ok(())
+    }
+
+    /// Finds currently effective events for the given keys, walking the chain
+    /// from the head until all of them are found
+    async fn get_current_events(
+        &self,
+        dataset_handle: &odf::DatasetHandle,
+        mut keys: Vec<String>,
+    ) -> Result<HashMap<String, odf::MetadataEvent>, UpdateDatasetFromSnapshotError> {
+        use futures::TryStreamExt;
+        use odf::dataset::MetadataChainExt;
+
+        let resolved_dataset = self
+            .dataset_registry
+            .get_dataset_by_handle(dataset_handle)
+            .await;
+
+        let mut current_events = HashMap::new();
+
+        let mut blocks = resolved_dataset.as_metadata_chain().iter_blocks();
+        while !keys.is_empty()
+            && let Some((_, block)) = blocks.try_next().await.int_err()?
+        {
+            let Some(key) = Self::event_key(&block.event) else {
+                continue;
+            };
+
+            if let Some(i) = keys.iter().position(|k| *k == key) {
+                keys.swap_remove(i);
+
+                match block.event {
+                    odf::MetadataEvent::DisablePollingSource(_)
+                    | odf::MetadataEvent::DisablePushSource(_) => {}
+                    event => {
+                        current_events.insert(key, event);
+                    }
+                }
+            }
+        }
+
+        Ok(current_events)
+    }
+}
+
+#[async_trait::async_trait]
+impl UpdateDatasetFromSnapshotUseCase for UpdateDatasetFromSnapshotUseCaseImpl {
+    #[tracing::instrument(
+        level = "info",
+        name = "UpdateDatasetFromSnapshotUseCase::execute",
+        skip_all,
+        fields(%dataset_handle, ?options)
+    )]
+    async fn execute(
+        &self,
+        dataset_handle: &odf::DatasetHandle,
+        mut snapshot: odf::DatasetSnapshot,
+        options: UpdateDatasetFromSnapshotUseCaseOptions,
+    ) -> Result<UpdateDatasetFromSnapshotResult, UpdateDatasetFromSnapshotError> {
+        AccessDatasetHelper::new(&self.dataset_registry, &self.dataset_action_authorizer)
+            .access_dataset(&dataset_handle.as_local_ref(), DatasetAction::Write)
+            .await
+            .map_err(|e| match e {
+                DatasetAccessError::Access(e) => UpdateDatasetFromSnapshotError::Access(e),
+                unexpected_error => unexpected_error.int_err().into(),
+            })?;
+
+        self.check_snapshot_matches_dataset(dataset_handle, &snapshot)
+            .await?;
+
+        // Validate / resolve metadata events from the snapshot
+        odf::dataset::normalize_and_validate_dataset_snapshot(
+            self.dataset_registry.as_ref(),
+            &mut snapshot,
+        )
+        .await?;
+
+        // Only the last event of each kind in the snapshot matters
+        let mut new_events: Vec<(String, odf::MetadataEvent)> = Vec::new();
+        for event in snapshot.metadata {
+            let key = Self::event_key(&event).ok_or_else(|| {
+                odf::dataset::InvalidSnapshotError::new(format!(
+                    "Event is not allowed to appear in a DatasetSnapshot: {event:?}"
+                ))
+            })?;
+            new_events.retain(|(k, _)| *k != key);
+            new_events.push((key, event));
+        }
+
+        let mut current_events = self
+            .get_current_events(
+                dataset_handle,
+                new_events.iter().map(|(k, _)| k.clone()).collect(),
+            )
+            .await?;
+
+        let changes: Vec<_> = new_events
+            .into_iter()
+            .filter_map(|(key, new_event)| {
+                let old_event = current_events.remove(&key);
+                if old_event.as_ref() == Some(&new_event) {
+                    None
+                } else {
+                    Some(DatasetSnapshotChange {
+                        old_event,
+                        new_event,
+                    })
+                }
+            })
+            .collect();
+
+        if options.dry_run {
+            return Ok(UpdateDatasetFromSnapshotResult {
+                changes,
+                new_head: None,
+            });
+        }
+
+        if changes.is_empty() {
+            return Ok(UpdateDatasetFromSnapshotResult {
+                changes,
+                new_head: None,
+            });
+        }
+
+        // All changes are committed at once, so the dataset is not left half
+        // updated when one of the events gets rejected
+        let commit_result = self
+            .commit_dataset_event_use_case
+            .execute_multi(
+                dataset_handle,
+                changes.iter().map(|c| c.new_event.clone()).collect(),
+            )
+            .await?;
+
+        Ok(UpdateDatasetFromSnapshotResult {
+            changes,
+            new_head: Some(commit_result.new_head),
+        })
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
@@ -7,6 +7,8 @@
 // the Business Source License, use of this software will be governed
 // by the Apache License, Version 2.0.
 
+mod test_dataset_account_lifecycle_message_consumer;
 mod test_dataset_entry_service;
 mod test_dataset_env_var_service_impl;
+mod test_dataset_freshness_agent_impl;
 mod test_dependency_graph_service_impl;
@@ -0,0 +1,199 @@
+// Copyright Kamu Data, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::assert_matches::assert_matches;
+use std::sync::Arc;
+
+use dill::Catalog;
+use email_utils::Email;
+use kamu::testing::{BaseUseCaseHarness, BaseUseCaseHarnessOptions};
+use kamu_accounts::{
+    Account,
+    AccountDeletionBlockedError,
+    AccountDeletionBlockedReason,
+    AccountDeletionError,
+    AccountDeletionGuard,
+    AccountLifecycleMessage,
+};
+use kamu_datasets::{CreateDatasetResult, DatasetEntry};
+use kamu_datasets_inmem::InMemoryDatasetDependencyRepository;
+use kamu_datasets_services::testing::{expect_outbox_dataset_deleted, FakeDatasetEntryService};
+use kamu_datasets_services::{
+    DatasetAccountDeletionGuard,
+    DatasetAccountLifecycleMessageConsumer,
+    DatasetEntryWriter,
+    DependencyGraphIndexer,
+    DependencyGraphServiceImpl,
+    MockDatasetEntryWriter,
+};
+use messaging_outbox::{consume_deserialized_message, ConsumerFilter, Message, MockOutbox};
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[test_log::test(tokio::test)]
+async fn test_owned_datasets_deleted_with_account() {
+    let (_, wasya_id) = odf::AccountID::new_generated_ed25519();
+    let (_, petya_id) = odf::AccountID::new_generated_ed25519();
+
+    let alias_foo = odf::DatasetAlias::new(None, odf::DatasetName::new_unchecked("foo"));
+    let alias_bar = odf::DatasetAlias::new(None, odf::DatasetName::new_unchecked("bar"));
+    let alias_baz = odf::DatasetAlias::new(None, odf::DatasetName::new_unchecked("baz"));
+
+    let mut mock_entry_writer = MockDatasetEntryWriter::new();
+    mock_entry_writer
+        .expect_remove_entry()
+        .times(2)
+        .returning(|_| Ok(()));
+
+    let mut mock_outbox = MockOutbox::new();
+    expect_outbox_dataset_deleted(&mut mock_outbox, 2);
+
+    let harness = DatasetAccountLifecycleHarness::new(mock_entry_writer, mock_outbox);
+
+    let foo = harness.create_root_dataset(&alias_foo).await;
+    let bar = harness
+        .create_derived_dataset(&alias_bar, vec![alias_foo.as_local_ref()])
+        .await;
+    let baz = harness.create_root_dataset(&alias_baz).await;
+    harness.reindex_dependency_graph().await;
+
+    harness.add_entry(&foo, &wasya_id);
+    harness.add_entry(&bar, &wasya_id);
+    harness.add_entry(&baz, &petya_id);
+
+    assert_matches!(harness.check_account_deletion(&wasya_id).await, Ok(()));
+
+    harness.mimic_account_deleted(&wasya_id).await.unwrap();
+
+    assert_matches!(
+        harness.check_dataset_exists(&alias_foo).await,
+        Err(odf::DatasetRefUnresolvedError::NotFound(_))
+    );
+    assert_matches!(
+        harness.check_dataset_exists(&alias_bar).await,
+        Err(odf::DatasetRefUnresolvedError::NotFound(_))
+    );
+    assert_matches!(harness.check_dataset_exists(&alias_baz).await, Ok(_));
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[test_log::test(tokio::test)]
+async fn test_foreign_downstream_dataset_blocks_deletion() {
+    let (_, wasya_id) = odf::AccountID::new_generated_ed25519();
+    let (_, petya_id) = odf::AccountID::new_generated_ed25519();
+
+    let alias_foo = odf::DatasetAlias::new(None, odf::DatasetName::new_unchecked("foo"));
+    let alias_bar = odf::DatasetAlias::new(None, odf::DatasetName::new_unchecked("bar"));
+
+    let harness =
+        DatasetAccountLifecycleHarness::new(MockDatasetEntryWriter::new(), MockOutbox::new());
+
+    let foo = harness.create_root_dataset(&alias_foo).await;
+    let bar = harness
+        .create_derived_dataset(&alias_bar, vec![alias_foo.as_local_ref()])
+        .await;
+    harness.reindex_dependency_graph().await;
+
+    harness.add_entry(&foo, &wasya_id);
+    harness.add_entry(&bar, &petya_id);
+
+    assert_matches!(
+        harness.check_account_deletion(&wasya_id).await,
+        Err(AccountDeletionError::Blocked(AccountDeletionBlockedError {
+            reason: AccountDeletionBlockedReason::ForeignDownstreamDatasets {
+                downstream_datasets_count: 1
+            },
+            ..
+        }))
+    );
+    // Deleting the downstream dataset itself is fine
+    assert_matches!(harness.check_account_deletion(&petya_id).await, Ok(()));
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[oop::extend(BaseUseCaseHarness, base_use_case_harness)]
+struct DatasetAccountLifecycleHarness {
+    base_use_case_harness: BaseUseCaseHarness,
+    catalog: Catalog,
+    dataset_entry_service: Arc<FakeDatasetEntryService>,
+    indexer: Arc<DependencyGraphIndexer>,
+}
+
+impl DatasetAccountLifecycleHarness {
+    fn new(mock_dataset_entry_writer: MockDatasetEntryWriter, mock_outbox: MockOutbox) -> Self {
+        let base_use_case_harness =
+            BaseUseCaseHarness::new(BaseUseCaseHarnessOptions::new().with_outbox(mock_outbox));
+
+        let catalog = dill::CatalogBuilder::new_chained(base_use_case_harness.catalog())
+            .add::<DatasetAccountLifecycleMessageConsumer>()
+            .add::<DatasetAccountDeletionGuard>()
+            .add::<FakeDatasetEntryService>()
+            .add::<DependencyGraphServiceImpl>()
+            .add::<InMemoryDatasetDependencyRepository>()
+            .add::<DependencyGraphIndexer>()
+            .add_value(mock_dataset_entry_writer)
+            .bind::<dyn DatasetEntryWriter, MockDatasetEntryWriter>()
+            .build();
+
+        Self {
+            dataset_entry_service: catalog.get_one().unwrap(),
+            indexer: catalog.get_one().unwrap(),
+            base_use_case_harness,
+            catalog,
+        }
+    }
+
+    fn add_entry(&self, created: &CreateDatasetResult, owner_id: &odf::AccountID) {
+        self.dataset_entry_service.add_entry(DatasetEntry::new(
+            created.dataset_handle.id.clone(),
+            owner_id.clone(),
+            created.dataset_handle.alias.dataset_name.clone(),
+            chrono::Utc::now(),
+        ));
+    }
+
+    async fn check_account_deletion(
+        &self,
+        account_id: &odf::AccountID,
+    ) -> Result<(), AccountDeletionError> {
+        let deletion_guard = self.catalog.get_one::<dyn AccountDeletionGuard>().unwrap();
+        deletion_guard
+            .check_account_deletion(&Account::test(account_id.clone(), "wasya"))
+            .await
+    }
+
+    async fn mimic_account_deleted(
+        &self,
+        account_id: &odf::AccountID,
+    ) -> Result<(), internal_error::InternalError> {
+        let message = AccountLifecycleMessage::deleted(
+            account_id.clone(),
+            odf::AccountName::new_unchecked("wasya"),
+            Email::parse("wasya@example.com").unwrap(),
+        );
+        let content_json = serde_json::to_string(&message).unwrap();
+
+        consume_deserialized_message::<AccountLifecycleMessage>(
+            &self.catalog,
+            ConsumerFilter::AllConsumers,
+            &content_json,
+            AccountLifecycleMessage::version(),
+        )
+        .await
+    }
+
+    async fn reindex_dependency_graph(&self) {
+        use init_on_startup::InitOnStartup;
+        self.indexer.run_initialization().await.unwrap();
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
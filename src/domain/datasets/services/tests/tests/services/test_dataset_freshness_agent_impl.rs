@@ -0,0 +1,156 @@
+// Copyright Kamu Data, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::sync::Arc;
+
+use chrono::Duration;
+use database_common::NoOpDatabasePlugin;
+use kamu::testing::BaseRepoHarness;
+use kamu::{DatasetFreshnessServiceImpl, MetadataQueryServiceImpl};
+use kamu_core::{
+    DatasetFreshnessAgent,
+    DatasetFreshnessConfig,
+    DatasetFreshnessService,
+    DatasetFreshnessSla,
+    ResolvedDataset,
+    TenancyConfig,
+};
+use kamu_datasets::CreateDatasetResult;
+use kamu_datasets_services::{DatasetFreshnessAgentImpl, DatasetFreshnessMetrics};
+use odf::metadata::testing::MetadataFactory;
+use prometheus::core::Collector;
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[test_log::test(tokio::test)]
+async fn test_stale_datasets_are_reported() {
+    let harness = DatasetFreshnessAgentHarness::new();
+
+    let foo = harness.create_dataset("foo").await;
+    let bar = harness.create_dataset("bar").await;
+
+    harness
+        .set_sla(
+            &foo,
+            Some(DatasetFreshnessSla {
+                max_update_interval: Duration::hours(1),
+                max_watermark_lag: None,
+            }),
+        )
+        .await;
+
+    // "foo" never received data, "bar" does not declare an SLA
+    harness.agent.run_freshness_checks().await.unwrap();
+
+    assert_eq!(harness.reported_stale(&foo), Some(true));
+    assert_eq!(harness.reported_stale(&bar), None);
+    assert_eq!(harness.num_reported_datasets(), 1);
+
+    foo.dataset
+        .commit_event(
+            odf::MetadataEvent::AddData(
+                MetadataFactory::add_data()
+                    .some_new_data_with_offset(0, 9)
+                    .build(),
+            ),
+            odf::dataset::CommitOpts {
+                check_object_refs: false,
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+
+    harness.agent.run_freshness_checks().await.unwrap();
+
+    assert_eq!(harness.reported_stale(&foo), Some(false));
+    assert_eq!(harness.num_reported_datasets(), 1);
+
+    // Removing the SLA stops reporting the dataset
+    harness.set_sla(&foo, None).await;
+
+    harness.agent.run_freshness_checks().await.unwrap();
+
+    assert_eq!(harness.reported_stale(&foo), None);
+    assert_eq!(harness.num_reported_datasets(), 0);
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+// Harness
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[oop::extend(BaseRepoHarness, base_repo_harness)]
+struct DatasetFreshnessAgentHarness {
+    base_repo_harness: BaseRepoHarness,
+    freshness_service: Arc<dyn DatasetFreshnessService>,
+    metrics: Arc<DatasetFreshnessMetrics>,
+    agent: Arc<dyn DatasetFreshnessAgent>,
+}
+
+impl DatasetFreshnessAgentHarness {
+    fn new() -> Self {
+        let base_repo_harness = BaseRepoHarness::builder()
+            .tenancy_config(TenancyConfig::SingleTenant)
+            .build();
+
+        let mut b = dill::CatalogBuilder::new_chained(base_repo_harness.catalog());
+        b.add::<MetadataQueryServiceImpl>()
+            .add::<DatasetFreshnessServiceImpl>()
+            .add::<DatasetFreshnessMetrics>()
+            .add::<DatasetFreshnessAgentImpl>()
+            .add_value(DatasetFreshnessConfig::default());
+
+        NoOpDatabasePlugin::init_database_components(&mut b);
+
+        let catalog = b.build();
+
+        Self {
+            base_repo_harness,
+            freshness_service: catalog.get_one().unwrap(),
+            metrics: catalog.get_one().unwrap(),
+            agent: catalog.get_one().unwrap(),
+        }
+    }
+
+    async fn create_dataset(&self, name: &str) -> CreateDatasetResult {
+        self.base_repo_harness
+            .create_root_dataset(&odf::DatasetAlias::new(
+                None,
+                odf::DatasetName::new_unchecked(name),
+            ))
+            .await
+    }
+
+    async fn set_sla(&self, dataset: &CreateDatasetResult, sla: Option<DatasetFreshnessSla>) {
+        self.freshness_service
+            .set_sla(&ResolvedDataset::from_created(dataset), sla)
+            .await
+            .unwrap();
+    }
+
+    fn reported_stale(&self, dataset: &CreateDatasetResult) -> Option<bool> {
+        let dataset_id = dataset.dataset_handle.id.to_string();
+
+        self.metrics.stale.collect()[0]
+            .get_metric()
+            .iter()
+            .find(|m| {
+                m.get_label()
+                    .iter()
+                    .any(|l| l.get_name() == "dataset_id" && l.get_value() == dataset_id)
+            })
+            .map(|m| m.get_gauge().get_value() > 0.0)
+    }
+
+    fn num_reported_datasets(&self) -> usize {
+        self.metrics.stale.collect()[0].get_metric().len()
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
@@ -14,4 +14,6 @@ mod test_create_dataset_use_case;
 mod test_delete_dataset_use_case;
 mod test_edit_dataset_use_case;
 mod test_rename_dataset_use_case;
+mod test_transfer_dataset_ownership_use_case;
+mod test_update_dataset_from_snapshot_use_case;
 mod test_view_dataset_use_case;
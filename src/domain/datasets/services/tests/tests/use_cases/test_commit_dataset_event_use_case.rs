@@ -13,11 +13,13 @@ use std::sync::Arc;
 use kamu::testing::{BaseUseCaseHarness, BaseUseCaseHarnessOptions, MockDatasetActionAuthorizer};
 use kamu_core::MockDidGenerator;
 use kamu_datasets::CommitDatasetEventUseCase;
+use kamu_datasets_services::testing::expect_outbox_dataset_reference_updated;
 use kamu_datasets_services::{
     CommitDatasetEventUseCaseImpl,
     DependencyGraphWriter,
     MockDependencyGraphWriter,
 };
+use messaging_outbox::MockOutbox;
 use odf::metadata::testing::MetadataFactory;
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
@@ -30,10 +32,14 @@ async fn test_commit_dataset_event() {
     let mock_authorizer =
         MockDatasetActionAuthorizer::new().expect_check_write_dataset(&dataset_id_foo, 1, true);
 
+    let mut mock_outbox = MockOutbox::new();
+    expect_outbox_dataset_reference_updated(&mut mock_outbox, 1);
+
     let harness = CommitDatasetEventUseCaseHarness::new(
         mock_authorizer,
         MockDidGenerator::predefined_dataset_ids(vec![dataset_id_foo]),
         MockDependencyGraphWriter::new(),
+        mock_outbox,
     );
     let foo = harness.create_root_dataset(&alias_foo).await;
 
@@ -61,6 +67,7 @@ async fn test_commit_event_unauthorized() {
         mock_authorizer,
         MockDidGenerator::predefined_dataset_ids(vec![dataset_id_foo]),
         MockDependencyGraphWriter::new(),
+        MockOutbox::new(),
     );
     let foo = harness.create_root_dataset(&alias_foo).await;
 
@@ -93,10 +100,14 @@ async fn test_commit_event_with_new_dependencies() {
         .once()
         .returning(|_, _, _| Ok(()));
 
+    let mut mock_outbox = MockOutbox::new();
+    expect_outbox_dataset_reference_updated(&mut mock_outbox, 1);
+
     let harness = CommitDatasetEventUseCaseHarness::new(
         mock_authorizer,
         MockDidGenerator::predefined_dataset_ids(vec![dataset_id_foo, dataset_id_bar]),
         mock_dependency_writer,
+        mock_outbox,
     );
 
     let foo = harness.create_root_dataset(&alias_foo).await;
@@ -134,11 +145,13 @@ impl CommitDatasetEventUseCaseHarness {
         mock_dataset_action_authorizer: MockDatasetActionAuthorizer,
         mock_did_generator: MockDidGenerator,
         mock_dependency_graph_writer: MockDependencyGraphWriter,
+        mock_outbox: MockOutbox,
     ) -> Self {
         let base_use_case_harness = BaseUseCaseHarness::new(
             BaseUseCaseHarnessOptions::new()
                 .with_maybe_authorizer(Some(mock_dataset_action_authorizer))
-                .with_maybe_mock_did_generator(Some(mock_did_generator)),
+                .with_maybe_mock_did_generator(Some(mock_did_generator))
+                .with_outbox(mock_outbox),
         );
 
         let catalog = dill::CatalogBuilder::new_chained(base_use_case_harness.catalog())
@@ -0,0 +1,213 @@
+// Copyright Kamu Data, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::assert_matches::assert_matches;
+use std::sync::Arc;
+
+use kamu::testing::{BaseUseCaseHarness, BaseUseCaseHarnessOptions, MockDatasetActionAuthorizer};
+use kamu_accounts::{Account, AccountRepository};
+use kamu_accounts_inmem::InMemoryAccountRepository;
+use kamu_accounts_services::AccountServiceImpl;
+use kamu_core::MockDidGenerator;
+use kamu_datasets::{
+    DatasetEntryNameCollisionError,
+    TransferDatasetOwnershipError,
+    TransferDatasetOwnershipUseCase,
+};
+use kamu_datasets_services::{
+    DatasetEntryWriter,
+    MockDatasetEntryWriter,
+    TransferDatasetEntryError,
+    TransferDatasetOwnershipUseCaseImpl,
+};
+use mockall::predicate::function;
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[tokio::test]
+async fn test_transfer_dataset_ownership_success() {
+    let alias_foo = odf::DatasetAlias::new(None, odf::DatasetName::new_unchecked("foo"));
+    let (_, foo_id) = odf::DatasetID::new_generated_ed25519();
+    let (_, bob_id) = odf::AccountID::new_generated_ed25519();
+
+    let foo_id_clone = foo_id.clone();
+    let bob_id_clone = bob_id.clone();
+    let mut mock_entry_writer = MockDatasetEntryWriter::new();
+    mock_entry_writer
+        .expect_transfer_entry()
+        .with(
+            function(move |hdl: &odf::DatasetHandle| hdl.id == foo_id_clone),
+            function(move |owner_id: &odf::AccountID| *owner_id == bob_id_clone),
+        )
+        .once()
+        .returning(|_, _| Ok(()));
+
+    let mock_authorizer =
+        MockDatasetActionAuthorizer::new().expect_check_write_dataset(&foo_id, 1, true);
+
+    let harness = TransferOwnershipUseCaseHarness::new(
+        mock_entry_writer,
+        mock_authorizer,
+        Some(MockDidGenerator::predefined_dataset_ids(vec![
+            foo_id.clone()
+        ])),
+    );
+    harness.create_root_dataset(&alias_foo).await;
+    harness.create_account(bob_id, "bob").await;
+
+    let new_handle = harness
+        .use_case
+        .execute(
+            &alias_foo.as_local_ref(),
+            &odf::AccountName::new_unchecked("bob"),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(new_handle.id, foo_id);
+    assert_eq!(new_handle.alias.to_string(), "bob/foo");
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[tokio::test]
+async fn test_transfer_dataset_ownership_to_unknown_account() {
+    let alias_foo = odf::DatasetAlias::new(None, odf::DatasetName::new_unchecked("foo"));
+    let (_, foo_id) = odf::DatasetID::new_generated_ed25519();
+
+    let harness = TransferOwnershipUseCaseHarness::new(
+        MockDatasetEntryWriter::new(),
+        MockDatasetActionAuthorizer::new().expect_check_write_dataset(&foo_id, 1, true),
+        Some(MockDidGenerator::predefined_dataset_ids(vec![foo_id])),
+    );
+    harness.create_root_dataset(&alias_foo).await;
+
+    assert_matches!(
+        harness
+            .use_case
+            .execute(
+                &alias_foo.as_local_ref(),
+                &odf::AccountName::new_unchecked("bob")
+            )
+            .await,
+        Err(TransferDatasetOwnershipError::NewOwnerNotFound(e))
+            if e.account_name.as_str() == "bob"
+    );
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[tokio::test]
+async fn test_transfer_dataset_ownership_name_collision() {
+    let alias_foo = odf::DatasetAlias::new(None, odf::DatasetName::new_unchecked("foo"));
+    let (_, foo_id) = odf::DatasetID::new_generated_ed25519();
+    let (_, bob_id) = odf::AccountID::new_generated_ed25519();
+
+    let mut mock_entry_writer = MockDatasetEntryWriter::new();
+    mock_entry_writer
+        .expect_transfer_entry()
+        .once()
+        .returning(|hdl, _| {
+            Err(TransferDatasetEntryError::NameCollision(
+                DatasetEntryNameCollisionError::new(hdl.alias.dataset_name.clone()),
+            ))
+        });
+
+    let harness = TransferOwnershipUseCaseHarness::new(
+        mock_entry_writer,
+        MockDatasetActionAuthorizer::new().expect_check_write_dataset(&foo_id, 1, true),
+        Some(MockDidGenerator::predefined_dataset_ids(vec![foo_id])),
+    );
+    harness.create_root_dataset(&alias_foo).await;
+    harness.create_account(bob_id, "bob").await;
+
+    assert_matches!(
+        harness
+            .use_case
+            .execute(
+                &alias_foo.as_local_ref(),
+                &odf::AccountName::new_unchecked("bob")
+            )
+            .await,
+        Err(TransferDatasetOwnershipError::NameCollision(e))
+            if e.alias.to_string() == "bob/foo"
+    );
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[tokio::test]
+async fn test_transfer_dataset_ownership_unauthorized() {
+    let alias_foo = odf::DatasetAlias::new(None, odf::DatasetName::new_unchecked("foo"));
+    let (_, foo_id) = odf::DatasetID::new_generated_ed25519();
+
+    let harness = TransferOwnershipUseCaseHarness::new(
+        MockDatasetEntryWriter::new(),
+        MockDatasetActionAuthorizer::new().expect_check_write_dataset(&foo_id, 1, false),
+        Some(MockDidGenerator::predefined_dataset_ids(vec![foo_id])),
+    );
+    harness.create_root_dataset(&alias_foo).await;
+
+    assert_matches!(
+        harness
+            .use_case
+            .execute(
+                &alias_foo.as_local_ref(),
+                &odf::AccountName::new_unchecked("bob")
+            )
+            .await,
+        Err(TransferDatasetOwnershipError::Access(_))
+    );
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[oop::extend(BaseUseCaseHarness, base_use_case_harness)]
+struct TransferOwnershipUseCaseHarness {
+    base_use_case_harness: BaseUseCaseHarness,
+    use_case: Arc<dyn TransferDatasetOwnershipUseCase>,
+    account_repo: Arc<dyn AccountRepository>,
+}
+
+impl TransferOwnershipUseCaseHarness {
+    fn new(
+        mock_dataset_entry_writer: MockDatasetEntryWriter,
+        mock_dataset_action_authorizer: MockDatasetActionAuthorizer,
+        maybe_mock_did_generator: Option<MockDidGenerator>,
+    ) -> Self {
+        let base_use_case_harness = BaseUseCaseHarness::new(
+            BaseUseCaseHarnessOptions::new()
+                .with_maybe_authorizer(Some(mock_dataset_action_authorizer))
+                .with_maybe_mock_did_generator(maybe_mock_did_generator),
+        );
+
+        let catalog = dill::CatalogBuilder::new_chained(base_use_case_harness.catalog())
+            .add::<TransferDatasetOwnershipUseCaseImpl>()
+            .add::<InMemoryAccountRepository>()
+            .add::<AccountServiceImpl>()
+            .add_value(mock_dataset_entry_writer)
+            .bind::<dyn DatasetEntryWriter, MockDatasetEntryWriter>()
+            .build();
+
+        Self {
+            base_use_case_harness,
+            use_case: catalog.get_one().unwrap(),
+            account_repo: catalog.get_one().unwrap(),
+        }
+    }
+
+    async fn create_account(&self, account_id: odf::AccountID, account_name: &str) {
+        self.account_repo
+            .create_account(&Account::test(account_id, account_name))
+            .await
+            .unwrap();
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
@@ -0,0 +1,312 @@
+// Copyright Kamu Data, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::assert_matches::assert_matches;
+use std::sync::Arc;
+
+use kamu::testing::{BaseUseCaseHarness, BaseUseCaseHarnessOptions, MockDatasetActionAuthorizer};
+use kamu_core::MockDidGenerator;
+use kamu_datasets::{
+    UpdateDatasetFromSnapshotError,
+    UpdateDatasetFromSnapshotUseCase,
+    UpdateDatasetFromSnapshotUseCaseOptions,
+};
+use kamu_datasets_services::testing::expect_outbox_dataset_reference_updated;
+use kamu_datasets_services::{
+    CommitDatasetEventUseCaseImpl,
+    DependencyGraphWriter,
+    MockDependencyGraphWriter,
+    UpdateDatasetFromSnapshotUseCaseImpl,
+};
+use messaging_outbox::MockOutbox;
+use odf::metadata::testing::MetadataFactory;
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[tokio::test]
+async fn test_update_dataset_from_snapshot() {
+    let alias_foo = odf::DatasetAlias::new(None, odf::DatasetName::new_unchecked("foo"));
+    let (_, dataset_id_foo) = odf::DatasetID::new_generated_ed25519();
+
+    // Dry run, actual run with one commit, repeated run
+    let mock_authorizer =
+        MockDatasetActionAuthorizer::new().expect_check_write_dataset(&dataset_id_foo, 4, true);
+
+    let mut mock_outbox = MockOutbox::new();
+    expect_outbox_dataset_reference_updated(&mut mock_outbox, 1);
+
+    let harness = UpdateDatasetFromSnapshotUseCaseHarness::new(
+        mock_authorizer,
+        MockDidGenerator::predefined_dataset_ids(vec![dataset_id_foo]),
+        mock_outbox,
+    );
+    let foo = harness.create_root_dataset(&alias_foo).await;
+
+    let set_info =
+        odf::MetadataEvent::SetInfo(MetadataFactory::set_info().description("test").build());
+    let snapshot = MetadataFactory::dataset_snapshot()
+        .name(alias_foo.clone())
+        .kind(odf::DatasetKind::Root)
+        .push_event(MetadataFactory::set_polling_source().build())
+        .push_event(set_info.clone())
+        .build();
+
+    // Unchanged polling source is not reported
+    let res = harness
+        .use_case
+        .execute(
+            &foo.dataset_handle,
+            snapshot.clone(),
+            UpdateDatasetFromSnapshotUseCaseOptions { dry_run: true },
+        )
+        .await
+        .unwrap();
+    assert_eq!(res.changes.len(), 1);
+    assert_eq!(res.changes[0].old_event, None);
+    assert_eq!(res.changes[0].new_event, set_info);
+    assert_eq!(res.new_head, None);
+
+    // Dry run did not commit anything
+    let res = harness
+        .use_case
+        .execute(&foo.dataset_handle, snapshot.clone(), Default::default())
+        .await
+        .unwrap();
+    assert_eq!(res.changes.len(), 1);
+    assert_matches!(res.new_head, Some(new_head) if new_head != foo.head);
+
+    let res = harness
+        .use_case
+        .execute(&foo.dataset_handle, snapshot, Default::default())
+        .await
+        .unwrap();
+    assert!(res.changes.is_empty());
+    assert_eq!(res.new_head, None);
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[tokio::test]
+async fn test_update_dataset_from_snapshot_replaces_polling_source() {
+    let alias_foo = odf::DatasetAlias::new(None, odf::DatasetName::new_unchecked("foo"));
+    let (_, dataset_id_foo) = odf::DatasetID::new_generated_ed25519();
+
+    let mock_authorizer =
+        MockDatasetActionAuthorizer::new().expect_check_write_dataset(&dataset_id_foo, 1, true);
+
+    let harness = UpdateDatasetFromSnapshotUseCaseHarness::new(
+        mock_authorizer,
+        MockDidGenerator::predefined_dataset_ids(vec![dataset_id_foo]),
+        MockOutbox::new(),
+    );
+    let foo = harness.create_root_dataset(&alias_foo).await;
+
+    let new_polling_source = odf::MetadataEvent::SetPollingSource(
+        MetadataFactory::set_polling_source()
+            .fetch(odf::metadata::FetchStepUrl {
+                url: "https://example.com/data.csv".to_string(),
+                event_time: None,
+                cache: None,
+                headers: None,
+            })
+            .build(),
+    );
+
+    let res = harness
+        .use_case
+        .execute(
+            &foo.dataset_handle,
+            MetadataFactory::dataset_snapshot()
+                .name(alias_foo.clone())
+                .kind(odf::DatasetKind::Root)
+                .push_event(new_polling_source.clone())
+                .build(),
+            UpdateDatasetFromSnapshotUseCaseOptions { dry_run: true },
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(res.changes.len(), 1);
+    assert_eq!(
+        res.changes[0].old_event,
+        Some(odf::MetadataEvent::SetPollingSource(
+            MetadataFactory::set_polling_source().build()
+        ))
+    );
+    assert_eq!(res.changes[0].new_event, new_polling_source);
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[tokio::test]
+async fn test_update_dataset_from_snapshot_unauthorized() {
+    let alias_foo = odf::DatasetAlias::new(None, odf::DatasetName::new_unchecked("foo"));
+    let (_, dataset_id_foo) = odf::DatasetID::new_generated_ed25519();
+
+    let mock_authorizer =
+        MockDatasetActionAuthorizer::new().expect_check_write_dataset(&dataset_id_foo, 1, false);
+
+    let harness = UpdateDatasetFromSnapshotUseCaseHarness::new(
+        mock_authorizer,
+        MockDidGenerator::predefined_dataset_ids(vec![dataset_id_foo]),
+        MockOutbox::new(),
+    );
+    let foo = harness.create_root_dataset(&alias_foo).await;
+
+    let res = harness
+        .use_case
+        .execute(
+            &foo.dataset_handle,
+            MetadataFactory::dataset_snapshot()
+                .name(alias_foo.clone())
+                .kind(odf::DatasetKind::Root)
+                .push_event(MetadataFactory::set_info().description("test").build())
+                .build(),
+            Default::default(),
+        )
+        .await;
+    assert_matches!(res, Err(UpdateDatasetFromSnapshotError::Access(_)));
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[tokio::test]
+async fn test_update_dataset_from_snapshot_of_other_dataset() {
+    let alias_foo = odf::DatasetAlias::new(None, odf::DatasetName::new_unchecked("foo"));
+    let alias_bar = odf::DatasetAlias::new(None, odf::DatasetName::new_unchecked("bar"));
+    let (_, dataset_id_foo) = odf::DatasetID::new_generated_ed25519();
+
+    let mock_authorizer =
+        MockDatasetActionAuthorizer::new().expect_check_write_dataset(&dataset_id_foo, 2, true);
+
+    let harness = UpdateDatasetFromSnapshotUseCaseHarness::new(
+        mock_authorizer,
+        MockDidGenerator::predefined_dataset_ids(vec![dataset_id_foo]),
+        MockOutbox::new(),
+    );
+    let foo = harness.create_root_dataset(&alias_foo).await;
+
+    // Snapshot of another dataset
+    let res = harness
+        .use_case
+        .execute(
+            &foo.dataset_handle,
+            MetadataFactory::dataset_snapshot()
+                .name(alias_bar)
+                .kind(odf::DatasetKind::Root)
+                .push_event(MetadataFactory::set_info().description("test").build())
+                .build(),
+            UpdateDatasetFromSnapshotUseCaseOptions { dry_run: true },
+        )
+        .await;
+    assert_matches!(res, Err(UpdateDatasetFromSnapshotError::InvalidSnapshot(_)));
+
+    // Snapshot of a dataset of another kind
+    let res = harness
+        .use_case
+        .execute(
+            &foo.dataset_handle,
+            MetadataFactory::dataset_snapshot()
+                .name(alias_foo.clone())
+                .kind(odf::DatasetKind::Derivative)
+                .push_event(MetadataFactory::set_info().description("test").build())
+                .build(),
+            UpdateDatasetFromSnapshotUseCaseOptions { dry_run: true },
+        )
+        .await;
+    assert_matches!(res, Err(UpdateDatasetFromSnapshotError::InvalidSnapshot(_)));
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[tokio::test]
+async fn test_update_dataset_from_snapshot_with_rejected_event_commits_nothing() {
+    let alias_foo = odf::DatasetAlias::new(None, odf::DatasetName::new_unchecked("foo"));
+    let (_, dataset_id_foo) = odf::DatasetID::new_generated_ed25519();
+
+    let mock_authorizer =
+        MockDatasetActionAuthorizer::new().expect_check_write_dataset(&dataset_id_foo, 2, true);
+
+    let harness = UpdateDatasetFromSnapshotUseCaseHarness::new(
+        mock_authorizer,
+        MockDidGenerator::predefined_dataset_ids(vec![dataset_id_foo]),
+        MockOutbox::new(),
+    );
+    let foo = harness.create_root_dataset(&alias_foo).await;
+
+    // Push source can't be added while the polling source is active, so the
+    // second event is rejected by the metadata chain
+    let res = harness
+        .use_case
+        .execute(
+            &foo.dataset_handle,
+            MetadataFactory::dataset_snapshot()
+                .name(alias_foo.clone())
+                .kind(odf::DatasetKind::Root)
+                .push_event(MetadataFactory::set_info().description("test").build())
+                .push_event(MetadataFactory::add_push_source().build())
+                .build(),
+            Default::default(),
+        )
+        .await;
+    assert_matches!(res, Err(UpdateDatasetFromSnapshotError::InvalidSnapshot(_)));
+
+    // The accepted first event did not get into the dataset
+    assert_eq!(harness.get_head(&foo.dataset_handle).await, foo.head);
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[oop::extend(BaseUseCaseHarness, base_use_case_harness)]
+struct UpdateDatasetFromSnapshotUseCaseHarness {
+    base_use_case_harness: BaseUseCaseHarness,
+    use_case: Arc<dyn UpdateDatasetFromSnapshotUseCase>,
+}
+
+impl UpdateDatasetFromSnapshotUseCaseHarness {
+    fn new(
+        mock_dataset_action_authorizer: MockDatasetActionAuthorizer,
+        mock_did_generator: MockDidGenerator,
+        mock_outbox: MockOutbox,
+    ) -> Self {
+        let base_use_case_harness = BaseUseCaseHarness::new(
+            BaseUseCaseHarnessOptions::new()
+                .with_maybe_authorizer(Some(mock_dataset_action_authorizer))
+                .with_maybe_mock_did_generator(Some(mock_did_generator))
+                .with_outbox(mock_outbox),
+        );
+
+        let catalog = dill::CatalogBuilder::new_chained(base_use_case_harness.catalog())
+            .add::<UpdateDatasetFromSnapshotUseCaseImpl>()
+            .add::<CommitDatasetEventUseCaseImpl>()
+            .add_value(MockDependencyGraphWriter::new())
+            .bind::<dyn DependencyGraphWriter, MockDependencyGraphWriter>()
+            .build();
+
+        let use_case = catalog
+            .get_one::<dyn UpdateDatasetFromSnapshotUseCase>()
+            .unwrap();
+
+        Self {
+            base_use_case_harness,
+            use_case,
+        }
+    }
+    async fn get_head(&self, dataset_handle: &odf::DatasetHandle) -> odf::Multihash {
+        self.dataset_registry()
+            .get_dataset_by_handle(dataset_handle)
+            .await
+            .as_metadata_chain()
+            .resolve_ref(&odf::BlockRef::Head)
+            .await
+            .unwrap()
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
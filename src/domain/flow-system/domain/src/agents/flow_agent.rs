@@ -27,6 +27,10 @@ pub struct FlowAgentConfig {
     pub awaiting_step: chrono::Duration,
     /// Defines minimal time between 2 runs of the same flow configuration
     pub mandatory_throttling_period: chrono::Duration,
+    /// Defines how often heads of pull remotes of upstream datasets are
+    /// compared against local copies, so that changes on remote nodes
+    /// trigger updates of their derivatives. Polling is disabled when `None`
+    pub remote_upstream_polling_interval: Option<chrono::Duration>,
 }
 
 impl FlowAgentConfig {
@@ -37,9 +41,18 @@ impl FlowAgentConfig {
         Self {
             awaiting_step,
             mandatory_throttling_period,
+            remote_upstream_polling_interval: None,
         }
     }
 
+    pub fn with_remote_upstream_polling_interval(
+        mut self,
+        remote_upstream_polling_interval: chrono::Duration,
+    ) -> Self {
+        self.remote_upstream_polling_interval = Some(remote_upstream_polling_interval);
+        self
+    }
+
     pub fn round_time(&self, time: DateTime<Utc>) -> Result<DateTime<Utc>, InternalError> {
         let rounded_time = time.duration_round(self.awaiting_step).int_err()?;
         Ok(rounded_time)
@@ -7,7 +7,7 @@
 // the Business Source License, use of this software will be governed
 // by the Apache License, Version 2.0.
 
-use kamu_core::{CompactionResult, PullResult, PullResultUpToDate};
+use kamu_core::{CompactionResult, PullResult, PullResultUpToDate, ReprocessResult};
 use kamu_task_system::{self as ts, ResetDatasetTaskError, UpdateDatasetTaskError};
 use serde::{Deserialize, Serialize};
 use ts::TaskError;
@@ -146,7 +146,9 @@ impl From<ts::TaskResult> for FlowResult {
             }
             ts::TaskResult::CompactionDatasetResult(task_compaction_result) => {
                 match task_compaction_result.compaction_result {
-                    CompactionResult::NothingToDo => Self::Empty,
+                    CompactionResult::NothingToDo | CompactionResult::DataSlicesMerged { .. } => {
+                        Self::Empty
+                    }
                     CompactionResult::Success {
                         new_head,
                         old_num_blocks,
@@ -159,6 +161,19 @@ impl From<ts::TaskResult> for FlowResult {
                     }),
                 }
             }
+            ts::TaskResult::ReprocessDatasetResult(task_reprocess_result) => {
+                match task_reprocess_result.reprocess_result {
+                    ReprocessResult::UpToDate => Self::Empty,
+                    ReprocessResult::Updated {
+                        old_head, new_head, ..
+                    } => Self::DatasetUpdate(FlowResultDatasetUpdate::Changed(
+                        FlowResultDatasetUpdateChanged {
+                            old_head: Some(old_head),
+                            new_head,
+                        },
+                    )),
+                }
+            }
         }
     }
 }
@@ -11,7 +11,7 @@ use chrono::{DateTime, Duration, Utc};
 use kamu_task_system::TaskID;
 use serde::{Deserialize, Serialize};
 
-use crate::BatchingRule;
+use crate::{BatchingRule, TimeWindow};
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
 
@@ -20,6 +20,7 @@ pub enum FlowStartCondition {
     Schedule(FlowStartConditionSchedule),
     Throttling(FlowStartConditionThrottling),
     Batching(FlowStartConditionBatching),
+    Window(FlowStartConditionWindow),
     Executor(FlowStartConditionExecutor),
 }
 
@@ -28,6 +29,7 @@ impl FlowStartCondition {
         match self {
             Self::Schedule(s) => Some(s.wake_up_at),
             Self::Throttling(t) => Some(t.wake_up_at),
+            Self::Window(w) => Some(w.wake_up_at),
             Self::Batching(_) | Self::Executor(_) => None,
         }
     }
@@ -61,6 +63,15 @@ pub struct FlowStartConditionBatching {
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
 
+/// Flow is ready, but waits for its time window to open
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FlowStartConditionWindow {
+    pub time_window: TimeWindow,
+    pub wake_up_at: DateTime<Utc>,
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub struct FlowStartConditionExecutor {
     pub task_id: TaskID,
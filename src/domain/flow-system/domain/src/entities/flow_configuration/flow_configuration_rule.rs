@@ -9,7 +9,7 @@
 
 use serde::{Deserialize, Serialize};
 
-use crate::{CompactionRule, IngestRule, ResetRule};
+use crate::{CompactionRule, IngestRule, ReprocessRule, ResetRule};
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
 
@@ -18,6 +18,7 @@ pub enum FlowConfigurationRule {
     CompactionRule(CompactionRule),
     IngestRule(IngestRule),
     ResetRule(ResetRule),
+    ReprocessRule(ReprocessRule),
 }
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
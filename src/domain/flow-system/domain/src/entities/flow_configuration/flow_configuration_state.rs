@@ -51,6 +51,14 @@ impl FlowConfigurationState {
             None
         }
     }
+
+    pub fn try_get_reprocess_rule(self) -> Option<ReprocessRule> {
+        if let FlowConfigurationRule::ReprocessRule(reprocess_rule) = self.rule {
+            Some(reprocess_rule)
+        } else {
+            None
+        }
+    }
 }
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
@@ -11,6 +11,8 @@ use chrono::Duration;
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
+use crate::TimeWindow;
+
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
 
 #[serde_with::serde_as]
@@ -19,6 +21,14 @@ pub struct BatchingRule {
     min_records_to_await: u64,
     #[serde_as(as = "serde_with::DurationSeconds<i64>")]
     max_batching_interval: Duration,
+    /// Wait until every input dataset got updated since the last run.
+    /// Inputs mirrored from remote nodes count as updated once their mirror
+    /// pulled the new data.
+    #[serde(default)]
+    await_all_inputs: bool,
+    /// Only start flows within this daily time window
+    #[serde(default)]
+    time_window: Option<TimeWindow>,
 }
 
 impl BatchingRule {
@@ -45,9 +55,21 @@ impl BatchingRule {
         Ok(Self {
             min_records_to_await,
             max_batching_interval,
+            await_all_inputs: false,
+            time_window: None,
         })
     }
 
+    pub fn with_await_all_inputs(mut self, await_all_inputs: bool) -> Self {
+        self.await_all_inputs = await_all_inputs;
+        self
+    }
+
+    pub fn with_time_window(mut self, time_window: Option<TimeWindow>) -> Self {
+        self.time_window = time_window;
+        self
+    }
+
     #[inline]
     pub fn min_records_to_await(&self) -> u64 {
         self.min_records_to_await
@@ -57,6 +79,16 @@ impl BatchingRule {
     pub fn max_batching_interval(&self) -> &Duration {
         &self.max_batching_interval
     }
+
+    #[inline]
+    pub fn await_all_inputs(&self) -> bool {
+        self.await_all_inputs
+    }
+
+    #[inline]
+    pub fn time_window(&self) -> Option<&TimeWindow> {
+        self.time_window.as_ref()
+    }
 }
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
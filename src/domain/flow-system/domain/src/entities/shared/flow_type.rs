@@ -18,6 +18,7 @@ pub enum DatasetFlowType {
     ExecuteTransform,
     HardCompaction,
     Reset,
+    Reprocess,
 }
 
 impl DatasetFlowType {
@@ -27,6 +28,7 @@ impl DatasetFlowType {
             Self::ExecuteTransform,
             Self::HardCompaction,
             Self::Reset,
+            Self::Reprocess,
         ]
     }
 
@@ -35,7 +37,9 @@ impl DatasetFlowType {
             DatasetFlowType::Ingest | DatasetFlowType::HardCompaction => {
                 Some(odf::DatasetKind::Root)
             }
-            DatasetFlowType::ExecuteTransform => Some(odf::DatasetKind::Derivative),
+            DatasetFlowType::ExecuteTransform | DatasetFlowType::Reprocess => {
+                Some(odf::DatasetKind::Derivative)
+            }
             DatasetFlowType::Reset => None,
         }
     }
@@ -71,7 +75,8 @@ impl AnyFlowType {
                 DatasetFlowType::Ingest
                 | DatasetFlowType::ExecuteTransform
                 | DatasetFlowType::HardCompaction
-                | DatasetFlowType::Reset,
+                | DatasetFlowType::Reset
+                | DatasetFlowType::Reprocess,
             ) => FlowSuccessFollowupMethod::TriggerDependent,
             _ => FlowSuccessFollowupMethod::Ignore,
         }
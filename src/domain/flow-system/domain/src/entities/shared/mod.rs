@@ -13,8 +13,10 @@ mod flow_key;
 mod flow_task_metadata;
 mod flow_type;
 mod ingest_rule;
+mod reprocess_rule;
 mod reset_rule;
 mod schedule;
+mod time_window;
 
 pub use batching_rule::*;
 pub use compaction_rule::*;
@@ -22,5 +24,7 @@ pub use flow_key::*;
 pub use flow_task_metadata::*;
 pub use flow_type::*;
 pub use ingest_rule::*;
+pub use reprocess_rule::*;
 pub use reset_rule::*;
 pub use schedule::*;
+pub use time_window::*;
@@ -10,6 +10,7 @@
 use std::str::FromStr;
 
 use chrono::{DateTime, Utc};
+use chrono_tz::Tz;
 use internal_error::{ErrorIntoInternal, InternalError};
 use serde::{Deserialize, Serialize};
 use serde_with::{serde_as, DisplayFromStr};
@@ -41,6 +42,9 @@ pub struct ScheduleCron {
     pub source_5component_cron_expression: String,
     #[serde_as(as = "DisplayFromStr")]
     pub cron_schedule: cron::Schedule,
+    /// IANA time zone the expression is evaluated in, UTC if not specified
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    pub time_zone: Option<Tz>,
 }
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
@@ -50,6 +54,9 @@ pub enum ScheduleCronError {
     #[error(transparent)]
     InvalidCronExpression(#[from] InvalidCronExpressionError),
 
+    #[error(transparent)]
+    InvalidTimeZone(#[from] InvalidTimeZoneError),
+
     #[error(transparent)]
     Internal(#[from] InternalError),
 }
@@ -60,6 +67,12 @@ pub struct InvalidCronExpressionError {
     pub expression: String,
 }
 
+#[derive(Error, Debug)]
+#[error("Time zone {time_zone} is unknown")]
+pub struct InvalidTimeZoneError {
+    pub time_zone: String,
+}
+
 #[derive(Error, Debug)]
 #[error("Cron expression {expression} iteration has been exceeded")]
 pub struct CronExpressionIterationError {
@@ -74,6 +87,30 @@ const CLASSIC_CRONTAB_COMPONENTS_COUNT: usize = 5;
 impl Schedule {
     pub fn try_from_5component_cron_expression(
         source_5component_cron_expression: &str,
+    ) -> Result<Schedule, ScheduleCronError> {
+        Self::try_from_5component_cron_expression_impl(source_5component_cron_expression, None)
+    }
+
+    /// Same as [`Schedule::try_from_5component_cron_expression`], but the
+    /// expression is evaluated in the given IANA time zone (e.g.
+    /// `Europe/Berlin`), following its daylight saving time transitions
+    pub fn try_from_5component_cron_expression_in_time_zone(
+        source_5component_cron_expression: &str,
+        time_zone: &str,
+    ) -> Result<Schedule, ScheduleCronError> {
+        let time_zone = Tz::from_str(time_zone).map_err(|_| InvalidTimeZoneError {
+            time_zone: time_zone.to_string(),
+        })?;
+
+        Self::try_from_5component_cron_expression_impl(
+            source_5component_cron_expression,
+            Some(time_zone),
+        )
+    }
+
+    fn try_from_5component_cron_expression_impl(
+        source_5component_cron_expression: &str,
+        time_zone: Option<Tz>,
     ) -> Result<Schedule, ScheduleCronError> {
         // Ensure we obtained classic 5-component CRONTAB expression
         let components_count = source_5component_cron_expression.split_whitespace().count();
@@ -103,6 +140,7 @@ impl Schedule {
             Some(_) => Ok(Schedule::Cron(ScheduleCron {
                 source_5component_cron_expression: source_5component_cron_expression.to_string(),
                 cron_schedule,
+                time_zone,
             })),
             None => Err(ScheduleCronError::Internal(
                 CronExpressionIterationError {
@@ -136,11 +174,15 @@ impl Schedule {
             }
             // CRON expressions do not care of current or last activation time,
             // they always pick next by the CRON expression
-            Schedule::Cron(ce) => ce
-                .cron_schedule
-                .after(&now)
-                .next()
-                .expect("CRON expressions we allow should never expire"),
+            Schedule::Cron(ce) => match ce.time_zone {
+                None => ce.cron_schedule.after(&now).next(),
+                Some(tz) => ce
+                    .cron_schedule
+                    .after(&now.with_timezone(&tz))
+                    .next()
+                    .map(|t| t.with_timezone(&Utc)),
+            }
+            .expect("CRON expressions we allow should never expire"),
         }
     }
 }
@@ -189,6 +231,43 @@ mod tests {
         assert_eq!(next_time, expected_time);
     }
 
+    #[test]
+    fn test_get_next_time_from_cron_expression_in_time_zone() {
+        let schedule = Schedule::try_from_5component_cron_expression_in_time_zone(
+            "0 6 * * *",
+            "Europe/Berlin",
+        )
+        .unwrap();
+
+        // Winter time: UTC+1
+        let now = Utc.with_ymd_and_hms(2025, 1, 15, 12, 0, 0).unwrap();
+        assert_eq!(
+            schedule.next_activation_time(now, None),
+            Utc.with_ymd_and_hms(2025, 1, 16, 5, 0, 0).unwrap()
+        );
+
+        // Summer time: UTC+2
+        let now = Utc.with_ymd_and_hms(2025, 7, 15, 12, 0, 0).unwrap();
+        assert_eq!(
+            schedule.next_activation_time(now, None),
+            Utc.with_ymd_and_hms(2025, 7, 16, 4, 0, 0).unwrap()
+        );
+
+        // Across the switch to summer time on 30 March 2025
+        let now = Utc.with_ymd_and_hms(2025, 3, 29, 12, 0, 0).unwrap();
+        assert_eq!(
+            schedule.next_activation_time(now, None),
+            Utc.with_ymd_and_hms(2025, 3, 30, 4, 0, 0).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_parse_cron_expression_with_unknown_time_zone_fails() {
+        let res =
+            Schedule::try_from_5component_cron_expression_in_time_zone("0 6 * * *", "Mars/Olympus");
+        assert_matches!(res, Err(ScheduleCronError::InvalidTimeZone(_)));
+    }
+
     #[test]
     fn test_parse_cron_expression_with_year_fails() {
         let res = Schedule::try_from_5component_cron_expression("0 0 1 JAN ? 2024");
@@ -0,0 +1,262 @@
+// Copyright Kamu Data, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::str::FromStr;
+
+use chrono::{DateTime, Days, NaiveDateTime, NaiveTime, Offset, TimeZone, Utc};
+use chrono_tz::Tz;
+use serde::{Deserialize, Serialize};
+use serde_with::{serde_as, DisplayFromStr};
+use thiserror::Error;
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+/// Daily period of time when flows are allowed to start, in UTC or in the
+/// given IANA time zone. A window with `end` earlier than `start` spans over
+/// midnight.
+#[serde_as]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TimeWindow {
+    #[serde_as(as = "DisplayFromStr")]
+    start: NaiveTime,
+    #[serde_as(as = "DisplayFromStr")]
+    end: NaiveTime,
+    /// IANA time zone the window is evaluated in, UTC if not specified
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    time_zone: Option<Tz>,
+}
+
+impl TimeWindow {
+    pub fn new_checked(
+        start: NaiveTime,
+        end: NaiveTime,
+    ) -> Result<Self, TimeWindowValidationError> {
+        if start == end {
+            return Err(TimeWindowValidationError::EmptyWindow);
+        }
+
+        Ok(Self {
+            start,
+            end,
+            time_zone: None,
+        })
+    }
+
+    /// Evaluates the window in the given IANA time zone (e.g.
+    /// `Europe/Berlin`), following its daylight saving time transitions
+    pub fn in_time_zone(mut self, time_zone: &str) -> Result<Self, TimeWindowValidationError> {
+        let time_zone =
+            Tz::from_str(time_zone).map_err(|_| TimeWindowValidationError::UnknownTimeZone {
+                time_zone: time_zone.to_string(),
+            })?;
+
+        self.time_zone = Some(time_zone);
+        Ok(self)
+    }
+
+    #[inline]
+    pub fn start(&self) -> NaiveTime {
+        self.start
+    }
+
+    #[inline]
+    pub fn end(&self) -> NaiveTime {
+        self.end
+    }
+
+    #[inline]
+    pub fn time_zone(&self) -> Option<Tz> {
+        self.time_zone
+    }
+
+    pub fn contains(&self, time: DateTime<Utc>) -> bool {
+        let time = self.local_time_of(time).time();
+        if self.start < self.end {
+            self.start <= time && time < self.end
+        } else {
+            self.start <= time || time < self.end
+        }
+    }
+
+    /// Returns the given moment if it falls into the window, otherwise the
+    /// moment the window opens next time
+    pub fn next_opening(&self, after: DateTime<Utc>) -> DateTime<Utc> {
+        if self.contains(after) {
+            return after;
+        }
+
+        let today = self.local_time_of(after).date();
+        let opening_today = self.utc_time_of(today.and_time(self.start));
+        if opening_today > after {
+            opening_today
+        } else {
+            self.utc_time_of((today + Days::new(1)).and_time(self.start))
+        }
+    }
+
+    fn local_time_of(&self, time: DateTime<Utc>) -> NaiveDateTime {
+        match self.time_zone {
+            None => time.naive_utc(),
+            Some(tz) => time.with_timezone(&tz).naive_local(),
+        }
+    }
+
+    fn utc_time_of(&self, local: NaiveDateTime) -> DateTime<Utc> {
+        let Some(tz) = self.time_zone else {
+            return local.and_utc();
+        };
+
+        match tz.from_local_datetime(&local).earliest() {
+            Some(time) => time.with_timezone(&Utc),
+            // The local time was skipped by a daylight saving time transition:
+            // shift it forward by the length of the gap, by interpreting it
+            // with the offset that was in effect before the transition
+            None => {
+                let offset_before = tz.offset_from_utc_datetime(&(local - Days::new(1))).fix();
+                (local - offset_before).and_utc()
+            }
+        }
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[derive(Error, Debug)]
+pub enum TimeWindowValidationError {
+    #[error("Time window start and end should differ")]
+    EmptyWindow,
+
+    #[error("Time zone {time_zone} is unknown")]
+    UnknownTimeZone { time_zone: String },
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use std::assert_matches::assert_matches;
+
+    use chrono::{NaiveTime, TimeZone, Utc};
+
+    use crate::{TimeWindow, TimeWindowValidationError};
+
+    fn hm(hour: u32, min: u32) -> NaiveTime {
+        NaiveTime::from_hms_opt(hour, min, 0).unwrap()
+    }
+
+    #[test]
+    fn test_empty_window() {
+        assert_matches!(
+            TimeWindow::new_checked(hm(6, 0), hm(6, 0)),
+            Err(TimeWindowValidationError::EmptyWindow)
+        );
+    }
+
+    #[test]
+    fn test_next_opening_within_day() {
+        let window = TimeWindow::new_checked(hm(6, 0), hm(9, 0)).unwrap();
+
+        let before = Utc.with_ymd_and_hms(2025, 1, 1, 3, 0, 0).unwrap();
+        assert!(!window.contains(before));
+        assert_eq!(
+            window.next_opening(before),
+            Utc.with_ymd_and_hms(2025, 1, 1, 6, 0, 0).unwrap()
+        );
+
+        let inside = Utc.with_ymd_and_hms(2025, 1, 1, 7, 30, 0).unwrap();
+        assert!(window.contains(inside));
+        assert_eq!(window.next_opening(inside), inside);
+
+        let after = Utc.with_ymd_and_hms(2025, 1, 1, 9, 0, 0).unwrap();
+        assert!(!window.contains(after));
+        assert_eq!(
+            window.next_opening(after),
+            Utc.with_ymd_and_hms(2025, 1, 2, 6, 0, 0).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_next_opening_over_midnight() {
+        let window = TimeWindow::new_checked(hm(22, 0), hm(2, 0)).unwrap();
+
+        let late = Utc.with_ymd_and_hms(2025, 1, 1, 23, 0, 0).unwrap();
+        assert_eq!(window.next_opening(late), late);
+
+        let early = Utc.with_ymd_and_hms(2025, 1, 2, 1, 0, 0).unwrap();
+        assert_eq!(window.next_opening(early), early);
+
+        let day = Utc.with_ymd_and_hms(2025, 1, 2, 12, 0, 0).unwrap();
+        assert_eq!(
+            window.next_opening(day),
+            Utc.with_ymd_and_hms(2025, 1, 2, 22, 0, 0).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_window_in_time_zone() {
+        let window = TimeWindow::new_checked(hm(6, 0), hm(9, 0))
+            .unwrap()
+            .in_time_zone("Europe/Berlin")
+            .unwrap();
+
+        // Winter time: UTC+1
+        let now = Utc.with_ymd_and_hms(2025, 1, 15, 5, 30, 0).unwrap();
+        assert!(window.contains(now));
+        let now = Utc.with_ymd_and_hms(2025, 1, 15, 12, 0, 0).unwrap();
+        assert!(!window.contains(now));
+        assert_eq!(
+            window.next_opening(now),
+            Utc.with_ymd_and_hms(2025, 1, 16, 5, 0, 0).unwrap()
+        );
+
+        // Summer time: UTC+2
+        let now = Utc.with_ymd_and_hms(2025, 7, 15, 7, 30, 0).unwrap();
+        assert!(!window.contains(now));
+        assert_eq!(
+            window.next_opening(now),
+            Utc.with_ymd_and_hms(2025, 7, 16, 4, 0, 0).unwrap()
+        );
+
+        // Across the switch to summer time on 30 March 2025
+        let now = Utc.with_ymd_and_hms(2025, 3, 29, 12, 0, 0).unwrap();
+        assert_eq!(
+            window.next_opening(now),
+            Utc.with_ymd_and_hms(2025, 3, 30, 4, 0, 0).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_window_opening_skipped_by_time_transition() {
+        // 02:30 does not exist in Berlin on 30 March 2025
+        let window = TimeWindow::new_checked(hm(2, 30), hm(5, 0))
+            .unwrap()
+            .in_time_zone("Europe/Berlin")
+            .unwrap();
+
+        let now = Utc.with_ymd_and_hms(2025, 3, 29, 12, 0, 0).unwrap();
+        let opening = window.next_opening(now);
+        assert_eq!(
+            opening,
+            Utc.with_ymd_and_hms(2025, 3, 30, 1, 30, 0).unwrap()
+        );
+        assert!(window.contains(opening));
+    }
+
+    #[test]
+    fn test_window_in_unknown_time_zone() {
+        assert_matches!(
+            TimeWindow::new_checked(hm(6, 0), hm(9, 0))
+                .unwrap()
+                .in_time_zone("Mars/Olympus"),
+            Err(TimeWindowValidationError::UnknownTimeZone { .. })
+        );
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
@@ -62,6 +62,12 @@ pub trait FlowConfigurationServiceExt {
         flow_type: DatasetFlowType,
     ) -> Result<Option<ResetRule>, FindFlowConfigurationError>;
 
+    async fn try_get_dataset_reprocess_rule(
+        &self,
+        dataset_id: odf::DatasetID,
+        flow_type: DatasetFlowType,
+    ) -> Result<Option<ReprocessRule>, FindFlowConfigurationError>;
+
     async fn try_get_config_snapshot_by_key(
         &self,
         flow_key: FlowKey,
@@ -127,6 +133,25 @@ impl<T: FlowConfigurationService + ?Sized> FlowConfigurationServiceExt for T {
         )
     }
 
+    async fn try_get_dataset_reprocess_rule(
+        &self,
+        dataset_id: odf::DatasetID,
+        flow_type: DatasetFlowType,
+    ) -> Result<Option<ReprocessRule>, FindFlowConfigurationError> {
+        let maybe_config = self
+            .find_configuration(FlowKey::dataset(dataset_id, flow_type))
+            .await?;
+        Ok(
+            if let Some(config) = maybe_config
+                && config.is_active()
+            {
+                config.try_get_reprocess_rule()
+            } else {
+                None
+            },
+        )
+    }
+
     async fn try_get_config_snapshot_by_key(
         &self,
         flow_key: FlowKey,
@@ -149,6 +174,13 @@ impl<T: FlowConfigurationService + ?Sized> FlowConfigurationServiceExt for T {
                     )
                     .await?
                     .map(FlowConfigurationRule::ResetRule),
+                DatasetFlowType::Reprocess => self
+                    .try_get_dataset_reprocess_rule(
+                        dataset_flow_key.dataset_id,
+                        dataset_flow_key.flow_type,
+                    )
+                    .await?
+                    .map(FlowConfigurationRule::ReprocessRule),
                 DatasetFlowType::HardCompaction => self
                     .try_get_dataset_compaction_rule(
                         dataset_flow_key.dataset_id,
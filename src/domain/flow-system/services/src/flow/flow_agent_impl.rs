@@ -9,6 +9,7 @@
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
 
+use std::collections::HashSet;
 use std::sync::Arc;
 
 use chrono::{DateTime, Utc};
@@ -18,7 +19,14 @@ use dill::*;
 use futures::TryStreamExt;
 use init_on_startup::{InitOnStartup, InitOnStartupMeta};
 use internal_error::InternalError;
-use kamu_datasets::{DatasetLifecycleMessage, MESSAGE_PRODUCER_KAMU_DATASET_SERVICE};
+use kamu_core::utils::metadata_chain_comparator::CompareChainsResult;
+use kamu_core::{DatasetRegistry, DatasetRegistryExt, DependencyGraphService, RemoteStatusService};
+use kamu_datasets::{
+    DatasetEntryService,
+    DatasetLifecycleMessage,
+    GetDatasetEntryError,
+    MESSAGE_PRODUCER_KAMU_DATASET_SERVICE,
+};
 use kamu_flow_system::*;
 use kamu_task_system::*;
 use messaging_outbox::{
@@ -224,6 +232,127 @@ impl FlowAgentImpl {
         Ok(())
     }
 
+    /// Upstream datasets hosted on remote nodes are represented by local
+    /// mirrors, i.e. datasets with a pull alias. When a remote moved ahead of
+    /// its mirror, the mirror gets updated, and its update cascades to the
+    /// derivatives like for any other input
+    #[transactional_method]
+    async fn poll_remote_upstreams(&self, poll_time: DateTime<Utc>) -> Result<(), InternalError> {
+        let flow_trigger_service = transaction_catalog
+            .get_one::<dyn FlowTriggerService>()
+            .unwrap();
+        let dependency_graph_service = transaction_catalog
+            .get_one::<dyn DependencyGraphService>()
+            .unwrap();
+        let dataset_registry = transaction_catalog
+            .get_one::<dyn DatasetRegistry>()
+            .unwrap();
+        let remote_status_service = transaction_catalog
+            .get_one::<dyn RemoteStatusService>()
+            .unwrap();
+        let scheduling_helper = transaction_catalog
+            .get_one::<FlowSchedulingHelper>()
+            .unwrap();
+
+        // Only inputs of derivatives, which are updated automatically, matter
+        let enabled_triggers: Vec<_> = flow_trigger_service
+            .list_enabled_triggers()
+            .try_collect()
+            .await?;
+
+        let mut upstream_ids = HashSet::new();
+        for enabled_trigger in enabled_triggers {
+            if let FlowKey::Dataset(fk_dataset) = &enabled_trigger.flow_key
+                && fk_dataset.flow_type == DatasetFlowType::ExecuteTransform
+                && matches!(enabled_trigger.rule, FlowTriggerRule::Batching(_))
+            {
+                use futures::StreamExt;
+                let input_ids: Vec<_> = dependency_graph_service
+                    .get_upstream_dependencies(&fk_dataset.dataset_id)
+                    .await
+                    .int_err()?
+                    .collect()
+                    .await;
+                upstream_ids.extend(input_ids);
+            }
+        }
+
+        for upstream_id in upstream_ids {
+            let Some(upstream_handle) = dataset_registry
+                .try_resolve_dataset_handle_by_ref(&upstream_id.as_local_ref())
+                .await?
+            else {
+                continue;
+            };
+
+            let pull_statuses = remote_status_service
+                .check_pull_remotes_status(&upstream_handle)
+                .await?;
+
+            let mut remote_is_ahead = false;
+            for pull_status in pull_statuses.statuses {
+                match pull_status.check_result {
+                    Ok(CompareChainsResult::LhsBehind { .. }) => remote_is_ahead = true,
+                    Ok(_) => {}
+                    Err(e) => {
+                        tracing::warn!(
+                            dataset = %upstream_handle,
+                            remote = %pull_status.remote,
+                            error = ?e,
+                            error_msg = %e,
+                            "Checking remote upstream status failed"
+                        );
+                    }
+                }
+            }
+            if !remote_is_ahead {
+                continue;
+            }
+
+            let summary = dataset_registry
+                .get_dataset_by_handle(&upstream_handle)
+                .await
+                .get_summary(odf::dataset::GetSummaryOpts::default())
+                .await
+                .int_err()?;
+            let flow_type = match summary.kind {
+                odf::DatasetKind::Root => DatasetFlowType::Ingest,
+                odf::DatasetKind::Derivative => DatasetFlowType::ExecuteTransform,
+            };
+            let flow_key = FlowKey::dataset(upstream_handle.id.clone(), flow_type);
+
+            // Paused updates of the mirror are respected
+            let maybe_trigger = flow_trigger_service
+                .find_trigger(flow_key.clone())
+                .await
+                .int_err()?;
+            if let Some(trigger) = maybe_trigger
+                && !trigger.is_active()
+            {
+                continue;
+            }
+
+            tracing::info!(
+                dataset = %upstream_handle,
+                ?flow_type,
+                "Remote upstream dataset has new data, triggering update"
+            );
+
+            scheduling_helper
+                .trigger_flow_common(
+                    &flow_key,
+                    None,
+                    FlowTriggerType::AutoPolling(FlowTriggerAutoPolling {
+                        trigger_time: poll_time,
+                    }),
+                    None,
+                )
+                .await?;
+        }
+
+        Ok(())
+    }
+
     #[transactional_method]
     async fn tick_current_timeslot(&self) -> Result<(), InternalError> {
         let flow_event_store = transaction_catalog.get_one::<dyn FlowEventStore>().unwrap();
@@ -323,6 +452,10 @@ impl FlowAgentImpl {
         let logical_plan =
             self.make_task_logical_plan(&flow.flow_key, flow.config_snapshot.as_ref())?;
 
+        let scheduling_options = self
+            .make_task_scheduling_options(&target_catalog, flow)
+            .await?;
+
         let task_scheduler = target_catalog.get_one::<dyn TaskScheduler>().unwrap();
         let task = task_scheduler
             .create_task(
@@ -331,6 +464,7 @@ impl FlowAgentImpl {
                     METADATA_TASK_FLOW_ID,
                     flow.flow_id.to_string(),
                 )])),
+                scheduling_options,
             )
             .await
             .int_err()?;
@@ -352,6 +486,40 @@ impl FlowAgentImpl {
         Ok(task.task_id)
     }
 
+    /// Manually requested flows are served first, while flows cascaded from
+    /// updates of inputs wait for the rest. Dataset flows consume the workers
+    /// share of the dataset owner
+    async fn make_task_scheduling_options(
+        &self,
+        target_catalog: &Catalog,
+        flow: &Flow,
+    ) -> Result<TaskSchedulingOptions, InternalError> {
+        let priority = match flow.primary_trigger() {
+            FlowTriggerType::Manual(_) => TaskPriority::Manual,
+            FlowTriggerType::AutoPolling(_) | FlowTriggerType::Push(_) => TaskPriority::Scheduled,
+            FlowTriggerType::InputDatasetFlow(_) => TaskPriority::Cascaded,
+        };
+
+        let account_id = match &flow.flow_key {
+            FlowKey::Dataset(flow_key) => {
+                let dataset_entry_service =
+                    target_catalog.get_one::<dyn DatasetEntryService>().unwrap();
+                match dataset_entry_service.get_entry(&flow_key.dataset_id).await {
+                    Ok(entry) => Some(entry.owner_id),
+                    // The dataset is being deleted, the task will fail on its own
+                    Err(GetDatasetEntryError::NotFound(_)) => None,
+                    Err(GetDatasetEntryError::Internal(e)) => return Err(e),
+                }
+            }
+            FlowKey::System(_) => None,
+        };
+
+        Ok(TaskSchedulingOptions {
+            priority,
+            account_id,
+        })
+    }
+
     /// Creates task logical plan that corresponds to template
     pub fn make_task_logical_plan(
         &self,
@@ -409,6 +577,19 @@ impl FlowAgentImpl {
                     }
                     InternalError::bail("Reset flow cannot be called without configuration")
                 }
+                DatasetFlowType::Reprocess => {
+                    if let Some(config_rule) = maybe_config_snapshot
+                        && let FlowConfigurationRule::ReprocessRule(reprocess_rule) = config_rule
+                    {
+                        return Ok(LogicalPlan::ReprocessDataset(LogicalPlanReprocessDataset {
+                            dataset_id: flow_key.dataset_id.clone(),
+                            start: reprocess_rule.start.clone(),
+                            new_transform: reprocess_rule.new_transform.clone(),
+                            primary_key: reprocess_rule.primary_key.clone(),
+                        }));
+                    }
+                    InternalError::bail("Reprocess flow cannot be called without configuration")
+                }
             },
             FlowKey::System(flow_key) => {
                 match flow_key.flow_type {
@@ -440,6 +621,8 @@ impl FlowAgentImpl {
 impl FlowAgent for FlowAgentImpl {
     /// Runs the update main loop
     async fn run(&self) -> Result<(), InternalError> {
+        let mut next_remote_upstream_poll_time = self.time_source.now();
+
         // Main scanning loop
         loop {
             // Run scheduling for current time slot
@@ -447,6 +630,25 @@ impl FlowAgent for FlowAgentImpl {
                 .instrument(tracing::debug_span!("FlowAgent::tick"))
                 .await?;
 
+            // Check whether upstream datasets on remote nodes got new data
+            if let Some(polling_interval) = self.agent_config.remote_upstream_polling_interval {
+                let current_time = self.time_source.now();
+                if current_time >= next_remote_upstream_poll_time {
+                    next_remote_upstream_poll_time = current_time + polling_interval;
+                    if let Err(e) = self
+                        .poll_remote_upstreams(current_time)
+                        .instrument(tracing::debug_span!("FlowAgent::poll_remote_upstreams"))
+                        .await
+                    {
+                        tracing::error!(
+                            error = ?e,
+                            error_msg = %e,
+                            "Polling remote upstream datasets failed"
+                        );
+                    }
+                }
+            }
+
             self.time_source
                 .sleep(self.agent_config.awaiting_step)
                 .await;
@@ -7,6 +7,7 @@
 // the Business Source License, use of this software will be governed
 // by the Apache License, Version 2.0.
 
+use std::collections::HashSet;
 use std::sync::Arc;
 
 use chrono::{DateTime, Utc};
@@ -260,7 +261,9 @@ impl FlowSchedulingHelper {
         maybe_config_snapshot: Option<&FlowConfigurationRule>,
     ) -> DownstreamDependencyTriggerType {
         match dataset_flow_type {
-            DatasetFlowType::Ingest | DatasetFlowType::ExecuteTransform => {
+            DatasetFlowType::Ingest
+            | DatasetFlowType::ExecuteTransform
+            | DatasetFlowType::Reprocess => {
                 DownstreamDependencyTriggerType::TriggerAllEnabledExecuteTransform
             }
             DatasetFlowType::HardCompaction => {
@@ -529,6 +532,7 @@ impl FlowSchedulingHelper {
         let mut accumulated_records_count = 0;
         let mut watermark_modified = false;
         let mut is_compacted = false;
+        let mut updated_inputs = HashSet::new();
 
         // Scan each accumulated trigger to decide
         for trigger in &flow.triggers {
@@ -554,6 +558,7 @@ impl FlowSchedulingHelper {
 
                             accumulated_records_count += increment.num_records;
                             watermark_modified |= increment.updated_watermark.is_some();
+                            updated_inputs.insert(trigger_type.dataset_id.clone());
                         }
                     }
                 }
@@ -564,8 +569,14 @@ impl FlowSchedulingHelper {
         let batching_deadline =
             flow.primary_trigger().trigger_time() + *batching_rule.max_batching_interval();
 
-        // Accumulated something if at least some input changed or watermark was touched
-        let accumulated_something = accumulated_records_count > 0 || watermark_modified;
+        // Accumulated something if at least some input changed or watermark was
+        // touched. When all inputs are awaited, nothing counts until each of
+        // them was updated.
+        let accumulated_something = (accumulated_records_count > 0 || watermark_modified)
+            && (!batching_rule.await_all_inputs()
+                || self
+                    .all_inputs_updated(&flow.flow_key, &updated_inputs)
+                    .await?);
 
         // The condition is satisfied if
         //   - we crossed the number of new records thresholds
@@ -612,9 +623,27 @@ impl FlowSchedulingHelper {
             }
 
             // Throttling boundary correction
-            let corrected_finish_time =
+            let mut corrected_finish_time =
                 std::cmp::max(batching_finish_time, throttling_boundary_time);
 
+            // Postpone the start until the time window opens
+            if let Some(time_window) = batching_rule.time_window() {
+                let window_opening = time_window.next_opening(corrected_finish_time);
+                if window_opening > corrected_finish_time {
+                    if satisfied || is_compacted {
+                        flow.set_relevant_start_condition(
+                            self.time_source.now(),
+                            FlowStartCondition::Window(FlowStartConditionWindow {
+                                time_window: *time_window,
+                                wake_up_at: window_opening,
+                            }),
+                        )
+                        .int_err()?;
+                    }
+                    corrected_finish_time = window_opening;
+                }
+            }
+
             let should_activate = match flow.timing.scheduled_for_activation_at {
                 Some(scheduled_for_activation_at) => {
                     scheduled_for_activation_at > corrected_finish_time
@@ -630,6 +659,29 @@ impl FlowSchedulingHelper {
         Ok(())
     }
 
+    /// Inputs are the upstream dependencies known to the local dependency
+    /// graph. Datasets on remote nodes take part through their local mirrors.
+    async fn all_inputs_updated(
+        &self,
+        flow_key: &FlowKey,
+        updated_inputs: &HashSet<odf::DatasetID>,
+    ) -> Result<bool, InternalError> {
+        let FlowKey::Dataset(fk_dataset) = flow_key else {
+            return Ok(true);
+        };
+
+        use futures::StreamExt;
+        let input_ids: Vec<_> = self
+            .dependency_graph_service
+            .get_upstream_dependencies(&fk_dataset.dataset_id)
+            .await
+            .int_err()?
+            .collect()
+            .await;
+
+        Ok(input_ids.iter().all(|id| updated_inputs.contains(id)))
+    }
+
     fn indicate_throttling_activity(
         &self,
         flow: &mut Flow,
@@ -354,6 +354,7 @@ async fn test_cron_config() {
                           FlowTriggerRule::Schedule(Schedule::Cron(ScheduleCron {
                             source_5component_cron_expression: String::from("<irrelevant>"),
                             cron_schedule: cron::Schedule::from_str("*/5 * * * * *").unwrap(),
+                            time_zone: None,
                           })),
                         )
                         .await;
@@ -195,6 +195,13 @@ impl std::fmt::Display for FlowSystemTestListener {
                                 b.active_batching_rule.min_records_to_await(),
                                 (b.batching_deadline - initial_time).num_milliseconds(),
                             )?,
+                            FlowStartCondition::Window(w) => {
+                                write!(
+                                    f,
+                                    " Window(wakeup={}ms)",
+                                    (w.wake_up_at - initial_time).num_milliseconds(),
+                                )?;
+                            }
                             FlowStartCondition::Executor(e) => {
                                 write!(
                                     f,
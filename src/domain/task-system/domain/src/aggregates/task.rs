@@ -24,6 +24,7 @@ impl Task {
         task_id: TaskID,
         logical_plan: LogicalPlan,
         metadata: Option<TaskMetadata>,
+        scheduling_options: TaskSchedulingOptions,
     ) -> Self {
         Self(
             Aggregate::new(
@@ -33,6 +34,8 @@ impl Task {
                     task_id,
                     logical_plan,
                     metadata,
+                    priority: scheduling_options.priority,
+                    account_id: scheduling_options.account_id,
                 },
             )
             .unwrap(),
@@ -8,6 +8,7 @@
 // by the Apache License, Version 2.0.
 
 use enum_variants::*;
+use kamu_core::ReprocessStart;
 use serde::{Deserialize, Serialize};
 
 use crate::TaskOutcome;
@@ -26,9 +27,20 @@ pub enum LogicalPlan {
     HardCompactDataset(LogicalPlanHardCompactDataset),
     /// Perform a dataset resetting
     ResetDataset(LogicalPlanResetDataset),
+    /// Perform a reprocessing of a derivative dataset
+    ReprocessDataset(LogicalPlanReprocessDataset),
 }
 
 impl LogicalPlan {
+    /// Names of all plan kinds, as returned by [`LogicalPlan::kind`]
+    pub const KINDS: &'static [&'static str] = &[
+        "UpdateDataset",
+        "Probe",
+        "HardCompactDataset",
+        "ResetDataset",
+        "ReprocessDataset",
+    ];
+
     /// Returns the dataset ID this plan operates on if any
     pub fn dataset_id(&self) -> Option<&odf::DatasetID> {
         match self {
@@ -36,6 +48,19 @@ impl LogicalPlan {
             LogicalPlan::Probe(p) => p.dataset_id.as_ref(),
             LogicalPlan::HardCompactDataset(hard_compaction) => Some(&hard_compaction.dataset_id),
             LogicalPlan::ResetDataset(reset) => Some(&reset.dataset_id),
+            LogicalPlan::ReprocessDataset(reprocess) => Some(&reprocess.dataset_id),
+        }
+    }
+
+    /// Returns the name of the plan kind, used to apply per-kind concurrency
+    /// limits when executing tasks
+    pub fn kind(&self) -> &'static str {
+        match self {
+            LogicalPlan::UpdateDataset(_) => "UpdateDataset",
+            LogicalPlan::Probe(_) => "Probe",
+            LogicalPlan::HardCompactDataset(_) => "HardCompactDataset",
+            LogicalPlan::ResetDataset(_) => "ResetDataset",
+            LogicalPlan::ReprocessDataset(_) => "ReprocessDataset",
         }
     }
 }
@@ -86,11 +111,27 @@ pub struct LogicalPlanResetDataset {
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
 
+/// A task to recompute the output of a derivative dataset, committing the
+/// difference with the existing output as retractions and corrections
+#[serde_with::serde_as]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LogicalPlanReprocessDataset {
+    pub dataset_id: odf::DatasetID,
+    pub start: Option<ReprocessStart>,
+    #[serde_as(as = "Option<odf::serde::yaml::TransformDef>")]
+    pub new_transform: Option<odf::metadata::Transform>,
+    #[serde(default)]
+    pub primary_key: Vec<String>,
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
 // TODO: Replace with derive macro
 impl_enum_with_variants!(LogicalPlan);
 impl_enum_variant!(LogicalPlan::UpdateDataset(LogicalPlanUpdateDataset));
 impl_enum_variant!(LogicalPlan::Probe(LogicalPlanProbe));
 impl_enum_variant!(LogicalPlan::ResetDataset(LogicalPlanResetDataset));
+impl_enum_variant!(LogicalPlan::ReprocessDataset(LogicalPlanReprocessDataset));
 impl_enum_variant!(LogicalPlan::HardCompactDataset(
     LogicalPlanHardCompactDataset
 ));
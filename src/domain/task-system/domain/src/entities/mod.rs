@@ -10,13 +10,19 @@
 mod logical_plan;
 mod task_event;
 mod task_id;
+mod task_lease;
+mod task_log;
 mod task_metadata;
+mod task_priority;
 mod task_state;
 mod task_status;
 
 pub use logical_plan::*;
 pub use task_event::*;
 pub use task_id::*;
+pub use task_lease::*;
+pub use task_log::*;
 pub use task_metadata::*;
+pub use task_priority::*;
 pub use task_state::*;
 pub use task_status::*;
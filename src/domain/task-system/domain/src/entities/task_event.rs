@@ -39,6 +39,10 @@ pub struct TaskEventCreated {
     pub task_id: TaskID,
     pub logical_plan: LogicalPlan,
     pub metadata: Option<TaskMetadata>,
+    #[serde(default)]
+    pub priority: TaskPriority,
+    #[serde(default)]
+    pub account_id: Option<odf::AccountID>,
 }
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
@@ -0,0 +1,25 @@
+// Copyright Kamu Data, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use chrono::{DateTime, Utc};
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+/// Claim of a running task by a worker. Worker is expected to periodically
+/// extend the lease while the task is executing; tasks with expired leases
+/// are considered abandoned and get re-queued
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TaskLease {
+    /// Identity of the worker executing the task
+    pub worker_id: String,
+    /// Moment after which the task may be re-queued
+    pub expires_at: DateTime<Utc>,
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
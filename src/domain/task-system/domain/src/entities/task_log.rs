@@ -0,0 +1,140 @@
+// Copyright Kamu Data, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::path::Path;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+/// Source of log entries produced by the task system itself
+pub const TASK_LOG_SOURCE_TASK: &str = "task";
+
+/// Source of log entries copied from tracing events emitted while running a
+/// task
+pub const TASK_LOG_SOURCE_TRACING: &str = "tracing";
+
+/// Maximum number of trailing lines captured from a single log file
+pub const TASK_LOG_MAX_LINES_PER_FILE: usize = 10_000;
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+/// Single line of the log captured while running a task
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TaskLogEntry {
+    pub event_time: DateTime<Utc>,
+    /// Either [`TASK_LOG_SOURCE_TASK`], [`TASK_LOG_SOURCE_TRACING`] or the
+    /// name of a captured log file (e.g. `engine.stderr.txt`)
+    pub source: String,
+    pub message: String,
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+/// Accumulates log entries during a single run of a task
+#[derive(Debug, Default)]
+pub struct TaskRunLog {
+    entries: Vec<TaskLogEntry>,
+}
+
+impl TaskRunLog {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn entries(&self) -> &[TaskLogEntry] {
+        &self.entries
+    }
+
+    pub fn into_entries(self) -> Vec<TaskLogEntry> {
+        self.entries
+    }
+
+    /// Merges entries recorded elsewhere, keeping the log ordered by time
+    pub fn merge(&mut self, entries: Vec<TaskLogEntry>) {
+        self.entries.extend(entries);
+        self.entries.sort_by_key(|entry| entry.event_time);
+    }
+
+    /// Records a message of the task system itself, one entry per line
+    pub fn message(&mut self, event_time: DateTime<Utc>, message: impl AsRef<str>) {
+        self.push_lines(event_time, TASK_LOG_SOURCE_TASK, message.as_ref().lines());
+    }
+
+    /// Records an error along with the chain of its causes
+    pub fn error(&mut self, event_time: DateTime<Utc>, error: &(dyn std::error::Error + 'static)) {
+        self.message(event_time, format!("Error: {error}"));
+
+        // Transparent error wrappers repeat the message of their source
+        let mut last_message = error.to_string();
+        let mut source = error.source();
+        while let Some(cause) = source {
+            let message = cause.to_string();
+            if message != last_message {
+                self.message(event_time, format!("Caused by: {message}"));
+                last_message = message;
+            }
+            source = cause.source();
+        }
+    }
+
+    /// Captures contents of a log file written by an engine or a fetch
+    /// container, keeping only the last [`TASK_LOG_MAX_LINES_PER_FILE`] lines
+    pub fn attach_file(&mut self, event_time: DateTime<Utc>, path: &Path) {
+        let source = path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_else(|| path.display().to_string());
+
+        match std::fs::read(path) {
+            Ok(content) => {
+                let content = String::from_utf8_lossy(&content);
+                let num_lines = content.lines().count();
+                self.push_lines(
+                    event_time,
+                    &source,
+                    content
+                        .lines()
+                        .skip(num_lines.saturating_sub(TASK_LOG_MAX_LINES_PER_FILE)),
+                );
+            }
+            Err(e) => self.message(
+                event_time,
+                format!("Failed to capture log file {}: {e}", path.display()),
+            ),
+        }
+    }
+
+    /// Captures contents of all specified log files
+    pub fn attach_files<P: AsRef<Path>>(&mut self, event_time: DateTime<Utc>, paths: &[P]) {
+        for path in paths {
+            self.attach_file(event_time, path.as_ref());
+        }
+    }
+
+    fn push_lines<'a>(
+        &mut self,
+        event_time: DateTime<Utc>,
+        source: &str,
+        lines: impl Iterator<Item = &'a str>,
+    ) {
+        self.entries.extend(lines.map(|line| TaskLogEntry {
+            event_time,
+            source: source.to_string(),
+            message: line.to_string(),
+        }));
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
@@ -0,0 +1,61 @@
+// Copyright Kamu Data, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use serde::{Deserialize, Serialize};
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+/// Determines the order in which queued tasks of the same account are taken.
+/// Variants are declared from the lowest to the highest priority.
+#[derive(
+    Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+pub enum TaskPriority {
+    /// Task was triggered by an update of an input dataset
+    Cascaded,
+    /// Task was triggered by a schedule or by a push of new data
+    #[default]
+    Scheduled,
+    /// Task was requested explicitly by a user
+    Manual,
+}
+
+impl TaskPriority {
+    /// Numeric representation used by the repositories, greater is more urgent
+    pub fn rank(self) -> i16 {
+        match self {
+            Self::Cascaded => 0,
+            Self::Scheduled => 1,
+            Self::Manual => 2,
+        }
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+/// Parameters that affect how a task competes with others in the queue
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct TaskSchedulingOptions {
+    pub priority: TaskPriority,
+    /// Account whose share of workers the task consumes, tasks not attributed
+    /// to any account share a single common pool
+    pub account_id: Option<odf::AccountID>,
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+/// Number of tasks of a single account waiting in the queue or being executed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TaskQueueDepth {
+    pub account_id: Option<odf::AccountID>,
+    pub queued_count: usize,
+    pub running_count: usize,
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
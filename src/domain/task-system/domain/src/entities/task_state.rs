@@ -27,6 +27,10 @@ pub struct TaskState {
     pub logical_plan: LogicalPlan,
     /// Optional associated metadata
     pub metadata: TaskMetadata,
+    /// Priority of the task among queued tasks of the same account
+    pub priority: TaskPriority,
+    /// Account the task is attributed to for fair scheduling
+    pub account_id: Option<odf::AccountID>,
 
     /// Time when task was originally created and placed in a queue
     pub created_at: DateTime<Utc>,
@@ -67,12 +71,16 @@ impl Projection for TaskState {
                     task_id,
                     logical_plan,
                     metadata,
+                    priority,
+                    account_id,
                 }) => Ok(Self {
                     task_id,
                     outcome: None,
                     cancellation_requested: false,
                     logical_plan,
                     metadata: metadata.unwrap_or_default(),
+                    priority,
+                    account_id,
                     created_at: event_time,
                     ran_at: None,
                     cancellation_requested_at: None,
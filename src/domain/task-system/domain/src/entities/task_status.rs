@@ -7,7 +7,7 @@
 // the Business Source License, use of this software will be governed
 // by the Apache License, Version 2.0.
 
-use kamu_core::{CompactionResult, PullResult, ResetResult};
+use kamu_core::{CompactionResult, PullResult, ReprocessResult, ResetResult};
 use serde::{Deserialize, Serialize};
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
@@ -55,6 +55,34 @@ pub enum TaskResult {
     UpdateDatasetResult(TaskUpdateDatasetResult),
     ResetDatasetResult(TaskResetDatasetResult),
     CompactionDatasetResult(TaskCompactionDatasetResult),
+    ReprocessDatasetResult(TaskReprocessDatasetResult),
+}
+
+impl TaskResult {
+    /// Previous and new head of the dataset, if the task has moved it
+    pub fn dataset_head_update(&self) -> Option<(Option<&odf::Multihash>, &odf::Multihash)> {
+        match self {
+            Self::Empty => None,
+            Self::UpdateDatasetResult(r) => match &r.pull_result {
+                PullResult::UpToDate(_) => None,
+                PullResult::Updated { old_head, new_head } => Some((old_head.as_ref(), new_head)),
+            },
+            // Previous head is not tracked by resets
+            Self::ResetDatasetResult(r) => Some((None, &r.reset_result.new_head)),
+            Self::CompactionDatasetResult(r) => match &r.compaction_result {
+                CompactionResult::Success {
+                    old_head, new_head, ..
+                } => Some((Some(old_head), new_head)),
+                CompactionResult::NothingToDo | CompactionResult::DataSlicesMerged { .. } => None,
+            },
+            Self::ReprocessDatasetResult(r) => match &r.reprocess_result {
+                ReprocessResult::UpToDate => None,
+                ReprocessResult::Updated {
+                    old_head, new_head, ..
+                } => Some((Some(old_head), new_head)),
+            },
+        }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -80,6 +108,11 @@ impl From<CompactionResult> for TaskCompactionDatasetResult {
     }
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TaskReprocessDatasetResult {
+    pub reprocess_result: ReprocessResult,
+}
+
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
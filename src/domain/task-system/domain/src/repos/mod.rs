@@ -8,5 +8,7 @@
 // by the Apache License, Version 2.0.
 
 mod task_event_store;
+mod task_log_store;
 
 pub use task_event_store::*;
+pub use task_log_store::*;
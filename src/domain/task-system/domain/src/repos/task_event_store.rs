@@ -7,6 +7,7 @@
 // the Business Source License, use of this software will be governed
 // by the Apache License, Version 2.0.
 
+use chrono::{DateTime, Utc};
 use database_common::PaginationOpts;
 use event_sourcing::EventStore;
 
@@ -19,8 +20,22 @@ pub trait TaskEventStore: EventStore<TaskState> {
     /// Generates new unique task identifier
     async fn new_task_id(&self) -> Result<TaskID, InternalError>;
 
-    /// Attempts to get the earliest queued task, if any
-    async fn try_get_queued_task(&self) -> Result<Option<TaskID>, InternalError>;
+    /// Attempts to get the next queued task, if any, skipping tasks of the
+    /// specified logical plan kinds. Tasks of accounts with the fewest running
+    /// tasks go first, then tasks of higher priority, then the earliest ones
+    async fn try_get_queued_task(
+        &self,
+        excluded_plan_kinds: &[&str],
+    ) -> Result<Option<TaskID>, InternalError>;
+
+    /// Assigns or extends the lease of a running task on behalf of a worker.
+    /// Returns `false` if the task is no longer running or is leased by
+    /// another worker
+    async fn save_task_lease(
+        &self,
+        task_id: TaskID,
+        lease: &TaskLease,
+    ) -> Result<bool, InternalError>;
 
     /// Returns list of tasks, which are in Running state,
     /// from earliest to latest
@@ -29,6 +44,26 @@ pub trait TaskEventStore: EventStore<TaskState> {
     /// Returns total number of tasks, which are in Running state
     async fn get_count_running_tasks(&self) -> Result<usize, InternalError>;
 
+    /// Returns the number of queued and running tasks per account, for the
+    /// accounts that have any
+    async fn get_queue_depth_by_account(&self) -> Result<Vec<TaskQueueDepth>, InternalError>;
+
+    /// Returns list of tasks, which are in Running state, but whose lease
+    /// has expired by the specified moment or was never assigned,
+    /// from earliest to latest
+    fn get_running_tasks_with_expired_lease(
+        &self,
+        now: DateTime<Utc>,
+        pagination: PaginationOpts,
+    ) -> TaskIDStream;
+
+    /// Returns total number of tasks, which are in Running state, but whose
+    /// lease has expired by the specified moment or was never assigned
+    async fn get_count_running_tasks_with_expired_lease(
+        &self,
+        now: DateTime<Utc>,
+    ) -> Result<usize, InternalError>;
+
     /// Returns page of the tasks associated with the specified dataset in
     /// reverse chronological order based on creation time
     /// Note: no longer used, but might be used in future (admin view)
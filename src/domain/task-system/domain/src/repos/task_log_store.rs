@@ -0,0 +1,56 @@
+// Copyright Kamu Data, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use chrono::{DateTime, Utc};
+use database_common::{EntityPageListing, PaginationOpts};
+use url::Url;
+
+use crate::*;
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+/// Storage of logs captured while running tasks
+#[async_trait::async_trait]
+pub trait TaskLogStore: Send + Sync {
+    /// Appends entries to the log of the specified task
+    async fn append_task_log(
+        &self,
+        task_id: TaskID,
+        entries: &[TaskLogEntry],
+    ) -> Result<(), InternalError>;
+
+    /// Returns a page of log entries of the specified task in the order they
+    /// were recorded. Tasks without captured logs have an empty log
+    async fn get_task_log(
+        &self,
+        task_id: TaskID,
+        pagination: PaginationOpts,
+    ) -> Result<EntityPageListing<TaskLogEntry>, InternalError>;
+
+    /// Removes logs of tasks that were last written before the specified
+    /// moment, returning the number of removed logs
+    async fn delete_task_logs_older_than(
+        &self,
+        cutoff: DateTime<Utc>,
+    ) -> Result<usize, InternalError>;
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[derive(Debug, Clone)]
+pub struct TaskLogsConfig {
+    /// Location of stored logs: a `file://` directory or an `s3://` prefix
+    pub storage_url: Url,
+    /// For how long logs are kept, `None` keeps them forever
+    pub retention_period: Option<chrono::Duration>,
+    /// Interval between removals of expired logs
+    pub cleanup_interval: chrono::Duration,
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
@@ -7,6 +7,8 @@
 // the Business Source License, use of this software will be governed
 // by the Apache License, Version 2.0.
 
+use std::collections::HashMap;
+
 use crate::*;
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
@@ -27,14 +29,100 @@ pub struct TaskAgentConfig {
     /// Defines interval between task executor checks whether there are any new
     /// tasks
     pub task_checking_interval: chrono::Duration,
+    /// Maximum number of tasks executed by the agent simultaneously.
+    /// Zero disables task execution in this agent, leaving it to standalone
+    /// workers, while still recovering abandoned tasks.
+    pub max_concurrent_tasks: usize,
+    /// Additional limits of simultaneously executed tasks per logical plan
+    /// kind (e.g. `UpdateDataset`)
+    pub max_concurrent_tasks_per_plan_kind: HashMap<String, usize>,
+    /// For how long a running task remains leased to the agent without a
+    /// heartbeat before it is considered abandoned and gets re-queued
+    pub task_lease_duration: chrono::Duration,
+    /// Interval between heartbeats extending the leases of running tasks
+    pub task_heartbeat_interval: chrono::Duration,
 }
 
 impl TaskAgentConfig {
+    pub const DEFAULT_MAX_CONCURRENT_TASKS: usize = 1;
+    pub const DEFAULT_TASK_LEASE_DURATION_SECS: i64 = 60;
+    pub const DEFAULT_TASK_HEARTBEAT_INTERVAL_SECS: i64 = 15;
+
     pub fn new(task_checking_interval: chrono::Duration) -> Self {
         Self {
             task_checking_interval,
+            max_concurrent_tasks: Self::DEFAULT_MAX_CONCURRENT_TASKS,
+            max_concurrent_tasks_per_plan_kind: HashMap::new(),
+            task_lease_duration: chrono::Duration::seconds(Self::DEFAULT_TASK_LEASE_DURATION_SECS),
+            task_heartbeat_interval: chrono::Duration::seconds(
+                Self::DEFAULT_TASK_HEARTBEAT_INTERVAL_SECS,
+            ),
         }
     }
+
+    pub fn with_max_concurrent_tasks(
+        mut self,
+        max_concurrent_tasks: usize,
+        max_concurrent_tasks_per_plan_kind: HashMap<String, usize>,
+    ) -> Self {
+        self.max_concurrent_tasks = max_concurrent_tasks;
+        self.max_concurrent_tasks_per_plan_kind = max_concurrent_tasks_per_plan_kind;
+        self
+    }
+
+    pub fn with_task_lease(
+        mut self,
+        task_lease_duration: chrono::Duration,
+        task_heartbeat_interval: chrono::Duration,
+    ) -> Self {
+        self.task_lease_duration = task_lease_duration;
+        self.task_heartbeat_interval = task_heartbeat_interval;
+        self
+    }
+
+    /// Checks values that come from user configuration
+    pub fn validate(&self) -> Result<(), InvalidTaskAgentConfigError> {
+        if self.task_heartbeat_interval >= self.task_lease_duration {
+            return Err(InvalidTaskAgentConfigError::HeartbeatIntervalTooLong {
+                task_heartbeat_interval: self.task_heartbeat_interval,
+                task_lease_duration: self.task_lease_duration,
+            });
+        }
+
+        let mut plan_kinds: Vec<_> = self.max_concurrent_tasks_per_plan_kind.keys().collect();
+        plan_kinds.sort();
+
+        if let Some(plan_kind) = plan_kinds
+            .into_iter()
+            .find(|plan_kind| !LogicalPlan::KINDS.contains(&plan_kind.as_str()))
+        {
+            return Err(InvalidTaskAgentConfigError::UnknownPlanKind {
+                plan_kind: plan_kind.clone(),
+            });
+        }
+
+        Ok(())
+    }
 }
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[derive(thiserror::Error, Debug)]
+pub enum InvalidTaskAgentConfigError {
+    #[error(
+        "Task heartbeat interval ({task_heartbeat_interval}) must be shorter than the task lease \
+         duration ({task_lease_duration})"
+    )]
+    HeartbeatIntervalTooLong {
+        task_heartbeat_interval: chrono::Duration,
+        task_lease_duration: chrono::Duration,
+    },
+
+    #[error(
+        "Unknown logical plan kind '{plan_kind}' in task concurrency limits, expected one of: {}",
+        LogicalPlan::KINDS.join(", ")
+    )]
+    UnknownPlanKind { plan_kind: String },
+}
+
+////
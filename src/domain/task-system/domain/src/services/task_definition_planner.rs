@@ -8,7 +8,14 @@
 // by the Apache License, Version 2.0.
 
 use internal_error::InternalError;
-use kamu_core::{CompactionPlan, PullOptions, PullPlanIterationJob, ResetPlan, ResolvedDataset};
+use kamu_core::{
+    CompactionPlan,
+    PullOptions,
+    PullPlanIterationJob,
+    ReprocessPlan,
+    ResetPlan,
+    ResolvedDataset,
+};
 
 use crate::{LogicalPlan, LogicalPlanProbe};
 
@@ -30,6 +37,7 @@ pub enum TaskDefinition {
     Update(TaskDefinitionUpdate),
     Reset(TaskDefinitionReset),
     HardCompact(TaskDefinitionHardCompact),
+    Reprocess(TaskDefinitionReprocess),
 }
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
@@ -64,3 +72,11 @@ pub struct TaskDefinitionHardCompact {
 }
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[derive(Debug)]
+pub struct TaskDefinitionReprocess {
+    pub target: ResolvedDataset,
+    pub reprocess_plan: ReprocessPlan,
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
@@ -10,14 +10,19 @@
 use event_sourcing::InternalError;
 
 use super::TaskDefinition;
-use crate::TaskOutcome;
+use crate::{TaskOutcome, TaskRunLog};
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
 
 #[async_trait::async_trait]
 pub trait TaskRunner: Send + Sync {
-    async fn run_task(&self, task_definition: TaskDefinition)
-        -> Result<TaskOutcome, InternalError>;
+    /// Runs the task, recording details of its execution (e.g. engine logs of
+    /// a failed update) into the provided log
+    async fn run_task(
+        &self,
+        task_definition: TaskDefinition,
+        task_log: &mut TaskRunLog,
+    ) -> Result<TaskOutcome, InternalError>;
 }
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
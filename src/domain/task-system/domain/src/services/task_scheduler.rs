@@ -21,6 +21,7 @@ pub trait TaskScheduler: Sync + Send {
         &self,
         plan: LogicalPlan,
         metadata: Option<TaskMetadata>,
+        scheduling_options: TaskSchedulingOptions,
     ) -> Result<TaskState, CreateTaskError>;
 
     /// Returns current state of a given task
@@ -29,8 +30,20 @@ pub trait TaskScheduler: Sync + Send {
     /// Attempts to cancel the given task
     async fn cancel_task(&self, task_id: TaskID) -> Result<TaskState, CancelTaskError>;
 
-    /// Takes the earliest available queued task, if any, without blocking
-    async fn try_take(&self) -> Result<Option<Task>, TakeTaskError>;
+    /// Takes the next available queued task, if any, without blocking.
+    /// Accounts with the fewest running tasks are served first, then tasks of
+    /// higher priority, and then the earliest ones.
+    /// The task gets leased to the specified worker for the given duration.
+    /// Tasks of the excluded logical plan kinds are skipped.
+    async fn try_take(
+        &self,
+        worker_id: &str,
+        lease_duration: chrono::Duration,
+        excluded_plan_kinds: &[&str],
+    ) -> Result<Option<Task>, TakeTaskError>;
+
+    /// Returns the number of queued and running tasks per account
+    async fn get_queue_depth(&self) -> Result<Vec<TaskQueueDepth>, InternalError>;
 }
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
@@ -18,6 +18,8 @@ pub fn register_dependencies(catalog_builder: &mut CatalogBuilder) {
     catalog_builder.add::<TaskSchedulerImpl>();
     catalog_builder.add::<TaskDefinitionPlannerImpl>();
     catalog_builder.add::<TaskRunnerImpl>();
+    catalog_builder.add::<TaskLogStoreImpl>();
+    catalog_builder.add::<TaskLogCleanupAgent>();
 }
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
@@ -13,11 +13,17 @@ pub use kamu_task_system as domain;
 mod dependencies;
 mod task_agent_impl;
 mod task_definition_planner_impl;
+mod task_log_capture;
+mod task_log_cleanup_agent;
+mod task_log_store_impl;
 mod task_runner_impl;
 mod task_scheduler_impl;
 
 pub use dependencies::*;
 pub use task_agent_impl::*;
 pub use task_definition_planner_impl::*;
+pub use task_log_capture::*;
+pub use task_log_cleanup_agent::*;
+pub use task_log_store_impl::*;
 pub use task_runner_impl::*;
 pub use task_scheduler_impl::*;
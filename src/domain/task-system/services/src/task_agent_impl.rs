@@ -7,24 +7,31 @@
 // the Business Source License, use of this software will be governed
 // by the Apache License, Version 2.0.
 
+use std::collections::HashMap;
 use std::sync::Arc;
 
 use database_common::{DatabaseTransactionRunner, PaginationOpts};
 use database_common_macros::{transactional_method1, transactional_method2};
 use dill::*;
+use futures::{StreamExt, TryStreamExt};
 use init_on_startup::{InitOnStartup, InitOnStartupMeta};
+use kamu_datasets::{DatasetReferenceMessage, MESSAGE_PRODUCER_KAMU_DATASET_REFERENCE_SERVICE};
 use kamu_task_system::*;
 use messaging_outbox::{Outbox, OutboxExt};
 use time_source::SystemTimeSource;
 use tracing::Instrument as _;
 
+use crate::capture_task_log;
+
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
 
 pub struct TaskAgentImpl {
     catalog: Catalog,
     task_runner: Arc<dyn TaskRunner>,
+    task_log_store: Arc<dyn TaskLogStore>,
     time_source: Arc<dyn SystemTimeSource>,
     agent_config: Arc<TaskAgentConfig>,
+    worker_id: String,
 }
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
@@ -42,68 +49,111 @@ impl TaskAgentImpl {
     pub fn new(
         catalog: Catalog,
         task_runner: Arc<dyn TaskRunner>,
+        task_log_store: Arc<dyn TaskLogStore>,
         time_source: Arc<dyn SystemTimeSource>,
         agent_config: Arc<TaskAgentConfig>,
     ) -> Self {
         Self {
             catalog,
             task_runner,
+            task_log_store,
             time_source,
             agent_config,
+            worker_id: format!("task-agent-{}", uuid::Uuid::new_v4()),
         }
     }
 
     async fn run_task_iteration(&self) -> Result<(), InternalError> {
         let task = self.take_task().await?;
+        self.execute_task(task).await
+    }
 
-        let task_outcome = self
-            .run_task(&task)
+    async fn execute_task(&self, task: Task) -> Result<(), InternalError> {
+        let maybe_task_outcome = self
+            .run_task_with_heartbeats(&task)
             .instrument(observability::tracing::root_span!(
                 "TaskAgent::run_task",
                 task_id = %task.task_id,
             ))
             .await?;
 
+        // The task was interrupted after losing its lease, it will be re-run
+        // by whichever agent takes it next
+        let Some(task_outcome) = maybe_task_outcome else {
+            return Ok(());
+        };
+
         self.process_task_outcome(task, task_outcome).await?;
 
         Ok(())
     }
 
+    /// Returns plan kinds that reached their concurrency limit
+    fn get_saturated_plan_kinds(
+        &self,
+        running_tasks_per_plan_kind: &HashMap<&'static str, usize>,
+    ) -> Vec<&str> {
+        self.agent_config
+            .max_concurrent_tasks_per_plan_kind
+            .iter()
+            .filter(|(plan_kind, limit)| {
+                running_tasks_per_plan_kind
+                    .get(plan_kind.as_str())
+                    .copied()
+                    .unwrap_or_default()
+                    >= **limit
+            })
+            .map(|(plan_kind, _)| plan_kind.as_str())
+            .collect()
+    }
+
     #[transactional_method1(task_event_store: Arc<dyn TaskEventStore>)]
     async fn recover_running_tasks(&self) -> Result<(), InternalError> {
         // Recovering tasks means we are re-queuing tasks that started running, but got
-        // aborted due to server shutdown or crash
+        // aborted due to server shutdown or crash, so their lease was not extended
+
+        let now = self.time_source.now();
 
-        // Total number of running tasks
-        let total_running_tasks = task_event_store.get_count_running_tasks().await?;
+        // Total number of abandoned running tasks
+        let total_abandoned_tasks = task_event_store
+            .get_count_running_tasks_with_expired_lease(now)
+            .await?;
 
-        // Process them in pages
-        let mut processed_running_tasks = 0;
-        while processed_running_tasks < total_running_tasks {
+        // Process them in pages. Requeued tasks leave the listing, so we always
+        // read the first page
+        let mut processed_abandoned_tasks = 0;
+        while processed_abandoned_tasks < total_abandoned_tasks {
             // Load another page
-            use futures::TryStreamExt;
-            let running_task_ids: Vec<_> = task_event_store
-                .get_running_tasks(PaginationOpts {
-                    offset: processed_running_tasks,
-                    limit: 100,
-                })
+            let abandoned_task_ids: Vec<_> = task_event_store
+                .get_running_tasks_with_expired_lease(
+                    now,
+                    PaginationOpts {
+                        offset: 0,
+                        limit: 100,
+                    },
+                )
                 .try_collect()
                 .await?;
-            let batch_size = running_task_ids.len();
+            let batch_size = abandoned_task_ids.len();
+            if batch_size == 0 {
+                break;
+            }
 
-            let tasks = Task::load_multi(running_task_ids, task_event_store.as_ref())
+            let tasks = Task::load_multi(abandoned_task_ids, task_event_store.as_ref())
                 .await
                 .int_err()?;
 
             for task in tasks {
                 let mut t = task.int_err()?;
 
+                tracing::warn!(task_id = %t.task_id, "Re-queuing abandoned task");
+
                 // Requeue
-                t.requeue(self.time_source.now()).int_err()?;
+                t.requeue(now).int_err()?;
                 t.save(task_event_store.as_ref()).await.int_err()?;
             }
 
-            processed_running_tasks += batch_size;
+            processed_abandoned_tasks += batch_size;
         }
 
         Ok(())
@@ -111,7 +161,7 @@ impl TaskAgentImpl {
 
     async fn take_task(&self) -> Result<Task, InternalError> {
         loop {
-            let maybe_task = self.take_task_non_blocking().await?;
+            let maybe_task = self.take_task_non_blocking(&[]).await?;
 
             if let Some(task) = maybe_task {
                 return Ok(task);
@@ -124,8 +174,18 @@ impl TaskAgentImpl {
     }
 
     #[transactional_method2(task_scheduler: Arc<dyn TaskScheduler>, outbox: Arc<dyn Outbox>)]
-    async fn take_task_non_blocking(&self) -> Result<Option<Task>, InternalError> {
-        let maybe_task = task_scheduler.try_take().await.int_err()?;
+    async fn take_task_non_blocking(
+        &self,
+        excluded_plan_kinds: &[&str],
+    ) -> Result<Option<Task>, InternalError> {
+        let maybe_task = task_scheduler
+            .try_take(
+                &self.worker_id,
+                self.agent_config.task_lease_duration,
+                excluded_plan_kinds,
+            )
+            .await
+            .int_err()?;
         let Some(task) = maybe_task else {
             return Ok(None);
         };
@@ -146,7 +206,117 @@ impl TaskAgentImpl {
         Ok(Some(task))
     }
 
+    /// Runs the task while extending its lease. Returns `None` if the lease was
+    /// lost, in which case the task is interrupted
+    async fn run_task_with_heartbeats(
+        &self,
+        task: &Task,
+    ) -> Result<Option<TaskOutcome>, InternalError> {
+        let mut run_task_future = std::pin::pin!(self.run_task(task));
+
+        let mut lease_expires_at = self.time_source.now() + self.agent_config.task_lease_duration;
+
+        // Keep extending the lease while the task is running, so that it is not
+        // considered abandoned by other agents
+        loop {
+            tokio::select! {
+                task_outcome = &mut run_task_future => return task_outcome.map(Some),
+                _ = self.time_source.sleep(self.agent_config.task_heartbeat_interval) => {
+                    let lease = self.make_task_lease();
+
+                    match self.extend_task_lease(task.task_id, &lease).await {
+                        Ok(true) => lease_expires_at = lease.expires_at,
+                        Ok(false) => {
+                            tracing::warn!(
+                                task_id = %task.task_id,
+                                worker_id = %self.worker_id,
+                                "Lease of a running task was lost, interrupting the task",
+                            );
+                            return Ok(None);
+                        }
+                        // Failed heartbeats are retried until the lease expires
+                        Err(e) if self.time_source.now() < lease_expires_at => {
+                            tracing::warn!(
+                                task_id = %task.task_id,
+                                error = ?e,
+                                error_msg = %e,
+                                "Failed to extend the lease of a running task, will retry",
+                            );
+                        }
+                        Err(e) => {
+                            tracing::error!(
+                                task_id = %task.task_id,
+                                error = ?e,
+                                error_msg = %e,
+                                "Lease of a running task expired, interrupting the task",
+                            );
+                            return Ok(None);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    fn make_task_lease(&self) -> TaskLease {
+        TaskLease {
+            worker_id: self.worker_id.clone(),
+            expires_at: self.time_source.now() + self.agent_config.task_lease_duration,
+        }
+    }
+
+    #[transactional_method1(task_event_store: Arc<dyn TaskEventStore>)]
+    async fn extend_task_lease(
+        &self,
+        task_id: TaskID,
+        lease: &TaskLease,
+    ) -> Result<bool, InternalError> {
+        task_event_store.save_task_lease(task_id, lease).await
+    }
+
     async fn run_task(&self, task: &Task) -> Result<TaskOutcome, InternalError> {
+        let mut task_log = TaskRunLog::new();
+        task_log.message(
+            self.time_source.now(),
+            format!("Running task on worker {}", self.worker_id),
+        );
+
+        let (task_outcome, captured_entries) =
+            capture_task_log(self.run_task_logged(task, &mut task_log)).await;
+        task_log.merge(captured_entries);
+
+        match &task_outcome {
+            Ok(task_outcome) => {
+                task_log.message(
+                    self.time_source.now(),
+                    format!("Task finished: {task_outcome:?}"),
+                );
+            }
+            Err(e) => task_log.error(self.time_source.now(), e),
+        }
+
+        // Losing the logs should not affect the outcome of the task
+        if let Err(e) = self
+            .task_log_store
+            .append_task_log(task.task_id, task_log.entries())
+            .await
+        {
+            tracing::warn!(
+                task_id = %task.task_id,
+                error = ?e,
+                error_msg = %e,
+                "Failed to save task log",
+            );
+        }
+
+        task_outcome
+    }
+
+    async fn run_task_logged(
+        &self,
+        task: &Task,
+        task_log: &mut TaskRunLog,
+    ) -> Result<TaskOutcome, InternalError> {
         tracing::debug!(
             task_id = %task.task_id,
             logical_plan = ?task.logical_plan,
@@ -172,12 +342,13 @@ impl TaskAgentImpl {
                     error_msg = %e,
                     "Task definition preparation failed"
                 );
+                task_log.error(self.time_source.now(), &e);
                 return Ok(TaskOutcome::Failed(TaskError::Empty));
             }
         };
 
         // Run task via definition
-        let task_run_result = self.task_runner.run_task(task_definition).await;
+        let task_run_result = self.task_runner.run_task(task_definition, task_log).await;
 
         // Deal with errors: we should not interrupt the main loop if task fails
         let task_outcome = match task_run_result {
@@ -190,6 +361,7 @@ impl TaskAgentImpl {
                     error_msg = %e,
                     "Task run failed"
                 );
+                task_log.error(self.time_source.now(), &e);
                 TaskOutcome::Failed(TaskError::Empty)
             }
         };
@@ -210,12 +382,45 @@ impl TaskAgentImpl {
         mut task: Task,
         task_outcome: TaskOutcome,
     ) -> Result<(), InternalError> {
+        // Only the lease holder may record the outcome: the task might have been
+        // re-queued and taken by another agent since the last heartbeat
+        let lease = self.make_task_lease();
+        if !event_store.save_task_lease(task.task_id, &lease).await? {
+            tracing::warn!(
+                task_id = %task.task_id,
+                worker_id = %self.worker_id,
+                ?task_outcome,
+                "Discarding outcome of a task whose lease was lost",
+            );
+            return Ok(());
+        }
+
         // Refresh the task in case it was updated concurrently (e.g. late cancellation)
         task.update(event_store.as_ref()).await.int_err()?;
         task.finish(self.time_source.now(), task_outcome.clone())
             .int_err()?;
         task.save(event_store.as_ref()).await.int_err()?;
 
+        let head_update = match &task_outcome {
+            TaskOutcome::Success(task_result) => task_result.dataset_head_update(),
+            TaskOutcome::Failed(_) | TaskOutcome::Cancelled => None,
+        };
+        if let (Some((old_head, new_head)), Some(dataset_id)) =
+            (head_update, task.logical_plan.dataset_id())
+        {
+            outbox
+                .post_message(
+                    MESSAGE_PRODUCER_KAMU_DATASET_REFERENCE_SERVICE,
+                    DatasetReferenceMessage::updated(
+                        self.time_source.now(),
+                        dataset_id.clone(),
+                        old_head.cloned(),
+                        new_head.clone(),
+                    ),
+                )
+                .await?;
+        }
+
         outbox
             .post_message(
                 MESSAGE_PRODUCER_KAMU_TASK_AGENT,
@@ -236,10 +441,74 @@ impl TaskAgentImpl {
 
 #[async_trait::async_trait]
 impl TaskAgent for TaskAgentImpl {
-    // TODO: Error and panic handling strategy
+    // TODO: Panic handling strategy
     async fn run(&self) -> Result<(), InternalError> {
+        tracing::info!(
+            worker_id = %self.worker_id,
+            max_concurrent_tasks = self.agent_config.max_concurrent_tasks,
+            "Task agent started",
+        );
+
+        let mut running_tasks = futures::stream::FuturesUnordered::new();
+        let mut running_tasks_per_plan_kind: HashMap<&'static str, usize> = HashMap::new();
+        let mut next_recovery_at = self.time_source.now() + self.agent_config.task_lease_duration;
+
         loop {
-            self.run_task_iteration().await?;
+            // Take as many tasks as the concurrency limits allow
+            while running_tasks.len() < self.agent_config.max_concurrent_tasks {
+                let saturated_plan_kinds =
+                    self.get_saturated_plan_kinds(&running_tasks_per_plan_kind);
+
+                let task = match self.take_task_non_blocking(&saturated_plan_kinds).await {
+                    Ok(Some(task)) => task,
+                    Ok(None) => break,
+                    Err(e) => {
+                        tracing::error!(
+                            error = ?e,
+                            error_msg = %e,
+                            "Failed to take the next task, will retry",
+                        );
+                        break;
+                    }
+                };
+
+                let plan_kind = task.logical_plan.kind();
+                *running_tasks_per_plan_kind.entry(plan_kind).or_default() += 1;
+
+                running_tasks.push(async move { (plan_kind, self.execute_task(task).await) });
+            }
+
+            // Wait until either some task finishes, or it's time to check for new tasks
+            tokio::select! {
+                Some((plan_kind, res)) = running_tasks.next() => {
+                    // The task stays leased to this agent until the lease expires, after
+                    // which it gets re-queued by the recovery, so other tasks keep running
+                    if let Err(e) = res {
+                        tracing::error!(
+                            error = ?e,
+                            error_msg = %e,
+                            "Failed to complete a task",
+                        );
+                    }
+                    if let Some(count) = running_tasks_per_plan_kind.get_mut(plan_kind) {
+                        *count -= 1;
+                    }
+                }
+                _ = self.time_source.sleep(self.agent_config.task_checking_interval) => {}
+            }
+
+            // Periodically re-queue tasks abandoned by crashed agents
+            let now = self.time_source.now();
+            if now >= next_recovery_at {
+                if let Err(e) = self.recover_running_tasks().await {
+                    tracing::error!(
+                        error = ?e,
+                        error_msg = %e,
+                        "Failed to re-queue abandoned tasks, will retry",
+                    );
+                }
+                next_recovery_at = now + self.agent_config.task_lease_duration;
+            }
         }
     }
 
@@ -255,6 +524,8 @@ impl TaskAgent for TaskAgentImpl {
 #[async_trait::async_trait]
 impl InitOnStartup for TaskAgentImpl {
     async fn run_initialization(&self) -> Result<(), InternalError> {
+        self.agent_config.validate().int_err()?;
+
         self.recover_running_tasks().await
     }
 }
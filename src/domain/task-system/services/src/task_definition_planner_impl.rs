@@ -24,6 +24,7 @@ pub struct TaskDefinitionPlannerImpl {
     pull_request_planner: Arc<dyn PullRequestPlanner>,
     compaction_planner: Arc<dyn CompactionPlanner>,
     reset_planner: Arc<dyn ResetPlanner>,
+    reprocess_planner: Arc<dyn ReprocessPlanner>,
     tenancy_config: Arc<TenancyConfig>,
 }
 
@@ -38,6 +39,7 @@ impl TaskDefinitionPlannerImpl {
         pull_request_planner: Arc<dyn PullRequestPlanner>,
         compaction_planner: Arc<dyn CompactionPlanner>,
         reset_planner: Arc<dyn ResetPlanner>,
+        reprocess_planner: Arc<dyn ReprocessPlanner>,
         tenancy_config: Arc<TenancyConfig>,
     ) -> Self {
         Self {
@@ -46,6 +48,7 @@ impl TaskDefinitionPlannerImpl {
             pull_request_planner,
             compaction_planner,
             reset_planner,
+            reprocess_planner,
             tenancy_config,
         }
     }
@@ -137,6 +140,35 @@ impl TaskDefinitionPlannerImpl {
         }))
     }
 
+    #[tracing::instrument(level = "debug", skip_all, fields(?args))]
+    async fn plan_reprocess(
+        &self,
+        args: &LogicalPlanReprocessDataset,
+    ) -> Result<TaskDefinition, InternalError> {
+        let target = self
+            .dataset_registry
+            .get_dataset_by_ref(&args.dataset_id.as_local_ref())
+            .await
+            .int_err()?;
+
+        let reprocess_options = ReprocessOptions {
+            start: args.start.clone(),
+            new_transform: args.new_transform.clone(),
+            primary_key: args.primary_key.clone(),
+        };
+
+        let reprocess_plan = self
+            .reprocess_planner
+            .plan_reprocess(target.clone(), reprocess_options)
+            .await
+            .int_err()?;
+
+        Ok(TaskDefinition::Reprocess(TaskDefinitionReprocess {
+            target,
+            reprocess_plan,
+        }))
+    }
+
     #[tracing::instrument(level = "debug", skip_all, fields(?args))]
     async fn plan_hard_compaction(
         &self,
@@ -152,6 +184,7 @@ impl TaskDefinitionPlannerImpl {
             max_slice_size: args.max_slice_size,
             max_slice_records: args.max_slice_records,
             keep_metadata_only: args.keep_metadata_only,
+            ..Default::default()
         };
 
         let compaction_plan = self
@@ -184,6 +217,7 @@ impl TaskDefinitionPlanner for TaskDefinitionPlannerImpl {
             LogicalPlan::HardCompactDataset(compaction) => {
                 self.plan_hard_compaction(compaction).await?
             }
+            LogicalPlan::ReprocessDataset(reprocess) => self.plan_reprocess(reprocess).await?,
         };
 
         Ok(task_definition)
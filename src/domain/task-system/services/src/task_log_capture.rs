@@ -0,0 +1,105 @@
+// Copyright Kamu Data, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::cell::RefCell;
+use std::fmt::Write as _;
+use std::future::Future;
+
+use chrono::Utc;
+use kamu_task_system::{TaskLogEntry, TASK_LOG_SOURCE_TRACING};
+use tracing::field::{Field, Visit};
+use tracing::{Event, Level, Subscriber};
+use tracing_subscriber::layer::Context;
+use tracing_subscriber::Layer;
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+/// Maximum number of tracing events captured during a single run of a task
+pub const TASK_LOG_MAX_CAPTURED_EVENTS: usize = 10_000;
+
+tokio::task_local! {
+    static CAPTURED_TASK_LOG: RefCell<Vec<TaskLogEntry>>;
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+/// Copies tracing events of `INFO` level and above into the log of the task
+/// that is being run by the current tokio task. Events of futures spawned
+/// separately from the task run are not captured.
+///
+/// Has no effect unless added to the application's tracing subscriber.
+pub struct TaskLogCaptureLayer;
+
+impl<S: Subscriber> Layer<S> for TaskLogCaptureLayer {
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        let level = *event.metadata().level();
+        if level > Level::INFO {
+            return;
+        }
+
+        // Outside of a task run
+        let _ = CAPTURED_TASK_LOG.try_with(|captured| {
+            let Ok(mut captured) = captured.try_borrow_mut() else {
+                return;
+            };
+            if captured.len() >= TASK_LOG_MAX_CAPTURED_EVENTS {
+                return;
+            }
+
+            let mut visitor = EventMessageVisitor::default();
+            event.record(&mut visitor);
+
+            captured.push(TaskLogEntry {
+                event_time: Utc::now(),
+                source: TASK_LOG_SOURCE_TRACING.to_string(),
+                message: format!("{level} {}{}", visitor.message, visitor.fields),
+            });
+        });
+    }
+}
+
+/// Runs the future collecting the tracing events it emits, see
+/// [`TaskLogCaptureLayer`]
+pub async fn capture_task_log<F: Future>(future: F) -> (F::Output, Vec<TaskLogEntry>) {
+    CAPTURED_TASK_LOG
+        .scope(RefCell::new(Vec::new()), async move {
+            let output = future.await;
+            let captured = CAPTURED_TASK_LOG.with(RefCell::take);
+            (output, captured)
+        })
+        .await
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[derive(Default)]
+struct EventMessageVisitor {
+    message: String,
+    fields: String,
+}
+
+impl Visit for EventMessageVisitor {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == "message" {
+            self.message.push_str(value);
+        } else {
+            let _ = write!(self.fields, " {}={value}", field.name());
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        if field.name() == "message" {
+            let _ = write!(self.message, "{value:?}");
+        } else {
+            let _ = write!(self.fields, " {}={value:?}", field.name());
+        }
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
@@ -0,0 +1,71 @@
+// Copyright Kamu Data, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::sync::Arc;
+
+use dill::*;
+use internal_error::InternalError;
+use kamu_task_system::{TaskLogStore, TaskLogsConfig};
+use time_source::SystemTimeSource;
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+/// Periodically removes task logs that outlived the retention period
+pub struct TaskLogCleanupAgent {
+    task_log_store: Arc<dyn TaskLogStore>,
+    task_logs_config: Arc<TaskLogsConfig>,
+    time_source: Arc<dyn SystemTimeSource>,
+}
+
+#[component(pub)]
+#[scope(Singleton)]
+impl TaskLogCleanupAgent {
+    pub fn new(
+        task_log_store: Arc<dyn TaskLogStore>,
+        task_logs_config: Arc<TaskLogsConfig>,
+        time_source: Arc<dyn SystemTimeSource>,
+    ) -> Self {
+        Self {
+            task_log_store,
+            task_logs_config,
+            time_source,
+        }
+    }
+
+    pub async fn run(&self) -> Result<(), InternalError> {
+        loop {
+            // A failed cleanup is not fatal for the server, it will be retried
+            if let Err(e) = self.run_cleanup().await {
+                tracing::error!(error = ?e, error_msg = %e, "Expired task logs cleanup failed");
+            }
+
+            self.time_source
+                .sleep(self.task_logs_config.cleanup_interval)
+                .await;
+        }
+    }
+
+    #[tracing::instrument(level = "debug", skip_all)]
+    pub async fn run_cleanup(&self) -> Result<usize, InternalError> {
+        let Some(retention_period) = self.task_logs_config.retention_period else {
+            return Ok(0);
+        };
+
+        let num_removed = self
+            .task_log_store
+            .delete_task_logs_older_than(self.time_source.now() - retention_period)
+            .await?;
+        if num_removed > 0 {
+            tracing::info!(num_removed, "Removed expired task logs");
+        }
+        Ok(num_removed)
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
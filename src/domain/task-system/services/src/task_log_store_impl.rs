@@ -0,0 +1,250 @@
+// Copyright Kamu Data, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use chrono::{DateTime, Utc};
+use database_common::{EntityPageListing, PaginationOpts};
+use dill::*;
+use futures::TryStreamExt;
+use internal_error::{InternalError, ResultIntoInternal};
+use kamu::ObjectStoreBuilderS3;
+use kamu_core::ObjectStoreBuilder;
+use kamu_task_system::*;
+use object_store::path::Path;
+use object_store::{ObjectStore, PutPayload};
+use s3_utils::S3Context;
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+const TASK_LOG_EXTENSION: &str = "jsonl";
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+/// Keeps the log of every task as a series of JSON Lines chunks under a
+/// per-task prefix in a local directory or under an S3 prefix. Every append
+/// writes a new chunk, so appends never rewrite existing data and can't
+/// overwrite each other
+pub struct TaskLogStoreImpl {
+    config: Arc<TaskLogsConfig>,
+    storage: tokio::sync::OnceCell<TaskLogStorage>,
+}
+
+struct TaskLogStorage {
+    object_store: Arc<dyn ObjectStore>,
+    root: Path,
+}
+
+#[component(pub)]
+#[interface(dyn TaskLogStore)]
+#[scope(Singleton)]
+impl TaskLogStoreImpl {
+    pub fn new(config: Arc<TaskLogsConfig>) -> Self {
+        Self {
+            config,
+            storage: tokio::sync::OnceCell::new(),
+        }
+    }
+
+    async fn storage(&self) -> Result<&TaskLogStorage, InternalError> {
+        self.storage
+            .get_or_try_init(|| Self::open_storage(&self.config.storage_url))
+            .await
+    }
+
+    async fn open_storage(storage_url: &url::Url) -> Result<TaskLogStorage, InternalError> {
+        match storage_url.scheme() {
+            "file" => {
+                let root_dir = storage_url
+                    .to_file_path()
+                    .map_err(|_| format!("Invalid task logs path: {storage_url}").int_err())?;
+                std::fs::create_dir_all(&root_dir).int_err()?;
+
+                let object_store =
+                    object_store::local::LocalFileSystem::new_with_prefix(&root_dir).int_err()?;
+
+                Ok(TaskLogStorage {
+                    object_store: Arc::new(object_store),
+                    root: Path::default(),
+                })
+            }
+            "s3" | "s3+http" | "s3+https" => {
+                let mut base_url = storage_url.clone();
+                if !base_url.path().ends_with('/') {
+                    base_url.set_path(&format!("{}/", base_url.path()));
+                }
+                let s3_context = S3Context::from_url(&base_url).await;
+                let root = Path::from(s3_context.key_prefix());
+                let allow_http = storage_url.scheme() == "s3+http";
+
+                let object_store =
+                    ObjectStoreBuilderS3::new(s3_context, allow_http).build_object_store()?;
+
+                Ok(TaskLogStorage { object_store, root })
+            }
+            scheme => Err(format!("Unsupported task logs location scheme: {scheme}").int_err()),
+        }
+    }
+
+    async fn read_task_log(&self, task_id: TaskID) -> Result<Vec<TaskLogEntry>, InternalError> {
+        let storage = self.storage().await?;
+
+        let mut chunk_paths: Vec<_> = match storage
+            .object_store
+            .list(Some(&storage.task_log_prefix(task_id)))
+            .map_ok(|meta| meta.location)
+            .try_collect()
+            .await
+        {
+            Ok(chunk_paths) => chunk_paths,
+            Err(object_store::Error::NotFound { .. }) => return Ok(Vec::new()),
+            Err(e) => return Err(e.int_err()),
+        };
+
+        // Chunk names start with the time of the append
+        chunk_paths.sort();
+
+        let mut task_log = Vec::new();
+        for chunk_path in chunk_paths {
+            let content = match storage.object_store.get(&chunk_path).await {
+                Ok(res) => res.bytes().await.int_err()?,
+                // Removed by the cleanup in the meantime
+                Err(object_store::Error::NotFound { .. }) => continue,
+                Err(e) => return Err(e.int_err()),
+            };
+
+            for line in std::str::from_utf8(&content).int_err()?.lines() {
+                if !line.is_empty() {
+                    task_log.push(serde_json::from_str(line).int_err()?);
+                }
+            }
+        }
+
+        Ok(task_log)
+    }
+}
+
+impl TaskLogStorage {
+    fn task_log_prefix(&self, task_id: TaskID) -> Path {
+        self.root.child(task_id.to_string().as_str())
+    }
+
+    fn new_chunk_path(&self, task_id: TaskID) -> Path {
+        self.task_log_prefix(task_id).child(
+            format!(
+                "{:020}-{}.{TASK_LOG_EXTENSION}",
+                Utc::now().timestamp_nanos_opt().unwrap_or_default(),
+                uuid::Uuid::new_v4().simple()
+            )
+            .as_str(),
+        )
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[async_trait::async_trait]
+impl TaskLogStore for TaskLogStoreImpl {
+    #[tracing::instrument(level = "debug", skip_all, fields(%task_id, num_entries = entries.len()))]
+    async fn append_task_log(
+        &self,
+        task_id: TaskID,
+        entries: &[TaskLogEntry],
+    ) -> Result<(), InternalError> {
+        if entries.is_empty() {
+            return Ok(());
+        }
+
+        let mut content = Vec::new();
+        for entry in entries {
+            serde_json::to_writer(&mut content, entry).int_err()?;
+            content.push(b'\n');
+        }
+
+        let storage = self.storage().await?;
+        storage
+            .object_store
+            .put(&storage.new_chunk_path(task_id), PutPayload::from(content))
+            .await
+            .int_err()?;
+
+        Ok(())
+    }
+
+    #[tracing::instrument(level = "debug", skip_all, fields(%task_id, ?pagination))]
+    async fn get_task_log(
+        &self,
+        task_id: TaskID,
+        pagination: PaginationOpts,
+    ) -> Result<EntityPageListing<TaskLogEntry>, InternalError> {
+        let task_log = self.read_task_log(task_id).await?;
+        let total_count = task_log.len();
+
+        let list = task_log
+            .into_iter()
+            .skip(pagination.offset)
+            .take(pagination.limit)
+            .collect();
+
+        Ok(EntityPageListing { list, total_count })
+    }
+
+    #[tracing::instrument(level = "debug", skip_all, fields(%cutoff))]
+    async fn delete_task_logs_older_than(
+        &self,
+        cutoff: DateTime<Utc>,
+    ) -> Result<usize, InternalError> {
+        let storage = self.storage().await?;
+
+        let chunks: Vec<_> = storage
+            .object_store
+            .list(Some(&storage.root))
+            .try_filter(|meta| {
+                std::future::ready(meta.location.extension() == Some(TASK_LOG_EXTENSION))
+            })
+            .try_collect()
+            .await
+            .int_err()?;
+
+        // A log expires as a whole, once its latest chunk is older than the cutoff
+        let mut task_logs: HashMap<String, (DateTime<Utc>, Vec<Path>)> = HashMap::new();
+        for chunk in chunks {
+            let task_log_prefix = chunk
+                .location
+                .as_ref()
+                .rsplit_once(object_store::path::DELIMITER)
+                .map(|(prefix, _)| prefix.to_string())
+                .unwrap_or_default();
+            let (last_modified, chunk_paths) = task_logs
+                .entry(task_log_prefix)
+                .or_insert_with(|| (chunk.last_modified, Vec::new()));
+            *last_modified = (*last_modified).max(chunk.last_modified);
+            chunk_paths.push(chunk.location);
+        }
+
+        let mut num_removed = 0;
+        for (last_modified, chunk_paths) in task_logs.into_values() {
+            if last_modified >= cutoff {
+                continue;
+            }
+            for chunk_path in &chunk_paths {
+                match storage.object_store.delete(chunk_path).await {
+                    Ok(()) | Err(object_store::Error::NotFound { .. }) => {}
+                    Err(e) => return Err(e.int_err()),
+                }
+            }
+            num_removed += 1;
+        }
+
+        Ok(num_removed)
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
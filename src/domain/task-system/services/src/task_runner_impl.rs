@@ -13,6 +13,7 @@ use dill::*;
 use internal_error::InternalError;
 use kamu_core::*;
 use kamu_task_system::*;
+use time_source::SystemTimeSource;
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
 
@@ -21,8 +22,10 @@ pub struct TaskRunnerImpl {
     transform_elaboration_service: Arc<dyn TransformElaborationService>,
     transform_executor: Arc<dyn TransformExecutor>,
     reset_executor: Arc<dyn ResetExecutor>,
+    reprocess_executor: Arc<dyn ReprocessExecutor>,
     compaction_executor: Arc<dyn CompactionExecutor>,
     sync_service: Arc<dyn SyncService>,
+    time_source: Arc<dyn SystemTimeSource>,
 }
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
@@ -35,19 +38,34 @@ impl TaskRunnerImpl {
         transform_elaboration_service: Arc<dyn TransformElaborationService>,
         transform_executor: Arc<dyn TransformExecutor>,
         reset_executor: Arc<dyn ResetExecutor>,
+        reprocess_executor: Arc<dyn ReprocessExecutor>,
         compaction_executor: Arc<dyn CompactionExecutor>,
         sync_service: Arc<dyn SyncService>,
+        time_source: Arc<dyn SystemTimeSource>,
     ) -> Self {
         Self {
             polling_ingest_service,
             transform_elaboration_service,
             transform_executor,
             reset_executor,
+            reprocess_executor,
             compaction_executor,
             sync_service,
+            time_source,
         }
     }
 
+    fn log_failure(
+        &self,
+        task_log: &mut TaskRunLog,
+        error: &(dyn std::error::Error + 'static),
+        log_files: &[std::path::PathBuf],
+    ) {
+        let now = self.time_source.now();
+        task_log.error(now, error);
+        task_log.attach_files(now, log_files);
+    }
+
     #[tracing::instrument(level = "debug", skip_all, fields(?task_probe))]
     async fn run_probe(
         &self,
@@ -67,19 +85,25 @@ impl TaskRunnerImpl {
     async fn run_update(
         &self,
         task_update: TaskDefinitionUpdate,
+        task_log: &mut TaskRunLog,
     ) -> Result<TaskOutcome, InternalError> {
         match task_update.pull_job {
             PullPlanIterationJob::Ingest(ingest_item) => {
-                self.run_ingest_update(ingest_item, task_update.pull_options.ingest_options)
-                    .await
+                self.run_ingest_update(
+                    ingest_item,
+                    task_update.pull_options.ingest_options,
+                    task_log,
+                )
+                .await
             }
             PullPlanIterationJob::Transform(transform_item) => {
-                self.run_transform_update(transform_item).await
+                self.run_transform_update(transform_item, task_log).await
             }
             PullPlanIterationJob::Sync(sync_item) => {
                 self.run_sync_update(
                     *sync_item.sync_request,
                     task_update.pull_options.sync_options,
+                    task_log,
                 )
                 .await
             }
@@ -90,6 +114,7 @@ impl TaskRunnerImpl {
         &self,
         sync_request: SyncRequest,
         sync_opts: SyncOptions,
+        task_log: &mut TaskRunLog,
     ) -> Result<TaskOutcome, InternalError> {
         let sync_response = self.sync_service.sync(sync_request, sync_opts, None).await;
         match sync_response {
@@ -98,7 +123,10 @@ impl TaskRunnerImpl {
                     pull_result: sync_result.into(),
                 },
             ))),
-            Err(_) => Ok(TaskOutcome::Failed(TaskError::Empty)),
+            Err(e) => {
+                self.log_failure(task_log, &e, &[]);
+                Ok(TaskOutcome::Failed(TaskError::Empty))
+            }
         }
     }
 
@@ -106,6 +134,7 @@ impl TaskRunnerImpl {
         &self,
         ingest_item: PullIngestItem,
         ingest_options: PollingIngestOptions,
+        task_log: &mut TaskRunLog,
     ) -> Result<TaskOutcome, InternalError> {
         let ingest_response = self
             .polling_ingest_service
@@ -122,13 +151,17 @@ impl TaskRunnerImpl {
                     pull_result: ingest_result.into(),
                 },
             ))),
-            Err(_) => Ok(TaskOutcome::Failed(TaskError::Empty)),
+            Err(e) => {
+                self.log_failure(task_log, &e, e.log_files());
+                Ok(TaskOutcome::Failed(TaskError::Empty))
+            }
         }
     }
 
     async fn run_transform_update(
         &self,
         transform_item: PullTransformItem,
+        task_log: &mut TaskRunLog,
     ) -> Result<TaskOutcome, InternalError> {
         let transform_elaboration = match self
             .transform_elaboration_service
@@ -143,6 +176,7 @@ impl TaskRunnerImpl {
             Ok(request) => Ok(request),
             // Special case: input dataset compacted
             Err(TransformElaborateError::InvalidInputInterval(e)) => {
+                self.log_failure(task_log, &e, &[]);
                 return Ok(TaskOutcome::Failed(TaskError::UpdateDatasetError(
                     UpdateDatasetTaskError::InputDatasetCompacted(InputDatasetCompactedError {
                         dataset_id: e.input_dataset_id,
@@ -151,6 +185,7 @@ impl TaskRunnerImpl {
             }
             Err(e) => {
                 tracing::error!(error = ?e, "Update failed");
+                self.log_failure(task_log, &e, &[]);
                 Err("Transform request elaboration failed".int_err())
             }
         }?;
@@ -170,6 +205,11 @@ impl TaskRunnerImpl {
                     )),
                     Err(e) => {
                         tracing::error!(error = ?e, "Transform execution failed");
+                        let log_files: &[std::path::PathBuf] = match &e {
+                            TransformExecuteError::EngineError(e) => e.log_files(),
+                            _ => &[],
+                        };
+                        self.log_failure(task_log, &e, log_files);
                         Ok(TaskOutcome::Failed(TaskError::Empty))
                     }
                 }
@@ -182,6 +222,7 @@ impl TaskRunnerImpl {
     async fn run_reset(
         &self,
         task_reset: TaskDefinitionReset,
+        task_log: &mut TaskRunLog,
     ) -> Result<TaskOutcome, InternalError> {
         let reset_result_maybe = self
             .reset_executor
@@ -192,22 +233,58 @@ impl TaskRunnerImpl {
             Ok(reset_result) => Ok(TaskOutcome::Success(TaskResult::ResetDatasetResult(
                 TaskResetDatasetResult { reset_result },
             ))),
-            Err(err) => match err {
-                ResetExecutionError::SetReferenceFailed(
-                    odf::dataset::SetChainRefError::BlockNotFound(_),
-                ) => Ok(TaskOutcome::Failed(TaskError::ResetDatasetError(
-                    ResetDatasetTaskError::ResetHeadNotFound,
-                ))),
-                err => {
-                    tracing::error!(
-                        error = ?err,
-                        error_msg = %err,
-                        "Reset failed",
-                    );
+            Err(err) => {
+                self.log_failure(task_log, &err, &[]);
+
+                match err {
+                    ResetExecutionError::SetReferenceFailed(
+                        odf::dataset::SetChainRefError::BlockNotFound(_),
+                    ) => Ok(TaskOutcome::Failed(TaskError::ResetDatasetError(
+                        ResetDatasetTaskError::ResetHeadNotFound,
+                    ))),
+                    err => {
+                        tracing::error!(
+                            error = ?err,
+                            error_msg = %err,
+                            "Reset failed",
+                        );
 
-                    Ok(TaskOutcome::Failed(TaskError::Empty))
+                        Ok(TaskOutcome::Failed(TaskError::Empty))
+                    }
                 }
-            },
+            }
+        }
+    }
+
+    #[tracing::instrument(level = "debug", skip_all, fields(?task_reprocess))]
+    async fn run_reprocess(
+        &self,
+        task_reprocess: TaskDefinitionReprocess,
+        task_log: &mut TaskRunLog,
+    ) -> Result<TaskOutcome, InternalError> {
+        let reprocess_result_maybe = self
+            .reprocess_executor
+            .execute(task_reprocess.target, task_reprocess.reprocess_plan)
+            .await;
+
+        match reprocess_result_maybe {
+            Ok(reprocess_result) => Ok(TaskOutcome::Success(TaskResult::ReprocessDatasetResult(
+                TaskReprocessDatasetResult { reprocess_result },
+            ))),
+            Err(err) => {
+                tracing::error!(
+                    error = ?err,
+                    error_msg = %err,
+                    "Reprocessing failed",
+                );
+                let log_files: &[std::path::PathBuf] = match &err {
+                    ReprocessExecutionError::EngineError(e) => e.log_files(),
+                    _ => &[],
+                };
+                self.log_failure(task_log, &err, log_files);
+
+                Ok(TaskOutcome::Failed(TaskError::Empty))
+            }
         }
     }
 
@@ -215,6 +292,7 @@ impl TaskRunnerImpl {
     async fn run_hard_compaction(
         &self,
         task_compact: TaskDefinitionHardCompact,
+        task_log: &mut TaskRunLog,
     ) -> Result<TaskOutcome, InternalError> {
         let compaction_result = self
             .compaction_executor
@@ -231,6 +309,7 @@ impl TaskRunnerImpl {
                     error_msg = %err,
                     "Hard compaction failed",
                 );
+                self.log_failure(task_log, &err, &[]);
 
                 Ok(TaskOutcome::Failed(TaskError::Empty))
             }
@@ -246,14 +325,20 @@ impl TaskRunner for TaskRunnerImpl {
     async fn run_task(
         &self,
         task_definition: TaskDefinition,
+        task_log: &mut TaskRunLog,
     ) -> Result<TaskOutcome, InternalError> {
         tracing::debug!(?task_definition, "Running task");
 
         let task_outcome = match task_definition {
             TaskDefinition::Probe(td_probe) => self.run_probe(td_probe).await?,
-            TaskDefinition::Update(td_update) => self.run_update(td_update).await?,
-            TaskDefinition::Reset(td_reset) => self.run_reset(td_reset).await?,
-            TaskDefinition::HardCompact(td_compact) => self.run_hard_compaction(td_compact).await?,
+            TaskDefinition::Update(td_update) => self.run_update(td_update, task_log).await?,
+            TaskDefinition::Reset(td_reset) => self.run_reset(td_reset, task_log).await?,
+            TaskDefinition::HardCompact(td_compact) => {
+                self.run_hard_compaction(td_compact, task_log).await?
+            }
+            TaskDefinition::Reprocess(td_reprocess) => {
+                self.run_reprocess(td_reprocess, task_log).await?
+            }
         };
 
         Ok(task_outcome)
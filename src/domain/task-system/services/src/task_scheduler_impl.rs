@@ -44,14 +44,21 @@ impl TaskScheduler for TaskSchedulerImpl {
         &self,
         logical_plan: LogicalPlan,
         metadata: Option<TaskMetadata>,
+        scheduling_options: TaskSchedulingOptions,
     ) -> Result<TaskState, CreateTaskError> {
-        tracing::info!(logical_plan = ?logical_plan, "Creating task");
+        tracing::info!(
+            logical_plan = ?logical_plan,
+            priority = ?scheduling_options.priority,
+            account_id = ?scheduling_options.account_id,
+            "Creating task"
+        );
 
         let mut task = Task::new(
             self.time_source.now(),
             self.task_event_store.new_task_id().await?,
             logical_plan,
             metadata,
+            scheduling_options,
         );
         task.save(self.task_event_store.as_ref()).await.int_err()?;
 
@@ -82,11 +89,17 @@ impl TaskScheduler for TaskSchedulerImpl {
     }
 
     #[tracing::instrument(level = "debug", skip_all)]
-    async fn try_take(&self) -> Result<Option<Task>, TakeTaskError> {
-        // Try reading just 1 earliest queued task
+    async fn try_take(
+        &self,
+        worker_id: &str,
+        lease_duration: chrono::Duration,
+        excluded_plan_kinds: &[&str],
+    ) -> Result<Option<Task>, TakeTaskError> {
+        // Try reading just 1 queued task of a kind that has capacity, giving
+        // preference to the accounts that occupy the fewest workers
         let Some(task_id) = self
             .task_event_store
-            .try_get_queued_task()
+            .try_get_queued_task(excluded_plan_kinds)
             .await
             .map_err(TakeTaskError::Internal)?
         else {
@@ -98,17 +111,42 @@ impl TaskScheduler for TaskSchedulerImpl {
         let mut task = Task::load(task_id, self.task_event_store.as_ref())
             .await
             .int_err()?;
-        task.run(self.time_source.now()).int_err()?;
+        let now = self.time_source.now();
+        task.run(now).int_err()?;
         task.save(self.task_event_store.as_ref()).await.int_err()?;
 
+        // Lease the task to the worker, so that it is not recovered while running
+        let lease = TaskLease {
+            worker_id: worker_id.to_string(),
+            expires_at: now + lease_duration,
+        };
+        let leased = self
+            .task_event_store
+            .save_task_lease(task_id, &lease)
+            .await
+            .map_err(TakeTaskError::Internal)?;
+        if !leased {
+            // Another agent took the same task concurrently
+            tracing::debug!(%task_id, %worker_id, "Task is leased by another agent, skipping");
+            return Ok(None);
+        }
+
         tracing::info!(
             %task_id,
+            %worker_id,
             logical_plan = ?task.logical_plan,
+            priority = ?task.priority,
+            account_id = ?task.account_id,
             "Handing over a task to an agent",
         );
 
         Ok(Some(task))
     }
+
+    #[tracing::instrument(level = "debug", skip_all)]
+    async fn get_queue_depth(&self) -> Result<Vec<TaskQueueDepth>, InternalError> {
+        self.task_event_store.get_queue_depth_by_account().await
+    }
 }
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
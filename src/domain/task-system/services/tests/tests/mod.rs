@@ -10,4 +10,5 @@
 mod test_task_aggregate;
 
 mod test_task_agent_impl;
+mod test_task_log_store_impl;
 mod test_task_scheduler_impl;
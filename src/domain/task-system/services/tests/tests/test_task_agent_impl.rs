@@ -8,9 +8,9 @@
 // by the Apache License, Version 2.0.
 
 use std::assert_matches::assert_matches;
-use std::sync::Arc;
+use std::sync::{Arc, OnceLock};
 
-use database_common::NoOpDatabasePlugin;
+use database_common::{NoOpDatabasePlugin, PaginationOpts};
 use dill::{Catalog, CatalogBuilder, Component};
 use kamu::utils::ipfs_wrapper::IpfsClient;
 use kamu::*;
@@ -31,7 +31,7 @@ use time_source::SystemTimeSourceDefault;
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
 
 #[test_log::test(tokio::test)]
-async fn test_pre_run_requeues_running_tasks() {
+async fn test_pre_run_requeues_running_tasks_with_expired_lease() {
     let harness = TaskAgentHarness::new(MockOutbox::new(), MockTaskRunner::new());
 
     // Schedule 3 tasks
@@ -39,9 +39,10 @@ async fn test_pre_run_requeues_running_tasks() {
     let task_id_2 = harness.schedule_probe_task().await;
     let task_id_3 = harness.schedule_probe_task().await;
 
-    // Make 2 of 3 Running
-    let task_1 = harness.try_take_task().await;
-    let task_2 = harness.try_take_task().await;
+    // Make 2 of 3 Running: the lease of the 1st expires immediately,
+    // while the 2nd is still being worked on
+    let task_1 = harness.try_take_task(chrono::Duration::zero()).await;
+    let task_2 = harness.try_take_task(chrono::Duration::hours(1)).await;
     assert_matches!(task_1, Some(t) if t.task_id == task_id_1);
     assert_matches!(task_2, Some(t) if t.task_id == task_id_2);
 
@@ -53,17 +54,17 @@ async fn test_pre_run_requeues_running_tasks() {
     assert_eq!(task_2.status(), TaskStatus::Running);
     assert_eq!(task_3.status(), TaskStatus::Queued);
 
-    // A recovery must convert all Running into Queued
+    // A recovery must convert Running tasks with expired leases into Queued
     init_on_startup::run_startup_jobs(&harness.catalog)
         .await
         .unwrap();
 
-    // 1, 2, 3 - Queued
+    // 1, 3 - Queued, 2 - still Running
     let task_1 = harness.get_task(task_id_1).await;
     let task_2 = harness.get_task(task_id_2).await;
     let task_3 = harness.get_task(task_id_3).await;
     assert_eq!(task_1.status(), TaskStatus::Queued);
-    assert_eq!(task_2.status(), TaskStatus::Queued);
+    assert_eq!(task_2.status(), TaskStatus::Running);
     assert_eq!(task_3.status(), TaskStatus::Queued);
 }
 
@@ -143,11 +144,142 @@ async fn test_run_two_of_three_tasks() {
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
 
+#[test_log::test(tokio::test)]
+async fn test_run_task_saves_task_log() {
+    let mut mock_outbox = MockOutbox::new();
+    TaskAgentHarness::add_outbox_task_expectations(&mut mock_outbox, TaskID::new(0));
+
+    // Runner fails the task, explaining the reason in the log
+    let mut mock_task_runner = MockTaskRunner::new();
+    mock_task_runner
+        .expect_run_task()
+        .times(1)
+        .returning(|_, task_log| {
+            task_log.message(chrono::Utc::now(), "Engine exited with code 1\nSee logs");
+            Ok(TaskOutcome::Failed(TaskError::Empty))
+        });
+
+    let harness = TaskAgentHarness::new(mock_outbox, mock_task_runner);
+    let task_id = harness.schedule_probe_task().await;
+
+    harness.task_agent.run_single_task().await.unwrap();
+
+    let task_log = harness
+        .task_log_store
+        .get_task_log(
+            task_id,
+            PaginationOpts {
+                offset: 0,
+                limit: 100,
+            },
+        )
+        .await
+        .unwrap();
+
+    let messages: Vec<_> = task_log
+        .list
+        .iter()
+        .map(|entry| entry.message.as_str())
+        .collect();
+    assert_eq!(task_log.total_count, 4);
+    assert!(messages[0].starts_with("Running task on worker task-agent-"));
+    assert_eq!(
+        messages[1..],
+        [
+            "Engine exited with code 1",
+            "See logs",
+            "Task finished: Failed(Empty)",
+        ]
+    );
+    assert!(task_log
+        .list
+        .iter()
+        .all(|entry| entry.source == TASK_LOG_SOURCE_TASK));
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[test_log::test(tokio::test)]
+async fn test_outcome_of_task_with_lost_lease_is_discarded() {
+    // The task starts Running, but never reports being Finished
+    let mut mock_outbox = MockOutbox::new();
+    TaskAgentHarness::add_outbox_task_running_expectation(&mut mock_outbox, TaskID::new(0));
+
+    // While the task runs, another agent considers it abandoned and re-queues it
+    let catalog_slot: Arc<OnceLock<Catalog>> = Arc::default();
+    let mut mock_task_runner = MockTaskRunner::new();
+    mock_task_runner.expect_run_task().times(1).returning({
+        let catalog_slot = catalog_slot.clone();
+        move |_, _| {
+            let task_event_store = catalog_slot
+                .get()
+                .unwrap()
+                .get_one::<dyn TaskEventStore>()
+                .unwrap();
+            futures::executor::block_on(async {
+                let mut task = Task::load(TaskID::new(0), task_event_store.as_ref())
+                    .await
+                    .unwrap();
+                task.requeue(chrono::Utc::now()).unwrap();
+                task.save(task_event_store.as_ref()).await.unwrap();
+            });
+            Ok(TaskOutcome::Success(TaskResult::Empty))
+        }
+    });
+
+    let harness = TaskAgentHarness::new(mock_outbox, mock_task_runner);
+    catalog_slot.set(harness.catalog.clone()).unwrap();
+
+    let task_id = harness.schedule_probe_task().await;
+
+    harness.task_agent.run_single_task().await.unwrap();
+
+    // The outcome is not recorded, the task waits for another run
+    let task = harness.get_task(task_id).await;
+    assert_eq!(task.status(), TaskStatus::Queued);
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[test]
+fn test_agent_config_validation() {
+    let config = TaskAgentConfig::new(chrono::Duration::seconds(1));
+    assert_matches!(config.validate(), Ok(()));
+
+    let config = TaskAgentConfig::new(chrono::Duration::seconds(1))
+        .with_task_lease(chrono::Duration::seconds(10), chrono::Duration::seconds(10));
+    assert_matches!(
+        config.validate(),
+        Err(InvalidTaskAgentConfigError::HeartbeatIntervalTooLong { .. })
+    );
+
+    let config = TaskAgentConfig::new(chrono::Duration::seconds(1)).with_max_concurrent_tasks(
+        2,
+        [
+            (
+                LogicalPlan::Probe(LogicalPlanProbe::default())
+                    .kind()
+                    .to_string(),
+                1,
+            ),
+            ("UpdateDatasets".to_string(), 1),
+        ]
+        .into(),
+    );
+    assert_matches!(
+        config.validate(),
+        Err(InvalidTaskAgentConfigError::UnknownPlanKind { plan_kind }) if plan_kind == "UpdateDatasets"
+    );
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
 struct TaskAgentHarness {
     _tempdir: TempDir,
     catalog: Catalog,
     task_agent: Arc<dyn TaskAgent>,
     task_scheduler: Arc<dyn TaskScheduler>,
+    task_log_store: Arc<dyn TaskLogStore>,
 }
 
 impl TaskAgentHarness {
@@ -166,6 +298,13 @@ impl TaskAgentHarness {
             .add::<TaskSchedulerImpl>()
             .add::<InMemoryTaskEventStore>()
             .add::<TaskDefinitionPlannerImpl>()
+            .add::<TaskLogStoreImpl>()
+            .add_value(TaskLogsConfig {
+                storage_url: url::Url::from_directory_path(tempdir.path().join("task-logs"))
+                    .unwrap(),
+                retention_period: None,
+                cleanup_interval: chrono::Duration::hours(1),
+            })
             .add_value(mock_task_runner)
             .bind::<dyn TaskRunner, MockTaskRunner>()
             .add_value(mock_outbox)
@@ -174,6 +313,7 @@ impl TaskAgentHarness {
             .add::<PullRequestPlannerImpl>()
             .add::<CompactionPlannerImpl>()
             .add::<ResetPlannerImpl>()
+            .add::<ReprocessPlannerImpl>()
             .add::<TransformRequestPlannerImpl>()
             .add::<SyncRequestBuilder>()
             .add::<DatasetFactoryImpl>()
@@ -200,12 +340,14 @@ impl TaskAgentHarness {
 
         let task_agent = catalog.get_one().unwrap();
         let task_scheduler = catalog.get_one().unwrap();
+        let task_log_store = catalog.get_one().unwrap();
 
         Self {
             _tempdir: tempdir,
             catalog,
             task_agent,
             task_scheduler,
+            task_log_store,
         }
     }
 
@@ -217,14 +359,18 @@ impl TaskAgentHarness {
                 }
                 .into(),
                 None,
+                TaskSchedulingOptions::default(),
             )
             .await
             .unwrap()
             .task_id
     }
 
-    async fn try_take_task(&self) -> Option<Task> {
-        self.task_scheduler.try_take().await.unwrap()
+    async fn try_take_task(&self, lease_duration: chrono::Duration) -> Option<Task> {
+        self.task_scheduler
+            .try_take("test-worker", lease_duration, &[])
+            .await
+            .unwrap()
     }
 
     async fn get_task(&self, task_id: TaskID) -> TaskState {
@@ -232,6 +378,8 @@ impl TaskAgentHarness {
     }
 
     fn add_outbox_task_expectations(mock_outbox: &mut MockOutbox, a_task_id: TaskID) {
+        Self::add_outbox_task_running_expectation(mock_outbox, a_task_id);
+
         mock_outbox
             .expect_post_message_as_json()
             .with(
@@ -239,7 +387,7 @@ impl TaskAgentHarness {
                 function(move |message_as_json: &serde_json::Value| {
                     matches!(
                         serde_json::from_value::<TaskProgressMessage>(message_as_json.clone()),
-                        Ok(TaskProgressMessage::Running(TaskProgressMessageRunning {
+                        Ok(TaskProgressMessage::Finished(TaskProgressMessageFinished {
                             task_id,
                             ..
                         })) if task_id == a_task_id
@@ -249,7 +397,9 @@ impl TaskAgentHarness {
             )
             .times(1)
             .returning(|_, _, _| Ok(()));
+    }
 
+    fn add_outbox_task_running_expectation(mock_outbox: &mut MockOutbox, a_task_id: TaskID) {
         mock_outbox
             .expect_post_message_as_json()
             .with(
@@ -257,7 +407,7 @@ impl TaskAgentHarness {
                 function(move |message_as_json: &serde_json::Value| {
                     matches!(
                         serde_json::from_value::<TaskProgressMessage>(message_as_json.clone()),
-                        Ok(TaskProgressMessage::Finished(TaskProgressMessageFinished {
+                        Ok(TaskProgressMessage::Running(TaskProgressMessageRunning {
                             task_id,
                             ..
                         })) if task_id == a_task_id
@@ -276,7 +426,7 @@ impl TaskAgentHarness {
     ) {
         mock_task_runner
             .expect_run_task()
-            .withf(move |td| {
+            .withf(move |td, _| {
                 matches!(
                     td,
                     TaskDefinition::Probe(TaskDefinitionProbe { probe: probe_ })
@@ -284,7 +434,7 @@ impl TaskAgentHarness {
                 )
             })
             .times(times)
-            .returning(|_| Ok(TaskOutcome::Success(TaskResult::Empty)));
+            .returning(|_, _| Ok(TaskOutcome::Success(TaskResult::Empty)));
     }
 }
 
@@ -295,7 +445,11 @@ mockall::mock! {
 
     #[async_trait::async_trait]
     impl TaskRunner for TaskRunner {
-        async fn run_task(&self, task_definition: TaskDefinition) -> Result<TaskOutcome, InternalError>;
+        async fn run_task(
+            &self,
+            task_definition: TaskDefinition,
+            task_log: &mut TaskRunLog,
+        ) -> Result<TaskOutcome, InternalError>;
     }
 }
 
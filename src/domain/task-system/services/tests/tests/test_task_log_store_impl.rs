@@ -0,0 +1,227 @@
+// Copyright Kamu Data, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use chrono::{Duration, TimeZone, Utc};
+use database_common::PaginationOpts;
+use kamu_task_system::*;
+use kamu_task_system_services::*;
+use tempfile::TempDir;
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[test_log::test(tokio::test)]
+async fn test_get_log_of_unknown_task() {
+    let harness = TaskLogStoreHarness::new();
+
+    let listing = harness
+        .store
+        .get_task_log(TaskID::new(1), all_entries())
+        .await
+        .unwrap();
+
+    assert_eq!(listing.total_count, 0);
+    assert!(listing.list.is_empty());
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[test_log::test(tokio::test)]
+async fn test_append_and_paginate() {
+    let harness = TaskLogStoreHarness::new();
+    let task_id = TaskID::new(1);
+    let other_task_id = TaskID::new(2);
+
+    harness
+        .store
+        .append_task_log(task_id, &[entry("first"), entry("second")])
+        .await
+        .unwrap();
+    harness
+        .store
+        .append_task_log(other_task_id, &[entry("other")])
+        .await
+        .unwrap();
+    harness
+        .store
+        .append_task_log(task_id, &[entry("third")])
+        .await
+        .unwrap();
+
+    let listing = harness
+        .store
+        .get_task_log(task_id, all_entries())
+        .await
+        .unwrap();
+    assert_eq!(listing.total_count, 3);
+    assert_eq!(
+        listing.list,
+        [entry("first"), entry("second"), entry("third")]
+    );
+
+    let listing = harness
+        .store
+        .get_task_log(
+            task_id,
+            PaginationOpts {
+                offset: 1,
+                limit: 1,
+            },
+        )
+        .await
+        .unwrap();
+    assert_eq!(listing.total_count, 3);
+    assert_eq!(listing.list, [entry("second")]);
+
+    let listing = harness
+        .store
+        .get_task_log(other_task_id, all_entries())
+        .await
+        .unwrap();
+    assert_eq!(listing.list, [entry("other")]);
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[test_log::test(tokio::test)]
+async fn test_delete_logs_older_than() {
+    let harness = TaskLogStoreHarness::new();
+    let task_id = TaskID::new(1);
+
+    harness
+        .store
+        .append_task_log(task_id, &[entry("first")])
+        .await
+        .unwrap();
+
+    // Log was just written, so it is not older than a day ago
+    let num_removed = harness
+        .store
+        .delete_task_logs_older_than(Utc::now() - Duration::days(1))
+        .await
+        .unwrap();
+    assert_eq!(num_removed, 0);
+
+    let num_removed = harness
+        .store
+        .delete_task_logs_older_than(Utc::now() + Duration::days(1))
+        .await
+        .unwrap();
+    assert_eq!(num_removed, 1);
+
+    let listing = harness
+        .store
+        .get_task_log(task_id, all_entries())
+        .await
+        .unwrap();
+    assert_eq!(listing.total_count, 0);
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[test]
+fn test_run_log_captures_errors_and_files() {
+    let tempdir = tempfile::tempdir().unwrap();
+    let log_path = tempdir.path().join("engine.stderr.txt");
+    std::fs::write(&log_path, "line 1\nline 2\n").unwrap();
+
+    let event_time = Utc.with_ymd_and_hms(2050, 1, 1, 12, 0, 0).unwrap();
+    let error = std::io::Error::other("Engine crashed");
+
+    let mut task_log = TaskRunLog::new();
+    task_log.error(event_time, &error);
+    task_log.attach_files(event_time, &[log_path, tempdir.path().join("missing.txt")]);
+
+    let entries: Vec<_> = task_log
+        .entries()
+        .iter()
+        .map(|e| (e.source.as_str(), e.message.as_str()))
+        .collect();
+
+    assert_eq!(entries.len(), 4);
+    assert_eq!(entries[0], (TASK_LOG_SOURCE_TASK, "Error: Engine crashed"));
+    assert_eq!(entries[1], ("engine.stderr.txt", "line 1"));
+    assert_eq!(entries[2], ("engine.stderr.txt", "line 2"));
+    assert_eq!(entries[3].0, TASK_LOG_SOURCE_TASK);
+    assert!(entries[3].1.starts_with("Failed to capture log file"));
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[tokio::test]
+async fn test_capture_task_log_events() {
+    use tracing_subscriber::layer::SubscriberExt;
+
+    let subscriber = tracing_subscriber::registry().with(TaskLogCaptureLayer);
+    let _guard = tracing::subscriber::set_default(subscriber);
+
+    tracing::info!("Outside of a task run");
+
+    let (output, captured) = capture_task_log(async {
+        tracing::info!(num_records = 5, "Ingested data");
+        tracing::debug!("Too verbose to be captured");
+        tracing::warn!("Source is slow");
+        42
+    })
+    .await;
+
+    assert_eq!(output, 42);
+
+    let entries: Vec<_> = captured
+        .iter()
+        .map(|e| (e.source.as_str(), e.message.as_str()))
+        .collect();
+    assert_eq!(
+        entries,
+        [
+            (TASK_LOG_SOURCE_TRACING, "INFO Ingested data num_records=5"),
+            (TASK_LOG_SOURCE_TRACING, "WARN Source is slow"),
+        ]
+    );
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+struct TaskLogStoreHarness {
+    _tempdir: TempDir,
+    store: TaskLogStoreImpl,
+}
+
+impl TaskLogStoreHarness {
+    fn new() -> Self {
+        let tempdir = tempfile::tempdir().unwrap();
+
+        let store = TaskLogStoreImpl::new(std::sync::Arc::new(TaskLogsConfig {
+            storage_url: url::Url::from_directory_path(tempdir.path().join("task-logs")).unwrap(),
+            retention_period: None,
+            cleanup_interval: Duration::hours(1),
+        }));
+
+        Self {
+            _tempdir: tempdir,
+            store,
+        }
+    }
+}
+
+fn entry(message: &str) -> TaskLogEntry {
+    TaskLogEntry {
+        event_time: Utc.with_ymd_and_hms(2050, 1, 1, 12, 0, 0).unwrap(),
+        source: TASK_LOG_SOURCE_TASK.to_string(),
+        message: message.to_string(),
+    }
+}
+
+fn all_entries() -> PaginationOpts {
+    PaginationOpts {
+        offset: 0,
+        limit: 100,
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
@@ -10,9 +10,11 @@
 use std::assert_matches::assert_matches;
 use std::sync::Arc;
 
+use chrono::Duration;
 use kamu_task_system::{
     LogicalPlan,
     LogicalPlanProbe,
+    LogicalPlanUpdateDataset,
     TaskMetadata,
     TaskScheduler,
     TaskState,
@@ -24,6 +26,10 @@ use time_source::SystemTimeSourceStub;
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
 
+const TEST_WORKER_ID: &str = "test-worker";
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
 #[test_log::test(tokio::test)]
 async fn test_creates_task() {
     let task_sched = create_task_scheduler();
@@ -39,6 +45,7 @@ async fn test_creates_task() {
         .create_task(
             logical_plan_expected.clone(),
             Some(metadata_expected.clone()),
+            TaskSchedulingOptions::default(),
         )
         .await
         .unwrap();
@@ -61,7 +68,10 @@ async fn test_creates_task() {
 async fn test_queues_tasks() {
     let task_sched = create_task_scheduler();
 
-    let maybe_task_0 = task_sched.try_take().await.unwrap();
+    let maybe_task_0 = task_sched
+        .try_take(TEST_WORKER_ID, Duration::minutes(1), &[])
+        .await
+        .unwrap();
     assert!(maybe_task_0.is_none());
 
     let task_id_1 = task_sched
@@ -71,6 +81,7 @@ async fn test_queues_tasks() {
             }
             .into(),
             None,
+            TaskSchedulingOptions::default(),
         )
         .await
         .unwrap()
@@ -83,18 +94,28 @@ async fn test_queues_tasks() {
             }
             .into(),
             None,
+            TaskSchedulingOptions::default(),
         )
         .await
         .unwrap()
         .task_id;
 
-    let maybe_task_1 = task_sched.try_take().await.unwrap();
+    let maybe_task_1 = task_sched
+        .try_take(TEST_WORKER_ID, Duration::minutes(1), &[])
+        .await
+        .unwrap();
     assert!(maybe_task_1.is_some_and(|t| t.task_id == task_id_1));
 
-    let maybe_task_2 = task_sched.try_take().await.unwrap();
+    let maybe_task_2 = task_sched
+        .try_take(TEST_WORKER_ID, Duration::minutes(1), &[])
+        .await
+        .unwrap();
     assert!(maybe_task_2.is_some_and(|t| t.task_id == task_id_2));
 
-    let maybe_task_3 = task_sched.try_take().await.unwrap();
+    let maybe_task_3 = task_sched
+        .try_take(TEST_WORKER_ID, Duration::minutes(1), &[])
+        .await
+        .unwrap();
     assert!(maybe_task_3.is_none());
 }
 
@@ -111,6 +132,7 @@ async fn test_task_taken_task_is_running() {
             }
             .into(),
             None,
+            TaskSchedulingOptions::default(),
         )
         .await
         .unwrap()
@@ -123,6 +145,7 @@ async fn test_task_taken_task_is_running() {
             }
             .into(),
             None,
+            TaskSchedulingOptions::default(),
         )
         .await
         .unwrap()
@@ -133,7 +156,10 @@ async fn test_task_taken_task_is_running() {
     assert_eq!(task_1.status(), TaskStatus::Queued);
     assert_eq!(task_2.status(), TaskStatus::Queued);
 
-    task_sched.try_take().await.unwrap();
+    task_sched
+        .try_take(TEST_WORKER_ID, Duration::minutes(1), &[])
+        .await
+        .unwrap();
 
     let task_1 = task_sched.get_task(task_id_1).await.unwrap();
     let task_2 = task_sched.get_task(task_id_2).await.unwrap();
@@ -154,6 +180,7 @@ async fn test_task_cancellation() {
             }
             .into(),
             None,
+            TaskSchedulingOptions::default(),
         )
         .await
         .unwrap()
@@ -166,6 +193,7 @@ async fn test_task_cancellation() {
             }
             .into(),
             None,
+            TaskSchedulingOptions::default(),
         )
         .await
         .unwrap()
@@ -173,15 +201,139 @@ async fn test_task_cancellation() {
 
     task_sched.cancel_task(task_id_1).await.unwrap();
 
-    let maybe_task = task_sched.try_take().await.unwrap();
+    let maybe_task = task_sched
+        .try_take(TEST_WORKER_ID, Duration::minutes(1), &[])
+        .await
+        .unwrap();
     assert!(maybe_task.is_some_and(|t| t.task_id == task_id_2));
 
-    let maybe_another_task = task_sched.try_take().await.unwrap();
+    let maybe_another_task = task_sched
+        .try_take(TEST_WORKER_ID, Duration::minutes(1), &[])
+        .await
+        .unwrap();
     assert!(maybe_another_task.is_none());
 }
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
 
+#[test_log::test(tokio::test)]
+async fn test_take_task_skips_excluded_plan_kinds() {
+    let task_sched = create_task_scheduler();
+
+    let task_id_1 = task_sched
+        .create_task(
+            LogicalPlanProbe::default().into(),
+            None,
+            TaskSchedulingOptions::default(),
+        )
+        .await
+        .unwrap()
+        .task_id;
+
+    let task_id_2 = task_sched
+        .create_task(
+            LogicalPlanUpdateDataset {
+                dataset_id: odf::DatasetID::new_seeded_ed25519(b"foo"),
+                fetch_uncacheable: false,
+            }
+            .into(),
+            None,
+            TaskSchedulingOptions::default(),
+        )
+        .await
+        .unwrap()
+        .task_id;
+
+    // Probes are saturated, so the update should be taken first
+    let maybe_task = task_sched
+        .try_take(TEST_WORKER_ID, Duration::minutes(1), &["Probe"])
+        .await
+        .unwrap();
+    assert!(maybe_task.is_some_and(|t| t.task_id == task_id_2));
+
+    let maybe_task = task_sched
+        .try_take(TEST_WORKER_ID, Duration::minutes(1), &["Probe"])
+        .await
+        .unwrap();
+    assert!(maybe_task.is_none());
+
+    // Without exclusions the probe is taken as well
+    let maybe_task = task_sched
+        .try_take(TEST_WORKER_ID, Duration::minutes(1), &[])
+        .await
+        .unwrap();
+    assert!(maybe_task.is_some_and(|t| t.task_id == task_id_1));
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[test_log::test(tokio::test)]
+async fn test_take_task_by_priority_sharing_workers_between_accounts() {
+    let task_sched = create_task_scheduler();
+
+    let busy_account_id = odf::AccountID::new_seeded_ed25519(b"busy");
+    let other_account_id = odf::AccountID::new_seeded_ed25519(b"other");
+
+    // One account floods the queue before the other one schedules its task
+    let mut task_ids = Vec::new();
+    for (priority, account_id) in [
+        (TaskPriority::Cascaded, &busy_account_id),
+        (TaskPriority::Manual, &busy_account_id),
+        (TaskPriority::Manual, &busy_account_id),
+        (TaskPriority::Scheduled, &other_account_id),
+    ] {
+        let task = task_sched
+            .create_task(
+                LogicalPlanProbe::default().into(),
+                None,
+                TaskSchedulingOptions {
+                    priority,
+                    account_id: Some(account_id.clone()),
+                },
+            )
+            .await
+            .unwrap();
+        task_ids.push(task.task_id);
+    }
+
+    let task_depths = task_sched.get_queue_depth().await.unwrap();
+    assert_eq!(
+        task_depths
+            .iter()
+            .find(|d| d.account_id.as_ref() == Some(&busy_account_id))
+            .map(|d| (d.queued_count, d.running_count)),
+        Some((3, 0))
+    );
+
+    // Manual task goes before the earlier cascaded one, but once the busy
+    // account occupies a worker, the other account is served
+    for expected_task_id in [task_ids[1], task_ids[3], task_ids[2], task_ids[0]] {
+        let maybe_task = task_sched
+            .try_take(TEST_WORKER_ID, Duration::minutes(1), &[])
+            .await
+            .unwrap();
+        assert!(maybe_task.is_some_and(|t| t.task_id == expected_task_id));
+    }
+
+    let maybe_task = task_sched
+        .try_take(TEST_WORKER_ID, Duration::minutes(1), &[])
+        .await
+        .unwrap();
+    assert!(maybe_task.is_none());
+
+    let task_depths = task_sched.get_queue_depth().await.unwrap();
+    assert_eq!(
+        task_depths
+            .iter()
+            .map(|d| d.queued_count + d.running_count)
+            .sum::<usize>(),
+        4
+    );
+    assert!(task_depths.iter().all(|d| d.queued_count == 0));
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
 fn create_task_scheduler() -> impl TaskScheduler {
     let task_event_store = Arc::new(InMemoryTaskEventStore::new());
     let time_source = Arc::new(SystemTimeSourceStub::new());
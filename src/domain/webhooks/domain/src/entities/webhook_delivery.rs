@@ -0,0 +1,118 @@
+// Copyright Kamu Data, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::{UnknownWebhookStatusError, WebhookEvent};
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+/// Entry of the delivery log: an attempt to deliver a single event to a single
+/// subscription, including all of its retries
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WebhookDelivery {
+    pub id: Uuid,
+    pub subscription_id: Uuid,
+    pub event: WebhookEvent,
+    pub status: WebhookDeliveryStatus,
+    /// Number of attempts made so far
+    pub attempts: u32,
+    /// When the next attempt is due, set only while the delivery is pending
+    pub next_attempt_at: Option<DateTime<Utc>>,
+    /// HTTP status code returned by the endpoint on the last attempt
+    pub response_status_code: Option<u16>,
+    /// Transport error or unexpected response of the last attempt
+    pub last_error: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl WebhookDelivery {
+    pub fn new(id: Uuid, subscription_id: Uuid, event: WebhookEvent, now: DateTime<Utc>) -> Self {
+        Self {
+            id,
+            subscription_id,
+            event,
+            status: WebhookDeliveryStatus::Pending,
+            attempts: 0,
+            next_attempt_at: Some(now),
+            response_status_code: None,
+            last_error: None,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    pub fn succeeded(&mut self, now: DateTime<Utc>, response_status_code: u16) {
+        self.attempts += 1;
+        self.status = WebhookDeliveryStatus::Succeeded;
+        self.next_attempt_at = None;
+        self.response_status_code = Some(response_status_code);
+        self.last_error = None;
+        self.updated_at = now;
+    }
+
+    /// Records a failed attempt, scheduling a retry when `retry_at` is
+    /// specified or marking the delivery as failed otherwise
+    pub fn attempt_failed(
+        &mut self,
+        now: DateTime<Utc>,
+        response_status_code: Option<u16>,
+        error: String,
+        retry_at: Option<DateTime<Utc>>,
+    ) {
+        self.attempts += 1;
+        self.status = if retry_at.is_some() {
+            WebhookDeliveryStatus::Pending
+        } else {
+            WebhookDeliveryStatus::Failed
+        };
+        self.next_attempt_at = retry_at;
+        self.response_status_code = response_status_code;
+        self.last_error = Some(error);
+        self.updated_at = now;
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum WebhookDeliveryStatus {
+    Pending,
+    Succeeded,
+    Failed,
+}
+
+impl WebhookDeliveryStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Pending => "pending",
+            Self::Succeeded => "succeeded",
+            Self::Failed => "failed",
+        }
+    }
+}
+
+impl std::str::FromStr for WebhookDeliveryStatus {
+    type Err = UnknownWebhookStatusError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "pending" => Ok(Self::Pending),
+            "succeeded" => Ok(Self::Succeeded),
+            "failed" => Ok(Self::Failed),
+            _ => Err(UnknownWebhookStatusError {
+                status: s.to_string(),
+            }),
+        }
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
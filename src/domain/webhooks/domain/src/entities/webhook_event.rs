@@ -0,0 +1,108 @@
+// Copyright Kamu Data, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum WebhookEventType {
+    #[serde(rename = "dataset.created")]
+    DatasetCreated,
+    #[serde(rename = "dataset.deleted")]
+    DatasetDeleted,
+    /// New data was committed to the dataset by an update flow
+    #[serde(rename = "dataset.updated")]
+    DatasetUpdated,
+    #[serde(rename = "flow.running")]
+    FlowRunning,
+    #[serde(rename = "flow.succeeded")]
+    FlowSucceeded,
+    #[serde(rename = "flow.failed")]
+    FlowFailed,
+    /// Flow was aborted while running or cancelled before it started
+    #[serde(rename = "flow.aborted")]
+    FlowAborted,
+    #[serde(rename = "task.running")]
+    TaskRunning,
+    #[serde(rename = "task.finished")]
+    TaskFinished,
+}
+
+impl WebhookEventType {
+    pub const ALL: [WebhookEventType; 9] = [
+        Self::DatasetCreated,
+        Self::DatasetDeleted,
+        Self::DatasetUpdated,
+        Self::FlowRunning,
+        Self::FlowSucceeded,
+        Self::FlowFailed,
+        Self::FlowAborted,
+        Self::TaskRunning,
+        Self::TaskFinished,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::DatasetCreated => "dataset.created",
+            Self::DatasetDeleted => "dataset.deleted",
+            Self::DatasetUpdated => "dataset.updated",
+            Self::FlowRunning => "flow.running",
+            Self::FlowSucceeded => "flow.succeeded",
+            Self::FlowFailed => "flow.failed",
+            Self::FlowAborted => "flow.aborted",
+            Self::TaskRunning => "task.running",
+            Self::TaskFinished => "task.finished",
+        }
+    }
+}
+
+impl std::fmt::Display for WebhookEventType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl std::str::FromStr for WebhookEventType {
+    type Err = UnknownWebhookEventTypeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|t| t.as_str() == s)
+            .ok_or_else(|| UnknownWebhookEventTypeError {
+                event_type: s.to_string(),
+            })
+    }
+}
+
+#[derive(Error, Debug)]
+#[error("Unknown webhook event type: '{event_type}'")]
+pub struct UnknownWebhookEventTypeError {
+    pub event_type: String,
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+/// Event that is delivered to the subscribed endpoints, serialized as the
+/// request body
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WebhookEvent {
+    pub event_type: WebhookEventType,
+    pub event_time: DateTime<Utc>,
+    pub account_id: odf::AccountID,
+    pub dataset_id: Option<odf::DatasetID>,
+    /// Event-specific details
+    pub data: serde_json::Value,
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
@@ -0,0 +1,92 @@
+// Copyright Kamu Data, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use chrono::{DateTime, Utc};
+use thiserror::Error;
+use url::Url;
+use uuid::Uuid;
+
+use crate::WebhookEventType;
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+/// Registered endpoint that receives events of the selected types either for
+/// a single dataset or for all datasets of the owning account
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WebhookSubscription {
+    pub id: Uuid,
+    pub owner_account_id: odf::AccountID,
+    /// When not set the subscription covers all datasets of the owner
+    pub dataset_id: Option<odf::DatasetID>,
+    pub target_url: Url,
+    pub label: String,
+    pub event_types: Vec<WebhookEventType>,
+    /// Shared secret used to sign the payloads
+    pub secret: String,
+    pub status: WebhookSubscriptionStatus,
+    pub created_at: DateTime<Utc>,
+}
+
+impl WebhookSubscription {
+    pub fn is_interested_in(
+        &self,
+        event_type: WebhookEventType,
+        dataset_id: Option<&odf::DatasetID>,
+    ) -> bool {
+        if self.status != WebhookSubscriptionStatus::Enabled
+            || !self.event_types.contains(&event_type)
+        {
+            return false;
+        }
+
+        match &self.dataset_id {
+            None => true,
+            Some(subscribed_dataset_id) => Some(subscribed_dataset_id) == dataset_id,
+        }
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum WebhookSubscriptionStatus {
+    Enabled,
+    Paused,
+}
+
+impl WebhookSubscriptionStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Enabled => "enabled",
+            Self::Paused => "paused",
+        }
+    }
+}
+
+impl std::str::FromStr for WebhookSubscriptionStatus {
+    type Err = UnknownWebhookStatusError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "enabled" => Ok(Self::Enabled),
+            "paused" => Ok(Self::Paused),
+            _ => Err(UnknownWebhookStatusError {
+                status: s.to_string(),
+            }),
+        }
+    }
+}
+
+#[derive(Error, Debug)]
+#[error("Unknown webhook status: '{status}'")]
+pub struct UnknownWebhookStatusError {
+    pub status: String,
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
@@ -0,0 +1,76 @@
+// Copyright Kamu Data, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use chrono::{DateTime, Utc};
+use database_common::PaginationOpts;
+use internal_error::InternalError;
+use thiserror::Error;
+use uuid::Uuid;
+
+use crate::WebhookDelivery;
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[async_trait::async_trait]
+pub trait WebhookDeliveryRepository: Send + Sync {
+    async fn create_delivery(&self, delivery: &WebhookDelivery) -> Result<(), InternalError>;
+
+    async fn update_delivery(
+        &self,
+        delivery: &WebhookDelivery,
+    ) -> Result<(), UpdateWebhookDeliveryError>;
+
+    /// Claims pending deliveries with the next attempt due at or before `now`
+    /// by postponing their next attempt until `lease_until`, so that other
+    /// agents skip them while the attempt is in progress. A delivery whose
+    /// attempt was never recorded becomes due again once the lease expires.
+    /// Returns the claimed deliveries, oldest first
+    async fn claim_due_deliveries(
+        &self,
+        now: DateTime<Utc>,
+        lease_until: DateTime<Utc>,
+        limit: usize,
+    ) -> Result<Vec<WebhookDelivery>, InternalError>;
+
+    async fn get_deliveries_count_by_subscription_id(
+        &self,
+        subscription_id: &Uuid,
+    ) -> Result<usize, InternalError>;
+
+    /// Returns deliveries of the subscription, newest first
+    async fn get_deliveries_by_subscription_id(
+        &self,
+        subscription_id: &Uuid,
+        pagination: &PaginationOpts,
+    ) -> Result<Vec<WebhookDelivery>, InternalError>;
+
+    async fn delete_deliveries_by_subscription_id(
+        &self,
+        subscription_id: &Uuid,
+    ) -> Result<(), InternalError>;
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[derive(Error, Debug)]
+pub enum UpdateWebhookDeliveryError {
+    #[error(transparent)]
+    NotFound(WebhookDeliveryNotFoundError),
+
+    #[error(transparent)]
+    Internal(#[from] InternalError),
+}
+
+#[derive(Error, Debug)]
+#[error("Webhook delivery not found: '{delivery_id}'")]
+pub struct WebhookDeliveryNotFoundError {
+    pub delivery_id: Uuid,
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
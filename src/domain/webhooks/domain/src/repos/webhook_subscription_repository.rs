@@ -0,0 +1,89 @@
+// Copyright Kamu Data, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use internal_error::InternalError;
+use thiserror::Error;
+use uuid::Uuid;
+
+use crate::{WebhookSubscription, WebhookSubscriptionStatus};
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[async_trait::async_trait]
+pub trait WebhookSubscriptionRepository: Send + Sync {
+    async fn create_subscription(
+        &self,
+        subscription: &WebhookSubscription,
+    ) -> Result<(), InternalError>;
+
+    async fn get_subscription_by_id(
+        &self,
+        subscription_id: &Uuid,
+    ) -> Result<WebhookSubscription, GetWebhookSubscriptionError>;
+
+    /// Returns all subscriptions owned by the account, both account-wide and
+    /// dataset-specific ones
+    async fn get_subscriptions_by_account_id(
+        &self,
+        account_id: &odf::AccountID,
+    ) -> Result<Vec<WebhookSubscription>, InternalError>;
+
+    async fn get_subscriptions_by_dataset_id(
+        &self,
+        dataset_id: &odf::DatasetID,
+    ) -> Result<Vec<WebhookSubscription>, InternalError>;
+
+    async fn update_subscription_status(
+        &self,
+        subscription_id: &Uuid,
+        status: WebhookSubscriptionStatus,
+    ) -> Result<(), UpdateWebhookSubscriptionError>;
+
+    async fn delete_subscription(
+        &self,
+        subscription_id: &Uuid,
+    ) -> Result<(), DeleteWebhookSubscriptionError>;
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[derive(Error, Debug)]
+pub enum GetWebhookSubscriptionError {
+    #[error(transparent)]
+    NotFound(WebhookSubscriptionNotFoundError),
+
+    #[error(transparent)]
+    Internal(#[from] InternalError),
+}
+
+#[derive(Error, Debug)]
+pub enum UpdateWebhookSubscriptionError {
+    #[error(transparent)]
+    NotFound(WebhookSubscriptionNotFoundError),
+
+    #[error(transparent)]
+    Internal(#[from] InternalError),
+}
+
+#[derive(Error, Debug)]
+pub enum DeleteWebhookSubscriptionError {
+    #[error(transparent)]
+    NotFound(WebhookSubscriptionNotFoundError),
+
+    #[error(transparent)]
+    Internal(#[from] InternalError),
+}
+
+#[derive(Error, Debug)]
+#[error("Webhook subscription not found: '{subscription_id}'")]
+pub struct WebhookSubscriptionNotFoundError {
+    pub subscription_id: Uuid,
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
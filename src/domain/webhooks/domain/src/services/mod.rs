@@ -0,0 +1,16 @@
+// Copyright Kamu Data, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+mod webhook_delivery_agent;
+mod webhook_sender;
+mod webhook_subscription_service;
+
+pub use webhook_delivery_agent::*;
+pub use webhook_sender::*;
+pub use webhook_subscription_service::*;
@@ -0,0 +1,94 @@
+// Copyright Kamu Data, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use internal_error::InternalError;
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[async_trait::async_trait]
+pub trait WebhookDeliveryAgent: Send + Sync {
+    /// Runs the agent main loop
+    async fn run(&self) -> Result<(), InternalError>;
+
+    /// Makes a single attempt for every delivery that is due (for tests only!)
+    async fn run_due_deliveries(&self) -> Result<(), InternalError>;
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[derive(Debug)]
+pub struct WebhookDeliveryConfig {
+    /// Interval between checks whether there are any deliveries due
+    pub awaiting_step: chrono::Duration,
+    /// Maximum number of deliveries attempted in one iteration
+    pub batch_size: usize,
+    /// Number of attempts after which a delivery is considered failed
+    pub max_attempts: u32,
+    /// Delay before the first retry, doubled after every failed attempt
+    pub initial_backoff: chrono::Duration,
+    /// Upper bound of the delay between retries
+    pub max_backoff: chrono::Duration,
+    /// Time the endpoint has to respond in
+    pub request_timeout: chrono::Duration,
+}
+
+impl WebhookDeliveryConfig {
+    pub fn new(
+        awaiting_step: chrono::Duration,
+        batch_size: usize,
+        max_attempts: u32,
+        initial_backoff: chrono::Duration,
+        max_backoff: chrono::Duration,
+        request_timeout: chrono::Duration,
+    ) -> Self {
+        Self {
+            awaiting_step,
+            batch_size,
+            max_attempts,
+            initial_backoff,
+            max_backoff,
+            request_timeout,
+        }
+    }
+
+    /// Time a claimed batch of deliveries stays hidden from other agents: long
+    /// enough for every delivery of the batch to time out
+    pub fn claim_lease(&self) -> chrono::Duration {
+        self.request_timeout
+            .checked_mul(i32::try_from(self.batch_size).unwrap_or(i32::MAX))
+            .unwrap_or(self.max_backoff)
+            + self.awaiting_step
+    }
+
+    /// Delay before the attempt following the specified number of failed ones
+    pub fn backoff(&self, failed_attempts: u32) -> chrono::Duration {
+        let factor = 2_i32.saturating_pow(failed_attempts.saturating_sub(1));
+        std::cmp::min(
+            self.initial_backoff
+                .checked_mul(factor)
+                .unwrap_or(self.max_backoff),
+            self.max_backoff,
+        )
+    }
+}
+
+impl Default for WebhookDeliveryConfig {
+    fn default() -> Self {
+        Self {
+            awaiting_step: chrono::Duration::seconds(1),
+            batch_size: 20,
+            max_attempts: 5,
+            initial_backoff: chrono::Duration::seconds(10),
+            max_backoff: chrono::Duration::hours(1),
+            request_timeout: chrono::Duration::seconds(10),
+        }
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
@@ -0,0 +1,55 @@
+// Copyright Kamu Data, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use thiserror::Error;
+use url::Url;
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+pub const WEBHOOK_HEADER_EVENT: &str = "X-Kamu-Webhook-Event";
+pub const WEBHOOK_HEADER_DELIVERY: &str = "X-Kamu-Webhook-Delivery";
+pub const WEBHOOK_HEADER_SIGNATURE: &str = "X-Kamu-Signature-256";
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+/// Performs a single attempt to deliver a signed payload to an endpoint
+#[async_trait::async_trait]
+pub trait WebhookSender: Send + Sync {
+    async fn send(&self, request: WebhookRequest) -> Result<WebhookResponse, WebhookSendError>;
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[derive(Debug, Clone)]
+pub struct WebhookRequest {
+    pub target_url: Url,
+    /// Extra headers, including the event type, delivery ID and signature
+    pub headers: Vec<(&'static str, String)>,
+    /// JSON-encoded event
+    pub body: Vec<u8>,
+}
+
+#[derive(Debug, Clone)]
+pub struct WebhookResponse {
+    pub status_code: u16,
+}
+
+impl WebhookResponse {
+    pub fn is_success(&self) -> bool {
+        (200..300).contains(&self.status_code)
+    }
+}
+
+#[derive(Error, Debug)]
+#[error("Failed to send webhook request: {reason}")]
+pub struct WebhookSendError {
+    pub reason: String,
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
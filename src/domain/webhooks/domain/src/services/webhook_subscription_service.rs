@@ -0,0 +1,91 @@
+// Copyright Kamu Data, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use database_common::{EntityPageListing, PaginationOpts};
+use internal_error::InternalError;
+use thiserror::Error;
+use url::Url;
+use uuid::Uuid;
+
+use crate::{
+    DeleteWebhookSubscriptionError,
+    GetWebhookSubscriptionError,
+    UpdateWebhookSubscriptionError,
+    WebhookDelivery,
+    WebhookEventType,
+    WebhookSubscription,
+    WebhookSubscriptionStatus,
+};
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[async_trait::async_trait]
+pub trait WebhookSubscriptionService: Send + Sync {
+    /// Registers a new endpoint, generating the secret used to sign payloads
+    async fn create_subscription(
+        &self,
+        owner_account_id: &odf::AccountID,
+        dataset_id: Option<&odf::DatasetID>,
+        target_url: Url,
+        label: String,
+        event_types: Vec<WebhookEventType>,
+    ) -> Result<WebhookSubscription, CreateWebhookSubscriptionError>;
+
+    async fn get_subscription(
+        &self,
+        subscription_id: &Uuid,
+    ) -> Result<WebhookSubscription, GetWebhookSubscriptionError>;
+
+    async fn list_account_subscriptions(
+        &self,
+        account_id: &odf::AccountID,
+    ) -> Result<Vec<WebhookSubscription>, InternalError>;
+
+    async fn list_dataset_subscriptions(
+        &self,
+        dataset_id: &odf::DatasetID,
+    ) -> Result<Vec<WebhookSubscription>, InternalError>;
+
+    async fn set_subscription_status(
+        &self,
+        subscription_id: &Uuid,
+        status: WebhookSubscriptionStatus,
+    ) -> Result<(), UpdateWebhookSubscriptionError>;
+
+    /// Removes the subscription along with its delivery log
+    async fn remove_subscription(
+        &self,
+        subscription_id: &Uuid,
+    ) -> Result<(), DeleteWebhookSubscriptionError>;
+
+    async fn list_deliveries(
+        &self,
+        subscription_id: &Uuid,
+        pagination: PaginationOpts,
+    ) -> Result<EntityPageListing<WebhookDelivery>, InternalError>;
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[derive(Error, Debug)]
+pub enum CreateWebhookSubscriptionError {
+    #[error(transparent)]
+    Invalid(InvalidWebhookSubscriptionError),
+
+    #[error(transparent)]
+    Internal(#[from] InternalError),
+}
+
+#[derive(Error, Debug)]
+#[error("Invalid webhook subscription: {reason}")]
+pub struct InvalidWebhookSubscriptionError {
+    pub reason: String,
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
@@ -0,0 +1,105 @@
+// Copyright Kamu Data, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+use dill::*;
+use kamu_webhooks::*;
+use reqwest::dns::{Addrs, Name, Resolve, Resolving};
+
+use crate::{is_forbidden_webhook_address, is_forbidden_webhook_host};
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+pub struct HttpWebhookSender {
+    client: reqwest::Client,
+}
+
+#[component(pub)]
+#[interface(dyn WebhookSender)]
+#[scope(Singleton)]
+impl HttpWebhookSender {
+    pub fn new(config: Arc<WebhookDeliveryConfig>) -> Self {
+        let client = reqwest::Client::builder()
+            .timeout(config.request_timeout.to_std().unwrap())
+            .redirect(reqwest::redirect::Policy::none())
+            .dns_resolver(Arc::new(WebhookTargetResolver))
+            .build()
+            .unwrap();
+
+        Self { client }
+    }
+}
+
+#[async_trait::async_trait]
+impl WebhookSender for HttpWebhookSender {
+    #[tracing::instrument(level = "debug", skip_all, fields(target_url = %request.target_url))]
+    async fn send(&self, request: WebhookRequest) -> Result<WebhookResponse, WebhookSendError> {
+        // IP literals bypass the resolver, so the host is checked as written too
+        if is_forbidden_webhook_host(&request.target_url) {
+            return Err(WebhookSendError {
+                reason: "Target URL points to a local, link-local or metadata address".to_string(),
+            });
+        }
+
+        let mut request_builder = self
+            .client
+            .post(request.target_url)
+            .header(reqwest::header::CONTENT_TYPE, "application/json");
+
+        for (name, value) in request.headers {
+            request_builder = request_builder.header(name, value);
+        }
+
+        let response = request_builder
+            .body(request.body)
+            .send()
+            .await
+            .map_err(|e| WebhookSendError {
+                reason: e.to_string(),
+            })?;
+
+        Ok(WebhookResponse {
+            status_code: response.status().as_u16(),
+        })
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+/// Resolves hosts of the target URLs at the moment of connecting, refusing the
+/// ones that point to forbidden addresses, so that a name cannot be re-pointed
+/// to the node internals after the subscription was validated
+struct WebhookTargetResolver;
+
+impl Resolve for WebhookTargetResolver {
+    fn resolve(&self, name: Name) -> Resolving {
+        Box::pin(async move {
+            let addrs: Vec<SocketAddr> =
+                tokio::net::lookup_host((name.as_str(), 0)).await?.collect();
+
+            if let Some(addr) = addrs
+                .iter()
+                .find(|addr| is_forbidden_webhook_address(addr.ip()))
+            {
+                return Err(format!(
+                    "Host '{}' resolves to forbidden address {}",
+                    name.as_str(),
+                    addr.ip()
+                )
+                .into());
+            }
+
+            Ok(Box::new(addrs.into_iter()) as Addrs)
+        })
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
@@ -0,0 +1,22 @@
+// Copyright Kamu Data, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+mod http_webhook_sender;
+mod webhook_delivery_agent_impl;
+mod webhook_event_dispatcher;
+mod webhook_signature;
+mod webhook_subscription_service_impl;
+mod webhook_target_guard;
+
+pub use http_webhook_sender::*;
+pub use webhook_delivery_agent_impl::*;
+pub use webhook_event_dispatcher::*;
+pub use webhook_signature::*;
+pub use webhook_subscription_service_impl::*;
+pub use webhook_target_guard::*;
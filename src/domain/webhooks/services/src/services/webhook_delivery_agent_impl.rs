@@ -0,0 +1,189 @@
+// Copyright Kamu Data, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::sync::Arc;
+
+use database_common_macros::transactional_method1;
+use dill::*;
+use internal_error::{InternalError, ResultIntoInternal};
+use kamu_webhooks::*;
+use time_source::SystemTimeSource;
+
+use crate::sign_webhook_payload;
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+pub struct WebhookDeliveryAgentImpl {
+    catalog: Catalog,
+    webhook_sender: Arc<dyn WebhookSender>,
+    time_source: Arc<dyn SystemTimeSource>,
+    config: Arc<WebhookDeliveryConfig>,
+}
+
+#[component(pub)]
+#[interface(dyn WebhookDeliveryAgent)]
+#[scope(Singleton)]
+impl WebhookDeliveryAgentImpl {
+    pub fn new(
+        catalog: Catalog,
+        webhook_sender: Arc<dyn WebhookSender>,
+        time_source: Arc<dyn SystemTimeSource>,
+        config: Arc<WebhookDeliveryConfig>,
+    ) -> Self {
+        Self {
+            catalog,
+            webhook_sender,
+            time_source,
+            config,
+        }
+    }
+
+    #[transactional_method1(delivery_repo: Arc<dyn WebhookDeliveryRepository>)]
+    async fn claim_due_deliveries(&self) -> Result<Vec<WebhookDelivery>, InternalError> {
+        let now = self.time_source.now();
+        delivery_repo
+            .claim_due_deliveries(now, now + self.config.claim_lease(), self.config.batch_size)
+            .await
+    }
+
+    #[transactional_method1(subscription_repo: Arc<dyn WebhookSubscriptionRepository>)]
+    async fn get_subscription(
+        &self,
+        subscription_id: uuid::Uuid,
+    ) -> Result<Option<WebhookSubscription>, InternalError> {
+        match subscription_repo
+            .get_subscription_by_id(&subscription_id)
+            .await
+        {
+            Ok(subscription) => Ok(Some(subscription)),
+            Err(GetWebhookSubscriptionError::NotFound(_)) => Ok(None),
+            Err(GetWebhookSubscriptionError::Internal(e)) => Err(e),
+        }
+    }
+
+    #[transactional_method1(delivery_repo: Arc<dyn WebhookDeliveryRepository>)]
+    async fn save_delivery(&self, delivery: WebhookDelivery) -> Result<(), InternalError> {
+        // Subscription and its log could have been removed while we were sending
+        match delivery_repo.update_delivery(&delivery).await {
+            Ok(()) | Err(UpdateWebhookDeliveryError::NotFound(_)) => Ok(()),
+            Err(UpdateWebhookDeliveryError::Internal(e)) => Err(e),
+        }
+    }
+
+    #[tracing::instrument(level = "debug", skip_all, fields(delivery_id = %delivery.id, subscription_id = %delivery.subscription_id))]
+    async fn attempt_delivery(&self, mut delivery: WebhookDelivery) -> Result<(), InternalError> {
+        let subscription = match self.get_subscription(delivery.subscription_id).await? {
+            Some(subscription) if subscription.status == WebhookSubscriptionStatus::Enabled => {
+                subscription
+            }
+            maybe_subscription => {
+                let error = if maybe_subscription.is_some() {
+                    "Subscription was paused"
+                } else {
+                    "Subscription no longer exists"
+                };
+                delivery.attempt_failed(self.time_source.now(), None, error.to_string(), None);
+                return self.save_delivery(delivery).await;
+            }
+        };
+
+        let body = serde_json::to_vec(&delivery.event).int_err()?;
+        let request = WebhookRequest {
+            target_url: subscription.target_url.clone(),
+            headers: vec![
+                (WEBHOOK_HEADER_EVENT, delivery.event.event_type.to_string()),
+                (WEBHOOK_HEADER_DELIVERY, delivery.id.to_string()),
+                (
+                    WEBHOOK_HEADER_SIGNATURE,
+                    sign_webhook_payload(&subscription.secret, &body),
+                ),
+            ],
+            body,
+        };
+
+        let (response_status_code, error) = match self.webhook_sender.send(request).await {
+            Ok(response) if response.is_success() => (Some(response.status_code), None),
+            Ok(response) => (
+                Some(response.status_code),
+                Some(format!(
+                    "Endpoint responded with status code {}",
+                    response.status_code
+                )),
+            ),
+            Err(e) => (None, Some(e.reason)),
+        };
+
+        let now = self.time_source.now();
+
+        match error {
+            None => {
+                tracing::debug!(?response_status_code, "Webhook delivered");
+                delivery.succeeded(now, response_status_code.unwrap());
+            }
+            Some(error) => {
+                let failed_attempts = delivery.attempts + 1;
+                let retry_at = (failed_attempts < self.config.max_attempts)
+                    .then(|| now + self.config.backoff(failed_attempts));
+
+                tracing::warn!(
+                    ?response_status_code,
+                    %error,
+                    failed_attempts,
+                    ?retry_at,
+                    "Webhook delivery attempt failed"
+                );
+
+                delivery.attempt_failed(now, response_status_code, error, retry_at);
+            }
+        }
+
+        self.save_delivery(delivery).await
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[async_trait::async_trait]
+impl WebhookDeliveryAgent for WebhookDeliveryAgentImpl {
+    async fn run(&self) -> Result<(), InternalError> {
+        loop {
+            if let Err(e) = self.run_due_deliveries().await {
+                tracing::error!(error = ?e, error_msg = %e, "Failed to run due webhook deliveries");
+            }
+
+            self.time_source.sleep(self.config.awaiting_step).await;
+        }
+    }
+
+    #[tracing::instrument(level = "debug", skip_all)]
+    async fn run_due_deliveries(&self) -> Result<(), InternalError> {
+        loop {
+            let deliveries = self.claim_due_deliveries().await?;
+            if deliveries.is_empty() {
+                return Ok(());
+            }
+
+            let batch_size = deliveries.len();
+
+            // A failure of one delivery should not hold back the rest of the batch,
+            // the failed one will be picked up again when its claim expires
+            for delivery in deliveries {
+                if let Err(e) = self.attempt_delivery(delivery).await {
+                    tracing::error!(error = ?e, error_msg = %e, "Webhook delivery attempt crashed");
+                }
+            }
+
+            if batch_size < self.config.batch_size {
+                return Ok(());
+            }
+        }
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
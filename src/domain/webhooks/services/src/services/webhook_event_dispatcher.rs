@@ -0,0 +1,426 @@
+// Copyright Kamu Data, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::sync::Arc;
+
+use dill::*;
+use internal_error::{InternalError, ResultIntoInternal};
+use kamu_datasets::{
+    DatasetEntryService,
+    DatasetLifecycleMessage,
+    GetDatasetEntryError,
+    MESSAGE_PRODUCER_KAMU_DATASET_SERVICE,
+};
+use kamu_flow_system::{
+    FlowError,
+    FlowKey,
+    FlowOutcome,
+    FlowProgressMessage,
+    FlowQueryService,
+    FlowResult,
+    FlowResultDatasetUpdate,
+    GetFlowError,
+};
+use kamu_flow_system_services::MESSAGE_PRODUCER_KAMU_FLOW_PROGRESS_SERVICE;
+use kamu_task_system::{
+    GetTaskError,
+    TaskID,
+    TaskOutcome,
+    TaskProgressMessage,
+    TaskScheduler,
+    MESSAGE_PRODUCER_KAMU_TASK_AGENT,
+};
+use kamu_webhooks::*;
+use messaging_outbox::{
+    MessageConsumer,
+    MessageConsumerMeta,
+    MessageConsumerT,
+    MessageDeliveryMechanism,
+};
+use serde_json::json;
+use time_source::SystemTimeSource;
+use uuid::Uuid;
+
+use crate::MESSAGE_CONSUMER_KAMU_WEBHOOK_EVENT_DISPATCHER;
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+/// Translates internal messages into webhook events and enqueues their
+/// deliveries to the interested subscriptions. Actual sending is performed by
+/// the [`WebhookDeliveryAgent`].
+pub struct WebhookEventDispatcher {
+    subscription_repo: Arc<dyn WebhookSubscriptionRepository>,
+    delivery_repo: Arc<dyn WebhookDeliveryRepository>,
+    time_source: Arc<dyn SystemTimeSource>,
+}
+
+#[component(pub)]
+#[interface(dyn MessageConsumer)]
+#[interface(dyn MessageConsumerT<DatasetLifecycleMessage>)]
+#[interface(dyn MessageConsumerT<FlowProgressMessage>)]
+#[interface(dyn MessageConsumerT<TaskProgressMessage>)]
+#[meta(MessageConsumerMeta {
+    consumer_name: MESSAGE_CONSUMER_KAMU_WEBHOOK_EVENT_DISPATCHER,
+    feeding_producers: &[
+        MESSAGE_PRODUCER_KAMU_DATASET_SERVICE,
+        MESSAGE_PRODUCER_KAMU_FLOW_PROGRESS_SERVICE,
+        MESSAGE_PRODUCER_KAMU_TASK_AGENT,
+    ],
+    delivery: MessageDeliveryMechanism::Transactional,
+})]
+impl WebhookEventDispatcher {
+    pub fn new(
+        subscription_repo: Arc<dyn WebhookSubscriptionRepository>,
+        delivery_repo: Arc<dyn WebhookDeliveryRepository>,
+        time_source: Arc<dyn SystemTimeSource>,
+    ) -> Self {
+        Self {
+            subscription_repo,
+            delivery_repo,
+            time_source,
+        }
+    }
+
+    /// Enqueues the event for every enabled subscription of the dataset and of
+    /// the owning account that is interested in this event type
+    async fn dispatch(&self, event: WebhookEvent) -> Result<(), InternalError> {
+        let mut subscriptions = self
+            .subscription_repo
+            .get_subscriptions_by_account_id(&event.account_id)
+            .await?;
+
+        if let Some(dataset_id) = &event.dataset_id {
+            // Dataset subscriptions of other accounts are possible when the
+            // ownership is not the only way to get the write access
+            for subscription in self
+                .subscription_repo
+                .get_subscriptions_by_dataset_id(dataset_id)
+                .await?
+            {
+                if !subscriptions.iter().any(|s| s.id == subscription.id) {
+                    subscriptions.push(subscription);
+                }
+            }
+        }
+
+        let now = self.time_source.now();
+
+        for subscription in subscriptions
+            .into_iter()
+            .filter(|s| s.is_interested_in(event.event_type, event.dataset_id.as_ref()))
+        {
+            tracing::debug!(
+                subscription_id = %subscription.id,
+                event_type = %event.event_type,
+                "Enqueueing webhook delivery"
+            );
+
+            let delivery =
+                WebhookDelivery::new(Uuid::new_v4(), subscription.id, event.clone(), now);
+            self.delivery_repo.create_delivery(&delivery).await?;
+        }
+
+        Ok(())
+    }
+
+    async fn resolve_owner(
+        target_catalog: &Catalog,
+        dataset_id: &odf::DatasetID,
+    ) -> Result<Option<odf::AccountID>, InternalError> {
+        let dataset_entry_service = target_catalog.get_one::<dyn DatasetEntryService>().unwrap();
+        match dataset_entry_service.get_entry(dataset_id).await {
+            Ok(entry) => Ok(Some(entry.owner_id)),
+            Err(GetDatasetEntryError::NotFound(_)) => Ok(None),
+            Err(GetDatasetEntryError::Internal(e)) => Err(e),
+        }
+    }
+
+    async fn dispatch_dataset_event(
+        &self,
+        target_catalog: &Catalog,
+        event_type: WebhookEventType,
+        event_time: chrono::DateTime<chrono::Utc>,
+        dataset_id: &odf::DatasetID,
+        data: serde_json::Value,
+    ) -> Result<(), InternalError> {
+        // Dataset might have been deleted in the meantime
+        let Some(account_id) = Self::resolve_owner(target_catalog, dataset_id).await? else {
+            tracing::debug!(%dataset_id, %event_type, "Skipping event of unknown dataset");
+            return Ok(());
+        };
+
+        self.dispatch(WebhookEvent {
+            event_type,
+            event_time,
+            account_id,
+            dataset_id: Some(dataset_id.clone()),
+            data,
+        })
+        .await
+    }
+
+    async fn handle_dataset_lifecycle_message(
+        &self,
+        message: &DatasetLifecycleMessage,
+    ) -> Result<(), InternalError> {
+        let now = self.time_source.now();
+
+        match message {
+            DatasetLifecycleMessage::Created(message) => {
+                self.dispatch(WebhookEvent {
+                    event_type: WebhookEventType::DatasetCreated,
+                    event_time: now,
+                    account_id: message.owner_account_id.clone(),
+                    dataset_id: Some(message.dataset_id.clone()),
+                    data: json!({
+                        "datasetName": message.dataset_name.to_string(),
+                    }),
+                })
+                .await
+            }
+            DatasetLifecycleMessage::Deleted(message) => {
+                // The dataset entry is already gone, so the owner can only be
+                // learned from the dataset subscriptions themselves
+                for subscription in self
+                    .subscription_repo
+                    .get_subscriptions_by_dataset_id(&message.dataset_id)
+                    .await?
+                    .into_iter()
+                    .filter(|s| {
+                        s.is_interested_in(
+                            WebhookEventType::DatasetDeleted,
+                            Some(&message.dataset_id),
+                        )
+                    })
+                {
+                    let event = WebhookEvent {
+                        event_type: WebhookEventType::DatasetDeleted,
+                        event_time: now,
+                        account_id: subscription.owner_account_id.clone(),
+                        dataset_id: Some(message.dataset_id.clone()),
+                        data: json!({}),
+                    };
+                    let delivery =
+                        WebhookDelivery::new(Uuid::new_v4(), subscription.id, event, now);
+                    self.delivery_repo.create_delivery(&delivery).await?;
+                }
+                Ok(())
+            }
+        }
+    }
+
+    async fn handle_flow_progress_message(
+        &self,
+        target_catalog: &Catalog,
+        message: &FlowProgressMessage,
+    ) -> Result<(), InternalError> {
+        let (flow_id, event_time) = match message {
+            FlowProgressMessage::Scheduled(_) => return Ok(()),
+            FlowProgressMessage::Running(m) => (m.flow_id, m.event_time),
+            FlowProgressMessage::Finished(m) => (m.flow_id, m.event_time),
+            FlowProgressMessage::Cancelled(m) => (m.flow_id, m.event_time),
+        };
+
+        let flow_query_service = target_catalog.get_one::<dyn FlowQueryService>().unwrap();
+        let flow = match flow_query_service.get_flow(flow_id).await {
+            Ok(flow) => flow,
+            Err(GetFlowError::NotFound(_)) => return Ok(()),
+            Err(GetFlowError::Internal(e)) => return Err(e),
+        };
+
+        // System flows are not related to any account
+        let FlowKey::Dataset(flow_key) = flow.flow_key else {
+            return Ok(());
+        };
+
+        let data = |extra: serde_json::Value| -> Result<serde_json::Value, InternalError> {
+            let mut data = json!({
+                "flowId": flow_id.to_string(),
+                "flowType": serde_json::to_value(flow_key.flow_type).int_err()?,
+            });
+            if let (Some(data), serde_json::Value::Object(extra)) = (data.as_object_mut(), extra) {
+                data.extend(extra);
+            }
+            Ok(data)
+        };
+
+        let events = match message {
+            FlowProgressMessage::Scheduled(_) => unreachable!(),
+            FlowProgressMessage::Running(_) => {
+                vec![(WebhookEventType::FlowRunning, data(json!({}))?)]
+            }
+            FlowProgressMessage::Cancelled(_) => {
+                vec![(WebhookEventType::FlowAborted, data(json!({}))?)]
+            }
+            FlowProgressMessage::Finished(m) => match &m.outcome {
+                FlowOutcome::Success(result) => {
+                    let mut events = vec![(WebhookEventType::FlowSucceeded, data(json!({}))?)];
+                    if let FlowResult::DatasetUpdate(FlowResultDatasetUpdate::Changed(update)) =
+                        result
+                    {
+                        events.push((
+                            WebhookEventType::DatasetUpdated,
+                            data(json!({
+                                "oldHead": update.old_head.as_ref().map(ToString::to_string),
+                                "newHead": update.new_head.to_string(),
+                            }))?,
+                        ));
+                    }
+                    events
+                }
+                FlowOutcome::Failed(error) => vec![(
+                    WebhookEventType::FlowFailed,
+                    data(json!({ "error": Self::describe_flow_error(error) }))?,
+                )],
+                FlowOutcome::Aborted => {
+                    vec![(WebhookEventType::FlowAborted, data(json!({}))?)]
+                }
+            },
+        };
+
+        for (event_type, data) in events {
+            self.dispatch_dataset_event(
+                target_catalog,
+                event_type,
+                event_time,
+                &flow_key.dataset_id,
+                data,
+            )
+            .await?;
+        }
+
+        Ok(())
+    }
+
+    fn describe_flow_error(error: &FlowError) -> String {
+        match error {
+            FlowError::Failed => "Flow failed".to_string(),
+            FlowError::InputDatasetCompacted(e) => {
+                format!("Input dataset {} was compacted", e.dataset_id)
+            }
+            FlowError::ResetHeadNotFound => "New head for reset not found".to_string(),
+        }
+    }
+
+    async fn handle_task_progress_message(
+        &self,
+        target_catalog: &Catalog,
+        message: &TaskProgressMessage,
+    ) -> Result<(), InternalError> {
+        let (task_id, event_time) = match message {
+            TaskProgressMessage::Running(m) => (m.task_id, m.event_time),
+            TaskProgressMessage::Finished(m) => (m.task_id, m.event_time),
+        };
+
+        let Some(dataset_id) = Self::resolve_task_dataset(target_catalog, task_id).await? else {
+            return Ok(());
+        };
+
+        let (event_type, data) = match message {
+            TaskProgressMessage::Running(_) => (
+                WebhookEventType::TaskRunning,
+                json!({ "taskId": task_id.to_string() }),
+            ),
+            TaskProgressMessage::Finished(m) => (
+                WebhookEventType::TaskFinished,
+                json!({
+                    "taskId": task_id.to_string(),
+                    "outcome": match m.outcome {
+                        TaskOutcome::Success(_) => "success",
+                        TaskOutcome::Failed(_) => "failed",
+                        TaskOutcome::Cancelled => "cancelled",
+                    },
+                }),
+            ),
+        };
+
+        self.dispatch_dataset_event(target_catalog, event_type, event_time, &dataset_id, data)
+            .await
+    }
+
+    async fn resolve_task_dataset(
+        target_catalog: &Catalog,
+        task_id: TaskID,
+    ) -> Result<Option<odf::DatasetID>, InternalError> {
+        let task_scheduler = target_catalog.get_one::<dyn TaskScheduler>().unwrap();
+        match task_scheduler.get_task(task_id).await {
+            Ok(task) => Ok(task.logical_plan.dataset_id().cloned()),
+            Err(GetTaskError::NotFound(_)) => Ok(None),
+            Err(GetTaskError::Internal(e)) => Err(e),
+        }
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+impl MessageConsumer for WebhookEventDispatcher {}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[async_trait::async_trait]
+impl MessageConsumerT<DatasetLifecycleMessage> for WebhookEventDispatcher {
+    #[tracing::instrument(
+        level = "debug",
+        skip_all,
+        name = "WebhookEventDispatcher[DatasetLifecycleMessage]"
+    )]
+    async fn consume_message(
+        &self,
+        _: &Catalog,
+        message: &DatasetLifecycleMessage,
+    ) -> Result<(), InternalError> {
+        tracing::debug!(received_message = ?message, "Received dataset lifecycle message");
+
+        self.handle_dataset_lifecycle_message(message).await
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[async_trait::async_trait]
+impl MessageConsumerT<FlowProgressMessage> for WebhookEventDispatcher {
+    #[tracing::instrument(
+        level = "debug",
+        skip_all,
+        name = "WebhookEventDispatcher[FlowProgressMessage]"
+    )]
+    async fn consume_message(
+        &self,
+        target_catalog: &Catalog,
+        message: &FlowProgressMessage,
+    ) -> Result<(), InternalError> {
+        tracing::debug!(received_message = ?message, "Received flow progress message");
+
+        self.handle_flow_progress_message(target_catalog, message)
+            .await
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[async_trait::async_trait]
+impl MessageConsumerT<TaskProgressMessage> for WebhookEventDispatcher {
+    #[tracing::instrument(
+        level = "debug",
+        skip_all,
+        name = "WebhookEventDispatcher[TaskProgressMessage]"
+    )]
+    async fn consume_message(
+        &self,
+        target_catalog: &Catalog,
+        message: &TaskProgressMessage,
+    ) -> Result<(), InternalError> {
+        tracing::debug!(received_message = ?message, "Received task progress message");
+
+        self.handle_task_progress_message(target_catalog, message)
+            .await
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
@@ -0,0 +1,45 @@
+// Copyright Kamu Data, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+const SIGNATURE_PREFIX: &str = "sha256=";
+
+/// Signs the request body with the subscription secret, producing the value
+/// of the [`kamu_webhooks::WEBHOOK_HEADER_SIGNATURE`] header
+pub fn sign_webhook_payload(secret: &str, body: &[u8]) -> String {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any size");
+    mac.update(body);
+    format!(
+        "{SIGNATURE_PREFIX}{}",
+        hex::encode(mac.finalize().into_bytes())
+    )
+}
+
+/// Verifies the signature header value in constant time, as receivers are
+/// expected to do
+pub fn verify_webhook_signature(secret: &str, body: &[u8], signature: &str) -> bool {
+    let Some(signature) = signature
+        .strip_prefix(SIGNATURE_PREFIX)
+        .and_then(|s| hex::decode(s).ok())
+    else {
+        return false;
+    };
+
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any size");
+    mac.update(body);
+    mac.verify_slice(&signature).is_ok()
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
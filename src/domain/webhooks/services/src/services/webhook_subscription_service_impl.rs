@@ -0,0 +1,199 @@
+// Copyright Kamu Data, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::sync::Arc;
+
+use database_common::{EntityPageListing, PaginationOpts};
+use dill::*;
+use internal_error::InternalError;
+use kamu_webhooks::*;
+use rand::Rng;
+use time_source::SystemTimeSource;
+use url::Url;
+use uuid::Uuid;
+
+use crate::is_forbidden_webhook_host;
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+const MAX_LABEL_LENGTH: usize = 100;
+const SECRET_LENGTH_BYTES: usize = 32;
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+pub struct WebhookSubscriptionServiceImpl {
+    subscription_repo: Arc<dyn WebhookSubscriptionRepository>,
+    delivery_repo: Arc<dyn WebhookDeliveryRepository>,
+    time_source: Arc<dyn SystemTimeSource>,
+}
+
+#[component(pub)]
+#[interface(dyn WebhookSubscriptionService)]
+impl WebhookSubscriptionServiceImpl {
+    pub fn new(
+        subscription_repo: Arc<dyn WebhookSubscriptionRepository>,
+        delivery_repo: Arc<dyn WebhookDeliveryRepository>,
+        time_source: Arc<dyn SystemTimeSource>,
+    ) -> Self {
+        Self {
+            subscription_repo,
+            delivery_repo,
+            time_source,
+        }
+    }
+
+    fn validate(
+        target_url: &Url,
+        label: &str,
+        event_types: &[WebhookEventType],
+    ) -> Result<(), InvalidWebhookSubscriptionError> {
+        let invalid = |reason: &str| InvalidWebhookSubscriptionError {
+            reason: reason.to_string(),
+        };
+
+        if !matches!(target_url.scheme(), "http" | "https") {
+            return Err(invalid("Target URL must use http or https scheme"));
+        }
+        if is_forbidden_webhook_host(target_url) {
+            return Err(invalid(
+                "Target URL cannot point to a local, link-local or metadata address",
+            ));
+        }
+        if label.chars().count() > MAX_LABEL_LENGTH {
+            return Err(invalid(&format!(
+                "Label cannot be longer than {MAX_LABEL_LENGTH} characters"
+            )));
+        }
+        if event_types.is_empty() {
+            return Err(invalid("At least one event type is required"));
+        }
+
+        Ok(())
+    }
+
+    fn generate_secret() -> String {
+        let mut secret_bytes = [0_u8; SECRET_LENGTH_BYTES];
+        rand::thread_rng().fill(&mut secret_bytes);
+        hex::encode(secret_bytes)
+    }
+}
+
+#[async_trait::async_trait]
+impl WebhookSubscriptionService for WebhookSubscriptionServiceImpl {
+    #[tracing::instrument(level = "info", skip_all, fields(%owner_account_id, ?dataset_id, %target_url))]
+    async fn create_subscription(
+        &self,
+        owner_account_id: &odf::AccountID,
+        dataset_id: Option<&odf::DatasetID>,
+        target_url: Url,
+        label: String,
+        mut event_types: Vec<WebhookEventType>,
+    ) -> Result<WebhookSubscription, CreateWebhookSubscriptionError> {
+        Self::validate(&target_url, &label, &event_types)
+            .map_err(CreateWebhookSubscriptionError::Invalid)?;
+
+        event_types.sort();
+        event_types.dedup();
+
+        let subscription = WebhookSubscription {
+            id: Uuid::new_v4(),
+            owner_account_id: owner_account_id.clone(),
+            dataset_id: dataset_id.cloned(),
+            target_url,
+            label,
+            event_types,
+            secret: Self::generate_secret(),
+            status: WebhookSubscriptionStatus::Enabled,
+            created_at: self.time_source.now(),
+        };
+
+        self.subscription_repo
+            .create_subscription(&subscription)
+            .await?;
+
+        Ok(subscription)
+    }
+
+    async fn get_subscription(
+        &self,
+        subscription_id: &Uuid,
+    ) -> Result<WebhookSubscription, GetWebhookSubscriptionError> {
+        self.subscription_repo
+            .get_subscription_by_id(subscription_id)
+            .await
+    }
+
+    async fn list_account_subscriptions(
+        &self,
+        account_id: &odf::AccountID,
+    ) -> Result<Vec<WebhookSubscription>, InternalError> {
+        self.subscription_repo
+            .get_subscriptions_by_account_id(account_id)
+            .await
+    }
+
+    async fn list_dataset_subscriptions(
+        &self,
+        dataset_id: &odf::DatasetID,
+    ) -> Result<Vec<WebhookSubscription>, InternalError> {
+        self.subscription_repo
+            .get_subscriptions_by_dataset_id(dataset_id)
+            .await
+    }
+
+    #[tracing::instrument(level = "info", skip_all, fields(%subscription_id, ?status))]
+    async fn set_subscription_status(
+        &self,
+        subscription_id: &Uuid,
+        status: WebhookSubscriptionStatus,
+    ) -> Result<(), UpdateWebhookSubscriptionError> {
+        self.subscription_repo
+            .update_subscription_status(subscription_id, status)
+            .await
+    }
+
+    #[tracing::instrument(level = "info", skip_all, fields(%subscription_id))]
+    async fn remove_subscription(
+        &self,
+        subscription_id: &Uuid,
+    ) -> Result<(), DeleteWebhookSubscriptionError> {
+        self.subscription_repo
+            .delete_subscription(subscription_id)
+            .await?;
+
+        self.delivery_repo
+            .delete_deliveries_by_subscription_id(subscription_id)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn list_deliveries(
+        &self,
+        subscription_id: &Uuid,
+        pagination: PaginationOpts,
+    ) -> Result<EntityPageListing<WebhookDelivery>, InternalError> {
+        let total_count = self
+            .delivery_repo
+            .get_deliveries_count_by_subscription_id(subscription_id)
+            .await?;
+
+        let list = if total_count == 0 {
+            vec![]
+        } else {
+            self.delivery_repo
+                .get_deliveries_by_subscription_id(subscription_id, &pagination)
+                .await?
+        };
+
+        Ok(EntityPageListing { list, total_count })
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
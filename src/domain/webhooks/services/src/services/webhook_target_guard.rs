@@ -0,0 +1,61 @@
+// Copyright Kamu Data, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+use url::{Host, Url};
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+/// Metadata endpoint of Alibaba Cloud, which lies outside of link-local range
+const ALIBABA_METADATA_IPV4: Ipv4Addr = Ipv4Addr::new(100, 100, 100, 200);
+
+/// IPv6 metadata endpoint of AWS
+const AWS_METADATA_IPV6: Ipv6Addr = Ipv6Addr::new(0xfd00, 0xec2, 0, 0, 0, 0, 0, 0x254);
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+/// Whether webhooks may never be delivered to the address: loopback,
+/// unspecified and link-local addresses, as well as cloud metadata endpoints,
+/// belong to the node itself rather than to a subscriber
+pub fn is_forbidden_webhook_address(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => is_forbidden_ipv4(ip),
+        IpAddr::V6(ip) => match ip.to_ipv4_mapped() {
+            Some(ip) => is_forbidden_ipv4(ip),
+            None => {
+                ip.is_loopback()
+                    || ip.is_unspecified()
+                    || (ip.segments()[0] & 0xffc0) == 0xfe80
+                    || ip == AWS_METADATA_IPV6
+            }
+        },
+    }
+}
+
+/// Checks the host of the target URL as written, without resolving it. Names
+/// are checked against [`is_forbidden_webhook_address`] once resolved at
+/// delivery time
+pub fn is_forbidden_webhook_host(target_url: &Url) -> bool {
+    match target_url.host() {
+        None => true,
+        Some(Host::Domain(domain)) => {
+            let domain = domain.trim_end_matches('.').to_ascii_lowercase();
+            domain == "localhost" || domain.ends_with(".localhost")
+        }
+        Some(Host::Ipv4(ip)) => is_forbidden_webhook_address(IpAddr::V4(ip)),
+        Some(Host::Ipv6(ip)) => is_forbidden_webhook_address(IpAddr::V6(ip)),
+    }
+}
+
+fn is_forbidden_ipv4(ip: Ipv4Addr) -> bool {
+    ip.is_loopback() || ip.is_unspecified() || ip.is_link_local() || ip == ALIBABA_METADATA_IPV4
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
@@ -0,0 +1,323 @@
+// Copyright Kamu Data, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use database_common::NoOpDatabasePlugin;
+use dill::{Catalog, CatalogBuilder};
+use kamu_webhooks::*;
+use kamu_webhooks_inmem::{
+    InMemoryWebhookDeliveryRepository,
+    InMemoryWebhookSubscriptionRepository,
+};
+use kamu_webhooks_services::{verify_webhook_signature, WebhookDeliveryAgentImpl};
+use time_source::{SystemTimeSource, SystemTimeSourceStub};
+use url::Url;
+use uuid::Uuid;
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[test_log::test(tokio::test)]
+async fn test_delivery_succeeds_with_signed_payload() {
+    let harness = WebhookDeliveryAgentHarness::new(vec![Ok(200)]);
+
+    let subscription = harness
+        .create_subscription(WebhookSubscriptionStatus::Enabled)
+        .await;
+    let delivery_id = harness.create_delivery(subscription.id).await;
+
+    harness.agent.run_due_deliveries().await.unwrap();
+
+    let requests = harness.sender.requests();
+    assert_eq!(requests.len(), 1);
+
+    let request = &requests[0];
+    assert_eq!(request.target_url, subscription.target_url);
+
+    let header = |name: &str| {
+        request
+            .headers
+            .iter()
+            .find(|(header_name, _)| *header_name == name)
+            .map(|(_, value)| value.as_str())
+            .unwrap()
+    };
+    assert_eq!(header(WEBHOOK_HEADER_EVENT), "dataset.updated");
+    assert_eq!(header(WEBHOOK_HEADER_DELIVERY), delivery_id.to_string());
+    assert!(verify_webhook_signature(
+        &subscription.secret,
+        &request.body,
+        header(WEBHOOK_HEADER_SIGNATURE)
+    ));
+    assert!(!verify_webhook_signature(
+        "wrong-secret",
+        &request.body,
+        header(WEBHOOK_HEADER_SIGNATURE)
+    ));
+
+    let event: WebhookEvent = serde_json::from_slice(&request.body).unwrap();
+    assert_eq!(event.event_type, WebhookEventType::DatasetUpdated);
+
+    let delivery = harness.get_delivery(subscription.id).await;
+    assert_eq!(delivery.status, WebhookDeliveryStatus::Succeeded);
+    assert_eq!(delivery.attempts, 1);
+    assert_eq!(delivery.response_status_code, Some(200));
+    assert_eq!(delivery.next_attempt_at, None);
+}
+
+#[test_log::test(tokio::test)]
+async fn test_delivery_retried_with_backoff() {
+    let harness =
+        WebhookDeliveryAgentHarness::new(vec![Ok(500), Err("connection refused"), Ok(204)]);
+
+    let subscription = harness
+        .create_subscription(WebhookSubscriptionStatus::Enabled)
+        .await;
+    harness.create_delivery(subscription.id).await;
+
+    // First attempt fails with an unexpected status
+    harness.agent.run_due_deliveries().await.unwrap();
+
+    let delivery = harness.get_delivery(subscription.id).await;
+    assert_eq!(delivery.status, WebhookDeliveryStatus::Pending);
+    assert_eq!(delivery.attempts, 1);
+    assert_eq!(delivery.response_status_code, Some(500));
+    assert_eq!(
+        delivery.next_attempt_at,
+        Some(harness.t0 + Duration::seconds(10))
+    );
+
+    // Nothing is attempted before the backoff expires
+    harness.agent.run_due_deliveries().await.unwrap();
+    assert_eq!(harness.sender.requests().len(), 1);
+
+    // Second attempt fails on transport level, backoff doubles
+    harness.advance_to(harness.t0 + Duration::seconds(10));
+    harness.agent.run_due_deliveries().await.unwrap();
+
+    let delivery = harness.get_delivery(subscription.id).await;
+    assert_eq!(delivery.status, WebhookDeliveryStatus::Pending);
+    assert_eq!(delivery.attempts, 2);
+    assert_eq!(delivery.response_status_code, None);
+    assert_eq!(delivery.last_error.as_deref(), Some("connection refused"));
+    assert_eq!(
+        delivery.next_attempt_at,
+        Some(harness.t0 + Duration::seconds(30))
+    );
+
+    // Third attempt succeeds
+    harness.advance_to(harness.t0 + Duration::seconds(30));
+    harness.agent.run_due_deliveries().await.unwrap();
+
+    let delivery = harness.get_delivery(subscription.id).await;
+    assert_eq!(delivery.status, WebhookDeliveryStatus::Succeeded);
+    assert_eq!(delivery.attempts, 3);
+    assert_eq!(delivery.last_error, None);
+    assert_eq!(harness.sender.requests().len(), 3);
+}
+
+#[test_log::test(tokio::test)]
+async fn test_delivery_fails_after_max_attempts() {
+    let harness = WebhookDeliveryAgentHarness::new(vec![Ok(500), Ok(502), Ok(503)]);
+
+    let subscription = harness
+        .create_subscription(WebhookSubscriptionStatus::Enabled)
+        .await;
+    harness.create_delivery(subscription.id).await;
+
+    for offset_secs in [0, 10, 30] {
+        harness.advance_to(harness.t0 + Duration::seconds(offset_secs));
+        harness.agent.run_due_deliveries().await.unwrap();
+    }
+
+    let delivery = harness.get_delivery(subscription.id).await;
+    assert_eq!(delivery.status, WebhookDeliveryStatus::Failed);
+    assert_eq!(delivery.attempts, 3);
+    assert_eq!(delivery.response_status_code, Some(503));
+    assert_eq!(delivery.next_attempt_at, None);
+
+    // No more attempts are made
+    harness.advance_to(harness.t0 + Duration::hours(1));
+    harness.agent.run_due_deliveries().await.unwrap();
+    assert_eq!(harness.sender.requests().len(), 3);
+}
+
+#[test_log::test(tokio::test)]
+async fn test_delivery_to_paused_subscription_is_dropped() {
+    let harness = WebhookDeliveryAgentHarness::new(vec![]);
+
+    let subscription = harness
+        .create_subscription(WebhookSubscriptionStatus::Paused)
+        .await;
+    harness.create_delivery(subscription.id).await;
+
+    harness.agent.run_due_deliveries().await.unwrap();
+
+    assert!(harness.sender.requests().is_empty());
+
+    let delivery = harness.get_delivery(subscription.id).await;
+    assert_eq!(delivery.status, WebhookDeliveryStatus::Failed);
+    assert_eq!(
+        delivery.last_error.as_deref(),
+        Some("Subscription was paused")
+    );
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+struct WebhookDeliveryAgentHarness {
+    _catalog: Catalog,
+    agent: Arc<dyn WebhookDeliveryAgent>,
+    subscription_repo: Arc<dyn WebhookSubscriptionRepository>,
+    delivery_repo: Arc<dyn WebhookDeliveryRepository>,
+    sender: Arc<FakeWebhookSender>,
+    time_source: Arc<SystemTimeSourceStub>,
+    t0: DateTime<Utc>,
+}
+
+impl WebhookDeliveryAgentHarness {
+    fn new(responses: Vec<Result<u16, &'static str>>) -> Self {
+        let t0 = Utc.with_ymd_and_hms(2025, 1, 1, 12, 0, 0).unwrap();
+
+        let catalog = {
+            let mut b = CatalogBuilder::new();
+
+            b.add::<WebhookDeliveryAgentImpl>()
+                .add::<InMemoryWebhookSubscriptionRepository>()
+                .add::<InMemoryWebhookDeliveryRepository>()
+                .add_value(FakeWebhookSender::new(responses))
+                .bind::<dyn WebhookSender, FakeWebhookSender>()
+                .add_value(SystemTimeSourceStub::new_set(t0))
+                .bind::<dyn SystemTimeSource, SystemTimeSourceStub>()
+                .add_value(WebhookDeliveryConfig::new(
+                    Duration::seconds(1),
+                    10,
+                    3,
+                    Duration::seconds(10),
+                    Duration::minutes(10),
+                    Duration::seconds(5),
+                ));
+
+            NoOpDatabasePlugin::init_database_components(&mut b);
+
+            b.build()
+        };
+
+        Self {
+            agent: catalog.get_one().unwrap(),
+            subscription_repo: catalog.get_one().unwrap(),
+            delivery_repo: catalog.get_one().unwrap(),
+            sender: catalog.get_one().unwrap(),
+            time_source: catalog.get_one().unwrap(),
+            _catalog: catalog,
+            t0,
+        }
+    }
+
+    fn advance_to(&self, t: DateTime<Utc>) {
+        self.time_source.set(t);
+    }
+
+    async fn create_subscription(&self, status: WebhookSubscriptionStatus) -> WebhookSubscription {
+        let subscription = WebhookSubscription {
+            id: Uuid::new_v4(),
+            owner_account_id: odf::AccountID::new_seeded_ed25519(b"alice"),
+            dataset_id: None,
+            target_url: Url::parse("https://example.com/hooks").unwrap(),
+            label: "dashboard".to_string(),
+            event_types: vec![WebhookEventType::DatasetUpdated],
+            secret: "secret".to_string(),
+            status,
+            created_at: self.t0,
+        };
+
+        self.subscription_repo
+            .create_subscription(&subscription)
+            .await
+            .unwrap();
+
+        subscription
+    }
+
+    async fn create_delivery(&self, subscription_id: Uuid) -> Uuid {
+        let delivery = WebhookDelivery::new(
+            Uuid::new_v4(),
+            subscription_id,
+            WebhookEvent {
+                event_type: WebhookEventType::DatasetUpdated,
+                event_time: self.t0,
+                account_id: odf::AccountID::new_seeded_ed25519(b"alice"),
+                dataset_id: Some(odf::DatasetID::new_seeded_ed25519(b"foo")),
+                data: serde_json::json!({
+                    "flowId": "1",
+                }),
+            },
+            self.t0,
+        );
+
+        self.delivery_repo.create_delivery(&delivery).await.unwrap();
+
+        delivery.id
+    }
+
+    async fn get_delivery(&self, subscription_id: Uuid) -> WebhookDelivery {
+        let mut deliveries = self
+            .delivery_repo
+            .get_deliveries_by_subscription_id(
+                &subscription_id,
+                &database_common::PaginationOpts {
+                    limit: 10,
+                    offset: 0,
+                },
+            )
+            .await
+            .unwrap();
+        assert_eq!(deliveries.len(), 1);
+        deliveries.remove(0)
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+struct FakeWebhookSender {
+    responses: Mutex<VecDeque<Result<u16, &'static str>>>,
+    requests: Mutex<Vec<WebhookRequest>>,
+}
+
+impl FakeWebhookSender {
+    fn new(responses: Vec<Result<u16, &'static str>>) -> Self {
+        Self {
+            responses: Mutex::new(responses.into()),
+            requests: Mutex::new(Vec::new()),
+        }
+    }
+
+    fn requests(&self) -> Vec<WebhookRequest> {
+        self.requests.lock().unwrap().clone()
+    }
+}
+
+#[async_trait::async_trait]
+impl WebhookSender for FakeWebhookSender {
+    async fn send(&self, request: WebhookRequest) -> Result<WebhookResponse, WebhookSendError> {
+        self.requests.lock().unwrap().push(request);
+
+        match self.responses.lock().unwrap().pop_front() {
+            Some(Ok(status_code)) => Ok(WebhookResponse { status_code }),
+            Some(Err(reason)) => Err(WebhookSendError {
+                reason: reason.to_string(),
+            }),
+            None => panic!("Unexpected webhook request"),
+        }
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
@@ -0,0 +1,267 @@
+// Copyright Kamu Data, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::assert_matches::assert_matches;
+use std::sync::Arc;
+
+use chrono::{TimeZone, Utc};
+use database_common::PaginationOpts;
+use dill::{Catalog, CatalogBuilder};
+use kamu_webhooks::*;
+use kamu_webhooks_inmem::{
+    InMemoryWebhookDeliveryRepository,
+    InMemoryWebhookSubscriptionRepository,
+};
+use kamu_webhooks_services::WebhookSubscriptionServiceImpl;
+use time_source::SystemTimeSourceDefault;
+use url::Url;
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[test_log::test(tokio::test)]
+async fn test_create_subscription() {
+    let harness = WebhookSubscriptionServiceHarness::new();
+
+    let subscription = harness
+        .subscription_service
+        .create_subscription(
+            &harness.account_id,
+            Some(&harness.dataset_id),
+            Url::parse("https://example.com/hooks").unwrap(),
+            "dashboard".to_string(),
+            vec![
+                WebhookEventType::FlowFailed,
+                WebhookEventType::DatasetUpdated,
+                WebhookEventType::FlowFailed,
+            ],
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(subscription.status, WebhookSubscriptionStatus::Enabled);
+    assert_eq!(
+        subscription.event_types,
+        vec![
+            WebhookEventType::DatasetUpdated,
+            WebhookEventType::FlowFailed
+        ]
+    );
+    assert_eq!(subscription.secret.len(), 64);
+
+    let stored = harness
+        .subscription_service
+        .get_subscription(&subscription.id)
+        .await
+        .unwrap();
+    assert_eq!(stored, subscription);
+
+    let by_account = harness
+        .subscription_service
+        .list_account_subscriptions(&harness.account_id)
+        .await
+        .unwrap();
+    assert_eq!(by_account, vec![subscription.clone()]);
+
+    let by_dataset = harness
+        .subscription_service
+        .list_dataset_subscriptions(&harness.dataset_id)
+        .await
+        .unwrap();
+    assert_eq!(by_dataset, vec![subscription]);
+}
+
+#[test_log::test(tokio::test)]
+async fn test_create_subscription_invalid() {
+    let harness = WebhookSubscriptionServiceHarness::new();
+
+    let create = |target_url: &str, label: String, event_types: Vec<WebhookEventType>| {
+        let target_url = Url::parse(target_url).unwrap();
+        let subscription_service = harness.subscription_service.clone();
+        let account_id = harness.account_id.clone();
+        async move {
+            subscription_service
+                .create_subscription(&account_id, None, target_url, label, event_types)
+                .await
+        }
+    };
+
+    assert_matches!(
+        create(
+            "ftp://example.com/hooks",
+            "dashboard".to_string(),
+            vec![WebhookEventType::FlowFailed]
+        )
+        .await,
+        Err(CreateWebhookSubscriptionError::Invalid(_))
+    );
+    assert_matches!(
+        create(
+            "https://example.com/hooks",
+            "x".repeat(101),
+            vec![WebhookEventType::FlowFailed]
+        )
+        .await,
+        Err(CreateWebhookSubscriptionError::Invalid(_))
+    );
+    assert_matches!(
+        create("https://example.com/hooks", "dashboard".to_string(), vec![]).await,
+        Err(CreateWebhookSubscriptionError::Invalid(_))
+    );
+    for target_url in [
+        "http://localhost:8080/hooks",
+        "http://127.0.0.1/hooks",
+        "http://[::1]/hooks",
+        "http://169.254.169.254/latest/meta-data",
+        "http://[::ffff:169.254.169.254]/latest/meta-data",
+    ] {
+        assert_matches!(
+            create(
+                target_url,
+                "dashboard".to_string(),
+                vec![WebhookEventType::FlowFailed]
+            )
+            .await,
+            Err(CreateWebhookSubscriptionError::Invalid(_)),
+            "{target_url}"
+        );
+    }
+
+    let by_account = harness
+        .subscription_service
+        .list_account_subscriptions(&harness.account_id)
+        .await
+        .unwrap();
+    assert!(by_account.is_empty());
+}
+
+#[test_log::test(tokio::test)]
+async fn test_pause_and_remove_subscription() {
+    let harness = WebhookSubscriptionServiceHarness::new();
+
+    let subscription = harness
+        .subscription_service
+        .create_subscription(
+            &harness.account_id,
+            None,
+            Url::parse("https://example.com/hooks").unwrap(),
+            "dashboard".to_string(),
+            vec![WebhookEventType::DatasetUpdated],
+        )
+        .await
+        .unwrap();
+
+    harness
+        .subscription_service
+        .set_subscription_status(&subscription.id, WebhookSubscriptionStatus::Paused)
+        .await
+        .unwrap();
+
+    let stored = harness
+        .subscription_service
+        .get_subscription(&subscription.id)
+        .await
+        .unwrap();
+    assert_eq!(stored.status, WebhookSubscriptionStatus::Paused);
+
+    let now = Utc.with_ymd_and_hms(2025, 1, 1, 12, 0, 0).unwrap();
+    harness
+        .delivery_repo
+        .create_delivery(&WebhookDelivery::new(
+            uuid::Uuid::new_v4(),
+            subscription.id,
+            WebhookEvent {
+                event_type: WebhookEventType::DatasetUpdated,
+                event_time: now,
+                account_id: harness.account_id.clone(),
+                dataset_id: Some(harness.dataset_id.clone()),
+                data: serde_json::json!({}),
+            },
+            now,
+        ))
+        .await
+        .unwrap();
+
+    let deliveries = harness
+        .subscription_service
+        .list_deliveries(
+            &subscription.id,
+            PaginationOpts {
+                limit: 10,
+                offset: 0,
+            },
+        )
+        .await
+        .unwrap();
+    assert_eq!(deliveries.total_count, 1);
+
+    harness
+        .subscription_service
+        .remove_subscription(&subscription.id)
+        .await
+        .unwrap();
+
+    assert_matches!(
+        harness
+            .subscription_service
+            .get_subscription(&subscription.id)
+            .await,
+        Err(GetWebhookSubscriptionError::NotFound(_))
+    );
+    assert_eq!(
+        harness
+            .delivery_repo
+            .get_deliveries_count_by_subscription_id(&subscription.id)
+            .await
+            .unwrap(),
+        0
+    );
+
+    assert_matches!(
+        harness
+            .subscription_service
+            .remove_subscription(&subscription.id)
+            .await,
+        Err(DeleteWebhookSubscriptionError::NotFound(_))
+    );
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+struct WebhookSubscriptionServiceHarness {
+    _catalog: Catalog,
+    subscription_service: Arc<dyn WebhookSubscriptionService>,
+    delivery_repo: Arc<dyn WebhookDeliveryRepository>,
+    account_id: odf::AccountID,
+    dataset_id: odf::DatasetID,
+}
+
+impl WebhookSubscriptionServiceHarness {
+    fn new() -> Self {
+        let catalog = {
+            let mut b = CatalogBuilder::new();
+
+            b.add::<WebhookSubscriptionServiceImpl>()
+                .add::<InMemoryWebhookSubscriptionRepository>()
+                .add::<InMemoryWebhookDeliveryRepository>()
+                .add::<SystemTimeSourceDefault>();
+
+            b.build()
+        };
+
+        Self {
+            subscription_service: catalog.get_one().unwrap(),
+            delivery_repo: catalog.get_one().unwrap(),
+            _catalog: catalog,
+            account_id: odf::AccountID::new_seeded_ed25519(b"alice"),
+            dataset_id: odf::DatasetID::new_seeded_ed25519(b"foo"),
+        }
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
@@ -45,3 +45,10 @@ kamu_cli_execute_command_e2e_test!(
 );
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+kamu_cli_execute_command_e2e_test!(
+    storage = postgres,
+    fixture = kamu_cli_e2e_repo_tests::commands::test_add_from_template
+);
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
@@ -254,3 +254,137 @@ pub async fn test_add_with_circular_dependency(kamu: KamuCliPuppet) {
 }
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+pub async fn test_add_from_template(kamu: KamuCliPuppet) {
+    std::fs::write(
+        kamu.workspace_path().join("weather.yaml"),
+        indoc::indoc! {
+            "
+            kind: DatasetSnapshot
+            version: 1
+            content:
+              name: weather.${{ vars.city }}
+              kind: Root
+              metadata:
+                - kind: SetInfo
+                  description: Weather in ${{ vars.city }}
+                  keywords:
+                    # ${{ for keyword in vars.keywords }}
+                    - ${{ vars.keyword }}
+                    # ${{ end }}
+            "
+        },
+    )
+    .unwrap();
+
+    let vars_path = kamu.workspace_path().join("cities.csv");
+    std::fs::write(
+        &vars_path,
+        indoc::indoc! {
+            "
+            city,keywords
+            berlin,Germany;Weather
+            paris,France
+            "
+        },
+    )
+    .unwrap();
+
+    kamu.assert_success_command_execution(
+        [
+            "add",
+            "--template",
+            "weather.yaml",
+            "--vars",
+            vars_path.to_str().unwrap(),
+        ],
+        None,
+        Some([
+            "Added: weather.berlin",
+            "Added: weather.paris",
+            r#"Added 2 and updated 0 dataset\(s\)"#,
+        ]),
+    )
+    .await;
+
+    std::fs::write(
+        &vars_path,
+        indoc::indoc! {
+            "
+            city,keywords
+            berlin,Germany;Weather
+            paris,France;Weather
+            "
+        },
+    )
+    .unwrap();
+
+    let assert = kamu
+        .execute([
+            "add",
+            "--template",
+            "weather.yaml",
+            "--vars",
+            vars_path.to_str().unwrap(),
+            "--dry-run",
+        ])
+        .await
+        .success();
+
+    let stdout = std::str::from_utf8(&assert.get_output().stdout).unwrap();
+    let stderr = std::str::from_utf8(&assert.get_output().stderr).unwrap();
+
+    assert!(
+        stdout.starts_with("--- weather.paris\n+++ weather.paris\n"),
+        "Unexpected output:\n{stdout}"
+    );
+    assert_eq!(
+        stdout
+            .lines()
+            .filter(
+                |line| line.starts_with('+') && line.trim_start_matches('+').trim() == "- Weather"
+            )
+            .count(),
+        1,
+        "Unexpected output:\n{stdout}"
+    );
+    assert!(
+        !stdout
+            .lines()
+            .any(|line| line.starts_with('-') && !line.starts_with("---")),
+        "Unexpected output:\n{stdout}"
+    );
+    assert!(stderr.contains("Unchanged: weather.berlin"), "{stderr}");
+    assert!(
+        stderr.contains("Would update: weather.paris: 1 event(s)"),
+        "{stderr}"
+    );
+
+    kamu.assert_success_command_execution(
+        [
+            "add",
+            "--template",
+            "weather.yaml",
+            "--vars",
+            vars_path.to_str().unwrap(),
+        ],
+        None,
+        Some([
+            "Unchanged: weather.berlin",
+            r#"Updated: weather.paris: 1 event\(s\)"#,
+            r#"Added 0 and updated 1 dataset\(s\)"#,
+        ]),
+    )
+    .await;
+
+    let dataset_names = kamu
+        .list_datasets()
+        .await
+        .into_iter()
+        .map(|dataset| dataset.name.to_string())
+        .collect::<Vec<_>>();
+
+    pretty_assertions::assert_eq!(vec!["weather.berlin", "weather.paris"], dataset_names);
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
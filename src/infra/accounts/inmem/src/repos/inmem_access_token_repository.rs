@@ -202,4 +202,23 @@ impl AccessTokenRepository for InMemoryAccessTokenRepository {
 
         Ok(account)
     }
+
+    async fn delete_access_tokens_by_account_id(
+        &self,
+        account_id: &odf::AccountID,
+    ) -> Result<(), DeleteAccessTokensError> {
+        let mut guard = self.state.lock().unwrap();
+
+        let Some(token_ids) = guard.token_hashes_by_account_id.remove(account_id) else {
+            return Ok(());
+        };
+        for token_id in &token_ids {
+            guard.tokens_by_id.remove(token_id);
+        }
+        guard
+            .token_ids_by_name
+            .retain(|_, token_id| !token_ids.contains(token_id));
+
+        Ok(())
+    }
 }
@@ -7,7 +7,7 @@
 // the Business Source License, use of this software will be governed
 // by the Apache License, Version 2.0.
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::{Arc, Mutex};
 
 use database_common::PaginationOpts;
@@ -31,6 +31,8 @@ struct State {
     accounts_by_name: HashMap<odf::AccountName, Account>,
     account_id_by_provider_identity_key: HashMap<String, odf::AccountID>,
     password_hash_by_account_name: HashMap<odf::AccountName, String>,
+    disabled_account_ids: HashSet<odf::AccountID>,
+    unverified_email_account_ids: HashSet<odf::AccountID>,
 }
 
 impl State {
@@ -40,6 +42,8 @@ impl State {
             accounts_by_name: HashMap::new(),
             account_id_by_provider_identity_key: HashMap::new(),
             password_hash_by_account_name: HashMap::new(),
+            disabled_account_ids: HashSet::new(),
+            unverified_email_account_ids: HashSet::new(),
         }
     }
 
@@ -170,6 +174,14 @@ impl AccountRepository for InMemoryAccountRepository {
 
         if updated_account.account_name != account.account_name {
             guard.accounts_by_name.remove(&account.account_name);
+            if let Some(password_hash) = guard
+                .password_hash_by_account_name
+                .remove(&account.account_name)
+            {
+                guard
+                    .password_hash_by_account_name
+                    .insert(updated_account.account_name.clone(), password_hash);
+            }
         }
         guard.accounts_by_name.insert(
             updated_account.account_name.clone(),
@@ -228,6 +240,96 @@ impl AccountRepository for InMemoryAccountRepository {
         Ok(())
     }
 
+    async fn set_account_disabled(
+        &self,
+        account_id: &odf::AccountID,
+        disabled: bool,
+    ) -> Result<(), UpdateAccountError> {
+        let mut guard = self.state.lock().unwrap();
+        if !guard.accounts_by_id.contains_key(account_id) {
+            return Err(UpdateAccountError::NotFound(AccountNotFoundByIdError {
+                account_id: account_id.clone(),
+            }));
+        }
+
+        if disabled {
+            guard.disabled_account_ids.insert(account_id.clone());
+        } else {
+            guard.disabled_account_ids.remove(account_id);
+        }
+
+        Ok(())
+    }
+
+    async fn is_account_disabled(
+        &self,
+        account_id: &odf::AccountID,
+    ) -> Result<bool, GetAccountByIdError> {
+        let guard = self.state.lock().unwrap();
+        if !guard.accounts_by_id.contains_key(account_id) {
+            return Err(GetAccountByIdError::NotFound(AccountNotFoundByIdError {
+                account_id: account_id.clone(),
+            }));
+        }
+
+        Ok(guard.disabled_account_ids.contains(account_id))
+    }
+
+    async fn set_account_email_verified(
+        &self,
+        account_id: &odf::AccountID,
+        email_verified: bool,
+    ) -> Result<(), UpdateAccountError> {
+        let mut guard = self.state.lock().unwrap();
+        if !guard.accounts_by_id.contains_key(account_id) {
+            return Err(UpdateAccountError::NotFound(AccountNotFoundByIdError {
+                account_id: account_id.clone(),
+            }));
+        }
+
+        if email_verified {
+            guard.unverified_email_account_ids.remove(account_id);
+        } else {
+            guard
+                .unverified_email_account_ids
+                .insert(account_id.clone());
+        }
+
+        Ok(())
+    }
+
+    async fn is_account_email_verified(
+        &self,
+        account_id: &odf::AccountID,
+    ) -> Result<bool, GetAccountByIdError> {
+        let guard = self.state.lock().unwrap();
+        if !guard.accounts_by_id.contains_key(account_id) {
+            return Err(GetAccountByIdError::NotFound(AccountNotFoundByIdError {
+                account_id: account_id.clone(),
+            }));
+        }
+
+        Ok(!guard.unverified_email_account_ids.contains(account_id))
+    }
+
+    async fn delete_account(&self, account_id: &odf::AccountID) -> Result<(), DeleteAccountError> {
+        let mut guard = self.state.lock().unwrap();
+        let Some(account) = guard.accounts_by_id.remove(account_id) else {
+            return Err(DeleteAccountError::NotFound(AccountNotFoundByIdError {
+                account_id: account_id.clone(),
+            }));
+        };
+
+        guard.accounts_by_name.remove(&account.account_name);
+        guard
+            .account_id_by_provider_identity_key
+            .remove(&account.provider_identity_key);
+        guard.disabled_account_ids.remove(account_id);
+        guard.unverified_email_account_ids.remove(account_id);
+
+        Ok(())
+    }
+
     async fn get_account_by_id(
         &self,
         account_id: &odf::AccountID,
@@ -352,6 +454,14 @@ impl PasswordHashRepository for InMemoryAccountRepository {
         Ok(())
     }
 
+    async fn update_password_hash(
+        &self,
+        account_name: &odf::AccountName,
+        password_hash: String,
+    ) -> Result<(), SavePasswordHashError> {
+        self.save_password_hash(account_name, password_hash).await
+    }
+
     async fn find_password_hash_by_account_name(
         &self,
         account_name: &odf::AccountName,
@@ -363,6 +473,15 @@ impl PasswordHashRepository for InMemoryAccountRepository {
             .cloned();
         Ok(maybe_hash_as_string)
     }
+
+    async fn delete_password_hash(
+        &self,
+        account_name: &odf::AccountName,
+    ) -> Result<(), DeletePasswordHashError> {
+        let mut guard = self.state.lock().unwrap();
+        guard.password_hash_by_account_name.remove(account_name);
+        Ok(())
+    }
 }
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
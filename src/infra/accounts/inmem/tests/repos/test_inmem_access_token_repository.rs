@@ -77,6 +77,14 @@ database_transactional_test!(
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
 
+database_transactional_test!(
+    storage = inmem,
+    fixture = kamu_accounts_repo_tests::test_delete_access_tokens_by_account_id,
+    harness = InMemoryAccessTokenRepositoryHarness
+);
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
 struct InMemoryAccessTokenRepositoryHarness {
     catalog: Catalog,
 }
@@ -29,6 +29,30 @@ database_transactional_test!(
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
 
+database_transactional_test!(
+    storage = inmem,
+    fixture = kamu_accounts_repo_tests::test_update_password_hash,
+    harness = InMemoryPasswordHashRepositoryHarness
+);
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+database_transactional_test!(
+    storage = inmem,
+    fixture = kamu_accounts_repo_tests::test_password_hash_follows_account_rename,
+    harness = InMemoryPasswordHashRepositoryHarness
+);
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+database_transactional_test!(
+    storage = inmem,
+    fixture = kamu_accounts_repo_tests::test_delete_password_hash,
+    harness = InMemoryPasswordHashRepositoryHarness
+);
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
 struct InMemoryPasswordHashRepositoryHarness {
     catalog: Catalog,
 }
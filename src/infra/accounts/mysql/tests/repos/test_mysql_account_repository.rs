@@ -159,6 +159,30 @@ database_transactional_test!(
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
 
+database_transactional_test!(
+    storage = mysql,
+    fixture = kamu_accounts_repo_tests::test_disable_account,
+    harness = MySqlAccountRepositoryHarness
+);
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+database_transactional_test!(
+    storage = mysql,
+    fixture = kamu_accounts_repo_tests::test_account_email_verification,
+    harness = MySqlAccountRepositoryHarness
+);
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+database_transactional_test!(
+    storage = mysql,
+    fixture = kamu_accounts_repo_tests::test_delete_account,
+    harness = MySqlAccountRepositoryHarness
+);
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
 struct MySqlAccountRepositoryHarness {
     catalog: Catalog,
 }
@@ -31,6 +31,30 @@ database_transactional_test!(
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
 
+database_transactional_test!(
+    storage = mysql,
+    fixture = kamu_accounts_repo_tests::test_update_password_hash,
+    harness = MySqlPasswordHashRepositoryHarness
+);
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+database_transactional_test!(
+    storage = mysql,
+    fixture = kamu_accounts_repo_tests::test_password_hash_follows_account_rename,
+    harness = MySqlPasswordHashRepositoryHarness
+);
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+database_transactional_test!(
+    storage = mysql,
+    fixture = kamu_accounts_repo_tests::test_delete_password_hash,
+    harness = MySqlPasswordHashRepositoryHarness
+);
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
 struct MySqlPasswordHashRepositoryHarness {
     catalog: Catalog,
 }
@@ -252,6 +252,27 @@ impl AccessTokenRepository for PostgresAccessTokenRepository {
             ))
         }
     }
+
+    async fn delete_access_tokens_by_account_id(
+        &self,
+        account_id: &odf::AccountID,
+    ) -> Result<(), DeleteAccessTokensError> {
+        let mut tr = self.transaction.lock().await;
+
+        let connection_mut = tr.connection_mut().await?;
+
+        sqlx::query!(
+            r#"
+                DELETE FROM access_tokens WHERE account_id = $1
+            "#,
+            account_id.to_string(),
+        )
+        .execute(connection_mut)
+        .await
+        .int_err()?;
+
+        Ok(())
+    }
 }
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
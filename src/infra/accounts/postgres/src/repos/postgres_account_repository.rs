@@ -97,6 +97,16 @@ impl AccountRepository for PostgresAccountRepository {
 
         let connection_mut = tr.connection_mut().await?;
 
+        let maybe_old_account_name = sqlx::query_scalar!(
+            r#"
+            SELECT account_name FROM accounts WHERE id = $1
+            "#,
+            updated_account.id.to_string(),
+        )
+        .fetch_optional(&mut *connection_mut)
+        .await
+        .int_err()?;
+
         let update_result = sqlx::query!(
             r#"
             UPDATE accounts SET
@@ -122,7 +132,7 @@ impl AccountRepository for PostgresAccountRepository {
             updated_account.provider.to_string(),
             updated_account.provider_identity_key.to_string(),
         )
-        .execute(connection_mut)
+        .execute(&mut *connection_mut)
         .await
         .map_err(|e: sqlx::Error| match e {
             sqlx::Error::Database(e) => {
@@ -143,6 +153,23 @@ impl AccountRepository for PostgresAccountRepository {
             }));
         }
 
+        // Password hashes are keyed by account name, so they follow renames
+        if let Some(old_account_name) = maybe_old_account_name.filter(|old_account_name| {
+            !old_account_name.eq_ignore_ascii_case(updated_account.account_name.as_str())
+        }) {
+            sqlx::query!(
+                r#"
+                UPDATE accounts_passwords SET account_name = $2
+                    WHERE lower(account_name) = lower($1)
+                "#,
+                old_account_name,
+                updated_account.account_name.to_string(),
+            )
+            .execute(connection_mut)
+            .await
+            .int_err()?;
+        }
+
         Ok(())
     }
 
@@ -186,6 +213,138 @@ impl AccountRepository for PostgresAccountRepository {
         Ok(())
     }
 
+    async fn set_account_disabled(
+        &self,
+        account_id: &odf::AccountID,
+        disabled: bool,
+    ) -> Result<(), UpdateAccountError> {
+        let mut tr = self.transaction.lock().await;
+
+        let connection_mut = tr.connection_mut().await?;
+
+        let update_result = sqlx::query!(
+            r#"
+            UPDATE accounts SET disabled = $1 WHERE id = $2
+            "#,
+            disabled,
+            account_id.to_string(),
+        )
+        .execute(connection_mut)
+        .await
+        .int_err()?;
+
+        if update_result.rows_affected() == 0 {
+            return Err(UpdateAccountError::NotFound(AccountNotFoundByIdError {
+                account_id: account_id.clone(),
+            }));
+        }
+
+        Ok(())
+    }
+
+    async fn is_account_disabled(
+        &self,
+        account_id: &odf::AccountID,
+    ) -> Result<bool, GetAccountByIdError> {
+        let mut tr = self.transaction.lock().await;
+
+        let connection_mut = tr.connection_mut().await?;
+
+        let maybe_disabled = sqlx::query_scalar!(
+            r#"
+            SELECT disabled FROM accounts WHERE id = $1
+            "#,
+            account_id.to_string(),
+        )
+        .fetch_optional(connection_mut)
+        .await
+        .int_err()?;
+
+        maybe_disabled.ok_or_else(|| {
+            GetAccountByIdError::NotFound(AccountNotFoundByIdError {
+                account_id: account_id.clone(),
+            })
+        })
+    }
+
+    async fn set_account_email_verified(
+        &self,
+        account_id: &odf::AccountID,
+        email_verified: bool,
+    ) -> Result<(), UpdateAccountError> {
+        let mut tr = self.transaction.lock().await;
+
+        let connection_mut = tr.connection_mut().await?;
+
+        let update_result = sqlx::query!(
+            r#"
+            UPDATE accounts SET email_verified = $1 WHERE id = $2
+            "#,
+            email_verified,
+            account_id.to_string(),
+        )
+        .execute(connection_mut)
+        .await
+        .int_err()?;
+
+        if update_result.rows_affected() == 0 {
+            return Err(UpdateAccountError::NotFound(AccountNotFoundByIdError {
+                account_id: account_id.clone(),
+            }));
+        }
+
+        Ok(())
+    }
+
+    async fn is_account_email_verified(
+        &self,
+        account_id: &odf::AccountID,
+    ) -> Result<bool, GetAccountByIdError> {
+        let mut tr = self.transaction.lock().await;
+
+        let connection_mut = tr.connection_mut().await?;
+
+        let maybe_email_verified = sqlx::query_scalar!(
+            r#"
+            SELECT email_verified FROM accounts WHERE id = $1
+            "#,
+            account_id.to_string(),
+        )
+        .fetch_optional(connection_mut)
+        .await
+        .int_err()?;
+
+        maybe_email_verified.ok_or_else(|| {
+            GetAccountByIdError::NotFound(AccountNotFoundByIdError {
+                account_id: account_id.clone(),
+            })
+        })
+    }
+
+    async fn delete_account(&self, account_id: &odf::AccountID) -> Result<(), DeleteAccountError> {
+        let mut tr = self.transaction.lock().await;
+
+        let connection_mut = tr.connection_mut().await?;
+
+        let delete_result = sqlx::query!(
+            r#"
+            DELETE FROM accounts WHERE id = $1
+            "#,
+            account_id.to_string(),
+        )
+        .execute(connection_mut)
+        .await
+        .int_err()?;
+
+        if delete_result.rows_affected() == 0 {
+            return Err(DeleteAccountError::NotFound(AccountNotFoundByIdError {
+                account_id: account_id.clone(),
+            }));
+        }
+
+        Ok(())
+    }
+
     async fn get_account_by_id(
         &self,
         account_id: &odf::AccountID,
@@ -470,6 +629,44 @@ impl PasswordHashRepository for PostgresAccountRepository {
         Ok(())
     }
 
+    async fn update_password_hash(
+        &self,
+        account_name: &odf::AccountName,
+        password_hash: String,
+    ) -> Result<(), SavePasswordHashError> {
+        let mut tr = self.transaction.lock().await;
+
+        let connection_mut = tr.connection_mut().await?;
+
+        let update_result = sqlx::query!(
+            r#"
+            UPDATE accounts_passwords SET password_hash = $2
+                WHERE lower(account_name) = lower($1)
+            "#,
+            account_name.to_string(),
+            password_hash,
+        )
+        .execute(&mut *connection_mut)
+        .await
+        .int_err()?;
+
+        if update_result.rows_affected() == 0 {
+            sqlx::query!(
+                r#"
+                INSERT INTO accounts_passwords (account_name, password_hash)
+                    VALUES ($1, $2)
+                "#,
+                account_name.to_string(),
+                password_hash,
+            )
+            .execute(connection_mut)
+            .await
+            .int_err()?;
+        }
+
+        Ok(())
+    }
+
     async fn find_password_hash_by_account_name(
         &self,
         account_name: &odf::AccountName,
@@ -492,6 +689,28 @@ impl PasswordHashRepository for PostgresAccountRepository {
 
         Ok(maybe_password_row.map(|password_row| password_row.password_hash))
     }
+
+    async fn delete_password_hash(
+        &self,
+        account_name: &odf::AccountName,
+    ) -> Result<(), DeletePasswordHashError> {
+        let mut tr = self.transaction.lock().await;
+
+        let connection_mut = tr.connection_mut().await?;
+
+        sqlx::query!(
+            r#"
+            DELETE FROM accounts_passwords
+                WHERE lower(account_name) = lower($1)
+            "#,
+            account_name.to_string(),
+        )
+        .execute(connection_mut)
+        .await
+        .int_err()?;
+
+        Ok(())
+    }
 }
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
@@ -31,6 +31,30 @@ database_transactional_test!(
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
 
+database_transactional_test!(
+    storage = postgres,
+    fixture = kamu_accounts_repo_tests::test_update_password_hash,
+    harness = PostgresPasswordHashRepositoryHarness
+);
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+database_transactional_test!(
+    storage = postgres,
+    fixture = kamu_accounts_repo_tests::test_password_hash_follows_account_rename,
+    harness = PostgresPasswordHashRepositoryHarness
+);
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+database_transactional_test!(
+    storage = postgres,
+    fixture = kamu_accounts_repo_tests::test_delete_password_hash,
+    harness = PostgresPasswordHashRepositoryHarness
+);
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
 struct PostgresPasswordHashRepositoryHarness {
     catalog: Catalog,
 }
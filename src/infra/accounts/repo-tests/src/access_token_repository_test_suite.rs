@@ -15,7 +15,12 @@ use dill::Catalog;
 use kamu_accounts::*;
 use uuid::Uuid;
 
-use crate::{make_test_access_token, make_test_account, GITHUB_ACCOUNT_ID_WASYA};
+use crate::{
+    make_test_access_token,
+    make_test_account,
+    GITHUB_ACCOUNT_ID_PETYA,
+    GITHUB_ACCOUNT_ID_WASYA,
+};
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
 
@@ -295,3 +300,78 @@ pub async fn test_find_account_by_active_token_id(catalog: &Catalog) {
 }
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+pub async fn test_delete_access_tokens_by_account_id(catalog: &Catalog) {
+    let account_wasya = make_test_account(
+        "wasya",
+        "wasya@example.com",
+        kamu_adapter_oauth::PROVIDER_GITHUB,
+        GITHUB_ACCOUNT_ID_WASYA,
+    );
+    let account_petya = make_test_account(
+        "petya",
+        "petya@example.com",
+        kamu_adapter_oauth::PROVIDER_GITHUB,
+        GITHUB_ACCOUNT_ID_PETYA,
+    );
+
+    let account_repo = catalog.get_one::<dyn AccountRepository>().unwrap();
+    let access_token_repo = catalog.get_one::<dyn AccessTokenRepository>().unwrap();
+
+    account_repo.create_account(&account_wasya).await.unwrap();
+    account_repo.create_account(&account_petya).await.unwrap();
+
+    let foo_access_token = make_test_access_token("foo", None, "wasya");
+    let bar_access_token = make_test_access_token("bar", None, "wasya");
+    let baz_access_token = make_test_access_token("baz", None, "petya");
+
+    for access_token in [&foo_access_token, &bar_access_token, &baz_access_token] {
+        access_token_repo
+            .save_access_token(access_token)
+            .await
+            .unwrap();
+    }
+
+    // Revoked tokens are removed as well
+    access_token_repo
+        .mark_revoked(&bar_access_token.id, Utc::now().round_subsecs(6))
+        .await
+        .unwrap();
+
+    access_token_repo
+        .delete_access_tokens_by_account_id(&account_wasya.id)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        access_token_repo
+            .get_access_tokens_count_by_account_id(&account_wasya.id)
+            .await
+            .unwrap(),
+        0
+    );
+    assert_matches!(
+        access_token_repo
+            .get_token_by_id(&foo_access_token.id)
+            .await,
+        Err(GetAccessTokenError::NotFound(_))
+    );
+    assert_matches!(
+        access_token_repo
+            .get_token_by_id(&bar_access_token.id)
+            .await,
+        Err(GetAccessTokenError::NotFound(_))
+    );
+    assert_matches!(
+        access_token_repo.get_token_by_id(&baz_access_token.id).await,
+        Ok(access_token) if access_token == baz_access_token
+    );
+
+    // The name of a deleted token can be reused
+    access_token_repo
+        .save_access_token(&make_test_access_token("foo", None, "wasya"))
+        .await
+        .unwrap();
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
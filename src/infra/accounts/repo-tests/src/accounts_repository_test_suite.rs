@@ -619,3 +619,128 @@ pub async fn test_update_account_duplicate_provider_identity(catalog: &Catalog)
 }
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+pub async fn test_disable_account(catalog: &Catalog) {
+    let account = make_test_account("wasya", "wasya@example.com", PROVIDER_PASSWORD, "wasya");
+
+    let account_repo = catalog.get_one::<dyn AccountRepository>().unwrap();
+
+    account_repo.create_account(&account).await.unwrap();
+
+    assert_matches!(
+        account_repo.is_account_disabled(&account.id).await,
+        Ok(false)
+    );
+
+    account_repo
+        .set_account_disabled(&account.id, true)
+        .await
+        .unwrap();
+    assert_matches!(
+        account_repo.is_account_disabled(&account.id).await,
+        Ok(true)
+    );
+
+    account_repo
+        .set_account_disabled(&account.id, false)
+        .await
+        .unwrap();
+    assert_matches!(
+        account_repo.is_account_disabled(&account.id).await,
+        Ok(false)
+    );
+
+    let wrong_id = odf::AccountID::new_seeded_ed25519(b"wrong");
+    assert_matches!(
+        account_repo.set_account_disabled(&wrong_id, true).await,
+        Err(UpdateAccountError::NotFound(_))
+    );
+    assert_matches!(
+        account_repo.is_account_disabled(&wrong_id).await,
+        Err(GetAccountByIdError::NotFound(_))
+    );
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+pub async fn test_account_email_verification(catalog: &Catalog) {
+    let account = make_test_account("wasya", "wasya@example.com", PROVIDER_PASSWORD, "wasya");
+
+    let account_repo = catalog.get_one::<dyn AccountRepository>().unwrap();
+
+    account_repo.create_account(&account).await.unwrap();
+
+    assert_matches!(
+        account_repo.is_account_email_verified(&account.id).await,
+        Ok(true)
+    );
+
+    account_repo
+        .set_account_email_verified(&account.id, false)
+        .await
+        .unwrap();
+    assert_matches!(
+        account_repo.is_account_email_verified(&account.id).await,
+        Ok(false)
+    );
+
+    account_repo
+        .set_account_email_verified(&account.id, true)
+        .await
+        .unwrap();
+    assert_matches!(
+        account_repo.is_account_email_verified(&account.id).await,
+        Ok(true)
+    );
+
+    let wrong_id = odf::AccountID::new_seeded_ed25519(b"wrong");
+    assert_matches!(
+        account_repo
+            .set_account_email_verified(&wrong_id, true)
+            .await,
+        Err(UpdateAccountError::NotFound(_))
+    );
+    assert_matches!(
+        account_repo.is_account_email_verified(&wrong_id).await,
+        Err(GetAccountByIdError::NotFound(_))
+    );
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+pub async fn test_delete_account(catalog: &Catalog) {
+    let account_1 = make_test_account("wasya", "wasya@example.com", PROVIDER_PASSWORD, "wasya");
+    let account_2 = make_test_account("petya", "petya@example.com", PROVIDER_PASSWORD, "petya");
+
+    let account_repo = catalog.get_one::<dyn AccountRepository>().unwrap();
+
+    account_repo.create_account(&account_1).await.unwrap();
+    account_repo.create_account(&account_2).await.unwrap();
+
+    account_repo.delete_account(&account_1.id).await.unwrap();
+
+    assert_matches!(
+        account_repo.get_account_by_id(&account_1.id).await,
+        Err(GetAccountByIdError::NotFound(_))
+    );
+    assert_matches!(
+        account_repo
+            .find_account_id_by_name(&account_1.account_name)
+            .await,
+        Ok(None)
+    );
+    assert_matches!(
+        account_repo.get_account_by_id(&account_2.id).await,
+        Ok(account) if account == account_2
+    );
+
+    assert_matches!(
+        account_repo.delete_account(&account_1.id).await,
+        Err(DeleteAccountError::NotFound(_))
+    );
+
+    // Name and email become available again
+    account_repo.create_account(&account_1).await.unwrap();
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
@@ -76,3 +76,135 @@ pub async fn test_store_couple_account_passwords(catalog: &Catalog) {
 }
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+pub async fn test_update_password_hash(catalog: &Catalog) {
+    let account_repo = catalog.get_one::<dyn AccountRepository>().unwrap();
+    let password_hash_repo = catalog.get_one::<dyn PasswordHashRepository>().unwrap();
+
+    let account_wasya = make_test_account("wasya", "wasya@example.com", PROVIDER_PASSWORD, "wasya");
+    account_repo.create_account(&account_wasya).await.unwrap();
+
+    let hash_old = make_password_hash("password_old", &generate_salt());
+    let hash_new = make_password_hash("password_new", &generate_salt());
+
+    // Missing hash gets created
+    password_hash_repo
+        .update_password_hash(&account_wasya.account_name, hash_old.to_string())
+        .await
+        .unwrap();
+    assert_eq!(
+        password_hash_repo
+            .find_password_hash_by_account_name(&account_wasya.account_name)
+            .await
+            .unwrap(),
+        Some(hash_old.to_string())
+    );
+
+    // Existing hash gets replaced
+    password_hash_repo
+        .update_password_hash(&account_wasya.account_name, hash_new.to_string())
+        .await
+        .unwrap();
+    assert_eq!(
+        password_hash_repo
+            .find_password_hash_by_account_name(&account_wasya.account_name)
+            .await
+            .unwrap(),
+        Some(hash_new.to_string())
+    );
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+pub async fn test_password_hash_follows_account_rename(catalog: &Catalog) {
+    let account_repo = catalog.get_one::<dyn AccountRepository>().unwrap();
+    let password_hash_repo = catalog.get_one::<dyn PasswordHashRepository>().unwrap();
+
+    let account_wasya = make_test_account("wasya", "wasya@example.com", PROVIDER_PASSWORD, "wasya");
+    account_repo.create_account(&account_wasya).await.unwrap();
+
+    let hash_wasya = make_password_hash("password_wasya", &generate_salt());
+    password_hash_repo
+        .save_password_hash(&account_wasya.account_name, hash_wasya.to_string())
+        .await
+        .unwrap();
+
+    let new_account_name = odf::AccountName::new_unchecked("vasily");
+    account_repo
+        .update_account(kamu_accounts::Account {
+            account_name: new_account_name.clone(),
+            provider_identity_key: new_account_name.to_string(),
+            ..account_wasya.clone()
+        })
+        .await
+        .unwrap();
+
+    assert_eq!(
+        password_hash_repo
+            .find_password_hash_by_account_name(&account_wasya.account_name)
+            .await
+            .unwrap(),
+        None
+    );
+    assert_eq!(
+        password_hash_repo
+            .find_password_hash_by_account_name(&new_account_name)
+            .await
+            .unwrap(),
+        Some(hash_wasya.to_string())
+    );
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+pub async fn test_delete_password_hash(catalog: &Catalog) {
+    let account_repo = catalog.get_one::<dyn AccountRepository>().unwrap();
+    let password_hash_repo = catalog.get_one::<dyn PasswordHashRepository>().unwrap();
+
+    let account_wasya = make_test_account("wasya", "wasya@example.com", PROVIDER_PASSWORD, "wasya");
+    let account_petya = make_test_account("petya", "petya@example.com", PROVIDER_PASSWORD, "petya");
+
+    account_repo.create_account(&account_wasya).await.unwrap();
+    account_repo.create_account(&account_petya).await.unwrap();
+
+    let hash_wasya = make_password_hash("password_wasya", &generate_salt());
+    let hash_petya = make_password_hash("password_petya", &generate_salt());
+
+    password_hash_repo
+        .save_password_hash(&account_wasya.account_name, hash_wasya.to_string())
+        .await
+        .unwrap();
+    password_hash_repo
+        .save_password_hash(&account_petya.account_name, hash_petya.to_string())
+        .await
+        .unwrap();
+
+    // Account names are case-insensitive
+    password_hash_repo
+        .delete_password_hash(&odf::AccountName::new_unchecked("WaSyA"))
+        .await
+        .unwrap();
+
+    assert_eq!(
+        password_hash_repo
+            .find_password_hash_by_account_name(&account_wasya.account_name)
+            .await
+            .unwrap(),
+        None
+    );
+    assert_eq!(
+        password_hash_repo
+            .find_password_hash_by_account_name(&account_petya.account_name)
+            .await
+            .unwrap(),
+        Some(hash_petya.to_string())
+    );
+
+    // Deleting a missing hash is not an error
+    password_hash_repo
+        .delete_password_hash(&account_wasya.account_name)
+        .await
+        .unwrap();
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
@@ -79,6 +79,14 @@ database_transactional_test!(
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
 
+database_transactional_test!(
+    storage = sqlite,
+    fixture = kamu_accounts_repo_tests::test_delete_access_tokens_by_account_id,
+    harness = SqliteAccessTokenRepositoryHarness
+);
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
 struct SqliteAccessTokenRepositoryHarness {
     catalog: Catalog,
 }
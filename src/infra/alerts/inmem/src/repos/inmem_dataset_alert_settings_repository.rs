@@ -0,0 +1,109 @@
+// Copyright Kamu Data, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use chrono::{DateTime, Utc};
+use dill::*;
+use internal_error::InternalError;
+
+use crate::domain::*;
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+pub struct InMemoryDatasetAlertSettingsRepository {
+    state: Arc<Mutex<State>>,
+}
+
+#[derive(Default)]
+struct State {
+    settings_by_dataset_id: HashMap<odf::DatasetID, DatasetAlertSettings>,
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[component(pub)]
+#[interface(dyn DatasetAlertSettingsRepository)]
+#[scope(Singleton)]
+impl InMemoryDatasetAlertSettingsRepository {
+    pub fn new() -> Self {
+        Self {
+            state: Arc::new(Mutex::new(State::default())),
+        }
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[async_trait::async_trait]
+impl DatasetAlertSettingsRepository for InMemoryDatasetAlertSettingsRepository {
+    async fn save_settings(&self, settings: &DatasetAlertSettings) -> Result<(), InternalError> {
+        let mut guard = self.state.lock().unwrap();
+        guard
+            .settings_by_dataset_id
+            .insert(settings.dataset_id.clone(), settings.clone());
+        Ok(())
+    }
+
+    async fn get_settings(
+        &self,
+        dataset_id: &odf::DatasetID,
+    ) -> Result<Option<DatasetAlertSettings>, InternalError> {
+        let guard = self.state.lock().unwrap();
+        Ok(guard.settings_by_dataset_id.get(dataset_id).cloned())
+    }
+
+    async fn get_settings_with_stale_after(
+        &self,
+    ) -> Result<Vec<DatasetAlertSettings>, InternalError> {
+        let guard = self.state.lock().unwrap();
+        let mut settings: Vec<_> = guard
+            .settings_by_dataset_id
+            .values()
+            .filter(|s| s.stale_after.is_some())
+            .cloned()
+            .collect();
+        settings.sort_by(|a, b| a.dataset_id.cmp(&b.dataset_id));
+        Ok(settings)
+    }
+
+    async fn mark_dataset_updated(
+        &self,
+        dataset_id: &odf::DatasetID,
+        updated_at: DateTime<Utc>,
+    ) -> Result<(), InternalError> {
+        let mut guard = self.state.lock().unwrap();
+        if let Some(settings) = guard.settings_by_dataset_id.get_mut(dataset_id) {
+            settings.last_updated_at = updated_at;
+            settings.stale_notified_at = None;
+        }
+        Ok(())
+    }
+
+    async fn mark_stale_notified(
+        &self,
+        dataset_id: &odf::DatasetID,
+        notified_at: DateTime<Utc>,
+    ) -> Result<(), InternalError> {
+        let mut guard = self.state.lock().unwrap();
+        if let Some(settings) = guard.settings_by_dataset_id.get_mut(dataset_id) {
+            settings.stale_notified_at = Some(notified_at);
+        }
+        Ok(())
+    }
+
+    async fn delete_settings(&self, dataset_id: &odf::DatasetID) -> Result<(), InternalError> {
+        let mut guard = self.state.lock().unwrap();
+        guard.settings_by_dataset_id.remove(dataset_id);
+        Ok(())
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
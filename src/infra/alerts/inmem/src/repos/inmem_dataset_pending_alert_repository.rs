@@ -0,0 +1,76 @@
+// Copyright Kamu Data, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use dill::*;
+use internal_error::InternalError;
+
+use crate::domain::*;
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+pub struct InMemoryDatasetPendingAlertRepository {
+    state: Arc<Mutex<State>>,
+}
+
+#[derive(Default)]
+struct State {
+    alerts_by_key: HashMap<String, DatasetPendingAlert>,
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[component(pub)]
+#[interface(dyn DatasetPendingAlertRepository)]
+#[scope(Singleton)]
+impl InMemoryDatasetPendingAlertRepository {
+    pub fn new() -> Self {
+        Self {
+            state: Arc::new(Mutex::new(State::default())),
+        }
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[async_trait::async_trait]
+impl DatasetPendingAlertRepository for InMemoryDatasetPendingAlertRepository {
+    async fn save_pending_alert(&self, alert: &DatasetPendingAlert) -> Result<(), InternalError> {
+        let mut guard = self.state.lock().unwrap();
+        guard
+            .alerts_by_key
+            .entry(alert.alert_key.clone())
+            .or_insert_with(|| alert.clone());
+        Ok(())
+    }
+
+    async fn get_pending_alerts(
+        &self,
+        limit: usize,
+    ) -> Result<Vec<DatasetPendingAlert>, InternalError> {
+        let guard = self.state.lock().unwrap();
+        let mut alerts: Vec<_> = guard.alerts_by_key.values().cloned().collect();
+        alerts.sort_by(|a, b| {
+            a.created_at
+                .cmp(&b.created_at)
+                .then_with(|| a.alert_key.cmp(&b.alert_key))
+        });
+        alerts.truncate(limit);
+        Ok(alerts)
+    }
+
+    async fn delete_pending_alert(&self, alert_key: &str) -> Result<(), InternalError> {
+        let mut guard = self.state.lock().unwrap();
+        guard.alerts_by_key.remove(alert_key);
+        Ok(())
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
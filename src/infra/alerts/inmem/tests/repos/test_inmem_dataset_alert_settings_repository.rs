@@ -0,0 +1,72 @@
+// Copyright Kamu Data, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use database_common_macros::database_transactional_test;
+use dill::{Catalog, CatalogBuilder};
+use kamu_alerts_inmem::InMemoryDatasetAlertSettingsRepository;
+use kamu_alerts_repo_tests::dataset_alert_settings_repo;
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+database_transactional_test!(
+    storage = inmem,
+    fixture = dataset_alert_settings_repo::test_missing_settings_not_found,
+    harness = InMemoryDatasetAlertSettingsRepositoryHarness
+);
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+database_transactional_test!(
+    storage = inmem,
+    fixture = dataset_alert_settings_repo::test_save_and_get_settings,
+    harness = InMemoryDatasetAlertSettingsRepositoryHarness
+);
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+database_transactional_test!(
+    storage = inmem,
+    fixture = dataset_alert_settings_repo::test_get_settings_with_stale_after,
+    harness = InMemoryDatasetAlertSettingsRepositoryHarness
+);
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+database_transactional_test!(
+    storage = inmem,
+    fixture = dataset_alert_settings_repo::test_mark_dataset_updated_and_stale_notified,
+    harness = InMemoryDatasetAlertSettingsRepositoryHarness
+);
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+database_transactional_test!(
+    storage = inmem,
+    fixture = dataset_alert_settings_repo::test_delete_settings,
+    harness = InMemoryDatasetAlertSettingsRepositoryHarness
+);
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+struct InMemoryDatasetAlertSettingsRepositoryHarness {
+    catalog: Catalog,
+}
+
+impl InMemoryDatasetAlertSettingsRepositoryHarness {
+    pub fn new() -> Self {
+        let mut catalog_builder = CatalogBuilder::new();
+        catalog_builder.add::<InMemoryDatasetAlertSettingsRepository>();
+
+        Self {
+            catalog: catalog_builder.build(),
+        }
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
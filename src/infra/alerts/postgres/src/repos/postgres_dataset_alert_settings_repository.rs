@@ -0,0 +1,241 @@
+// Copyright Kamu Data, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use chrono::{DateTime, Duration, Utc};
+use database_common::{TransactionRef, TransactionRefT};
+use dill::{component, interface};
+use email_utils::Email;
+use internal_error::{InternalError, ResultIntoInternal};
+use sqlx::types::Json;
+
+use crate::domain::*;
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+pub struct PostgresDatasetAlertSettingsRepository {
+    transaction: TransactionRefT<sqlx::Postgres>,
+}
+
+#[component(pub)]
+#[interface(dyn DatasetAlertSettingsRepository)]
+impl PostgresDatasetAlertSettingsRepository {
+    pub fn new(transaction: TransactionRef) -> Self {
+        Self {
+            transaction: transaction.into(),
+        }
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[async_trait::async_trait]
+impl DatasetAlertSettingsRepository for PostgresDatasetAlertSettingsRepository {
+    async fn save_settings(&self, settings: &DatasetAlertSettings) -> Result<(), InternalError> {
+        let mut tr = self.transaction.lock().await;
+        let connection_mut = tr.connection_mut().await?;
+
+        let dataset_id = settings.dataset_id.to_string();
+        let stale_after_secs = settings.stale_after.map(|d| d.num_seconds());
+
+        sqlx::query!(
+            r#"
+            INSERT INTO dataset_alert_settings (dataset_id, notify_owner, recipients, notify_on_flow_failure, stale_after_secs, created_at, last_updated_at, stale_notified_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+            ON CONFLICT (dataset_id) DO UPDATE SET
+                notify_owner = excluded.notify_owner,
+                recipients = excluded.recipients,
+                notify_on_flow_failure = excluded.notify_on_flow_failure,
+                stale_after_secs = excluded.stale_after_secs,
+                created_at = excluded.created_at,
+                last_updated_at = excluded.last_updated_at,
+                stale_notified_at = excluded.stale_notified_at
+            "#,
+            dataset_id,
+            settings.notify_owner,
+            Json(&settings.recipients) as _,
+            settings.notify_on_flow_failure,
+            stale_after_secs,
+            settings.created_at,
+            settings.last_updated_at,
+            settings.stale_notified_at,
+        )
+        .execute(connection_mut)
+        .await
+        .int_err()?;
+
+        Ok(())
+    }
+
+    async fn get_settings(
+        &self,
+        dataset_id: &odf::DatasetID,
+    ) -> Result<Option<DatasetAlertSettings>, InternalError> {
+        let mut tr = self.transaction.lock().await;
+        let connection_mut = tr.connection_mut().await?;
+
+        let dataset_id = dataset_id.to_string();
+
+        let maybe_row = sqlx::query_as!(
+            DatasetAlertSettingsRowModel,
+            r#"
+            SELECT
+                dataset_id,
+                notify_owner,
+                recipients as "recipients: Json<Vec<Email>>",
+                notify_on_flow_failure,
+                stale_after_secs,
+                created_at,
+                last_updated_at,
+                stale_notified_at
+            FROM dataset_alert_settings
+            WHERE dataset_id = $1
+            "#,
+            dataset_id,
+        )
+        .fetch_optional(connection_mut)
+        .await
+        .int_err()?;
+
+        maybe_row.map(TryInto::try_into).transpose()
+    }
+
+    async fn get_settings_with_stale_after(
+        &self,
+    ) -> Result<Vec<DatasetAlertSettings>, InternalError> {
+        let mut tr = self.transaction.lock().await;
+        let connection_mut = tr.connection_mut().await?;
+
+        let rows = sqlx::query_as!(
+            DatasetAlertSettingsRowModel,
+            r#"
+            SELECT
+                dataset_id,
+                notify_owner,
+                recipients as "recipients: Json<Vec<Email>>",
+                notify_on_flow_failure,
+                stale_after_secs,
+                created_at,
+                last_updated_at,
+                stale_notified_at
+            FROM dataset_alert_settings
+            WHERE stale_after_secs IS NOT NULL
+            ORDER BY dataset_id
+            "#,
+        )
+        .fetch_all(connection_mut)
+        .await
+        .int_err()?;
+
+        rows.into_iter().map(TryInto::try_into).collect()
+    }
+
+    async fn mark_dataset_updated(
+        &self,
+        dataset_id: &odf::DatasetID,
+        updated_at: DateTime<Utc>,
+    ) -> Result<(), InternalError> {
+        let mut tr = self.transaction.lock().await;
+        let connection_mut = tr.connection_mut().await?;
+
+        let dataset_id = dataset_id.to_string();
+
+        sqlx::query!(
+            r#"
+            UPDATE dataset_alert_settings
+                SET last_updated_at = $1, stale_notified_at = NULL
+                WHERE dataset_id = $2
+            "#,
+            updated_at,
+            dataset_id,
+        )
+        .execute(connection_mut)
+        .await
+        .int_err()?;
+
+        Ok(())
+    }
+
+    async fn mark_stale_notified(
+        &self,
+        dataset_id: &odf::DatasetID,
+        notified_at: DateTime<Utc>,
+    ) -> Result<(), InternalError> {
+        let mut tr = self.transaction.lock().await;
+        let connection_mut = tr.connection_mut().await?;
+
+        let dataset_id = dataset_id.to_string();
+
+        sqlx::query!(
+            r#"
+            UPDATE dataset_alert_settings
+                SET stale_notified_at = $1
+                WHERE dataset_id = $2
+            "#,
+            notified_at,
+            dataset_id,
+        )
+        .execute(connection_mut)
+        .await
+        .int_err()?;
+
+        Ok(())
+    }
+
+    async fn delete_settings(&self, dataset_id: &odf::DatasetID) -> Result<(), InternalError> {
+        let mut tr = self.transaction.lock().await;
+        let connection_mut = tr.connection_mut().await?;
+
+        let dataset_id = dataset_id.to_string();
+
+        sqlx::query!(
+            r#"
+            DELETE FROM dataset_alert_settings WHERE dataset_id = $1
+            "#,
+            dataset_id,
+        )
+        .execute(connection_mut)
+        .await
+        .int_err()?;
+
+        Ok(())
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[derive(Debug)]
+struct DatasetAlertSettingsRowModel {
+    dataset_id: String,
+    notify_owner: bool,
+    recipients: Json<Vec<Email>>,
+    notify_on_flow_failure: bool,
+    stale_after_secs: Option<i64>,
+    created_at: DateTime<Utc>,
+    last_updated_at: DateTime<Utc>,
+    stale_notified_at: Option<DateTime<Utc>>,
+}
+
+impl TryFrom<DatasetAlertSettingsRowModel> for DatasetAlertSettings {
+    type Error = InternalError;
+
+    fn try_from(row: DatasetAlertSettingsRowModel) -> Result<Self, Self::Error> {
+        Ok(Self {
+            dataset_id: odf::DatasetID::from_did_str(&row.dataset_id).int_err()?,
+            notify_owner: row.notify_owner,
+            recipients: row.recipients.0,
+            notify_on_flow_failure: row.notify_on_flow_failure,
+            stale_after: row.stale_after_secs.map(Duration::seconds),
+            created_at: row.created_at,
+            last_updated_at: row.last_updated_at,
+            stale_notified_at: row.stale_notified_at,
+        })
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
@@ -0,0 +1,141 @@
+// Copyright Kamu Data, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+use chrono::{DateTime, Utc};
+use database_common::{TransactionRef, TransactionRefT};
+use dill::{component, interface};
+use email_utils::Email;
+use internal_error::{InternalError, ResultIntoInternal};
+use sqlx::types::Json;
+
+use crate::domain::*;
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+pub struct PostgresDatasetPendingAlertRepository {
+    transaction: TransactionRefT<sqlx::Postgres>,
+}
+
+#[component(pub)]
+#[interface(dyn DatasetPendingAlertRepository)]
+impl PostgresDatasetPendingAlertRepository {
+    pub fn new(transaction: TransactionRef) -> Self {
+        Self {
+            transaction: transaction.into(),
+        }
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[async_trait::async_trait]
+impl DatasetPendingAlertRepository for PostgresDatasetPendingAlertRepository {
+    async fn save_pending_alert(&self, alert: &DatasetPendingAlert) -> Result<(), InternalError> {
+        let mut tr = self.transaction.lock().await;
+        let connection_mut = tr.connection_mut().await?;
+
+        let dataset_id = alert.dataset_id.to_string();
+
+        sqlx::query!(
+            r#"
+            INSERT INTO dataset_pending_alerts (alert_key, dataset_id, recipients, subject, body, created_at)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            ON CONFLICT (alert_key) DO NOTHING
+            "#,
+            alert.alert_key,
+            dataset_id,
+            Json(&alert.recipients) as _,
+            alert.subject,
+            alert.body,
+            alert.created_at,
+        )
+        .execute(connection_mut)
+        .await
+        .int_err()?;
+
+        Ok(())
+    }
+
+    async fn get_pending_alerts(
+        &self,
+        limit: usize,
+    ) -> Result<Vec<DatasetPendingAlert>, InternalError> {
+        let mut tr = self.transaction.lock().await;
+        let connection_mut = tr.connection_mut().await?;
+
+        let limit = i64::try_from(limit).unwrap();
+
+        let rows = sqlx::query_as!(
+            DatasetPendingAlertRowModel,
+            r#"
+            SELECT
+                alert_key,
+                dataset_id,
+                recipients as "recipients: Json<Vec<Email>>",
+                subject,
+                body,
+                created_at
+            FROM dataset_pending_alerts
+            ORDER BY created_at, alert_key
+            LIMIT $1
+            "#,
+            limit,
+        )
+        .fetch_all(connection_mut)
+        .await
+        .int_err()?;
+
+        rows.into_iter().map(TryInto::try_into).collect()
+    }
+
+    async fn delete_pending_alert(&self, alert_key: &str) -> Result<(), InternalError> {
+        let mut tr = self.transaction.lock().await;
+        let connection_mut = tr.connection_mut().await?;
+
+        sqlx::query!(
+            r#"
+            DELETE FROM dataset_pending_alerts WHERE alert_key = $1
+            "#,
+            alert_key,
+        )
+        .execute(connection_mut)
+        .await
+        .int_err()?;
+
+        Ok(())
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[derive(Debug)]
+struct DatasetPendingAlertRowModel {
+    alert_key: String,
+    dataset_id: String,
+    recipients: Json<Vec<Email>>,
+    subject: String,
+    body: String,
+    created_at: DateTime<Utc>,
+}
+
+impl TryFrom<DatasetPendingAlertRowModel> for DatasetPendingAlert {
+    type Error = InternalError;
+
+    fn try_from(row: DatasetPendingAlertRowModel) -> Result<Self, Self::Error> {
+        Ok(Self {
+            alert_key: row.alert_key,
+            dataset_id: odf::DatasetID::from_did_str(&row.dataset_id).int_err()?,
+            recipients: row.recipients.0,
+            subject: row.subject,
+            body: row.body,
+            created_at: row.created_at,
+        })
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
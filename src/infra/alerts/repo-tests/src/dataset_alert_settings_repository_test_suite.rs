@@ -0,0 +1,182 @@
+// Copyright Kamu Data, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use chrono::{DateTime, Duration, SubsecRound, Utc};
+use dill::Catalog;
+use email_utils::Email;
+use kamu_alerts::*;
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+fn now() -> DateTime<Utc> {
+    Utc::now().round_subsecs(6)
+}
+
+fn new_settings(
+    dataset_id: &odf::DatasetID,
+    stale_after: Option<Duration>,
+    created_at: DateTime<Utc>,
+) -> DatasetAlertSettings {
+    DatasetAlertSettings {
+        dataset_id: dataset_id.clone(),
+        notify_owner: true,
+        recipients: vec![
+            Email::parse("alice@example.com").unwrap(),
+            Email::parse("bob@example.com").unwrap(),
+        ],
+        notify_on_flow_failure: true,
+        stale_after,
+        created_at,
+        last_updated_at: created_at,
+        stale_notified_at: None,
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+pub async fn test_missing_settings_not_found(catalog: &Catalog) {
+    let repo = catalog
+        .get_one::<dyn DatasetAlertSettingsRepository>()
+        .unwrap();
+
+    let dataset_id = odf::DatasetID::new_seeded_ed25519(b"foo");
+
+    assert_eq!(repo.get_settings(&dataset_id).await.unwrap(), None);
+    assert!(repo
+        .get_settings_with_stale_after()
+        .await
+        .unwrap()
+        .is_empty());
+
+    // Updates of datasets without settings are ignored
+    repo.mark_dataset_updated(&dataset_id, now()).await.unwrap();
+    repo.mark_stale_notified(&dataset_id, now()).await.unwrap();
+    repo.delete_settings(&dataset_id).await.unwrap();
+
+    assert_eq!(repo.get_settings(&dataset_id).await.unwrap(), None);
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+pub async fn test_save_and_get_settings(catalog: &Catalog) {
+    let repo = catalog
+        .get_one::<dyn DatasetAlertSettingsRepository>()
+        .unwrap();
+
+    let dataset_id = odf::DatasetID::new_seeded_ed25519(b"foo");
+    let settings = new_settings(&dataset_id, Some(Duration::hours(6)), now());
+
+    repo.save_settings(&settings).await.unwrap();
+    assert_eq!(
+        repo.get_settings(&dataset_id).await.unwrap(),
+        Some(settings.clone())
+    );
+
+    let updated_settings = DatasetAlertSettings {
+        notify_owner: false,
+        recipients: vec![Email::parse("carol@example.com").unwrap()],
+        notify_on_flow_failure: false,
+        stale_after: None,
+        ..settings
+    };
+
+    repo.save_settings(&updated_settings).await.unwrap();
+    assert_eq!(
+        repo.get_settings(&dataset_id).await.unwrap(),
+        Some(updated_settings)
+    );
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+pub async fn test_get_settings_with_stale_after(catalog: &Catalog) {
+    let repo = catalog
+        .get_one::<dyn DatasetAlertSettingsRepository>()
+        .unwrap();
+
+    let foo_settings = new_settings(
+        &odf::DatasetID::new_seeded_ed25519(b"foo"),
+        Some(Duration::hours(6)),
+        now(),
+    );
+    let bar_settings = new_settings(&odf::DatasetID::new_seeded_ed25519(b"bar"), None, now());
+    let baz_settings = new_settings(
+        &odf::DatasetID::new_seeded_ed25519(b"baz"),
+        Some(Duration::days(1)),
+        now(),
+    );
+
+    for settings in [&foo_settings, &bar_settings, &baz_settings] {
+        repo.save_settings(settings).await.unwrap();
+    }
+
+    let mut actual = repo.get_settings_with_stale_after().await.unwrap();
+    actual.sort_by(|a, b| a.dataset_id.cmp(&b.dataset_id));
+
+    let mut expected = vec![foo_settings, baz_settings];
+    expected.sort_by(|a, b| a.dataset_id.cmp(&b.dataset_id));
+
+    assert_eq!(actual, expected);
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+pub async fn test_mark_dataset_updated_and_stale_notified(catalog: &Catalog) {
+    let repo = catalog
+        .get_one::<dyn DatasetAlertSettingsRepository>()
+        .unwrap();
+
+    let dataset_id = odf::DatasetID::new_seeded_ed25519(b"foo");
+    let created_at = now();
+    let settings = new_settings(&dataset_id, Some(Duration::hours(6)), created_at);
+    repo.save_settings(&settings).await.unwrap();
+
+    let notified_at = created_at + Duration::hours(7);
+    repo.mark_stale_notified(&dataset_id, notified_at)
+        .await
+        .unwrap();
+
+    let stored = repo.get_settings(&dataset_id).await.unwrap().unwrap();
+    assert_eq!(stored.last_updated_at, created_at);
+    assert_eq!(stored.stale_notified_at, Some(notified_at));
+
+    let updated_at = created_at + Duration::hours(8);
+    repo.mark_dataset_updated(&dataset_id, updated_at)
+        .await
+        .unwrap();
+
+    let stored = repo.get_settings(&dataset_id).await.unwrap().unwrap();
+    assert_eq!(stored.last_updated_at, updated_at);
+    assert_eq!(stored.stale_notified_at, None);
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+pub async fn test_delete_settings(catalog: &Catalog) {
+    let repo = catalog
+        .get_one::<dyn DatasetAlertSettingsRepository>()
+        .unwrap();
+
+    let foo_id = odf::DatasetID::new_seeded_ed25519(b"foo");
+    let bar_id = odf::DatasetID::new_seeded_ed25519(b"bar");
+
+    repo.save_settings(&new_settings(&foo_id, None, now()))
+        .await
+        .unwrap();
+    repo.save_settings(&new_settings(&bar_id, None, now()))
+        .await
+        .unwrap();
+
+    repo.delete_settings(&foo_id).await.unwrap();
+
+    assert_eq!(repo.get_settings(&foo_id).await.unwrap(), None);
+    assert!(repo.get_settings(&bar_id).await.unwrap().is_some());
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
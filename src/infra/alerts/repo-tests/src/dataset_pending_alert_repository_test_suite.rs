@@ -0,0 +1,84 @@
+// Copyright Kamu Data, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+use chrono::{DateTime, Duration, SubsecRound, Utc};
+use dill::Catalog;
+use email_utils::Email;
+use kamu_alerts::*;
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+fn new_alert(alert_key: &str, subject: &str, created_at: DateTime<Utc>) -> DatasetPendingAlert {
+    DatasetPendingAlert {
+        alert_key: alert_key.to_string(),
+        dataset_id: odf::DatasetID::new_seeded_ed25519(b"foo"),
+        recipients: vec![
+            Email::parse("alice@example.com").unwrap(),
+            Email::parse("bob@example.com").unwrap(),
+        ],
+        subject: subject.to_string(),
+        body: "Body\n".to_string(),
+        created_at,
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+pub async fn test_no_pending_alerts(catalog: &Catalog) {
+    let repo = catalog
+        .get_one::<dyn DatasetPendingAlertRepository>()
+        .unwrap();
+
+    assert!(repo.get_pending_alerts(10).await.unwrap().is_empty());
+
+    // Deleting missing alerts is not an error
+    repo.delete_pending_alert("flow-failed/1").await.unwrap();
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+pub async fn test_save_get_and_delete_pending_alerts(catalog: &Catalog) {
+    let repo = catalog
+        .get_one::<dyn DatasetPendingAlertRepository>()
+        .unwrap();
+
+    let t0 = Utc::now().round_subsecs(6);
+    let alert_1 = new_alert("flow-failed/1", "First", t0);
+    let alert_2 = new_alert("flow-failed/2", "Second", t0 + Duration::seconds(1));
+    let alert_3 = new_alert("flow-failed/3", "Third", t0 + Duration::seconds(2));
+
+    for alert in [&alert_3, &alert_1, &alert_2] {
+        repo.save_pending_alert(alert).await.unwrap();
+    }
+
+    // Same key does not produce another alert
+    repo.save_pending_alert(&new_alert(
+        "flow-failed/1",
+        "Duplicate",
+        t0 + Duration::seconds(3),
+    ))
+    .await
+    .unwrap();
+
+    assert_eq!(
+        repo.get_pending_alerts(10).await.unwrap(),
+        vec![alert_1.clone(), alert_2.clone(), alert_3.clone()]
+    );
+    assert_eq!(
+        repo.get_pending_alerts(2).await.unwrap(),
+        vec![alert_1.clone(), alert_2.clone()]
+    );
+
+    repo.delete_pending_alert(&alert_1.alert_key).await.unwrap();
+    assert_eq!(
+        repo.get_pending_alerts(10).await.unwrap(),
+        vec![alert_2, alert_3]
+    );
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
@@ -14,3 +14,27 @@ pub struct ODFEngineConfig {
     pub start_timeout: Duration,
     pub shutdown_timeout: Duration,
 }
+
+/// Configuration of warm engine containers that are reused across operations
+#[derive(Debug, Clone)]
+pub struct EnginePoolConfig {
+    /// Maximum number of idle containers kept running, zero disables pooling
+    pub max_size: usize,
+    /// For how long an idle container is kept before it is stopped
+    pub idle_timeout: Duration,
+}
+
+impl EnginePoolConfig {
+    pub fn is_enabled(&self) -> bool {
+        self.max_size > 0
+    }
+}
+
+impl Default for EnginePoolConfig {
+    fn default() -> Self {
+        Self {
+            max_size: 0,
+            idle_timeout: Duration::from_secs(60),
+        }
+    }
+}
@@ -80,6 +80,15 @@ impl EngineContainer {
         self.container.terminate().await
     }
 
+    pub fn terminate_blocking(mut self) -> std::io::Result<TerminateStatus> {
+        self.container.terminate_blocking()
+    }
+
+    /// Checks whether the container process is still alive
+    pub fn is_running(&mut self) -> bool {
+        matches!(self.container.try_wait(), Ok(None))
+    }
+
     pub fn log_files(&self) -> Vec<PathBuf> {
         self.logs_config.log_files()
     }
@@ -0,0 +1,247 @@
+// Copyright Kamu Data, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+
+use container_runtime::*;
+use internal_error::{InternalError, ResultIntoInternal};
+use kamu_core::engine::EngineError;
+use kamu_core::RunInfoDir;
+use random_names::get_random_name;
+
+use super::engine_container::{EngineContainer, LogsConfig};
+use super::{EngineIoStrategySharedVolume, EnginePoolConfig, ODFEngineConfig};
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+/// Location where the I/O directory is mounted inside of the pooled containers
+pub(crate) const POOLED_CONTAINER_RUN_DIR: &str = "/opt/engine/run";
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+/// Keeps engine containers running between operations to avoid paying the
+/// start-up cost of an engine for every transformation.
+///
+/// Since volumes cannot be added to a running container, every pooled
+/// container mounts a dedicated I/O directory once. An operation places its
+/// files there (see [`EngineIoStrategySharedVolume`]) and moves them out into
+/// its run info directory when finished, so the container only ever sees the
+/// files of the operation it currently serves.
+pub(crate) struct EngineContainerPool {
+    container_runtime: Arc<ContainerRuntime>,
+    engine_config: ODFEngineConfig,
+    pool_config: EnginePoolConfig,
+    run_info_dir: Arc<RunInfoDir>,
+    idle: Mutex<Vec<IdleEngineContainer>>,
+}
+
+struct IdleEngineContainer {
+    image: String,
+    pooled: PooledEngineContainer,
+    released_at: Instant,
+}
+
+/// Container of the pool along with the host directory mounted into it
+pub(crate) struct PooledEngineContainer {
+    container: EngineContainer,
+    io_dir: PathBuf,
+}
+
+impl PooledEngineContainer {
+    pub fn container(&self) -> &EngineContainer {
+        &self.container
+    }
+
+    /// Host directory visible to the container, expected to hold only the
+    /// files of the current operation
+    pub fn io_dir(&self) -> &Path {
+        &self.io_dir
+    }
+
+    pub fn io_strategy(&self) -> EngineIoStrategySharedVolume {
+        EngineIoStrategySharedVolume::new(self.io_dir.clone(), POOLED_CONTAINER_RUN_DIR)
+    }
+
+    /// Moves the files left by the finished operation into its own directory,
+    /// out of the reach of the following operations
+    pub fn take_operation_files(&self, operation_dir: &Path) -> Result<(), InternalError> {
+        for entry in std::fs::read_dir(&self.io_dir).int_err()? {
+            let entry = entry.int_err()?;
+            std::fs::rename(entry.path(), operation_dir.join(entry.file_name())).int_err()?;
+        }
+        Ok(())
+    }
+
+    pub async fn terminate(self) -> std::io::Result<TerminateStatus> {
+        self.container.terminate().await
+    }
+}
+
+impl EngineContainerPool {
+    pub fn new(
+        container_runtime: Arc<ContainerRuntime>,
+        engine_config: ODFEngineConfig,
+        pool_config: EnginePoolConfig,
+        run_info_dir: Arc<RunInfoDir>,
+    ) -> Self {
+        Self {
+            container_runtime,
+            engine_config,
+            pool_config,
+            run_info_dir,
+            idle: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Takes a warm container running the specified image or starts a new one
+    pub async fn acquire(&self, image: &str) -> Result<PooledEngineContainer, EngineError> {
+        self.stop_expired().await;
+
+        loop {
+            let idle = {
+                let mut idle = self.idle.lock().unwrap();
+                idle.iter()
+                    .rposition(|c| c.image == image)
+                    .map(|i| idle.remove(i))
+            };
+
+            let Some(mut idle) = idle else {
+                break;
+            };
+
+            if idle.pooled.container.is_running() {
+                tracing::info!(
+                    container_name = idle.pooled.container.container_name(),
+                    "Reusing warm engine container"
+                );
+                return Ok(idle.pooled);
+            }
+
+            tracing::warn!(
+                container_name = idle.pooled.container.container_name(),
+                "Discarding warm engine container that has exited"
+            );
+        }
+
+        self.start_container(image).await
+    }
+
+    /// Returns the container after a successful operation so it could be
+    /// reused. The files of the operation must have been taken out of its I/O
+    /// directory by then. Containers that failed an operation should be
+    /// terminated instead, as their state is unknown.
+    pub async fn release(self: &Arc<Self>, image: &str, pooled: PooledEngineContainer) {
+        let evicted: Vec<_> = {
+            let mut idle = self.idle.lock().unwrap();
+            idle.push(IdleEngineContainer {
+                image: image.to_string(),
+                pooled,
+                released_at: Instant::now(),
+            });
+
+            // Stopping the containers that were idle the longest
+            let num_excess = idle.len().saturating_sub(self.pool_config.max_size);
+            idle.drain(..num_excess).collect()
+        };
+
+        Self::stop_all(evicted).await;
+        self.schedule_expiration();
+    }
+
+    async fn start_container(&self, image: &str) -> Result<PooledEngineContainer, EngineError> {
+        let container_id = get_random_name(None, 10);
+        let container_dir = self.run_info_dir.join(format!("engine-{container_id}"));
+        let logs_dir = container_dir.join("logs");
+        let io_dir = container_dir.join("io");
+        std::fs::create_dir_all(&logs_dir).int_err()?;
+        std::fs::create_dir_all(&io_dir).int_err()?;
+
+        tracing::info!(image, "Starting pooled engine container");
+
+        let container = EngineContainer::new(
+            self.container_runtime.clone(),
+            self.engine_config.clone(),
+            LogsConfig::new(&logs_dir),
+            image,
+            vec![VolumeSpec {
+                source: io_dir.clone(),
+                dest: PathBuf::from(POOLED_CONTAINER_RUN_DIR),
+                access: VolumeAccess::ReadWrite,
+            }],
+            &container_id,
+        )
+        .await?;
+
+        Ok(PooledEngineContainer { container, io_dir })
+    }
+
+    fn schedule_expiration(self: &Arc<Self>) {
+        let pool = Arc::downgrade(self);
+        let idle_timeout = self.pool_config.idle_timeout;
+
+        tokio::spawn(async move {
+            tokio::time::sleep(idle_timeout).await;
+            if let Some(pool) = pool.upgrade() {
+                pool.stop_expired().await;
+            }
+        });
+    }
+
+    async fn stop_expired(&self) {
+        let expired: Vec<_> = {
+            let mut idle = self.idle.lock().unwrap();
+            let (expired, kept) = std::mem::take(&mut *idle)
+                .into_iter()
+                .partition(|c| c.released_at.elapsed() >= self.pool_config.idle_timeout);
+            *idle = kept;
+            expired
+        };
+
+        Self::stop_all(expired).await;
+    }
+
+    async fn stop_all(containers: Vec<IdleEngineContainer>) {
+        for idle in containers {
+            tracing::info!(
+                container_name = idle.pooled.container.container_name(),
+                "Stopping idle engine container"
+            );
+            if let Err(e) = idle.pooled.terminate().await {
+                tracing::warn!(error = ?e, error_msg = %e, "Failed to stop idle engine container");
+            }
+        }
+    }
+}
+
+impl Drop for EngineContainerPool {
+    fn drop(&mut self) {
+        let idle: Vec<_> = self.idle.get_mut().unwrap().drain(..).collect();
+        if idle.is_empty() {
+            return;
+        }
+
+        let terminate_all = move || {
+            for idle in idle {
+                let _ = idle.pooled.container.terminate_blocking();
+            }
+        };
+
+        // Waiting for containers to stop would block a runtime worker thread
+        match tokio::runtime::Handle::try_current() {
+            Ok(handle) => {
+                handle.spawn_blocking(terminate_all);
+            }
+            Err(_) => terminate_all(),
+        }
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
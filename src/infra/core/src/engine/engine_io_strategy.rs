@@ -11,7 +11,7 @@ use std::path::{Path, PathBuf};
 
 use container_runtime::*;
 use datafusion::arrow::datatypes::SchemaRef;
-use internal_error::{InternalError, ResultIntoInternal};
+use internal_error::{ErrorIntoInternal, InternalError, ResultIntoInternal};
 use kamu_core::engine::*;
 use kamu_core::*;
 use odf::storage::lfs::ObjectRepositoryLocalFSSha3;
@@ -357,6 +357,194 @@ impl EngineIoStrategy for EngineIoStrategyRemoteProxy {
     }
 }
 
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+// Shared Volume
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+/// This IO strategy is used for long-running engine containers that cannot
+/// have volumes mounted per operation. Instead, a host directory is mounted
+/// into the container once and all inputs are placed into the operation
+/// directory inside of it: local files are hard-linked, which requires them to
+/// be on the same file system, and remote ones are downloaded. Host paths are
+/// then remapped into the paths under the container mount point.
+pub struct EngineIoStrategySharedVolume {
+    host_root: PathBuf,
+    container_root: PathBuf,
+}
+
+impl EngineIoStrategySharedVolume {
+    pub fn new(host_root: impl Into<PathBuf>, container_root: impl Into<PathBuf>) -> Self {
+        Self {
+            host_root: host_root.into(),
+            container_root: container_root.into(),
+        }
+    }
+
+    /// Maps a path inside of the mounted host directory into the container
+    pub fn to_container_path(&self, host_path: &Path) -> Result<PathBuf, InternalError> {
+        let relative_path = host_path.strip_prefix(&self.host_root).int_err()?;
+
+        // Note: not using `PathBuf::join()` to ensure linux style paths
+        let mut container_path = self.container_root.to_string_lossy().into_owned();
+        for component in relative_path.components() {
+            container_path.push('/');
+            container_path.push_str(&component.as_os_str().to_string_lossy());
+        }
+        Ok(PathBuf::from(container_path))
+    }
+
+    async fn materialize_object(
+        &self,
+        repo: &dyn odf::storage::ObjectRepository,
+        hash: &odf::Multihash,
+        host_in_dir: &Path,
+    ) -> Result<PathBuf, InternalError> {
+        let host_path = host_in_dir.join(hash.to_string());
+
+        if let odf::storage::ObjectRepositoryProtocol::LocalFs { .. } = repo.protocol() {
+            let url = repo.get_internal_url(hash).await;
+            let source_path = odf::utils::data::local_url::into_local_path(url).int_err()?;
+            // Copying would silently duplicate potentially large data files
+            std::fs::hard_link(&source_path, &host_path).map_err(|source| {
+                SharedVolumeLinkError {
+                    source_path,
+                    host_path: host_path.clone(),
+                    source,
+                }
+                .int_err()
+            })?;
+        } else {
+            let tmp_repo = ObjectRepositoryLocalFSSha3::new(host_in_dir.to_path_buf());
+            let stream = repo.get_stream(hash).await.int_err()?;
+
+            use odf::storage::ObjectRepository;
+            tmp_repo
+                .insert_stream(
+                    stream,
+                    odf::storage::InsertOpts {
+                        precomputed_hash: Some(hash),
+                        expected_hash: Some(hash),
+                        size_hint: None,
+                    },
+                )
+                .await
+                .int_err()?;
+        }
+
+        self.to_container_path(&host_path)
+    }
+
+    async fn maybe_materialize_object(
+        &self,
+        repo: &dyn odf::storage::ObjectRepository,
+        hash: Option<&odf::Multihash>,
+        host_in_dir: &Path,
+    ) -> Result<Option<PathBuf>, InternalError> {
+        if let Some(hash) = hash {
+            Ok(Some(
+                self.materialize_object(repo, hash, host_in_dir).await?,
+            ))
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl EngineIoStrategy for EngineIoStrategySharedVolume {
+    #[tracing::instrument(skip_all)]
+    async fn materialize_request(
+        &self,
+        request: TransformRequestExt,
+        datasets_map: &ResolvedDatasetsMap,
+        operation_dir: &Path,
+    ) -> Result<MaterializedEngineRequest, InternalError> {
+        let host_in_dir = operation_dir.join("in");
+        let host_out_dir = operation_dir.join("out");
+        let host_out_data_path = host_out_dir.join("data");
+        let host_out_checkpoint_path = host_out_dir.join("checkpoint");
+        std::fs::create_dir(&host_in_dir).int_err()?;
+        std::fs::create_dir(&host_out_dir).int_err()?;
+
+        let container_out_data_path = self.to_container_path(&host_out_data_path)?;
+        let container_out_checkpoint_path = self.to_container_path(&host_out_checkpoint_path)?;
+
+        let target = datasets_map.get_by_handle(&request.dataset_handle);
+
+        let prev_checkpoint_path = self
+            .maybe_materialize_object(
+                target.as_checkpoint_repo(),
+                request.prev_checkpoint.as_ref(),
+                &host_in_dir,
+            )
+            .await?;
+
+        let mut query_inputs = Vec::new();
+        for input in request.inputs {
+            let input_resolved = datasets_map.get_by_handle(&input.dataset_handle);
+
+            let mut data_paths = Vec::new();
+            for hash in input.data_slices {
+                let container_path = self
+                    .materialize_object(input_resolved.as_data_repo(), &hash, &host_in_dir)
+                    .await?;
+
+                data_paths.push(container_path);
+            }
+
+            let offset_interval = if let Some(new_offset) = input.new_offset {
+                Some(odf::metadata::OffsetInterval {
+                    start: input.prev_offset.map_or(0, |v| v + 1),
+                    end: new_offset,
+                })
+            } else {
+                None
+            };
+
+            let schema_file = {
+                // FIXME: The .parquet extension is currently necessary for DataFusion to
+                // respect the single-file output
+                // See: https://github.com/apache/datafusion/issues/13323
+                let name = format!("schema-{}.parquet", input.dataset_handle.id.as_multibase());
+                let host_path = host_in_dir.join(&name);
+                write_schema_file(&input.schema, &host_path).await?;
+                self.to_container_path(&host_path)?
+            };
+
+            query_inputs.push(odf::metadata::TransformRequestInput {
+                dataset_id: input.dataset_handle.id,
+                dataset_alias: input.dataset_handle.alias,
+                query_alias: input.alias,
+                vocab: input.vocab,
+                offset_interval,
+                data_paths,
+                schema_file,
+                explicit_watermarks: input.explicit_watermarks,
+            });
+        }
+
+        let engine_request = odf::metadata::TransformRequest {
+            dataset_id: request.dataset_handle.id,
+            dataset_alias: request.dataset_handle.alias,
+            system_time: request.system_time,
+            next_offset: request.prev_offset.map_or(0, |v| v + 1),
+            vocab: request.vocab,
+            transform: request.transform,
+            query_inputs,
+            prev_checkpoint_path,
+            new_checkpoint_path: container_out_checkpoint_path,
+            new_data_path: container_out_data_path,
+        };
+
+        Ok(MaterializedEngineRequest {
+            engine_request,
+            out_data_path: host_out_data_path,
+            out_checkpoint_path: host_out_checkpoint_path,
+            volumes: Vec::new(),
+        })
+    }
+}
+
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
 
 // TODO: Remove in favor of passing serialized schema
@@ -398,3 +586,18 @@ async fn write_schema_file(schema: &SchemaRef, path: &Path) -> Result<(), Intern
 }
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[derive(thiserror::Error, Debug)]
+#[error(
+    "Failed to link {} into the engine I/O directory as {}, the run info directory must reside \
+     on the same file system as the datasets when the engine pool is enabled",
+    source_path.display(),
+    host_path.display()
+)]
+struct SharedVolumeLinkError {
+    source_path: PathBuf,
+    host_path: PathBuf,
+    source: std::io::Error,
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
@@ -8,19 +8,20 @@
 // by the Apache License, Version 2.0.
 
 use std::collections::HashMap;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::process::Stdio;
 use std::sync::Arc;
 
 use container_runtime::*;
 use datafusion::config::{ParquetOptions, TableParquetOptions};
 use file_utils::OwnedFile;
-use internal_error::ResultIntoInternal;
+use internal_error::{InternalError, ResultIntoInternal};
 use kamu_core::engine::*;
 use kamu_core::*;
 use odf::metadata::engine::{EngineGrpcClient, ExecuteRawQueryError, ExecuteTransformError};
 
 use super::engine_container::{EngineContainer, LogsConfig};
+use super::engine_container_pool::{EngineContainerPool, PooledEngineContainer};
 use super::engine_io_strategy::*;
 use super::ODFEngineConfig;
 
@@ -28,7 +29,9 @@ pub struct ODFEngine {
     container_runtime: Arc<ContainerRuntime>,
     engine_config: ODFEngineConfig,
     image: String,
+    io_strategy: EngineIoStrategyKind,
     run_info_dir: Arc<RunInfoDir>,
+    pool: Option<Arc<EngineContainerPool>>,
 }
 
 impl ODFEngine {
@@ -36,21 +39,80 @@ impl ODFEngine {
         container_runtime: Arc<ContainerRuntime>,
         engine_config: ODFEngineConfig,
         image: &str,
+        io_strategy: EngineIoStrategyKind,
         run_info_dir: Arc<RunInfoDir>,
+        pool: Option<Arc<EngineContainerPool>>,
     ) -> Self {
         Self {
             container_runtime,
             engine_config,
             image: image.to_owned(),
+            io_strategy,
             run_info_dir,
+            pool,
         }
     }
 
+    /// Starts a container dedicated to the operation
+    async fn start_container(
+        &self,
+        volumes: Vec<VolumeSpec>,
+        logs_dir: &Path,
+        operation_id: &str,
+    ) -> Result<OperationContainer, EngineError> {
+        let engine_container = EngineContainer::new(
+            self.container_runtime.clone(),
+            self.engine_config.clone(),
+            LogsConfig::new(logs_dir),
+            &self.image,
+            volumes,
+            operation_id,
+        )
+        .await?;
+
+        Ok(OperationContainer::Dedicated(engine_container))
+    }
+
+    /// Moves the files of the operation out of the I/O directory of a pooled
+    /// container and returns it into the pool after a successful operation,
+    /// otherwise terminates the container
+    async fn release_container(
+        &self,
+        container: OperationContainer,
+        operation_dir: &Path,
+        succeeded: bool,
+    ) -> Result<(), EngineError> {
+        match container {
+            OperationContainer::Pooled(pooled) => {
+                let taken = pooled.take_operation_files(operation_dir);
+                if succeeded && taken.is_ok() {
+                    self.pool
+                        .as_ref()
+                        .unwrap()
+                        .release(&self.image, pooled)
+                        .await;
+                } else {
+                    pooled.terminate().await?;
+                }
+                taken?;
+            }
+            OperationContainer::Dedicated(engine_container) => {
+                engine_container.terminate().await?;
+            }
+        }
+        Ok(())
+    }
+
     // TODO: Currently we are always proxying remote inputs, but in future we should
     // have a capabilities mechanism for engines to declare that they can work
     // with some remote storages directly without us needing to proxy data.
     fn get_io_strategy(&self, target_dataset: &dyn odf::Dataset) -> Arc<dyn EngineIoStrategy> {
         use odf::storage::ObjectRepositoryProtocol;
+
+        if self.io_strategy == EngineIoStrategyKind::RemoteProxy {
+            return Arc::new(EngineIoStrategyRemoteProxy {});
+        }
+
         match target_dataset.as_data_repo().protocol() {
             ObjectRepositoryProtocol::LocalFs { .. } => Arc::new(EngineIoStrategyLocalVolume {}),
             ObjectRepositoryProtocol::Memory
@@ -250,8 +312,19 @@ impl Engine for ODFEngine {
         std::fs::create_dir(&operation_dir).int_err()?;
         std::fs::create_dir(&logs_dir).int_err()?;
 
-        let host_in_dir = operation_dir.join("in");
-        let host_out_dir = operation_dir.join("out");
+        // Pooled containers see only their I/O directory, so the files of the
+        // operation are placed there while it runs
+        let maybe_pooled = match &self.pool {
+            Some(pool) => Some(pool.acquire(&self.image).await?),
+            None => None,
+        };
+        let host_io_dir = match &maybe_pooled {
+            Some(pooled) => pooled.io_dir().to_path_buf(),
+            None => operation_dir.clone(),
+        };
+
+        let host_in_dir = host_io_dir.join("in");
+        let host_out_dir = host_io_dir.join("out");
         let _ = std::fs::create_dir_all(&host_in_dir);
         let _ = std::fs::create_dir_all(&host_out_dir);
 
@@ -261,67 +334,93 @@ impl Engine for ODFEngine {
         let host_input_data_path = host_in_dir.join("input.parquet");
         let host_output_data_path = host_out_dir.join("output.parquet");
 
-        // Note: not using `PathBuf::join()` below to ensure linux style paths
-        let container_in_dir = PathBuf::from("/opt/engine/in");
-        let container_out_dir = PathBuf::from("/opt/engine/out");
-        let container_input_data_path = PathBuf::from("/opt/engine/in/input.parquet");
-        let container_output_data_path = PathBuf::from("/opt/engine/out/output.parquet");
-
-        let volumes = vec![
-            VolumeSpec {
-                source: host_in_dir.clone(),
-                dest: container_in_dir,
-                access: VolumeAccess::ReadOnly,
-            },
-            VolumeSpec {
-                source: host_out_dir.clone(),
-                dest: container_out_dir,
-                access: VolumeAccess::ReadWrite,
-            },
-        ];
-
-        // TODO: Reconsider single-file output
-        request
-            .input_data
-            .write_parquet(
-                host_input_data_path.as_os_str().to_str().unwrap(),
-                datafusion::dataframe::DataFrameWriteOptions::new().with_single_file_output(true),
-                Some(TableParquetOptions {
-                    global: ParquetOptions {
-                        writer_version: "1.0".into(),
-                        compression: Some("snappy".into()),
-                        ..Default::default()
-                    },
-                    column_specific_options: HashMap::new(),
-                    key_value_metadata: HashMap::new(),
-                }),
-            )
-            .await
-            .int_err()?;
+        let (container_input_data_path, container_output_data_path) = match &maybe_pooled {
+            Some(pooled) => {
+                let shared_volume = pooled.io_strategy();
+                (
+                    shared_volume.to_container_path(&host_input_data_path)?,
+                    shared_volume.to_container_path(&host_output_data_path)?,
+                )
+            }
+            // Note: not using `PathBuf::join()` below to ensure linux style paths
+            None => (
+                PathBuf::from("/opt/engine/in/input.parquet"),
+                PathBuf::from("/opt/engine/out/output.parquet"),
+            ),
+        };
+
+        // Queries that don't read any input (e.g. the ones that access external
+        // sources directly) are passed an input without columns, which cannot be
+        // represented in Parquet
+        let input_data_paths = if request.input_data.schema().fields().is_empty() {
+            Vec::new()
+        } else {
+            // TODO: Reconsider single-file output
+            request
+                .input_data
+                .write_parquet(
+                    host_input_data_path.as_os_str().to_str().unwrap(),
+                    datafusion::dataframe::DataFrameWriteOptions::new()
+                        .with_single_file_output(true),
+                    Some(TableParquetOptions {
+                        global: ParquetOptions {
+                            writer_version: "1.0".into(),
+                            compression: Some("snappy".into()),
+                            ..Default::default()
+                        },
+                        column_specific_options: HashMap::new(),
+                        key_value_metadata: HashMap::new(),
+                    }),
+                )
+                .await
+                .int_err()?;
+
+            vec![container_input_data_path]
+        };
 
         let materialized_request = odf::metadata::RawQueryRequest {
-            input_data_paths: vec![container_input_data_path],
+            input_data_paths,
             transform: request.transform,
             output_data_path: container_output_data_path.clone(),
         };
 
-        let engine_container = EngineContainer::new(
-            self.container_runtime.clone(),
-            self.engine_config.clone(),
-            LogsConfig::new(&logs_dir),
-            &self.image,
-            volumes,
-            &operation_id,
-        )
-        .await?;
+        let container = match maybe_pooled {
+            Some(pooled) => OperationContainer::Pooled(pooled),
+            None => {
+                let volumes = vec![
+                    VolumeSpec {
+                        source: host_in_dir,
+                        dest: PathBuf::from("/opt/engine/in"),
+                        access: VolumeAccess::ReadOnly,
+                    },
+                    VolumeSpec {
+                        source: host_out_dir,
+                        dest: PathBuf::from("/opt/engine/out"),
+                        access: VolumeAccess::ReadWrite,
+                    },
+                ];
+                self.start_container(volumes, &logs_dir, &operation_id)
+                    .await?
+            }
+        };
 
-        let mut engine_client = engine_container.connect_client().await?;
+        let engine_response = match container.engine_container().connect_client().await {
+            Ok(mut engine_client) => {
+                self.execute_raw_query(
+                    container.engine_container(),
+                    &mut engine_client,
+                    materialized_request,
+                )
+                .await
+            }
+            Err(e) => Err(e),
+        };
 
-        let engine_response = self
-            .execute_raw_query(&engine_container, &mut engine_client, materialized_request)
-            .await;
+        self.release_container(container, &operation_dir, engine_response.is_ok())
+            .await?;
 
-        engine_container.terminate().await?;
+        // Files of a pooled container were moved into the operation directory
+        let host_output_data_path = operation_dir.join("out").join("output.parquet");
 
         let output_data = if engine_response?.num_records == 0 {
             None
@@ -357,40 +456,108 @@ impl Engine for ODFEngine {
         std::fs::create_dir(&operation_dir).int_err()?;
         std::fs::create_dir(&logs_dir).int_err()?;
 
-        let target = datasets_map.get_by_handle(&request.dataset_handle);
-        let io_strategy = self.get_io_strategy(target.as_ref());
+        let (container, materialized_request) = match &self.pool {
+            Some(pool) => {
+                // Pooled containers see only their I/O directory, so the files of
+                // the operation are placed there while it runs
+                let pooled = pool.acquire(&self.image).await?;
+                match pooled
+                    .io_strategy()
+                    .materialize_request(request, datasets_map, pooled.io_dir())
+                    .await
+                {
+                    Ok(materialized_request) => {
+                        (OperationContainer::Pooled(pooled), materialized_request)
+                    }
+                    Err(e) => {
+                        self.release_container(
+                            OperationContainer::Pooled(pooled),
+                            &operation_dir,
+                            false,
+                        )
+                        .await?;
+                        return Err(e.into());
+                    }
+                }
+            }
+            None => {
+                let target = datasets_map.get_by_handle(&request.dataset_handle);
+                let materialized_request = self
+                    .get_io_strategy(target.as_ref())
+                    .materialize_request(request, datasets_map, &operation_dir)
+                    .await
+                    .int_err()?;
+                let container = self
+                    .start_container(
+                        materialized_request.volumes.clone(),
+                        &logs_dir,
+                        &operation_id,
+                    )
+                    .await?;
+                (container, materialized_request)
+            }
+        };
 
-        let materialized_request = io_strategy
-            .materialize_request(request, datasets_map, &operation_dir)
-            .await
-            .int_err()?;
+        let engine_response = match container.engine_container().connect_client().await {
+            Ok(mut engine_client) => {
+                self.execute_transform(
+                    container.engine_container(),
+                    &mut engine_client,
+                    materialized_request.engine_request,
+                )
+                .await
+            }
+            Err(e) => Err(e),
+        };
 
-        let engine_container = EngineContainer::new(
-            self.container_runtime.clone(),
-            self.engine_config.clone(),
-            LogsConfig::new(&logs_dir),
-            &self.image,
-            materialized_request.volumes,
-            &operation_id,
-        )
-        .await?;
+        // Files of a pooled container are moved into the operation directory
+        let (out_data_path, out_checkpoint_path) = match &container {
+            OperationContainer::Pooled(pooled) => (
+                rebase_path(
+                    &materialized_request.out_data_path,
+                    pooled.io_dir(),
+                    &operation_dir,
+                )?,
+                rebase_path(
+                    &materialized_request.out_checkpoint_path,
+                    pooled.io_dir(),
+                    &operation_dir,
+                )?,
+            ),
+            OperationContainer::Dedicated(_) => (
+                materialized_request.out_data_path,
+                materialized_request.out_checkpoint_path,
+            ),
+        };
 
-        let mut engine_client = engine_container.connect_client().await?;
+        self.release_container(container, &operation_dir, engine_response.is_ok())
+            .await?;
 
-        let engine_response = self
-            .execute_transform(
-                &engine_container,
-                &mut engine_client,
-                materialized_request.engine_request,
-            )
-            .await;
+        self.materialize_response(engine_response?, out_data_path, out_checkpoint_path)
+    }
+}
 
-        engine_container.terminate().await?;
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
 
-        self.materialize_response(
-            engine_response?,
-            materialized_request.out_data_path,
-            materialized_request.out_checkpoint_path,
-        )
+/// Container serving a single engine operation
+enum OperationContainer {
+    /// Taken from the pool, sees only the files placed into its I/O directory
+    Pooled(PooledEngineContainer),
+    /// Started for this operation only, with its files mounted as volumes
+    Dedicated(EngineContainer),
+}
+
+impl OperationContainer {
+    fn engine_container(&self) -> &EngineContainer {
+        match self {
+            Self::Pooled(pooled) => pooled.container(),
+            Self::Dedicated(engine_container) => engine_container,
+        }
     }
 }
+
+fn rebase_path(path: &Path, from: &Path, to: &Path) -> Result<PathBuf, InternalError> {
+    Ok(to.join(path.strip_prefix(from).int_err()?))
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
@@ -7,7 +7,7 @@
 // the Business Source License, use of this software will be governed
 // by the Apache License, Version 2.0.
 
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::sync::{Arc, Mutex};
 use std::time::Duration;
 
@@ -16,6 +16,7 @@ use internal_error::{ErrorIntoInternal, ResultIntoInternal};
 use kamu_core::engine::*;
 use kamu_core::*;
 
+use super::engine_container_pool::EngineContainerPool;
 use super::engine_odf::*;
 use crate::utils::docker_images;
 use crate::*;
@@ -28,10 +29,16 @@ pub struct EngineProvisionerLocal {
     flink_engine: Arc<dyn Engine>,
     datafusion_engine: Arc<dyn Engine>,
     risingwave_engine: Arc<dyn Engine>,
+    custom_engines: HashMap<String, CustomEngine>,
     container_runtime: Arc<ContainerRuntime>,
     inner: Arc<Inner>,
 }
 
+struct CustomEngine {
+    engine: Arc<dyn Engine>,
+    image: String,
+}
+
 struct Inner {
     state: Mutex<State>,
     notify: tokio::sync::Notify,
@@ -44,10 +51,12 @@ struct State {
 
 #[dill::component(pub)]
 #[dill::interface(dyn EngineProvisioner)]
+#[dill::scope(dill::Singleton)]
 impl EngineProvisionerLocal {
     #[allow(clippy::needless_pass_by_value)]
     pub fn new(
         config: EngineProvisionerLocalConfig,
+        engine_registry_config: Option<Arc<EngineRegistryConfig>>,
         container_runtime: Arc<ContainerRuntime>,
         run_info_dir: Arc<RunInfoDir>,
     ) -> Self {
@@ -56,31 +65,88 @@ impl EngineProvisionerLocal {
             shutdown_timeout: config.shutdown_timeout,
         };
 
+        // In the host networking mode all engines listen on the same port, so
+        // idle containers would prevent other engines from starting
+        let pool = if !config.pool.is_enabled() {
+            None
+        } else if container_runtime.config.network_ns == NetworkNamespaceType::Host {
+            tracing::warn!("Engine container pooling is not supported in the Host networking mode");
+            None
+        } else {
+            Some(Arc::new(EngineContainerPool::new(
+                container_runtime.clone(),
+                engine_config.clone(),
+                config.pool.clone(),
+                run_info_dir.clone(),
+            )))
+        };
+
+        let custom_engines = engine_registry_config
+            .unwrap_or_default()
+            .custom_engines
+            .iter()
+            .filter(|spec| {
+                if spec.shadows_builtin_engine() {
+                    tracing::warn!(
+                        engine_id = %spec.name,
+                        "Ignoring custom engine that conflicts with a built-in one"
+                    );
+                }
+                !spec.shadows_builtin_engine()
+            })
+            .map(|spec| {
+                let engine: Arc<dyn Engine> = Arc::new(ODFEngine::new(
+                    container_runtime.clone(),
+                    engine_config.clone(),
+                    &spec.image,
+                    spec.io_strategy,
+                    run_info_dir.clone(),
+                    pool.clone(),
+                ));
+                (
+                    spec.name.to_lowercase(),
+                    CustomEngine {
+                        engine,
+                        image: spec.image.clone(),
+                    },
+                )
+            })
+            .collect();
+
         Self {
             spark_engine: Arc::new(ODFEngine::new(
                 container_runtime.clone(),
                 engine_config.clone(),
                 &config.spark_image,
+                EngineIoStrategyKind::Auto,
                 run_info_dir.clone(),
+                pool.clone(),
             )),
             flink_engine: Arc::new(ODFEngine::new(
                 container_runtime.clone(),
                 engine_config.clone(),
                 &config.flink_image,
+                EngineIoStrategyKind::Auto,
                 run_info_dir.clone(),
+                pool.clone(),
             )),
             datafusion_engine: Arc::new(ODFEngine::new(
                 container_runtime.clone(),
                 engine_config.clone(),
                 &config.datafusion_image,
+                EngineIoStrategyKind::Auto,
                 run_info_dir.clone(),
+                pool.clone(),
             )),
             risingwave_engine: Arc::new(ODFEngine::new(
                 container_runtime.clone(),
                 engine_config.clone(),
                 &config.risingwave_image,
+                EngineIoStrategyKind::Auto,
                 run_info_dir.clone(),
+                pool.clone(),
             )),
+            custom_engines,
             container_runtime,
             inner: Arc::new(Inner {
                 state: Mutex::new(State {
@@ -205,7 +271,10 @@ impl EngineProvisioner for EngineProvisionerLocal {
                 self.risingwave_engine.clone(),
                 &self.config.risingwave_image,
             )),
-            _ => Err(format!("Unsupported engine {engine_id}").int_err()),
+            _ => match self.custom_engines.get(&engine_id.to_lowercase()) {
+                Some(custom) => Ok((custom.engine.clone(), &custom.image)),
+                None => Err(format!("Unsupported engine {engine_id}").int_err()),
+            },
         }?;
 
         self.ensure_image(image, listener.clone()).await?;
@@ -230,6 +299,8 @@ pub struct EngineProvisionerLocalConfig {
     pub start_timeout: Duration,
     /// Timeout for waiting for engine container to shutdown cleanly
     pub shutdown_timeout: Duration,
+    /// Warm engine containers reused across operations
+    pub pool: EnginePoolConfig,
 
     // TODO: Remove in favor of explicit images in ODF protocol
     pub spark_image: String,
@@ -245,6 +316,7 @@ impl Default for EngineProvisionerLocalConfig {
             max_concurrency: None,
             start_timeout: Duration::from_secs(30),
             shutdown_timeout: Duration::from_secs(5),
+            pool: EnginePoolConfig::default(),
             spark_image: docker_images::SPARK.to_owned(),
             flink_image: docker_images::FLINK.to_owned(),
             datafusion_image: docker_images::DATAFUSION.to_owned(),
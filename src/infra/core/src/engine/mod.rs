@@ -9,6 +9,7 @@
 
 mod engine_config;
 mod engine_container;
+mod engine_container_pool;
 mod engine_datafusion_inproc;
 mod engine_io_strategy;
 mod engine_odf;
@@ -0,0 +1,185 @@
+// Copyright Kamu Data, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::collections::HashSet;
+
+use internal_error::{ErrorIntoInternal, InternalError, ResultIntoInternal};
+use kamu_core::{CompactedDataSlice, CompactedDataSlices};
+use odf::metadata::serde::yaml::Manifest;
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+const COMPACTED_DATA_SLICES_KEY: &str = "compacted-data-slices";
+const COMPACTED_DATA_SLICES_KIND: &str = "CompactedDataSlices";
+
+/// Serializes modifications of the index within the process.
+///
+/// Note that the index is local to the node: it is kept in the info repository
+/// of the dataset, is not transferred by sync, and modifications made by other
+/// processes working with the same storage are not coordinated.
+static COMPACTED_DATA_SLICES_LOCK: tokio::sync::Mutex<()> = tokio::sync::Mutex::const_new(());
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+pub(crate) async fn read_compacted_data_slices(
+    dataset: &dyn odf::Dataset,
+) -> Result<CompactedDataSlices, InternalError> {
+    match dataset.as_info_repo().get(COMPACTED_DATA_SLICES_KEY).await {
+        Ok(bytes) => {
+            let manifest: Manifest<CompactedDataSlices> =
+                serde_yaml::from_slice(&bytes[..]).int_err()?;
+            if manifest.kind != COMPACTED_DATA_SLICES_KIND {
+                return Err(format!(
+                    "Unexpected manifest kind '{}' of compacted data slices",
+                    manifest.kind
+                )
+                .int_err());
+            }
+            Ok(manifest.content)
+        }
+        Err(odf::storage::GetNamedError::Internal(e)) => Err(e),
+        Err(odf::storage::GetNamedError::Access(e)) => Err(e.int_err()),
+        Err(odf::storage::GetNamedError::NotFound(_)) => Ok(CompactedDataSlices::default()),
+    }
+}
+
+pub(crate) async fn write_compacted_data_slices(
+    dataset: &dyn odf::Dataset,
+    compacted_data_slices: &CompactedDataSlices,
+) -> Result<(), InternalError> {
+    let manifest = Manifest {
+        kind: COMPACTED_DATA_SLICES_KIND.to_owned(),
+        version: 1,
+        content: compacted_data_slices.clone(),
+    };
+    let manifest_yaml = serde_yaml::to_string(&manifest).int_err()?;
+    dataset
+        .as_info_repo()
+        .set(COMPACTED_DATA_SLICES_KEY, manifest_yaml.as_bytes())
+        .await
+        .int_err()?;
+    Ok(())
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+/// Replaces the merged slices of the dataset, unless the index was modified
+/// since `expected_slices` were read, in which case the files of the new slices
+/// are deleted and an error is returned. See [`replace_compacted_data_slices`]
+pub(crate) async fn update_compacted_data_slices(
+    dataset: &dyn odf::Dataset,
+    expected_slices: &[CompactedDataSlice],
+    slices: Vec<CompactedDataSlice>,
+) -> Result<usize, InternalError> {
+    let _guard = COMPACTED_DATA_SLICES_LOCK.lock().await;
+
+    let previous_slices = read_compacted_data_slices(dataset).await?.slices;
+    if previous_slices != expected_slices {
+        for slice in &slices {
+            let is_new = |s: &CompactedDataSlice| s.physical_hash != slice.physical_hash;
+            if expected_slices.iter().all(is_new) && previous_slices.iter().all(is_new) {
+                dataset
+                    .as_data_repo()
+                    .delete(&slice.physical_hash)
+                    .await
+                    .int_err()?;
+            }
+        }
+
+        return InternalError::bail(
+            "Merged data slices were modified by a concurrent compaction of the dataset",
+        );
+    }
+
+    replace_compacted_data_slices(dataset, previous_slices, slices).await
+}
+
+/// Drops the merged slices that don't belong to the current chain anymore.
+/// Returns the number of the remaining slices
+pub(crate) async fn drop_stale_compacted_data_slices(
+    dataset: &dyn odf::Dataset,
+) -> Result<usize, InternalError> {
+    let _guard = COMPACTED_DATA_SLICES_LOCK.lock().await;
+
+    let previous_slices = read_compacted_data_slices(dataset).await?.slices;
+    if previous_slices.is_empty() {
+        return Ok(0);
+    }
+
+    replace_compacted_data_slices(dataset, previous_slices.clone(), previous_slices).await
+}
+
+/// Replaces the merged slices of the dataset, dropping those that don't belong
+/// to the current chain. Files of the dropped slices are deleted, unless they
+/// are still referenced by the chain or by the remaining slices. Returns the
+/// number of the remaining slices
+async fn replace_compacted_data_slices(
+    dataset: &dyn odf::Dataset,
+    previous_slices: Vec<CompactedDataSlice>,
+    slices: Vec<CompactedDataSlice>,
+) -> Result<usize, InternalError> {
+    use futures::TryStreamExt;
+
+    let chain = dataset.as_metadata_chain();
+    let head = chain.resolve_ref(&odf::BlockRef::Head).await.int_err()?;
+
+    let mut chain_block_hashes = HashSet::new();
+    let mut chain_data_hashes = HashSet::new();
+
+    let mut block_stream = chain.iter_blocks_interval(&head, None, false);
+    while let Some((block_hash, block)) = block_stream.try_next().await.int_err()? {
+        chain_block_hashes.insert(block_hash);
+
+        let new_data = match block.event {
+            odf::MetadataEvent::AddData(e) => e.new_data,
+            odf::MetadataEvent::ExecuteTransform(e) => e.new_data,
+            _ => None,
+        };
+        if let Some(new_data) = new_data {
+            chain_data_hashes.insert(new_data.physical_hash);
+        }
+    }
+
+    let (valid_slices, stale_slices): (Vec<_>, Vec<_>) = slices
+        .into_iter()
+        .partition(|s| chain_block_hashes.contains(&s.last_block_hash));
+
+    let referenced_hashes: HashSet<_> = valid_slices
+        .iter()
+        .map(|s| &s.physical_hash)
+        .chain(chain_data_hashes.iter())
+        .collect();
+
+    let stale_hashes: HashSet<_> = previous_slices
+        .iter()
+        .chain(stale_slices.iter())
+        .map(|s| &s.physical_hash)
+        .filter(|hash| !referenced_hashes.contains(hash))
+        .collect();
+
+    let num_valid_slices = valid_slices.len();
+
+    write_compacted_data_slices(
+        dataset,
+        &CompactedDataSlices {
+            slices: valid_slices,
+        },
+    )
+    .await?;
+
+    // Files are deleted only after the index stopped referring to them
+    for hash in stale_hashes {
+        tracing::debug!(%hash, "Deleting file of a stale merged data slice");
+        dataset.as_data_repo().delete(hash).await.int_err()?;
+    }
+
+    Ok(num_valid_slices)
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
@@ -11,15 +11,17 @@ use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
+use datafusion::arrow::array::{Int64Array, RecordBatch};
 use datafusion::prelude::*;
 use dill::{component, interface};
 use file_utils::OwnedFile;
-use internal_error::ResultIntoInternal;
+use internal_error::{InternalError, ResultIntoInternal};
 use kamu_core::*;
 use random_names::get_random_name;
 use time_source::SystemTimeSource;
 use url::Url;
 
+use super::{drop_stale_compacted_data_slices, update_compacted_data_slices};
 use crate::new_session_context;
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
@@ -58,53 +60,132 @@ impl CompactionExecutorImpl {
         plan: &CompactionPlan,
         compaction_dir_path: &Path,
     ) -> Result<HashMap<usize, PathBuf>, CompactionExecutionError> {
+        use futures::{StreamExt, TryStreamExt};
+
         let ctx = new_session_context(self.object_store_registry.clone());
 
-        let mut new_file_paths = HashMap::new();
+        // Batches are independent of one another, so they can be merged concurrently
+        let new_file_paths =
+            futures::stream::iter(plan.data_slice_batches.iter().enumerate().filter_map(
+                |(index, data_slice_batch)| match data_slice_batch {
+                    CompactionDataSliceBatch::CompactedBatch(data_slice_batch_info) => {
+                        Some((index, data_slice_batch_info))
+                    }
+                    CompactionDataSliceBatch::SingleBlock(_) => None,
+                },
+            ))
+            .map(|(index, data_slice_batch_info)| {
+                let ctx = ctx.clone();
+                async move {
+                    // FIXME: The .parquet extension is currently necessary for DataFusion to
+                    // respect the single-file output
+                    // See: https://github.com/apache/datafusion/issues/13323
+                    let new_file_path =
+                        compaction_dir_path.join(format!("merge-slice-{index}.parquet").as_str());
 
-        for (index, data_slice_batch) in plan.data_slice_batches.iter().enumerate() {
-            if let CompactionDataSliceBatch::CompactedBatch(data_slice_batch_info) =
-                data_slice_batch
-            {
-                let data_frame = ctx
-                    .read_parquet(
-                        data_slice_batch_info.data_slices_batch.clone(),
-                        datafusion::execution::options::ParquetReadOptions {
-                            file_extension: "",
-                            ..Default::default()
-                        },
-                    )
-                    .await
-                    .int_err()?
-                    // TODO: PERF: Consider passing sort order hint to `read_parquet` to let DF now
-                    // that the data is already pre-sorted
-                    .sort(vec![
-                        col(Column::from_name(&plan.offset_column_name)).sort(true, false)
-                    ])
-                    .int_err()?;
-
-                // FIXME: The .parquet extension is currently necessary for DataFusion to
-                // respect the single-file output
-                // See: https://github.com/apache/datafusion/issues/13323
-                let new_file_path =
-                    compaction_dir_path.join(format!("merge-slice-{index}.parquet").as_str());
-
-                data_frame
-                    .write_parquet(
-                        new_file_path.to_str().unwrap(),
-                        datafusion::dataframe::DataFrameWriteOptions::new()
-                            .with_single_file_output(true),
-                        None,
-                    )
-                    .await
-                    .int_err()?;
-                new_file_paths.insert(index, new_file_path);
-            }
-        }
+                    self.merge_batch(&ctx, plan, data_slice_batch_info, &new_file_path)
+                        .await?;
+
+                    Ok::<_, CompactionExecutionError>((index, new_file_path))
+                }
+            })
+            .buffer_unordered(plan.merge_parallelism)
+            .try_collect()
+            .await?;
 
         Ok(new_file_paths)
     }
 
+    async fn merge_batch(
+        &self,
+        ctx: &SessionContext,
+        plan: &CompactionPlan,
+        data_slice_batch_info: &CompactionDataSliceBatchInfo,
+        new_file_path: &Path,
+    ) -> Result<(), CompactionExecutionError> {
+        let offset_column = col(Column::from_name(&plan.offset_column_name));
+
+        let data_frame = ctx
+            .read_parquet(
+                data_slice_batch_info.data_slices_batch.clone(),
+                datafusion::execution::options::ParquetReadOptions {
+                    file_extension: "",
+                    ..Default::default()
+                },
+            )
+            .await
+            .int_err()?;
+
+        // Records are re-sorted by reassigning offsets within the interval of the
+        // batch, as the data file must remain ordered by offset
+        let data_frame = if plan.sort_columns.is_empty() {
+            data_frame
+        } else {
+            let sort_order = plan
+                .sort_columns
+                .iter()
+                .map(|c| col(Column::from_name(c)).sort(true, true))
+                .chain(std::iter::once(offset_column.clone().sort(true, true)))
+                .collect();
+
+            let records = data_frame
+                .sort(sort_order)
+                .int_err()?
+                .collect()
+                .await
+                .int_err()?;
+
+            let records = Self::reassign_offsets(
+                records,
+                &plan.offset_column_name,
+                data_slice_batch_info.lower_bound.start_offset,
+            )?;
+
+            ctx.read_batches(records).int_err()?
+        };
+
+        data_frame
+            // TODO: PERF: Consider passing sort order hint to `read_parquet` to let DF now
+            // that the data is already pre-sorted
+            .sort(vec![offset_column.sort(true, false)])
+            .int_err()?
+            .write_parquet(
+                new_file_path.to_str().unwrap(),
+                datafusion::dataframe::DataFrameWriteOptions::new().with_single_file_output(true),
+                None,
+            )
+            .await
+            .int_err()?;
+
+        Ok(())
+    }
+
+    /// Assigns consecutive offsets starting with `start_offset` to the records
+    /// in the order they appear
+    fn reassign_offsets(
+        records: Vec<RecordBatch>,
+        offset_column_name: &str,
+        start_offset: u64,
+    ) -> Result<Vec<RecordBatch>, InternalError> {
+        let mut next_offset = i64::try_from(start_offset).int_err()?;
+
+        records
+            .into_iter()
+            .map(|batch| {
+                let offset_index = batch.schema().index_of(offset_column_name).int_err()?;
+                let num_rows = i64::try_from(batch.num_rows()).int_err()?;
+
+                let mut columns = batch.columns().to_vec();
+                columns[offset_index] = Arc::new(Int64Array::from_iter_values(
+                    next_offset..next_offset + num_rows,
+                ));
+                next_offset += num_rows;
+
+                RecordBatch::try_new(batch.schema(), columns).int_err()
+            })
+            .collect()
+    }
+
     async fn commit_new_blocks(
         &self,
         target: &ResolvedDataset,
@@ -112,9 +193,9 @@ impl CompactionExecutorImpl {
         new_file_paths: HashMap<usize, PathBuf>,
     ) -> Result<(Vec<Url>, odf::Multihash, usize), CompactionExecutionError> {
         let chain = target.as_metadata_chain();
-        let mut current_head = plan.seed.clone();
+        let mut current_head = plan.base_block.clone();
         let mut old_data_slices: Vec<Url> = vec![];
-        // set it to 1 to include seed block
+        // set it to 1 to include base block
         let mut new_num_blocks: usize = 1;
 
         for (index, data_slice_batch) in plan.data_slice_batches.iter().enumerate().rev() {
@@ -186,6 +267,78 @@ impl CompactionExecutorImpl {
 
         Ok((old_data_slices, current_head, new_num_blocks))
     }
+
+    async fn save_merged_data_slices(
+        &self,
+        target: &ResolvedDataset,
+        plan: &CompactionPlan,
+        new_file_paths: HashMap<usize, PathBuf>,
+    ) -> Result<(usize, usize), CompactionExecutionError> {
+        let mut compacted_data_slices = plan.compacted_data_slices.clone();
+        let mut num_merged_slices = 0;
+
+        for (index, data_slice_batch) in plan.data_slice_batches.iter().enumerate() {
+            let CompactionDataSliceBatch::CompactedBatch(data_slice_batch_info) = data_slice_batch
+            else {
+                continue;
+            };
+
+            let new_file_path = new_file_paths
+                .get(&index)
+                .expect("File path for the compacted chunk should be defined")
+                .clone();
+
+            let size = std::fs::metadata(&new_file_path).int_err()?.len();
+            let physical_hash = {
+                let path = new_file_path.clone();
+                tokio::task::spawn_blocking(move || {
+                    odf::utils::data::hash::get_file_physical_hash(&path)
+                })
+                .await
+                .int_err()?
+                .int_err()?
+            };
+
+            target
+                .as_data_repo()
+                .insert_file_move(
+                    &new_file_path,
+                    odf::storage::InsertOpts {
+                        precomputed_hash: Some(&physical_hash),
+                        expected_hash: None,
+                        size_hint: Some(size),
+                    },
+                )
+                .await
+                .int_err()?;
+
+            compacted_data_slices.push(CompactedDataSlice {
+                physical_hash,
+                size,
+                start_offset: data_slice_batch_info.lower_bound.start_offset,
+                end_offset: data_slice_batch_info.upper_bound.end_offset,
+                last_block_hash: data_slice_batch_info
+                    .upper_bound
+                    .block_hash
+                    .clone()
+                    .expect("Batch should have its newest block defined"),
+            });
+            num_merged_slices += data_slice_batch_info.data_slices_batch.len();
+        }
+
+        let num_new_files = compacted_data_slices.len() - plan.compacted_data_slices.len();
+
+        // The chain could have been rewritten since planning, so merged slices
+        // are checked against it once again
+        update_compacted_data_slices(
+            target.as_ref(),
+            &plan.indexed_data_slices,
+            compacted_data_slices,
+        )
+        .await?;
+
+        Ok((num_merged_slices, num_new_files))
+    }
 }
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
@@ -199,9 +352,9 @@ impl CompactionExecutor for CompactionExecutorImpl {
         plan: CompactionPlan,
         maybe_listener: Option<Arc<dyn CompactionListener>>,
     ) -> Result<CompactionResult, CompactionExecutionError> {
-        // if slices amount +1(seed block) eq to amount of blocks we will not perform
+        // if slices amount +1(base block) eq to amount of blocks we will not perform
         // compaction
-        if plan.data_slice_batches.len() + 1 == plan.old_num_blocks {
+        if plan.has_no_effect() {
             return Ok(CompactionResult::NothingToDo);
         }
 
@@ -213,6 +366,23 @@ impl CompactionExecutor for CompactionExecutorImpl {
         listener.begin_phase(CompactionPhase::MergeDataslices);
         let new_file_paths = self.merge_files(&plan, &compaction_dir_path).await?;
 
+        if plan.mode == CompactionMode::Soft {
+            tracing::debug!("Saving merged data slices");
+            listener.begin_phase(CompactionPhase::SaveMergedDataSlices);
+            let (num_merged_slices, num_new_files) = self
+                .save_merged_data_slices(&target, &plan, new_file_paths)
+                .await?;
+
+            let res = CompactionResult::DataSlicesMerged {
+                num_merged_slices,
+                num_new_files,
+            };
+
+            listener.execute_success(&res);
+
+            return Ok(res);
+        }
+
         tracing::debug!("Committing new compacted blocks");
         listener.begin_phase(CompactionPhase::CommitNewBlocks);
         let (_old_data_slices, new_head, new_num_blocks) = self
@@ -232,6 +402,9 @@ impl CompactionExecutor for CompactionExecutorImpl {
             )
             .await?;
 
+        // Rewritten history invalidates the slices merged by soft compactions
+        drop_stale_compacted_data_slices(target.as_ref()).await?;
+
         let res = CompactionResult::Success {
             old_head: plan.old_head,
             new_head,
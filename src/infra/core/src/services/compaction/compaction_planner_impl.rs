@@ -8,11 +8,15 @@
 // by the Apache License, Version 2.0.
 
 use std::cmp::Ordering;
+use std::collections::HashMap;
 use std::sync::Arc;
 
 use dill::{component, interface};
 use internal_error::ResultIntoInternal;
 use kamu_core::*;
+use odf::dataset::MetadataChainExt;
+
+use super::read_compacted_data_slices;
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
 
@@ -28,7 +32,8 @@ impl CompactionPlannerImpl {
             target=%target.get_handle(),
             max_slice_size,
             max_slice_records,
-            keep_metadata_only
+            keep_metadata_only,
+            start_offset
         )
     )]
     async fn plan_dataset_compaction(
@@ -37,17 +42,17 @@ impl CompactionPlannerImpl {
         max_slice_size: u64,
         max_slice_records: u64,
         keep_metadata_only: bool,
+        start_offset: Option<u64>,
         listener: Arc<dyn CompactionListener>,
-    ) -> Result<CompactionPlan, CompactionPlanningError> {
+    ) -> Result<(CompactionPlan, odf::metadata::DatasetVocabulary), CompactionPlanningError> {
         listener.begin_phase(CompactionPhase::GatherChainInfo);
 
         // Declare mut values for result
 
         let mut old_num_blocks: usize = 0;
-        let mut maybe_seed: Option<odf::Multihash> = None;
+        let mut maybe_base_block: Option<odf::Multihash> = None;
 
         let mut current_hash: Option<odf::Multihash> = None;
-        let mut vocab_event: Option<odf::metadata::SetVocab> = None;
         let mut data_slice_batch_info = CompactionDataSliceBatchInfo::default();
         let mut data_slice_batches: Vec<CompactionDataSliceBatch> = vec![];
         let (mut batch_size, mut batch_records) = (0u64, 0u64);
@@ -61,7 +66,6 @@ impl CompactionPlannerImpl {
 
         {
             use futures::TryStreamExt;
-            use odf::dataset::MetadataChainExt;
             let mut block_stream = chain.iter_blocks_interval(&head, None, false);
             while let Some((block_hash, block)) = block_stream.try_next().await? {
                 old_num_blocks += 1;
@@ -69,6 +73,20 @@ impl CompactionPlannerImpl {
                     odf::MetadataEvent::AddData(add_data_event) => {
                         if !keep_metadata_only && let Some(output_slice) = &add_data_event.new_data
                         {
+                            // Block that precedes the compacted range stays intact and
+                            // becomes the base for the new blocks
+                            if let Some(start_offset) = start_offset
+                                && output_slice.offset_interval.end < start_offset
+                            {
+                                self.append_add_data_batch_to_chain_info(
+                                    &mut data_slice_batches,
+                                    current_hash.as_ref(),
+                                    &mut data_slice_batch_info,
+                                );
+                                maybe_base_block = Some(block_hash);
+                                break;
+                            }
+
                             let data_slice_url = object_data_repo
                                 .get_internal_url(&output_slice.physical_hash)
                                 .await;
@@ -79,6 +97,8 @@ impl CompactionPlannerImpl {
                             if data_slice_batch_info.data_slices_batch.is_empty() {
                                 data_slice_batch_info.upper_bound.end_offset =
                                     output_slice.offset_interval.end;
+                                data_slice_batch_info.upper_bound.block_hash =
+                                    Some(block_hash.clone());
                             }
 
                             let current_records = output_slice.num_records();
@@ -96,6 +116,8 @@ impl CompactionPlannerImpl {
                                     data_slice_batch_info = CompactionDataSliceBatchInfo::default();
                                     data_slice_batch_info.upper_bound.end_offset =
                                         output_slice.offset_interval.end;
+                                    data_slice_batch_info.upper_bound.block_hash =
+                                        Some(block_hash.clone());
                                 }
 
                                 data_slice_batch_info.data_slices_batch = vec![data_slice_url];
@@ -130,16 +152,13 @@ impl CompactionPlannerImpl {
                                 add_data_event.new_watermark;
                         }
                     }
-                    odf::MetadataEvent::Seed(_) => maybe_seed = Some(block_hash),
+                    odf::MetadataEvent::Seed(_) => maybe_base_block = Some(block_hash),
                     odf::MetadataEvent::ExecuteTransform(_) => {
                         if keep_metadata_only {
                             continue;
                         }
                     }
-                    event => {
-                        if let odf::MetadataEvent::SetVocab(set_vocab_event) = event {
-                            vocab_event = Some(set_vocab_event);
-                        }
+                    _ => {
                         let is_appended = self.append_add_data_batch_to_chain_info(
                             &mut data_slice_batches,
                             current_hash.as_ref(),
@@ -155,17 +174,196 @@ impl CompactionPlannerImpl {
             }
         }
 
-        let vocab: odf::metadata::DatasetVocabulary = vocab_event.unwrap_or_default().into();
+        let vocab: odf::metadata::DatasetVocabulary = chain
+            .accept_one(odf::dataset::SearchSetVocabVisitor::new())
+            .await
+            .int_err()?
+            .into_event()
+            .unwrap_or_default()
+            .into();
+
+        Ok((
+            CompactionPlan {
+                data_slice_batches,
+                old_head: head,
+                offset_column_name: vocab.offset_column.clone(),
+                sort_columns: Vec::new(),
+                merge_parallelism: DEFAULT_MERGE_PARALLELISM,
+                mode: CompactionMode::Hard,
+                base_block: maybe_base_block.expect("Seed must be present"),
+                old_num_blocks,
+                compacted_data_slices: Vec::new(),
+                indexed_data_slices: Vec::new(),
+            },
+            vocab,
+        ))
+    }
+
+    #[tracing::instrument(
+        level = "debug",
+        skip_all,
+        fields(
+            target=%target.get_handle(),
+            max_slice_size,
+            max_slice_records
+        )
+    )]
+    async fn plan_soft_compaction(
+        &self,
+        target: ResolvedDataset,
+        max_slice_size: u64,
+        max_slice_records: u64,
+        listener: Arc<dyn CompactionListener>,
+    ) -> Result<CompactionPlan, CompactionPlanningError> {
+        use futures::TryStreamExt;
+
+        listener.begin_phase(CompactionPhase::GatherChainInfo);
+
+        let chain = target.as_metadata_chain();
+        let head = chain.resolve_ref(&odf::BlockRef::Head).await?;
+
+        let object_data_repo = target.as_data_repo();
+
+        // Slices merged by previous compactions are keyed by their newest block, so
+        // that the ones left over from a rewritten history are dropped
+        let indexed_data_slices = read_compacted_data_slices(target.as_ref()).await?.slices;
+        let mut known_compacted_slices: HashMap<_, _> = indexed_data_slices
+            .iter()
+            .map(|s| (s.last_block_hash.clone(), s.clone()))
+            .collect();
+
+        let mut old_num_blocks: usize = 0;
+        let mut compacted_data_slices = Vec::new();
+        let mut data_slice_batches = Vec::new();
+        let mut data_slice_batch_info = CompactionDataSliceBatchInfo::default();
+        let (mut batch_size, mut batch_records) = (0u64, 0u64);
+
+        // Start offset of the merged slice covering the blocks being visited
+        let mut covered_from_offset: Option<u64> = None;
+
+        let mut block_stream = chain.iter_blocks_interval(&head, None, false);
+        while let Some((block_hash, block)) = block_stream.try_next().await? {
+            old_num_blocks += 1;
+
+            let output_slice = match block.event {
+                odf::MetadataEvent::AddData(e) => e.new_data,
+                odf::MetadataEvent::ExecuteTransform(e) => e.new_data,
+                _ => None,
+            };
+            let Some(output_slice) = output_slice else {
+                continue;
+            };
+
+            if let Some(start_offset) = covered_from_offset {
+                if output_slice.offset_interval.start >= start_offset {
+                    continue;
+                }
+                covered_from_offset = None;
+            }
+
+            if let Some(compacted_slice) = known_compacted_slices.remove(&block_hash) {
+                Self::append_soft_batch(&mut data_slice_batches, &mut data_slice_batch_info);
+                (batch_size, batch_records) = (0, 0);
+
+                covered_from_offset = Some(compacted_slice.start_offset);
+                compacted_data_slices.push(compacted_slice);
+                continue;
+            }
+
+            let current_records = output_slice.num_records();
+
+            if batch_size + output_slice.size > max_slice_size
+                || batch_records + current_records > max_slice_records
+            {
+                Self::append_soft_batch(&mut data_slice_batches, &mut data_slice_batch_info);
+                (batch_size, batch_records) = (0, 0);
+            }
+
+            if data_slice_batch_info.data_slices_batch.is_empty() {
+                data_slice_batch_info.upper_bound.end_offset = output_slice.offset_interval.end;
+                data_slice_batch_info.upper_bound.block_hash = Some(block_hash);
+            }
+
+            data_slice_batch_info.data_slices_batch.push(
+                object_data_repo
+                    .get_internal_url(&output_slice.physical_hash)
+                    .await,
+            );
+            data_slice_batch_info.lower_bound.start_offset = output_slice.offset_interval.start;
+            batch_size += output_slice.size;
+            batch_records += current_records;
+        }
+
+        Self::append_soft_batch(&mut data_slice_batches, &mut data_slice_batch_info);
+
+        let vocab: odf::metadata::DatasetVocabulary = chain
+            .accept_one(odf::dataset::SearchSetVocabVisitor::new())
+            .await
+            .int_err()?
+            .into_event()
+            .unwrap_or_default()
+            .into();
 
         Ok(CompactionPlan {
-            data_slice_batches,
+            base_block: head.clone(),
             old_head: head,
-            offset_column_name: vocab.offset_column,
-            seed: maybe_seed.expect("Seed must be present"),
             old_num_blocks,
+            offset_column_name: vocab.offset_column,
+            sort_columns: Vec::new(),
+            merge_parallelism: DEFAULT_MERGE_PARALLELISM,
+            mode: CompactionMode::Soft,
+            data_slice_batches,
+            compacted_data_slices,
+            indexed_data_slices,
         })
     }
 
+    /// Single slices are left as is, as there is nothing to merge them with
+    fn append_soft_batch(
+        data_slice_batches: &mut Vec<CompactionDataSliceBatch>,
+        data_slice_batch_info: &mut CompactionDataSliceBatchInfo,
+    ) {
+        let batch_info = std::mem::take(data_slice_batch_info);
+        if batch_info.data_slices_batch.len() > 1 {
+            data_slice_batches.push(CompactionDataSliceBatch::CompactedBatch(batch_info));
+        }
+    }
+
+    async fn resolve_sort_columns(
+        &self,
+        target: &ResolvedDataset,
+        sort_by: Option<&CompactionSortBy>,
+        vocab: &odf::metadata::DatasetVocabulary,
+    ) -> Result<Vec<String>, CompactionPlanningError> {
+        let columns = match sort_by {
+            None => return Ok(Vec::new()),
+            Some(CompactionSortBy::EventTime) => return Ok(vec![vocab.event_time_column.clone()]),
+            Some(CompactionSortBy::Columns(columns)) => columns.clone(),
+        };
+
+        let schema = target
+            .as_metadata_chain()
+            .accept_one(odf::dataset::SearchSetDataSchemaVisitor::new())
+            .await
+            .int_err()?
+            .into_event()
+            .map(|e| e.schema_as_arrow())
+            .transpose()
+            .int_err()?;
+
+        if let Some(schema) = schema
+            && let Some(column) = columns.iter().find(|c| schema.field_with_name(c).is_err())
+        {
+            return Err(UnknownSortColumnError {
+                dataset_alias: target.get_alias().clone(),
+                column: column.clone(),
+            }
+            .into());
+        }
+
+        Ok(columns)
+    }
+
     fn append_add_data_batch_to_chain_info(
         &self,
         data_slice_batches: &mut Vec<CompactionDataSliceBatch>,
@@ -206,7 +404,11 @@ impl CompactionPlanner for CompactionPlannerImpl {
             .int_err()?
             .kind;
 
-        if !options.keep_metadata_only && dataset_kind != odf::DatasetKind::Root {
+        // Soft compaction leaves the chain intact, so dependents are not affected
+        if options.mode == CompactionMode::Hard
+            && !options.keep_metadata_only
+            && dataset_kind != odf::DatasetKind::Root
+        {
             return Err(CompactionPlanningError::InvalidDatasetKind(
                 InvalidDatasetKindError {
                     dataset_alias: target.get_alias().clone(),
@@ -221,16 +423,47 @@ impl CompactionPlanner for CompactionPlannerImpl {
             .max_slice_records
             .unwrap_or(DEFAULT_MAX_SLICE_RECORDS);
 
-        match self
-            .plan_dataset_compaction(
-                target,
-                max_slice_size,
-                max_slice_records,
-                options.keep_metadata_only,
-                listener.clone(),
-            )
-            .await
-        {
+        let plan_res = async {
+            let mut plan = match options.mode {
+                CompactionMode::Hard => {
+                    let (mut plan, vocab) = self
+                        .plan_dataset_compaction(
+                            target.clone(),
+                            max_slice_size,
+                            max_slice_records,
+                            options.keep_metadata_only,
+                            options.start_offset,
+                            listener.clone(),
+                        )
+                        .await?;
+
+                    plan.sort_columns = self
+                        .resolve_sort_columns(&target, options.sort_by.as_ref(), &vocab)
+                        .await?;
+                    plan
+                }
+                // Re-sorting would reassign offsets, which soft compaction must preserve
+                CompactionMode::Soft => {
+                    self.plan_soft_compaction(
+                        target.clone(),
+                        max_slice_size,
+                        max_slice_records,
+                        listener.clone(),
+                    )
+                    .await?
+                }
+            };
+
+            plan.merge_parallelism = options
+                .merge_parallelism
+                .unwrap_or(DEFAULT_MERGE_PARALLELISM)
+                .max(1);
+
+            Ok::<_, CompactionPlanningError>(plan)
+        }
+        .await;
+
+        match plan_res {
             Ok(plan) => {
                 listener.plan_success(&plan);
                 Ok(plan)
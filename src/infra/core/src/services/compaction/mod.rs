@@ -7,8 +7,10 @@
 // the Business Source License, use of this software will be governed
 // by the Apache License, Version 2.0.
 
+mod compacted_data_slices_repo;
 mod compaction_executor_impl;
 mod compaction_planner_impl;
 
+pub(crate) use compacted_data_slices_repo::*;
 pub use compaction_executor_impl::*;
 pub use compaction_planner_impl::*;
@@ -0,0 +1,189 @@
+// Copyright Kamu Data, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::sync::Arc;
+
+use datafusion::arrow::array::AsArray;
+use datafusion::arrow::datatypes::{DataType, Int64Type};
+use datafusion::arrow::record_batch::RecordBatch;
+use dill::*;
+use internal_error::{InternalError, ResultIntoInternal};
+use kamu_core::auth::{DatasetAction, DatasetActionAuthorizer};
+use kamu_core::*;
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+pub struct DatasetChangeFeedServiceImpl {
+    dataset_registry: Arc<dyn DatasetRegistry>,
+    dataset_action_authorizer: Arc<dyn DatasetActionAuthorizer>,
+    dataset_changes_service: Arc<dyn DatasetChangesService>,
+    query_service: Arc<dyn QueryService>,
+}
+
+#[component(pub)]
+#[interface(dyn DatasetChangeFeedService)]
+impl DatasetChangeFeedServiceImpl {
+    pub fn new(
+        dataset_registry: Arc<dyn DatasetRegistry>,
+        dataset_action_authorizer: Arc<dyn DatasetActionAuthorizer>,
+        dataset_changes_service: Arc<dyn DatasetChangesService>,
+        query_service: Arc<dyn QueryService>,
+    ) -> Self {
+        Self {
+            dataset_registry,
+            dataset_action_authorizer,
+            dataset_changes_service,
+            query_service,
+        }
+    }
+
+    fn caught_up(cursor: &ChangeFeedCursor, head: odf::Multihash) -> DatasetChangesChunk {
+        DatasetChangesChunk {
+            schema: None,
+            record_batches: Vec::new(),
+            num_records: 0,
+            cursor: ChangeFeedCursor {
+                last_offset: cursor.last_offset,
+                caught_up_head: Some(head),
+            },
+            caught_up: true,
+        }
+    }
+
+    fn last_offset(
+        record_batches: &[RecordBatch],
+        offset_column: &str,
+    ) -> Result<Option<u64>, InternalError> {
+        let Some(last_batch) = record_batches.iter().rev().find(|b| b.num_rows() != 0) else {
+            return Ok(None);
+        };
+
+        let Some(column) = last_batch.column_by_name(offset_column) else {
+            return InternalError::bail(format!("Offset column {offset_column} not found"));
+        };
+
+        let offsets = datafusion::arrow::compute::cast(column, &DataType::Int64).int_err()?;
+        let last_offset = offsets
+            .as_primitive::<Int64Type>()
+            .value(last_batch.num_rows() - 1);
+
+        Ok(Some(u64::try_from(last_offset).int_err()?))
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[async_trait::async_trait]
+impl DatasetChangeFeedService for DatasetChangeFeedServiceImpl {
+    #[tracing::instrument(level = "debug", skip_all, fields(%dataset_ref, ?cursor, %max_records))]
+    async fn read_changes(
+        &self,
+        dataset_ref: &odf::DatasetRef,
+        cursor: &ChangeFeedCursor,
+        max_records: u64,
+    ) -> Result<DatasetChangesChunk, QueryError> {
+        let dataset_handle = self
+            .dataset_registry
+            .resolve_dataset_handle_by_ref(dataset_ref)
+            .await?;
+
+        // Access is re-checked on every read, as permissions can be revoked while
+        // the consumer is following the dataset
+        self.dataset_action_authorizer
+            .check_action_allowed(&dataset_handle.id, DatasetAction::Read)
+            .await?;
+
+        let resolved_dataset = self
+            .dataset_registry
+            .get_dataset_by_handle(&dataset_handle)
+            .await;
+
+        use odf::dataset::MetadataChainExt;
+        let head = resolved_dataset
+            .as_metadata_chain()
+            .resolve_ref(&odf::BlockRef::Head)
+            .await
+            .int_err()?;
+
+        // Avoid querying the data when new blocks don't carry any records
+        if let Some(caught_up_head) = &cursor.caught_up_head {
+            if *caught_up_head == head {
+                return Ok(Self::caught_up(cursor, head));
+            }
+
+            // Note: history of the dataset could have been rewritten (e.g. by a reset or
+            // compaction), in which case the increment can't be computed and we fall back
+            // to querying the data
+            if let Ok(increment) = self
+                .dataset_changes_service
+                .get_increment_between(&dataset_handle.id, Some(caught_up_head), &head)
+                .await
+                && increment.num_records == 0
+            {
+                return Ok(Self::caught_up(cursor, head));
+            }
+        }
+
+        let df = match self
+            .query_service
+            .get_changes(
+                &dataset_handle.as_local_ref(),
+                cursor.last_offset,
+                Some(head.clone()),
+                max_records,
+            )
+            .await
+        {
+            Ok(df) => df,
+            Err(QueryError::DatasetSchemaNotAvailable(_)) => {
+                return Ok(Self::caught_up(cursor, head));
+            }
+            Err(err) => return Err(err),
+        };
+
+        let schema = df.schema().inner().clone();
+        let record_batches = df.collect().await?;
+
+        let num_records: u64 = record_batches
+            .iter()
+            .map(|b| u64::try_from(b.num_rows()).unwrap())
+            .sum();
+
+        let vocab: odf::metadata::DatasetVocabulary = resolved_dataset
+            .as_metadata_chain()
+            .accept_one(odf::dataset::SearchSetVocabVisitor::new())
+            .await
+            .int_err()?
+            .into_event()
+            .unwrap_or_default()
+            .into();
+
+        let last_offset =
+            Self::last_offset(&record_batches, &vocab.offset_column)?.or(cursor.last_offset);
+
+        let caught_up = num_records < max_records;
+
+        Ok(DatasetChangesChunk {
+            schema: Some(schema),
+            record_batches,
+            num_records,
+            cursor: ChangeFeedCursor {
+                last_offset,
+                caught_up_head: if caught_up {
+                    Some(head)
+                } else {
+                    cursor.caught_up_head.clone()
+                },
+            },
+            caught_up,
+        })
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
@@ -0,0 +1,288 @@
+// Copyright Kamu Data, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::sync::Arc;
+
+use datafusion::arrow::array::{Int64Array, UInt8Array};
+use datafusion::arrow::datatypes::DataType;
+use datafusion::functions_aggregate::expr_fn::count;
+use datafusion::prelude::*;
+use dill::*;
+use internal_error::{ErrorIntoInternal, InternalError, ResultIntoInternal};
+use kamu_core::*;
+
+use crate::new_session_context;
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+pub struct DatasetDiffServiceImpl {
+    object_store_registry: Arc<dyn ObjectStoreRegistry>,
+}
+
+#[component(pub)]
+#[interface(dyn DatasetDiffService)]
+impl DatasetDiffServiceImpl {
+    pub fn new(object_store_registry: Arc<dyn ObjectStoreRegistry>) -> Self {
+        Self {
+            object_store_registry,
+        }
+    }
+
+    async fn ensure_block_exists(
+        target: &ResolvedDataset,
+        hash: &odf::Multihash,
+    ) -> Result<(), DatasetDiffError> {
+        match target.as_metadata_chain().get_block(hash).await {
+            Ok(_) => Ok(()),
+            Err(odf::GetBlockError::NotFound(e)) => Err(e.into()),
+            Err(odf::GetBlockError::Access(e)) => Err(e.into()),
+            Err(e) => Err(e.int_err().into()),
+        }
+    }
+
+    async fn collect_metadata_changes(
+        target: &ResolvedDataset,
+        from: Option<&odf::Multihash>,
+        to: &odf::Multihash,
+    ) -> Result<Vec<odf::dataset::HashedMetadataBlock>, DatasetDiffError> {
+        let chain = target.as_metadata_chain();
+
+        let res = MetadataChainComparator::compare_chains(
+            chain,
+            to,
+            chain,
+            from,
+            &NullCompareChainsListener,
+        )
+        .await
+        .map_err(|e| match e {
+            CompareChainsError::Access(e) => DatasetDiffError::Access(e),
+            CompareChainsError::Corrupted(e) => DatasetDiffError::Internal(e.int_err()),
+            CompareChainsError::Internal(e) => DatasetDiffError::Internal(e),
+        })?;
+
+        match res {
+            CompareChainsResult::Equal => Ok(Vec::new()),
+            CompareChainsResult::LhsAhead { lhs_ahead_blocks } => Ok(lhs_ahead_blocks),
+            CompareChainsResult::LhsBehind { .. } | CompareChainsResult::Divergence { .. } => {
+                Err(InvalidDiffIntervalError {
+                    from: from.unwrap().clone(),
+                    to: to.clone(),
+                }
+                .into())
+            }
+        }
+    }
+
+    /// Finds the vocabulary and the snapshot primary key that were in effect at
+    /// the `to` block
+    async fn resolve_vocab_and_primary_key(
+        target: &ResolvedDataset,
+        to: &odf::Multihash,
+    ) -> Result<(odf::metadata::DatasetVocabulary, Vec<String>), InternalError> {
+        use odf::metadata::MetadataEventTypeFlags as Flag;
+
+        let mut set_vocab_visitor = odf::dataset::SearchSetVocabVisitor::new();
+        let mut merge_strategy_visitor = odf::dataset::GenericCallbackVisitor::new(
+            None::<odf::metadata::MergeStrategy>,
+            odf::dataset::MetadataVisitorDecision::NextOfType(
+                Flag::SET_POLLING_SOURCE | Flag::ADD_PUSH_SOURCE,
+            ),
+            |state, _, block| {
+                *state = match &block.event {
+                    odf::MetadataEvent::SetPollingSource(e) => Some(e.merge.clone()),
+                    odf::MetadataEvent::AddPushSource(e) => Some(e.merge.clone()),
+                    _ => unreachable!(),
+                };
+                odf::dataset::MetadataVisitorDecision::Stop
+            },
+        );
+
+        use odf::dataset::MetadataChainExt;
+        target
+            .as_metadata_chain()
+            .accept_by_hash(
+                &mut [&mut set_vocab_visitor, &mut merge_strategy_visitor],
+                to,
+            )
+            .await
+            .int_err()?;
+
+        let vocab = set_vocab_visitor.into_event().unwrap_or_default().into();
+
+        let primary_key = match merge_strategy_visitor.into_state() {
+            Some(odf::metadata::MergeStrategy::Snapshot(snapshot)) => snapshot.primary_key,
+            _ => Vec::new(),
+        };
+
+        Ok((vocab, primary_key))
+    }
+
+    async fn collect_data_changes(
+        &self,
+        target: &ResolvedDataset,
+        metadata_blocks: &[odf::dataset::HashedMetadataBlock],
+        to: &odf::Multihash,
+    ) -> Result<DatasetDataDiff, InternalError> {
+        use odf::metadata::IntoDataStreamBlock;
+
+        let mut offset_interval: Option<odf::metadata::OffsetInterval> = None;
+        let mut data_urls = Vec::new();
+
+        // Blocks go in reverse chronological order, but we want to read files in the
+        // order they were written
+        for (_, block) in metadata_blocks.iter().rev() {
+            let Some(data_block) = block.as_data_stream_block() else {
+                continue;
+            };
+            let Some(new_data) = data_block.event.new_data else {
+                continue;
+            };
+
+            offset_interval = Some(match offset_interval {
+                None => new_data.offset_interval.clone(),
+                Some(i) => odf::metadata::OffsetInterval {
+                    start: i.start,
+                    end: new_data.offset_interval.end,
+                },
+            });
+
+            data_urls.push(
+                target
+                    .as_data_repo()
+                    .get_internal_url(&new_data.physical_hash)
+                    .await
+                    .to_string(),
+            );
+        }
+
+        if data_urls.is_empty() {
+            return Ok(DatasetDataDiff::default());
+        }
+
+        let (vocab, primary_key) = Self::resolve_vocab_and_primary_key(target, to).await?;
+
+        let ctx = new_session_context(self.object_store_registry.clone());
+
+        let df = ctx
+            .read_parquet(
+                data_urls,
+                datafusion::execution::options::ParquetReadOptions {
+                    file_extension: "",
+                    ..Default::default()
+                },
+            )
+            .await
+            .int_err()?;
+
+        let mut num_appended = 0;
+        let mut num_retracted = 0;
+        let mut num_corrected = 0;
+
+        let op_counts = df
+            .clone()
+            .aggregate(
+                vec![cast(
+                    col(Column::from_name(&vocab.operation_type_column)),
+                    DataType::UInt8,
+                )
+                .alias("op")],
+                vec![count(lit(1)).alias("num_records")],
+            )
+            .int_err()?
+            .collect()
+            .await
+            .int_err()?;
+
+        for batch in op_counts {
+            let ops = batch
+                .column(0)
+                .as_any()
+                .downcast_ref::<UInt8Array>()
+                .unwrap();
+            let counts = batch
+                .column(1)
+                .as_any()
+                .downcast_ref::<Int64Array>()
+                .unwrap();
+
+            for i in 0..batch.num_rows() {
+                let num_records = u64::try_from(counts.value(i)).unwrap();
+                match odf::metadata::OperationType::try_from(ops.value(i)).int_err()? {
+                    odf::metadata::OperationType::Append => num_appended += num_records,
+                    odf::metadata::OperationType::Retract => num_retracted += num_records,
+                    // Corrections are represented by pairs of records, so we only count one half
+                    odf::metadata::OperationType::CorrectFrom => num_corrected += num_records,
+                    odf::metadata::OperationType::CorrectTo => {}
+                }
+            }
+        }
+
+        let sort_exprs = primary_key
+            .iter()
+            .chain(std::iter::once(&vocab.offset_column))
+            .map(|c| col(Column::from_name(c)).sort(true, false))
+            .collect();
+
+        let records = df.sort(sort_exprs).int_err()?;
+
+        Ok(DatasetDataDiff {
+            offset_interval,
+            num_appended,
+            num_retracted,
+            num_corrected,
+            primary_key,
+            records: Some(records),
+        })
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[async_trait::async_trait]
+impl DatasetDiffService for DatasetDiffServiceImpl {
+    #[tracing::instrument(level = "info", skip_all, fields(target=%target.get_handle(), ?from, ?to))]
+    async fn diff(
+        &self,
+        target: ResolvedDataset,
+        from: Option<&odf::Multihash>,
+        to: Option<&odf::Multihash>,
+    ) -> Result<DatasetDiff, DatasetDiffError> {
+        let to = match to {
+            Some(to) => {
+                Self::ensure_block_exists(&target, to).await?;
+                to.clone()
+            }
+            None => target
+                .as_metadata_chain()
+                .resolve_ref(&odf::BlockRef::Head)
+                .await
+                .int_err()?,
+        };
+
+        if let Some(from) = from {
+            Self::ensure_block_exists(&target, from).await?;
+        }
+
+        let metadata_blocks = Self::collect_metadata_changes(&target, from, &to).await?;
+
+        let data = self
+            .collect_data_changes(&target, &metadata_blocks, &to)
+            .await?;
+
+        Ok(DatasetDiff {
+            from_block: from.cloned(),
+            to_block: to,
+            metadata_blocks,
+            data,
+        })
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
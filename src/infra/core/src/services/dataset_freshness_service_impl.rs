@@ -0,0 +1,148 @@
+// Copyright Kamu Data, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::sync::Arc;
+
+use chrono::Duration;
+use dill::*;
+use internal_error::{ErrorIntoInternal, InternalError, ResultIntoInternal};
+use kamu_core::*;
+use odf::metadata::serde::yaml::Manifest;
+use time_source::SystemTimeSource;
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+const FRESHNESS_SLA_KEY: &str = "freshness-sla";
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+pub struct DatasetFreshnessServiceImpl {
+    metadata_query_service: Arc<dyn MetadataQueryService>,
+    time_source: Arc<dyn SystemTimeSource>,
+}
+
+#[component(pub)]
+#[interface(dyn DatasetFreshnessService)]
+impl DatasetFreshnessServiceImpl {
+    pub fn new(
+        metadata_query_service: Arc<dyn MetadataQueryService>,
+        time_source: Arc<dyn SystemTimeSource>,
+    ) -> Self {
+        Self {
+            metadata_query_service,
+            time_source,
+        }
+    }
+
+    fn validate(sla: &DatasetFreshnessSla) -> Result<(), InvalidFreshnessSlaError> {
+        let invalid = |reason: &str| InvalidFreshnessSlaError {
+            reason: reason.to_string(),
+        };
+
+        let is_whole_minutes = |d: Duration| {
+            d >= Duration::minutes(1) && (d - Duration::minutes(d.num_minutes())).is_zero()
+        };
+
+        if !is_whole_minutes(sla.max_update_interval) {
+            return Err(invalid(
+                "Update interval must be a whole number of minutes and at least one minute",
+            ));
+        }
+        if let Some(max_watermark_lag) = sla.max_watermark_lag
+            && !is_whole_minutes(max_watermark_lag)
+        {
+            return Err(invalid(
+                "Watermark lag must be a whole number of minutes and at least one minute",
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[async_trait::async_trait]
+impl DatasetFreshnessService for DatasetFreshnessServiceImpl {
+    #[tracing::instrument(level = "debug", skip_all, fields(target=%target.get_handle()))]
+    async fn get_sla(
+        &self,
+        target: &ResolvedDataset,
+    ) -> Result<Option<DatasetFreshnessSla>, InternalError> {
+        match target.as_info_repo().get(FRESHNESS_SLA_KEY).await {
+            Ok(bytes) => {
+                let manifest: Manifest<DatasetFreshnessSla> =
+                    serde_yaml::from_slice(&bytes[..]).int_err()?;
+                assert_eq!(manifest.kind, "DatasetFreshnessSla");
+                Ok(Some(manifest.content))
+            }
+            Err(odf::storage::GetNamedError::Internal(e)) => Err(e),
+            Err(odf::storage::GetNamedError::Access(e)) => Err(e.int_err()),
+            Err(odf::storage::GetNamedError::NotFound(_)) => Ok(None),
+        }
+    }
+
+    #[tracing::instrument(level = "info", skip_all, fields(target=%target.get_handle(), ?sla))]
+    async fn set_sla(
+        &self,
+        target: &ResolvedDataset,
+        sla: Option<DatasetFreshnessSla>,
+    ) -> Result<(), SetFreshnessSlaError> {
+        let Some(sla) = sla else {
+            target
+                .as_info_repo()
+                .delete(FRESHNESS_SLA_KEY)
+                .await
+                .int_err()?;
+            return Ok(());
+        };
+
+        Self::validate(&sla)?;
+
+        let manifest = Manifest {
+            kind: "DatasetFreshnessSla".to_owned(),
+            version: 1,
+            content: sla,
+        };
+        let manifest_yaml = serde_yaml::to_string(&manifest).int_err()?;
+        target
+            .as_info_repo()
+            .set(FRESHNESS_SLA_KEY, manifest_yaml.as_bytes())
+            .await
+            .int_err()?;
+
+        Ok(())
+    }
+
+    #[tracing::instrument(level = "debug", skip_all, fields(target=%target.get_handle()))]
+    async fn get_freshness(
+        &self,
+        target: &ResolvedDataset,
+    ) -> Result<DatasetFreshness, InternalError> {
+        let sla = self.get_sla(target).await?;
+
+        let last_updated_at = self
+            .metadata_query_service
+            .try_get_last_data_update_time(target.clone())
+            .await?;
+        let watermark = self
+            .metadata_query_service
+            .try_get_last_data_watermark(target.clone())
+            .await?;
+
+        Ok(DatasetFreshness::evaluate(
+            sla,
+            last_updated_at,
+            watermark,
+            self.time_source.now(),
+        ))
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
@@ -0,0 +1,303 @@
+// Copyright Kamu Data, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use datafusion::arrow::array::RecordBatch;
+use datafusion::arrow::datatypes::DataType;
+use datafusion::common::Column;
+use datafusion::functions::expr_fn::regexp_like;
+use datafusion::prelude::*;
+use internal_error::{InternalError, ResultIntoInternal};
+use kamu_core::*;
+use thiserror::Error;
+
+use super::DatasetExpectationsServiceImpl;
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+/// Evaluates expectations declared on a dataset against the new data before
+/// it gets committed
+pub(crate) struct DataExpectationsChecker;
+
+impl DataExpectationsChecker {
+    /// Checks the new data against the dataset expectations.
+    ///
+    /// When `allow_quarantine` is set the records violating row-level
+    /// expectations with [`DataExpectationAction::Quarantine`] action are
+    /// excluded from the returned data frame, otherwise such expectations
+    /// behave as [`DataExpectationAction::Fail`].
+    #[tracing::instrument(level = "info", skip_all, fields(target=%target.get_handle()))]
+    pub async fn check(
+        target: &ResolvedDataset,
+        data: Option<DataFrame>,
+        allow_quarantine: bool,
+    ) -> Result<DataExpectationsCheck, CheckDataExpectationsError> {
+        let Some(df) = data else {
+            return Ok(DataExpectationsCheck::unchecked(None));
+        };
+
+        let expectations = DatasetExpectationsServiceImpl::read_expectations(target.as_ref())
+            .await?
+            .expectations;
+
+        if expectations.is_empty() {
+            return Ok(DataExpectationsCheck::unchecked(Some(df)));
+        }
+
+        let num_records = u64::try_from(df.clone().count().await.int_err()?).unwrap();
+
+        let mut results = Vec::new();
+        let mut quarantine_predicates = Vec::new();
+
+        for expectation in expectations {
+            let action = match expectation.on_failure {
+                DataExpectationAction::Quarantine
+                    if !allow_quarantine || !expectation.rule.is_row_level() =>
+                {
+                    DataExpectationAction::Fail
+                }
+                action => action,
+            };
+
+            let num_violations = match &expectation.rule {
+                DataExpectationRule::RowCount { min, max } => {
+                    if min.is_some_and(|min| num_records < min)
+                        || max.is_some_and(|max| num_records > max)
+                    {
+                        num_records
+                    } else {
+                        0
+                    }
+                }
+                DataExpectationRule::Unique { columns } => {
+                    let columns: Vec<_> = columns.iter().map(String::as_str).collect();
+                    let num_distinct = df
+                        .clone()
+                        .select_columns(&columns)
+                        .int_err()?
+                        .distinct()
+                        .int_err()?
+                        .count()
+                        .await
+                        .int_err()?;
+                    num_records - u64::try_from(num_distinct).unwrap()
+                }
+                rule => {
+                    let predicate = Self::violation_predicate(rule);
+                    let num_violations = df
+                        .clone()
+                        .filter(predicate.clone())
+                        .int_err()?
+                        .count()
+                        .await
+                        .int_err()?;
+                    let num_violations = u64::try_from(num_violations).unwrap();
+
+                    if action == DataExpectationAction::Quarantine && num_violations != 0 {
+                        quarantine_predicates.push(predicate);
+                    }
+
+                    num_violations
+                }
+            };
+
+            tracing::debug!(
+                name = %expectation.name,
+                ?action,
+                num_violations,
+                "Evaluated expectation"
+            );
+
+            results.push(DataExpectationResult {
+                name: expectation.name,
+                action,
+                passed: num_violations == 0,
+                num_violations,
+            });
+        }
+
+        let failed: Vec<_> = results
+            .iter()
+            .filter(|r| !r.passed && r.action == DataExpectationAction::Fail)
+            .cloned()
+            .collect();
+
+        if !failed.is_empty() {
+            return Err(DataExpectationsFailedError { failed }.into());
+        }
+
+        for res in results.iter().filter(|r| !r.passed) {
+            tracing::warn!(
+                name = %res.name,
+                action = ?res.action,
+                num_violations = res.num_violations,
+                "Data expectation not met",
+            );
+        }
+
+        let (df, quarantined) = match quarantine_predicates.into_iter().reduce(Expr::or) {
+            None => (df, Vec::new()),
+            Some(predicate) => {
+                let quarantined = df
+                    .clone()
+                    .filter(predicate.clone())
+                    .int_err()?
+                    .collect()
+                    .await
+                    .int_err()?;
+                (df.filter(not(predicate)).int_err()?, quarantined)
+            }
+        };
+
+        let num_quarantined = quarantined.iter().map(|b| b.num_rows() as u64).sum();
+
+        Ok(DataExpectationsCheck {
+            data: Some(df),
+            report: Some(DataExpectationsReport {
+                results,
+                num_quarantined,
+                quarantined_data: None,
+            }),
+            quarantined,
+        })
+    }
+
+    /// Returns a predicate that is `true` only for records violating the
+    /// row-level rule
+    fn violation_predicate(rule: &DataExpectationRule) -> Expr {
+        match rule {
+            DataExpectationRule::NotNull { column } => col(Column::from_name(column)).is_null(),
+            DataExpectationRule::ValueRange { column, min, max } => {
+                let value = cast(col(Column::from_name(column)), DataType::Float64);
+                let below = min.map(|min| value.clone().lt(lit(min)));
+                let above = max.map(|max| value.gt(lit(max)));
+                below
+                    .into_iter()
+                    .chain(above)
+                    .reduce(Expr::or)
+                    .unwrap_or(lit(false))
+                    .is_true()
+            }
+            DataExpectationRule::RegexMatch { column, pattern } => not(regexp_like(
+                cast(col(Column::from_name(column)), DataType::Utf8),
+                lit(pattern.clone()),
+                None,
+            ))
+            .is_true(),
+            DataExpectationRule::Unique { .. } | DataExpectationRule::RowCount { .. } => {
+                unreachable!()
+            }
+        }
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+pub(crate) struct DataExpectationsCheck {
+    /// New data with quarantined records excluded
+    pub data: Option<DataFrame>,
+    report: Option<DataExpectationsReport>,
+    quarantined: Vec<RecordBatch>,
+}
+
+impl DataExpectationsCheck {
+    fn unchecked(data: Option<DataFrame>) -> Self {
+        Self {
+            data,
+            report: None,
+            quarantined: Vec::new(),
+        }
+    }
+
+    /// Stores the quarantined records in the data repository and prepares the
+    /// event recording the report of the data committed in the specified
+    /// block. The event has to be committed right after that block. Returns
+    /// `None` when no expectations were evaluated.
+    pub async fn into_report_event(
+        self,
+        target: &ResolvedDataset,
+        block_hash: &odf::Multihash,
+    ) -> Result<Option<odf::MetadataEvent>, InternalError> {
+        let Some(report) = self.report else {
+            return Ok(None);
+        };
+
+        let quarantined_parquet = if report.num_quarantined == 0 {
+            None
+        } else {
+            let mut writer = datafusion::parquet::arrow::ArrowWriter::try_new(
+                Vec::new(),
+                self.quarantined[0].schema(),
+                None,
+            )
+            .int_err()?;
+            for batch in &self.quarantined {
+                writer.write(batch).int_err()?;
+            }
+            Some(writer.into_inner().int_err()?)
+        };
+
+        DatasetExpectationsServiceImpl::report_event(
+            target.as_ref(),
+            block_hash,
+            report,
+            quarantined_parquet,
+        )
+        .await
+        .map(Some)
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[derive(Debug, Error)]
+pub(crate) enum CheckDataExpectationsError {
+    #[error(transparent)]
+    Failed(#[from] DataExpectationsFailedError),
+
+    #[error(transparent)]
+    Internal(#[from] InternalError),
+}
+
+impl From<CheckDataExpectationsError> for PushIngestError {
+    fn from(value: CheckDataExpectationsError) -> Self {
+        match value {
+            CheckDataExpectationsError::Failed(e) => Self::ExpectationsFailed(e),
+            CheckDataExpectationsError::Internal(e) => Self::Internal(e),
+        }
+    }
+}
+
+impl From<CheckDataExpectationsError> for PollingIngestError {
+    fn from(value: CheckDataExpectationsError) -> Self {
+        match value {
+            CheckDataExpectationsError::Failed(e) => Self::ExpectationsFailed(e),
+            CheckDataExpectationsError::Internal(e) => Self::Internal(e),
+        }
+    }
+}
+
+impl From<CheckDataExpectationsError> for TransformExecuteError {
+    fn from(value: CheckDataExpectationsError) -> Self {
+        match value {
+            CheckDataExpectationsError::Failed(e) => Self::ExpectationsFailed(e),
+            CheckDataExpectationsError::Internal(e) => Self::Internal(e),
+        }
+    }
+}
+
+impl From<CheckDataExpectationsError> for ReprocessExecutionError {
+    fn from(value: CheckDataExpectationsError) -> Self {
+        match value {
+            CheckDataExpectationsError::Failed(e) => Self::ExpectationsFailed(e),
+            CheckDataExpectationsError::Internal(e) => Self::Internal(e),
+        }
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
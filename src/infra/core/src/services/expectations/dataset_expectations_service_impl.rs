@@ -0,0 +1,289 @@
+// Copyright Kamu Data, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::collections::HashSet;
+
+use dill::*;
+use futures::TryStreamExt;
+use internal_error::{InternalError, ResultIntoInternal};
+use kamu_core::*;
+use odf::dataset::MetadataChainExt as _;
+use odf::metadata::serde::yaml::Manifest;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+const EXPECTATIONS_ATTACHMENT_PATH: &str = "kamu/expectations.yaml";
+const REPORT_ATTACHMENT_PATH: &str = "kamu/expectations-report.yaml";
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[component(pub)]
+#[interface(dyn DatasetExpectationsService)]
+pub struct DatasetExpectationsServiceImpl {}
+
+impl DatasetExpectationsServiceImpl {
+    async fn read_attachments(
+        dataset: &dyn odf::Dataset,
+    ) -> Result<Vec<odf::metadata::AttachmentEmbedded>, InternalError> {
+        Ok(dataset
+            .as_metadata_chain()
+            .accept_one(odf::dataset::SearchSetAttachmentsVisitor::new())
+            .await
+            .int_err()?
+            .into_event()
+            .map(|e| {
+                let odf::metadata::Attachments::Embedded(at) = e.attachments;
+                at.items
+            })
+            .unwrap_or_default())
+    }
+
+    fn find_attachment<T: DeserializeOwned>(
+        items: &[odf::metadata::AttachmentEmbedded],
+        path: &str,
+        kind: &str,
+    ) -> Result<Option<T>, InternalError> {
+        let Some(item) = items.iter().find(|i| i.path == path) else {
+            return Ok(None);
+        };
+
+        let manifest: Manifest<T> = serde_yaml::from_str(&item.content).int_err()?;
+        if manifest.kind != kind {
+            return InternalError::bail(format!(
+                "Attachment {path} contains {} instead of {kind}",
+                manifest.kind
+            ));
+        }
+        Ok(Some(manifest.content))
+    }
+
+    /// Returns the event that replaces (or removes when `content` is `None`)
+    /// the attachment at the specified path while preserving the others
+    fn set_attachment_event<T: Serialize>(
+        mut items: Vec<odf::metadata::AttachmentEmbedded>,
+        path: &str,
+        kind: &str,
+        content: Option<T>,
+    ) -> Result<odf::MetadataEvent, InternalError> {
+        items.retain(|i| i.path != path);
+
+        if let Some(content) = content {
+            let manifest = Manifest {
+                kind: kind.to_owned(),
+                version: 1,
+                content,
+            };
+            items.push(odf::metadata::AttachmentEmbedded {
+                path: path.to_owned(),
+                content: serde_yaml::to_string(&manifest).int_err()?,
+            });
+        }
+
+        Ok(odf::metadata::SetAttachments {
+            attachments: odf::metadata::AttachmentsEmbedded { items }.into(),
+        }
+        .into())
+    }
+
+    pub(crate) async fn read_expectations(
+        dataset: &dyn odf::Dataset,
+    ) -> Result<DatasetExpectations, InternalError> {
+        let attachments = Self::read_attachments(dataset).await?;
+
+        Ok(Self::find_attachment(
+            &attachments,
+            EXPECTATIONS_ATTACHMENT_PATH,
+            "DatasetExpectations",
+        )?
+        .unwrap_or_default())
+    }
+
+    /// Stores the quarantined records in the data repository and prepares the
+    /// event recording the report of the data committed in the specified
+    /// block. The event has to be committed right after that block.
+    pub(crate) async fn report_event(
+        dataset: &dyn odf::Dataset,
+        block_hash: &odf::Multihash,
+        mut report: DataExpectationsReport,
+        quarantined_parquet: Option<Vec<u8>>,
+    ) -> Result<odf::MetadataEvent, InternalError> {
+        if let Some(quarantined_parquet) = quarantined_parquet {
+            let insert_result = dataset
+                .as_data_repo()
+                .insert_bytes(&quarantined_parquet, odf::storage::InsertOpts::default())
+                .await
+                .int_err()?;
+
+            report.quarantined_data = Some(insert_result.hash);
+        }
+
+        Self::set_attachment_event(
+            Self::read_attachments(dataset).await?,
+            REPORT_ATTACHMENT_PATH,
+            "DataExpectationsReport",
+            Some(DataExpectationsReportAttachment {
+                block_hash: block_hash.clone(),
+                report,
+            }),
+        )
+    }
+
+    fn validate(expectations: &[DataExpectation]) -> Result<(), InvalidDataExpectationError> {
+        let mut names = HashSet::new();
+
+        for expectation in expectations {
+            let invalid = |reason: &str| InvalidDataExpectationError {
+                name: expectation.name.clone(),
+                reason: reason.to_string(),
+            };
+
+            if expectation.name.is_empty() {
+                return Err(invalid("Name cannot be empty"));
+            }
+            if !names.insert(expectation.name.as_str()) {
+                return Err(invalid("Name is not unique"));
+            }
+
+            match &expectation.rule {
+                DataExpectationRule::NotNull { column } => {
+                    if column.is_empty() {
+                        return Err(invalid("Column cannot be empty"));
+                    }
+                }
+                DataExpectationRule::Unique { columns } => {
+                    if columns.is_empty() || columns.iter().any(String::is_empty) {
+                        return Err(invalid("At least one non-empty column is required"));
+                    }
+                }
+                DataExpectationRule::ValueRange { column, min, max } => {
+                    if column.is_empty() {
+                        return Err(invalid("Column cannot be empty"));
+                    }
+                    match (min, max) {
+                        (None, None) => return Err(invalid("Either min or max must be specified")),
+                        (Some(min), Some(max)) if min > max => {
+                            return Err(invalid("Min cannot be greater than max"))
+                        }
+                        _ => {}
+                    }
+                }
+                DataExpectationRule::RegexMatch { column, pattern } => {
+                    if column.is_empty() {
+                        return Err(invalid("Column cannot be empty"));
+                    }
+                    if let Err(e) = regex::Regex::new(pattern) {
+                        return Err(invalid(&format!("Invalid pattern: {e}")));
+                    }
+                }
+                DataExpectationRule::RowCount { min, max } => match (min, max) {
+                    (None, None) => return Err(invalid("Either min or max must be specified")),
+                    (Some(min), Some(max)) if min > max => {
+                        return Err(invalid("Min cannot be greater than max"))
+                    }
+                    _ => {}
+                },
+            }
+        }
+
+        Ok(())
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[async_trait::async_trait]
+impl DatasetExpectationsService for DatasetExpectationsServiceImpl {
+    #[tracing::instrument(level = "debug", skip_all, fields(target=%target.get_handle()))]
+    async fn get_expectations(
+        &self,
+        target: &ResolvedDataset,
+    ) -> Result<Vec<DataExpectation>, InternalError> {
+        Ok(Self::read_expectations(target.as_ref()).await?.expectations)
+    }
+
+    #[tracing::instrument(level = "info", skip_all, fields(target=%target.get_handle()))]
+    async fn prepare_set_expectations(
+        &self,
+        target: &ResolvedDataset,
+        expectations: Vec<DataExpectation>,
+    ) -> Result<Option<odf::MetadataEvent>, SetDataExpectationsError> {
+        Self::validate(&expectations)?;
+
+        let attachments = Self::read_attachments(target.as_ref()).await?;
+
+        let old_expectations = Self::find_attachment::<DatasetExpectations>(
+            &attachments,
+            EXPECTATIONS_ATTACHMENT_PATH,
+            "DatasetExpectations",
+        )?;
+
+        let new_expectations =
+            (!expectations.is_empty()).then_some(DatasetExpectations { expectations });
+
+        if old_expectations == new_expectations {
+            return Ok(None);
+        }
+
+        Ok(Some(Self::set_attachment_event(
+            attachments,
+            EXPECTATIONS_ATTACHMENT_PATH,
+            "DatasetExpectations",
+            new_expectations,
+        )?))
+    }
+
+    #[tracing::instrument(level = "debug", skip_all, fields(target=%target.get_handle(), %block_hash))]
+    async fn get_report(
+        &self,
+        target: &ResolvedDataset,
+        block_hash: &odf::Multihash,
+    ) -> Result<Option<DataExpectationsReport>, InternalError> {
+        // The report is recorded by the block that immediately follows the
+        // checked one
+        let mut blocks = target.as_metadata_chain().iter_blocks();
+
+        while let Some((hash, block)) = blocks.try_next().await.int_err()? {
+            if hash == *block_hash {
+                break;
+            }
+            if block.prev_block_hash.as_ref() != Some(block_hash) {
+                continue;
+            }
+
+            let odf::MetadataEvent::SetAttachments(e) = block.event else {
+                break;
+            };
+            let odf::metadata::Attachments::Embedded(at) = e.attachments;
+
+            return Ok(Self::find_attachment::<DataExpectationsReportAttachment>(
+                &at.items,
+                REPORT_ATTACHMENT_PATH,
+                "DataExpectationsReport",
+            )?
+            .filter(|a| a.block_hash == *block_hash)
+            .map(|a| a.report));
+        }
+
+        Ok(None)
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+/// Report as recorded in the attachments, bound to the block it describes
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+struct DataExpectationsReportAttachment {
+    block_hash: odf::Multihash,
+    report: DataExpectationsReport,
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
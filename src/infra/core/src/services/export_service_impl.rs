@@ -8,6 +8,7 @@
 // by the Apache License, Version 2.0.
 
 use std::path::Path;
+use std::sync::Arc;
 
 use datafusion::arrow::array::{AsArray, RecordBatch};
 use datafusion::arrow::datatypes::UInt64Type;
@@ -15,17 +16,30 @@ use datafusion::dataframe::{DataFrame, DataFrameWriteOptions};
 use datafusion::logical_expr::Partitioning;
 use dill::{component, interface};
 use internal_error::{ErrorIntoInternal, ResultIntoInternal};
-use kamu_core::{ExportError, ExportFormat, ExportOptions, ExportService};
+use kamu_core::{
+    ExportError,
+    ExportFormat,
+    ExportOptions,
+    ExportService,
+    IcebergExportResult,
+    ResolvedDataset,
+};
+use time_source::SystemTimeSource;
+use url::Url;
+
+use super::iceberg::IcebergExporter;
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
 
-pub struct ExportServiceImpl {}
+pub struct ExportServiceImpl {
+    time_source: Arc<dyn SystemTimeSource>,
+}
 
 #[component(pub)]
 #[interface(dyn ExportService)]
 impl ExportServiceImpl {
-    pub fn new() -> Self {
-        Self {}
+    pub fn new(time_source: Arc<dyn SystemTimeSource>) -> Self {
+        Self { time_source }
     }
 
     fn records_written(&self, batches: &Vec<RecordBatch>) -> Result<u64, ExportError> {
@@ -100,8 +114,25 @@ impl ExportService for ExportServiceImpl {
                     .write_json(path_str, DataFrameWriteOptions::new(), None)
                     .await
             }
+            ExportFormat::Iceberg => {
+                return Err(ExportError::Internal(
+                    "Iceberg tables are exported from datasets, not queries".int_err(),
+                ));
+            }
         }?;
 
         self.records_written(&result)
     }
+
+    #[tracing::instrument(level = "info", skip_all, fields(dataset = %target.get_handle(), %table_url))]
+    async fn export_to_iceberg(
+        &self,
+        target: ResolvedDataset,
+        table_url: &Url,
+    ) -> Result<IcebergExportResult, ExportError> {
+        IcebergExporter::open(target, table_url, self.time_source.now())
+            .await?
+            .export()
+            .await
+    }
 }
@@ -0,0 +1,382 @@
+// Copyright Kamu Data, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, Utc};
+use internal_error::{ErrorIntoInternal, InternalError, ResultIntoInternal};
+use kamu_core::*;
+use url::Url;
+
+use super::*;
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+/// Appends blocks of a dataset that were not exported yet to an Iceberg table,
+/// creating the table on the first export. All new snapshots are committed
+/// with a single metadata version
+pub(crate) struct IcebergExporter {
+    target: ResolvedDataset,
+    storage: IcebergTableStorage,
+    now: DateTime<Utc>,
+}
+
+impl IcebergExporter {
+    pub async fn open(
+        target: ResolvedDataset,
+        table_url: &Url,
+        now: DateTime<Utc>,
+    ) -> Result<Self, InternalError> {
+        let storage = IcebergTableStorage::open(table_url).await?;
+        Ok(Self {
+            target,
+            storage,
+            now,
+        })
+    }
+
+    pub async fn export(self) -> Result<IcebergExportResult, ExportError> {
+        let current = self.storage.read_current_metadata().await?;
+
+        let old_head = match &current {
+            Some((_, metadata)) => {
+                self.check_table_dataset(metadata)?;
+                Self::last_exported_block(metadata)?
+            }
+            None => None,
+        };
+
+        let blocks = self.collect_new_blocks(old_head.as_ref()).await?;
+
+        let mut export = IcebergExport {
+            metadata: current.as_ref().map(|(_, m)| m.clone()),
+            manifests: match &current {
+                Some((_, metadata)) => self.read_current_manifests(metadata).await?,
+                None => Vec::new(),
+            },
+            pending_schema: None,
+            new_head: old_head.clone(),
+            num_snapshots: 0,
+            num_records: 0,
+        };
+
+        for (block_hash, block) in blocks {
+            let new_data = match block.event {
+                odf::MetadataEvent::SetDataSchema(e) => {
+                    export.pending_schema = Some(e.schema_as_arrow().int_err()?);
+                    continue;
+                }
+                odf::MetadataEvent::AddData(e) => e.new_data,
+                odf::MetadataEvent::ExecuteTransform(e) => e.new_data,
+                _ => continue,
+            };
+
+            if let Some(new_data) = new_data {
+                self.append_snapshot(&mut export, &block_hash, block.system_time, &new_data)
+                    .await?;
+            }
+        }
+
+        if export.num_snapshots != 0 {
+            let mut metadata = export.metadata.unwrap();
+            metadata.last_updated_ms = self.now.timestamp_millis();
+
+            let version = match &current {
+                Some((version, prev_metadata)) => {
+                    metadata.metadata_log.push(MetadataLogEntry {
+                        timestamp_ms: prev_metadata.last_updated_ms,
+                        metadata_file: self.storage.metadata_file_location(
+                            &IcebergTableStorage::metadata_file_name(*version),
+                        ),
+                    });
+                    version + 1
+                }
+                None => 1,
+            };
+
+            self.storage.write_metadata(version, &metadata).await?;
+
+            tracing::info!(
+                location = self.storage.location(),
+                version,
+                num_snapshots = export.num_snapshots,
+                num_records = export.num_records,
+                "Committed Iceberg table metadata",
+            );
+        }
+
+        Ok(IcebergExportResult {
+            old_head,
+            new_head: export.new_head,
+            num_snapshots: export.num_snapshots,
+            num_records: export.num_records,
+        })
+    }
+
+    fn check_table_dataset(&self, metadata: &TableMetadata) -> Result<(), ExportError> {
+        let dataset_id = self.target.get_id();
+
+        match metadata.properties.get(PROPERTY_DATASET_ID) {
+            Some(table_dataset_id) if *table_dataset_id == dataset_id.to_string() => Ok(()),
+            table_dataset_id => Err(ExportError::IcebergTableDatasetMismatch(
+                IcebergTableDatasetMismatchError {
+                    dataset_id: dataset_id.clone(),
+                    table_dataset_id: table_dataset_id.cloned().unwrap_or_default(),
+                },
+            )),
+        }
+    }
+
+    fn last_exported_block(
+        metadata: &TableMetadata,
+    ) -> Result<Option<odf::Multihash>, InternalError> {
+        metadata
+            .current_snapshot()
+            .and_then(|snapshot| snapshot.summary.get(SUMMARY_BLOCK_HASH))
+            .map(|hash| odf::Multihash::from_multibase(hash).int_err())
+            .transpose()
+    }
+
+    /// Returns blocks following the last exported one, oldest first
+    async fn collect_new_blocks(
+        &self,
+        last_exported_block: Option<&odf::Multihash>,
+    ) -> Result<Vec<(odf::Multihash, odf::MetadataBlock)>, ExportError> {
+        use futures::TryStreamExt;
+        use odf::dataset::MetadataChainExt;
+
+        let chain = self.target.as_metadata_chain();
+        let head = chain.resolve_ref(&odf::BlockRef::Head).await.int_err()?;
+
+        let mut blocks: Vec<_> = chain
+            .iter_blocks_interval(&head, last_exported_block, false)
+            .try_collect()
+            .await
+            .map_err(|e| match e {
+                odf::IterBlocksError::InvalidInterval(_) => {
+                    ExportError::IcebergTableOutOfSync(IcebergTableOutOfSyncError {
+                        dataset_id: self.target.get_id().clone(),
+                        last_exported_block: last_exported_block.unwrap().clone(),
+                    })
+                }
+                e => ExportError::Internal(e.int_err()),
+            })?;
+
+        blocks.reverse();
+        Ok(blocks)
+    }
+
+    async fn read_current_manifests(
+        &self,
+        metadata: &TableMetadata,
+    ) -> Result<Vec<ManifestFile>, InternalError> {
+        let Some(snapshot) = metadata.current_snapshot() else {
+            return Ok(Vec::new());
+        };
+
+        let manifest_list = self.storage.read_file(&snapshot.manifest_list).await?;
+        read_manifest_list(&manifest_list)
+    }
+
+    async fn append_snapshot(
+        &self,
+        export: &mut IcebergExport,
+        block_hash: &odf::Multihash,
+        system_time: DateTime<Utc>,
+        new_data: &odf::DataSlice,
+    ) -> Result<(), ExportError> {
+        self.apply_pending_schema(export)?;
+        let metadata = export.metadata.as_mut().unwrap();
+
+        let data_file = self.place_data_file(new_data).await?;
+        let data_files = std::slice::from_ref(&data_file);
+
+        let snapshot_id = Self::snapshot_id(block_hash);
+        let sequence_number = metadata.last_sequence_number + 1;
+        let parent_snapshot = metadata.current_snapshot().cloned();
+        let parent_total = |key: &str| parent_snapshot.as_ref().map_or(0, |s| s.summary_i64(key));
+
+        // Manifest with the single data file of the block
+        let manifest = write_manifest(metadata.current_schema(), snapshot_id, data_files)?;
+        let manifest_name = format!("{}-m0.avro", uuid::Uuid::new_v4());
+        let manifest_length = i64::try_from(manifest.len()).unwrap();
+        self.storage
+            .write_metadata_file(&manifest_name, manifest)
+            .await?;
+
+        export.manifests.push(ManifestFile::new_data(
+            self.storage.metadata_file_location(&manifest_name),
+            manifest_length,
+            snapshot_id,
+            sequence_number,
+            data_files,
+        ));
+
+        // Manifest list carries manifests of all previous snapshots
+        let manifest_list = write_manifest_list(
+            snapshot_id,
+            parent_snapshot.as_ref().map(|s| s.snapshot_id),
+            sequence_number,
+            &export.manifests,
+        )?;
+        let manifest_list_name = format!("snap-{snapshot_id}-1-{}.avro", uuid::Uuid::new_v4());
+        self.storage
+            .write_metadata_file(&manifest_list_name, manifest_list)
+            .await?;
+
+        let summary = BTreeMap::from([
+            ("operation".to_string(), "append".to_string()),
+            ("added-data-files".to_string(), "1".to_string()),
+            (
+                "added-records".to_string(),
+                data_file.record_count.to_string(),
+            ),
+            (
+                "added-files-size".to_string(),
+                data_file.file_size_in_bytes.to_string(),
+            ),
+            (
+                "total-data-files".to_string(),
+                (parent_total("total-data-files") + 1).to_string(),
+            ),
+            (
+                "total-records".to_string(),
+                (parent_total("total-records") + data_file.record_count).to_string(),
+            ),
+            (
+                "total-files-size".to_string(),
+                (parent_total("total-files-size") + data_file.file_size_in_bytes).to_string(),
+            ),
+            (
+                SUMMARY_BLOCK_HASH.to_string(),
+                block_hash.as_multibase().to_string(),
+            ),
+            (
+                SUMMARY_OFFSET_START.to_string(),
+                new_data.offset_interval.start.to_string(),
+            ),
+            (
+                SUMMARY_OFFSET_END.to_string(),
+                new_data.offset_interval.end.to_string(),
+            ),
+        ]);
+
+        metadata.add_snapshot(Snapshot {
+            snapshot_id,
+            parent_snapshot_id: parent_snapshot.map(|s| s.snapshot_id),
+            sequence_number,
+            // Time travel by timestamp should match the system time of the dataset
+            timestamp_ms: system_time.timestamp_millis(),
+            manifest_list: self.storage.metadata_file_location(&manifest_list_name),
+            summary,
+            schema_id: Some(metadata.current_schema_id),
+        });
+
+        export.new_head = Some(block_hash.clone());
+        export.num_snapshots += 1;
+        export.num_records += new_data.num_records();
+
+        Ok(())
+    }
+
+    /// Creates the table or evolves its schema when the dataset schema was set
+    /// since the last snapshot
+    fn apply_pending_schema(&self, export: &mut IcebergExport) -> Result<(), ExportError> {
+        let Some(arrow_schema) = export.pending_schema.take() else {
+            if export.metadata.is_none() {
+                return Err("Dataset has data blocks preceding its schema"
+                    .int_err()
+                    .into());
+            }
+            return Ok(());
+        };
+
+        let (existing_schemas, last_column_id) = match &export.metadata {
+            Some(metadata) => (metadata.schemas.as_slice(), metadata.last_column_id),
+            None => (&[][..], 0),
+        };
+
+        let mut converter = IcebergSchemaConverter::new(existing_schemas, last_column_id);
+        let fields = converter
+            .convert(&arrow_schema)
+            .map_err(ExportError::UnsupportedDataType)?;
+
+        let metadata = if let Some(metadata) = export.metadata.as_mut() {
+            metadata.set_current_schema(fields);
+            metadata
+        } else {
+            export.metadata.insert(TableMetadata::new(
+                self.storage.location().to_string(),
+                IcebergSchema::new(0, fields),
+                0,
+                BTreeMap::from([(
+                    PROPERTY_DATASET_ID.to_string(),
+                    self.target.get_id().to_string(),
+                )]),
+                self.now.timestamp_millis(),
+            ))
+        };
+
+        metadata.last_column_id = converter.last_column_id();
+        metadata.properties.insert(
+            PROPERTY_NAME_MAPPING.to_string(),
+            build_name_mapping(&metadata.schemas),
+        );
+
+        Ok(())
+    }
+
+    /// Copies the data file of a block into the table. Files of the dataset are
+    /// never referenced in place, as compactions and resets of the dataset
+    /// would remove them from under the table
+    async fn place_data_file(
+        &self,
+        new_data: &odf::DataSlice,
+    ) -> Result<IcebergDataFile, InternalError> {
+        let physical_hash = &new_data.physical_hash;
+        let file_name = format!("{}.parquet", physical_hash.as_multibase());
+
+        let data = self
+            .target
+            .as_data_repo()
+            .get_stream(physical_hash)
+            .await
+            .int_err()?;
+        self.storage.copy_data_file(&file_name, data).await?;
+        let file_path = self.storage.data_file_location(&file_name);
+
+        Ok(IcebergDataFile {
+            file_path,
+            record_count: i64::try_from(new_data.num_records()).unwrap(),
+            file_size_in_bytes: i64::try_from(new_data.size).unwrap(),
+        })
+    }
+
+    /// Snapshot IDs are derived from block hashes, so that re-exporting the
+    /// dataset into a new table produces the same IDs
+    fn snapshot_id(block_hash: &odf::Multihash) -> i64 {
+        let digest = block_hash.digest();
+        let mut bytes = [0u8; 8];
+        bytes.copy_from_slice(&digest[..8]);
+        i64::from_be_bytes(bytes) & i64::MAX
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+struct IcebergExport {
+    metadata: Option<TableMetadata>,
+    manifests: Vec<ManifestFile>,
+    pending_schema: Option<datafusion::arrow::datatypes::SchemaRef>,
+    new_head: Option<odf::Multihash>,
+    num_snapshots: usize,
+    num_records: u64,
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
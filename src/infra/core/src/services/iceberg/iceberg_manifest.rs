@@ -0,0 +1,254 @@
+// Copyright Kamu Data, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::sync::LazyLock;
+
+use apache_avro::{Reader, Schema as AvroSchema, Writer};
+use internal_error::{InternalError, ResultIntoInternal};
+use serde::{Deserialize, Serialize};
+
+use super::{IcebergSchema, FORMAT_VERSION};
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+// Avro schemas of manifest and manifest list files as defined by the Iceberg
+// spec (format version 2). Readers resolve fields by their `field-id`
+// attributes, so only the required fields are written
+
+static MANIFEST_ENTRY_SCHEMA: LazyLock<AvroSchema> = LazyLock::new(|| {
+    AvroSchema::parse_str(
+        r#"{
+            "type": "record",
+            "name": "manifest_entry",
+            "fields": [
+                {"name": "status", "type": "int", "field-id": 0},
+                {"name": "snapshot_id", "type": ["null", "long"], "default": null, "field-id": 1},
+                {"name": "sequence_number", "type": ["null", "long"], "default": null, "field-id": 3},
+                {"name": "file_sequence_number", "type": ["null", "long"], "default": null, "field-id": 4},
+                {"name": "data_file", "field-id": 2, "type": {
+                    "type": "record",
+                    "name": "r2",
+                    "fields": [
+                        {"name": "content", "type": "int", "field-id": 134},
+                        {"name": "file_path", "type": "string", "field-id": 100},
+                        {"name": "file_format", "type": "string", "field-id": 101},
+                        {"name": "partition", "field-id": 102, "type": {
+                            "type": "record",
+                            "name": "r102",
+                            "fields": []
+                        }},
+                        {"name": "record_count", "type": "long", "field-id": 103},
+                        {"name": "file_size_in_bytes", "type": "long", "field-id": 104}
+                    ]
+                }}
+            ]
+        }"#,
+    )
+    .unwrap()
+});
+
+static MANIFEST_FILE_SCHEMA: LazyLock<AvroSchema> = LazyLock::new(|| {
+    AvroSchema::parse_str(
+        r#"{
+            "type": "record",
+            "name": "manifest_file",
+            "fields": [
+                {"name": "manifest_path", "type": "string", "field-id": 500},
+                {"name": "manifest_length", "type": "long", "field-id": 501},
+                {"name": "partition_spec_id", "type": "int", "field-id": 502},
+                {"name": "content", "type": "int", "field-id": 517},
+                {"name": "sequence_number", "type": "long", "field-id": 515},
+                {"name": "min_sequence_number", "type": "long", "field-id": 516},
+                {"name": "added_snapshot_id", "type": "long", "field-id": 503},
+                {"name": "added_files_count", "type": "int", "field-id": 504},
+                {"name": "existing_files_count", "type": "int", "field-id": 505},
+                {"name": "deleted_files_count", "type": "int", "field-id": 506},
+                {"name": "added_rows_count", "type": "long", "field-id": 512},
+                {"name": "existing_rows_count", "type": "long", "field-id": 513},
+                {"name": "deleted_rows_count", "type": "long", "field-id": 514}
+            ]
+        }"#,
+    )
+    .unwrap()
+});
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+const MANIFEST_ENTRY_STATUS_ADDED: i32 = 1;
+const CONTENT_DATA: i32 = 0;
+const FILE_FORMAT_PARQUET: &str = "PARQUET";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct ManifestEntry {
+    status: i32,
+    snapshot_id: Option<i64>,
+    /// Not set for added files, which inherit it from the manifest list
+    sequence_number: Option<i64>,
+    file_sequence_number: Option<i64>,
+    data_file: DataFile,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct DataFile {
+    content: i32,
+    file_path: String,
+    file_format: String,
+    partition: EmptyPartition,
+    record_count: i64,
+    file_size_in_bytes: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct EmptyPartition {}
+
+/// Entry of a manifest list
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct ManifestFile {
+    pub manifest_path: String,
+    pub manifest_length: i64,
+    pub partition_spec_id: i32,
+    pub content: i32,
+    pub sequence_number: i64,
+    pub min_sequence_number: i64,
+    pub added_snapshot_id: i64,
+    pub added_files_count: i32,
+    pub existing_files_count: i32,
+    pub deleted_files_count: i32,
+    pub added_rows_count: i64,
+    pub existing_rows_count: i64,
+    pub deleted_rows_count: i64,
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+pub(crate) struct IcebergDataFile {
+    pub file_path: String,
+    pub record_count: i64,
+    pub file_size_in_bytes: i64,
+}
+
+/// Writes a manifest listing data files added by a snapshot
+pub(crate) fn write_manifest(
+    schema: &IcebergSchema,
+    snapshot_id: i64,
+    data_files: &[IcebergDataFile],
+) -> Result<Vec<u8>, InternalError> {
+    let mut writer = Writer::new(&MANIFEST_ENTRY_SCHEMA, Vec::new());
+
+    writer
+        .add_user_metadata(
+            "schema".to_string(),
+            serde_json::to_string(schema).int_err()?,
+        )
+        .int_err()?;
+    writer
+        .add_user_metadata("schema-id".to_string(), schema.schema_id.to_string())
+        .int_err()?;
+    writer
+        .add_user_metadata("partition-spec".to_string(), "[]")
+        .int_err()?;
+    writer
+        .add_user_metadata("partition-spec-id".to_string(), "0")
+        .int_err()?;
+    writer
+        .add_user_metadata("format-version".to_string(), FORMAT_VERSION.to_string())
+        .int_err()?;
+    writer
+        .add_user_metadata("content".to_string(), "data")
+        .int_err()?;
+
+    for data_file in data_files {
+        writer
+            .append_ser(ManifestEntry {
+                status: MANIFEST_ENTRY_STATUS_ADDED,
+                snapshot_id: Some(snapshot_id),
+                sequence_number: None,
+                file_sequence_number: None,
+                data_file: DataFile {
+                    content: CONTENT_DATA,
+                    file_path: data_file.file_path.clone(),
+                    file_format: FILE_FORMAT_PARQUET.to_string(),
+                    partition: EmptyPartition {},
+                    record_count: data_file.record_count,
+                    file_size_in_bytes: data_file.file_size_in_bytes,
+                },
+            })
+            .int_err()?;
+    }
+
+    writer.into_inner().int_err()
+}
+
+/// Writes a manifest list of a snapshot
+pub(crate) fn write_manifest_list(
+    snapshot_id: i64,
+    parent_snapshot_id: Option<i64>,
+    sequence_number: i64,
+    manifests: &[ManifestFile],
+) -> Result<Vec<u8>, InternalError> {
+    let mut writer = Writer::new(&MANIFEST_FILE_SCHEMA, Vec::new());
+
+    writer
+        .add_user_metadata("snapshot-id".to_string(), snapshot_id.to_string())
+        .int_err()?;
+    writer
+        .add_user_metadata(
+            "parent-snapshot-id".to_string(),
+            parent_snapshot_id.map_or_else(|| "null".to_string(), |id| id.to_string()),
+        )
+        .int_err()?;
+    writer
+        .add_user_metadata("sequence-number".to_string(), sequence_number.to_string())
+        .int_err()?;
+    writer
+        .add_user_metadata("format-version".to_string(), FORMAT_VERSION.to_string())
+        .int_err()?;
+
+    for manifest in manifests {
+        writer.append_ser(manifest).int_err()?;
+    }
+
+    writer.into_inner().int_err()
+}
+
+pub(crate) fn read_manifest_list(data: &[u8]) -> Result<Vec<ManifestFile>, InternalError> {
+    let reader = Reader::with_schema(&MANIFEST_FILE_SCHEMA, data).int_err()?;
+
+    reader
+        .map(|value| apache_avro::from_value::<ManifestFile>(&value.int_err()?).int_err())
+        .collect()
+}
+
+impl ManifestFile {
+    pub fn new_data(
+        manifest_path: String,
+        manifest_length: i64,
+        snapshot_id: i64,
+        sequence_number: i64,
+        data_files: &[IcebergDataFile],
+    ) -> Self {
+        Self {
+            manifest_path,
+            manifest_length,
+            partition_spec_id: 0,
+            content: CONTENT_DATA,
+            sequence_number,
+            min_sequence_number: sequence_number,
+            added_snapshot_id: snapshot_id,
+            added_files_count: i32::try_from(data_files.len()).unwrap(),
+            existing_files_count: 0,
+            deleted_files_count: 0,
+            added_rows_count: data_files.iter().map(|f| f.record_count).sum(),
+            existing_rows_count: 0,
+            deleted_rows_count: 0,
+        }
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
@@ -0,0 +1,317 @@
+// Copyright Kamu Data, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::collections::HashMap;
+
+use datafusion::arrow::datatypes::{DataType, Field, Fields, Schema, TimeUnit};
+use kamu_core::ExportUnsupportedDataTypeError;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+/// Iceberg table schema. Field types are kept in their JSON form, as we only
+/// ever produce them from Arrow schemas and compare them as a whole
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub(crate) struct IcebergSchema {
+    #[serde(rename = "type")]
+    pub schema_type: String,
+    pub schema_id: i32,
+    pub fields: Vec<serde_json::Value>,
+}
+
+impl IcebergSchema {
+    pub fn new(schema_id: i32, fields: Vec<serde_json::Value>) -> Self {
+        Self {
+            schema_type: String::from("struct"),
+            schema_id,
+            fields,
+        }
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+/// Converts Arrow schemas into Iceberg ones. Field IDs of columns that already
+/// exist in the table are preserved, so that schema changes of the dataset
+/// translate into a proper schema evolution of the table
+pub(crate) struct IcebergSchemaConverter {
+    known_field_ids: HashMap<String, i32>,
+    last_column_id: i32,
+}
+
+impl IcebergSchemaConverter {
+    pub fn new(existing_schemas: &[IcebergSchema], last_column_id: i32) -> Self {
+        let mut known_field_ids = HashMap::new();
+        for schema in existing_schemas {
+            collect_field_ids(&schema.fields, "", &mut known_field_ids);
+        }
+
+        Self {
+            known_field_ids,
+            last_column_id,
+        }
+    }
+
+    pub fn last_column_id(&self) -> i32 {
+        self.last_column_id
+    }
+
+    pub fn convert(
+        &mut self,
+        schema: &Schema,
+    ) -> Result<Vec<serde_json::Value>, ExportUnsupportedDataTypeError> {
+        self.convert_fields(schema.fields(), "")
+    }
+
+    fn convert_fields(
+        &mut self,
+        fields: &Fields,
+        prefix: &str,
+    ) -> Result<Vec<serde_json::Value>, ExportUnsupportedDataTypeError> {
+        fields
+            .iter()
+            .map(|field| {
+                let path = format!("{prefix}{}", field.name());
+                let id = self.field_id(&path);
+                let field_type = self.convert_type(field, &path)?;
+
+                Ok(json!({
+                    "id": id,
+                    "name": field.name(),
+                    "required": !field.is_nullable(),
+                    "type": field_type,
+                }))
+            })
+            .collect()
+    }
+
+    fn convert_type(
+        &mut self,
+        field: &Field,
+        path: &str,
+    ) -> Result<serde_json::Value, ExportUnsupportedDataTypeError> {
+        let primitive = |t: &str| Ok(json!(t));
+
+        match field.data_type() {
+            DataType::Boolean => primitive("boolean"),
+            DataType::Int8
+            | DataType::Int16
+            | DataType::Int32
+            | DataType::UInt8
+            | DataType::UInt16 => primitive("int"),
+            DataType::Int64 => primitive("long"),
+            DataType::Float32 => primitive("float"),
+            DataType::Float64 => primitive("double"),
+            DataType::Decimal128(precision, scale) => {
+                primitive(&format!("decimal({precision}, {scale})"))
+            }
+            DataType::Date32 | DataType::Date64 => primitive("date"),
+            DataType::Time32(TimeUnit::Millisecond) | DataType::Time64(TimeUnit::Microsecond) => {
+                primitive("time")
+            }
+            // Nanosecond precision is only supported by format version 3
+            DataType::Timestamp(
+                TimeUnit::Second | TimeUnit::Millisecond | TimeUnit::Microsecond,
+                tz,
+            ) => primitive(if tz.is_some() {
+                "timestamptz"
+            } else {
+                "timestamp"
+            }),
+            DataType::Utf8 | DataType::LargeUtf8 | DataType::Utf8View => primitive("string"),
+            DataType::Binary | DataType::LargeBinary | DataType::BinaryView => primitive("binary"),
+            DataType::FixedSizeBinary(size) => primitive(&format!("fixed[{size}]")),
+            DataType::Dictionary(_, value_type) => self.convert_type(
+                &field.clone().with_data_type(value_type.as_ref().clone()),
+                path,
+            ),
+            DataType::List(element) | DataType::LargeList(element) => {
+                let element_path = format!("{path}.element");
+                let element_id = self.field_id(&element_path);
+                let element_type = self.convert_type(element, &element_path)?;
+
+                Ok(json!({
+                    "type": "list",
+                    "element-id": element_id,
+                    "element": element_type,
+                    "element-required": !element.is_nullable(),
+                }))
+            }
+            DataType::Struct(fields) => Ok(json!({
+                "type": "struct",
+                "fields": self.convert_fields(fields, &format!("{path}."))?,
+            })),
+            DataType::Map(entries, _) => {
+                let DataType::Struct(kv) = entries.data_type() else {
+                    return Err(unsupported(field));
+                };
+                let [key, value] = kv.iter().collect::<Vec<_>>()[..] else {
+                    return Err(unsupported(field));
+                };
+
+                let key_path = format!("{path}.key");
+                let value_path = format!("{path}.value");
+                let key_id = self.field_id(&key_path);
+                let value_id = self.field_id(&value_path);
+
+                Ok(json!({
+                    "type": "map",
+                    "key-id": key_id,
+                    "key": self.convert_type(key, &key_path)?,
+                    "value-id": value_id,
+                    "value": self.convert_type(value, &value_path)?,
+                    "value-required": !value.is_nullable(),
+                }))
+            }
+            // Unsigned 32/64-bit integers have no lossless counterpart
+            _ => Err(unsupported(field)),
+        }
+    }
+
+    fn field_id(&mut self, path: &str) -> i32 {
+        if let Some(id) = self.known_field_ids.get(path) {
+            return *id;
+        }
+
+        self.last_column_id += 1;
+        self.known_field_ids
+            .insert(path.to_string(), self.last_column_id);
+        self.last_column_id
+    }
+}
+
+fn unsupported(field: &Field) -> ExportUnsupportedDataTypeError {
+    ExportUnsupportedDataTypeError {
+        column: field.name().clone(),
+        data_type: field.data_type().to_string(),
+    }
+}
+
+fn collect_field_ids(
+    fields: &[serde_json::Value],
+    prefix: &str,
+    known_field_ids: &mut HashMap<String, i32>,
+) {
+    for field in fields {
+        let (Some(id), Some(name)) = (field["id"].as_i64(), field["name"].as_str()) else {
+            continue;
+        };
+        let path = format!("{prefix}{name}");
+        collect_type_ids(&field["type"], &path, known_field_ids);
+        known_field_ids.insert(path, i32::try_from(id).unwrap());
+    }
+}
+
+fn collect_type_ids(
+    field_type: &serde_json::Value,
+    path: &str,
+    known_field_ids: &mut HashMap<String, i32>,
+) {
+    let mut collect_nested = |id_key: &str, type_key: &str, suffix: &str| {
+        if let Some(id) = field_type[id_key].as_i64() {
+            let nested_path = format!("{path}.{suffix}");
+            collect_type_ids(&field_type[type_key], &nested_path, known_field_ids);
+            known_field_ids.insert(nested_path, i32::try_from(id).unwrap());
+        }
+    };
+
+    match field_type["type"].as_str() {
+        Some("list") => collect_nested("element-id", "element", "element"),
+        Some("map") => {
+            collect_nested("key-id", "key", "key");
+            collect_nested("value-id", "value", "value");
+        }
+        Some("struct") => {
+            if let Some(fields) = field_type["fields"].as_array() {
+                collect_field_ids(fields, &format!("{path}."), known_field_ids);
+            }
+        }
+        _ => {}
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+// Name mapping
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "kebab-case")]
+struct MappedField {
+    field_id: i32,
+    names: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    fields: Vec<MappedField>,
+}
+
+/// Builds the name mapping covering all schema versions of the table, so that
+/// data files written under older schemas resolve their columns too
+pub(crate) fn build_name_mapping(schemas: &[IcebergSchema]) -> String {
+    let mut mapping = Vec::new();
+    for schema in schemas {
+        merge_mapped_fields(&mut mapping, map_fields(&schema.fields));
+    }
+    serde_json::to_string(&mapping).unwrap()
+}
+
+fn map_fields(fields: &[serde_json::Value]) -> Vec<MappedField> {
+    fields
+        .iter()
+        .filter_map(|field| {
+            Some(MappedField {
+                field_id: i32::try_from(field["id"].as_i64()?).unwrap(),
+                names: vec![field["name"].as_str()?.to_string()],
+                fields: map_type(&field["type"]),
+            })
+        })
+        .collect()
+}
+
+fn map_type(field_type: &serde_json::Value) -> Vec<MappedField> {
+    let nested = |id_key: &str, type_key: &str, name: &str| {
+        field_type[id_key].as_i64().map(|id| MappedField {
+            field_id: i32::try_from(id).unwrap(),
+            names: vec![name.to_string()],
+            fields: map_type(&field_type[type_key]),
+        })
+    };
+
+    match field_type["type"].as_str() {
+        Some("list") => nested("element-id", "element", "element")
+            .into_iter()
+            .collect(),
+        Some("map") => nested("key-id", "key", "key")
+            .into_iter()
+            .chain(nested("value-id", "value", "value"))
+            .collect(),
+        Some("struct") => field_type["fields"]
+            .as_array()
+            .map(|fields| map_fields(fields))
+            .unwrap_or_default(),
+        _ => Vec::new(),
+    }
+}
+
+fn merge_mapped_fields(into: &mut Vec<MappedField>, from: Vec<MappedField>) {
+    for field in from {
+        if let Some(existing) = into.iter_mut().find(|f| f.field_id == field.field_id) {
+            for name in field.names {
+                if !existing.names.contains(&name) {
+                    existing.names.push(name);
+                }
+            }
+            merge_mapped_fields(&mut existing.fields, field.fields);
+        } else {
+            into.push(field);
+        }
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
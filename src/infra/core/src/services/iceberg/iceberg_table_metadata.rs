@@ -0,0 +1,217 @@
+// Copyright Kamu Data, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use super::IcebergSchema;
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+pub(crate) const FORMAT_VERSION: u8 = 2;
+
+/// Snapshot summary property holding the hash of the block the snapshot was
+/// created from
+pub(crate) const SUMMARY_BLOCK_HASH: &str = "kamu.block-hash";
+pub(crate) const SUMMARY_OFFSET_START: &str = "kamu.offset-start";
+pub(crate) const SUMMARY_OFFSET_END: &str = "kamu.offset-end";
+
+/// Table property holding the ID of the dataset the table mirrors
+pub(crate) const PROPERTY_DATASET_ID: &str = "kamu.dataset-id";
+
+/// Our data files carry no Parquet field IDs, so readers resolve columns by
+/// name using this mapping
+pub(crate) const PROPERTY_NAME_MAPPING: &str = "schema.name-mapping.default";
+
+const MAIN_BRANCH: &str = "main";
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+/// Iceberg table metadata file (format version 2)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub(crate) struct TableMetadata {
+    pub format_version: u8,
+    pub table_uuid: String,
+    pub location: String,
+    pub last_sequence_number: i64,
+    pub last_updated_ms: i64,
+    pub last_column_id: i32,
+    pub schemas: Vec<IcebergSchema>,
+    pub current_schema_id: i32,
+    pub partition_specs: Vec<PartitionSpec>,
+    pub default_spec_id: i32,
+    pub last_partition_id: i32,
+    #[serde(default)]
+    pub properties: BTreeMap<String, String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub current_snapshot_id: Option<i64>,
+    #[serde(default)]
+    pub snapshots: Vec<Snapshot>,
+    #[serde(default)]
+    pub snapshot_log: Vec<SnapshotLogEntry>,
+    #[serde(default)]
+    pub metadata_log: Vec<MetadataLogEntry>,
+    pub sort_orders: Vec<SortOrder>,
+    pub default_sort_order_id: i32,
+    #[serde(default)]
+    pub refs: BTreeMap<String, SnapshotRef>,
+}
+
+impl TableMetadata {
+    /// Unpartitioned and unsorted table without snapshots
+    pub fn new(
+        location: String,
+        schema: IcebergSchema,
+        last_column_id: i32,
+        properties: BTreeMap<String, String>,
+        now_ms: i64,
+    ) -> Self {
+        Self {
+            format_version: FORMAT_VERSION,
+            table_uuid: uuid::Uuid::new_v4().to_string(),
+            location,
+            last_sequence_number: 0,
+            last_updated_ms: now_ms,
+            last_column_id,
+            current_schema_id: schema.schema_id,
+            schemas: vec![schema],
+            partition_specs: vec![PartitionSpec {
+                spec_id: 0,
+                fields: Vec::new(),
+            }],
+            default_spec_id: 0,
+            // As per spec, partition field IDs start at 1000
+            last_partition_id: 999,
+            properties,
+            current_snapshot_id: None,
+            snapshots: Vec::new(),
+            snapshot_log: Vec::new(),
+            metadata_log: Vec::new(),
+            sort_orders: vec![SortOrder {
+                order_id: 0,
+                fields: Vec::new(),
+            }],
+            default_sort_order_id: 0,
+            refs: BTreeMap::new(),
+        }
+    }
+
+    pub fn current_schema(&self) -> &IcebergSchema {
+        self.schemas
+            .iter()
+            .find(|s| s.schema_id == self.current_schema_id)
+            .expect("Current schema is missing")
+    }
+
+    pub fn current_snapshot(&self) -> Option<&Snapshot> {
+        let snapshot_id = self.current_snapshot_id?;
+        self.snapshots.iter().find(|s| s.snapshot_id == snapshot_id)
+    }
+
+    /// Makes the schema current, adding it unless an identical one exists
+    pub fn set_current_schema(&mut self, schema_fields: Vec<serde_json::Value>) {
+        if let Some(existing) = self.schemas.iter().find(|s| s.fields == schema_fields) {
+            self.current_schema_id = existing.schema_id;
+            return;
+        }
+
+        let schema_id = self
+            .schemas
+            .iter()
+            .map(|s| s.schema_id)
+            .max()
+            .map_or(0, |id| id + 1);
+
+        self.schemas
+            .push(IcebergSchema::new(schema_id, schema_fields));
+        self.current_schema_id = schema_id;
+    }
+
+    pub fn add_snapshot(&mut self, snapshot: Snapshot) {
+        self.last_sequence_number = snapshot.sequence_number;
+        self.current_snapshot_id = Some(snapshot.snapshot_id);
+        self.snapshot_log.push(SnapshotLogEntry {
+            timestamp_ms: snapshot.timestamp_ms,
+            snapshot_id: snapshot.snapshot_id,
+        });
+        self.refs.insert(
+            MAIN_BRANCH.to_string(),
+            SnapshotRef {
+                snapshot_id: snapshot.snapshot_id,
+                ref_type: String::from("branch"),
+            },
+        );
+        self.snapshots.push(snapshot);
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub(crate) struct PartitionSpec {
+    pub spec_id: i32,
+    pub fields: Vec<serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub(crate) struct SortOrder {
+    pub order_id: i32,
+    pub fields: Vec<serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub(crate) struct Snapshot {
+    pub snapshot_id: i64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parent_snapshot_id: Option<i64>,
+    pub sequence_number: i64,
+    pub timestamp_ms: i64,
+    pub manifest_list: String,
+    pub summary: BTreeMap<String, String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub schema_id: Option<i32>,
+}
+
+impl Snapshot {
+    pub fn summary_i64(&self, key: &str) -> i64 {
+        self.summary
+            .get(key)
+            .and_then(|v| v.parse().ok())
+            .unwrap_or_default()
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub(crate) struct SnapshotLogEntry {
+    pub timestamp_ms: i64,
+    pub snapshot_id: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub(crate) struct MetadataLogEntry {
+    pub timestamp_ms: i64,
+    pub metadata_file: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub(crate) struct SnapshotRef {
+    pub snapshot_id: i64,
+    #[serde(rename = "type")]
+    pub ref_type: String,
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
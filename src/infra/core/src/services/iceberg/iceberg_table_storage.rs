@@ -0,0 +1,240 @@
+// Copyright Kamu Data, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::sync::Arc;
+
+use bytes::Bytes;
+use internal_error::{ErrorIntoInternal, InternalError, ResultIntoInternal};
+use object_store::path::Path;
+use object_store::{ObjectStore, PutMode, PutOptions, PutPayload};
+use s3_utils::S3Context;
+use url::Url;
+
+use super::TableMetadata;
+use crate::ObjectStoreBuilderS3;
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+const METADATA_DIR: &str = "metadata";
+const DATA_DIR: &str = "data";
+
+/// Follows the file-system table layout, where the current metadata version is
+/// pointed to by the version hint file
+const VERSION_HINT_FILE: &str = "version-hint.text";
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+/// Access to files of an Iceberg table located on local FS or S3
+pub(crate) struct IcebergTableStorage {
+    object_store: Arc<dyn ObjectStore>,
+    root: Path,
+    /// Table location as recorded in the metadata
+    location: String,
+}
+
+impl IcebergTableStorage {
+    pub async fn open(table_url: &Url) -> Result<Self, InternalError> {
+        match table_url.scheme() {
+            "file" => {
+                let table_path = table_url
+                    .to_file_path()
+                    .map_err(|_| format!("Invalid table path: {table_url}").int_err())?;
+                std::fs::create_dir_all(&table_path).int_err()?;
+
+                let object_store =
+                    object_store::local::LocalFileSystem::new_with_prefix(&table_path).int_err()?;
+
+                Ok(Self {
+                    object_store: Arc::new(object_store),
+                    root: Path::default(),
+                    location: Url::from_directory_path(&table_path)
+                        .unwrap()
+                        .as_str()
+                        .trim_end_matches('/')
+                        .to_string(),
+                })
+            }
+            "s3" | "s3+http" | "s3+https" => {
+                let mut base_url = table_url.clone();
+                if !base_url.path().ends_with('/') {
+                    base_url.set_path(&format!("{}/", base_url.path()));
+                }
+                let s3_context = S3Context::from_url(&base_url).await;
+                let allow_http = table_url.scheme() == "s3+http";
+
+                let location = format!(
+                    "s3://{}/{}",
+                    s3_context.bucket(),
+                    s3_context.key_prefix().trim_end_matches('/')
+                );
+                let root = Path::from(s3_context.key_prefix());
+
+                use kamu_core::ObjectStoreBuilder;
+                let object_store =
+                    ObjectStoreBuilderS3::new(s3_context, allow_http).build_object_store()?;
+
+                Ok(Self {
+                    object_store,
+                    root,
+                    location,
+                })
+            }
+            scheme => Err(format!("Unsupported Iceberg table location scheme: {scheme}").int_err()),
+        }
+    }
+
+    pub fn location(&self) -> &str {
+        &self.location
+    }
+
+    pub fn metadata_file_name(version: u64) -> String {
+        format!("v{version}.metadata.json")
+    }
+
+    pub fn metadata_file_location(&self, file_name: &str) -> String {
+        format!("{}/{METADATA_DIR}/{file_name}", self.location)
+    }
+
+    pub fn data_file_location(&self, file_name: &str) -> String {
+        format!("{}/{DATA_DIR}/{file_name}", self.location)
+    }
+
+    /// Returns the current metadata version along with its content
+    pub async fn read_current_metadata(
+        &self,
+    ) -> Result<Option<(u64, TableMetadata)>, InternalError> {
+        let Some(version_hint) = self.try_get(&self.metadata_path(VERSION_HINT_FILE)).await? else {
+            return Ok(None);
+        };
+
+        let version: u64 = std::str::from_utf8(&version_hint)
+            .int_err()?
+            .trim()
+            .parse()
+            .int_err()?;
+
+        let Some(metadata) = self
+            .try_get(&self.metadata_path(&Self::metadata_file_name(version)))
+            .await?
+        else {
+            return InternalError::bail(format!(
+                "Metadata version {version} of the table at {} is missing",
+                self.location
+            ));
+        };
+
+        Ok(Some((
+            version,
+            serde_json::from_slice(&metadata).int_err()?,
+        )))
+    }
+
+    /// Commits a new metadata version. Fails if the version was already
+    /// committed by a concurrent export
+    pub async fn write_metadata(
+        &self,
+        version: u64,
+        metadata: &TableMetadata,
+    ) -> Result<(), InternalError> {
+        let content = serde_json::to_vec_pretty(metadata).int_err()?;
+
+        self.object_store
+            .put_opts(
+                &self.metadata_path(&Self::metadata_file_name(version)),
+                PutPayload::from(content),
+                PutOptions {
+                    mode: PutMode::Create,
+                    ..Default::default()
+                },
+            )
+            .await
+            .int_err()?;
+
+        self.object_store
+            .put(
+                &self.metadata_path(VERSION_HINT_FILE),
+                PutPayload::from(version.to_string()),
+            )
+            .await
+            .int_err()?;
+
+        Ok(())
+    }
+
+    pub async fn write_metadata_file(
+        &self,
+        file_name: &str,
+        content: Vec<u8>,
+    ) -> Result<(), InternalError> {
+        self.object_store
+            .put(&self.metadata_path(file_name), PutPayload::from(content))
+            .await
+            .int_err()?;
+        Ok(())
+    }
+
+    pub async fn read_file(&self, location: &str) -> Result<Bytes, InternalError> {
+        let Some(relative) = location
+            .strip_prefix(&self.location)
+            .map(|p| p.trim_start_matches('/'))
+        else {
+            return InternalError::bail(format!(
+                "File {location} is outside of the table location {}",
+                self.location
+            ));
+        };
+
+        let path = self
+            .root
+            .parts()
+            .chain(Path::from(relative).parts())
+            .collect();
+
+        self.object_store
+            .get(&path)
+            .await
+            .int_err()?
+            .bytes()
+            .await
+            .int_err()
+    }
+
+    /// Copies a data file into the table's data directory
+    pub async fn copy_data_file(
+        &self,
+        file_name: &str,
+        mut data: impl tokio::io::AsyncRead + Unpin,
+    ) -> Result<(), InternalError> {
+        use tokio::io::AsyncWriteExt;
+
+        let mut writer = object_store::buffered::BufWriter::new(
+            self.object_store.clone(),
+            self.root.child(DATA_DIR).child(file_name),
+        );
+
+        tokio::io::copy(&mut data, &mut writer).await.int_err()?;
+        writer.shutdown().await.int_err()?;
+
+        Ok(())
+    }
+
+    fn metadata_path(&self, file_name: &str) -> Path {
+        self.root.child(METADATA_DIR).child(file_name)
+    }
+
+    async fn try_get(&self, path: &Path) -> Result<Option<Bytes>, InternalError> {
+        match self.object_store.get(path).await {
+            Ok(result) => Ok(Some(result.bytes().await.int_err()?)),
+            Err(object_store::Error::NotFound { .. }) => Ok(None),
+            Err(e) => Err(e.int_err()),
+        }
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
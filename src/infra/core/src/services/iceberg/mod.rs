@@ -0,0 +1,20 @@
+// Copyright Kamu Data, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+mod iceberg_exporter;
+mod iceberg_manifest;
+mod iceberg_schema;
+mod iceberg_table_metadata;
+mod iceberg_table_storage;
+
+pub(crate) use iceberg_exporter::*;
+pub(crate) use iceberg_manifest::*;
+pub(crate) use iceberg_schema::*;
+pub(crate) use iceberg_table_metadata::*;
+pub(crate) use iceberg_table_storage::*;
@@ -24,3 +24,5 @@ mod http;
 #[cfg(feature = "ingest-mqtt")]
 mod mqtt;
 mod template;
+
+pub(crate) use template::expand_snapshot_template;
@@ -9,25 +9,49 @@
 
 use std::borrow::Cow;
 
-use kamu_core::{TemplateError, TemplateInvalidPatternError, TemplateValueNotFoundError};
+use kamu_core::{
+    TemplateError,
+    TemplateInvalidDocumentError,
+    TemplateInvalidPatternError,
+    TemplateValueNotFoundError,
+    TemplateVars,
+};
 use lazy_static::lazy_static;
 use regex::Regex;
+use serde::Deserialize as _;
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
 
 lazy_static! {
     static ref RE_ENV: Regex =
         Regex::new(r"^env\.([a-zA-Z-_0-9]+)$").expect("Invalid template env variable regex");
+    static ref RE_VAR: Regex =
+        Regex::new(r"^vars\.([a-zA-Z-_0-9]+)$").expect("Invalid template variable regex");
     static ref RE_NUM: Regex =
         Regex::new(r"^(-?[0-9]+(?:.[0-9]+)?)$").expect("Invalid template number regex");
     static ref RE_STR: Regex = Regex::new(r"^'([^']*)'$").expect("Invalid template string regex");
+    static ref RE_TPL: Regex = Regex::new(r"\$\{\{([^}]*)\}\}").expect("Invalid template regex");
+    static ref RE_FOR: Regex = Regex::new(
+        r"^(?:#\s*)?\$\{\{\s*for\s+([a-zA-Z-_0-9]+)\s+in\s+vars\.([a-zA-Z-_0-9]+)\s*\}\}$"
+    )
+    .expect("Invalid template loop regex");
+    static ref RE_END: Regex =
+        Regex::new(r"^(?:#\s*)?\$\{\{\s*end\s*\}\}$").expect("Invalid template loop end regex");
 }
 
+/// Separates items of a variable iterated by a `for` loop
+const LIST_SEPARATOR: char = ';';
+
+/// Stands in for a substituted value until the template is parsed as YAML
+const VALUE_MARKER_PREFIX: &str = "__kamu_template_value_";
+const VALUE_MARKER_SUFFIX: &str = "__";
+
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
 
 #[derive(Debug)]
 pub enum TemplateToken {
     EnvVar(String),
+    Var(String),
     Literal(String),
 }
 
@@ -40,6 +64,13 @@ fn parse_token(token_str: &str) -> Option<TemplateToken> {
             .as_str()
             .trim();
         Some(TemplateToken::EnvVar(env_name.to_string()))
+    } else if let Some(cap_var) = RE_VAR.captures(token_str) {
+        let var_name = cap_var
+            .get(1)
+            .expect("Missing variable name capture")
+            .as_str()
+            .trim();
+        Some(TemplateToken::Var(var_name.to_string()))
     } else if let Some(cap_num) = RE_NUM.captures(token_str) {
         let number = cap_num
             .get(1)
@@ -99,6 +130,8 @@ pub fn template_string<'a>(
                 .iter()
                 .map(|token| match token {
                     TemplateToken::EnvVar(v) => lookup_fn(v),
+                    // Snapshot variables are expected to be expanded before the dataset is created
+                    TemplateToken::Var(_) => None,
                     TemplateToken::Literal(v) => Some(v.clone()),
                 })
                 .find_map(std::convert::identity);
@@ -120,12 +153,279 @@ pub fn template_string<'a>(
     }
 }
 
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+// Snapshot templates
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+enum TemplateNode<'a> {
+    Line(&'a str),
+    Loop {
+        item: String,
+        list_var: String,
+        body: Vec<TemplateNode<'a>>,
+    },
+}
+
+/// Expands `${{ vars.<name> }}` placeholders and `${{ for <item> in
+/// vars.<name> }}` ... `${{ end }}` loop blocks of a snapshot template.
+///
+/// Loop directives must occupy a whole line and can be prefixed with `#` to
+/// keep the template a valid YAML. Items of the iterated variable are
+/// separated by `;` and are available in the loop body as `vars.<item>`.
+/// Expressions referring to `env` are left intact to be resolved at fetch
+/// time, but can't share a line with `vars` expressions.
+///
+/// Values are substituted into the parsed YAML rather than into its text, so
+/// they can't alter the structure of the document. A value that fills a whole
+/// scalar becomes a number or a boolean if it reads as one, and a string
+/// otherwise.
+pub fn expand_snapshot_template(
+    template: &str,
+    vars: &TemplateVars,
+) -> Result<String, TemplateError> {
+    let nodes = parse_template_nodes(&mut template.lines(), None)?;
+
+    let mut res = String::with_capacity(template.len());
+    let mut values = Vec::new();
+    render_template_nodes(&nodes, vars, &mut res, &mut values)?;
+
+    if values.is_empty() {
+        return Ok(res);
+    }
+
+    let mut documents = Vec::new();
+    for document in serde_yaml::Deserializer::from_str(&res) {
+        let mut document = serde_yaml::Value::deserialize(document).map_err(|e| {
+            TemplateError::InvalidDocument(TemplateInvalidDocumentError::new(e.to_string()))
+        })?;
+        substitute_values(&mut document, &values);
+        documents.push(serde_yaml::to_string(&document).map_err(|e| {
+            TemplateError::InvalidDocument(TemplateInvalidDocumentError::new(e.to_string()))
+        })?);
+    }
+
+    Ok(documents.join("---\n"))
+}
+
+fn parse_template_nodes<'a>(
+    lines: &mut std::str::Lines<'a>,
+    loop_directive: Option<&str>,
+) -> Result<Vec<TemplateNode<'a>>, TemplateError> {
+    let mut nodes = Vec::new();
+
+    while let Some(line) = lines.next() {
+        let directive = line.trim();
+        if let Some(cap_for) = RE_FOR.captures(directive) {
+            let item = cap_for.get(1).expect("Missing loop item capture").as_str();
+            let list_var = cap_for
+                .get(2)
+                .expect("Missing loop variable capture")
+                .as_str();
+            let body = parse_template_nodes(lines, Some(directive))?;
+            nodes.push(TemplateNode::Loop {
+                item: item.to_string(),
+                list_var: list_var.to_string(),
+                body,
+            });
+        } else if RE_END.is_match(directive) {
+            return match loop_directive {
+                Some(_) => Ok(nodes),
+                None => Err(TemplateError::InvalidPattern(
+                    TemplateInvalidPatternError::new(directive),
+                )),
+            };
+        } else {
+            nodes.push(TemplateNode::Line(line));
+        }
+    }
+
+    match loop_directive {
+        // Loop was not closed
+        Some(directive) => Err(TemplateError::InvalidPattern(
+            TemplateInvalidPatternError::new(directive),
+        )),
+        None => Ok(nodes),
+    }
+}
+
+fn render_template_nodes(
+    nodes: &[TemplateNode<'_>],
+    vars: &TemplateVars,
+    res: &mut String,
+    values: &mut Vec<String>,
+) -> Result<(), TemplateError> {
+    for node in nodes {
+        match node {
+            TemplateNode::Line(line) => {
+                res.push_str(&expand_snapshot_template_line(line, vars, values)?);
+                res.push('\n');
+            }
+            TemplateNode::Loop {
+                item,
+                list_var,
+                body,
+            } => {
+                let Some(list) = vars.get(list_var) else {
+                    return Err(TemplateError::ValueNotFound(
+                        TemplateValueNotFoundError::new(format!("vars.{list_var}")),
+                    ));
+                };
+
+                for value in list
+                    .split(LIST_SEPARATOR)
+                    .map(str::trim)
+                    .filter(|v| !v.is_empty())
+                {
+                    let mut loop_vars = vars.clone();
+                    loop_vars.insert(item.clone(), value.to_string());
+                    render_template_nodes(body, &loop_vars, res, values)?;
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Replaces `vars` expressions of the line with markers of the values they
+/// resolve to
+fn expand_snapshot_template_line(
+    line: &str,
+    vars: &TemplateVars,
+    values: &mut Vec<String>,
+) -> Result<String, TemplateError> {
+    let mut expressions = Vec::new();
+    for ctpl in RE_TPL.captures_iter(line) {
+        let tpl = ctpl.get(0).expect("Missing template capture");
+        let tokens_str = ctpl
+            .get(1)
+            .expect("Missing variable capture")
+            .as_str()
+            .trim();
+        expressions.push((tpl, tokens_str, parse_tokens(tokens_str)?));
+    }
+
+    let has_vars = expressions
+        .iter()
+        .flat_map(|(_, _, tokens)| tokens)
+        .any(|t| matches!(t, TemplateToken::Var(_)));
+    let has_env_vars = expressions
+        .iter()
+        .flat_map(|(_, _, tokens)| tokens)
+        .any(|t| matches!(t, TemplateToken::EnvVar(_)));
+
+    if !has_vars {
+        return Ok(line.to_string());
+    }
+    // Values would otherwise end up in expressions resolved at fetch time
+    if has_env_vars {
+        return Err(TemplateError::InvalidPattern(
+            TemplateInvalidPatternError::new(line.trim()),
+        ));
+    }
+
+    let mut res = String::with_capacity(line.len());
+    let mut pos = 0;
+
+    for (tpl, tokens_str, tokens) in expressions {
+        res.push_str(&line[pos..tpl.start()]);
+        pos = tpl.end();
+
+        let value = tokens
+            .iter()
+            .map(|token| match token {
+                TemplateToken::Var(v) => vars.get(v).cloned(),
+                TemplateToken::Literal(v) => Some(v.clone()),
+                TemplateToken::EnvVar(_) => unreachable!(),
+            })
+            .find_map(std::convert::identity);
+
+        let Some(value) = value else {
+            return Err(TemplateError::ValueNotFound(
+                TemplateValueNotFoundError::new(tokens_str),
+            ));
+        };
+        // Expressions in values would be resolved at fetch time
+        if RE_TPL.is_match(&value) {
+            return Err(TemplateError::InvalidPattern(
+                TemplateInvalidPatternError::new(value),
+            ));
+        }
+
+        res.push_str(&value_marker(values.len()));
+        values.push(value);
+    }
+
+    res.push_str(&line[pos..]);
+    Ok(res)
+}
+
+fn substitute_values(node: &mut serde_yaml::Value, values: &[String]) {
+    match node {
+        serde_yaml::Value::String(s) => {
+            if let Some(value) = whole_marker_value(s, values) {
+                *node = match serde_yaml::from_str(value) {
+                    Ok(scalar @ (serde_yaml::Value::Number(_) | serde_yaml::Value::Bool(_))) => {
+                        scalar
+                    }
+                    _ => serde_yaml::Value::String(value.to_string()),
+                };
+            } else {
+                *s = substitute_string_values(s, values);
+            }
+        }
+        serde_yaml::Value::Sequence(items) => {
+            for item in items {
+                substitute_values(item, values);
+            }
+        }
+        serde_yaml::Value::Mapping(mapping) => {
+            let entries = std::mem::take(mapping);
+            for (mut key, mut value) in entries {
+                if let serde_yaml::Value::String(k) = &mut key {
+                    *k = substitute_string_values(k, values);
+                }
+                substitute_values(&mut value, values);
+                mapping.insert(key, value);
+            }
+        }
+        serde_yaml::Value::Tagged(tagged) => substitute_values(&mut tagged.value, values),
+        serde_yaml::Value::Null | serde_yaml::Value::Bool(_) | serde_yaml::Value::Number(_) => {}
+    }
+}
+
+fn whole_marker_value<'a>(s: &str, values: &'a [String]) -> Option<&'a str> {
+    let index: usize = s
+        .strip_prefix(VALUE_MARKER_PREFIX)?
+        .strip_suffix(VALUE_MARKER_SUFFIX)?
+        .parse()
+        .ok()?;
+    values.get(index).map(String::as_str)
+}
+
+fn substitute_string_values(s: &str, values: &[String]) -> String {
+    let mut res = s.to_string();
+    for (index, value) in values.iter().enumerate() {
+        let marker = value_marker(index);
+        if res.contains(&marker) {
+            res = res.replace(&marker, value);
+        }
+    }
+    res
+}
+
+fn value_marker(index: usize) -> String {
+    format!("{VALUE_MARKER_PREFIX}{index}{VALUE_MARKER_SUFFIX}")
+}
+
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
 
 #[cfg(test)]
 mod tests {
     use std::assert_matches::assert_matches;
 
+    use serde::Deserialize as _;
+
     use super::*;
 
     #[test]
@@ -248,6 +548,155 @@ mod tests {
         );
         assert_matches!(result, Ok(val) if val.as_str() == "foo=val1");
     }
+
+    fn vars(values: &[(&str, &str)]) -> TemplateVars {
+        values
+            .iter()
+            .map(|(k, v)| ((*k).to_string(), (*v).to_string()))
+            .collect()
+    }
+
+    fn yaml(s: &str) -> serde_yaml::Value {
+        serde_yaml::from_str(s).unwrap()
+    }
+
+    #[test]
+    fn test_expand_snapshot_template() {
+        let template = indoc::indoc!(
+            "
+            name: weather.${{ vars.city }}
+            units: ${{ vars.units || 'metric' }}
+            token: ${{ env.API_TOKEN }}
+            "
+        );
+
+        assert_eq!(
+            yaml(&expand_snapshot_template(template, &vars(&[("city", "berlin")])).unwrap()),
+            yaml(indoc::indoc!(
+                "
+                name: weather.berlin
+                units: metric
+                token: ${{ env.API_TOKEN }}
+                "
+            ))
+        );
+
+        assert_matches!(
+            expand_snapshot_template(template, &vars(&[])),
+            Err(TemplateError::ValueNotFound(err))
+                if err.template.as_str() == "vars.city");
+    }
+
+    #[test]
+    fn test_expand_snapshot_template_loops() {
+        let template = indoc::indoc!(
+            "
+            sensors:
+            # ${{ for sensor in vars.sensors }}
+            ${{ for metric in vars.metrics }}
+              - ${{ vars.sensor }}.${{ vars.metric }}
+            ${{ end }}
+            # ${{ end }}
+            "
+        );
+
+        assert_eq!(
+            yaml(
+                &expand_snapshot_template(
+                    template,
+                    &vars(&[("sensors", "a; b"), ("metrics", "temp;")])
+                )
+                .unwrap()
+            ),
+            yaml(indoc::indoc!(
+                "
+                sensors:
+                  - a.temp
+                  - b.temp
+                "
+            ))
+        );
+
+        assert_matches!(
+            expand_snapshot_template(template, &vars(&[("metrics", "temp")])),
+            Err(TemplateError::ValueNotFound(err))
+                if err.template.as_str() == "vars.sensors");
+    }
+
+    #[test]
+    fn test_expand_snapshot_template_values_are_scalars() {
+        let template = indoc::indoc!(
+            "
+            name: ${{ vars.name }}
+            title: City of ${{ vars.name }}
+            size: ${{ vars.size }}
+            enabled: ${{ vars.enabled }}
+            ---
+            name: other
+            "
+        );
+
+        let expanded = expand_snapshot_template(
+            template,
+            &vars(&[
+                ("name", "berlin\nkind: Derivative"),
+                ("size", "10"),
+                ("enabled", "true"),
+            ]),
+        )
+        .unwrap();
+
+        let documents: Vec<_> = serde_yaml::Deserializer::from_str(&expanded)
+            .map(|d| serde_yaml::Value::deserialize(d).unwrap())
+            .collect();
+        assert_eq!(documents.len(), 2);
+        assert_eq!(
+            documents[0],
+            yaml(indoc::indoc!(
+                r#"
+                name: "berlin\nkind: Derivative"
+                title: "City of berlin\nkind: Derivative"
+                size: 10
+                enabled: true
+                "#
+            ))
+        );
+        assert_eq!(documents[1], yaml("name: other"));
+    }
+
+    #[test]
+    fn test_expand_snapshot_template_rejects_env_expressions() {
+        // Variables and environment on the same line
+        assert_matches!(
+            expand_snapshot_template(
+                "url: https://${{ vars.host }}/?token=${{ env.TOKEN }}\n",
+                &vars(&[("host", "example.com")])
+            ),
+            Err(TemplateError::InvalidPattern(err))
+                if err.pattern.as_str() == "url: https://${{ vars.host }}/?token=${{ env.TOKEN }}");
+
+        // Expressions smuggled in values
+        assert_matches!(
+            expand_snapshot_template(
+                "url: https://${{ vars.host }}/\n",
+                &vars(&[("host", "${{ env.AWS_SECRET_ACCESS_KEY }}")])
+            ),
+            Err(TemplateError::InvalidPattern(err))
+                if err.pattern.as_str() == "${{ env.AWS_SECRET_ACCESS_KEY }}");
+    }
+
+    #[test]
+    fn test_expand_snapshot_template_unbalanced_loops() {
+        assert_matches!(
+            expand_snapshot_template("${{ for a in vars.list }}\nfoo\n", &vars(&[])),
+            Err(TemplateError::InvalidPattern(err))
+                if err.pattern.as_str() == "${{ for a in vars.list }}");
+
+        assert_matches!(
+            expand_snapshot_template("foo\n${{ end }}\n", &vars(&[])),
+            Err(TemplateError::InvalidPattern(err))
+                if err.pattern.as_str() == "${{ end }}");
+    }
 }
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
@@ -22,6 +22,7 @@ use random_names::get_random_name;
 use time_source::SystemTimeSource;
 
 use super::*;
+use crate::DataExpectationsChecker;
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
 
@@ -101,6 +102,7 @@ impl PollingIngestServiceImpl {
             // TODO: Avoid excessive cloning
             let iteration_args = IngestIterationArgs {
                 dataset_handle: target.get_handle().clone(),
+                target: target.clone(),
                 iteration,
                 operation_id,
                 operation_dir,
@@ -240,6 +242,8 @@ impl PollingIngestServiceImpl {
             None
         };
 
+        let expectations_check = DataExpectationsChecker::check(&args.target, df, true).await?;
+
         let new_source_state = savepoint.source_state.map(|ss| ss.to_source_state());
 
         let out_dir = args.operation_dir.join("out");
@@ -249,7 +253,7 @@ impl PollingIngestServiceImpl {
         let stage_result = args
             .data_writer
             .stage(
-                df,
+                expectations_check.data.clone(),
                 WriteDataOpts {
                     system_time: args.system_time,
                     source_event_time: savepoint.source_event_time.unwrap_or(args.system_time),
@@ -280,7 +284,18 @@ impl PollingIngestServiceImpl {
                     TotalSteps::Exact(1),
                 );
 
-                let res = args.data_writer.commit(staged).await?;
+                let mut res = args.data_writer.commit(staged).await?;
+
+                if let Some(report_event) = expectations_check
+                    .into_report_event(&args.target, &res.new_head)
+                    .await?
+                {
+                    res.new_head = args
+                        .data_writer
+                        .commit_event(report_event, args.system_time)
+                        .await?
+                        .new_head;
+                }
 
                 Ok(PollingIngestResult::Updated {
                     old_head: res.old_head,
@@ -601,6 +616,7 @@ pub(crate) struct PrepStepResult {
 
 struct IngestIterationArgs<'a> {
     dataset_handle: odf::DatasetHandle,
+    target: ResolvedDataset,
     iteration: usize,
     operation_id: String,
     operation_dir: PathBuf,
@@ -19,6 +19,7 @@ use kamu_ingest_datafusion::*;
 use tokio::io::AsyncRead;
 
 use super::ingest_common;
+use crate::DataExpectationsChecker;
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
 
@@ -64,7 +65,14 @@ impl PushIngestExecutorImpl {
         listener.begin();
 
         match self
-            .do_ingest_inner(plan.args, source, data_writer, ctx, listener.clone())
+            .do_ingest_inner(
+                &target,
+                plan.args,
+                source,
+                data_writer,
+                ctx,
+                listener.clone(),
+            )
             .await
         {
             Ok(res) => {
@@ -89,6 +97,7 @@ impl PushIngestExecutorImpl {
     )]
     async fn do_ingest_inner(
         &self,
+        target: &ResolvedDataset,
         args: PushIngestArgs,
         source: DataSource,
         mut data_writer: DataWriterDataFusion,
@@ -130,13 +139,15 @@ impl PushIngestExecutorImpl {
             None
         };
 
+        let expectations_check = DataExpectationsChecker::check(target, df, true).await?;
+
         let out_dir = args.operation_dir.join("out");
         let data_staging_path = out_dir.join("data.parquet");
         std::fs::create_dir(&out_dir).int_err()?;
 
         let stage_result = data_writer
             .stage(
-                df,
+                expectations_check.data.clone(),
                 WriteDataOpts {
                     system_time: args.system_time,
                     source_event_time: args.opts.source_event_time.unwrap_or(args.system_time),
@@ -153,12 +164,24 @@ impl PushIngestExecutorImpl {
             Ok(staged) => {
                 listener.on_stage_progress(PushIngestStage::Commit, 0, TotalSteps::Exact(1));
 
-                let res = data_writer.commit(staged).await?;
+                let mut res = data_writer.commit(staged).await?;
+                let mut num_blocks = 1;
+
+                if let Some(report_event) = expectations_check
+                    .into_report_event(target, &res.new_head)
+                    .await?
+                {
+                    res.new_head = data_writer
+                        .commit_event(report_event, args.system_time)
+                        .await?
+                        .new_head;
+                    num_blocks += 1;
+                }
 
                 Ok(PushIngestResult::Updated {
                     old_head: res.old_head,
                     new_head: res.new_head,
-                    num_blocks: 1,
+                    num_blocks,
                 })
             }
             Err(StageDataError::BadInputSchema(e)) => Err(e.into()),
@@ -101,6 +101,47 @@ impl MetadataQueryService for MetadataQueryServiceImpl {
 
         Ok(current_watermark)
     }
+
+    /// Attempt reading watermark of the last `AddData` or `ExecuteTransform`
+    /// block
+    #[tracing::instrument(level = "info", skip_all)]
+    async fn try_get_last_data_watermark(
+        &self,
+        resolved_dataset: ResolvedDataset,
+    ) -> Result<Option<DateTime<Utc>>, InternalError> {
+        use odf::dataset::MetadataChainExt;
+
+        // Both `AddData` and `ExecuteTransform` events carry the watermark
+        let last_watermark = resolved_dataset
+            .as_metadata_chain()
+            .last_data_block()
+            .await
+            .int_err()?
+            .into_event()
+            .and_then(|e| e.new_watermark);
+
+        Ok(last_watermark)
+    }
+
+    /// Returns system time of the last `AddData` or `ExecuteTransform` block
+    /// that added new data to a dataset
+    #[tracing::instrument(level = "info", skip_all)]
+    async fn try_get_last_data_update_time(
+        &self,
+        resolved_dataset: ResolvedDataset,
+    ) -> Result<Option<DateTime<Utc>>, InternalError> {
+        use odf::dataset::MetadataChainExt;
+
+        let last_update_time = resolved_dataset
+            .as_metadata_chain()
+            .last_data_block_with_new_data()
+            .await
+            .int_err()?
+            .into_block()
+            .map(|block| block.system_time);
+
+        Ok(last_update_time)
+    }
 }
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
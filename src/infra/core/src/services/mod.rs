@@ -8,25 +8,33 @@
 // by the Apache License, Version 2.0.
 
 mod compaction;
+mod expectations;
+mod iceberg;
 pub mod ingest;
 mod object_store;
 mod query;
 mod remote;
+mod reprocess;
 mod reset;
 mod sync;
 mod transform;
 mod watermark;
 
 pub use compaction::*;
+pub use expectations::*;
 pub use ingest::*;
 pub use object_store::*;
 pub use remote::*;
+pub use reprocess::*;
 pub use reset::*;
 pub use sync::*;
 pub use transform::*;
 pub use watermark::*;
 
+mod dataset_change_feed_service_impl;
 mod dataset_changes_service_impl;
+mod dataset_diff_service_impl;
+mod dataset_freshness_service_impl;
 mod dataset_registry_solo_unit_bridge;
 mod export_service_impl;
 mod metadata_query_service_impl;
@@ -37,7 +45,10 @@ mod query_service_impl;
 
 mod verification_service_impl;
 
+pub use dataset_change_feed_service_impl::*;
 pub use dataset_changes_service_impl::*;
+pub use dataset_diff_service_impl::*;
+pub use dataset_freshness_service_impl::*;
 pub use dataset_registry_solo_unit_bridge::*;
 pub use export_service_impl::*;
 pub use metadata_query_service_impl::*;
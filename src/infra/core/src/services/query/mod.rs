@@ -8,7 +8,7 @@
 // by the Apache License, Version 2.0.
 
 use std::borrow::Cow;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::{Arc, Mutex};
 
 use auth::DatasetAction;
@@ -29,6 +29,9 @@ use futures::stream::{self, StreamExt};
 use internal_error::{InternalError, ResultIntoInternal};
 use kamu_core::auth::DatasetActionAuthorizerExt;
 use kamu_core::*;
+
+use super::compaction::read_compacted_data_slices;
+
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
 // Catalog
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
@@ -401,14 +404,17 @@ impl KamuTable {
         tracing::debug!(?as_of, "Collecting data slices");
 
         let last_records_to_consider = self.hints.as_ref().and_then(|o| o.last_records_to_consider);
+        let since_offset = self.hints.as_ref().and_then(|o| o.since_offset);
 
         type Flag = odf::metadata::MetadataEventTypeFlags;
         type Decision = odf::dataset::MetadataVisitorDecision;
 
         struct DataSliceCollectorVisitorState {
-            files: Vec<odf::Multihash>,
+            files: Vec<(odf::Multihash, odf::metadata::OffsetInterval)>,
+            blocks: HashSet<odf::Multihash>,
             num_records: u64,
             last_records_to_consider: Option<u64>,
+            since_offset: Option<u64>,
         }
 
         use odf::dataset::MetadataChainExt;
@@ -419,11 +425,13 @@ impl KamuTable {
                 &hash,
                 DataSliceCollectorVisitorState {
                     files: Vec::new(),
+                    blocks: HashSet::new(),
                     num_records: 0,
                     last_records_to_consider,
+                    since_offset,
                 },
                 Decision::NextOfType(Flag::DATA_BLOCK),
-                |state, _hash, block| {
+                |state, hash, block| {
                     let new_data = match &block.event {
                         odf::MetadataEvent::AddData(e) => e.new_data.as_ref(),
                         odf::MetadataEvent::ExecuteTransform(e) => e.new_data.as_ref(),
@@ -433,8 +441,19 @@ impl KamuTable {
                         return Decision::NextOfType(Flag::DATA_BLOCK);
                     };
 
+                    // Slices are visited from newest to oldest, so once we reach the one that
+                    // was already seen by the caller - all preceding slices can be skipped too
+                    if let Some(since_offset) = &state.since_offset
+                        && slice.offset_interval.end <= *since_offset
+                    {
+                        return Decision::Stop;
+                    }
+
                     state.num_records += slice.num_records();
-                    state.files.push(slice.physical_hash.clone());
+                    state
+                        .files
+                        .push((slice.physical_hash.clone(), slice.offset_interval.clone()));
+                    state.blocks.insert(hash.clone());
 
                     if let Some(last_records_to_consider) = &state.last_records_to_consider
                         && *last_records_to_consider <= state.num_records
@@ -449,7 +468,53 @@ impl KamuTable {
             .int_err()?;
 
         tracing::debug!(num_slices = final_state.files.len(), "Slices collected");
-        Ok(final_state.files)
+
+        // Slices are collected from newest to oldest
+        let Some(min_offset) = final_state.files.last().map(|(_, i)| i.start) else {
+            return Ok(Vec::new());
+        };
+
+        // Replace slices with the files that soft compactions merged them into. The
+        // merged slice must be fully within the collected range and belong to the
+        // same history
+        let compacted_slices: Vec<_> = read_compacted_data_slices(self.resolved_dataset.as_ref())
+            .await?
+            .slices
+            .into_iter()
+            .filter(|s| {
+                s.start_offset >= min_offset && final_state.blocks.contains(&s.last_block_hash)
+            })
+            .collect();
+
+        let mut files: Vec<_> = final_state
+            .files
+            .into_iter()
+            .filter(|(_, interval)| {
+                !compacted_slices
+                    .iter()
+                    .any(|s| s.start_offset <= interval.start && interval.end <= s.end_offset)
+            })
+            .map(|(hash, interval)| (interval.start, hash))
+            .collect();
+
+        if !compacted_slices.is_empty() {
+            tracing::debug!(
+                num_merged_files = compacted_slices.len(),
+                num_slices = files.len(),
+                "Using merged slices"
+            );
+
+            // Merged files take the place of the slices they replace, keeping the
+            // newest to oldest order
+            files.extend(
+                compacted_slices
+                    .into_iter()
+                    .map(|s| (s.start_offset, s.physical_hash)),
+            );
+            files.sort_by(|(a, _), (b, _)| b.cmp(a));
+        }
+
+        Ok(files.into_iter().map(|(_, hash)| hash).collect())
     }
 }
 
@@ -33,6 +33,7 @@ pub struct QueryServiceImpl {
     dataset_registry: Arc<dyn DatasetRegistry>,
     object_store_registry: Arc<dyn ObjectStoreRegistry>,
     dataset_action_authorizer: Arc<dyn DatasetActionAuthorizer>,
+    engine_registry_config: Arc<EngineRegistryConfig>,
 }
 
 #[component(pub)]
@@ -42,11 +43,13 @@ impl QueryServiceImpl {
         dataset_registry: Arc<dyn DatasetRegistry>,
         object_store_registry: Arc<dyn ObjectStoreRegistry>,
         dataset_action_authorizer: Arc<dyn DatasetActionAuthorizer>,
+        engine_registry_config: Option<Arc<EngineRegistryConfig>>,
     ) -> Self {
         Self {
             dataset_registry,
             object_store_registry,
             dataset_action_authorizer,
+            engine_registry_config: engine_registry_config.unwrap_or_default(),
         }
     }
 
@@ -209,7 +212,8 @@ impl QueryServiceImpl {
     async fn single_dataset(
         &self,
         dataset_ref: &odf::DatasetRef,
-        last_records_to_consider: Option<u64>,
+        block_hash: Option<odf::Multihash>,
+        hints: DatasetQueryHints,
     ) -> Result<(ResolvedDataset, DataFrame), QueryError> {
         let resolved_dataset = self.resolve_dataset(dataset_ref).await?;
 
@@ -219,10 +223,8 @@ impl QueryServiceImpl {
                     resolved_dataset.get_id().clone(),
                     QueryOptionsDataset {
                         alias: resolved_dataset.get_alias().to_string(),
-                        block_hash: None,
-                        hints: Some(DatasetQueryHints {
-                            last_records_to_consider,
-                        }),
+                        block_hash,
+                        hints: Some(hints),
                     },
                 )]),
             })
@@ -237,6 +239,21 @@ impl QueryServiceImpl {
         Ok((resolved_dataset, df))
     }
 
+    async fn get_vocab(
+        &self,
+        resolved_dataset: &ResolvedDataset,
+    ) -> Result<odf::metadata::DatasetVocabulary, QueryError> {
+        use odf::dataset::MetadataChainExt;
+        Ok(resolved_dataset
+            .as_metadata_chain()
+            .accept_one(odf::dataset::SearchSetVocabVisitor::new())
+            .await
+            .int_err()?
+            .into_event()
+            .unwrap_or_default()
+            .into())
+    }
+
     #[tracing::instrument(level = "debug", skip_all)]
     async fn get_schema_impl(
         &self,
@@ -347,7 +364,16 @@ impl QueryService for QueryServiceImpl {
         skip: u64,
         limit: u64,
     ) -> Result<DataFrame, QueryError> {
-        let (resolved_dataset, df) = self.single_dataset(dataset_ref, Some(skip + limit)).await?;
+        let (resolved_dataset, df) = self
+            .single_dataset(
+                dataset_ref,
+                None,
+                DatasetQueryHints {
+                    last_records_to_consider: Some(skip + limit),
+                    ..Default::default()
+                },
+            )
+            .await?;
 
         // Our custom catalog provider resolves schemas lazily, so the dataset will be
         // found even if it's empty and its schema will be empty, but we decide not to
@@ -358,15 +384,7 @@ impl QueryService for QueryServiceImpl {
             })?;
         }
 
-        use odf::dataset::MetadataChainExt;
-        let vocab: odf::metadata::DatasetVocabulary = resolved_dataset
-            .as_metadata_chain()
-            .accept_one(odf::dataset::SearchSetVocabVisitor::new())
-            .await
-            .int_err()?
-            .into_event()
-            .unwrap_or_default()
-            .into();
+        let vocab = self.get_vocab(&resolved_dataset).await?;
 
         let df = df
             .sort(vec![
@@ -383,6 +401,57 @@ impl QueryService for QueryServiceImpl {
         Ok(df)
     }
 
+    #[tracing::instrument(
+        level = "info",
+        name = "get_changes",
+        skip_all,
+        fields(%dataset_ref, ?since_offset, ?as_of, %limit)
+    )]
+    async fn get_changes(
+        &self,
+        dataset_ref: &odf::DatasetRef,
+        since_offset: Option<u64>,
+        as_of: Option<odf::Multihash>,
+        limit: u64,
+    ) -> Result<DataFrame, QueryError> {
+        let (resolved_dataset, df) = self
+            .single_dataset(
+                dataset_ref,
+                as_of,
+                DatasetQueryHints {
+                    since_offset,
+                    ..Default::default()
+                },
+            )
+            .await?;
+
+        // See comment in `tail()`
+        if df.schema().fields().is_empty() {
+            Err(DatasetSchemaNotAvailableError {
+                dataset_ref: dataset_ref.clone(),
+            })?;
+        }
+
+        let vocab = self.get_vocab(&resolved_dataset).await?;
+
+        let df = if let Some(since_offset) = since_offset {
+            df.filter(
+                col(Column::from_name(&vocab.offset_column))
+                    .gt(lit(i64::try_from(since_offset).unwrap())),
+            )?
+        } else {
+            df
+        };
+
+        let df = df
+            .sort(vec![
+                col(Column::from_name(&vocab.offset_column)).sort(true, false)
+            ])?
+            .limit(0, Some(usize::try_from(limit).unwrap()))?;
+
+        Ok(df)
+    }
+
     #[tracing::instrument(level = "info", skip_all)]
     async fn sql_statement(
         &self,
@@ -441,12 +510,14 @@ impl QueryService for QueryServiceImpl {
     #[tracing::instrument(level = "info", skip_all, fields(%dataset_ref))]
     async fn get_data(&self, dataset_ref: &odf::DatasetRef) -> Result<DataFrame, QueryError> {
         // TODO: PERF: Limit push-down opportunity
-        let (_dataset, df) = self.single_dataset(dataset_ref, None).await?;
+        let (_dataset, df) = self
+            .single_dataset(dataset_ref, None, DatasetQueryHints::default())
+            .await?;
         Ok(df)
     }
 
     async fn get_known_engines(&self) -> Result<Vec<EngineDesc>, InternalError> {
-        Ok(vec![
+        let builtin_engines = [
             EngineDesc {
                 name: "Spark".to_string(),
                 dialect: QueryDialect::SqlSpark,
@@ -467,7 +538,20 @@ impl QueryService for QueryServiceImpl {
                 dialect: QueryDialect::SqlRisingWave,
                 latest_image: docker_images::RISINGWAVE.to_string(),
             },
-        ])
+        ];
+
+        let custom_engines = self
+            .engine_registry_config
+            .custom_engines
+            .iter()
+            .filter(|spec| !spec.shadows_builtin_engine())
+            .map(|spec| EngineDesc {
+                name: spec.name.clone(),
+                dialect: spec.dialect,
+                latest_image: spec.image.clone(),
+            });
+
+        Ok(builtin_engines.into_iter().chain(custom_engines).collect())
     }
 }
 
@@ -18,21 +18,27 @@ use kamu_core::utils::metadata_chain_comparator::{
     NullCompareChainsListener,
 };
 use kamu_core::{
+    DatasetPullStatuses,
     DatasetPushStatuses,
     DatasetRegistry,
+    PullStatus,
     PushStatus,
     RemoteAliasKind,
     RemoteAliasesRegistry,
+    RemoteRepositoryRegistry,
     RemoteStatusService,
     StatusCheckError,
 };
 
+use crate::resolve_remote_dataset_url;
+
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
 
 pub struct RemoteStatusServiceImpl {
     dataset_registry: Arc<dyn DatasetRegistry>,
     dataset_factory: Arc<dyn odf::dataset::DatasetFactory>,
     remote_alias_reg: Arc<dyn RemoteAliasesRegistry>,
+    remote_repo_reg: Arc<dyn RemoteRepositoryRegistry>,
 }
 
 #[component(pub)]
@@ -42,12 +48,54 @@ impl RemoteStatusServiceImpl {
         dataset_registry: Arc<dyn DatasetRegistry>,
         dataset_factory: Arc<dyn odf::dataset::DatasetFactory>,
         remote_alias_reg: Arc<dyn RemoteAliasesRegistry>,
+        remote_repo_reg: Arc<dyn RemoteRepositoryRegistry>,
     ) -> Self {
         Self {
             dataset_registry,
             dataset_factory,
             remote_alias_reg,
+            remote_repo_reg,
+        }
+    }
+
+    async fn check_statuses<S, F>(
+        &self,
+        dataset_handle: &odf::DatasetHandle,
+        kind: RemoteAliasKind,
+        make_status: F,
+    ) -> Result<Vec<S>, InternalError>
+    where
+        F: Fn(odf::DatasetRefRemote, Result<CompareChainsResult, StatusCheckError>) -> S + Send,
+    {
+        let lhs_ds = self
+            .dataset_registry
+            .get_dataset_by_handle(dataset_handle)
+            .await;
+        let lhs_chain = lhs_ds.as_metadata_chain();
+        let lhs_head = lhs_chain
+            .resolve_ref(&odf::BlockRef::Head)
+            .await
+            .int_err()?;
+
+        let aliases = self
+            .remote_alias_reg
+            .get_remote_aliases(dataset_handle)
+            .await
+            .int_err()?;
+        let kind_aliases: Vec<&odf::DatasetRefRemote> = aliases.get_by_kind(kind).collect();
+
+        tracing::debug!(?kind, aliases = ?kind_aliases, "Fetched dataset remote aliases");
+
+        let mut statuses = vec![];
+
+        for alias in kind_aliases {
+            statuses.push(make_status(
+                alias.clone(),
+                self.status(lhs_chain, &lhs_head, alias).await,
+            ));
         }
+
+        Ok(statuses)
     }
 
     async fn status(
@@ -56,10 +104,8 @@ impl RemoteStatusServiceImpl {
         lhs_head: &odf::Multihash,
         alias: &odf::DatasetRefRemote,
     ) -> Result<CompareChainsResult, StatusCheckError> {
-        let url = alias.url().ok_or(StatusCheckError::Internal(
-            "Couldn't figure out remote dataset location".int_err(),
-        ))?;
-        let Ok(rhs_ds) = self.dataset_factory.get_dataset(url, false).await else {
+        let url = resolve_remote_dataset_url(self.remote_repo_reg.as_ref(), alias).int_err()?;
+        let Ok(rhs_ds) = self.dataset_factory.get_dataset(&url, false).await else {
             return Err(StatusCheckError::Internal(
                 "Couldn't figure out remote dataset location".int_err(),
             ));
@@ -96,37 +142,40 @@ impl RemoteStatusService for RemoteStatusServiceImpl {
         &self,
         dataset_handle: &odf::DatasetHandle,
     ) -> Result<DatasetPushStatuses, InternalError> {
-        let lhs_ds = self
-            .dataset_registry
-            .get_dataset_by_handle(dataset_handle)
-            .await;
-        let lhs_chain = lhs_ds.as_metadata_chain();
-        let lhs_head = lhs_chain
-            .resolve_ref(&odf::BlockRef::Head)
-            .await
-            .int_err()?;
-
-        let aliases = self
-            .remote_alias_reg
-            .get_remote_aliases(dataset_handle)
-            .await
-            .int_err()?;
-        let push_aliases: Vec<&odf::DatasetRefRemote> =
-            aliases.get_by_kind(RemoteAliasKind::Push).collect();
+        let statuses = self
+            .check_statuses(
+                dataset_handle,
+                RemoteAliasKind::Push,
+                |remote, check_result| PushStatus {
+                    remote,
+                    check_result,
+                },
+            )
+            .await?;
 
-        tracing::debug!(?push_aliases, "Fetched dataset remote push aliases");
+        tracing::debug!(?statuses, "Determined push alias statuses");
 
-        let mut statuses = vec![];
+        Ok(DatasetPushStatuses { statuses })
+    }
 
-        for alias in push_aliases {
-            statuses.push(PushStatus {
-                remote: alias.clone(),
-                check_result: self.status(lhs_chain, &lhs_head, alias).await,
-            });
-        }
+    #[tracing::instrument(level = "debug", skip_all, fields(%dataset_handle))]
+    async fn check_pull_remotes_status(
+        &self,
+        dataset_handle: &odf::DatasetHandle,
+    ) -> Result<DatasetPullStatuses, InternalError> {
+        let statuses = self
+            .check_statuses(
+                dataset_handle,
+                RemoteAliasKind::Pull,
+                |remote, check_result| PullStatus {
+                    remote,
+                    check_result,
+                },
+            )
+            .await?;
 
-        tracing::debug!(?statuses, "Determined push alias statuses");
+        tracing::debug!(?statuses, "Determined pull alias statuses");
 
-        Ok(DatasetPushStatuses { statuses })
+        Ok(DatasetPullStatuses { statuses })
     }
 }
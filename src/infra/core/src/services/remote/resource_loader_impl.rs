@@ -15,6 +15,8 @@ use kamu_core::*;
 use odf::serde::yaml::YamlDatasetSnapshotDeserializer;
 use url::Url;
 
+use crate::ingest::expand_snapshot_template;
+
 #[component]
 #[interface(dyn ResourceLoader)]
 pub struct ResourceLoaderImpl {}
@@ -28,18 +30,20 @@ impl ResourceLoaderImpl {
         &self,
         url: &Url,
     ) -> Result<odf::DatasetSnapshot, ResourceError> {
+        use odf::serde::DatasetSnapshotDeserializer;
+        let bytes = self.read_from_http(url).await?;
+        YamlDatasetSnapshotDeserializer
+            .read_manifest(&bytes)
+            .map_err(ResourceError::serde)
+    }
+
+    async fn read_from_http(&self, url: &Url) -> Result<Vec<u8>, ResourceError> {
         match reqwest::get(url.clone())
             .await
             .int_err()?
             .error_for_status()
         {
-            Ok(response) => {
-                use odf::serde::DatasetSnapshotDeserializer;
-                let bytes = response.bytes().await.int_err()?;
-                YamlDatasetSnapshotDeserializer
-                    .read_manifest(&bytes)
-                    .map_err(ResourceError::serde)
-            }
+            Ok(response) => Ok(response.bytes().await.int_err()?.to_vec()),
             Err(err) if err.status() == Some(http::StatusCode::NOT_FOUND) => {
                 Err(ResourceError::not_found(url.as_str().to_owned(), None))
             }
@@ -49,6 +53,24 @@ impl ResourceLoaderImpl {
             )),
         }
     }
+
+    async fn read_from_ref(&self, sref: &str) -> Result<Vec<u8>, ResourceError> {
+        let path = Path::new(sref);
+        if !path.exists()
+            && let Ok(url) = Url::parse(sref)
+        {
+            match url.scheme() {
+                "file" => {
+                    let path = url.to_file_path().expect("Invalid file URL");
+                    Ok(std::fs::read(path).int_err()?)
+                }
+                "http" | "https" => self.read_from_http(&url).await,
+                _ => unimplemented!("Unsupported scheme {}", url.scheme()),
+            }
+        } else {
+            Ok(std::fs::read(path).int_err()?)
+        }
+    }
 }
 
 #[async_trait::async_trait]
@@ -92,4 +114,27 @@ impl ResourceLoader for ResourceLoaderImpl {
             self.load_dataset_snapshot_from_path(path).await
         }
     }
+
+    async fn load_dataset_snapshots_from_template(
+        &self,
+        sref: &str,
+        vars: &[TemplateVars],
+    ) -> Result<Vec<odf::DatasetSnapshot>, ResourceError> {
+        let buffer = self.read_from_ref(sref).await?;
+        let template = std::str::from_utf8(&buffer).map_err(ResourceError::serde)?;
+
+        let mut snapshots = Vec::new();
+        for (index, vars) in vars.iter().enumerate() {
+            let manifests = expand_snapshot_template(template, vars)
+                .map_err(|e| ResourceError::template(index, e))?;
+
+            snapshots.extend(
+                YamlDatasetSnapshotDeserializer
+                    .read_manifests(manifests.as_bytes())
+                    .map_err(ResourceError::serde)?,
+            );
+        }
+
+        Ok(snapshots)
+    }
 }
@@ -0,0 +1,510 @@
+// Copyright Kamu Data, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use chrono::{DateTime, Utc};
+use datafusion::arrow::array::RecordBatch;
+use datafusion::arrow::datatypes::SchemaRef;
+use datafusion::logical_expr::SortExpr;
+use datafusion::prelude::*;
+use datafusion::scalar::ScalarValue;
+use dill::{component, interface};
+use file_utils::OwnedFile;
+use internal_error::{ErrorIntoInternal, InternalError, ResultIntoInternal};
+use kamu_core::*;
+use kamu_ingest_datafusion::{DataWriterDataFusion, MergeStrategy, MergeStrategySnapshot};
+use odf::utils::data::dataframe_ext::DataFrameExt;
+use random_names::get_random_name;
+
+use crate::{new_session_context, DataExpectationsChecker};
+
+type Op = odf::metadata::OperationType;
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+pub struct ReprocessExecutorImpl {
+    engine_provisioner: Arc<dyn EngineProvisioner>,
+    object_store_registry: Arc<dyn ObjectStoreRegistry>,
+    run_info_dir: Arc<RunInfoDir>,
+}
+
+#[component(pub)]
+#[interface(dyn ReprocessExecutor)]
+impl ReprocessExecutorImpl {
+    pub fn new(
+        engine_provisioner: Arc<dyn EngineProvisioner>,
+        object_store_registry: Arc<dyn ObjectStoreRegistry>,
+        run_info_dir: Arc<RunInfoDir>,
+    ) -> Self {
+        Self {
+            engine_provisioner,
+            object_store_registry,
+            run_info_dir,
+        }
+    }
+
+    fn create_run_reprocess_dir(&self) -> Result<PathBuf, InternalError> {
+        let reprocess_dir_path = self
+            .run_info_dir
+            .join(get_random_name(Some("reprocess-"), 10));
+        std::fs::create_dir_all(&reprocess_dir_path).int_err()?;
+        Ok(reprocess_dir_path)
+    }
+
+    async fn read_data_slices(
+        ctx: &SessionContext,
+        target: &ResolvedDataset,
+        data_slices: &[odf::Multihash],
+        schema: &SchemaRef,
+    ) -> Result<DataFrame, InternalError> {
+        if data_slices.is_empty() {
+            return ctx
+                .read_batch(RecordBatch::new_empty(schema.clone()))
+                .int_err();
+        }
+
+        let data_repo = target.as_data_repo();
+
+        use futures::StreamExt;
+        let data_paths: Vec<_> = futures::stream::iter(data_slices)
+            .then(|hash| data_repo.get_internal_url(hash))
+            .map(|url| url.to_string())
+            .collect()
+            .await;
+
+        ctx.read_parquet(
+            data_paths,
+            ParquetReadOptions {
+                file_extension: "",
+                ..Default::default()
+            },
+        )
+        .await
+        .int_err()
+    }
+
+    /// Compares records keyed by primary key, emitting correction pairs for
+    /// modified records, retractions for removed ones, and appends for new
+    /// ones
+    fn diff_by_primary_key(
+        vocab: &odf::metadata::DatasetVocabulary,
+        primary_key: &[String],
+        old: DataFrame,
+        new: DataFrame,
+    ) -> Result<(DataFrame, Vec<SortExpr>), InternalError> {
+        let merge_strategy = MergeStrategySnapshot::new(
+            vocab.clone(),
+            odf::metadata::MergeStrategySnapshot {
+                primary_key: primary_key.to_vec(),
+                compare_columns: None,
+            },
+        );
+
+        let new_state = merge_strategy
+            .project(new)?
+            .without_columns(&[
+                &vocab.offset_column,
+                &vocab.operation_type_column,
+                &vocab.system_time_column,
+            ])
+            .int_err()?;
+
+        let changes = merge_strategy.merge(Some(old), new_state).int_err()?;
+
+        Ok((changes, merge_strategy.sort_order()))
+    }
+
+    /// Compares entire records, retracting the old records that are no longer
+    /// produced and appending the ones that were not produced before
+    fn diff_by_records(
+        vocab: &odf::metadata::DatasetVocabulary,
+        old: DataFrame,
+        new: DataFrame,
+    ) -> Result<(DataFrame, Vec<SortExpr>), InternalError> {
+        let old_state = Self::net_records(vocab, old)?;
+        let new_state = Self::net_records(vocab, new)?;
+
+        let retracted = old_state
+            .clone()
+            .except(new_state.clone())
+            .int_err()?
+            .with_column(&vocab.operation_type_column, lit(Op::Retract as i32))
+            .int_err()?;
+
+        let appended = new_state
+            .except(old_state)
+            .int_err()?
+            .with_column(&vocab.operation_type_column, lit(Op::Append as i32))
+            .int_err()?;
+
+        let changes = retracted.union(appended).int_err()?;
+
+        // Retractions go first so that consumers never observe both versions of a
+        // record at the same time
+        let sort_order = vec![
+            col(Column::from_name(&vocab.operation_type_column)).sort(false, true),
+            col(Column::from_name(&vocab.event_time_column)).sort(true, true),
+        ];
+
+        Ok((changes, sort_order))
+    }
+
+    /// Reduces a ledger to the records it currently holds, stripped of the
+    /// system columns
+    fn net_records(
+        vocab: &odf::metadata::DatasetVocabulary,
+        ledger: DataFrame,
+    ) -> Result<DataFrame, InternalError> {
+        let data_columns: Vec<_> = ledger
+            .schema()
+            .fields()
+            .iter()
+            .map(|f| f.name().clone())
+            .filter(|n| {
+                *n != vocab.offset_column
+                    && *n != vocab.operation_type_column
+                    && *n != vocab.system_time_column
+            })
+            .collect();
+        let data_columns: Vec<_> = data_columns.iter().map(String::as_str).collect();
+
+        let op = || col(Column::from_name(&vocab.operation_type_column));
+
+        let added = ledger
+            .clone()
+            .filter(
+                op().eq(lit(Op::Append as i32))
+                    .or(op().eq(lit(Op::CorrectTo as i32))),
+            )
+            .int_err()?
+            .select_columns(&data_columns)
+            .int_err()?;
+
+        let removed = ledger
+            .filter(
+                op().eq(lit(Op::Retract as i32))
+                    .or(op().eq(lit(Op::CorrectFrom as i32))),
+            )
+            .int_err()?
+            .select_columns(&data_columns)
+            .int_err()?;
+
+        added.except(removed).int_err()
+    }
+
+    fn with_system_columns(
+        changes: DataFrame,
+        sort_order: Vec<SortExpr>,
+        vocab: &odf::metadata::DatasetVocabulary,
+        schema: &SchemaRef,
+        system_time: DateTime<Utc>,
+        start_offset: u64,
+    ) -> Result<DataFrame, InternalError> {
+        let data_type = |name: &str| -> Result<_, InternalError> {
+            Ok(schema.field_with_name(name).int_err()?.data_type().clone())
+        };
+
+        let df = changes
+            .with_column(
+                &vocab.system_time_column,
+                cast(
+                    Expr::Literal(ScalarValue::TimestampMillisecond(
+                        Some(system_time.timestamp_millis()),
+                        Some("UTC".into()),
+                    )),
+                    data_type(&vocab.system_time_column)?,
+                ),
+            )
+            .int_err()?
+            .with_column(
+                &vocab.operation_type_column,
+                cast(
+                    col(Column::from_name(&vocab.operation_type_column)),
+                    data_type(&vocab.operation_type_column)?,
+                ),
+            )
+            .int_err()?
+            .with_column(
+                &vocab.offset_column,
+                datafusion::functions_window::row_number::row_number()
+                    .order_by(sort_order)
+                    .partition_by(vec![lit(1)])
+                    .build()
+                    .int_err()?,
+            )
+            .int_err()?
+            .with_column(
+                &vocab.offset_column,
+                cast(
+                    col(Column::from_name(&vocab.offset_column))
+                        + lit(i64::try_from(start_offset).int_err()? - 1),
+                    data_type(&vocab.offset_column)?,
+                ),
+            )
+            .int_err()?;
+
+        let columns: Vec<_> = schema.fields().iter().map(|f| f.name().as_str()).collect();
+
+        df.select_columns(&columns)
+            .int_err()?
+            .sort(vec![
+                col(Column::from_name(&vocab.offset_column)).sort(true, true)
+            ])
+            .int_err()
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[async_trait::async_trait]
+impl ReprocessExecutor for ReprocessExecutorImpl {
+    #[tracing::instrument(level = "info", skip_all, fields(target=%target.get_handle()))]
+    async fn execute(
+        &self,
+        target: ResolvedDataset,
+        plan: ReprocessPlan,
+    ) -> Result<ReprocessResult, ReprocessExecutionError> {
+        tracing::info!(?plan, "Reprocessing dataset");
+
+        let prev_state = plan.prev_state;
+        let transform_changed = plan.new_set_transform.is_some();
+
+        // Recompute and stage the corrections before committing anything, so that
+        // a failing transform leaves the dataset intact
+        let staged = if let Some(request) = plan.request {
+            let Some(schema) = request.schema.clone() else {
+                return Err(format!(
+                    "Output schema of dataset {} is not known",
+                    target.get_alias()
+                )
+                .int_err()
+                .into());
+            };
+
+            let engine = self
+                .engine_provisioner
+                .provision_engine(
+                    match request.transform {
+                        odf::metadata::Transform::Sql(ref sql) => &sql.engine,
+                    },
+                    None,
+                )
+                .await?;
+
+            let response = engine
+                .execute_transform(request, &plan.datasets_map)
+                .await?;
+
+            if let Some(new_schema) = &response.output_schema {
+                DataWriterDataFusion::validate_output_schema_equivalence(&schema, new_schema)
+                    .int_err()?;
+            }
+
+            let ctx = new_session_context(self.object_store_registry.clone());
+
+            let old =
+                Self::read_data_slices(&ctx, &target, &plan.replaced_data_slices, &schema).await?;
+            let new = if let Some(new_data) = &response.new_data {
+                ctx.read_parquet(
+                    new_data.as_path().to_str().unwrap(),
+                    ParquetReadOptions {
+                        file_extension: "",
+                        ..Default::default()
+                    },
+                )
+                .await
+                .int_err()?
+            } else {
+                ctx.read_batch(RecordBatch::new_empty(schema.clone()))
+                    .int_err()?
+            };
+
+            // Derivative data cannot be partially committed, so quarantine is not allowed
+            let expectations_check =
+                DataExpectationsChecker::check(&target, Some(new.clone()), false).await?;
+
+            let (changes, sort_order) = if plan.primary_key.is_empty() {
+                Self::diff_by_records(&prev_state.vocab, old, new)?
+            } else {
+                Self::diff_by_primary_key(&prev_state.vocab, &plan.primary_key, old, new)?
+            };
+
+            let start_offset = prev_state.offset.map_or(0, |o| o + 1);
+            let changes = Self::with_system_columns(
+                changes,
+                sort_order,
+                &prev_state.vocab,
+                &schema,
+                plan.system_time,
+                start_offset,
+            )?;
+
+            let num_records = u64::try_from(changes.clone().count().await.int_err()?).unwrap();
+
+            let new_data = if num_records == 0 {
+                None
+            } else {
+                // FIXME: The .parquet extension is currently necessary for DataFusion to
+                // respect the single-file output
+                // See: https://github.com/apache/datafusion/issues/13323
+                let data_path = self.create_run_reprocess_dir()?.join("data.parquet");
+                changes
+                    .write_parquet(
+                        data_path.to_str().unwrap(),
+                        datafusion::dataframe::DataFrameWriteOptions::new()
+                            .with_single_file_output(true),
+                        None,
+                    )
+                    .await
+                    .int_err()?;
+
+                Some((
+                    OwnedFile::new(data_path),
+                    odf::metadata::OffsetInterval {
+                        start: start_offset,
+                        end: start_offset + num_records - 1,
+                    },
+                ))
+            };
+
+            // Checkpoint of the unchanged transform remains valid as it reflects the
+            // same input positions
+            let new_checkpoint = match response.new_checkpoint {
+                Some(checkpoint) => Some(odf::dataset::CheckpointRef::New(checkpoint)),
+                None if !transform_changed => prev_state
+                    .checkpoint
+                    .clone()
+                    .map(odf::dataset::CheckpointRef::Existed),
+                None => None,
+            };
+
+            Some((new_data, new_checkpoint, num_records, expectations_check))
+        } else {
+            None
+        };
+
+        // Blocks are appended without moving the HEAD, which is updated once both
+        // are in place, so that a failure leaves the dataset intact
+        let old_head = prev_state.head;
+        let mut new_head = old_head.clone();
+
+        if let Some(set_transform) = plan.new_set_transform {
+            new_head = target
+                .commit_event(
+                    set_transform.into(),
+                    odf::dataset::CommitOpts {
+                        block_ref: &odf::BlockRef::Head,
+                        system_time: Some(plan.system_time),
+                        prev_block_hash: Some(Some(&new_head)),
+                        check_object_refs: false,
+                        update_block_ref: false,
+                    },
+                )
+                .await?
+                .new_head;
+        }
+
+        let mut num_records = 0;
+        let mut expectations_check = None;
+
+        if let Some((new_data, new_checkpoint, staged_num_records, staged_expectations_check)) =
+            staged
+        {
+            let (new_data, new_offset_interval) = new_data.unzip();
+
+            let params = odf::dataset::ExecuteTransformParams {
+                query_inputs: prev_state.query_inputs,
+                prev_checkpoint: prev_state.checkpoint,
+                prev_offset: prev_state.offset,
+                new_offset_interval,
+                new_watermark: prev_state.watermark,
+            };
+
+            match target
+                .commit_execute_transform(
+                    params,
+                    new_data,
+                    new_checkpoint,
+                    odf::dataset::CommitOpts {
+                        block_ref: &odf::BlockRef::Head,
+                        system_time: Some(plan.system_time),
+                        prev_block_hash: Some(Some(&new_head)),
+                        check_object_refs: true,
+                        update_block_ref: false,
+                    },
+                )
+                .await
+            {
+                Ok(res) => {
+                    new_head = res.new_head;
+                    num_records = staged_num_records;
+                    Ok(())
+                }
+                Err(odf::dataset::CommitError::MetadataAppendError(
+                    odf::dataset::AppendError::InvalidBlock(
+                        odf::dataset::AppendValidationError::NoOpEvent(_),
+                    ),
+                )) => Ok(()),
+                Err(err) => Err(err),
+            }?;
+
+            expectations_check = Some(staged_expectations_check);
+        }
+
+        if new_head == old_head {
+            tracing::info!("Recomputed data matches the existing one");
+            return Ok(ReprocessResult::UpToDate);
+        }
+
+        if let Some(expectations_check) = expectations_check {
+            if let Some(report_event) = expectations_check
+                .into_report_event(&target, &new_head)
+                .await?
+            {
+                new_head = target
+                    .commit_event(
+                        report_event,
+                        odf::dataset::CommitOpts {
+                            block_ref: &odf::BlockRef::Head,
+                            system_time: Some(plan.system_time),
+                            prev_block_hash: Some(Some(&new_head)),
+                            check_object_refs: false,
+                            update_block_ref: false,
+                        },
+                    )
+                    .await?
+                    .new_head;
+            }
+        }
+
+        target
+            .as_metadata_chain()
+            .set_ref(
+                &odf::BlockRef::Head,
+                &new_head,
+                odf::dataset::SetRefOpts {
+                    validate_block_present: true,
+                    check_ref_is: Some(Some(&old_head)),
+                },
+            )
+            .await?;
+
+        tracing::info!(%old_head, %new_head, num_records, "Reprocessing committed");
+
+        Ok(ReprocessResult::Updated {
+            old_head,
+            new_head,
+            num_records,
+        })
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
@@ -0,0 +1,265 @@
+// Copyright Kamu Data, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::sync::Arc;
+
+use dill::*;
+use engine::TransformRequestExt;
+use internal_error::ResultIntoInternal;
+use kamu_core::*;
+use random_names::get_random_name;
+use time_source::SystemTimeSource;
+
+use crate::get_transform_input_from_query_input;
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+pub struct ReprocessPlannerImpl {
+    dataset_registry: Arc<dyn DatasetRegistry>,
+    time_source: Arc<dyn SystemTimeSource>,
+}
+
+#[component(pub)]
+#[interface(dyn ReprocessPlanner)]
+impl ReprocessPlannerImpl {
+    pub fn new(
+        dataset_registry: Arc<dyn DatasetRegistry>,
+        time_source: Arc<dyn SystemTimeSource>,
+    ) -> Self {
+        Self {
+            dataset_registry,
+            time_source,
+        }
+    }
+
+    fn normalize_transform(mut transform: odf::metadata::Transform) -> odf::metadata::Transform {
+        let odf::metadata::Transform::Sql(sql) = &mut transform;
+        if let Some(query) = sql.query.take()
+            && sql.queries.is_none()
+        {
+            sql.queries = Some(vec![odf::metadata::SqlQueryStep { alias: None, query }]);
+        }
+        transform
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[async_trait::async_trait]
+impl ReprocessPlanner for ReprocessPlannerImpl {
+    #[tracing::instrument(level = "debug", skip_all, fields(target=%target.get_handle(), ?options))]
+    async fn plan_reprocess(
+        &self,
+        target: ResolvedDataset,
+        options: ReprocessOptions,
+    ) -> Result<ReprocessPlan, ReprocessPlanningError> {
+        let metadata_chain = target.as_metadata_chain();
+        let head = metadata_chain
+            .resolve_ref(&odf::BlockRef::Head)
+            .await
+            .int_err()?;
+
+        // TODO: PERF: Reprocessing needs the entire history of transform blocks, so we
+        // scan the whole chain
+        let mut source: Option<odf::metadata::SetTransform> = None;
+        let mut set_vocab: Option<odf::metadata::SetVocab> = None;
+        let mut set_data_schema: Option<odf::metadata::SetDataSchema> = None;
+        let mut blocks = Vec::new();
+        {
+            use futures::TryStreamExt;
+            use odf::dataset::MetadataChainExt;
+
+            let mut stream = metadata_chain.iter_blocks_interval(&head, None, false);
+            while let Some((block_hash, block)) = stream.try_next().await.int_err()? {
+                match block.event {
+                    odf::MetadataEvent::SetTransform(e) if source.is_none() => {
+                        source = Some(e);
+                    }
+                    odf::MetadataEvent::SetVocab(e) if set_vocab.is_none() => {
+                        set_vocab = Some(e);
+                    }
+                    odf::MetadataEvent::SetDataSchema(e) if set_data_schema.is_none() => {
+                        set_data_schema = Some(e);
+                    }
+                    odf::MetadataEvent::ExecuteTransform(e) => blocks.push((block_hash, e)),
+                    _ => {}
+                }
+            }
+        }
+        blocks.reverse();
+
+        let Some(source) = source else {
+            return Err(TransformNotDefinedError {}.into());
+        };
+
+        let start_index = match &options.start {
+            None => Some(0),
+            Some(ReprocessStart::Block(start_hash)) => blocks
+                .iter()
+                .position(|(block_hash, _)| block_hash == start_hash),
+            Some(ReprocessStart::Offset(offset)) => blocks.iter().position(|(_, e)| {
+                e.new_data
+                    .as_ref()
+                    .is_some_and(|d| d.offset_interval.end >= *offset)
+            }),
+        };
+        let Some(start_index) = start_index else {
+            return Err(ReprocessStartNotFoundError {
+                start: options.start.unwrap(),
+            }
+            .into());
+        };
+
+        let new_set_transform = options
+            .new_transform
+            .map(Self::normalize_transform)
+            .filter(|t| *t != source.transform)
+            .map(|transform| odf::metadata::SetTransform {
+                inputs: source.inputs.clone(),
+                transform,
+            });
+
+        let vocab: odf::metadata::DatasetVocabulary = set_vocab.unwrap_or_default().into();
+        let schema = set_data_schema
+            .as_ref()
+            .map(odf::metadata::SetDataSchema::schema_as_arrow)
+            .transpose()
+            .int_err()?;
+
+        let last = blocks.last().map(|(_, e)| e);
+        let prev_state = ReprocessPrevState {
+            head: head.clone(),
+            vocab: vocab.clone(),
+            offset: last.and_then(odf::metadata::ExecuteTransform::last_offset),
+            checkpoint: last
+                .and_then(|e| e.new_checkpoint.as_ref())
+                .map(|c| c.physical_hash.clone()),
+            watermark: last.and_then(|e| e.new_watermark),
+            query_inputs: last
+                .map(|e| {
+                    e.query_inputs
+                        .iter()
+                        .map(|i| odf::metadata::ExecuteTransformInput {
+                            dataset_id: i.dataset_id.clone(),
+                            prev_block_hash: i.last_block_hash().cloned(),
+                            new_block_hash: None,
+                            prev_offset: i.last_offset(),
+                            new_offset: None,
+                        })
+                        .collect()
+                })
+                .unwrap_or_default(),
+        };
+
+        let affected_blocks = &blocks[start_index..];
+        let replaced_data_slices = affected_blocks
+            .iter()
+            .filter_map(|(_, e)| e.new_data.as_ref().map(|d| d.physical_hash.clone()))
+            .collect();
+
+        let mut datasets_map = ResolvedDatasetsMap::default();
+        datasets_map.register(target.clone());
+
+        let system_time = self.time_source.now();
+
+        // Nothing was computed yet, or the transform never produced any output
+        let (Some((_, first)), Some(last), Some(schema)) = (affected_blocks.first(), last, schema)
+        else {
+            return Ok(ReprocessPlan {
+                new_set_transform,
+                request: None,
+                datasets_map,
+                replaced_data_slices,
+                primary_key: options.primary_key,
+                system_time,
+                prev_state,
+            });
+        };
+
+        for input_decl in &source.inputs {
+            let hdl = self
+                .dataset_registry
+                .resolve_dataset_handle_by_ref(&input_decl.dataset_ref)
+                .await
+                .int_err()?;
+            let resolved_dataset = self.dataset_registry.get_dataset_by_handle(&hdl).await;
+            datasets_map.register(resolved_dataset);
+        }
+
+        // A single request covers input intervals of all reprocessed blocks
+        let mut inputs = Vec::new();
+        for ((input_decl, first_input), last_input) in source
+            .inputs
+            .iter()
+            .zip(&first.query_inputs)
+            .zip(&last.query_inputs)
+        {
+            let new_block_hash = last_input
+                .last_block_hash()
+                .filter(|h| Some(*h) != first_input.prev_block_hash.as_ref())
+                .cloned();
+            let new_offset = last_input
+                .last_offset()
+                .filter(|o| Some(*o) != first_input.prev_offset);
+
+            let query_input = odf::metadata::ExecuteTransformInput {
+                dataset_id: first_input.dataset_id.clone(),
+                prev_block_hash: first_input.prev_block_hash.clone(),
+                new_block_hash,
+                prev_offset: first_input.prev_offset,
+                new_offset,
+            };
+
+            inputs.push(
+                get_transform_input_from_query_input(
+                    query_input,
+                    input_decl.alias.clone().unwrap(),
+                    None,
+                    &datasets_map,
+                )
+                .await?,
+            );
+        }
+
+        // Checkpoint of the original transform can't be used to resume a different one
+        let prev_checkpoint = if new_set_transform.is_none() {
+            first.prev_checkpoint.clone()
+        } else {
+            None
+        };
+
+        let request = TransformRequestExt {
+            operation_id: get_random_name(None, 10),
+            dataset_handle: target.get_handle().clone(),
+            block_ref: odf::BlockRef::Head,
+            head,
+            transform: new_set_transform
+                .as_ref()
+                .map_or_else(|| source.transform.clone(), |e| e.transform.clone()),
+            system_time,
+            schema: Some(schema),
+            prev_offset: first.prev_offset,
+            inputs,
+            vocab,
+            prev_checkpoint,
+        };
+
+        Ok(ReprocessPlan {
+            new_set_transform,
+            request: Some(request),
+            datasets_map,
+            replaced_data_slices,
+            primary_key: options.primary_key,
+            system_time,
+            prev_state,
+        })
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
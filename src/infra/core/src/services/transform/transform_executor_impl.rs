@@ -9,12 +9,15 @@
 
 use std::sync::Arc;
 
+use datafusion::prelude::{ParquetReadOptions, SessionContext};
 use dill::*;
 use engine::{TransformRequestExt, TransformResponseExt};
 use internal_error::ResultIntoInternal;
 use kamu_core::*;
 use kamu_ingest_datafusion::DataWriterDataFusion;
 
+use crate::DataExpectationsChecker;
+
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
 
 pub struct TransformExecutorImpl {
@@ -111,6 +114,26 @@ impl TransformExecutorImpl {
             tracing::warn!("Engine did not produce a schema. In future this will become an error.");
         };
 
+        // Derivative data cannot be partially committed, so quarantine is not allowed
+        let new_data_df = if let Some(new_data) = &response.new_data {
+            Some(
+                SessionContext::new()
+                    .read_parquet(
+                        new_data.as_path().to_str().unwrap(),
+                        ParquetReadOptions {
+                            file_extension: "",
+                            ..Default::default()
+                        },
+                    )
+                    .await
+                    .int_err()?,
+            )
+        } else {
+            None
+        };
+        let expectations_check =
+            DataExpectationsChecker::check(&resolved_dataset, new_data_df, false).await?;
+
         if let Some(prev_schema) = request.schema {
             // Validate schema
             if let Some(new_schema) = response.output_schema {
@@ -180,6 +203,25 @@ impl TransformExecutorImpl {
             "Commit did not update neither schema nor data"
         );
 
+        if let Some(report_event) = expectations_check
+            .into_report_event(&resolved_dataset, &new_head)
+            .await?
+        {
+            new_head = resolved_dataset
+                .commit_event(
+                    report_event,
+                    odf::dataset::CommitOpts {
+                        block_ref: &request.block_ref,
+                        system_time: Some(request.system_time),
+                        prev_block_hash: Some(Some(&new_head)),
+                        check_object_refs: false,
+                        update_block_ref: true,
+                    },
+                )
+                .await?
+                .new_head;
+        }
+
         Ok(TransformResult::Updated { old_head, new_head })
     }
 }
@@ -306,7 +348,8 @@ impl TransformExecutor for TransformExecutorImpl {
                 TransformExecuteError::EngineError(e) => {
                     VerifyTransformExecuteError::EngineError(e)
                 }
-                TransformExecuteError::CommitError(_) => unreachable!(),
+                TransformExecuteError::CommitError(_)
+                | TransformExecuteError::ExpectationsFailed(_) => unreachable!(),
                 TransformExecuteError::Internal(e) => VerifyTransformExecuteError::Internal(e),
             })?;
 
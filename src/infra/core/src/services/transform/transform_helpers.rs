@@ -13,6 +13,7 @@ use kamu_core::engine::TransformRequestInputExt;
 use kamu_core::{
     InputSchemaNotDefinedError,
     InvalidInputIntervalError,
+    ReprocessPlanningError,
     ResolvedDataset,
     ResolvedDatasetsMap,
     TransformElaborateError,
@@ -295,4 +296,14 @@ impl From<GetTransformInputError> for VerifyTransformPlanError {
     }
 }
 
+impl From<GetTransformInputError> for ReprocessPlanningError {
+    fn from(value: GetTransformInputError) -> Self {
+        match value {
+            GetTransformInputError::InputSchemaNotDefined(e) => Self::InputSchemaNotDefined(e),
+            GetTransformInputError::InvalidInputInterval(e) => Self::InvalidInputInterval(e),
+            GetTransformInputError::Internal(e) => Self::Internal(e),
+        }
+    }
+}
+
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
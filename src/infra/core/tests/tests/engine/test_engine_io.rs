@@ -48,6 +48,7 @@ async fn test_engine_io_common<
 
     let engine_provisioner = Arc::new(EngineProvisionerLocal::new(
         EngineProvisionerLocalConfig::default(),
+        None,
         Arc::new(ContainerRuntime::default()),
         run_info_dir.clone(),
     ));
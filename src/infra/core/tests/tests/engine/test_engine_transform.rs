@@ -232,6 +232,10 @@ struct TestHarness {
 
 impl TestHarness {
     fn new() -> Self {
+        Self::new_with_engine_config(EngineProvisionerLocalConfig::default())
+    }
+
+    fn new_with_engine_config(engine_config: EngineProvisionerLocalConfig) -> Self {
         let tempdir = tempfile::tempdir().unwrap();
         let run_info_dir = tempdir.path().join("run");
         let cache_dir = tempdir.path().join("cache");
@@ -253,7 +257,7 @@ impl TestHarness {
             .bind::<dyn odf::DatasetStorageUnit, odf::dataset::DatasetStorageUnitLocalFs>()
             .bind::<dyn odf::DatasetStorageUnitWriter, odf::dataset::DatasetStorageUnitLocalFs>()
             .add::<DatasetRegistrySoloUnitBridge>()
-            .add_value(EngineProvisionerLocalConfig::default())
+            .add_value(engine_config)
             .add::<EngineProvisionerLocal>()
             .add_value(ObjectStoreRegistryImpl::new(vec![Arc::new(
                 ObjectStoreBuilderLocalFs::new(),
@@ -303,8 +307,14 @@ impl TestHarness {
 // TODO: Remove `test_retractions` flag once RisingWave can handle them without
 // crashing
 async fn test_transform_common(transform: odf::metadata::Transform, test_retractions: bool) {
-    let harness = TestHarness::new();
+    test_transform_common_with_harness(TestHarness::new(), transform, test_retractions).await;
+}
 
+async fn test_transform_common_with_harness(
+    harness: TestHarness,
+    transform: odf::metadata::Transform,
+    test_retractions: bool,
+) {
     ///////////////////////////////////////////////////////////////////////////
     // Root setup
     ///////////////////////////////////////////////////////////////////////////
@@ -638,6 +648,38 @@ async fn test_transform_with_engine_datafusion() {
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
 
+#[test_group::group(containerized, engine, transform, datafusion)]
+#[test_log::test(tokio::test)]
+async fn test_transform_with_engine_datafusion_pooled() {
+    // Subsequent transformations and verification reuse the same container
+    let harness = TestHarness::new_with_engine_config(EngineProvisionerLocalConfig {
+        pool: EnginePoolConfig {
+            max_size: 1,
+            ..Default::default()
+        },
+        ..Default::default()
+    });
+
+    test_transform_common_with_harness(
+        harness,
+        MetadataFactory::transform()
+            .engine("datafusion")
+            .query(
+                "SELECT
+                    op,
+                    event_time,
+                    city,
+                    cast(population * 10 as int) as population_x10
+                FROM root",
+            )
+            .build(),
+        true,
+    )
+    .await;
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
 // See: https://github.com/kamu-data/kamu-cli/issues/599
 #[test_group::group(containerized, engine, transform, risingwave)]
 #[ignore = "#599 Disabled for disk space issues reason"]
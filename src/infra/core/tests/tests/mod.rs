@@ -10,14 +10,20 @@
 mod engine;
 mod ingest;
 mod test_compaction_services_impl;
+mod test_dataset_change_feed_service_impl;
 mod test_dataset_changes_service_impl;
+mod test_dataset_diff_service_impl;
+mod test_dataset_expectations_service_impl;
+mod test_dataset_freshness_service_impl;
 mod test_datasets_filtering;
+mod test_export_service_iceberg;
 mod test_metadata_chain_comparator;
 mod test_object_store_s3;
 mod test_pull_request_planner_impl;
 mod test_push_request_planner_impl;
 mod test_query_service_impl;
 mod test_remote_status_service;
+mod test_reprocess_services_impl;
 mod test_reset_services_impl;
 mod test_resource_loader_impl;
 mod test_schema_utils;
@@ -8,11 +8,13 @@
 // by the Apache License, Version 2.0.
 
 use std::assert_matches::assert_matches;
+use std::collections::HashSet;
 use std::sync::Arc;
 
 use chrono::{DateTime, TimeZone, Utc};
 use datafusion::execution::config::SessionConfig;
 use datafusion::execution::context::SessionContext;
+use datafusion::prelude::col;
 use dill::Component;
 use domain::{CompactionError, CompactionOptions, CompactionResult};
 use futures::TryStreamExt;
@@ -602,6 +604,414 @@ async fn test_dataset_compaction_limits() {
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
 
+#[test_group::group(ingest, datafusion, compact)]
+#[tokio::test]
+async fn test_dataset_compaction_start_offset() {
+    let harness = CompactTestHarness::new();
+
+    let target = harness.create_test_root_dataset().await;
+    let dataset_ref = target.get_handle().as_local_ref();
+
+    for data_str in [
+        "date,city,population\n2020-01-01,A,1000\n2020-01-02,B,2000\n",
+        "date,city,population\n2020-01-03,C,3000\n2020-01-04,D,4000\n",
+        "date,city,population\n2020-01-05,E,5000\n2020-01-06,F,6000\n",
+        "date,city,population\n2020-01-07,G,7000\n2020-01-08,H,8000\n",
+    ] {
+        harness
+            .ingest_data(data_str.to_string(), target.clone())
+            .await;
+    }
+
+    // Initial state:
+    // seed <- add_push_source <- set_vocab <- set_schema <- add_data(0..1) <-
+    // add_data(2..3) <- add_data(4..5) <- add_data(6..7)
+    let old_blocks = harness.get_dataset_blocks(&dataset_ref).await;
+
+    assert_matches!(
+        harness
+            .compact_dataset(
+                target.clone(),
+                CompactionOptions {
+                    start_offset: Some(4),
+                    ..CompactionOptions::default()
+                },
+            )
+            .await,
+        Ok(CompactionResult::Success {
+            new_head,
+            old_head,
+            new_num_blocks: 2,
+            old_num_blocks: 3
+        }) if new_head != old_head,
+    );
+    assert!(harness.verify_dataset(target).await);
+
+    // Expected state:
+    // seed <- add_push_source <- set_vocab <- set_schema <- add_data(0..1) <-
+    // add_data(2..3) <- add_data(4..7)
+    let new_blocks = harness.get_dataset_blocks(&dataset_ref).await;
+
+    // Blocks preceding the range are left intact
+    assert_eq!(new_blocks[1..], old_blocks[2..]);
+
+    use odf::metadata::EnumWithVariants;
+    let new_data_events: Vec<_> = new_blocks
+        .into_iter()
+        .filter_map(|b| b.event.into_variant::<odf::metadata::AddData>())
+        .rev()
+        .collect();
+
+    assert_eq!(new_data_events.len(), 3);
+
+    CompactTestHarness::assert_offset_interval_eq(
+        &new_data_events[2],
+        &odf::metadata::OffsetInterval { start: 4, end: 7 },
+    );
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[test_group::group(ingest, datafusion, compact)]
+#[tokio::test]
+async fn test_dataset_compaction_sort_by_event_time() {
+    let harness = CompactTestHarness::new();
+
+    let target = harness.create_test_root_dataset().await;
+    let dataset_ref = target.get_handle().as_local_ref();
+
+    let data_helper = harness.dataset_data_helper(&dataset_ref).await;
+
+    for data_str in [
+        "date,city,population\n2020-01-04,A,4000\n2020-01-01,B,1000\n",
+        "date,city,population\n2020-01-03,C,3000\n2020-01-02,D,2000\n",
+        "date,city,population\n2020-01-08,E,8000\n2020-01-05,F,5000\n",
+        "date,city,population\n2020-01-07,G,7000\n2020-01-06,H,6000\n",
+    ] {
+        harness
+            .ingest_data(data_str.to_string(), target.clone())
+            .await;
+    }
+
+    // Two independent batches merged concurrently
+    assert_matches!(
+        harness
+            .compact_dataset(
+                target.clone(),
+                CompactionOptions {
+                    max_slice_records: Some(4),
+                    merge_parallelism: Some(2),
+                    sort_by: Some(CompactionSortBy::EventTime),
+                    ..CompactionOptions::default()
+                },
+            )
+            .await,
+        Ok(CompactionResult::Success {
+            new_head,
+            old_head,
+            new_num_blocks: 6,
+            old_num_blocks: 8
+        }) if new_head != old_head,
+    );
+    assert!(harness.verify_dataset(target).await);
+
+    data_helper
+        .assert_last_data_records_eq(indoc!(
+            r#"
+            +--------+----+----------------------+----------------------+------+------------+
+            | offset | op | system_time          | date                 | city | population |
+            +--------+----+----------------------+----------------------+------+------------+
+            | 4      | 0  | 2050-01-01T12:00:00Z | 2020-01-05T00:00:00Z | F    | 5000       |
+            | 5      | 0  | 2050-01-01T12:00:00Z | 2020-01-06T00:00:00Z | H    | 6000       |
+            | 6      | 0  | 2050-01-01T12:00:00Z | 2020-01-07T00:00:00Z | G    | 7000       |
+            | 7      | 0  | 2050-01-01T12:00:00Z | 2020-01-08T00:00:00Z | E    | 8000       |
+            +--------+----+----------------------+----------------------+------+------------+
+            "#
+        ))
+        .await;
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[test_group::group(ingest, datafusion, compact)]
+#[tokio::test]
+async fn test_dataset_compaction_sort_by_unknown_column() {
+    let harness = CompactTestHarness::new();
+
+    let target = harness.create_test_root_dataset().await;
+
+    harness
+        .ingest_data(
+            "date,city,population\n2020-01-01,A,1000\n".to_string(),
+            target.clone(),
+        )
+        .await;
+
+    assert_matches!(
+        harness
+            .compact_dataset(
+                target,
+                CompactionOptions {
+                    sort_by: Some(CompactionSortBy::Columns(vec!["country".to_string()])),
+                    ..CompactionOptions::default()
+                },
+            )
+            .await,
+        Err(CompactionError::Planning(
+            CompactionPlanningError::UnknownSortColumn(e)
+        )) if e.column == "country",
+    );
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[test_group::group(ingest, datafusion, compact)]
+#[tokio::test]
+async fn test_dataset_soft_compaction() {
+    let harness = CompactTestHarness::new();
+
+    let target = harness.create_test_root_dataset().await;
+    let dataset_ref = target.get_handle().as_local_ref();
+
+    for data_str in [
+        "date,city,population\n2020-01-01,A,1000\n2020-01-02,B,2000\n",
+        "date,city,population\n2020-01-03,C,3000\n",
+        "date,city,population\n2020-01-04,D,4000\n",
+    ] {
+        harness
+            .ingest_data(data_str.to_string(), target.clone())
+            .await;
+    }
+
+    let old_blocks = harness.get_dataset_blocks(&dataset_ref).await;
+
+    let soft_compaction_options = CompactionOptions {
+        mode: CompactionMode::Soft,
+        ..CompactionOptions::default()
+    };
+
+    assert_matches!(
+        harness
+            .compact_dataset(target.clone(), soft_compaction_options.clone())
+            .await,
+        Ok(CompactionResult::DataSlicesMerged {
+            num_merged_slices: 3,
+            num_new_files: 1,
+        })
+    );
+
+    // Metadata chain is left intact
+    assert_eq!(harness.get_dataset_blocks(&dataset_ref).await, old_blocks);
+    assert!(harness.verify_dataset(target.clone()).await);
+
+    // Queries read the merged file even when original slices are gone
+    harness.delete_data_slices(&target).await;
+
+    harness
+        .assert_query_data_eq(
+            &dataset_ref,
+            indoc!(
+                r#"
+                +--------+----+----------------------+----------------------+------+------------+
+                | offset | op | system_time          | date                 | city | population |
+                +--------+----+----------------------+----------------------+------+------------+
+                | 0      | 0  | 2050-01-01T12:00:00Z | 2020-01-01T00:00:00Z | A    | 1000       |
+                | 1      | 0  | 2050-01-01T12:00:00Z | 2020-01-02T00:00:00Z | B    | 2000       |
+                | 2      | 0  | 2050-01-01T12:00:00Z | 2020-01-03T00:00:00Z | C    | 3000       |
+                | 3      | 0  | 2050-01-01T12:00:00Z | 2020-01-04T00:00:00Z | D    | 4000       |
+                +--------+----+----------------------+----------------------+------+------------+
+                "#
+            ),
+        )
+        .await;
+
+    // Already merged slices are not merged again
+    assert_matches!(
+        harness
+            .compact_dataset(target.clone(), soft_compaction_options.clone())
+            .await,
+        Ok(CompactionResult::NothingToDo)
+    );
+
+    for data_str in [
+        "date,city,population\n2020-01-05,E,5000\n",
+        "date,city,population\n2020-01-06,F,6000\n",
+    ] {
+        harness
+            .ingest_data(data_str.to_string(), target.clone())
+            .await;
+    }
+
+    assert_matches!(
+        harness
+            .compact_dataset(target.clone(), soft_compaction_options)
+            .await,
+        Ok(CompactionResult::DataSlicesMerged {
+            num_merged_slices: 2,
+            num_new_files: 1,
+        })
+    );
+
+    harness.delete_data_slices(&target).await;
+
+    harness
+        .assert_query_data_eq(
+            &dataset_ref,
+            indoc!(
+                r#"
+                +--------+----+----------------------+----------------------+------+------------+
+                | offset | op | system_time          | date                 | city | population |
+                +--------+----+----------------------+----------------------+------+------------+
+                | 0      | 0  | 2050-01-01T12:00:00Z | 2020-01-01T00:00:00Z | A    | 1000       |
+                | 1      | 0  | 2050-01-01T12:00:00Z | 2020-01-02T00:00:00Z | B    | 2000       |
+                | 2      | 0  | 2050-01-01T12:00:00Z | 2020-01-03T00:00:00Z | C    | 3000       |
+                | 3      | 0  | 2050-01-01T12:00:00Z | 2020-01-04T00:00:00Z | D    | 4000       |
+                | 4      | 0  | 2050-01-01T12:00:00Z | 2020-01-05T00:00:00Z | E    | 5000       |
+                | 5      | 0  | 2050-01-01T12:00:00Z | 2020-01-06T00:00:00Z | F    | 6000       |
+                +--------+----+----------------------+----------------------+------+------------+
+                "#
+            ),
+        )
+        .await;
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[test_group::group(ingest, datafusion, compact)]
+#[tokio::test]
+async fn test_dataset_soft_compaction_ignored_after_hard_compaction() {
+    let harness = CompactTestHarness::new();
+
+    let target = harness.create_test_root_dataset().await;
+    let dataset_ref = target.get_handle().as_local_ref();
+
+    for data_str in [
+        "date,city,population\n2020-01-01,A,1000\n",
+        "date,city,population\n2020-01-02,B,2000\n",
+    ] {
+        harness
+            .ingest_data(data_str.to_string(), target.clone())
+            .await;
+    }
+
+    assert_matches!(
+        harness
+            .compact_dataset(
+                target.clone(),
+                CompactionOptions {
+                    mode: CompactionMode::Soft,
+                    ..CompactionOptions::default()
+                },
+            )
+            .await,
+        Ok(CompactionResult::DataSlicesMerged { .. })
+    );
+
+    // Rewriting history leaves the merged slice without its last block, so it is
+    // no longer used and records are not duplicated
+    assert_matches!(
+        harness
+            .compact_dataset(target.clone(), CompactionOptions::default())
+            .await,
+        Ok(CompactionResult::Success { .. })
+    );
+
+    let df = harness.query_svc.get_data(&dataset_ref).await.unwrap();
+    assert_eq!(df.count().await.unwrap(), 2);
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[test_group::group(ingest, datafusion, compact)]
+#[tokio::test]
+async fn test_dataset_soft_compaction_files_deleted_after_hard_compaction() {
+    let harness = CompactTestHarness::new();
+
+    let target = harness.create_test_root_dataset().await;
+    let dataset_ref = target.get_handle().as_local_ref();
+
+    for data_str in [
+        "date,city,population\n2020-01-01,A,1000\n",
+        "date,city,population\n2020-01-02,B,2000\n",
+    ] {
+        harness
+            .ingest_data(data_str.to_string(), target.clone())
+            .await;
+    }
+
+    assert_matches!(
+        harness
+            .compact_dataset(
+                target.clone(),
+                CompactionOptions {
+                    mode: CompactionMode::Soft,
+                    ..CompactionOptions::default()
+                },
+            )
+            .await,
+        Ok(CompactionResult::DataSlicesMerged {
+            num_merged_slices: 2,
+            num_new_files: 1,
+        })
+    );
+
+    harness
+        .ingest_data(
+            "date,city,population\n2020-01-03,C,3000\n".to_string(),
+            target.clone(),
+        )
+        .await;
+
+    // 3 original slices and the merged one
+    let old_data_files = harness.get_data_file_names(&dataset_ref).await;
+    assert_eq!(old_data_files.len(), 4);
+
+    assert_matches!(
+        harness
+            .compact_dataset(target.clone(), CompactionOptions::default())
+            .await,
+        Ok(CompactionResult::Success { .. })
+    );
+
+    // The merged file is replaced by the file of the new compacted block
+    let new_data_files = harness.get_data_file_names(&dataset_ref).await;
+    assert_eq!(new_data_files.len(), 4);
+    assert_eq!(old_data_files.difference(&new_data_files).count(), 1);
+
+    harness
+        .assert_query_data_eq(
+            &dataset_ref,
+            indoc!(
+                r#"
+                +--------+----+----------------------+----------------------+------+------------+
+                | offset | op | system_time          | date                 | city | population |
+                +--------+----+----------------------+----------------------+------+------------+
+                | 0      | 0  | 2050-01-01T12:00:00Z | 2020-01-01T00:00:00Z | A    | 1000       |
+                | 1      | 0  | 2050-01-01T12:00:00Z | 2020-01-02T00:00:00Z | B    | 2000       |
+                | 2      | 0  | 2050-01-01T12:00:00Z | 2020-01-03T00:00:00Z | C    | 3000       |
+                +--------+----+----------------------+----------------------+------+------------+
+                "#
+            ),
+        )
+        .await;
+
+    // Nothing is left to merge in the rewritten history
+    assert_matches!(
+        harness
+            .compact_dataset(
+                target.clone(),
+                CompactionOptions {
+                    mode: CompactionMode::Soft,
+                    ..CompactionOptions::default()
+                },
+            )
+            .await,
+        Ok(CompactionResult::NothingToDo)
+    );
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
 #[test_group::group(ingest, datafusion, compact)]
 #[tokio::test]
 async fn test_dataset_compaction_keep_all_non_data_blocks() {
@@ -1117,6 +1527,7 @@ struct CompactTestHarness {
     push_ingest_executor: Arc<dyn PushIngestExecutor>,
     transform_helper: TransformTestHelper,
     verification_svc: Arc<dyn VerificationService>,
+    query_svc: Arc<dyn QueryService>,
     current_date_time: DateTime<Utc>,
     ctx: SessionContext,
 }
@@ -1161,6 +1572,7 @@ impl CompactTestHarness {
             )
             .bind::<dyn EngineProvisioner, mock_engine_provisioner::MockEngineProvisioner>()
             .add::<VerificationServiceImpl>()
+            .add::<QueryServiceImpl>()
             .build();
 
         let transform_helper = TransformTestHelper::from_catalog(&catalog);
@@ -1175,6 +1587,7 @@ impl CompactTestHarness {
             push_ingest_planner: catalog.get_one().unwrap(),
             push_ingest_executor: catalog.get_one().unwrap(),
             verification_svc: catalog.get_one().unwrap(),
+            query_svc: catalog.get_one().unwrap(),
             transform_helper,
             current_date_time,
             ctx: SessionContext::new_with_config(SessionConfig::new().with_target_partitions(1)),
@@ -1206,6 +1619,7 @@ impl CompactTestHarness {
             .add_value(ObjectStoreBuilderS3::new(s3_context.clone(), true))
             .bind::<dyn ObjectStoreBuilder, ObjectStoreBuilderS3>()
             .add::<VerificationServiceImpl>()
+            .add::<QueryServiceImpl>()
             .add::<PushIngestExecutorImpl>()
             .add::<PushIngestPlannerImpl>()
             .add::<TransformRequestPlannerImpl>()
@@ -1232,6 +1646,7 @@ impl CompactTestHarness {
             push_ingest_executor: catalog.get_one().unwrap(),
             transform_helper,
             verification_svc: catalog.get_one().unwrap(),
+            query_svc: catalog.get_one().unwrap(),
             current_date_time,
             ctx,
         }
@@ -1251,6 +1666,49 @@ impl CompactTestHarness {
             .unwrap()
     }
 
+    async fn delete_data_slices(&self, target: &ResolvedDataset) {
+        use odf::dataset::MetadataChainExt;
+        use odf::metadata::EnumWithVariants;
+
+        let head = target
+            .as_metadata_chain()
+            .resolve_ref(&odf::BlockRef::Head)
+            .await
+            .unwrap();
+
+        let data_slices: Vec<_> = target
+            .as_metadata_chain()
+            .iter_blocks_interval(&head, None, false)
+            .try_filter_map(|(_, b)| async move {
+                Ok(b.event
+                    .into_variant::<odf::metadata::AddData>()
+                    .and_then(|e| e.new_data))
+            })
+            .try_collect()
+            .await
+            .unwrap();
+
+        for data_slice in data_slices {
+            target
+                .as_data_repo()
+                .delete(&data_slice.physical_hash)
+                .await
+                .unwrap();
+        }
+    }
+
+    async fn assert_query_data_eq(&self, dataset_ref: &odf::DatasetRef, expected: &str) {
+        let df = self
+            .query_svc
+            .get_data(dataset_ref)
+            .await
+            .unwrap()
+            .sort(vec![col("offset").sort(true, false)])
+            .unwrap();
+
+        odf::utils::testing::assert_data_eq(df, expected).await;
+    }
+
     async fn get_dataset_blocks(&self, dataset_ref: &odf::DatasetRef) -> Vec<odf::MetadataBlock> {
         let resolved_dataset = self
             .dataset_registry
@@ -1364,6 +1822,22 @@ impl CompactTestHarness {
         DatasetDataHelper::new_with_context((*resolved_dataset).clone(), self.ctx.clone())
     }
 
+    async fn get_data_file_names(&self, dataset_ref: &odf::DatasetRef) -> HashSet<String> {
+        let data_dir = self
+            .dataset_data_helper(dataset_ref)
+            .await
+            .get_last_data_file()
+            .await
+            .parent()
+            .unwrap()
+            .to_path_buf();
+
+        std::fs::read_dir(data_dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+            .collect()
+    }
+
     async fn ingest_multiple_blocks(
         &self,
         target: ResolvedDataset,
@@ -0,0 +1,297 @@
+// Copyright Kamu Data, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::sync::Arc;
+
+use datafusion::arrow::array::{Int32Array, Int64Array, StringArray};
+use datafusion::arrow::datatypes::{DataType, Field, Schema, SchemaRef};
+use datafusion::arrow::record_batch::RecordBatch;
+use file_utils::OwnedFile;
+use kamu::testing::{BaseRepoHarness, MockDatasetActionAuthorizer, ParquetWriterHelper};
+use kamu::*;
+use kamu_core::*;
+use odf::metadata::testing::MetadataFactory;
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[test_group::group(engine, datafusion)]
+#[test_log::test(tokio::test)]
+async fn test_read_changes_in_chunks() {
+    let harness = ChangeFeedHarness::new();
+    let foo = harness.create_dataset_with_schema("foo").await;
+
+    harness
+        .append_data(&foo, None, &[(0, 0, "a"), (1, 0, "b")])
+        .await;
+    harness
+        .append_data(&foo, Some(1), &[(2, 1, "b"), (3, 0, "c")])
+        .await;
+
+    let cursor = ChangeFeedCursor::since_offset(None);
+
+    let chunk = harness.read(&foo, &cursor, 3).await;
+    assert_eq!(chunk.num_records, 3);
+    assert!(!chunk.caught_up);
+    assert_eq!(chunk.cursor.last_offset, Some(2));
+    assert_eq!(chunk.cursor.caught_up_head, None);
+
+    odf::utils::testing::assert_data_eq(
+        harness.as_df(chunk.clone()),
+        indoc::indoc!(
+            r#"
+            +--------+----+------+
+            | offset | op | blah |
+            +--------+----+------+
+            | 0      | 0  | a    |
+            | 1      | 0  | b    |
+            | 2      | 1  | b    |
+            +--------+----+------+
+            "#
+        ),
+    )
+    .await;
+
+    let chunk = harness.read(&foo, &chunk.cursor, 3).await;
+    assert_eq!(chunk.num_records, 1);
+    assert!(chunk.caught_up);
+    assert_eq!(chunk.cursor.last_offset, Some(3));
+    assert_eq!(chunk.cursor.caught_up_head, Some(harness.head(&foo).await));
+
+    // Resuming from a client-supplied offset
+    let chunk = harness
+        .read(&foo, &ChangeFeedCursor::since_offset(Some(2)), 10)
+        .await;
+    assert_eq!(chunk.num_records, 1);
+    assert!(chunk.caught_up);
+    assert_eq!(chunk.cursor.last_offset, Some(3));
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[test_group::group(engine, datafusion)]
+#[test_log::test(tokio::test)]
+async fn test_follow_new_commits() {
+    let harness = ChangeFeedHarness::new();
+    let foo = harness.create_dataset_with_schema("foo").await;
+
+    // No data yet
+    let chunk = harness
+        .read(&foo, &ChangeFeedCursor::since_offset(None), 10)
+        .await;
+    assert_eq!(chunk.num_records, 0);
+    assert!(chunk.caught_up);
+    assert_eq!(chunk.cursor.last_offset, None);
+
+    harness.append_data(&foo, None, &[(0, 0, "a")]).await;
+
+    let chunk = harness.read(&foo, &chunk.cursor, 10).await;
+    assert_eq!(chunk.num_records, 1);
+    assert!(chunk.caught_up);
+    assert_eq!(chunk.cursor.last_offset, Some(0));
+
+    // Nothing was committed
+    let chunk = harness.read(&foo, &chunk.cursor, 10).await;
+    assert_eq!(chunk.num_records, 0);
+    assert!(chunk.caught_up);
+    assert_eq!(chunk.cursor.last_offset, Some(0));
+
+    // Metadata-only commit
+    foo.dataset
+        .commit_event(
+            MetadataFactory::set_info()
+                .description("Updated description")
+                .build()
+                .into(),
+            odf::dataset::CommitOpts::default(),
+        )
+        .await
+        .unwrap();
+
+    let chunk = harness.read(&foo, &chunk.cursor, 10).await;
+    assert_eq!(chunk.num_records, 0);
+    assert!(chunk.caught_up);
+    assert_eq!(chunk.cursor.last_offset, Some(0));
+    assert_eq!(chunk.cursor.caught_up_head, Some(harness.head(&foo).await));
+
+    // Retraction and correction
+    harness
+        .append_data(&foo, Some(0), &[(1, 2, "a"), (2, 3, "aa")])
+        .await;
+
+    let chunk = harness.read(&foo, &chunk.cursor, 10).await;
+    assert_eq!(chunk.num_records, 2);
+    assert!(chunk.caught_up);
+    assert_eq!(chunk.cursor.last_offset, Some(2));
+
+    odf::utils::testing::assert_data_eq(
+        harness.as_df(chunk),
+        indoc::indoc!(
+            r#"
+            +--------+----+------+
+            | offset | op | blah |
+            +--------+----+------+
+            | 1      | 2  | a    |
+            | 2      | 3  | aa   |
+            +--------+----+------+
+            "#
+        ),
+    )
+    .await;
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[test_log::test(tokio::test)]
+async fn test_read_changes_unauthorized() {
+    let harness = ChangeFeedHarness::new_with_authorizer(MockDatasetActionAuthorizer::denying());
+    let foo = harness.create_dataset_with_schema("foo").await;
+
+    let res = harness
+        .change_feed_service
+        .read_changes(
+            &foo.dataset_handle.as_local_ref(),
+            &ChangeFeedCursor::default(),
+            10,
+        )
+        .await;
+    assert!(matches!(res, Err(QueryError::Access(_))));
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[oop::extend(BaseRepoHarness, base_repo_harness)]
+struct ChangeFeedHarness {
+    base_repo_harness: BaseRepoHarness,
+    change_feed_service: Arc<dyn DatasetChangeFeedService>,
+    schema: SchemaRef,
+}
+
+impl ChangeFeedHarness {
+    fn new() -> Self {
+        Self::new_with_authorizer(MockDatasetActionAuthorizer::allowing())
+    }
+
+    fn new_with_authorizer(dataset_action_authorizer: MockDatasetActionAuthorizer) -> Self {
+        let base_repo_harness = BaseRepoHarness::builder()
+            .tenancy_config(TenancyConfig::SingleTenant)
+            .build();
+
+        let catalog = dill::CatalogBuilder::new_chained(base_repo_harness.catalog())
+            .add::<QueryServiceImpl>()
+            .add::<ObjectStoreRegistryImpl>()
+            .add::<ObjectStoreBuilderLocalFs>()
+            .add::<DatasetChangesServiceImpl>()
+            .add::<DatasetChangeFeedServiceImpl>()
+            .add_value(dataset_action_authorizer)
+            .bind::<dyn auth::DatasetActionAuthorizer, MockDatasetActionAuthorizer>()
+            .build();
+
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("offset", DataType::Int64, false),
+            Field::new("op", DataType::Int32, false),
+            Field::new("blah", DataType::Utf8, false),
+        ]));
+
+        Self {
+            base_repo_harness,
+            change_feed_service: catalog.get_one().unwrap(),
+            schema,
+        }
+    }
+
+    async fn create_dataset_with_schema(&self, name: &str) -> kamu_datasets::CreateDatasetResult {
+        let foo = self
+            .create_root_dataset(&odf::DatasetAlias::new(
+                None,
+                odf::DatasetName::new_unchecked(name),
+            ))
+            .await;
+
+        foo.dataset
+            .commit_event(
+                MetadataFactory::set_data_schema()
+                    .schema(&self.schema)
+                    .build()
+                    .into(),
+                odf::dataset::CommitOpts::default(),
+            )
+            .await
+            .unwrap();
+
+        foo
+    }
+
+    async fn append_data(
+        &self,
+        target: &kamu_datasets::CreateDatasetResult,
+        prev_offset: Option<u64>,
+        records: &[(i64, i32, &str)],
+    ) {
+        let record_batch = RecordBatch::try_new(
+            self.schema.clone(),
+            vec![
+                Arc::new(Int64Array::from_iter_values(records.iter().map(|r| r.0))),
+                Arc::new(Int32Array::from_iter_values(records.iter().map(|r| r.1))),
+                Arc::new(StringArray::from_iter_values(records.iter().map(|r| r.2))),
+            ],
+        )
+        .unwrap();
+
+        let tmp_data_path = self.temp_dir_path().join("data");
+        ParquetWriterHelper::from_record_batch(&tmp_data_path, &record_batch).unwrap();
+
+        let start = prev_offset.map_or(0, |v| v + 1);
+        let end = start + u64::try_from(records.len()).unwrap() - 1;
+
+        target
+            .dataset
+            .commit_add_data(
+                odf::dataset::AddDataParams {
+                    prev_checkpoint: None,
+                    prev_offset,
+                    new_offset_interval: Some(odf::metadata::OffsetInterval { start, end }),
+                    new_watermark: None,
+                    new_source_state: None,
+                },
+                Some(OwnedFile::new(tmp_data_path)),
+                None,
+                odf::dataset::CommitOpts::default(),
+            )
+            .await
+            .unwrap();
+    }
+
+    async fn head(&self, target: &kamu_datasets::CreateDatasetResult) -> odf::Multihash {
+        target
+            .dataset
+            .as_metadata_chain()
+            .resolve_ref(&odf::BlockRef::Head)
+            .await
+            .unwrap()
+    }
+
+    async fn read(
+        &self,
+        target: &kamu_datasets::CreateDatasetResult,
+        cursor: &ChangeFeedCursor,
+        max_records: u64,
+    ) -> DatasetChangesChunk {
+        self.change_feed_service
+            .read_changes(&target.dataset_handle.as_local_ref(), cursor, max_records)
+            .await
+            .unwrap()
+    }
+
+    fn as_df(&self, chunk: DatasetChangesChunk) -> datafusion::prelude::DataFrame {
+        let ctx = datafusion::prelude::SessionContext::new();
+        ctx.read_batches(chunk.record_batches).unwrap()
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
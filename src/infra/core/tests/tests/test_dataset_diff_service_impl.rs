@@ -0,0 +1,321 @@
+// Copyright Kamu Data, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::assert_matches::assert_matches;
+use std::sync::Arc;
+
+use chrono::{DateTime, TimeZone, Utc};
+use indoc::indoc;
+use kamu::domain::*;
+use kamu::*;
+use kamu_accounts::CurrentAccountSubject;
+use kamu_core::auth;
+use odf::dataset::testing::create_test_dataset_from_snapshot;
+use odf::metadata::testing::MetadataFactory;
+use time_source::{SystemTimeSource, SystemTimeSourceStub};
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[test_group::group(ingest, datafusion)]
+#[tokio::test]
+async fn test_diff_snapshot_changes_grouped_by_primary_key() {
+    let harness = DatasetDiffTestHarness::new();
+    let target = harness.create_snapshot_root_dataset().await;
+
+    harness
+        .ingest_data(
+            indoc!(
+                "
+                city,population
+                A,1000
+                B,2000
+                C,3000
+                "
+            ),
+            target.clone(),
+        )
+        .await;
+    let head_1 = harness.get_head(&target).await;
+
+    harness
+        .ingest_data(
+            indoc!(
+                "
+                city,population
+                A,1500
+                C,3000
+                D,4000
+                "
+            ),
+            target.clone(),
+        )
+        .await;
+    let head_2 = harness.get_head(&target).await;
+
+    let diff = harness
+        .dataset_diff_svc
+        .diff(target.clone(), Some(&head_1), None)
+        .await
+        .unwrap();
+
+    assert_eq!(diff.from_block, Some(head_1));
+    assert_eq!(diff.to_block, head_2);
+    assert_eq!(diff.metadata_blocks.len(), 1);
+    assert_matches!(
+        diff.metadata_blocks[0].1.event,
+        odf::MetadataEvent::AddData(_)
+    );
+
+    assert_eq!(
+        diff.data.offset_interval,
+        Some(odf::metadata::OffsetInterval { start: 3, end: 6 })
+    );
+    assert_eq!(diff.data.num_appended, 1);
+    assert_eq!(diff.data.num_retracted, 1);
+    assert_eq!(diff.data.num_corrected, 1);
+    assert_eq!(diff.data.primary_key, vec!["city".to_string()]);
+
+    odf::utils::testing::assert_data_eq(
+        diff.data
+            .records
+            .unwrap()
+            .select_columns(&["op", "city", "population"])
+            .unwrap(),
+        indoc!(
+            r#"
+            +----+------+------------+
+            | op | city | population |
+            +----+------+------------+
+            | 2  | A    | 1000       |
+            | 3  | A    | 1500       |
+            | 1  | B    | 2000       |
+            | 0  | D    | 4000       |
+            +----+------+------------+
+            "#
+        ),
+    )
+    .await;
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[test_group::group(ingest, datafusion)]
+#[tokio::test]
+async fn test_diff_whole_history() {
+    let harness = DatasetDiffTestHarness::new();
+    let target = harness.create_snapshot_root_dataset().await;
+
+    let diff = harness
+        .dataset_diff_svc
+        .diff(target.clone(), None, None)
+        .await
+        .unwrap();
+
+    assert!(!diff.metadata_blocks.is_empty());
+    assert_eq!(diff.data.offset_interval, None);
+    assert!(diff.data.records.is_none());
+
+    harness
+        .ingest_data(
+            indoc!(
+                "
+                city,population
+                A,1000
+                B,2000
+                "
+            ),
+            target.clone(),
+        )
+        .await;
+    harness
+        .ingest_data(
+            indoc!(
+                "
+                city,population
+                A,1000
+                "
+            ),
+            target.clone(),
+        )
+        .await;
+
+    let diff = harness
+        .dataset_diff_svc
+        .diff(target.clone(), None, None)
+        .await
+        .unwrap();
+
+    assert_eq!(diff.from_block, None);
+    assert_eq!(
+        diff.data.offset_interval,
+        Some(odf::metadata::OffsetInterval { start: 0, end: 2 })
+    );
+    assert_eq!(diff.data.num_appended, 2);
+    assert_eq!(diff.data.num_retracted, 1);
+    assert_eq!(diff.data.num_corrected, 0);
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[test_group::group(ingest, datafusion)]
+#[tokio::test]
+async fn test_diff_invalid_interval() {
+    let harness = DatasetDiffTestHarness::new();
+    let target = harness.create_snapshot_root_dataset().await;
+
+    let head_1 = harness.get_head(&target).await;
+    harness
+        .ingest_data(
+            indoc!(
+                "
+                city,population
+                A,1000
+                "
+            ),
+            target.clone(),
+        )
+        .await;
+    let head_2 = harness.get_head(&target).await;
+
+    assert_matches!(
+        harness
+            .dataset_diff_svc
+            .diff(target.clone(), Some(&head_2), Some(&head_1))
+            .await,
+        Err(DatasetDiffError::InvalidInterval(_))
+    );
+
+    assert_matches!(
+        harness
+            .dataset_diff_svc
+            .diff(
+                target.clone(),
+                Some(&odf::Multihash::from_digest_sha3_256(b"missing")),
+                None
+            )
+            .await,
+        Err(DatasetDiffError::BlockNotFound(_))
+    );
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+struct DatasetDiffTestHarness {
+    _temp_dir: tempfile::TempDir,
+    did_generator: Arc<dyn DidGenerator>,
+    dataset_registry: Arc<dyn DatasetRegistry>,
+    dataset_storage_unit_writer: Arc<dyn odf::DatasetStorageUnitWriter>,
+    push_ingest_planner: Arc<dyn PushIngestPlanner>,
+    push_ingest_executor: Arc<dyn PushIngestExecutor>,
+    dataset_diff_svc: Arc<dyn DatasetDiffService>,
+    current_date_time: DateTime<Utc>,
+}
+
+impl DatasetDiffTestHarness {
+    fn new() -> Self {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let run_info_dir = temp_dir.path().join("run");
+        let datasets_dir = temp_dir.path().join("datasets");
+        std::fs::create_dir(&run_info_dir).unwrap();
+        std::fs::create_dir(&datasets_dir).unwrap();
+        let current_date_time = Utc.with_ymd_and_hms(2050, 1, 1, 12, 0, 0).unwrap();
+
+        let catalog = dill::CatalogBuilder::new()
+            .add::<DidGeneratorDefault>()
+            .add_value(RunInfoDir::new(run_info_dir))
+            .add_value(CurrentAccountSubject::new_test())
+            .add_value(TenancyConfig::SingleTenant)
+            .add_builder(odf::dataset::DatasetStorageUnitLocalFs::builder().with_root(datasets_dir))
+            .bind::<dyn odf::DatasetStorageUnit, odf::dataset::DatasetStorageUnitLocalFs>()
+            .bind::<dyn odf::DatasetStorageUnitWriter, odf::dataset::DatasetStorageUnitLocalFs>()
+            .add::<DatasetRegistrySoloUnitBridge>()
+            .add::<auth::AlwaysHappyDatasetActionAuthorizer>()
+            .add_value(SystemTimeSourceStub::new_set(current_date_time))
+            .bind::<dyn SystemTimeSource, SystemTimeSourceStub>()
+            .add::<ObjectStoreRegistryImpl>()
+            .add::<ObjectStoreBuilderLocalFs>()
+            .add::<DataFormatRegistryImpl>()
+            .add::<PushIngestExecutorImpl>()
+            .add::<PushIngestPlannerImpl>()
+            .add::<EngineProvisionerNull>()
+            .add::<DatasetDiffServiceImpl>()
+            .build();
+
+        Self {
+            _temp_dir: temp_dir,
+            did_generator: catalog.get_one().unwrap(),
+            dataset_registry: catalog.get_one().unwrap(),
+            dataset_storage_unit_writer: catalog.get_one().unwrap(),
+            push_ingest_planner: catalog.get_one().unwrap(),
+            push_ingest_executor: catalog.get_one().unwrap(),
+            dataset_diff_svc: catalog.get_one().unwrap(),
+            current_date_time,
+        }
+    }
+
+    async fn create_snapshot_root_dataset(&self) -> ResolvedDataset {
+        let dataset_alias = odf::DatasetAlias::new(None, odf::DatasetName::new_unchecked("foo"));
+
+        let store_result = create_test_dataset_from_snapshot(
+            self.dataset_registry.as_ref(),
+            self.dataset_storage_unit_writer.as_ref(),
+            MetadataFactory::dataset_snapshot()
+                .name("foo")
+                .kind(odf::DatasetKind::Root)
+                .push_event(
+                    MetadataFactory::add_push_source()
+                        .read(odf::metadata::ReadStepCsv {
+                            header: Some(true),
+                            schema: Some(vec![
+                                "city STRING".to_string(),
+                                "population BIGINT".to_string(),
+                            ]),
+                            ..odf::metadata::ReadStepCsv::default()
+                        })
+                        .merge(odf::metadata::MergeStrategySnapshot {
+                            primary_key: vec!["city".to_string()],
+                            compare_columns: None,
+                        })
+                        .build(),
+                )
+                .build(),
+            self.did_generator.generate_dataset_id().0,
+            self.current_date_time,
+        )
+        .await
+        .unwrap();
+
+        ResolvedDataset::from_stored(&store_result, &dataset_alias)
+    }
+
+    async fn get_head(&self, target: &ResolvedDataset) -> odf::Multihash {
+        target
+            .as_metadata_chain()
+            .resolve_ref(&odf::BlockRef::Head)
+            .await
+            .unwrap()
+    }
+
+    async fn ingest_data(&self, data_str: &str, target: ResolvedDataset) {
+        let data = std::io::Cursor::new(data_str.to_string());
+
+        let ingest_plan = self
+            .push_ingest_planner
+            .plan_ingest(target.clone(), None, PushIngestOpts::default())
+            .await
+            .unwrap();
+
+        self.push_ingest_executor
+            .ingest_from_stream(target, ingest_plan, Box::new(data), None)
+            .await
+            .unwrap();
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
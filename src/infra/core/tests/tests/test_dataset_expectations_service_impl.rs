@@ -0,0 +1,517 @@
+// Copyright Kamu Data, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::assert_matches::assert_matches;
+use std::sync::Arc;
+
+use chrono::{DateTime, TimeZone, Utc};
+use indoc::indoc;
+use kamu::domain::*;
+use kamu::*;
+use kamu_accounts::CurrentAccountSubject;
+use kamu_core::auth;
+use odf::dataset::testing::create_test_dataset_from_snapshot;
+use odf::metadata::testing::MetadataFactory;
+use time_source::{SystemTimeSource, SystemTimeSourceStub};
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[tokio::test]
+async fn test_set_and_get_expectations() {
+    let harness = DatasetExpectationsTestHarness::new();
+    let target = harness.create_root_dataset().await;
+
+    assert_eq!(
+        harness
+            .dataset_expectations_svc
+            .get_expectations(&target)
+            .await
+            .unwrap(),
+        vec![]
+    );
+
+    let expectations = vec![
+        expectation(
+            "city-present",
+            DataExpectationRule::NotNull {
+                column: "city".to_string(),
+            },
+            DataExpectationAction::Fail,
+        ),
+        expectation(
+            "some-records",
+            DataExpectationRule::RowCount {
+                min: Some(1),
+                max: None,
+            },
+            DataExpectationAction::Warn,
+        ),
+    ];
+
+    harness
+        .set_expectations(&target, expectations.clone())
+        .await
+        .unwrap();
+
+    assert_eq!(
+        harness
+            .dataset_expectations_svc
+            .get_expectations(&target)
+            .await
+            .unwrap(),
+        expectations
+    );
+
+    // Expectations are declared in the chain
+    let head_block = harness.get_head_block(&target).await;
+    let odf::MetadataEvent::SetAttachments(e) = head_block.event else {
+        panic!("Expected SetAttachments event");
+    };
+    let odf::metadata::Attachments::Embedded(at) = e.attachments;
+    assert_eq!(
+        at.items.iter().map(|i| i.path.as_str()).collect::<Vec<_>>(),
+        vec!["kamu/expectations.yaml"]
+    );
+
+    assert_matches!(
+        harness
+            .dataset_expectations_svc
+            .prepare_set_expectations(&target, expectations.clone())
+            .await,
+        Ok(None)
+    );
+
+    assert_matches!(
+        harness
+            .dataset_expectations_svc
+            .prepare_set_expectations(
+                &target,
+                vec![expectation(
+                    "bad-pattern",
+                    DataExpectationRule::RegexMatch {
+                        column: "city".to_string(),
+                        pattern: "[".to_string(),
+                    },
+                    DataExpectationAction::Fail,
+                )],
+            )
+            .await,
+        Err(SetDataExpectationsError::Invalid(e)) if e.name == "bad-pattern"
+    );
+
+    harness.set_expectations(&target, vec![]).await.unwrap();
+
+    assert_eq!(
+        harness
+            .dataset_expectations_svc
+            .get_expectations(&target)
+            .await
+            .unwrap(),
+        vec![]
+    );
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[test_group::group(ingest, datafusion)]
+#[tokio::test]
+async fn test_ingest_rejected_by_failed_expectation() {
+    let harness = DatasetExpectationsTestHarness::new();
+    let target = harness.create_root_dataset().await;
+
+    harness
+        .set_expectations(
+            &target,
+            vec![expectation(
+                "population-present",
+                DataExpectationRule::NotNull {
+                    column: "population".to_string(),
+                },
+                DataExpectationAction::Fail,
+            )],
+        )
+        .await
+        .unwrap();
+
+    let head_before = harness.get_head(&target).await;
+
+    let res = harness
+        .ingest_data(
+            indoc!(
+                "
+                city,population
+                A,1000
+                B,
+                "
+            ),
+            target.clone(),
+        )
+        .await;
+
+    assert_matches!(
+        res,
+        Err(PushIngestError::ExpectationsFailed(e))
+            if e.failed.len() == 1 && e.failed[0].num_violations == 1
+    );
+    assert_eq!(harness.get_head(&target).await, head_before);
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[test_group::group(ingest, datafusion)]
+#[tokio::test]
+async fn test_ingest_quarantines_violating_records() {
+    let harness = DatasetExpectationsTestHarness::new();
+    let target = harness.create_root_dataset().await;
+
+    harness
+        .set_expectations(
+            &target,
+            vec![
+                expectation(
+                    "population-positive",
+                    DataExpectationRule::ValueRange {
+                        column: "population".to_string(),
+                        min: Some(0.0),
+                        max: None,
+                    },
+                    DataExpectationAction::Quarantine,
+                ),
+                expectation(
+                    "city-code",
+                    DataExpectationRule::RegexMatch {
+                        column: "city".to_string(),
+                        pattern: "^[A-Z]$".to_string(),
+                    },
+                    DataExpectationAction::Quarantine,
+                ),
+            ],
+        )
+        .await
+        .unwrap();
+
+    harness
+        .ingest_data(
+            indoc!(
+                "
+                city,population
+                A,1000
+                B,-1
+                cc,3000
+                D,4000
+                "
+            ),
+            target.clone(),
+        )
+        .await
+        .unwrap();
+
+    let data_block_hash = harness.get_reported_block_hash(&target).await;
+    assert_eq!(harness.num_new_records(&target, &data_block_hash).await, 2);
+
+    let report = harness
+        .dataset_expectations_svc
+        .get_report(&target, &data_block_hash)
+        .await
+        .unwrap()
+        .unwrap();
+
+    // Quarantined records are stored in the data repository
+    let quarantined_data = report.quarantined_data.clone().unwrap();
+    assert!(target
+        .as_data_repo()
+        .contains(&quarantined_data)
+        .await
+        .unwrap());
+
+    assert_eq!(
+        report,
+        DataExpectationsReport {
+            results: vec![
+                DataExpectationResult {
+                    name: "population-positive".to_string(),
+                    action: DataExpectationAction::Quarantine,
+                    passed: false,
+                    num_violations: 1,
+                },
+                DataExpectationResult {
+                    name: "city-code".to_string(),
+                    action: DataExpectationAction::Quarantine,
+                    passed: false,
+                    num_violations: 1,
+                },
+            ],
+            num_quarantined: 2,
+            quarantined_data: Some(quarantined_data),
+        }
+    );
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[test_group::group(ingest, datafusion)]
+#[tokio::test]
+async fn test_ingest_commits_with_warning() {
+    let harness = DatasetExpectationsTestHarness::new();
+    let target = harness.create_root_dataset().await;
+
+    harness
+        .set_expectations(
+            &target,
+            vec![
+                expectation(
+                    "enough-records",
+                    DataExpectationRule::RowCount {
+                        min: Some(10),
+                        max: None,
+                    },
+                    DataExpectationAction::Warn,
+                ),
+                // Quarantine is not applicable to non-row-level rules
+                expectation(
+                    "unique-city",
+                    DataExpectationRule::Unique {
+                        columns: vec!["city".to_string()],
+                    },
+                    DataExpectationAction::Quarantine,
+                ),
+            ],
+        )
+        .await
+        .unwrap();
+
+    harness
+        .ingest_data(
+            indoc!(
+                "
+                city,population
+                A,1000
+                B,2000
+                "
+            ),
+            target.clone(),
+        )
+        .await
+        .unwrap();
+
+    let data_block_hash = harness.get_reported_block_hash(&target).await;
+    assert_eq!(harness.num_new_records(&target, &data_block_hash).await, 2);
+
+    let report = harness
+        .dataset_expectations_svc
+        .get_report(&target, &data_block_hash)
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(report.num_failed(), 1);
+    assert_eq!(report.num_quarantined, 0);
+    assert_eq!(report.quarantined_data, None);
+    assert_eq!(
+        report.results[1],
+        DataExpectationResult {
+            name: "unique-city".to_string(),
+            action: DataExpectationAction::Fail,
+            passed: true,
+            num_violations: 0,
+        }
+    );
+
+    assert_matches!(
+        harness
+            .ingest_data(
+                indoc!(
+                    "
+                    city,population
+                    C,1000
+                    C,2000
+                    "
+                ),
+                target.clone(),
+            )
+            .await,
+        Err(PushIngestError::ExpectationsFailed(e)) if e.failed[0].name == "unique-city"
+    );
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+fn expectation(
+    name: &str,
+    rule: DataExpectationRule,
+    on_failure: DataExpectationAction,
+) -> DataExpectation {
+    DataExpectation {
+        name: name.to_string(),
+        rule,
+        on_failure,
+    }
+}
+
+struct DatasetExpectationsTestHarness {
+    _temp_dir: tempfile::TempDir,
+    did_generator: Arc<dyn DidGenerator>,
+    dataset_registry: Arc<dyn DatasetRegistry>,
+    dataset_storage_unit_writer: Arc<dyn odf::DatasetStorageUnitWriter>,
+    push_ingest_planner: Arc<dyn PushIngestPlanner>,
+    push_ingest_executor: Arc<dyn PushIngestExecutor>,
+    dataset_expectations_svc: Arc<dyn DatasetExpectationsService>,
+    current_date_time: DateTime<Utc>,
+}
+
+impl DatasetExpectationsTestHarness {
+    fn new() -> Self {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let run_info_dir = temp_dir.path().join("run");
+        let datasets_dir = temp_dir.path().join("datasets");
+        std::fs::create_dir(&run_info_dir).unwrap();
+        std::fs::create_dir(&datasets_dir).unwrap();
+        let current_date_time = Utc.with_ymd_and_hms(2050, 1, 1, 12, 0, 0).unwrap();
+
+        let catalog = dill::CatalogBuilder::new()
+            .add::<DidGeneratorDefault>()
+            .add_value(RunInfoDir::new(run_info_dir))
+            .add_value(CurrentAccountSubject::new_test())
+            .add_value(TenancyConfig::SingleTenant)
+            .add_builder(odf::dataset::DatasetStorageUnitLocalFs::builder().with_root(datasets_dir))
+            .bind::<dyn odf::DatasetStorageUnit, odf::dataset::DatasetStorageUnitLocalFs>()
+            .bind::<dyn odf::DatasetStorageUnitWriter, odf::dataset::DatasetStorageUnitLocalFs>()
+            .add::<DatasetRegistrySoloUnitBridge>()
+            .add::<auth::AlwaysHappyDatasetActionAuthorizer>()
+            .add_value(SystemTimeSourceStub::new_set(current_date_time))
+            .bind::<dyn SystemTimeSource, SystemTimeSourceStub>()
+            .add::<ObjectStoreRegistryImpl>()
+            .add::<ObjectStoreBuilderLocalFs>()
+            .add::<DataFormatRegistryImpl>()
+            .add::<PushIngestExecutorImpl>()
+            .add::<PushIngestPlannerImpl>()
+            .add::<EngineProvisionerNull>()
+            .add::<DatasetExpectationsServiceImpl>()
+            .build();
+
+        Self {
+            _temp_dir: temp_dir,
+            did_generator: catalog.get_one().unwrap(),
+            dataset_registry: catalog.get_one().unwrap(),
+            dataset_storage_unit_writer: catalog.get_one().unwrap(),
+            push_ingest_planner: catalog.get_one().unwrap(),
+            push_ingest_executor: catalog.get_one().unwrap(),
+            dataset_expectations_svc: catalog.get_one().unwrap(),
+            current_date_time,
+        }
+    }
+
+    async fn create_root_dataset(&self) -> ResolvedDataset {
+        let dataset_alias = odf::DatasetAlias::new(None, odf::DatasetName::new_unchecked("foo"));
+
+        let store_result = create_test_dataset_from_snapshot(
+            self.dataset_registry.as_ref(),
+            self.dataset_storage_unit_writer.as_ref(),
+            MetadataFactory::dataset_snapshot()
+                .name("foo")
+                .kind(odf::DatasetKind::Root)
+                .push_event(
+                    MetadataFactory::add_push_source()
+                        .read(odf::metadata::ReadStepCsv {
+                            header: Some(true),
+                            schema: Some(vec![
+                                "city STRING".to_string(),
+                                "population BIGINT".to_string(),
+                            ]),
+                            ..odf::metadata::ReadStepCsv::default()
+                        })
+                        .build(),
+                )
+                .build(),
+            self.did_generator.generate_dataset_id().0,
+            self.current_date_time,
+        )
+        .await
+        .unwrap();
+
+        ResolvedDataset::from_stored(&store_result, &dataset_alias)
+    }
+
+    async fn set_expectations(
+        &self,
+        target: &ResolvedDataset,
+        expectations: Vec<DataExpectation>,
+    ) -> Result<(), SetDataExpectationsError> {
+        if let Some(event) = self
+            .dataset_expectations_svc
+            .prepare_set_expectations(target, expectations)
+            .await?
+        {
+            target
+                .commit_event(event, odf::dataset::CommitOpts::default())
+                .await
+                .unwrap();
+        }
+        Ok(())
+    }
+
+    async fn get_head_block(&self, target: &ResolvedDataset) -> odf::MetadataBlock {
+        target
+            .as_metadata_chain()
+            .get_block(&self.get_head(target).await)
+            .await
+            .unwrap()
+    }
+
+    /// Returns the hash of the block checked against expectations, which is
+    /// followed by the block recording the report
+    async fn get_reported_block_hash(&self, target: &ResolvedDataset) -> odf::Multihash {
+        let head_block = self.get_head_block(target).await;
+        assert_matches!(head_block.event, odf::MetadataEvent::SetAttachments(_));
+        head_block.prev_block_hash.unwrap()
+    }
+
+    async fn get_head(&self, target: &ResolvedDataset) -> odf::Multihash {
+        target
+            .as_metadata_chain()
+            .resolve_ref(&odf::BlockRef::Head)
+            .await
+            .unwrap()
+    }
+
+    async fn num_new_records(&self, target: &ResolvedDataset, block_hash: &odf::Multihash) -> u64 {
+        let block = target
+            .as_metadata_chain()
+            .get_block(block_hash)
+            .await
+            .unwrap();
+
+        let odf::MetadataEvent::AddData(add_data) = block.event else {
+            panic!("Expected AddData event");
+        };
+
+        let interval = add_data.new_data.unwrap().offset_interval;
+        interval.end - interval.start + 1
+    }
+
+    async fn ingest_data(
+        &self,
+        data_str: &str,
+        target: ResolvedDataset,
+    ) -> Result<PushIngestResult, PushIngestError> {
+        let data = std::io::Cursor::new(data_str.to_string());
+
+        let ingest_plan = self
+            .push_ingest_planner
+            .plan_ingest(target.clone(), None, PushIngestOpts::default())
+            .await
+            .unwrap();
+
+        self.push_ingest_executor
+            .ingest_from_stream(target, ingest_plan, Box::new(data), None)
+            .await
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
@@ -0,0 +1,441 @@
+// Copyright Kamu Data, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::assert_matches::assert_matches;
+use std::sync::Arc;
+
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use kamu::domain::*;
+use kamu::*;
+use kamu_accounts::CurrentAccountSubject;
+use odf::dataset::testing::create_test_dataset_from_snapshot;
+use odf::metadata::testing::MetadataFactory;
+use time_source::{SystemTimeSource, SystemTimeSourceStub};
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[tokio::test]
+async fn test_set_get_and_clear_sla() {
+    let harness = DatasetFreshnessTestHarness::new();
+    let target = harness.create_root_dataset().await;
+
+    assert_eq!(
+        harness
+            .dataset_freshness_svc
+            .get_sla(&target)
+            .await
+            .unwrap(),
+        None
+    );
+
+    let sla = DatasetFreshnessSla {
+        max_update_interval: Duration::hours(6),
+        max_watermark_lag: Some(Duration::days(1)),
+    };
+
+    harness
+        .dataset_freshness_svc
+        .set_sla(&target, Some(sla))
+        .await
+        .unwrap();
+
+    assert_eq!(
+        harness
+            .dataset_freshness_svc
+            .get_sla(&target)
+            .await
+            .unwrap(),
+        Some(sla)
+    );
+
+    harness
+        .dataset_freshness_svc
+        .set_sla(&target, None)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        harness
+            .dataset_freshness_svc
+            .get_sla(&target)
+            .await
+            .unwrap(),
+        None
+    );
+
+    // Clearing a missing SLA is not an error
+    harness
+        .dataset_freshness_svc
+        .set_sla(&target, None)
+        .await
+        .unwrap();
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[tokio::test]
+async fn test_invalid_sla() {
+    let harness = DatasetFreshnessTestHarness::new();
+    let target = harness.create_root_dataset().await;
+
+    for sla in [
+        DatasetFreshnessSla {
+            max_update_interval: Duration::seconds(30),
+            max_watermark_lag: None,
+        },
+        DatasetFreshnessSla {
+            max_update_interval: Duration::seconds(90),
+            max_watermark_lag: None,
+        },
+        DatasetFreshnessSla {
+            max_update_interval: Duration::hours(1),
+            max_watermark_lag: Some(Duration::zero()),
+        },
+    ] {
+        assert_matches!(
+            harness
+                .dataset_freshness_svc
+                .set_sla(&target, Some(sla))
+                .await,
+            Err(SetFreshnessSlaError::Invalid(_)),
+            "SLA {sla:?} should be rejected"
+        );
+    }
+
+    assert_eq!(
+        harness
+            .dataset_freshness_svc
+            .get_sla(&target)
+            .await
+            .unwrap(),
+        None
+    );
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[tokio::test]
+async fn test_freshness_by_update_interval() {
+    let harness = DatasetFreshnessTestHarness::new();
+    let target = harness.create_root_dataset().await;
+
+    // No SLA declared
+    let freshness = harness
+        .dataset_freshness_svc
+        .get_freshness(&target)
+        .await
+        .unwrap();
+    assert_eq!(freshness.status, FreshnessStatus::Undefined);
+    assert_eq!(freshness.last_updated_at, None);
+
+    harness
+        .dataset_freshness_svc
+        .set_sla(
+            &target,
+            Some(DatasetFreshnessSla {
+                max_update_interval: Duration::hours(1),
+                max_watermark_lag: None,
+            }),
+        )
+        .await
+        .unwrap();
+
+    // Dataset that never received data is stale
+    let freshness = harness
+        .dataset_freshness_svc
+        .get_freshness(&target)
+        .await
+        .unwrap();
+    assert_eq!(freshness.status, FreshnessStatus::Stale);
+
+    let updated_at = harness.t0 + Duration::minutes(10);
+    harness.add_data(&target, 0, 9, None, updated_at).await;
+
+    harness.set_now(updated_at + Duration::minutes(30));
+    let freshness = harness
+        .dataset_freshness_svc
+        .get_freshness(&target)
+        .await
+        .unwrap();
+    assert_eq!(freshness.status, FreshnessStatus::Fresh);
+    assert_eq!(freshness.last_updated_at, Some(updated_at));
+
+    harness.set_now(updated_at + Duration::minutes(61));
+    let freshness = harness
+        .dataset_freshness_svc
+        .get_freshness(&target)
+        .await
+        .unwrap();
+    assert_eq!(freshness.status, FreshnessStatus::Stale);
+    assert_eq!(freshness.last_updated_at, Some(updated_at));
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[tokio::test]
+async fn test_freshness_by_watermark_lag() {
+    let harness = DatasetFreshnessTestHarness::new();
+    let target = harness.create_root_dataset().await;
+
+    harness
+        .dataset_freshness_svc
+        .set_sla(
+            &target,
+            Some(DatasetFreshnessSla {
+                max_update_interval: Duration::days(1),
+                max_watermark_lag: Some(Duration::hours(1)),
+            }),
+        )
+        .await
+        .unwrap();
+
+    // Data is recent, but watermark lags behind too much
+    let updated_at = harness.t0 + Duration::minutes(10);
+    harness
+        .add_data(
+            &target,
+            0,
+            9,
+            Some(updated_at - Duration::hours(2)),
+            updated_at,
+        )
+        .await;
+
+    harness.set_now(updated_at);
+    let freshness = harness
+        .dataset_freshness_svc
+        .get_freshness(&target)
+        .await
+        .unwrap();
+    assert_eq!(freshness.status, FreshnessStatus::Stale);
+    assert_eq!(freshness.watermark, Some(updated_at - Duration::hours(2)));
+
+    // Watermark catches up
+    let updated_at = updated_at + Duration::minutes(10);
+    harness
+        .add_data(
+            &target,
+            10,
+            19,
+            Some(updated_at - Duration::minutes(30)),
+            updated_at,
+        )
+        .await;
+
+    harness.set_now(updated_at);
+    let freshness = harness
+        .dataset_freshness_svc
+        .get_freshness(&target)
+        .await
+        .unwrap();
+    assert_eq!(freshness.status, FreshnessStatus::Fresh);
+    assert_eq!(
+        freshness.watermark,
+        Some(updated_at - Duration::minutes(30))
+    );
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[tokio::test]
+async fn test_freshness_of_derivative_dataset() {
+    let harness = DatasetFreshnessTestHarness::new();
+    let root = harness.create_root_dataset().await;
+    let target = harness.create_derivative_dataset(&root).await;
+
+    harness
+        .dataset_freshness_svc
+        .set_sla(
+            &target,
+            Some(DatasetFreshnessSla {
+                max_update_interval: Duration::days(1),
+                max_watermark_lag: Some(Duration::hours(1)),
+            }),
+        )
+        .await
+        .unwrap();
+
+    // Watermark of `ExecuteTransform` blocks is taken into account
+    let updated_at = harness.t0 + Duration::minutes(10);
+    let watermark = updated_at - Duration::minutes(30);
+    target
+        .commit_event(
+            odf::MetadataEvent::ExecuteTransform(
+                MetadataFactory::execute_transform()
+                    .empty_query_inputs_from_particular_ids([root.get_id().clone()])
+                    .some_new_data_with_offset(0, 9)
+                    .new_watermark(Some(watermark))
+                    .build(),
+            ),
+            odf::dataset::CommitOpts {
+                system_time: Some(updated_at),
+                check_object_refs: false,
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+
+    harness.set_now(updated_at);
+    let freshness = harness
+        .dataset_freshness_svc
+        .get_freshness(&target)
+        .await
+        .unwrap();
+    assert_eq!(freshness.status, FreshnessStatus::Fresh);
+    assert_eq!(freshness.last_updated_at, Some(updated_at));
+    assert_eq!(freshness.watermark, Some(watermark));
+
+    // The current watermark still only considers `AddData` blocks
+    assert_eq!(
+        harness
+            .metadata_query_svc
+            .try_get_current_watermark(target.clone())
+            .await
+            .unwrap(),
+        None
+    );
+    assert_eq!(
+        harness
+            .metadata_query_svc
+            .try_get_last_data_watermark(target)
+            .await
+            .unwrap(),
+        Some(watermark)
+    );
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+// Harness
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+struct DatasetFreshnessTestHarness {
+    _temp_dir: tempfile::TempDir,
+    did_generator: Arc<dyn DidGenerator>,
+    dataset_registry: Arc<dyn DatasetRegistry>,
+    dataset_storage_unit_writer: Arc<dyn odf::DatasetStorageUnitWriter>,
+    dataset_freshness_svc: Arc<dyn DatasetFreshnessService>,
+    metadata_query_svc: Arc<dyn MetadataQueryService>,
+    time_source: Arc<SystemTimeSourceStub>,
+    t0: DateTime<Utc>,
+}
+
+impl DatasetFreshnessTestHarness {
+    fn new() -> Self {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let run_info_dir = temp_dir.path().join("run");
+        let datasets_dir = temp_dir.path().join("datasets");
+        std::fs::create_dir(&run_info_dir).unwrap();
+        std::fs::create_dir(&datasets_dir).unwrap();
+        let t0 = Utc.with_ymd_and_hms(2050, 1, 1, 12, 0, 0).unwrap();
+
+        let catalog = dill::CatalogBuilder::new()
+            .add::<DidGeneratorDefault>()
+            .add_value(RunInfoDir::new(run_info_dir))
+            .add_value(CurrentAccountSubject::new_test())
+            .add_value(TenancyConfig::SingleTenant)
+            .add_builder(odf::dataset::DatasetStorageUnitLocalFs::builder().with_root(datasets_dir))
+            .bind::<dyn odf::DatasetStorageUnit, odf::dataset::DatasetStorageUnitLocalFs>()
+            .bind::<dyn odf::DatasetStorageUnitWriter, odf::dataset::DatasetStorageUnitLocalFs>()
+            .add::<DatasetRegistrySoloUnitBridge>()
+            .add_value(SystemTimeSourceStub::new_set(t0))
+            .bind::<dyn SystemTimeSource, SystemTimeSourceStub>()
+            .add::<MetadataQueryServiceImpl>()
+            .add::<DatasetFreshnessServiceImpl>()
+            .build();
+
+        Self {
+            _temp_dir: temp_dir,
+            did_generator: catalog.get_one().unwrap(),
+            dataset_registry: catalog.get_one().unwrap(),
+            dataset_storage_unit_writer: catalog.get_one().unwrap(),
+            dataset_freshness_svc: catalog.get_one().unwrap(),
+            metadata_query_svc: catalog.get_one().unwrap(),
+            time_source: catalog.get_one().unwrap(),
+            t0,
+        }
+    }
+
+    fn set_now(&self, now: DateTime<Utc>) {
+        self.time_source.set(now);
+    }
+
+    async fn create_root_dataset(&self) -> ResolvedDataset {
+        let dataset_alias = odf::DatasetAlias::new(None, odf::DatasetName::new_unchecked("foo"));
+
+        let store_result = create_test_dataset_from_snapshot(
+            self.dataset_registry.as_ref(),
+            self.dataset_storage_unit_writer.as_ref(),
+            MetadataFactory::dataset_snapshot()
+                .name("foo")
+                .kind(odf::DatasetKind::Root)
+                .push_event(MetadataFactory::set_polling_source().build())
+                .build(),
+            self.did_generator.generate_dataset_id().0,
+            self.t0,
+        )
+        .await
+        .unwrap();
+
+        ResolvedDataset::from_stored(&store_result, &dataset_alias)
+    }
+
+    async fn create_derivative_dataset(&self, input: &ResolvedDataset) -> ResolvedDataset {
+        let dataset_alias = odf::DatasetAlias::new(None, odf::DatasetName::new_unchecked("bar"));
+
+        let store_result = create_test_dataset_from_snapshot(
+            self.dataset_registry.as_ref(),
+            self.dataset_storage_unit_writer.as_ref(),
+            MetadataFactory::dataset_snapshot()
+                .name("bar")
+                .kind(odf::DatasetKind::Derivative)
+                .push_event(
+                    MetadataFactory::set_transform()
+                        .inputs_from_refs([input.get_id().as_local_ref()])
+                        .build(),
+                )
+                .push_event(MetadataFactory::set_data_schema().build())
+                .build(),
+            self.did_generator.generate_dataset_id().0,
+            self.t0,
+        )
+        .await
+        .unwrap();
+
+        ResolvedDataset::from_stored(&store_result, &dataset_alias)
+    }
+
+    async fn add_data(
+        &self,
+        target: &ResolvedDataset,
+        start: u64,
+        end: u64,
+        new_watermark: Option<DateTime<Utc>>,
+        system_time: DateTime<Utc>,
+    ) {
+        target
+            .commit_event(
+                odf::MetadataEvent::AddData(
+                    MetadataFactory::add_data()
+                        .some_new_data_with_offset(start, end)
+                        .new_watermark(new_watermark)
+                        .build(),
+                ),
+                odf::dataset::CommitOpts {
+                    system_time: Some(system_time),
+                    check_object_refs: false,
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
@@ -0,0 +1,376 @@
+// Copyright Kamu Data, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::assert_matches::assert_matches;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use chrono::{DateTime, TimeZone, Utc};
+use kamu::domain::*;
+use kamu::*;
+use kamu_accounts::CurrentAccountSubject;
+use odf::dataset::testing::create_test_dataset_from_snapshot;
+use odf::metadata::testing::MetadataFactory;
+use time_source::{SystemTimeSource, SystemTimeSourceStub};
+use url::Url;
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[test_group::group(ingest, datafusion)]
+#[tokio::test]
+async fn test_iceberg_export_snapshot_per_block() {
+    let harness = IcebergExportTestHarness::new();
+    let target = harness.create_root_dataset("foo").await;
+
+    harness
+        .ingest_data("city,population\nA,1000\nB,2000\n", target.clone())
+        .await;
+    let head_1 = harness.get_head(&target).await;
+    harness
+        .ingest_data("city,population\nC,3000\n", target.clone())
+        .await;
+    let head_2 = harness.get_head(&target).await;
+
+    let result = harness
+        .export_svc
+        .export_to_iceberg(target.clone(), &harness.table_url())
+        .await
+        .unwrap();
+
+    assert_eq!(
+        result,
+        IcebergExportResult {
+            old_head: None,
+            new_head: Some(head_2.clone()),
+            num_snapshots: 2,
+            num_records: 3,
+        }
+    );
+
+    let metadata = harness.read_table_metadata(1);
+    assert_eq!(metadata["format-version"], 2);
+    assert_eq!(
+        metadata["properties"]["kamu.dataset-id"],
+        target.get_id().to_string()
+    );
+    assert_eq!(
+        harness.snapshot_block_hashes(&metadata),
+        vec![head_1.to_string(), head_2.to_string()]
+    );
+
+    let current_snapshot = metadata["snapshots"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|s| s["snapshot-id"] == metadata["current-snapshot-id"])
+        .unwrap();
+    assert_eq!(current_snapshot["summary"]["operation"], "append");
+    assert_eq!(current_snapshot["summary"]["total-records"], "3");
+    assert_eq!(current_snapshot["summary"]["kamu.offset-start"], "2");
+    assert_eq!(current_snapshot["summary"]["kamu.offset-end"], "2");
+
+    let column_names: Vec<_> = metadata["schemas"][0]["fields"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|f| f["name"].as_str().unwrap())
+        .collect();
+    assert_eq!(
+        column_names,
+        [
+            "offset",
+            "op",
+            "system_time",
+            "event_time",
+            "city",
+            "population"
+        ]
+    );
+
+    // Data files are copied into the table, not referenced in the dataset
+    assert_eq!(
+        std::fs::read_dir(harness.table_dir().join("data"))
+            .unwrap()
+            .count(),
+        2
+    );
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[test_group::group(ingest, datafusion)]
+#[tokio::test]
+async fn test_iceberg_export_appends_new_blocks_only() {
+    let harness = IcebergExportTestHarness::new();
+    let target = harness.create_root_dataset("foo").await;
+
+    harness
+        .ingest_data("city,population\nA,1000\n", target.clone())
+        .await;
+    let head_1 = harness.get_head(&target).await;
+
+    harness
+        .export_svc
+        .export_to_iceberg(target.clone(), &harness.table_url())
+        .await
+        .unwrap();
+
+    harness
+        .ingest_data("city,population\nB,2000\n", target.clone())
+        .await;
+    let head_2 = harness.get_head(&target).await;
+
+    let result = harness
+        .export_svc
+        .export_to_iceberg(target.clone(), &harness.table_url())
+        .await
+        .unwrap();
+
+    assert_eq!(
+        result,
+        IcebergExportResult {
+            old_head: Some(head_1.clone()),
+            new_head: Some(head_2.clone()),
+            num_snapshots: 1,
+            num_records: 1,
+        }
+    );
+
+    let metadata = harness.read_table_metadata(2);
+    assert_eq!(
+        harness.snapshot_block_hashes(&metadata),
+        vec![head_1.to_string(), head_2.to_string()]
+    );
+    assert_eq!(metadata["metadata-log"].as_array().unwrap().len(), 1);
+    assert_eq!(harness.read_version_hint(), "2");
+
+    // Nothing to export
+    let result = harness
+        .export_svc
+        .export_to_iceberg(target.clone(), &harness.table_url())
+        .await
+        .unwrap();
+
+    assert_eq!(result.num_snapshots, 0);
+    assert_eq!(result.new_head, Some(head_2));
+    assert_eq!(harness.read_version_hint(), "2");
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[test_group::group(ingest, datafusion)]
+#[tokio::test]
+async fn test_iceberg_export_table_of_another_dataset() {
+    let harness = IcebergExportTestHarness::new();
+    let foo = harness.create_root_dataset("foo").await;
+    let bar = harness.create_root_dataset("bar").await;
+
+    harness
+        .ingest_data("city,population\nA,1000\n", foo.clone())
+        .await;
+    harness
+        .ingest_data("city,population\nB,2000\n", bar.clone())
+        .await;
+
+    harness
+        .export_svc
+        .export_to_iceberg(foo, &harness.table_url())
+        .await
+        .unwrap();
+
+    assert_matches!(
+        harness
+            .export_svc
+            .export_to_iceberg(bar, &harness.table_url())
+            .await,
+        Err(ExportError::IcebergTableDatasetMismatch(_))
+    );
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[test_group::group(ingest, datafusion)]
+#[tokio::test]
+async fn test_iceberg_export_table_out_of_sync() {
+    let harness = IcebergExportTestHarness::new();
+    let target = harness.create_root_dataset("foo").await;
+
+    harness
+        .ingest_data("city,population\nA,1000\n", target.clone())
+        .await;
+
+    harness
+        .export_svc
+        .export_to_iceberg(target.clone(), &harness.table_url())
+        .await
+        .unwrap();
+
+    // Simulate the dataset history being rewritten after the export
+    let missing_block = odf::Multihash::from_digest_sha3_256(b"missing");
+    let metadata_path = harness.table_dir().join("metadata/v1.metadata.json");
+    let metadata = std::fs::read_to_string(&metadata_path).unwrap();
+    let head = harness.get_head(&target).await;
+    std::fs::write(
+        &metadata_path,
+        metadata.replace(&head.to_string(), &missing_block.to_string()),
+    )
+    .unwrap();
+
+    assert_matches!(
+        harness
+            .export_svc
+            .export_to_iceberg(target, &harness.table_url())
+            .await,
+        Err(ExportError::IcebergTableOutOfSync(e)) if e.last_exported_block == missing_block
+    );
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+struct IcebergExportTestHarness {
+    temp_dir: tempfile::TempDir,
+    did_generator: Arc<dyn DidGenerator>,
+    dataset_registry: Arc<dyn DatasetRegistry>,
+    dataset_storage_unit_writer: Arc<dyn odf::DatasetStorageUnitWriter>,
+    push_ingest_planner: Arc<dyn PushIngestPlanner>,
+    push_ingest_executor: Arc<dyn PushIngestExecutor>,
+    export_svc: Arc<dyn ExportService>,
+    current_date_time: DateTime<Utc>,
+}
+
+impl IcebergExportTestHarness {
+    fn new() -> Self {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let run_info_dir = temp_dir.path().join("run");
+        let datasets_dir = temp_dir.path().join("datasets");
+        std::fs::create_dir(&run_info_dir).unwrap();
+        std::fs::create_dir(&datasets_dir).unwrap();
+        let current_date_time = Utc.with_ymd_and_hms(2050, 1, 1, 12, 0, 0).unwrap();
+
+        let catalog = dill::CatalogBuilder::new()
+            .add::<DidGeneratorDefault>()
+            .add_value(RunInfoDir::new(run_info_dir))
+            .add_value(CurrentAccountSubject::new_test())
+            .add_value(TenancyConfig::SingleTenant)
+            .add_builder(odf::dataset::DatasetStorageUnitLocalFs::builder().with_root(datasets_dir))
+            .bind::<dyn odf::DatasetStorageUnit, odf::dataset::DatasetStorageUnitLocalFs>()
+            .bind::<dyn odf::DatasetStorageUnitWriter, odf::dataset::DatasetStorageUnitLocalFs>()
+            .add::<DatasetRegistrySoloUnitBridge>()
+            .add_value(SystemTimeSourceStub::new_set(current_date_time))
+            .bind::<dyn SystemTimeSource, SystemTimeSourceStub>()
+            .add::<ObjectStoreRegistryImpl>()
+            .add::<ObjectStoreBuilderLocalFs>()
+            .add::<DataFormatRegistryImpl>()
+            .add::<PushIngestExecutorImpl>()
+            .add::<PushIngestPlannerImpl>()
+            .add::<EngineProvisionerNull>()
+            .add::<ExportServiceImpl>()
+            .build();
+
+        Self {
+            temp_dir,
+            did_generator: catalog.get_one().unwrap(),
+            dataset_registry: catalog.get_one().unwrap(),
+            dataset_storage_unit_writer: catalog.get_one().unwrap(),
+            push_ingest_planner: catalog.get_one().unwrap(),
+            push_ingest_executor: catalog.get_one().unwrap(),
+            export_svc: catalog.get_one().unwrap(),
+            current_date_time,
+        }
+    }
+
+    fn table_dir(&self) -> PathBuf {
+        self.temp_dir.path().join("tables").join("foo")
+    }
+
+    fn table_url(&self) -> Url {
+        Url::from_directory_path(self.table_dir()).unwrap()
+    }
+
+    fn read_version_hint(&self) -> String {
+        std::fs::read_to_string(self.table_dir().join("metadata/version-hint.text")).unwrap()
+    }
+
+    fn read_table_metadata(&self, version: u64) -> serde_json::Value {
+        let path = self
+            .table_dir()
+            .join(format!("metadata/v{version}.metadata.json"));
+        serde_json::from_str(&std::fs::read_to_string(path).unwrap()).unwrap()
+    }
+
+    fn snapshot_block_hashes(&self, metadata: &serde_json::Value) -> Vec<String> {
+        metadata["snapshots"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|s| {
+                s["summary"]["kamu.block-hash"]
+                    .as_str()
+                    .unwrap()
+                    .to_string()
+            })
+            .collect()
+    }
+
+    async fn create_root_dataset(&self, name: &str) -> ResolvedDataset {
+        let dataset_alias = odf::DatasetAlias::new(None, odf::DatasetName::new_unchecked(name));
+
+        let store_result = create_test_dataset_from_snapshot(
+            self.dataset_registry.as_ref(),
+            self.dataset_storage_unit_writer.as_ref(),
+            MetadataFactory::dataset_snapshot()
+                .name(name)
+                .kind(odf::DatasetKind::Root)
+                .push_event(
+                    MetadataFactory::add_push_source()
+                        .read(odf::metadata::ReadStepCsv {
+                            header: Some(true),
+                            schema: Some(vec![
+                                "city STRING".to_string(),
+                                "population BIGINT".to_string(),
+                            ]),
+                            ..odf::metadata::ReadStepCsv::default()
+                        })
+                        .build(),
+                )
+                .build(),
+            self.did_generator.generate_dataset_id().0,
+            self.current_date_time,
+        )
+        .await
+        .unwrap();
+
+        ResolvedDataset::from_stored(&store_result, &dataset_alias)
+    }
+
+    async fn get_head(&self, target: &ResolvedDataset) -> odf::Multihash {
+        target
+            .as_metadata_chain()
+            .resolve_ref(&odf::BlockRef::Head)
+            .await
+            .unwrap()
+    }
+
+    async fn ingest_data(&self, data_str: &str, target: ResolvedDataset) {
+        let data = std::io::Cursor::new(data_str.to_string());
+
+        let ingest_plan = self
+            .push_ingest_planner
+            .plan_ingest(target.clone(), None, PushIngestOpts::default())
+            .await
+            .unwrap();
+
+        self.push_ingest_executor
+            .ingest_from_stream(target, ingest_plan, Box::new(data), None)
+            .await
+            .unwrap();
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
@@ -436,6 +436,125 @@ async fn test_dataset_tail_empty_dataset() {
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
 
+#[test_group::group(engine, datafusion)]
+#[test_log::test(tokio::test)]
+async fn test_dataset_get_changes() {
+    let tempdir = tempfile::tempdir().unwrap();
+    let catalog = create_catalog_with_local_workspace(
+        tempdir.path(),
+        MockDatasetActionAuthorizer::allowing(),
+    );
+    let target = create_test_dataset(&catalog, tempdir.path(), "foo").await;
+    let dataset_ref = odf::DatasetRef::from(target.get_alias());
+
+    let query_svc = catalog.get_one::<dyn QueryService>().unwrap();
+
+    // From the beginning
+    let df = query_svc
+        .get_changes(&dataset_ref, None, None, 10)
+        .await
+        .unwrap();
+
+    odf::utils::testing::assert_data_eq(
+        df,
+        indoc::indoc!(
+            r#"
+            +--------+------+
+            | offset | blah |
+            +--------+------+
+            | 0      | a    |
+            | 1      | b    |
+            | 2      | c    |
+            | 3      | d    |
+            +--------+------+
+            "#
+        ),
+    )
+    .await;
+
+    // Crosses block boundary and respects the limit
+    let df = query_svc
+        .get_changes(&dataset_ref, Some(0), None, 2)
+        .await
+        .unwrap();
+
+    odf::utils::testing::assert_data_eq(
+        df,
+        indoc::indoc!(
+            r#"
+            +--------+------+
+            | offset | blah |
+            +--------+------+
+            | 1      | b    |
+            | 2      | c    |
+            +--------+------+
+            "#
+        ),
+    )
+    .await;
+
+    // Skips the fully consumed block
+    let df = query_svc
+        .get_changes(&dataset_ref, Some(1), None, 10)
+        .await
+        .unwrap();
+
+    odf::utils::testing::assert_data_eq(
+        df,
+        indoc::indoc!(
+            r#"
+            +--------+------+
+            | offset | blah |
+            +--------+------+
+            | 2      | c    |
+            | 3      | d    |
+            +--------+------+
+            "#
+        ),
+    )
+    .await;
+
+    // Nothing new
+    let df = query_svc
+        .get_changes(&dataset_ref, Some(3), None, 10)
+        .await
+        .unwrap();
+    assert_eq!(df.count().await.unwrap(), 0);
+
+    // As of the first block with data
+    use odf::dataset::MetadataChainExt;
+    let first_data_block_hash = target
+        .as_metadata_chain()
+        .last_data_block()
+        .await
+        .unwrap()
+        .into_block()
+        .unwrap()
+        .prev_block_hash
+        .unwrap();
+
+    let df = query_svc
+        .get_changes(&dataset_ref, Some(0), Some(first_data_block_hash), 10)
+        .await
+        .unwrap();
+
+    odf::utils::testing::assert_data_eq(
+        df,
+        indoc::indoc!(
+            r#"
+            +--------+------+
+            | offset | blah |
+            +--------+------+
+            | 1      | b    |
+            +--------+------+
+            "#
+        ),
+    )
+    .await;
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
 async fn test_dataset_tail_unauthorized_common(catalog: dill::Catalog, tempdir: &TempDir) {
     let target = create_test_dataset(&catalog, tempdir.path(), "foo").await;
 
@@ -1292,3 +1411,62 @@ async fn test_sql_statement_with_state_cte() {
 }
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[test_log::test(tokio::test)]
+async fn test_known_engines_include_custom_ones() {
+    let tempdir = tempfile::tempdir().unwrap();
+    let datasets_dir = tempdir.path().join("datasets");
+    std::fs::create_dir(&datasets_dir).unwrap();
+
+    let catalog = dill::CatalogBuilder::new()
+        .add::<DidGeneratorDefault>()
+        .add::<SystemTimeSourceDefault>()
+        .add_value(TenancyConfig::SingleTenant)
+        .add_builder(odf::dataset::DatasetStorageUnitLocalFs::builder().with_root(datasets_dir))
+        .bind::<dyn odf::DatasetStorageUnit, odf::dataset::DatasetStorageUnitLocalFs>()
+        .add::<DatasetRegistrySoloUnitBridge>()
+        .add::<QueryServiceImpl>()
+        .add::<ObjectStoreRegistryImpl>()
+        .add::<ObjectStoreBuilderLocalFs>()
+        .add_value(CurrentAccountSubject::new_test())
+        .add_value(MockDatasetActionAuthorizer::new())
+        .bind::<dyn auth::DatasetActionAuthorizer, MockDatasetActionAuthorizer>()
+        .add_value(EngineRegistryConfig {
+            custom_engines: vec![
+                CustomEngineSpec {
+                    name: "duckdb".to_string(),
+                    image: "example.com/engine-duckdb:0.1.0".to_string(),
+                    dialect: QueryDialect::SqlDataFusion,
+                    io_strategy: EngineIoStrategyKind::Auto,
+                },
+                // Built-in engines cannot be overridden
+                CustomEngineSpec {
+                    name: "Spark".to_string(),
+                    image: "example.com/engine-spark:0.1.0".to_string(),
+                    dialect: QueryDialect::SqlSpark,
+                    io_strategy: EngineIoStrategyKind::RemoteProxy,
+                },
+            ],
+        })
+        .build();
+
+    let query_svc = catalog.get_one::<dyn QueryService>().unwrap();
+    let engines = query_svc.get_known_engines().await.unwrap();
+
+    assert_eq!(
+        engines
+            .iter()
+            .map(|e| (e.name.as_str(), e.latest_image.as_str()))
+            .collect::<Vec<_>>(),
+        [
+            ("Spark", utils::docker_images::SPARK),
+            ("Flink", utils::docker_images::FLINK),
+            ("DataFusion", utils::docker_images::DATAFUSION),
+            ("RisingWave", utils::docker_images::RISINGWAVE),
+            ("duckdb", "example.com/engine-duckdb:0.1.0"),
+        ]
+    );
+    assert_eq!(engines[4].dialect, QueryDialect::SqlDataFusion);
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
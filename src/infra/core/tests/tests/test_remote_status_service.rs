@@ -229,6 +229,60 @@ async fn test_check_remotes_status_not_found() {
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
 
+#[tokio::test]
+async fn test_check_pull_remotes_status_remote_ahead() {
+    let harness = RemoteStatusTestHarness::new();
+
+    let local_ds = harness.create_dataset().await;
+
+    let head = &local_ds.head;
+    let schema_block = RemoteStatusTestHarness::schema_block(head);
+    let local_chain = local_ds.dataset.as_metadata_chain();
+    let _ = local_chain
+        .append(schema_block, odf::dataset::AppendOpts::default())
+        .await
+        .unwrap();
+
+    let remote = harness.push_dataset(&local_ds.dataset_handle).await;
+    harness
+        .remote_aliases_reg
+        .get_remote_aliases(&local_ds.dataset_handle)
+        .await
+        .unwrap()
+        .add(&remote, RemoteAliasKind::Pull)
+        .await
+        .unwrap();
+
+    local_chain
+        .set_ref(
+            &odf::BlockRef::Head,
+            head,
+            odf::dataset::SetRefOpts {
+                validate_block_present: true,
+                check_ref_is: None,
+            },
+        )
+        .await
+        .unwrap();
+
+    let result = harness
+        .remote_status_service
+        .check_pull_remotes_status(&local_ds.dataset_handle)
+        .await
+        .unwrap();
+
+    assert_eq!(result.statuses.len(), 1);
+    let status = result.statuses.first().unwrap();
+
+    assert_eq!(&status.remote, &remote);
+    assert_matches!(
+        status.check_result,
+        Ok(CompareChainsResult::LhsBehind { .. })
+    );
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
 #[oop::extend(BaseRepoHarness, base_repo_harness)]
 struct RemoteStatusTestHarness {
     base_repo_harness: BaseRepoHarness,
@@ -0,0 +1,438 @@
+// Copyright Kamu Data, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::assert_matches::assert_matches;
+use std::sync::Arc;
+
+use chrono::{DateTime, TimeZone, Utc};
+use kamu::domain::*;
+use kamu::*;
+use kamu_accounts::CurrentAccountSubject;
+use odf::dataset::testing::create_test_dataset_from_snapshot;
+use odf::metadata::testing::MetadataFactory;
+use tempfile::TempDir;
+use time_source::{SystemTimeSource, SystemTimeSourceStub};
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[test_log::test(tokio::test)]
+async fn test_reprocess_plan_without_executed_transforms() {
+    let harness = ReprocessTestHarness::new();
+    let (root, deriv, _) = harness.root_and_deriv().await;
+
+    harness.append_data_block(&root, 0, 9).await;
+
+    let plan = harness
+        .reprocess_planner
+        .plan_reprocess(deriv, ReprocessOptions::default())
+        .await
+        .unwrap();
+
+    assert!(plan.request.is_none());
+    assert!(plan.new_set_transform.is_none());
+    assert!(plan.replaced_data_slices.is_empty());
+    assert_eq!(plan.prev_state.offset, None);
+    assert_eq!(plan.prev_state.query_inputs, vec![]);
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[test_log::test(tokio::test)]
+async fn test_reprocess_plan_entire_history() {
+    let harness = ReprocessTestHarness::new();
+    let (root, deriv, source) = harness.root_and_deriv().await;
+
+    let root_head_1 = harness.append_data_block(&root, 0, 9).await;
+    harness
+        .append_execute_transform(&deriv, &root, None, &root_head_1, 0, 9)
+        .await;
+    let root_head_2 = harness.append_data_block(&root, 10, 19).await;
+    let deriv_head = harness
+        .append_execute_transform(&deriv, &root, Some(&root_head_1), &root_head_2, 10, 19)
+        .await;
+
+    let plan = harness
+        .reprocess_planner
+        .plan_reprocess(deriv.clone(), ReprocessOptions::default())
+        .await
+        .unwrap();
+
+    assert!(plan.new_set_transform.is_none());
+    assert_eq!(plan.replaced_data_slices.len(), 2);
+    assert_eq!(plan.system_time, harness.system_time_source.now());
+
+    assert_eq!(plan.prev_state.head, deriv_head);
+    assert_eq!(plan.prev_state.offset, Some(19));
+    assert_eq!(
+        plan.prev_state.query_inputs,
+        vec![odf::metadata::ExecuteTransformInput {
+            dataset_id: root.get_id().clone(),
+            prev_block_hash: Some(root_head_2.clone()),
+            new_block_hash: None,
+            prev_offset: Some(19),
+            new_offset: None,
+        }]
+    );
+
+    let request = plan.request.unwrap();
+    assert_eq!(request.transform, source.transform);
+    assert_eq!(request.head, deriv_head);
+    assert_eq!(request.prev_offset, None);
+    assert_eq!(request.inputs.len(), 1);
+
+    let input = &request.inputs[0];
+    assert_eq!(input.dataset_handle, *root.get_handle());
+    assert_eq!(input.prev_block_hash, None);
+    assert_eq!(input.new_block_hash, Some(root_head_2));
+    assert_eq!(input.prev_offset, None);
+    assert_eq!(input.new_offset, Some(19));
+    assert_eq!(input.data_slices.len(), 2);
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[test_log::test(tokio::test)]
+async fn test_reprocess_plan_from_offset() {
+    let harness = ReprocessTestHarness::new();
+    let (root, deriv, _) = harness.root_and_deriv().await;
+
+    let root_head_1 = harness.append_data_block(&root, 0, 9).await;
+    harness
+        .append_execute_transform(&deriv, &root, None, &root_head_1, 0, 9)
+        .await;
+    let root_head_2 = harness.append_data_block(&root, 10, 19).await;
+    harness
+        .append_execute_transform(&deriv, &root, Some(&root_head_1), &root_head_2, 10, 19)
+        .await;
+
+    let plan = harness
+        .reprocess_planner
+        .plan_reprocess(
+            deriv,
+            ReprocessOptions {
+                start: Some(ReprocessStart::Offset(15)),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(plan.replaced_data_slices.len(), 1);
+
+    let request = plan.request.unwrap();
+    assert_eq!(request.prev_offset, Some(9));
+
+    let input = &request.inputs[0];
+    assert_eq!(input.prev_block_hash, Some(root_head_1));
+    assert_eq!(input.new_block_hash, Some(root_head_2));
+    assert_eq!(input.prev_offset, Some(9));
+    assert_eq!(input.new_offset, Some(19));
+    assert_eq!(input.data_slices.len(), 1);
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[test_log::test(tokio::test)]
+async fn test_reprocess_plan_with_new_transform() {
+    let harness = ReprocessTestHarness::new();
+    let (root, deriv, source) = harness.root_and_deriv().await;
+
+    let root_head_1 = harness.append_data_block(&root, 0, 9).await;
+    harness
+        .append_execute_transform(&deriv, &root, None, &root_head_1, 0, 9)
+        .await;
+
+    // Same transform is not considered a change
+    let plan = harness
+        .reprocess_planner
+        .plan_reprocess(
+            deriv.clone(),
+            ReprocessOptions {
+                new_transform: Some(source.transform.clone()),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+    assert!(plan.new_set_transform.is_none());
+
+    let new_transform = MetadataFactory::transform()
+        .query("select event_time, city, population * 2 as population from foo")
+        .build();
+
+    let plan = harness
+        .reprocess_planner
+        .plan_reprocess(
+            deriv,
+            ReprocessOptions {
+                new_transform: Some(new_transform),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+
+    // Inputs are preserved in their normalized form
+    let new_set_transform = plan.new_set_transform.unwrap();
+    assert_eq!(
+        new_set_transform.inputs,
+        vec![odf::metadata::TransformInput {
+            dataset_ref: root.get_id().as_local_ref(),
+            alias: Some("foo".to_string()),
+        }]
+    );
+
+    let odf::metadata::Transform::Sql(sql) = &new_set_transform.transform;
+    assert_eq!(sql.query, None);
+    assert_eq!(
+        sql.queries,
+        Some(vec![odf::metadata::SqlQueryStep {
+            alias: None,
+            query: "select event_time, city, population * 2 as population from foo".to_string(),
+        }])
+    );
+
+    let request = plan.request.unwrap();
+    assert_eq!(request.transform, new_set_transform.transform);
+    assert_eq!(request.prev_checkpoint, None);
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[test_log::test(tokio::test)]
+async fn test_reprocess_plan_start_not_found() {
+    let harness = ReprocessTestHarness::new();
+    let (root, deriv, _) = harness.root_and_deriv().await;
+
+    let root_head_1 = harness.append_data_block(&root, 0, 9).await;
+    harness
+        .append_execute_transform(&deriv, &root, None, &root_head_1, 0, 9)
+        .await;
+
+    let a_hash_not_present_in_chain =
+        odf::Multihash::from_multibase("zW1a3CNT52HXiJNniLkWMeev3CPRy9QiNRMWGyTrVNg4hY8").unwrap();
+
+    let res = harness
+        .reprocess_planner
+        .plan_reprocess(
+            deriv.clone(),
+            ReprocessOptions {
+                start: Some(ReprocessStart::Block(a_hash_not_present_in_chain)),
+                ..Default::default()
+            },
+        )
+        .await;
+    assert_matches!(res, Err(ReprocessPlanningError::StartNotFound(_)));
+
+    let res = harness
+        .reprocess_planner
+        .plan_reprocess(
+            deriv,
+            ReprocessOptions {
+                start: Some(ReprocessStart::Offset(10)),
+                ..Default::default()
+            },
+        )
+        .await;
+    assert_matches!(res, Err(ReprocessPlanningError::StartNotFound(_)));
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[test_log::test(tokio::test)]
+async fn test_reprocess_plan_root_dataset() {
+    let harness = ReprocessTestHarness::new();
+    let (root, _, _) = harness.root_and_deriv().await;
+
+    let res = harness
+        .reprocess_planner
+        .plan_reprocess(root, ReprocessOptions::default())
+        .await;
+    assert_matches!(res, Err(ReprocessPlanningError::TransformNotDefined(_)));
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+struct ReprocessTestHarness {
+    _tempdir: TempDir,
+    system_time_source: Arc<dyn SystemTimeSource>,
+    did_generator: Arc<dyn DidGenerator>,
+    dataset_registry: Arc<dyn DatasetRegistry>,
+    dataset_storage_unit_writer: Arc<dyn odf::DatasetStorageUnitWriter>,
+    reprocess_planner: Arc<dyn ReprocessPlanner>,
+}
+
+impl ReprocessTestHarness {
+    fn new() -> Self {
+        let tempdir = tempfile::tempdir().unwrap();
+        let datasets_dir = tempdir.path().join("datasets");
+        std::fs::create_dir(&datasets_dir).unwrap();
+
+        let catalog = dill::CatalogBuilder::new()
+            .add::<DidGeneratorDefault>()
+            .add_value(CurrentAccountSubject::new_test())
+            .add_value(TenancyConfig::SingleTenant)
+            .add_builder(odf::dataset::DatasetStorageUnitLocalFs::builder().with_root(datasets_dir))
+            .bind::<dyn odf::DatasetStorageUnit, odf::dataset::DatasetStorageUnitLocalFs>()
+            .bind::<dyn odf::DatasetStorageUnitWriter, odf::dataset::DatasetStorageUnitLocalFs>()
+            .add::<DatasetRegistrySoloUnitBridge>()
+            .add_value(SystemTimeSourceStub::new_set(
+                Utc.with_ymd_and_hms(2050, 1, 1, 12, 0, 0).unwrap(),
+            ))
+            .bind::<dyn SystemTimeSource, SystemTimeSourceStub>()
+            .add::<ReprocessPlannerImpl>()
+            .build();
+
+        Self {
+            _tempdir: tempdir,
+            system_time_source: catalog.get_one().unwrap(),
+            did_generator: catalog.get_one().unwrap(),
+            dataset_registry: catalog.get_one().unwrap(),
+            dataset_storage_unit_writer: catalog.get_one().unwrap(),
+            reprocess_planner: catalog.get_one().unwrap(),
+        }
+    }
+
+    async fn create_dataset(&self, snapshot: odf::DatasetSnapshot) -> ResolvedDataset {
+        let alias = snapshot.name.clone();
+
+        let stored = create_test_dataset_from_snapshot(
+            self.dataset_registry.as_ref(),
+            self.dataset_storage_unit_writer.as_ref(),
+            snapshot,
+            self.did_generator.generate_dataset_id().0,
+            self.system_time_source.now(),
+        )
+        .await
+        .unwrap();
+
+        ResolvedDataset::from_stored(&stored, &alias)
+    }
+
+    async fn root_and_deriv(
+        &self,
+    ) -> (
+        ResolvedDataset,
+        ResolvedDataset,
+        odf::metadata::SetTransform,
+    ) {
+        let root = self
+            .create_dataset(
+                MetadataFactory::dataset_snapshot()
+                    .name("foo")
+                    .kind(odf::DatasetKind::Root)
+                    .push_event(MetadataFactory::set_data_schema().build())
+                    .build(),
+            )
+            .await;
+
+        let source = MetadataFactory::set_transform()
+            .inputs_from_refs([root.get_alias()])
+            .build();
+
+        let deriv = self
+            .create_dataset(
+                MetadataFactory::dataset_snapshot()
+                    .name("bar")
+                    .kind(odf::DatasetKind::Derivative)
+                    .push_event(source.clone())
+                    .push_event(MetadataFactory::set_data_schema().build())
+                    .build(),
+            )
+            .await;
+
+        (root, deriv, source)
+    }
+
+    async fn append_block(
+        &self,
+        target: &ResolvedDataset,
+        event: impl Into<odf::MetadataEvent>,
+        system_time: DateTime<Utc>,
+    ) -> odf::Multihash {
+        let chain = target.as_metadata_chain();
+        let prev_head = chain.resolve_ref(&odf::BlockRef::Head).await.unwrap();
+        let prev_block = chain.get_block(&prev_head).await.unwrap();
+
+        chain
+            .append(
+                MetadataFactory::metadata_block(event)
+                    .system_time(system_time)
+                    .prev(&prev_head, prev_block.sequence_number)
+                    .build(),
+                odf::dataset::AppendOpts::default(),
+            )
+            .await
+            .unwrap()
+    }
+
+    fn data_slice(start: u64, end: u64) -> odf::DataSlice {
+        odf::DataSlice {
+            logical_hash: odf::Multihash::from_digest_sha3_256(format!("{start}-{end}").as_bytes()),
+            physical_hash: odf::Multihash::from_digest_sha3_256(
+                format!("{start}-{end}").as_bytes(),
+            ),
+            offset_interval: odf::metadata::OffsetInterval { start, end },
+            size: 10,
+        }
+    }
+
+    async fn append_data_block(
+        &self,
+        root: &ResolvedDataset,
+        start: u64,
+        end: u64,
+    ) -> odf::Multihash {
+        self.append_block(
+            root,
+            odf::metadata::AddData {
+                prev_checkpoint: None,
+                prev_offset: start.checked_sub(1),
+                new_data: Some(Self::data_slice(start, end)),
+                new_checkpoint: None,
+                new_watermark: Some(Utc.with_ymd_and_hms(2020, 1, 1, 10, 0, 0).unwrap()),
+                new_source_state: None,
+            },
+            Utc.with_ymd_and_hms(2020, 1, 1, 11, 0, 0).unwrap(),
+        )
+        .await
+    }
+
+    async fn append_execute_transform(
+        &self,
+        deriv: &ResolvedDataset,
+        root: &ResolvedDataset,
+        prev_block_hash: Option<&odf::Multihash>,
+        new_block_hash: &odf::Multihash,
+        start: u64,
+        end: u64,
+    ) -> odf::Multihash {
+        self.append_block(
+            deriv,
+            odf::metadata::ExecuteTransform {
+                query_inputs: vec![odf::metadata::ExecuteTransformInput {
+                    dataset_id: root.get_id().clone(),
+                    prev_block_hash: prev_block_hash.cloned(),
+                    new_block_hash: Some(new_block_hash.clone()),
+                    prev_offset: start.checked_sub(1),
+                    new_offset: Some(end),
+                }],
+                prev_checkpoint: None,
+                prev_offset: start.checked_sub(1),
+                new_data: Some(Self::data_slice(start, end)),
+                new_checkpoint: None,
+                new_watermark: Some(Utc.with_ymd_and_hms(2020, 1, 1, 10, 0, 0).unwrap()),
+            },
+            Utc.with_ymd_and_hms(2020, 1, 1, 12, 0, 0).unwrap(),
+        )
+        .await
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
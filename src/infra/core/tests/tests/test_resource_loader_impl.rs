@@ -9,7 +9,7 @@
 
 use std::path::Path;
 
-use kamu::domain::{ResourceError, ResourceLoader};
+use kamu::domain::{ResourceError, ResourceLoader, TemplateError, TemplateVars};
 use kamu::ResourceLoaderImpl;
 use odf::metadata::testing::MetadataFactory;
 use odf::serde::yaml::YamlDatasetSnapshotSerializer;
@@ -100,3 +100,97 @@ async fn test_load_from_http_url_404() {
         Err(ResourceError::NotFound { .. })
     ));
 }
+
+#[tokio::test]
+async fn test_load_from_template() {
+    let tempdir = tempfile::tempdir().unwrap();
+
+    let path = tempdir.path().join("template.yaml");
+    std::fs::write(
+        &path,
+        indoc::indoc!(
+            r#"
+            kind: DatasetSnapshot
+            version: 1
+            content:
+              name: weather.${{ vars.city }}
+              kind: Root
+              metadata:
+                - kind: SetPollingSource
+                  fetch:
+                    kind: Url
+                    url: https://example.com/${{ vars.city }}.csv
+                    headers:
+                      - name: Authorization
+                        value: Bearer ${{ env.TOKEN }}
+                  read:
+                    kind: Csv
+                  merge:
+                    kind: Append
+                - kind: SetInfo
+                  keywords:
+                    # ${{ for keyword in vars.keywords }}
+                    - ${{ vars.keyword }}
+                    # ${{ end }}
+            "#
+        ),
+    )
+    .unwrap();
+
+    let vars: Vec<TemplateVars> = [("berlin", "Germany;Weather"), ("paris", "France")]
+        .into_iter()
+        .map(|(city, keywords)| {
+            [("city", city), ("keywords", keywords)]
+                .into_iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect()
+        })
+        .collect();
+
+    let loader = ResourceLoaderImpl::new();
+    let snapshots = loader
+        .load_dataset_snapshots_from_template(path.to_str().unwrap(), &vars)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        snapshots
+            .iter()
+            .map(|s| s.name.to_string())
+            .collect::<Vec<_>>(),
+        ["weather.berlin", "weather.paris"]
+    );
+
+    let odf::MetadataEvent::SetPollingSource(polling_source) = &snapshots[0].metadata[0] else {
+        panic!("Unexpected event: {:?}", snapshots[0].metadata[0]);
+    };
+    let odf::metadata::FetchStep::Url(fetch) = &polling_source.fetch else {
+        panic!("Unexpected fetch step: {:?}", polling_source.fetch);
+    };
+    assert_eq!(fetch.url, "https://example.com/berlin.csv");
+    assert_eq!(
+        fetch.headers.as_ref().unwrap()[0].value,
+        "Bearer ${{ env.TOKEN }}"
+    );
+
+    let odf::MetadataEvent::SetInfo(info) = &snapshots[1].metadata[1] else {
+        panic!("Unexpected event: {:?}", snapshots[1].metadata[1]);
+    };
+    assert_eq!(info.keywords, Some(vec!["France".to_string()]));
+
+    assert!(matches!(
+        loader
+            .load_dataset_snapshots_from_template(
+                path.to_str().unwrap(),
+                &[TemplateVars::from([(
+                    "city".to_string(),
+                    "rome".to_string()
+                )])]
+            )
+            .await,
+        Err(ResourceError::TemplateError {
+            index: 0,
+            source: TemplateError::ValueNotFound(_),
+        })
+    ));
+}
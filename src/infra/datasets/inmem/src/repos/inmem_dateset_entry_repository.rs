@@ -267,6 +267,61 @@ impl DatasetEntryRepository for InMemoryDatasetEntryRepository {
         Ok(())
     }
 
+    async fn update_dataset_entry_owner(
+        &self,
+        dataset_id: &odf::DatasetID,
+        new_owner_id: &odf::AccountID,
+    ) -> Result<(), UpdateDatasetEntryOwnerError> {
+        let mut writable_state = self.state.write().await;
+
+        let Some(found_dataset_entry) = writable_state.rows.get(dataset_id).cloned() else {
+            return Err(DatasetEntryNotFoundError::new(dataset_id.clone()).into());
+        };
+
+        if found_dataset_entry.owner_id == *new_owner_id {
+            return Ok(());
+        }
+
+        if let Some(owned_by_name) = writable_state.rows_by_owner_and_name.get(new_owner_id) {
+            if owned_by_name.contains_key(&found_dataset_entry.name) {
+                return Err(
+                    DatasetEntryNameCollisionError::new(found_dataset_entry.name.clone()).into(),
+                );
+            }
+        }
+
+        if let Some(owned_by_name) = writable_state
+            .rows_by_owner_and_name
+            .get_mut(&found_dataset_entry.owner_id)
+        {
+            owned_by_name.remove(&found_dataset_entry.name);
+        }
+        if let Some(owned_ids) = writable_state
+            .rows_by_owner
+            .get_mut(&found_dataset_entry.owner_id)
+        {
+            owned_ids.remove(dataset_id);
+        }
+
+        writable_state
+            .rows_by_owner_and_name
+            .entry(new_owner_id.clone())
+            .or_default()
+            .insert(found_dataset_entry.name.clone(), dataset_id.clone());
+        writable_state
+            .rows_by_owner
+            .entry(new_owner_id.clone())
+            .or_default()
+            .insert(dataset_id.clone());
+        writable_state
+            .rows
+            .get_mut(dataset_id)
+            .expect("Entry must be present")
+            .owner_id = new_owner_id.clone();
+
+        Ok(())
+    }
+
     async fn delete_dataset_entry(
         &self,
         dataset_id: &odf::DatasetID,
@@ -87,6 +87,14 @@ database_transactional_test!(
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
 
+database_transactional_test!(
+    storage = inmem,
+    fixture = dataset_entry_repo::test_update_dataset_entry_owner,
+    harness = InMemoryDatasetEntryRepositoryHarness
+);
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
 database_transactional_test!(
     storage = inmem,
     fixture = dataset_entry_repo::test_delete_dataset_entry,
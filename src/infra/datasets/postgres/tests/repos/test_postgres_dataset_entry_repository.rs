@@ -89,6 +89,14 @@ database_transactional_test!(
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
 
+database_transactional_test!(
+    storage = postgres,
+    fixture = dataset_entry_repo::test_update_dataset_entry_owner,
+    harness = PostgresDatasetEntryRepositoryHarness
+);
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
 database_transactional_test!(
     storage = postgres,
     fixture = dataset_entry_repo::test_delete_dataset_entry,
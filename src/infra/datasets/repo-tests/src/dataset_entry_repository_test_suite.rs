@@ -15,6 +15,7 @@ use kamu_accounts::AccountRepository;
 use kamu_datasets::{
     DatasetEntriesResolution,
     DatasetEntryByNameNotFoundError,
+    DatasetEntryNameCollisionError,
     DatasetEntryNotFoundError,
     DatasetEntryRepository,
     DeleteEntryDatasetError,
@@ -22,6 +23,7 @@ use kamu_datasets::{
     GetDatasetEntryError,
     SaveDatasetEntryError,
     UpdateDatasetEntryNameError,
+    UpdateDatasetEntryOwnerError,
 };
 
 use crate::helpers::*;
@@ -634,6 +636,81 @@ pub async fn test_update_dataset_entry_name(catalog: &Catalog) {
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
 
+pub async fn test_update_dataset_entry_owner(catalog: &Catalog) {
+    let account_repo = catalog.get_one::<dyn AccountRepository>().unwrap();
+    let dataset_entry_repo = catalog.get_one::<dyn DatasetEntryRepository>().unwrap();
+
+    let account_1 = new_account_with_name(&account_repo, "user1").await;
+    let account_2 = new_account_with_name(&account_repo, "user2").await;
+
+    let dataset_entry = new_dataset_entry_with(&account_1, "dataset");
+    {
+        let update_res = dataset_entry_repo
+            .update_dataset_entry_owner(&dataset_entry.id, &account_2.id)
+            .await;
+
+        assert_matches!(
+            update_res,
+            Err(UpdateDatasetEntryOwnerError::NotFound(DatasetEntryNotFoundError { dataset_id: actual_dataset_id }))
+                if actual_dataset_id == dataset_entry.id
+        );
+    }
+    {
+        let save_res = dataset_entry_repo.save_dataset_entry(&dataset_entry).await;
+
+        assert_matches!(save_res, Ok(_));
+    }
+    {
+        let update_res = dataset_entry_repo
+            .update_dataset_entry_owner(&dataset_entry.id, &account_2.id)
+            .await;
+
+        assert_matches!(update_res, Ok(_));
+    }
+    {
+        let get_res = dataset_entry_repo
+            .get_dataset_entry(&dataset_entry.id)
+            .await;
+
+        assert_matches!(get_res, Ok(entry) if entry.owner_id == account_2.id);
+    }
+    {
+        let count_res = dataset_entry_repo
+            .dataset_entries_count_by_owner_id(&account_1.id)
+            .await;
+
+        assert_matches!(count_res, Ok(0));
+    }
+    {
+        let get_res = dataset_entry_repo
+            .get_dataset_entry_by_owner_and_name(&account_2.id, &dataset_entry.name)
+            .await;
+
+        assert_matches!(get_res, Ok(entry) if entry.id == dataset_entry.id);
+    }
+    {
+        // Transferring back is blocked by a same-named dataset of the old owner
+        let colliding_entry = new_dataset_entry_with(&account_1, "dataset");
+        let save_res = dataset_entry_repo
+            .save_dataset_entry(&colliding_entry)
+            .await;
+
+        assert_matches!(save_res, Ok(_));
+
+        let update_res = dataset_entry_repo
+            .update_dataset_entry_owner(&dataset_entry.id, &account_1.id)
+            .await;
+
+        assert_matches!(
+            update_res,
+            Err(UpdateDatasetEntryOwnerError::NameCollision(DatasetEntryNameCollisionError { dataset_name }))
+                if dataset_name == dataset_entry.name
+        );
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
 pub async fn test_delete_dataset_entry(catalog: &Catalog) {
     let account_repo = catalog.get_one::<dyn AccountRepository>().unwrap();
     let dataset_entry_repo = catalog.get_one::<dyn DatasetEntryRepository>().unwrap();
@@ -383,6 +383,53 @@ impl DatasetEntryRepository for SqliteDatasetEntryRepository {
         Ok(())
     }
 
+    async fn update_dataset_entry_owner(
+        &self,
+        dataset_id: &odf::DatasetID,
+        new_owner_id: &odf::AccountID,
+    ) -> Result<(), UpdateDatasetEntryOwnerError> {
+        let mut tr = self.transaction.lock().await;
+
+        let connection_mut = tr.connection_mut().await?;
+
+        let stack_dataset_id = dataset_id.as_did_str().to_stack_string();
+        let stack_owner_id = new_owner_id.as_did_str().to_stack_string();
+
+        let maybe_dataset_name = sqlx::query_scalar!(
+            r#"
+            SELECT dataset_name FROM dataset_entries WHERE dataset_id = $1
+            "#,
+            stack_dataset_id.as_str(),
+        )
+        .fetch_optional(&mut *connection_mut)
+        .await
+        .int_err()?;
+        let Some(dataset_name) = maybe_dataset_name else {
+            return Err(DatasetEntryNotFoundError::new(dataset_id.clone()).into());
+        };
+
+        sqlx::query!(
+            r#"
+            UPDATE dataset_entries
+                SET owner_id = $1
+                WHERE dataset_id = $2
+            "#,
+            stack_owner_id.as_str(),
+            stack_dataset_id.as_str(),
+        )
+        .execute(&mut *connection_mut)
+        .await
+        .map_err(|e| match e {
+            sqlx::Error::Database(e) if e.is_unique_violation() => {
+                DatasetEntryNameCollisionError::new(odf::DatasetName::new_unchecked(&dataset_name))
+                    .into()
+            }
+            _ => UpdateDatasetEntryOwnerError::Internal(e.int_err()),
+        })?;
+
+        Ok(())
+    }
+
     async fn delete_dataset_entry(
         &self,
         dataset_id: &odf::DatasetID,
@@ -89,6 +89,14 @@ database_transactional_test!(
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
 
+database_transactional_test!(
+    storage = sqlite,
+    fixture = dataset_entry_repo::test_update_dataset_entry_owner,
+    harness = SqliteDatasetEntryRepositoryHarness
+);
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
 database_transactional_test!(
     storage = sqlite,
     fixture = dataset_entry_repo::test_delete_dataset_entry,
@@ -91,6 +91,41 @@ impl DataWriterDataFusion {
         self.ctx = ctx;
     }
 
+    /// Commits an auxiliary event (e.g. `SetAttachments`) on top of the blocks
+    /// written so far and advances the writer state. Events affecting the
+    /// data or the schema must go through [`DataWriter::commit`] instead.
+    pub async fn commit_event(
+        &mut self,
+        event: odf::MetadataEvent,
+        system_time: DateTime<Utc>,
+    ) -> Result<odf::dataset::CommitResult, odf::dataset::CommitError> {
+        assert!(
+            !matches!(
+                event,
+                odf::MetadataEvent::AddData(_) | odf::MetadataEvent::SetDataSchema(_)
+            ),
+            "Data events must be committed via DataWriter::commit"
+        );
+
+        let commit_result = self
+            .target
+            .commit_event(
+                event,
+                odf::dataset::CommitOpts {
+                    block_ref: &self.meta.block_ref,
+                    system_time: Some(system_time),
+                    prev_block_hash: Some(Some(&self.meta.head)),
+                    check_object_refs: false,
+                    update_block_ref: true,
+                },
+            )
+            .await?;
+
+        self.meta.head = commit_result.new_head.clone();
+
+        Ok(commit_result)
+    }
+
     fn validate_input(&self, df: &DataFrame) -> Result<(), BadInputSchemaError> {
         for system_column in [
             &self.meta.vocab.offset_column,
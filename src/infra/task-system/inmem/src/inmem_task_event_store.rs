@@ -7,9 +7,11 @@
 // the Business Source License, use of this software will be governed
 // by the Apache License, Version 2.0.
 
+use std::cmp::Reverse;
 use std::collections::hash_map::{Entry, HashMap};
 use std::collections::BTreeMap;
 
+use chrono::{DateTime, Utc};
 use database_common::PaginationOpts;
 use dill::*;
 use kamu_task_system::*;
@@ -27,10 +29,24 @@ struct State {
     events: Vec<TaskEvent>,
     tasks_by_dataset: HashMap<odf::DatasetID, Vec<TaskID>>,
     task_statuses: BTreeMap<TaskID, TaskStatus>,
+    task_queue_entries: HashMap<TaskID, QueueEntry>,
+    task_leases: HashMap<TaskID, TaskLease>,
     last_task_id: Option<TaskID>,
 }
 
+struct QueueEntry {
+    plan_kind: &'static str,
+    priority: TaskPriority,
+    account_id: Option<odf::AccountID>,
+}
+
 impl State {
+    fn account_of(&self, task_id: &TaskID) -> Option<&odf::AccountID> {
+        self.task_queue_entries
+            .get(task_id)
+            .and_then(|entry| entry.account_id.as_ref())
+    }
+
     fn next_task_id(&mut self) -> TaskID {
         let new_task_id = if let Some(last_task_id) = self.last_task_id {
             let id: u64 = last_task_id.into();
@@ -78,11 +94,30 @@ impl InMemoryTaskEventStore {
                 };
                 entries.push(event.task_id());
             }
+
+            state.task_queue_entries.insert(
+                e.task_id,
+                QueueEntry {
+                    plan_kind: e.logical_plan.kind(),
+                    priority: e.priority,
+                    account_id: e.account_id.clone(),
+                },
+            );
         }
 
+        let new_status = event.new_status();
+        if new_status != TaskStatus::Running {
+            state.task_leases.remove(&event.task_id());
+        }
+
+        state.task_statuses.insert(event.task_id(), new_status);
+    }
+
+    fn is_lease_expired(state: &State, task_id: TaskID, now: DateTime<Utc>) -> bool {
         state
-            .task_statuses
-            .insert(event.task_id(), event.new_status());
+            .task_leases
+            .get(&task_id)
+            .is_none_or(|lease| lease.expires_at <= now)
     }
 }
 
@@ -131,18 +166,72 @@ impl TaskEventStore for InMemoryTaskEventStore {
         Ok(self.inner.as_state().lock().unwrap().next_task_id())
     }
 
-    /// Attempts to get the earliest queued task, if any
-    async fn try_get_queued_task(&self) -> Result<Option<TaskID>, InternalError> {
+    /// Attempts to get the next queued task, if any, skipping tasks of the
+    /// specified logical plan kinds. Tasks of accounts with the fewest running
+    /// tasks go first, then tasks of higher priority, then the earliest ones
+    async fn try_get_queued_task(
+        &self,
+        excluded_plan_kinds: &[&str],
+    ) -> Result<Option<TaskID>, InternalError> {
         let state = self.inner.as_state();
         let g = state.lock().unwrap();
+
+        let mut running_by_account: HashMap<Option<&odf::AccountID>, usize> = HashMap::new();
+        for (id, status) in &g.task_statuses {
+            if *status == TaskStatus::Running {
+                *running_by_account.entry(g.account_of(id)).or_default() += 1;
+            }
+        }
+
         let maybe_task_id = g
             .task_statuses
             .iter()
-            .find(|(_, status)| **status == TaskStatus::Queued)
-            .map(|(id, _)| *id);
+            .filter(|(_, status)| **status == TaskStatus::Queued)
+            .map(|(id, _)| (*id, g.task_queue_entries.get(id)))
+            .filter(|(_, entry)| {
+                entry.is_none_or(|entry| !excluded_plan_kinds.contains(&entry.plan_kind))
+            })
+            .min_by_key(|(id, entry)| {
+                let running_count = running_by_account
+                    .get(&g.account_of(id))
+                    .copied()
+                    .unwrap_or_default();
+                let priority = entry.map(|entry| entry.priority).unwrap_or_default();
+                (running_count, Reverse(priority), *id)
+            })
+            .map(|(id, _)| id);
+
         Ok(maybe_task_id)
     }
 
+    /// Assigns or extends the lease of a running task on behalf of a worker
+    async fn save_task_lease(
+        &self,
+        task_id: TaskID,
+        lease: &TaskLease,
+    ) -> Result<bool, InternalError> {
+        let state = self.inner.as_state();
+        let mut g = state.lock().unwrap();
+
+        if g.task_statuses.get(&task_id) != Some(&TaskStatus::Running) {
+            return Ok(false);
+        }
+
+        match g.task_leases.entry(task_id) {
+            Entry::Occupied(mut e) => {
+                if e.get().worker_id != lease.worker_id {
+                    return Ok(false);
+                }
+                e.insert(lease.clone());
+            }
+            Entry::Vacant(e) => {
+                e.insert(lease.clone());
+            }
+        }
+
+        Ok(true)
+    }
+
     /// Returns list of tasks, which are in Running state,
     /// from earliest to latest
     fn get_running_tasks(&self, pagination: PaginationOpts) -> TaskIDStream {
@@ -176,6 +265,82 @@ impl TaskEventStore for InMemoryTaskEventStore {
         Ok(count)
     }
 
+    /// Returns the number of queued and running tasks per account, for the
+    /// accounts that have any
+    async fn get_queue_depth_by_account(&self) -> Result<Vec<TaskQueueDepth>, InternalError> {
+        let state = self.inner.as_state();
+        let g = state.lock().unwrap();
+
+        let mut depth_by_account: HashMap<Option<&odf::AccountID>, TaskQueueDepth> = HashMap::new();
+        for (id, status) in &g.task_statuses {
+            if *status == TaskStatus::Finished {
+                continue;
+            }
+
+            let account_id = g.account_of(id);
+            let depth = depth_by_account
+                .entry(account_id)
+                .or_insert_with(|| TaskQueueDepth {
+                    account_id: account_id.cloned(),
+                    queued_count: 0,
+                    running_count: 0,
+                });
+            match status {
+                TaskStatus::Queued => depth.queued_count += 1,
+                TaskStatus::Running => depth.running_count += 1,
+                TaskStatus::Finished => unreachable!(),
+            }
+        }
+
+        let mut depths: Vec<_> = depth_by_account.into_values().collect();
+        depths.sort_by_key(|depth| depth.account_id.as_ref().map(ToString::to_string));
+        Ok(depths)
+    }
+
+    /// Returns list of tasks, which are in Running state, but whose lease
+    /// has expired by the specified moment or was never assigned,
+    /// from earliest to latest
+    fn get_running_tasks_with_expired_lease(
+        &self,
+        now: DateTime<Utc>,
+        pagination: PaginationOpts,
+    ) -> TaskIDStream {
+        let task_ids_page: Vec<_> = {
+            let state = self.inner.as_state();
+            let g = state.lock().unwrap();
+            g.task_statuses
+                .iter()
+                .filter(|(id, status)| {
+                    **status == TaskStatus::Running && Self::is_lease_expired(&g, **id, now)
+                })
+                .skip(pagination.offset)
+                .take(pagination.limit)
+                .map(|(id, _)| Ok(*id))
+                .collect()
+        };
+
+        Box::pin(futures::stream::iter(task_ids_page))
+    }
+
+    /// Returns total number of tasks, which are in Running state, but whose
+    /// lease has expired by the specified moment or was never assigned
+    async fn get_count_running_tasks_with_expired_lease(
+        &self,
+        now: DateTime<Utc>,
+    ) -> Result<usize, InternalError> {
+        let state = self.inner.as_state();
+        let g = state.lock().unwrap();
+        let count = g
+            .task_statuses
+            .iter()
+            .filter(|(id, status)| {
+                **status == TaskStatus::Running && Self::is_lease_expired(&g, **id, now)
+            })
+            .count();
+
+        Ok(count)
+    }
+
     /// Returns page of the tasks associated with the specified dataset in
     /// reverse chronological order based on creation time
     /// Note: no longer used, but might be used in future (admin view)
@@ -7,6 +7,7 @@
 // the Business Source License, use of this software will be governed
 // by the Apache License, Version 2.0.
 
+use chrono::{DateTime, Utc};
 use database_common::{PaginationOpts, TransactionRef, TransactionRefT};
 use dill::*;
 use futures::TryStreamExt;
@@ -31,21 +32,23 @@ impl PostgresTaskEventStore {
     async fn register_task(
         &self,
         tr: &mut database_common::TransactionGuard<'_, Postgres>,
-        task_id: TaskID,
-        logical_plan: &LogicalPlan,
+        event: &TaskEventCreated,
     ) -> Result<(), InternalError> {
         let connection_mut = tr.connection_mut().await?;
 
-        let task_id: i64 = task_id.try_into().unwrap();
-        let maybe_dataset_id = logical_plan.dataset_id();
+        let task_id: i64 = event.task_id.try_into().unwrap();
+        let maybe_dataset_id = event.logical_plan.dataset_id();
 
         sqlx::query!(
             r#"
-            INSERT INTO tasks (task_id, dataset_id, task_status, last_event_id)
-                VALUES ($1, $2, 'queued'::task_status_type, NULL)
+            INSERT INTO tasks (task_id, dataset_id, task_status, last_event_id, logical_plan_kind, priority, account_id)
+                VALUES ($1, $2, 'queued'::task_status_type, NULL, $3, $4, $5)
             "#,
             task_id,
             maybe_dataset_id.map(ToString::to_string),
+            event.logical_plan.kind(),
+            event.priority.rank(),
+            event.account_id.as_ref().map(ToString::to_string),
         )
         .execute(connection_mut)
         .await
@@ -70,6 +73,9 @@ impl PostgresTaskEventStore {
 
         let event_task_id: i64 = (last_event.task_id()).try_into().unwrap();
         let latest_status = last_event.new_status();
+        let releases_lease = events
+            .iter()
+            .any(|event| event.new_status() != TaskStatus::Running);
 
         let affected_rows_count =
             sqlx::query!(
@@ -99,6 +105,22 @@ impl PostgresTaskEventStore {
             return Err(SaveEventsError::concurrent_modification());
         }
 
+        // Leases are only meaningful for running tasks, leaving that state releases
+        // them
+        if releases_lease {
+            sqlx::query!(
+                r#"
+                UPDATE tasks
+                    SET lease_worker_id = NULL, lease_expires_at = NULL
+                    WHERE task_id = $1
+                "#,
+                event_task_id,
+            )
+            .execute(tr.connection_mut().await?)
+            .await
+            .map_err(|e| SaveEventsError::Internal(e.int_err()))?;
+        }
+
         Ok(())
     }
 
@@ -241,7 +263,7 @@ impl EventStore<TaskState> for PostgresTaskEventStore {
             }
 
             // Make registration
-            self.register_task(&mut tr, *task_id, &e.logical_plan)
+            self.register_task(&mut tr, e)
                 .await
                 .map_err(SaveEventsError::Internal)?;
         }
@@ -296,28 +318,74 @@ impl TaskEventStore for PostgresTaskEventStore {
         Ok(TaskID::try_from(task_id).unwrap())
     }
 
-    /// Attempts to get the earliest queued task, if any
-    async fn try_get_queued_task(&self) -> Result<Option<TaskID>, InternalError> {
+    /// Attempts to get the next queued task, if any, skipping tasks of the
+    /// specified logical plan kinds. Tasks of accounts with the fewest running
+    /// tasks go first, then tasks of higher priority, then the earliest ones
+    async fn try_get_queued_task(
+        &self,
+        excluded_plan_kinds: &[&str],
+    ) -> Result<Option<TaskID>, InternalError> {
         let mut tr = self.transaction.lock().await;
         let connection_mut = tr.connection_mut().await?;
 
-        let maybe_task_id = sqlx::query!(
+        let excluded_plan_kinds: Vec<String> = excluded_plan_kinds
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+
+        // Rows locked by concurrent workers taking the same task are skipped
+        let maybe_task_id = sqlx::query_scalar!(
             r#"
-            SELECT task_id FROM tasks
-                WHERE task_status = 'queued'::task_status_type
-                ORDER BY task_id ASC
+            WITH running_by_account AS (
+                SELECT account_id, COUNT(task_id) AS running_count FROM tasks
+                    WHERE task_status = 'running'::task_status_type
+                    GROUP BY account_id
+            )
+            SELECT t.task_id FROM tasks t
+                LEFT JOIN running_by_account r ON r.account_id IS NOT DISTINCT FROM t.account_id
+                WHERE t.task_status = 'queued'::task_status_type
+                    AND (t.logical_plan_kind IS NULL OR NOT (t.logical_plan_kind = ANY($1)))
+                ORDER BY COALESCE(r.running_count, 0) ASC, t.priority DESC, t.task_id ASC
                 LIMIT 1
+                FOR UPDATE OF t SKIP LOCKED
             "#,
+            &excluded_plan_kinds,
         )
-        .try_map(|event_row| {
-            let task_id = event_row.task_id;
-            Ok(TaskID::try_from(task_id).unwrap())
-        })
         .fetch_optional(connection_mut)
         .await
-        .map_err(ErrorIntoInternal::int_err)?;
+        .int_err()?;
+
+        Ok(maybe_task_id.map(|task_id| TaskID::try_from(task_id).unwrap()))
+    }
+
+    /// Assigns or extends the lease of a running task on behalf of a worker
+    async fn save_task_lease(
+        &self,
+        task_id: TaskID,
+        lease: &TaskLease,
+    ) -> Result<bool, InternalError> {
+        let mut tr = self.transaction.lock().await;
+        let connection_mut = tr.connection_mut().await?;
+
+        let task_id: i64 = task_id.try_into().unwrap();
+
+        let update_result = sqlx::query!(
+            r#"
+            UPDATE tasks
+                SET lease_worker_id = $2, lease_expires_at = $3
+                WHERE task_id = $1
+                    AND task_status = 'running'::task_status_type
+                    AND (lease_worker_id IS NULL OR lease_worker_id = $2)
+            "#,
+            task_id,
+            lease.worker_id,
+            lease.expires_at,
+        )
+        .execute(connection_mut)
+        .await
+        .int_err()?;
 
-        Ok(maybe_task_id)
+        Ok(update_result.rows_affected() > 0)
     }
 
     /// Returns list of tasks, which are in Running state,
@@ -372,6 +440,106 @@ impl TaskEventStore for PostgresTaskEventStore {
         Ok(count)
     }
 
+    /// Returns the number of queued and running tasks per account, for the
+    /// accounts that have any
+    async fn get_queue_depth_by_account(&self) -> Result<Vec<TaskQueueDepth>, InternalError> {
+        let mut tr = self.transaction.lock().await;
+        let connection_mut = tr.connection_mut().await?;
+
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                account_id,
+                COUNT(task_id) FILTER (WHERE task_status = 'queued'::task_status_type) AS "queued_count!",
+                COUNT(task_id) FILTER (WHERE task_status = 'running'::task_status_type) AS "running_count!"
+            FROM tasks
+                WHERE task_status <> 'finished'::task_status_type
+                GROUP BY account_id
+                ORDER BY account_id ASC NULLS FIRST
+            "#,
+        )
+        .fetch_all(connection_mut)
+        .await
+        .int_err()?;
+
+        rows.into_iter()
+            .map(|row| {
+                Ok(TaskQueueDepth {
+                    account_id: row
+                        .account_id
+                        .as_deref()
+                        .map(odf::AccountID::from_did_str)
+                        .transpose()
+                        .int_err()?,
+                    queued_count: usize::try_from(row.queued_count).int_err()?,
+                    running_count: usize::try_from(row.running_count).int_err()?,
+                })
+            })
+            .collect()
+    }
+
+    /// Returns list of tasks, which are in Running state, but whose lease
+    /// has expired by the specified moment or was never assigned,
+    /// from earliest to latest
+    fn get_running_tasks_with_expired_lease(
+        &self,
+        now: DateTime<Utc>,
+        pagination: PaginationOpts,
+    ) -> TaskIDStream {
+        Box::pin(async_stream::stream! {
+            let mut tr = self.transaction.lock().await;
+            let connection_mut = tr.connection_mut().await?;
+
+            let limit = i64::try_from(pagination.limit).int_err()?;
+            let offset = i64::try_from(pagination.offset).int_err()?;
+
+            let mut query_stream = sqlx::query_scalar!(
+                r#"
+                SELECT task_id FROM tasks
+                    WHERE task_status = 'running'::task_status_type
+                        AND (lease_expires_at IS NULL OR lease_expires_at <= $1)
+                    ORDER BY task_id ASC
+                    LIMIT $2 OFFSET $3
+                "#,
+                now,
+                limit,
+                offset,
+            )
+            .fetch(connection_mut)
+            .map_ok(|task_id| TaskID::try_from(task_id).unwrap())
+            .map_err(ErrorIntoInternal::int_err);
+
+            while let Some(task_id) = query_stream.try_next().await? {
+                yield Ok(task_id);
+            }
+        })
+    }
+
+    /// Returns total number of tasks, which are in Running state, but whose
+    /// lease has expired by the specified moment or was never assigned
+    async fn get_count_running_tasks_with_expired_lease(
+        &self,
+        now: DateTime<Utc>,
+    ) -> Result<usize, InternalError> {
+        let mut tr = self.transaction.lock().await;
+        let connection_mut = tr.connection_mut().await?;
+
+        let result = sqlx::query!(
+            r#"
+            SELECT COUNT(task_id) AS tasks_count FROM tasks
+                WHERE task_status = 'running'::task_status_type
+                    AND (lease_expires_at IS NULL OR lease_expires_at <= $1)
+            "#,
+            now,
+        )
+        .fetch_one(connection_mut)
+        .await
+        .int_err()?;
+
+        let count = usize::try_from(result.tasks_count.unwrap()).int_err()?;
+        Ok(count)
+    }
+
     /// Returns page of the tasks associated with the specified dataset in
     /// reverse chronological order based on creation time
     fn get_tasks_by_dataset(
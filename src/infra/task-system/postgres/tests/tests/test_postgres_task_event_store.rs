@@ -76,6 +76,40 @@ database_transactional_test!(
     fixture = kamu_task_system_repo_tests::test_event_store_get_running_tasks,
     harness = PostgresTaskSystemEventStoreHarness
 );
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+database_transactional_test!(
+    storage = postgres,
+    fixture =
+        kamu_task_system_repo_tests::test_event_store_try_get_queued_task_excluding_plan_kinds,
+    harness = PostgresTaskSystemEventStoreHarness
+);
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+database_transactional_test!(
+    storage = postgres,
+    fixture = kamu_task_system_repo_tests::test_event_store_try_get_queued_task_fair_share,
+    harness = PostgresTaskSystemEventStoreHarness
+);
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+database_transactional_test!(
+    storage = postgres,
+    fixture = kamu_task_system_repo_tests::test_event_store_queue_depth_by_account,
+    harness = PostgresTaskSystemEventStoreHarness
+);
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+database_transactional_test!(
+    storage = postgres,
+    fixture = kamu_task_system_repo_tests::test_event_store_task_leases,
+    harness = PostgresTaskSystemEventStoreHarness
+);
+
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
 
 database_transactional_test!(
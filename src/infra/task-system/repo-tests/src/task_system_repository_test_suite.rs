@@ -9,7 +9,7 @@
 
 use std::assert_matches::assert_matches;
 
-use chrono::Utc;
+use chrono::{Duration, Utc};
 use database_common::PaginationOpts;
 use dill::Catalog;
 use futures::TryStreamExt;
@@ -64,6 +64,8 @@ pub async fn test_event_store_get_streams(catalog: &Catalog) {
         }
         .into(),
         metadata: None,
+        priority: TaskPriority::default(),
+        account_id: None,
     };
 
     let event_2 = TaskEventCreated {
@@ -75,6 +77,8 @@ pub async fn test_event_store_get_streams(catalog: &Catalog) {
         }
         .into(),
         metadata: None,
+        priority: TaskPriority::default(),
+        account_id: None,
     };
 
     let event_3 = TaskEventFinished {
@@ -142,6 +146,8 @@ pub async fn test_event_store_get_events_with_windowing(catalog: &Catalog) {
         }
         .into(),
         metadata: None,
+        priority: TaskPriority::default(),
+        account_id: None,
     };
 
     let event_2 = TaskEventRunning {
@@ -240,6 +246,8 @@ pub async fn test_event_store_get_events_by_tasks(catalog: &Catalog) {
         }
         .into(),
         metadata: None,
+        priority: TaskPriority::default(),
+        account_id: None,
     };
 
     let event_2_1 = TaskEventCreated {
@@ -251,6 +259,8 @@ pub async fn test_event_store_get_events_by_tasks(catalog: &Catalog) {
         }
         .into(),
         metadata: None,
+        priority: TaskPriority::default(),
+        account_id: None,
     };
 
     let event_1_2 = TaskEventRunning {
@@ -354,6 +364,8 @@ pub async fn test_event_store_get_dataset_tasks(catalog: &Catalog) {
         }
         .into(),
         metadata: None,
+        priority: TaskPriority::default(),
+        account_id: None,
     };
 
     let event_1_2 = TaskEventCreated {
@@ -365,6 +377,8 @@ pub async fn test_event_store_get_dataset_tasks(catalog: &Catalog) {
         }
         .into(),
         metadata: None,
+        priority: TaskPriority::default(),
+        account_id: None,
     };
 
     let event_2_1 = TaskEventCreated {
@@ -376,6 +390,8 @@ pub async fn test_event_store_get_dataset_tasks(catalog: &Catalog) {
         }
         .into(),
         metadata: None,
+        priority: TaskPriority::default(),
+        account_id: None,
     };
 
     let event_2_2 = TaskEventCreated {
@@ -387,6 +403,8 @@ pub async fn test_event_store_get_dataset_tasks(catalog: &Catalog) {
         }
         .into(),
         metadata: None,
+        priority: TaskPriority::default(),
+        account_id: None,
     };
 
     event_store
@@ -510,7 +528,7 @@ pub async fn test_event_store_try_get_queued_single_task(catalog: &Catalog) {
     let event_store = catalog.get_one::<dyn TaskEventStore>().unwrap();
 
     // Initially, there is nothing to get
-    let maybe_task_id = event_store.try_get_queued_task().await.unwrap();
+    let maybe_task_id = event_store.try_get_queued_task(&[]).await.unwrap();
     assert!(maybe_task_id.is_none());
 
     // Schedule a task
@@ -524,6 +542,8 @@ pub async fn test_event_store_try_get_queued_single_task(catalog: &Catalog) {
                 task_id: task_id_1,
                 logical_plan: LogicalPlanProbe::default().into(),
                 metadata: None,
+                priority: TaskPriority::default(),
+                account_id: None,
             }
             .into()],
         )
@@ -531,7 +551,7 @@ pub async fn test_event_store_try_get_queued_single_task(catalog: &Catalog) {
         .unwrap();
 
     // The only queued task should be returned
-    let maybe_task_id = event_store.try_get_queued_task().await.unwrap();
+    let maybe_task_id = event_store.try_get_queued_task(&[]).await.unwrap();
     assert_eq!(maybe_task_id, Some(task_id_1));
 
     // Mark the task as running
@@ -549,7 +569,7 @@ pub async fn test_event_store_try_get_queued_single_task(catalog: &Catalog) {
         .unwrap();
 
     // Right now nothing should be visible
-    let maybe_task_id = event_store.try_get_queued_task().await.unwrap();
+    let maybe_task_id = event_store.try_get_queued_task(&[]).await.unwrap();
     assert!(maybe_task_id.is_none());
 
     // Requeue the task (server restarted)
@@ -567,7 +587,7 @@ pub async fn test_event_store_try_get_queued_single_task(catalog: &Catalog) {
         .unwrap();
 
     // The task should be visible again
-    let maybe_task_id = event_store.try_get_queued_task().await.unwrap();
+    let maybe_task_id = event_store.try_get_queued_task(&[]).await.unwrap();
     assert_eq!(maybe_task_id, Some(task_id_1));
 
     // Now run and finish the task
@@ -593,7 +613,7 @@ pub async fn test_event_store_try_get_queued_single_task(catalog: &Catalog) {
         .unwrap();
 
     // The task should disappear again
-    let maybe_task_id = event_store.try_get_queued_task().await.unwrap();
+    let maybe_task_id = event_store.try_get_queued_task(&[]).await.unwrap();
     assert!(maybe_task_id.is_none());
 }
 
@@ -616,6 +636,8 @@ pub async fn test_event_store_try_get_queued_multiple_tasks(catalog: &Catalog) {
                     task_id,
                     logical_plan: LogicalPlanProbe::default().into(),
                     metadata: None,
+                    priority: TaskPriority::default(),
+                    account_id: None,
                 }
                 .into()],
             )
@@ -627,7 +649,7 @@ pub async fn test_event_store_try_get_queued_multiple_tasks(catalog: &Catalog) {
     }
 
     // We should see the earliest registered task
-    let maybe_task_id = event_store.try_get_queued_task().await.unwrap();
+    let maybe_task_id = event_store.try_get_queued_task(&[]).await.unwrap();
     assert_eq!(maybe_task_id, Some(task_ids[0]));
 
     // Mark task 0 as running
@@ -645,7 +667,7 @@ pub async fn test_event_store_try_get_queued_multiple_tasks(catalog: &Catalog) {
         .unwrap();
 
     // Now we should see the next registered task
-    let maybe_task_id = event_store.try_get_queued_task().await.unwrap();
+    let maybe_task_id = event_store.try_get_queued_task(&[]).await.unwrap();
     assert_eq!(maybe_task_id, Some(task_ids[1]));
 
     // Mark task 1 as running, then finished
@@ -671,7 +693,7 @@ pub async fn test_event_store_try_get_queued_multiple_tasks(catalog: &Catalog) {
         .unwrap();
 
     // Now we should see the last registered task
-    let maybe_task_id = event_store.try_get_queued_task().await.unwrap();
+    let maybe_task_id = event_store.try_get_queued_task(&[]).await.unwrap();
     assert_eq!(maybe_task_id, Some(task_ids[2]));
 
     // Task 0 got requeued
@@ -689,7 +711,7 @@ pub async fn test_event_store_try_get_queued_multiple_tasks(catalog: &Catalog) {
         .unwrap();
 
     // This should bring task 0 back to the top of the queue
-    let maybe_task_id = event_store.try_get_queued_task().await.unwrap();
+    let maybe_task_id = event_store.try_get_queued_task(&[]).await.unwrap();
     assert_eq!(maybe_task_id, Some(task_ids[0]));
 
     // Mark task 0 as running, then finished
@@ -715,7 +737,7 @@ pub async fn test_event_store_try_get_queued_multiple_tasks(catalog: &Catalog) {
         .unwrap();
 
     // Task 2 should be the top again
-    let maybe_task_id = event_store.try_get_queued_task().await.unwrap();
+    let maybe_task_id = event_store.try_get_queued_task(&[]).await.unwrap();
     assert_eq!(maybe_task_id, Some(task_ids[2]));
 
     // Mark task 2 as running
@@ -733,7 +755,7 @@ pub async fn test_event_store_try_get_queued_multiple_tasks(catalog: &Catalog) {
         .unwrap();
 
     // We should see empty queue
-    let maybe_task_id = event_store.try_get_queued_task().await.unwrap();
+    let maybe_task_id = event_store.try_get_queued_task(&[]).await.unwrap();
     assert!(maybe_task_id.is_none());
 }
 
@@ -771,6 +793,8 @@ pub async fn test_event_store_get_running_tasks(catalog: &Catalog) {
                     task_id,
                     logical_plan: LogicalPlanProbe::default().into(),
                     metadata: None,
+                    priority: TaskPriority::default(),
+                    account_id: None,
                 }
                 .into()],
             )
@@ -931,6 +955,365 @@ pub async fn test_event_store_get_running_tasks(catalog: &Catalog) {
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
 
+pub async fn test_event_store_try_get_queued_task_excluding_plan_kinds(catalog: &Catalog) {
+    let event_store = catalog.get_one::<dyn TaskEventStore>().unwrap();
+
+    // Schedule a probe task and an update task
+    let plans: [LogicalPlan; 2] = [
+        LogicalPlanProbe::default().into(),
+        LogicalPlanUpdateDataset {
+            dataset_id: odf::DatasetID::new_seeded_ed25519(b"foo"),
+            fetch_uncacheable: false,
+        }
+        .into(),
+    ];
+
+    let mut task_ids = Vec::new();
+    for logical_plan in plans {
+        let task_id = event_store.new_task_id().await.unwrap();
+        event_store
+            .save_events(
+                &task_id,
+                None,
+                vec![TaskEventCreated {
+                    event_time: Utc::now(),
+                    task_id,
+                    logical_plan,
+                    metadata: None,
+                    priority: TaskPriority::default(),
+                    account_id: None,
+                }
+                .into()],
+            )
+            .await
+            .unwrap();
+        task_ids.push(task_id);
+    }
+
+    // Exclusions of unrelated kinds have no effect
+    let maybe_task_id = event_store
+        .try_get_queued_task(&["HardCompactDataset"])
+        .await
+        .unwrap();
+    assert_eq!(maybe_task_id, Some(task_ids[0]));
+
+    // Excluding probes should skip to the update task
+    let maybe_task_id = event_store.try_get_queued_task(&["Probe"]).await.unwrap();
+    assert_eq!(maybe_task_id, Some(task_ids[1]));
+
+    // Excluding both kinds leaves nothing to take
+    let maybe_task_id = event_store
+        .try_get_queued_task(&["Probe", "UpdateDataset"])
+        .await
+        .unwrap();
+    assert!(maybe_task_id.is_none());
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+pub async fn test_event_store_try_get_queued_task_fair_share(catalog: &Catalog) {
+    let event_store = catalog.get_one::<dyn TaskEventStore>().unwrap();
+
+    let account_a = odf::AccountID::new_seeded_ed25519(b"a");
+    let account_b = odf::AccountID::new_seeded_ed25519(b"b");
+
+    // Account A has a cascaded and a manual task, account B - two scheduled ones
+    let mut task_ids = Vec::new();
+    let mut last_event_ids = Vec::new();
+    for (priority, account_id) in [
+        (TaskPriority::Cascaded, &account_a),
+        (TaskPriority::Manual, &account_a),
+        (TaskPriority::Scheduled, &account_b),
+        (TaskPriority::Scheduled, &account_b),
+    ] {
+        let task_id = event_store.new_task_id().await.unwrap();
+        let last_event_id = event_store
+            .save_events(
+                &task_id,
+                None,
+                vec![TaskEventCreated {
+                    event_time: Utc::now(),
+                    task_id,
+                    logical_plan: LogicalPlanProbe::default().into(),
+                    metadata: None,
+                    priority,
+                    account_id: Some(account_id.clone()),
+                }
+                .into()],
+            )
+            .await
+            .unwrap();
+
+        task_ids.push(task_id);
+        last_event_ids.push(last_event_id);
+    }
+
+    // Tasks are expected in the following order:
+    //  - nothing runs yet, so the manual task of A wins by priority
+    //  - A occupies a worker, so B gets its turn
+    //  - both accounts run one task each, the scheduled task of B beats the
+    //    cascaded task of A
+    //  - B occupies more workers, so the remaining task of A goes last
+    for expected_index in [1, 2, 3, 0] {
+        let maybe_task_id = event_store.try_get_queued_task(&[]).await.unwrap();
+        assert_eq!(maybe_task_id, Some(task_ids[expected_index]));
+
+        event_store
+            .save_events(
+                &task_ids[expected_index],
+                Some(last_event_ids[expected_index]),
+                vec![TaskEventRunning {
+                    event_time: Utc::now(),
+                    task_id: task_ids[expected_index],
+                }
+                .into()],
+            )
+            .await
+            .unwrap();
+    }
+
+    let maybe_task_id = event_store.try_get_queued_task(&[]).await.unwrap();
+    assert!(maybe_task_id.is_none());
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+pub async fn test_event_store_queue_depth_by_account(catalog: &Catalog) {
+    let event_store = catalog.get_one::<dyn TaskEventStore>().unwrap();
+
+    assert_eq!(event_store.get_queue_depth_by_account().await.unwrap(), []);
+
+    let account_a = odf::AccountID::new_seeded_ed25519(b"a");
+    let account_b = odf::AccountID::new_seeded_ed25519(b"b");
+
+    // Account A: one finished, one running and one queued task,
+    // account B: one queued task, plus one task of no account
+    for (account_id, status) in [
+        (Some(&account_a), TaskStatus::Finished),
+        (Some(&account_a), TaskStatus::Running),
+        (Some(&account_a), TaskStatus::Queued),
+        (Some(&account_b), TaskStatus::Queued),
+        (None, TaskStatus::Queued),
+    ] {
+        let task_id = event_store.new_task_id().await.unwrap();
+        let mut events: Vec<TaskEvent> = vec![TaskEventCreated {
+            event_time: Utc::now(),
+            task_id,
+            logical_plan: LogicalPlanProbe::default().into(),
+            metadata: None,
+            priority: TaskPriority::default(),
+            account_id: account_id.cloned(),
+        }
+        .into()];
+
+        if status != TaskStatus::Queued {
+            events.push(
+                TaskEventRunning {
+                    event_time: Utc::now(),
+                    task_id,
+                }
+                .into(),
+            );
+        }
+        if status == TaskStatus::Finished {
+            events.push(
+                TaskEventFinished {
+                    event_time: Utc::now(),
+                    task_id,
+                    outcome: TaskOutcome::Success(TaskResult::Empty),
+                }
+                .into(),
+            );
+        }
+
+        event_store
+            .save_events(&task_id, None, events)
+            .await
+            .unwrap();
+    }
+
+    let mut expected = vec![
+        TaskQueueDepth {
+            account_id: Some(account_a),
+            queued_count: 1,
+            running_count: 1,
+        },
+        TaskQueueDepth {
+            account_id: Some(account_b),
+            queued_count: 1,
+            running_count: 0,
+        },
+    ];
+    expected.sort_by_key(|depth| depth.account_id.as_ref().map(ToString::to_string));
+    expected.insert(
+        0,
+        TaskQueueDepth {
+            account_id: None,
+            queued_count: 1,
+            running_count: 0,
+        },
+    );
+
+    assert_eq!(
+        event_store.get_queue_depth_by_account().await.unwrap(),
+        expected
+    );
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+pub async fn test_event_store_task_leases(catalog: &Catalog) {
+    let event_store = catalog.get_one::<dyn TaskEventStore>().unwrap();
+
+    let now = Utc::now();
+    let lease = |worker_id: &str, expires_at| TaskLease {
+        worker_id: worker_id.to_string(),
+        expires_at,
+    };
+
+    // Schedule a few tasks
+    let mut task_ids = Vec::new();
+    let mut last_event_ids = Vec::new();
+    for _ in 0..3 {
+        let task_id = event_store.new_task_id().await.unwrap();
+        let last_event_id = event_store
+            .save_events(
+                &task_id,
+                None,
+                vec![TaskEventCreated {
+                    event_time: now,
+                    task_id,
+                    logical_plan: LogicalPlanProbe::default().into(),
+                    metadata: None,
+                    priority: TaskPriority::default(),
+                    account_id: None,
+                }
+                .into()],
+            )
+            .await
+            .unwrap();
+
+        task_ids.push(task_id);
+        last_event_ids.push(last_event_id);
+    }
+
+    // Queued tasks cannot be leased
+    assert!(!event_store
+        .save_task_lease(task_ids[0], &lease("a", now + Duration::minutes(1)))
+        .await
+        .unwrap());
+
+    // Mark all tasks as running
+    for (task_id, last_event_id) in task_ids.iter().zip(last_event_ids.iter_mut()) {
+        *last_event_id = event_store
+            .save_events(
+                task_id,
+                Some(*last_event_id),
+                vec![TaskEventRunning {
+                    event_time: now,
+                    task_id: *task_id,
+                }
+                .into()],
+            )
+            .await
+            .unwrap();
+    }
+
+    // Task 0 has an already expired lease, task 1 - an active one, task 2 - none
+    assert!(event_store
+        .save_task_lease(task_ids[0], &lease("a", now - Duration::minutes(1)))
+        .await
+        .unwrap());
+    assert!(event_store
+        .save_task_lease(task_ids[1], &lease("a", now + Duration::minutes(1)))
+        .await
+        .unwrap());
+
+    let expired_count = event_store
+        .get_count_running_tasks_with_expired_lease(now)
+        .await
+        .unwrap();
+    assert_eq!(expired_count, 2);
+
+    let expired_task_ids: Vec<_> = event_store
+        .get_running_tasks_with_expired_lease(
+            now,
+            PaginationOpts {
+                limit: 100,
+                offset: 0,
+            },
+        )
+        .try_collect()
+        .await
+        .unwrap();
+    assert_eq!(expired_task_ids, vec![task_ids[0], task_ids[2]]);
+
+    // Another worker cannot steal the lease, while the owner can extend it
+    assert!(!event_store
+        .save_task_lease(task_ids[1], &lease("b", now + Duration::minutes(2)))
+        .await
+        .unwrap());
+    assert!(event_store
+        .save_task_lease(task_ids[1], &lease("a", now + Duration::minutes(2)))
+        .await
+        .unwrap());
+
+    // Once the lease expires, task is reported as abandoned
+    let expired_task_ids: Vec<_> = event_store
+        .get_running_tasks_with_expired_lease(
+            now + Duration::minutes(3),
+            PaginationOpts {
+                limit: 100,
+                offset: 0,
+            },
+        )
+        .try_collect()
+        .await
+        .unwrap();
+    assert_eq!(expired_task_ids, task_ids);
+
+    // Requeuing task 0 releases its lease, so another worker can take it over
+    last_event_ids[0] = event_store
+        .save_events(
+            &task_ids[0],
+            Some(last_event_ids[0]),
+            vec![
+                TaskEventRequeued {
+                    event_time: now,
+                    task_id: task_ids[0],
+                }
+                .into(),
+                TaskEventRunning {
+                    event_time: now,
+                    task_id: task_ids[0],
+                }
+                .into(),
+            ],
+        )
+        .await
+        .unwrap();
+
+    assert!(event_store
+        .save_task_lease(task_ids[0], &lease("b", now + Duration::minutes(1)))
+        .await
+        .unwrap());
+
+    let expired_task_ids: Vec<_> = event_store
+        .get_running_tasks_with_expired_lease(
+            now,
+            PaginationOpts {
+                limit: 100,
+                offset: 0,
+            },
+        )
+        .try_collect()
+        .await
+        .unwrap();
+    assert_eq!(expired_task_ids, vec![task_ids[2]]);
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
 pub async fn test_event_store_concurrent_modification(catalog: &Catalog) {
     let event_store = catalog.get_one::<dyn TaskEventStore>().unwrap();
 
@@ -946,6 +1329,8 @@ pub async fn test_event_store_concurrent_modification(catalog: &Catalog) {
                 task_id,
                 logical_plan: LogicalPlanProbe::default().into(),
                 metadata: None,
+                priority: TaskPriority::default(),
+                account_id: None,
             }
             .into()],
         )
@@ -962,6 +1347,8 @@ pub async fn test_event_store_concurrent_modification(catalog: &Catalog) {
                 task_id,
                 logical_plan: LogicalPlanProbe::default().into(),
                 metadata: None,
+                priority: TaskPriority::default(),
+                account_id: None,
             }
             .into()],
         )
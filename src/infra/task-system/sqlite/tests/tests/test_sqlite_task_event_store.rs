@@ -79,6 +79,39 @@ database_transactional_test!(
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
 
+database_transactional_test!(
+    storage = sqlite,
+    fixture =
+        kamu_task_system_repo_tests::test_event_store_try_get_queued_task_excluding_plan_kinds,
+    harness = SqliteTaskSystemEventStoreHarness
+);
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+database_transactional_test!(
+    storage = sqlite,
+    fixture = kamu_task_system_repo_tests::test_event_store_try_get_queued_task_fair_share,
+    harness = SqliteTaskSystemEventStoreHarness
+);
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+database_transactional_test!(
+    storage = sqlite,
+    fixture = kamu_task_system_repo_tests::test_event_store_queue_depth_by_account,
+    harness = SqliteTaskSystemEventStoreHarness
+);
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+database_transactional_test!(
+    storage = sqlite,
+    fixture = kamu_task_system_repo_tests::test_event_store_task_leases,
+    harness = SqliteTaskSystemEventStoreHarness
+);
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
 database_transactional_test!(
     storage = sqlite,
     fixture = kamu_task_system_repo_tests::test_event_store_concurrent_modification,
@@ -0,0 +1,149 @@
+// Copyright Kamu Data, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use chrono::{DateTime, Utc};
+use database_common::PaginationOpts;
+use dill::*;
+use internal_error::InternalError;
+use uuid::Uuid;
+
+use crate::domain::*;
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+pub struct InMemoryWebhookDeliveryRepository {
+    state: Arc<Mutex<State>>,
+}
+
+#[derive(Default)]
+struct State {
+    deliveries_by_id: HashMap<Uuid, WebhookDelivery>,
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[component(pub)]
+#[interface(dyn WebhookDeliveryRepository)]
+#[scope(Singleton)]
+impl InMemoryWebhookDeliveryRepository {
+    pub fn new() -> Self {
+        Self {
+            state: Arc::new(Mutex::new(State::default())),
+        }
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[async_trait::async_trait]
+impl WebhookDeliveryRepository for InMemoryWebhookDeliveryRepository {
+    async fn create_delivery(&self, delivery: &WebhookDelivery) -> Result<(), InternalError> {
+        let mut guard = self.state.lock().unwrap();
+        guard.deliveries_by_id.insert(delivery.id, delivery.clone());
+        Ok(())
+    }
+
+    async fn update_delivery(
+        &self,
+        delivery: &WebhookDelivery,
+    ) -> Result<(), UpdateWebhookDeliveryError> {
+        let mut guard = self.state.lock().unwrap();
+        let Some(existing) = guard.deliveries_by_id.get_mut(&delivery.id) else {
+            return Err(UpdateWebhookDeliveryError::NotFound(
+                WebhookDeliveryNotFoundError {
+                    delivery_id: delivery.id,
+                },
+            ));
+        };
+        *existing = delivery.clone();
+        Ok(())
+    }
+
+    async fn claim_due_deliveries(
+        &self,
+        now: DateTime<Utc>,
+        lease_until: DateTime<Utc>,
+        limit: usize,
+    ) -> Result<Vec<WebhookDelivery>, InternalError> {
+        let mut guard = self.state.lock().unwrap();
+        let mut deliveries: Vec<_> = guard
+            .deliveries_by_id
+            .values_mut()
+            .filter(|d| {
+                d.status == WebhookDeliveryStatus::Pending
+                    && d.next_attempt_at.is_some_and(|t| t <= now)
+            })
+            .collect();
+        deliveries.sort_by(|a, b| {
+            a.next_attempt_at
+                .cmp(&b.next_attempt_at)
+                .then(a.created_at.cmp(&b.created_at))
+                .then(a.id.cmp(&b.id))
+        });
+        deliveries.truncate(limit);
+
+        let mut claimed: Vec<_> = deliveries
+            .into_iter()
+            .map(|d| {
+                d.next_attempt_at = Some(lease_until);
+                d.clone()
+            })
+            .collect();
+        claimed.sort_by(|a, b| a.created_at.cmp(&b.created_at).then(a.id.cmp(&b.id)));
+        Ok(claimed)
+    }
+
+    async fn get_deliveries_count_by_subscription_id(
+        &self,
+        subscription_id: &Uuid,
+    ) -> Result<usize, InternalError> {
+        let guard = self.state.lock().unwrap();
+        Ok(guard
+            .deliveries_by_id
+            .values()
+            .filter(|d| &d.subscription_id == subscription_id)
+            .count())
+    }
+
+    async fn get_deliveries_by_subscription_id(
+        &self,
+        subscription_id: &Uuid,
+        pagination: &PaginationOpts,
+    ) -> Result<Vec<WebhookDelivery>, InternalError> {
+        let guard = self.state.lock().unwrap();
+        let mut deliveries: Vec<_> = guard
+            .deliveries_by_id
+            .values()
+            .filter(|d| &d.subscription_id == subscription_id)
+            .cloned()
+            .collect();
+        deliveries.sort_by(|a, b| b.created_at.cmp(&a.created_at).then(b.id.cmp(&a.id)));
+        Ok(deliveries
+            .into_iter()
+            .skip(pagination.offset)
+            .take(pagination.limit)
+            .collect())
+    }
+
+    async fn delete_deliveries_by_subscription_id(
+        &self,
+        subscription_id: &Uuid,
+    ) -> Result<(), InternalError> {
+        let mut guard = self.state.lock().unwrap();
+        guard
+            .deliveries_by_id
+            .retain(|_, d| &d.subscription_id != subscription_id);
+        Ok(())
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
@@ -0,0 +1,136 @@
+// Copyright Kamu Data, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use dill::*;
+use internal_error::InternalError;
+use uuid::Uuid;
+
+use crate::domain::*;
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+pub struct InMemoryWebhookSubscriptionRepository {
+    state: Arc<Mutex<State>>,
+}
+
+#[derive(Default)]
+struct State {
+    subscriptions_by_id: HashMap<Uuid, WebhookSubscription>,
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[component(pub)]
+#[interface(dyn WebhookSubscriptionRepository)]
+#[scope(Singleton)]
+impl InMemoryWebhookSubscriptionRepository {
+    pub fn new() -> Self {
+        Self {
+            state: Arc::new(Mutex::new(State::default())),
+        }
+    }
+
+    fn collect_sorted(
+        &self,
+        predicate: impl Fn(&WebhookSubscription) -> bool,
+    ) -> Vec<WebhookSubscription> {
+        let guard = self.state.lock().unwrap();
+        let mut subscriptions: Vec<_> = guard
+            .subscriptions_by_id
+            .values()
+            .filter(|s| predicate(s))
+            .cloned()
+            .collect();
+        subscriptions.sort_by(|a, b| a.created_at.cmp(&b.created_at).then(a.id.cmp(&b.id)));
+        subscriptions
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[async_trait::async_trait]
+impl WebhookSubscriptionRepository for InMemoryWebhookSubscriptionRepository {
+    async fn create_subscription(
+        &self,
+        subscription: &WebhookSubscription,
+    ) -> Result<(), InternalError> {
+        let mut guard = self.state.lock().unwrap();
+        guard
+            .subscriptions_by_id
+            .insert(subscription.id, subscription.clone());
+        Ok(())
+    }
+
+    async fn get_subscription_by_id(
+        &self,
+        subscription_id: &Uuid,
+    ) -> Result<WebhookSubscription, GetWebhookSubscriptionError> {
+        let guard = self.state.lock().unwrap();
+        guard
+            .subscriptions_by_id
+            .get(subscription_id)
+            .cloned()
+            .ok_or_else(|| {
+                GetWebhookSubscriptionError::NotFound(WebhookSubscriptionNotFoundError {
+                    subscription_id: *subscription_id,
+                })
+            })
+    }
+
+    async fn get_subscriptions_by_account_id(
+        &self,
+        account_id: &odf::AccountID,
+    ) -> Result<Vec<WebhookSubscription>, InternalError> {
+        Ok(self.collect_sorted(|s| &s.owner_account_id == account_id))
+    }
+
+    async fn get_subscriptions_by_dataset_id(
+        &self,
+        dataset_id: &odf::DatasetID,
+    ) -> Result<Vec<WebhookSubscription>, InternalError> {
+        Ok(self.collect_sorted(|s| s.dataset_id.as_ref() == Some(dataset_id)))
+    }
+
+    async fn update_subscription_status(
+        &self,
+        subscription_id: &Uuid,
+        status: WebhookSubscriptionStatus,
+    ) -> Result<(), UpdateWebhookSubscriptionError> {
+        let mut guard = self.state.lock().unwrap();
+        let Some(subscription) = guard.subscriptions_by_id.get_mut(subscription_id) else {
+            return Err(UpdateWebhookSubscriptionError::NotFound(
+                WebhookSubscriptionNotFoundError {
+                    subscription_id: *subscription_id,
+                },
+            ));
+        };
+        subscription.status = status;
+        Ok(())
+    }
+
+    async fn delete_subscription(
+        &self,
+        subscription_id: &Uuid,
+    ) -> Result<(), DeleteWebhookSubscriptionError> {
+        let mut guard = self.state.lock().unwrap();
+        if guard.subscriptions_by_id.remove(subscription_id).is_none() {
+            return Err(DeleteWebhookSubscriptionError::NotFound(
+                WebhookSubscriptionNotFoundError {
+                    subscription_id: *subscription_id,
+                },
+            ));
+        }
+        Ok(())
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
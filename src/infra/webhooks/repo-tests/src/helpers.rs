@@ -0,0 +1,60 @@
+// Copyright Kamu Data, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use chrono::{DateTime, SubsecRound, Utc};
+use kamu_webhooks::*;
+use uuid::Uuid;
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+pub(crate) fn now() -> DateTime<Utc> {
+    Utc::now().round_subsecs(6)
+}
+
+pub(crate) fn new_subscription(
+    account_id: &odf::AccountID,
+    dataset_id: Option<&odf::DatasetID>,
+    created_at: DateTime<Utc>,
+) -> WebhookSubscription {
+    WebhookSubscription {
+        id: Uuid::new_v4(),
+        owner_account_id: account_id.clone(),
+        dataset_id: dataset_id.cloned(),
+        target_url: "https://example.com/hooks".parse().unwrap(),
+        label: "dashboard".to_string(),
+        event_types: vec![
+            WebhookEventType::DatasetUpdated,
+            WebhookEventType::FlowFailed,
+        ],
+        secret: "secret".to_string(),
+        status: WebhookSubscriptionStatus::Enabled,
+        created_at,
+    }
+}
+
+pub(crate) fn new_delivery(
+    subscription_id: Uuid,
+    account_id: &odf::AccountID,
+    created_at: DateTime<Utc>,
+) -> WebhookDelivery {
+    WebhookDelivery::new(
+        Uuid::new_v4(),
+        subscription_id,
+        WebhookEvent {
+            event_type: WebhookEventType::DatasetUpdated,
+            event_time: created_at,
+            account_id: account_id.clone(),
+            dataset_id: Some(odf::DatasetID::new_seeded_ed25519(b"foo")),
+            data: serde_json::json!({ "flowId": "1" }),
+        },
+        created_at,
+    )
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
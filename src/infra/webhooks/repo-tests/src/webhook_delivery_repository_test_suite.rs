@@ -0,0 +1,217 @@
+// Copyright Kamu Data, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::assert_matches::assert_matches;
+
+use database_common::PaginationOpts;
+use dill::Catalog;
+use kamu_webhooks::*;
+use uuid::Uuid;
+
+use crate::helpers::{new_delivery, now};
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+pub async fn test_no_deliveries(catalog: &Catalog) {
+    let repo = catalog.get_one::<dyn WebhookDeliveryRepository>().unwrap();
+
+    let subscription_id = Uuid::new_v4();
+
+    assert_eq!(
+        repo.get_deliveries_count_by_subscription_id(&subscription_id)
+            .await
+            .unwrap(),
+        0
+    );
+    assert!(repo
+        .claim_due_deliveries(now(), now(), 10)
+        .await
+        .unwrap()
+        .is_empty());
+
+    let account_id = odf::AccountID::new_seeded_ed25519(b"alice");
+    assert_matches!(
+        repo.update_delivery(&new_delivery(subscription_id, &account_id, now()))
+            .await,
+        Err(UpdateWebhookDeliveryError::NotFound(_))
+    );
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+pub async fn test_create_and_list_deliveries(catalog: &Catalog) {
+    let repo = catalog.get_one::<dyn WebhookDeliveryRepository>().unwrap();
+
+    let account_id = odf::AccountID::new_seeded_ed25519(b"alice");
+    let subscription_id = Uuid::new_v4();
+    let other_subscription_id = Uuid::new_v4();
+
+    let t = now();
+    let deliveries: Vec<_> = (0..3)
+        .map(|i| {
+            new_delivery(
+                subscription_id,
+                &account_id,
+                t + chrono::Duration::seconds(i),
+            )
+        })
+        .collect();
+    for d in &deliveries {
+        repo.create_delivery(d).await.unwrap();
+    }
+    repo.create_delivery(&new_delivery(other_subscription_id, &account_id, t))
+        .await
+        .unwrap();
+
+    assert_eq!(
+        repo.get_deliveries_count_by_subscription_id(&subscription_id)
+            .await
+            .unwrap(),
+        3
+    );
+
+    // Newest first
+    let page = repo
+        .get_deliveries_by_subscription_id(
+            &subscription_id,
+            &PaginationOpts {
+                limit: 2,
+                offset: 0,
+            },
+        )
+        .await
+        .unwrap();
+    assert_eq!(page, vec![deliveries[2].clone(), deliveries[1].clone()]);
+
+    let page = repo
+        .get_deliveries_by_subscription_id(
+            &subscription_id,
+            &PaginationOpts {
+                limit: 2,
+                offset: 2,
+            },
+        )
+        .await
+        .unwrap();
+    assert_eq!(page, vec![deliveries[0].clone()]);
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+pub async fn test_claim_due_deliveries(catalog: &Catalog) {
+    let repo = catalog.get_one::<dyn WebhookDeliveryRepository>().unwrap();
+
+    let account_id = odf::AccountID::new_seeded_ed25519(b"alice");
+    let subscription_id = Uuid::new_v4();
+
+    let t = now();
+    let mut retried = new_delivery(subscription_id, &account_id, t);
+    let fresh = new_delivery(
+        subscription_id,
+        &account_id,
+        t + chrono::Duration::seconds(1),
+    );
+    let mut delivered = new_delivery(subscription_id, &account_id, t);
+    for d in [&retried, &fresh, &delivered] {
+        repo.create_delivery(d).await.unwrap();
+    }
+
+    retried.attempt_failed(
+        t,
+        Some(500),
+        "Server error".to_string(),
+        Some(t + chrono::Duration::seconds(10)),
+    );
+    repo.update_delivery(&retried).await.unwrap();
+
+    delivered.succeeded(t, 200);
+    repo.update_delivery(&delivered).await.unwrap();
+
+    let lease_until = t + chrono::Duration::seconds(100);
+    let leased = |d: &WebhookDelivery| WebhookDelivery {
+        next_attempt_at: Some(lease_until),
+        ..d.clone()
+    };
+
+    assert_eq!(
+        repo.claim_due_deliveries(t + chrono::Duration::seconds(5), lease_until, 10)
+            .await
+            .unwrap(),
+        vec![leased(&fresh)]
+    );
+
+    // Claimed deliveries are skipped until their lease expires
+    assert_eq!(
+        repo.claim_due_deliveries(t + chrono::Duration::seconds(10), lease_until, 10)
+            .await
+            .unwrap(),
+        vec![leased(&retried)]
+    );
+    assert!(repo
+        .claim_due_deliveries(t + chrono::Duration::seconds(10), lease_until, 10)
+        .await
+        .unwrap()
+        .is_empty());
+
+    let expired = lease_until + chrono::Duration::seconds(1);
+    let lease_until = expired + chrono::Duration::seconds(100);
+    let leased = |d: &WebhookDelivery| WebhookDelivery {
+        next_attempt_at: Some(lease_until),
+        ..d.clone()
+    };
+    assert_eq!(
+        repo.claim_due_deliveries(expired, lease_until, 1)
+            .await
+            .unwrap(),
+        vec![leased(&retried)]
+    );
+    assert_eq!(
+        repo.claim_due_deliveries(expired, lease_until, 10)
+            .await
+            .unwrap(),
+        vec![leased(&fresh)]
+    );
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+pub async fn test_delete_deliveries_by_subscription(catalog: &Catalog) {
+    let repo = catalog.get_one::<dyn WebhookDeliveryRepository>().unwrap();
+
+    let account_id = odf::AccountID::new_seeded_ed25519(b"alice");
+    let subscription_id = Uuid::new_v4();
+    let other_subscription_id = Uuid::new_v4();
+
+    let t = now();
+    repo.create_delivery(&new_delivery(subscription_id, &account_id, t))
+        .await
+        .unwrap();
+    repo.create_delivery(&new_delivery(other_subscription_id, &account_id, t))
+        .await
+        .unwrap();
+
+    repo.delete_deliveries_by_subscription_id(&subscription_id)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        repo.get_deliveries_count_by_subscription_id(&subscription_id)
+            .await
+            .unwrap(),
+        0
+    );
+    assert_eq!(
+        repo.get_deliveries_count_by_subscription_id(&other_subscription_id)
+            .await
+            .unwrap(),
+        1
+    );
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
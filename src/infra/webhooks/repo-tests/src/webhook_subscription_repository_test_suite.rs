@@ -0,0 +1,158 @@
+// Copyright Kamu Data, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::assert_matches::assert_matches;
+
+use dill::Catalog;
+use kamu_webhooks::*;
+use uuid::Uuid;
+
+use crate::helpers::{new_subscription, now};
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+pub async fn test_missing_subscription_not_found(catalog: &Catalog) {
+    let repo = catalog
+        .get_one::<dyn WebhookSubscriptionRepository>()
+        .unwrap();
+
+    let missing_id = Uuid::new_v4();
+
+    assert_matches!(
+        repo.get_subscription_by_id(&missing_id).await,
+        Err(GetWebhookSubscriptionError::NotFound(_))
+    );
+    assert_matches!(
+        repo.update_subscription_status(&missing_id, WebhookSubscriptionStatus::Paused)
+            .await,
+        Err(UpdateWebhookSubscriptionError::NotFound(_))
+    );
+    assert_matches!(
+        repo.delete_subscription(&missing_id).await,
+        Err(DeleteWebhookSubscriptionError::NotFound(_))
+    );
+
+    let subscriptions = repo
+        .get_subscriptions_by_account_id(&odf::AccountID::new_seeded_ed25519(b"alice"))
+        .await
+        .unwrap();
+    assert!(subscriptions.is_empty());
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+pub async fn test_create_and_get_subscription(catalog: &Catalog) {
+    let repo = catalog
+        .get_one::<dyn WebhookSubscriptionRepository>()
+        .unwrap();
+
+    let account_id = odf::AccountID::new_seeded_ed25519(b"alice");
+    let dataset_id = odf::DatasetID::new_seeded_ed25519(b"foo");
+    let subscription = new_subscription(&account_id, Some(&dataset_id), now());
+
+    repo.create_subscription(&subscription).await.unwrap();
+
+    let actual = repo.get_subscription_by_id(&subscription.id).await.unwrap();
+    assert_eq!(actual, subscription);
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+pub async fn test_list_subscriptions(catalog: &Catalog) {
+    let repo = catalog
+        .get_one::<dyn WebhookSubscriptionRepository>()
+        .unwrap();
+
+    let alice = odf::AccountID::new_seeded_ed25519(b"alice");
+    let bob = odf::AccountID::new_seeded_ed25519(b"bob");
+    let foo = odf::DatasetID::new_seeded_ed25519(b"foo");
+    let bar = odf::DatasetID::new_seeded_ed25519(b"bar");
+
+    let t = now();
+    let alice_all = new_subscription(&alice, None, t);
+    let alice_foo = new_subscription(&alice, Some(&foo), t + chrono::Duration::seconds(1));
+    let bob_bar = new_subscription(&bob, Some(&bar), t + chrono::Duration::seconds(2));
+
+    for s in [&alice_all, &alice_foo, &bob_bar] {
+        repo.create_subscription(s).await.unwrap();
+    }
+
+    let ids = |subscriptions: Vec<WebhookSubscription>| -> Vec<Uuid> {
+        subscriptions.into_iter().map(|s| s.id).collect()
+    };
+
+    assert_eq!(
+        ids(repo.get_subscriptions_by_account_id(&alice).await.unwrap()),
+        vec![alice_all.id, alice_foo.id]
+    );
+    assert_eq!(
+        ids(repo.get_subscriptions_by_account_id(&bob).await.unwrap()),
+        vec![bob_bar.id]
+    );
+    assert_eq!(
+        ids(repo.get_subscriptions_by_dataset_id(&foo).await.unwrap()),
+        vec![alice_foo.id]
+    );
+    assert_eq!(
+        ids(repo.get_subscriptions_by_dataset_id(&bar).await.unwrap()),
+        vec![bob_bar.id]
+    );
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+pub async fn test_update_subscription_status(catalog: &Catalog) {
+    let repo = catalog
+        .get_one::<dyn WebhookSubscriptionRepository>()
+        .unwrap();
+
+    let account_id = odf::AccountID::new_seeded_ed25519(b"alice");
+    let subscription = new_subscription(&account_id, None, now());
+    repo.create_subscription(&subscription).await.unwrap();
+
+    repo.update_subscription_status(&subscription.id, WebhookSubscriptionStatus::Paused)
+        .await
+        .unwrap();
+
+    let actual = repo.get_subscription_by_id(&subscription.id).await.unwrap();
+    assert_eq!(actual.status, WebhookSubscriptionStatus::Paused);
+
+    repo.update_subscription_status(&subscription.id, WebhookSubscriptionStatus::Enabled)
+        .await
+        .unwrap();
+
+    let actual = repo.get_subscription_by_id(&subscription.id).await.unwrap();
+    assert_eq!(actual.status, WebhookSubscriptionStatus::Enabled);
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+pub async fn test_delete_subscription(catalog: &Catalog) {
+    let repo = catalog
+        .get_one::<dyn WebhookSubscriptionRepository>()
+        .unwrap();
+
+    let account_id = odf::AccountID::new_seeded_ed25519(b"alice");
+    let subscription = new_subscription(&account_id, None, now());
+    repo.create_subscription(&subscription).await.unwrap();
+
+    repo.delete_subscription(&subscription.id).await.unwrap();
+
+    assert_matches!(
+        repo.get_subscription_by_id(&subscription.id).await,
+        Err(GetWebhookSubscriptionError::NotFound(_))
+    );
+    assert!(repo
+        .get_subscriptions_by_account_id(&account_id)
+        .await
+        .unwrap()
+        .is_empty());
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
@@ -0,0 +1,287 @@
+// Copyright Kamu Data, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use chrono::{DateTime, Utc};
+use database_common::{PaginationOpts, TransactionRef, TransactionRefT};
+use dill::{component, interface};
+use internal_error::{InternalError, ResultIntoInternal};
+use uuid::Uuid;
+
+use crate::domain::*;
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+pub struct SqliteWebhookDeliveryRepository {
+    transaction: TransactionRefT<sqlx::Sqlite>,
+}
+
+#[component(pub)]
+#[interface(dyn WebhookDeliveryRepository)]
+impl SqliteWebhookDeliveryRepository {
+    pub fn new(transaction: TransactionRef) -> Self {
+        Self {
+            transaction: transaction.into(),
+        }
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[async_trait::async_trait]
+impl WebhookDeliveryRepository for SqliteWebhookDeliveryRepository {
+    async fn create_delivery(&self, delivery: &WebhookDelivery) -> Result<(), InternalError> {
+        let mut tr = self.transaction.lock().await;
+        let connection_mut = tr.connection_mut().await?;
+
+        let event = serde_json::to_string(&delivery.event).int_err()?;
+        let attempts = i64::from(delivery.attempts);
+        let response_status_code = delivery.response_status_code.map(i64::from);
+
+        sqlx::query!(
+            r#"
+            INSERT INTO webhook_deliveries (id, subscription_id, event_type, event, status, attempts, next_attempt_at, response_status_code, last_error, created_at, updated_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+            "#,
+            delivery.id,
+            delivery.subscription_id,
+            delivery.event.event_type.as_str(),
+            event,
+            delivery.status.as_str(),
+            attempts,
+            delivery.next_attempt_at,
+            response_status_code,
+            delivery.last_error,
+            delivery.created_at,
+            delivery.updated_at,
+        )
+        .execute(connection_mut)
+        .await
+        .int_err()?;
+
+        Ok(())
+    }
+
+    async fn update_delivery(
+        &self,
+        delivery: &WebhookDelivery,
+    ) -> Result<(), UpdateWebhookDeliveryError> {
+        let mut tr = self.transaction.lock().await;
+        let connection_mut = tr.connection_mut().await?;
+
+        let attempts = i64::from(delivery.attempts);
+        let response_status_code = delivery.response_status_code.map(i64::from);
+
+        let update_result = sqlx::query!(
+            r#"
+            UPDATE webhook_deliveries SET
+                status = $1,
+                attempts = $2,
+                next_attempt_at = $3,
+                response_status_code = $4,
+                last_error = $5,
+                updated_at = $6
+            WHERE id = $7
+            "#,
+            delivery.status.as_str(),
+            attempts,
+            delivery.next_attempt_at,
+            response_status_code,
+            delivery.last_error,
+            delivery.updated_at,
+            delivery.id,
+        )
+        .execute(connection_mut)
+        .await
+        .int_err()?;
+
+        if update_result.rows_affected() == 0 {
+            return Err(UpdateWebhookDeliveryError::NotFound(
+                WebhookDeliveryNotFoundError {
+                    delivery_id: delivery.id,
+                },
+            ));
+        }
+
+        Ok(())
+    }
+
+    async fn claim_due_deliveries(
+        &self,
+        now: DateTime<Utc>,
+        lease_until: DateTime<Utc>,
+        limit: usize,
+    ) -> Result<Vec<WebhookDelivery>, InternalError> {
+        let mut tr = self.transaction.lock().await;
+        let connection_mut = tr.connection_mut().await?;
+
+        let limit = i64::try_from(limit).unwrap();
+
+        let rows = sqlx::query_as!(
+            WebhookDeliveryRowModel,
+            r#"
+            UPDATE webhook_deliveries
+            SET next_attempt_at = $1
+            WHERE id IN (
+                SELECT id
+                FROM webhook_deliveries
+                WHERE status = $2 AND next_attempt_at <= $3
+                ORDER BY next_attempt_at, created_at, id
+                LIMIT $4
+            )
+            RETURNING
+                id as "id: Uuid",
+                subscription_id as "subscription_id: Uuid",
+                event as "event: _",
+                status,
+                attempts,
+                next_attempt_at as "next_attempt_at: _",
+                response_status_code,
+                last_error,
+                created_at as "created_at: _",
+                updated_at as "updated_at: _"
+            "#,
+            lease_until,
+            WebhookDeliveryStatus::Pending.as_str(),
+            now,
+            limit,
+        )
+        .fetch_all(connection_mut)
+        .await
+        .int_err()?;
+
+        let mut deliveries = rows
+            .into_iter()
+            .map(TryInto::try_into)
+            .collect::<Result<Vec<WebhookDelivery>, _>>()?;
+        deliveries.sort_by(|a, b| a.created_at.cmp(&b.created_at).then(a.id.cmp(&b.id)));
+        Ok(deliveries)
+    }
+
+    async fn get_deliveries_count_by_subscription_id(
+        &self,
+        subscription_id: &Uuid,
+    ) -> Result<usize, InternalError> {
+        let mut tr = self.transaction.lock().await;
+        let connection_mut = tr.connection_mut().await?;
+
+        let count = sqlx::query_scalar!(
+            r#"
+            SELECT COUNT(*) FROM webhook_deliveries WHERE subscription_id = $1
+            "#,
+            subscription_id,
+        )
+        .fetch_one(connection_mut)
+        .await
+        .int_err()?;
+
+        Ok(usize::try_from(count).unwrap())
+    }
+
+    async fn get_deliveries_by_subscription_id(
+        &self,
+        subscription_id: &Uuid,
+        pagination: &PaginationOpts,
+    ) -> Result<Vec<WebhookDelivery>, InternalError> {
+        let mut tr = self.transaction.lock().await;
+        let connection_mut = tr.connection_mut().await?;
+
+        let limit = i64::try_from(pagination.limit).unwrap();
+        let offset = i64::try_from(pagination.offset).unwrap();
+
+        let rows = sqlx::query_as!(
+            WebhookDeliveryRowModel,
+            r#"
+            SELECT
+                id as "id: Uuid",
+                subscription_id as "subscription_id: Uuid",
+                event as "event: _",
+                status,
+                attempts,
+                next_attempt_at as "next_attempt_at: _",
+                response_status_code,
+                last_error,
+                created_at as "created_at: _",
+                updated_at as "updated_at: _"
+            FROM webhook_deliveries
+            WHERE subscription_id = $1
+            ORDER BY created_at DESC, id DESC
+            LIMIT $2 OFFSET $3
+            "#,
+            subscription_id,
+            limit,
+            offset,
+        )
+        .fetch_all(connection_mut)
+        .await
+        .int_err()?;
+
+        rows.into_iter().map(TryInto::try_into).collect()
+    }
+
+    async fn delete_deliveries_by_subscription_id(
+        &self,
+        subscription_id: &Uuid,
+    ) -> Result<(), InternalError> {
+        let mut tr = self.transaction.lock().await;
+        let connection_mut = tr.connection_mut().await?;
+
+        sqlx::query!(
+            r#"
+            DELETE FROM webhook_deliveries WHERE subscription_id = $1
+            "#,
+            subscription_id,
+        )
+        .execute(connection_mut)
+        .await
+        .int_err()?;
+
+        Ok(())
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[derive(Debug)]
+struct WebhookDeliveryRowModel {
+    id: Uuid,
+    subscription_id: Uuid,
+    event: String,
+    status: String,
+    attempts: i64,
+    next_attempt_at: Option<DateTime<Utc>>,
+    response_status_code: Option<i64>,
+    last_error: Option<String>,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+}
+
+impl TryFrom<WebhookDeliveryRowModel> for WebhookDelivery {
+    type Error = InternalError;
+
+    fn try_from(row: WebhookDeliveryRowModel) -> Result<Self, Self::Error> {
+        Ok(Self {
+            id: row.id,
+            subscription_id: row.subscription_id,
+            event: serde_json::from_str(&row.event).int_err()?,
+            status: row.status.parse().int_err()?,
+            attempts: u32::try_from(row.attempts).int_err()?,
+            next_attempt_at: row.next_attempt_at,
+            response_status_code: row
+                .response_status_code
+                .map(u16::try_from)
+                .transpose()
+                .int_err()?,
+            last_error: row.last_error,
+            created_at: row.created_at,
+            updated_at: row.updated_at,
+        })
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
@@ -0,0 +1,276 @@
+// Copyright Kamu Data, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use chrono::{DateTime, Utc};
+use database_common::{TransactionRef, TransactionRefT};
+use dill::{component, interface};
+use internal_error::{InternalError, ResultIntoInternal};
+use uuid::Uuid;
+
+use crate::domain::*;
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+pub struct SqliteWebhookSubscriptionRepository {
+    transaction: TransactionRefT<sqlx::Sqlite>,
+}
+
+#[component(pub)]
+#[interface(dyn WebhookSubscriptionRepository)]
+impl SqliteWebhookSubscriptionRepository {
+    pub fn new(transaction: TransactionRef) -> Self {
+        Self {
+            transaction: transaction.into(),
+        }
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[async_trait::async_trait]
+impl WebhookSubscriptionRepository for SqliteWebhookSubscriptionRepository {
+    async fn create_subscription(
+        &self,
+        subscription: &WebhookSubscription,
+    ) -> Result<(), InternalError> {
+        let mut tr = self.transaction.lock().await;
+        let connection_mut = tr.connection_mut().await?;
+
+        let owner_account_id = subscription.owner_account_id.to_string();
+        let dataset_id = subscription.dataset_id.as_ref().map(ToString::to_string);
+        let event_types = serde_json::to_string(&subscription.event_types).int_err()?;
+
+        sqlx::query!(
+            r#"
+            INSERT INTO webhook_subscriptions (id, owner_account_id, dataset_id, target_url, label, event_types, secret, status, created_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+            "#,
+            subscription.id,
+            owner_account_id,
+            dataset_id,
+            subscription.target_url.as_str(),
+            subscription.label,
+            event_types,
+            subscription.secret,
+            subscription.status.as_str(),
+            subscription.created_at,
+        )
+        .execute(connection_mut)
+        .await
+        .int_err()?;
+
+        Ok(())
+    }
+
+    async fn get_subscription_by_id(
+        &self,
+        subscription_id: &Uuid,
+    ) -> Result<WebhookSubscription, GetWebhookSubscriptionError> {
+        let mut tr = self.transaction.lock().await;
+        let connection_mut = tr.connection_mut().await?;
+
+        let maybe_row = sqlx::query_as!(
+            WebhookSubscriptionRowModel,
+            r#"
+            SELECT
+                id as "id: Uuid",
+                owner_account_id,
+                dataset_id,
+                target_url,
+                label,
+                event_types as "event_types: _",
+                secret,
+                status,
+                created_at as "created_at: _"
+            FROM webhook_subscriptions
+            WHERE id = $1
+            "#,
+            subscription_id,
+        )
+        .fetch_optional(connection_mut)
+        .await
+        .int_err()?;
+
+        match maybe_row {
+            Some(row) => Ok(row.try_into()?),
+            None => Err(GetWebhookSubscriptionError::NotFound(
+                WebhookSubscriptionNotFoundError {
+                    subscription_id: *subscription_id,
+                },
+            )),
+        }
+    }
+
+    async fn get_subscriptions_by_account_id(
+        &self,
+        account_id: &odf::AccountID,
+    ) -> Result<Vec<WebhookSubscription>, InternalError> {
+        let mut tr = self.transaction.lock().await;
+        let connection_mut = tr.connection_mut().await?;
+
+        let account_id = account_id.to_string();
+
+        let rows = sqlx::query_as!(
+            WebhookSubscriptionRowModel,
+            r#"
+            SELECT
+                id as "id: Uuid",
+                owner_account_id,
+                dataset_id,
+                target_url,
+                label,
+                event_types as "event_types: _",
+                secret,
+                status,
+                created_at as "created_at: _"
+            FROM webhook_subscriptions
+            WHERE owner_account_id = $1
+            ORDER BY created_at, id
+            "#,
+            account_id,
+        )
+        .fetch_all(connection_mut)
+        .await
+        .int_err()?;
+
+        rows.into_iter().map(TryInto::try_into).collect()
+    }
+
+    async fn get_subscriptions_by_dataset_id(
+        &self,
+        dataset_id: &odf::DatasetID,
+    ) -> Result<Vec<WebhookSubscription>, InternalError> {
+        let mut tr = self.transaction.lock().await;
+        let connection_mut = tr.connection_mut().await?;
+
+        let dataset_id = dataset_id.to_string();
+
+        let rows = sqlx::query_as!(
+            WebhookSubscriptionRowModel,
+            r#"
+            SELECT
+                id as "id: Uuid",
+                owner_account_id,
+                dataset_id,
+                target_url,
+                label,
+                event_types as "event_types: _",
+                secret,
+                status,
+                created_at as "created_at: _"
+            FROM webhook_subscriptions
+            WHERE dataset_id = $1
+            ORDER BY created_at, id
+            "#,
+            dataset_id,
+        )
+        .fetch_all(connection_mut)
+        .await
+        .int_err()?;
+
+        rows.into_iter().map(TryInto::try_into).collect()
+    }
+
+    async fn update_subscription_status(
+        &self,
+        subscription_id: &Uuid,
+        status: WebhookSubscriptionStatus,
+    ) -> Result<(), UpdateWebhookSubscriptionError> {
+        let mut tr = self.transaction.lock().await;
+        let connection_mut = tr.connection_mut().await?;
+
+        let update_result = sqlx::query!(
+            r#"
+            UPDATE webhook_subscriptions SET status = $1 WHERE id = $2
+            "#,
+            status.as_str(),
+            subscription_id,
+        )
+        .execute(connection_mut)
+        .await
+        .int_err()?;
+
+        if update_result.rows_affected() == 0 {
+            return Err(UpdateWebhookSubscriptionError::NotFound(
+                WebhookSubscriptionNotFoundError {
+                    subscription_id: *subscription_id,
+                },
+            ));
+        }
+
+        Ok(())
+    }
+
+    async fn delete_subscription(
+        &self,
+        subscription_id: &Uuid,
+    ) -> Result<(), DeleteWebhookSubscriptionError> {
+        let mut tr = self.transaction.lock().await;
+        let connection_mut = tr.connection_mut().await?;
+
+        let delete_result = sqlx::query!(
+            r#"
+            DELETE FROM webhook_subscriptions WHERE id = $1
+            "#,
+            subscription_id,
+        )
+        .execute(connection_mut)
+        .await
+        .int_err()?;
+
+        if delete_result.rows_affected() == 0 {
+            return Err(DeleteWebhookSubscriptionError::NotFound(
+                WebhookSubscriptionNotFoundError {
+                    subscription_id: *subscription_id,
+                },
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[derive(Debug)]
+struct WebhookSubscriptionRowModel {
+    id: Uuid,
+    owner_account_id: String,
+    dataset_id: Option<String>,
+    target_url: String,
+    label: String,
+    event_types: String,
+    secret: String,
+    status: String,
+    created_at: DateTime<Utc>,
+}
+
+impl TryFrom<WebhookSubscriptionRowModel> for WebhookSubscription {
+    type Error = InternalError;
+
+    fn try_from(row: WebhookSubscriptionRowModel) -> Result<Self, Self::Error> {
+        Ok(Self {
+            id: row.id,
+            owner_account_id: odf::AccountID::from_did_str(&row.owner_account_id).int_err()?,
+            dataset_id: row
+                .dataset_id
+                .map(|dataset_id| odf::DatasetID::from_did_str(&dataset_id))
+                .transpose()
+                .int_err()?,
+            target_url: row.target_url.parse().int_err()?,
+            label: row.label,
+            event_types: serde_json::from_str(&row.event_types).int_err()?,
+            secret: row.secret,
+            status: row.status.parse().int_err()?,
+            created_at: row.created_at,
+        })
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
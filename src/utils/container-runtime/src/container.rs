@@ -468,10 +468,20 @@ impl ContainerProcess {
         self.child.wait().await
     }
 
+    /// Returns the exit status if the container has already exited
+    pub fn try_wait(&mut self) -> std::io::Result<Option<ExitStatus>> {
+        self.child.try_wait()
+    }
+
     #[tracing::instrument(level = "debug", name = "terminate_container", skip_all, fields(container_name = %self.container_name))]
     pub async fn terminate(&mut self) -> std::io::Result<TerminateStatus> {
         self.child.terminate(self.terminate_timeout).await
     }
+
+    /// A blocking version of [`ContainerProcess::terminate()`]
+    pub fn terminate_blocking(&mut self) -> std::io::Result<TerminateStatus> {
+        self.child.terminate_blocking(self.terminate_timeout)
+    }
 }
 
 impl Drop for ContainerProcess {
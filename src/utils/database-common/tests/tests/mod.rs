@@ -9,3 +9,4 @@
 
 mod test_entity_page_streamer;
 mod test_helpers;
+mod test_sqlite_migrations;
@@ -0,0 +1,140 @@
+// Copyright Kamu Data, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::str::FromStr;
+
+use database_common::SQLITE_MIGRATOR;
+use sqlx::sqlite::{SqliteConnectOptions, SqlitePoolOptions};
+use sqlx::{Connection, Executor, SqliteConnection};
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+const REPROCESS_FLOW_TYPE_MIGRATION: i64 = 20250407100028;
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[tokio::test]
+async fn test_reprocess_flow_type_migration_keeps_flows() {
+    let pool = SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect_with(SqliteConnectOptions::from_str("sqlite::memory:").unwrap())
+        .await
+        .unwrap();
+    let mut conn = pool.acquire().await.unwrap();
+
+    apply_migrations(&mut conn, |version| version < REPROCESS_FLOW_TYPE_MIGRATION).await;
+
+    (&mut *conn).execute(
+        r#"
+        INSERT INTO flow_ids (flow_id, created_time) VALUES (1, '2025-01-01T00:00:00Z');
+
+        INSERT INTO flows (flow_id, dataset_id, dataset_flow_type, initiator, flow_status)
+            VALUES (1, 'did:odf:foo', 'ingest', 'kamu', 'finished');
+
+        INSERT INTO flow_events (flow_id, event_type, event_time, event_payload)
+            VALUES (1, 'FlowEventInitiated', '2025-01-01T00:00:00Z', '{}');
+
+        UPDATE flows SET last_event_id = 1 WHERE flow_id = 1;
+
+        INSERT INTO flow_configuration_events
+            (created_time, dataset_id, dataset_flow_type, event_type, event_time, event_payload)
+            VALUES
+            ('2025-01-01T00:00:00Z', 'did:odf:foo', 'ingest', 'FlowConfigurationEventCreated', '2025-01-01T00:00:00Z', '{}');
+
+        INSERT INTO flow_trigger_events
+            (created_time, dataset_id, dataset_flow_type, event_type, event_time, event_payload)
+            VALUES
+            ('2025-01-01T00:00:00Z', 'did:odf:foo', 'ingest', 'FlowTriggerEventCreated', '2025-01-01T00:00:00Z', '{}');
+        "#,
+    )
+    .await
+    .unwrap();
+
+    apply_migrations(&mut conn, |version| {
+        version >= REPROCESS_FLOW_TYPE_MIGRATION
+    })
+    .await;
+
+    let flows: Vec<(i64, Option<i64>)> = sqlx::query_as("SELECT flow_id, last_event_id FROM flows")
+        .fetch_all(&mut *conn)
+        .await
+        .unwrap();
+    assert_eq!(flows, [(1, Some(1))]);
+
+    for table in [
+        "flow_events",
+        "flow_configuration_events",
+        "flow_trigger_events",
+    ] {
+        let (num_rows,): (i64,) = sqlx::query_as(&format!("SELECT COUNT(*) FROM {table}"))
+            .fetch_one(&mut *conn)
+            .await
+            .unwrap();
+        assert_eq!(num_rows, 1, "{table}");
+    }
+
+    let (foreign_keys,): (i64,) = sqlx::query_as("PRAGMA foreign_keys")
+        .fetch_one(&mut *conn)
+        .await
+        .unwrap();
+    assert_eq!(foreign_keys, 1);
+
+    let violations = sqlx::query("PRAGMA foreign_key_check")
+        .fetch_all(&mut *conn)
+        .await
+        .unwrap();
+    assert!(violations.is_empty());
+
+    // New flow type is accepted and events still reference the rebuilt table
+    (&mut *conn)
+        .execute(
+            r#"
+        INSERT INTO flow_ids (flow_id, created_time) VALUES (2, '2025-01-02T00:00:00Z');
+
+        INSERT INTO flows (flow_id, dataset_id, dataset_flow_type, initiator, flow_status)
+            VALUES (2, 'did:odf:foo', 'reprocess', 'kamu', 'waiting');
+
+        INSERT INTO flow_events (flow_id, event_type, event_time, event_payload)
+            VALUES (2, 'FlowEventInitiated', '2025-01-02T00:00:00Z', '{}');
+        "#,
+        )
+        .await
+        .unwrap();
+
+    assert!((&mut *conn)
+        .execute(
+            r#"
+            INSERT INTO flow_events (flow_id, event_type, event_time, event_payload)
+                VALUES (3, 'FlowEventInitiated', '2025-01-02T00:00:00Z', '{}');
+            "#,
+        )
+        .await
+        .is_err());
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+/// Applies the selected migrations the same way the migrator does, i.e. each
+/// in its own transaction unless it opts out of it
+async fn apply_migrations(conn: &mut SqliteConnection, filter: impl Fn(i64) -> bool) {
+    for migration in SQLITE_MIGRATOR
+        .iter()
+        .filter(|m| m.migration_type.is_up_migration() && filter(m.version))
+    {
+        if migration.no_tx {
+            (&mut *conn).execute(&*migration.sql).await.unwrap();
+        } else {
+            let mut tx = conn.begin().await.unwrap();
+            (&mut *tx).execute(&*migration.sql).await.unwrap();
+            tx.commit().await.unwrap();
+        }
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
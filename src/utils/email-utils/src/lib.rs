@@ -8,4 +8,9 @@
 // by the Apache License, Version 2.0.
 
 mod email;
+mod email_sender;
+mod senders;
+
 pub use email::*;
+pub use email_sender::*;
+pub use senders::*;
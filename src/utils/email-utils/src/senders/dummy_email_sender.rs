@@ -0,0 +1,48 @@
+// Copyright Kamu Data, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::sync::Mutex;
+
+use dill::*;
+use internal_error::InternalError;
+
+use crate::{EmailMessage, EmailSender};
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+/// Keeps sent emails in memory instead of sending them anywhere
+#[derive(Default)]
+pub struct DummyEmailSender {
+    sent_emails: Mutex<Vec<EmailMessage>>,
+}
+
+#[component(pub)]
+#[interface(dyn EmailSender)]
+#[scope(Singleton)]
+impl DummyEmailSender {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn sent_emails(&self) -> Vec<EmailMessage> {
+        self.sent_emails.lock().unwrap().clone()
+    }
+}
+
+#[async_trait::async_trait]
+impl EmailSender for DummyEmailSender {
+    async fn send_email(&self, message: EmailMessage) -> Result<(), InternalError> {
+        tracing::debug!(to = ?message.to, subject = %message.subject, "Dummy email sent");
+
+        self.sent_emails.lock().unwrap().push(message);
+        Ok(())
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
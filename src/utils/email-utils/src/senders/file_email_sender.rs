@@ -0,0 +1,75 @@
+// Copyright Kamu Data, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use dill::*;
+use internal_error::{InternalError, ResultIntoInternal};
+
+use crate::{EmailMessage, EmailSender};
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[derive(Debug, Clone)]
+pub struct FileEmailSenderConfig {
+    /// Directory where every email is stored as a separate `.eml` file
+    pub output_dir: PathBuf,
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+/// Writes emails to a local directory, useful for development and testing
+pub struct FileEmailSender {
+    config: Arc<FileEmailSenderConfig>,
+}
+
+#[component(pub)]
+#[interface(dyn EmailSender)]
+impl FileEmailSender {
+    pub fn new(config: Arc<FileEmailSenderConfig>) -> Self {
+        Self { config }
+    }
+
+    fn render(message: &EmailMessage) -> String {
+        let to = message
+            .to
+            .iter()
+            .map(AsRef::as_ref)
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        format!(
+            "To: {to}\r\nSubject: {}\r\nContent-Type: text/plain; charset=utf-8\r\n\r\n{}",
+            message.subject, message.body
+        )
+    }
+}
+
+#[async_trait::async_trait]
+impl EmailSender for FileEmailSender {
+    async fn send_email(&self, message: EmailMessage) -> Result<(), InternalError> {
+        tokio::fs::create_dir_all(&self.config.output_dir)
+            .await
+            .int_err()?;
+
+        let path = self
+            .config
+            .output_dir
+            .join(format!("{}.eml", uuid::Uuid::new_v4()));
+
+        tracing::debug!(?path, to = ?message.to, subject = %message.subject, "Writing email to file");
+
+        tokio::fs::write(&path, Self::render(&message))
+            .await
+            .int_err()
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
@@ -0,0 +1,18 @@
+// Copyright Kamu Data, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+mod dummy_email_sender;
+mod file_email_sender;
+#[cfg(feature = "smtp")]
+mod smtp_email_sender;
+
+pub use dummy_email_sender::*;
+pub use file_email_sender::*;
+#[cfg(feature = "smtp")]
+pub use smtp_email_sender::*;
@@ -0,0 +1,98 @@
+// Copyright Kamu Data, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::sync::Arc;
+
+use dill::*;
+use internal_error::{InternalError, ResultIntoInternal};
+use lettre::message::header::ContentType;
+use lettre::message::Mailbox;
+use lettre::transport::smtp::authentication::Credentials;
+use lettre::{AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor};
+
+use crate::{Email, EmailMessage, EmailSender};
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[derive(Debug, Clone)]
+pub struct SmtpEmailSenderConfig {
+    pub host: String,
+    /// Uses the default port of the selected encryption when not specified
+    pub port: Option<u16>,
+    pub username: Option<String>,
+    pub password: Option<String>,
+    /// Use implicit TLS instead of upgrading the connection via `STARTTLS`
+    pub implicit_tls: bool,
+    pub sender_address: Email,
+    pub sender_name: Option<String>,
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+pub struct SmtpEmailSender {
+    config: Arc<SmtpEmailSenderConfig>,
+    transport: AsyncSmtpTransport<Tokio1Executor>,
+}
+
+#[component(pub)]
+#[interface(dyn EmailSender)]
+#[scope(Singleton)]
+impl SmtpEmailSender {
+    pub fn new(config: Arc<SmtpEmailSenderConfig>) -> Self {
+        let mut builder = if config.implicit_tls {
+            AsyncSmtpTransport::<Tokio1Executor>::relay(&config.host)
+        } else {
+            AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(&config.host)
+        }
+        .expect("Invalid SMTP relay configuration");
+
+        if let Some(port) = config.port {
+            builder = builder.port(port);
+        }
+
+        if let (Some(username), Some(password)) = (&config.username, &config.password) {
+            builder = builder.credentials(Credentials::new(username.clone(), password.clone()));
+        }
+
+        Self {
+            transport: builder.build(),
+            config,
+        }
+    }
+
+    fn mailbox(email: &Email, name: Option<String>) -> Result<Mailbox, InternalError> {
+        Ok(Mailbox::new(name, email.as_ref().parse().int_err()?))
+    }
+}
+
+#[async_trait::async_trait]
+impl EmailSender for SmtpEmailSender {
+    #[tracing::instrument(level = "debug", skip_all, fields(to = ?message.to, subject = %message.subject))]
+    async fn send_email(&self, message: EmailMessage) -> Result<(), InternalError> {
+        let mut builder = Message::builder()
+            .from(Self::mailbox(
+                &self.config.sender_address,
+                self.config.sender_name.clone(),
+            )?)
+            .subject(message.subject)
+            .header(ContentType::TEXT_PLAIN);
+
+        for recipient in &message.to {
+            builder = builder.to(Self::mailbox(recipient, None)?);
+        }
+
+        let email = builder.body(message.body).int_err()?;
+
+        self.transport.send(email).await.int_err()?;
+
+        Ok(())
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
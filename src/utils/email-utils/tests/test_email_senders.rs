@@ -0,0 +1,66 @@
+// Copyright Kamu Data, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::sync::Arc;
+
+use email_utils::*;
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+fn message() -> EmailMessage {
+    EmailMessage {
+        to: vec![
+            Email::parse("alice@example.com").unwrap(),
+            Email::parse("bob@example.com").unwrap(),
+        ],
+        subject: "Flow failed".to_string(),
+        body: "Ingest flow of dataset 'foo' has failed".to_string(),
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[tokio::test]
+async fn test_dummy_email_sender() {
+    let sender = DummyEmailSender::new();
+
+    sender.send_email(message()).await.unwrap();
+
+    assert_eq!(sender.sent_emails(), vec![message()]);
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[tokio::test]
+async fn test_file_email_sender() {
+    let tempdir = tempfile::tempdir().unwrap();
+    let output_dir = tempdir.path().join("emails");
+
+    let sender = FileEmailSender::new(Arc::new(FileEmailSenderConfig {
+        output_dir: output_dir.clone(),
+    }));
+
+    sender.send_email(message()).await.unwrap();
+    sender.send_email(message()).await.unwrap();
+
+    let files: Vec<_> = std::fs::read_dir(&output_dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .collect();
+    assert_eq!(files.len(), 2);
+
+    let content = std::fs::read_to_string(&files[0]).unwrap();
+    assert_eq!(
+        content,
+        "To: alice@example.com, bob@example.com\r\nSubject: Flow failed\r\nContent-Type: \
+         text/plain; charset=utf-8\r\n\r\nIngest flow of dataset 'foo' has failed"
+    );
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
@@ -11,7 +11,7 @@ use std::collections::HashSet;
 use std::sync::{Arc, Mutex};
 
 use database_common_macros::transactional_method;
-use dill::Catalog;
+use dill::{Catalog, CatalogBuilder};
 use internal_error::{InternalError, ResultIntoInternal};
 use tracing::Instrument;
 
@@ -23,6 +23,7 @@ use crate::{
     OutboxMessageConsumptionBoundary,
     OutboxMessageConsumptionRepository,
     OutboxMessageID,
+    OutboxPostCommitActions,
 };
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
@@ -195,8 +196,17 @@ impl ConsumeMessageTransaction {
             message_id = %self.message.message_id,
         );
 
-        match self.invoke_transactional().instrument(span).await {
-            Ok(()) => Ok(self),
+        let post_commit_actions = OutboxPostCommitActions::default();
+
+        match self
+            .invoke_transactional(post_commit_actions.clone())
+            .instrument(span)
+            .await
+        {
+            Ok(()) => {
+                post_commit_actions.run();
+                Ok(self)
+            }
             Err(err) => {
                 tracing::error!(
                     error = ?err,
@@ -216,7 +226,10 @@ impl ConsumeMessageTransaction {
     }
 
     #[transactional_method]
-    async fn invoke_transactional(&self) -> Result<(), InternalError> {
+    async fn invoke_transactional(
+        &self,
+        post_commit_actions: OutboxPostCommitActions,
+    ) -> Result<(), InternalError> {
         tracing::debug!(
             outbox_message = ?self.message,
             "Consuming message"
@@ -224,9 +237,13 @@ impl ConsumeMessageTransaction {
 
         let content_json = self.message.content_json.to_string();
 
+        let consumer_catalog = CatalogBuilder::new_chained(&transaction_catalog)
+            .add_value(post_commit_actions)
+            .build();
+
         self.dispatcher
             .dispatch_message(
-                &transaction_catalog,
+                &consumer_catalog,
                 ConsumerFilter::SelectedConsumer(&self.consumer_name),
                 &content_json,
                 self.message.version,
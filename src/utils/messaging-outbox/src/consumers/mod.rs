@@ -11,8 +11,10 @@ mod message_consumer;
 mod message_consumers_utils;
 mod message_dispatcher;
 mod message_subscription;
+mod outbox_post_commit_actions;
 
 pub use message_consumer::*;
 pub use message_consumers_utils::*;
 pub use message_dispatcher::*;
 pub(crate) use message_subscription::*;
+pub use outbox_post_commit_actions::*;
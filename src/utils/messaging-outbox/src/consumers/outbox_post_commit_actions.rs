@@ -0,0 +1,38 @@
+// Copyright Kamu Data, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+use std::sync::{Arc, Mutex};
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+type PostCommitAction = Box<dyn FnOnce() + Send>;
+
+/// Side effects that transactional consumers defer until the transaction, in
+/// which they consume a message, is committed. Deferred actions are dropped if
+/// the transaction rolls back.
+///
+/// Available in the catalog passed to consumers by the outbox agent.
+#[derive(Clone, Default)]
+pub struct OutboxPostCommitActions {
+    actions: Arc<Mutex<Vec<PostCommitAction>>>,
+}
+
+impl OutboxPostCommitActions {
+    pub fn defer(&self, action: impl FnOnce() + Send + 'static) {
+        self.actions.lock().unwrap().push(Box::new(action));
+    }
+
+    pub(crate) fn run(self) {
+        let actions = std::mem::take(&mut *self.actions.lock().unwrap());
+        for action in actions {
+            action();
+        }
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
@@ -10,4 +10,5 @@
 mod test_dispatching_outbox_impl;
 mod test_immediate_outbox_impl;
 mod test_outbox_agent;
+mod test_outbox_post_commit_actions;
 mod test_transactional_outbox_impl;
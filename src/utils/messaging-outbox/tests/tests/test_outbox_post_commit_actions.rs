@@ -0,0 +1,149 @@
+// Copyright Kamu Data, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+use std::sync::{Arc, Mutex};
+
+use database_common::NoOpDatabasePlugin;
+use dill::*;
+use init_on_startup::InitOnStartup;
+use internal_error::InternalError;
+use kamu_messaging_outbox_inmem::{
+    InMemoryOutboxMessageConsumptionRepository,
+    InMemoryOutboxMessageRepository,
+};
+use messaging_outbox::*;
+use serde::{Deserialize, Serialize};
+use time_source::SystemTimeSourceDefault;
+
+use crate::test_message_type;
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+const TEST_PRODUCER_D: &str = "TEST-PRODUCER-D";
+
+test_message_type!(D);
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[test_log::test(tokio::test)]
+async fn test_post_commit_actions_run_after_successful_consumption_only() {
+    let harness = PostCommitActionsHarness::new();
+    harness.outbox_agent.run_initialization().await.unwrap();
+
+    for body in ["foo", "fail", "bar"] {
+        harness
+            .outbox
+            .post_message(
+                TEST_PRODUCER_D,
+                TestMessageD {
+                    body: body.to_string(),
+                },
+            )
+            .await
+            .unwrap();
+    }
+
+    harness
+        .outbox_agent
+        .run_single_iteration_only()
+        .await
+        .unwrap();
+
+    // Action of the failed message is dropped, and the consumer is paused
+    assert_eq!(harness.consumer.get_published(), vec!["foo".to_string()]);
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+/// Publishes message bodies via post-commit actions and fails on "fail"
+struct TestDeferringMessageConsumer {
+    published: Arc<Mutex<Vec<String>>>,
+}
+
+#[component(pub)]
+#[scope(Singleton)]
+#[interface(dyn MessageConsumer)]
+#[interface(dyn MessageConsumerT<TestMessageD>)]
+#[meta(MessageConsumerMeta {
+    consumer_name: "TestDeferringMessageConsumer",
+    feeding_producers: &[TEST_PRODUCER_D],
+    delivery: MessageDeliveryMechanism::Transactional,
+})]
+impl TestDeferringMessageConsumer {
+    fn new() -> Self {
+        Self {
+            published: Arc::new(Mutex::new(Vec::new())),
+        }
+    }
+
+    fn get_published(&self) -> Vec<String> {
+        self.published.lock().unwrap().clone()
+    }
+}
+
+impl MessageConsumer for TestDeferringMessageConsumer {}
+
+#[async_trait::async_trait]
+impl MessageConsumerT<TestMessageD> for TestDeferringMessageConsumer {
+    async fn consume_message(
+        &self,
+        target_catalog: &Catalog,
+        message: &TestMessageD,
+    ) -> Result<(), InternalError> {
+        let post_commit_actions = target_catalog.get_one::<OutboxPostCommitActions>().unwrap();
+
+        let published = self.published.clone();
+        let body = message.body.clone();
+        post_commit_actions.defer(move || published.lock().unwrap().push(body));
+
+        // Nothing is published while the transaction is still open
+        assert!(!self.get_published().contains(&message.body));
+
+        if message.body == "fail" {
+            return InternalError::bail("Consumer failure");
+        }
+        Ok(())
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+struct PostCommitActionsHarness {
+    outbox_agent: Arc<OutboxAgent>,
+    outbox: Arc<dyn Outbox>,
+    consumer: Arc<TestDeferringMessageConsumer>,
+}
+
+impl PostCommitActionsHarness {
+    fn new() -> Self {
+        let mut b = CatalogBuilder::new();
+        b.add::<OutboxAgent>();
+        b.add::<OutboxAgentMetrics>();
+        b.add_value(OutboxConfig::default());
+        b.add::<InMemoryOutboxMessageRepository>();
+        b.add::<InMemoryOutboxMessageConsumptionRepository>();
+        b.add::<OutboxTransactionalImpl>();
+        b.bind::<dyn Outbox, OutboxTransactionalImpl>();
+        b.add::<SystemTimeSourceDefault>();
+        b.add::<TestDeferringMessageConsumer>();
+
+        register_message_dispatcher::<TestMessageD>(&mut b, TEST_PRODUCER_D);
+
+        NoOpDatabasePlugin::init_database_components(&mut b);
+
+        let catalog = b.build();
+
+        Self {
+            outbox_agent: catalog.get_one().unwrap(),
+            outbox: catalog.get_one().unwrap(),
+            consumer: catalog.get_one().unwrap(),
+        }
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
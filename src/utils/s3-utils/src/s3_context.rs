@@ -14,12 +14,35 @@ use async_utils::AsyncReadObj;
 use aws_config::{BehaviorVersion, SdkConfig};
 use aws_sdk_s3::config::SharedCredentialsProvider;
 use aws_sdk_s3::error::SdkError;
+use aws_sdk_s3::operation::abort_multipart_upload::{
+    AbortMultipartUploadError,
+    AbortMultipartUploadOutput,
+};
+use aws_sdk_s3::operation::complete_multipart_upload::{
+    CompleteMultipartUploadError,
+    CompleteMultipartUploadOutput,
+};
+use aws_sdk_s3::operation::create_multipart_upload::{
+    CreateMultipartUploadError,
+    CreateMultipartUploadOutput,
+};
 use aws_sdk_s3::operation::delete_object::{DeleteObjectError, DeleteObjectOutput};
 use aws_sdk_s3::operation::get_object::{GetObjectError, GetObjectOutput};
 use aws_sdk_s3::operation::head_object::{HeadObjectError, HeadObjectOutput};
+use aws_sdk_s3::operation::list_parts::ListPartsError;
 use aws_sdk_s3::operation::put_object::{PutObjectError, PutObjectOutput};
+use aws_sdk_s3::operation::upload_part::UploadPartError;
 use aws_sdk_s3::presigning::{PresignedRequest, PresigningConfig};
-use aws_sdk_s3::types::{CommonPrefix, Delete, ObjectCannedAcl, ObjectIdentifier};
+use aws_sdk_s3::types::{
+    CommonPrefix,
+    CompletedMultipartUpload,
+    CompletedPart,
+    Delete,
+    MultipartUpload,
+    ObjectCannedAcl,
+    ObjectIdentifier,
+    Part,
+};
 use aws_sdk_s3::Client;
 use internal_error::{InternalError, ResultIntoInternal, *};
 use url::Url;
@@ -406,6 +429,156 @@ impl S3Context {
         .await
     }
 
+    pub async fn create_multipart_upload(
+        &self,
+        key: String,
+        acl: Option<ObjectCannedAcl>,
+    ) -> Result<CreateMultipartUploadOutput, SdkError<CreateMultipartUploadError>> {
+        self.api_call("create_multipart_upload", || async {
+            self.client
+                .create_multipart_upload()
+                .bucket(self.shared_state.bucket.clone())
+                .key(key)
+                .set_acl(acl)
+                .send()
+                .await
+        })
+        .await
+    }
+
+    pub async fn upload_part_presigned_request(
+        &self,
+        key: impl Into<String>,
+        upload_id: impl Into<String>,
+        part_number: i32,
+        presigned_config: PresigningConfig,
+    ) -> Result<PresignedRequest, SdkError<UploadPartError>> {
+        self.api_call("upload_part(presigned)", || async {
+            self.client
+                .upload_part()
+                .bucket(self.shared_state.bucket.clone())
+                .key(key)
+                .upload_id(upload_id)
+                .part_number(part_number)
+                .presigned(presigned_config)
+                .await
+        })
+        .await
+    }
+
+    /// Lists all parts uploaded so far within a multipart upload, following
+    /// the pagination of the underlying API
+    pub async fn list_all_parts(
+        &self,
+        key: &str,
+        upload_id: &str,
+    ) -> Result<Vec<Part>, SdkError<ListPartsError>> {
+        let mut parts = Vec::new();
+        let mut part_number_marker = None;
+
+        loop {
+            let current_part_number_marker = part_number_marker.take();
+            let list_parts_resp = self
+                .api_call("list_parts", || async {
+                    self.client
+                        .list_parts()
+                        .bucket(self.shared_state.bucket.clone())
+                        .key(key)
+                        .upload_id(upload_id)
+                        .set_part_number_marker(current_part_number_marker)
+                        .send()
+                        .await
+                })
+                .await?;
+
+            parts.extend(list_parts_resp.parts.unwrap_or_default());
+
+            if list_parts_resp.is_truncated.unwrap_or_default() {
+                part_number_marker = list_parts_resp.next_part_number_marker;
+            } else {
+                break;
+            }
+        }
+
+        Ok(parts)
+    }
+
+    pub async fn complete_multipart_upload(
+        &self,
+        key: String,
+        upload_id: String,
+        parts: Vec<CompletedPart>,
+    ) -> Result<CompleteMultipartUploadOutput, SdkError<CompleteMultipartUploadError>> {
+        self.api_call("complete_multipart_upload", || async {
+            self.client
+                .complete_multipart_upload()
+                .bucket(self.shared_state.bucket.clone())
+                .key(key)
+                .upload_id(upload_id)
+                .multipart_upload(
+                    CompletedMultipartUpload::builder()
+                        .set_parts(Some(parts))
+                        .build(),
+                )
+                .send()
+                .await
+        })
+        .await
+    }
+
+    pub async fn abort_multipart_upload(
+        &self,
+        key: String,
+        upload_id: String,
+    ) -> Result<AbortMultipartUploadOutput, SdkError<AbortMultipartUploadError>> {
+        self.api_call("abort_multipart_upload", || async {
+            self.client
+                .abort_multipart_upload()
+                .bucket(self.shared_state.bucket.clone())
+                .key(key)
+                .upload_id(upload_id)
+                .send()
+                .await
+        })
+        .await
+    }
+
+    /// Lists all multipart uploads in the bucket that were initiated but not
+    /// yet completed or aborted
+    pub async fn list_all_multipart_uploads(&self) -> Result<Vec<MultipartUpload>, InternalError> {
+        let mut uploads = Vec::new();
+        let mut key_marker = None;
+        let mut upload_id_marker = None;
+
+        loop {
+            let (current_key_marker, current_upload_id_marker) =
+                (key_marker.take(), upload_id_marker.take());
+            let list_uploads_resp = self
+                .api_call("list_multipart_uploads", || async {
+                    self.client
+                        .list_multipart_uploads()
+                        .bucket(self.shared_state.bucket.clone())
+                        .set_key_marker(current_key_marker)
+                        .set_upload_id_marker(current_upload_id_marker)
+                        .send()
+                        .await
+                })
+                .await
+                .int_err()?;
+
+            uploads.extend(list_uploads_resp.uploads.unwrap_or_default());
+
+            if list_uploads_resp.is_truncated.unwrap_or_default() {
+                key_marker = list_uploads_resp.next_key_marker;
+                upload_id_marker = list_uploads_resp.next_upload_id_marker;
+            } else {
+                break;
+            }
+        }
+
+        Ok(uploads)
+    }
+
     pub async fn bucket_path_exists(&self, key_prefix: &str) -> Result<bool, InternalError> {
         self.api_call("list_objects_v2(bucket_path_exists)", || async {
             let listing = self